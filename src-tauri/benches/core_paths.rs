@@ -0,0 +1,142 @@
+//! Benchmarks of the paths every screen waits on, run against generated
+//! fixtures: `cargo bench --features test-support`. Each benchmark has a
+//! budget in `BUDGETS`; a run that measures a mean over budget exits with an
+//! error, so a slowdown shows up before a release rather than in a user's
+//! repo. Budgets are for a developer laptop and leave room for noise; tighten
+//! one when a redesign makes its path faster.
+
+use app_lib::test_support::{self as ts, Codec, RepoBuilder, VaultBuilder};
+use app_lib::AuditEntry;
+use criterion::{criterion_group, BatchSize, Criterion};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+const CLAIMS: usize = 1_000;
+const NOTES: usize = 1_000;
+const AUDIT_ENTRIES: usize = 1_000;
+
+/// Mean time per iteration each benchmark may take, by "<group>/<name>"
+const BUDGETS: &[(&str, Duration)] = &[
+    ("decompress/zlib_claim", Duration::from_micros(100)),
+    ("decompress/zstd_claim", Duration::from_micros(100)),
+    ("claims/list_1000", Duration::from_millis(50)),
+    ("claims/get_claim", Duration::from_millis(1)),
+    ("vault/search_1000_notes", Duration::from_millis(250)),
+    ("audit/append_to_1000", Duration::from_millis(50)),
+];
+
+/// Inflating and parsing one claim object, as every listing does per object
+fn decompression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decompress");
+    for (name, codec) in [("zlib_claim", Codec::Zlib), ("zstd_claim", Codec::Zstd)] {
+        let repo = RepoBuilder::new().claims(100).codec(codec).build();
+        let paths = repo.claim_paths();
+        let mut next = paths.iter().cycle();
+        group.bench_function(name, |b| b.iter(|| ts::read_object(next.next().unwrap()).unwrap()));
+    }
+    group.finish();
+}
+
+/// Listing claims once the index is built, which is what the Truth panel
+/// does on every visit, and opening one
+fn claim_listing(c: &mut Criterion) {
+    let repo = RepoBuilder::new().claims(CLAIMS).verifications(CLAIMS).build();
+    let vault = VaultBuilder::new().build();
+    let _active = ts::activate(&repo.path(), vault.path());
+    assert_eq!(ts::list_claims(None).unwrap().claims.len(), CLAIMS);
+
+    let mut group = c.benchmark_group("claims");
+    group.bench_function("list_1000", |b| b.iter(|| ts::list_claims(None).unwrap()));
+    let hash = &repo.claims[CLAIMS / 2];
+    group.bench_function("get_claim", |b| b.iter(|| ts::get_claim(hash).unwrap()));
+    group.finish();
+}
+
+/// Searching a vault of notes in folders, as the Knowledge panel does per query
+fn vault_search(c: &mut Criterion) {
+    let repo = RepoBuilder::new().build();
+    let vault = VaultBuilder::new().notes(NOTES).folders(20).paragraphs(8).build();
+    let _active = ts::activate(&repo.path(), vault.path());
+    let query = ts::TOPICS[0];
+    assert!(!ts::search_notes(query).unwrap().is_empty());
+
+    let mut group = c.benchmark_group("vault");
+    group.bench_function("search_1000_notes", |b| b.iter(|| ts::search_notes(query).unwrap()));
+    group.finish();
+}
+
+/// Appending one entry to a long audit trail, which rewrites the whole file;
+/// the trail is reset before each append so it doesn't grow with the run
+fn audit_append(c: &mut Criterion) {
+    let repo = RepoBuilder::new().claims(10).audit_entries(AUDIT_ENTRIES).build();
+    let vault = VaultBuilder::new().build();
+    let _active = ts::activate(&repo.path(), vault.path());
+    let audit_file = repo.path().join("audit.json");
+    let trail = fs::read(&audit_file).unwrap();
+
+    let mut group = c.benchmark_group("audit");
+    group.bench_function("append_to_1000", |b| {
+        b.iter_batched(
+            || fs::write(&audit_file, &trail).unwrap(),
+            |_| {
+                ts::add_audit_entry(AuditEntry {
+                    action: "note".to_string(),
+                    claim: ts::claim_text(0),
+                    ..Default::default()
+                })
+                .unwrap()
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+/// Where criterion keeps its results when run through cargo
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"))
+        .join("criterion")
+}
+
+/// Exit with an error if a benchmark measured in this run is over budget.
+/// Benchmarks filtered out, or run once as tests, leave no fresh estimate.
+fn check_budgets(started: SystemTime) {
+    let dir = criterion_dir();
+    let mut over = Vec::new();
+    for (id, budget) in BUDGETS {
+        let file = dir.join(id).join("new").join("estimates.json");
+        let fresh = fs::metadata(&file)
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified >= started);
+        if !fresh {
+            continue;
+        }
+        let estimates: serde_json::Value = serde_json::from_slice(&fs::read(&file).unwrap()).unwrap();
+        let Some(nanos) = estimates["mean"]["point_estimate"].as_f64() else {
+            continue;
+        };
+        let mean = Duration::from_nanos(nanos as u64);
+        if mean > *budget {
+            over.push(format!("{}: mean {:?}, budget {:?}", id, mean, budget));
+        }
+    }
+    if !over.is_empty() {
+        eprintln!("Over budget:\n  {}", over.join("\n  "));
+        std::process::exit(1);
+    }
+}
+
+criterion_group!(benches, decompression, claim_listing, vault_search, audit_append);
+
+fn main() {
+    let started = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    check_budgets(started);
+}
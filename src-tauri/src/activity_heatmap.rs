@@ -0,0 +1,194 @@
+// ==================== ACTIVITY HEATMAP ====================
+//
+// Verifications and claim creations bucketed per day and per hour of day,
+// for the dashboard's heatmap. Verifications come from the audit trail plus
+// verification objects the app didn't record (CLI runs, synced objects);
+// claim creations from the claims' own timestamps. Days and hours are in
+// the local time zone. The result is a few columns of counts, so the
+// frontend never has to fetch and bucket the raw entries.
+
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, Offset, Timelike, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::index::created_at;
+use crate::process_metrics::{MetricsRange, VERIFY_ACTION};
+use crate::snapshots::{instant, parse_moment};
+use crate::verification::Verification;
+use crate::{mock, read_audit_trail, read_claims, read_verifications, run_blocking, AuditEntry};
+
+/// Range used when `from` is left out
+const DEFAULT_DAYS: i64 = 365;
+/// Ten years of days keeps the response small
+const MAX_DAYS: i64 = 3660;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivitySeries {
+    /// One count per entry of `ActivityHeatmap::days`
+    pub daily: Vec<u32>,
+    /// Counts per local hour of day, 0-23
+    pub hourly: [u32; 24],
+    pub total: u32,
+}
+
+impl ActivitySeries {
+    fn new(days: usize) -> Self {
+        Self {
+            daily: vec![0; days],
+            hourly: [0; 24],
+            total: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityHeatmap {
+    /// RFC 3339
+    pub from: String,
+    pub to: String,
+    /// Local dates, YYYY-MM-DD, oldest first
+    pub days: Vec<String>,
+    pub verifications: ActivitySeries,
+    pub claims: ActivitySeries,
+}
+
+struct Buckets {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    first_day: NaiveDate,
+    offset: FixedOffset,
+}
+
+impl Buckets {
+    fn add(&self, series: &mut ActivitySeries, at: &str) {
+        let Some(at) = instant(at).filter(|at| *at > self.from && *at <= self.to) else {
+            return;
+        };
+        let local = at.with_timezone(&self.offset);
+        let day = (local.date_naive() - self.first_day).num_days() as usize;
+        if let Some(count) = series.daily.get_mut(day) {
+            *count += 1;
+            series.hourly[local.hour() as usize] += 1;
+            series.total += 1;
+        }
+    }
+}
+
+fn compute(
+    claims: &[serde_json::Value],
+    verifications: &[Verification],
+    audit: &[AuditEntry],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    offset: FixedOffset,
+) -> ActivityHeatmap {
+    let first_day = from.with_timezone(&offset).date_naive();
+    let last_day = to.with_timezone(&offset).date_naive();
+    let days: Vec<String> = first_day
+        .iter_days()
+        .take_while(|day| *day <= last_day)
+        .map(|day| day.format("%Y-%m-%d").to_string())
+        .collect();
+    let buckets = Buckets {
+        from,
+        to,
+        first_day,
+        offset,
+    };
+
+    let mut verified = ActivitySeries::new(days.len());
+    let mut recorded = HashSet::new();
+    for entry in audit.iter().filter(|e| e.action == VERIFY_ACTION) {
+        buckets.add(&mut verified, &entry.timestamp);
+        recorded.extend(entry.verification_hash.as_deref());
+    }
+    for verification in verifications {
+        if verification.hash.is_empty() || !recorded.contains(verification.hash.as_str()) {
+            buckets.add(&mut verified, &verification.timestamp);
+        }
+    }
+
+    let mut created = ActivitySeries::new(days.len());
+    for claim in claims {
+        buckets.add(&mut created, created_at(claim));
+    }
+
+    ActivityHeatmap {
+        from: from.to_rfc3339(),
+        to: to.to_rfc3339(),
+        days,
+        verifications: verified,
+        claims: created,
+    }
+}
+
+/// Daily and hour-of-day counts of verifications and claim creations over
+/// `range`; the past year when `from` is left out
+#[tauri::command]
+pub async fn get_activity_heatmap(range: Option<MetricsRange>) -> Result<ActivityHeatmap, String> {
+    let range = range.unwrap_or_default();
+    let parse = |value: Option<String>| {
+        value
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| parse_moment(&v))
+            .transpose()
+    };
+    let to = parse(range.to)?.unwrap_or_else(Utc::now);
+    let from = parse(range.from)?.unwrap_or(to - Duration::days(DEFAULT_DAYS));
+    if from >= to {
+        return Err("The range must start before it ends".to_string());
+    }
+    if to - from > Duration::days(MAX_DAYS) {
+        return Err(format!("The range can span at most {} days", MAX_DAYS));
+    }
+    let offset = Local::now().offset().fix();
+    run_blocking(move || {
+        let claims = read_claims(None)?.claims;
+        let verifications = read_verifications()?.verifications;
+        let audit = if mock::is_active() {
+            Vec::new()
+        } else {
+            read_audit_trail()?
+        };
+        Ok(compute(&claims, &verifications, &audit, from, to, offset))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_activity_heatmap() {
+        let claims = vec![
+            json!({"metadata": {"created_at": "2026-03-01T09:30:00Z"}}),
+            json!({"metadata": {"created_at": "2026-03-03T23:30:00Z"}}),
+            json!({"metadata": {"created_at": "2026-02-01T00:00:00Z"}}),
+        ];
+        // vf1 was recorded in the audit trail, vf2 came from the CLI
+        let verifications = [
+            json!({"$hash": "vf1", "claim_hash": "aa", "timestamp": "2026-03-01T10:00:00Z"}),
+            json!({"$hash": "vf2", "claim_hash": "bb", "timestamp": "2026-03-02T10:15:00Z"}),
+        ]
+        .map(Verification::from);
+        let audit = vec![AuditEntry {
+            action: VERIFY_ACTION.to_string(),
+            verification_hash: Some("vf1".to_string()),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            ..Default::default()
+        }];
+        let from = parse_moment("2026-02-28").unwrap();
+        let to = parse_moment("2026-03-03").unwrap();
+        let offset = FixedOffset::east_opt(3600).unwrap();
+
+        let heatmap = compute(&claims, &verifications, &audit, from, to, offset);
+        // Local days run an hour ahead of UTC
+        assert_eq!(heatmap.days, ["2026-03-01", "2026-03-02", "2026-03-03", "2026-03-04"]);
+        assert_eq!(heatmap.verifications.daily, vec![1, 1, 0, 0]);
+        assert_eq!(heatmap.verifications.hourly[11], 2);
+        assert_eq!(heatmap.claims.daily, vec![1, 0, 0, 1]);
+        assert_eq!((heatmap.claims.hourly[0], heatmap.claims.total), (1, 2));
+    }
+}
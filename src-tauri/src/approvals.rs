@@ -0,0 +1,327 @@
+// ==================== TWO-PERSON APPROVAL ====================
+//
+// Claims in configured domains, or whose risk is at or above a threshold,
+// can't be approved by one person: a reviewer requests approval and a second
+// identity signs off. The risk of a claim is its own `risk_profile` (top level
+// or under `metadata`), else its registered domain's default. Requests live on
+// the claim's review in reviews.json and both steps go to the audit trail.
+// Both sides need an email and a signing key: the same email or key means the
+// same person, and so does an identity whose name or email changed after the
+// request, since settings are the user's own to edit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::identity::{self, Actor};
+use crate::reviews::{self, ClaimReview, ReviewState};
+use crate::settings_validation::RISK_PROFILES;
+use crate::{append_audit_entry, domains, index, mock, read_claim, repo_state, run_blocking, AuditEntry, SETTINGS};
+
+const MAX_NOTE_LEN: usize = 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalPolicy {
+    /// Domains whose claims always need a second reviewer
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// "low", "medium" or "high"; claims at or above it need a second
+    /// reviewer. None disables the risk rule.
+    #[serde(default)]
+    pub min_risk_profile: Option<String>,
+}
+
+impl ApprovalPolicy {
+    /// Whether a claim in `domain` at `risk` needs a second reviewer
+    fn applies(&self, domain: &str, risk: Option<&str>) -> bool {
+        let domain = domain.trim().to_lowercase();
+        if !domain.is_empty() && self.domains.iter().any(|d| d.trim().to_lowercase() == domain) {
+            return true;
+        }
+        let rank = |r: &str| RISK_PROFILES.iter().position(|p| *p == r);
+        match (self.min_risk_profile.as_deref().and_then(rank), risk.and_then(rank)) {
+            (Some(threshold), Some(risk)) => risk >= threshold,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub requested_by: Actor,
+    pub requested_at: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingApproval {
+    pub claim: serde_json::Value,
+    pub review: ClaimReview,
+    /// False when the current identity made the request or can't approve it
+    pub can_approve: bool,
+}
+
+/// An actor that can take part in an approval: one with an email and a
+/// signing key to tell it apart from others
+fn check_credentials(actor: &Actor) -> Result<(), String> {
+    if actor.email.is_none() {
+        return Err("Set your email in Settings → Identity to take part in approvals".to_string());
+    }
+    if actor.key_fingerprint.is_none() {
+        return Err("Your signing key couldn't be read; approvals need it to identify you".to_string());
+    }
+    Ok(())
+}
+
+/// Why `approver`, whose identity last changed at `changed_at`, can't sign
+/// off `request`, if they can't
+fn refusal(request: &ApprovalRequest, approver: &Actor, changed_at: Option<&str>) -> Option<String> {
+    if let Err(e) = check_credentials(approver) {
+        return Some(e);
+    }
+    let requester = &request.requested_by;
+    if requester.email.is_none() || requester.key_fingerprint.is_none() {
+        return Some("The request doesn't say who made it: it has to be requested again".to_string());
+    }
+    let same_email = matches!((&requester.email, &approver.email), (Some(a), Some(b)) if a.eq_ignore_ascii_case(b));
+    if same_email || requester.key_fingerprint == approver.key_fingerprint {
+        return Some("You requested this approval; a different reviewer has to approve it".to_string());
+    }
+    let parse = |t: &str| chrono::DateTime::parse_from_rfc3339(t).ok();
+    let changed_since = match (changed_at, parse(&request.requested_at)) {
+        (None, _) => false,
+        (Some(changed), Some(requested)) => parse(changed).is_none_or(|changed| changed >= requested),
+        (Some(_), None) => true,
+    };
+    if changed_since {
+        return Some(
+            "Your name or email changed after approval was requested; someone else has to approve it".to_string(),
+        );
+    }
+    None
+}
+
+fn claim_field<'a>(claim: &'a serde_json::Value, name: &str) -> Option<&'a str> {
+    claim
+        .get(name)
+        .or_else(|| claim.get("metadata").and_then(|m| m.get(name)))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Whether approving `claim` needs sign-off from a second identity
+pub fn requires_second_reviewer(claim: &serde_json::Value) -> Result<bool, String> {
+    let policy = SETTINGS
+        .read()
+        .map_err(|e| format!("Settings lock error: {}", e))?
+        .approval_policy
+        .clone();
+    if policy == ApprovalPolicy::default() {
+        return Ok(false);
+    }
+    let domain = claim_field(claim, "domain").unwrap_or("");
+    let risk = match claim_field(claim, "risk_profile") {
+        Some(risk) => Some(risk.to_lowercase()),
+        // Unknown domains are the domain registry's concern, not ours
+        None => domains::resolve_domain(domain)
+            .ok()
+            .flatten()
+            .and_then(|d| d.default_risk_profile),
+    };
+    Ok(policy.applies(domain, risk.as_deref()))
+}
+
+fn check_note(note: Option<String>) -> Result<Option<String>, String> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note.as_ref().is_some_and(|n| n.chars().count() > MAX_NOTE_LEN) {
+        return Err(format!("Note too long (max {} characters)", MAX_NOTE_LEN));
+    }
+    Ok(note)
+}
+
+/// Approve `review` as `approver`, enforcing the second-reviewer rule
+fn sign_off(
+    review: &mut ClaimReview,
+    approver: &Actor,
+    changed_at: Option<&str>,
+    required: bool,
+    note: Option<String>,
+) -> Result<(), String> {
+    let request = review.pending_approval.take();
+    if required {
+        let Some(request) = request else {
+            return Err("This claim needs a second reviewer: request approval first".to_string());
+        };
+        if let Some(reason) = refusal(&request, approver, changed_at) {
+            review.pending_approval = Some(request);
+            return Err(reason);
+        }
+        reviews::transition(review, ReviewState::Approved, approver, note)?;
+        if let Some(step) = review.history.last_mut() {
+            step.requested_by = Some(request.requested_by);
+        }
+        return Ok(());
+    }
+    reviews::transition(review, ReviewState::Approved, approver, note)
+}
+
+/// Ask a second reviewer to approve a claim that is in review
+#[tauri::command]
+pub async fn request_approval(hash: String, note: Option<String>) -> Result<ClaimReview, String> {
+    if mock::is_active() {
+        return Err("Claim reviews are disabled in mock mode".to_string());
+    }
+    reviews::validate_hash(&hash)?;
+    let note = check_note(note)?;
+
+    run_blocking(move || {
+        let claim = read_claim(hash.clone())?;
+        let requester = identity::require()?;
+        check_credentials(&requester)?;
+        let request = ApprovalRequest {
+            requested_by: requester.clone(),
+            requested_at: chrono::Utc::now().to_rfc3339(),
+            note,
+        };
+        let review = reviews::update_review(&hash, |review| {
+            if review.state != ReviewState::InReview {
+                return Err("Only claims in review can be sent for approval".to_string());
+            }
+            if let Some(pending) = &review.pending_approval {
+                return Err(format!("Approval already requested by {}", pending.requested_by.name));
+            }
+            review.pending_approval = Some(request.clone());
+            Ok(())
+        })?;
+        append_audit_entry(AuditEntry {
+            action: "approval_request".to_string(),
+            result_status: ReviewState::InReview.as_str().to_string(),
+            result_action: format!("approval requested by {}", requester.name),
+            ..reviews::claim_audit_entry(&claim, &hash, &requester, &request.requested_at)
+        })
+        .map_err(|e| format!("Request saved, but writing the audit entry failed: {}", e))?;
+        log::info!("Approval of claim {} requested by {}", hash, requester.name);
+        Ok(review)
+    })
+    .await
+}
+
+/// Approve a claim in review. Claims covered by the approval policy need a
+/// pending request from a different identity.
+#[tauri::command]
+pub async fn approve(hash: String, note: Option<String>) -> Result<ClaimReview, String> {
+    if mock::is_active() {
+        return Err("Claim reviews are disabled in mock mode".to_string());
+    }
+    reviews::validate_hash(&hash)?;
+    let note = check_note(note)?;
+
+    run_blocking(move || {
+        let claim = read_claim(hash.clone())?;
+        let approver = identity::require()?;
+        let required = requires_second_reviewer(&claim)?;
+        let changed_at = identity::changed_at();
+        let review =
+            reviews::update_review(&hash, |review| sign_off(review, &approver, changed_at.as_deref(), required, note))?;
+        if let Some(step) = review.history.last() {
+            append_audit_entry(reviews::audit_entry(&claim, &hash, step))
+                .map_err(|e| format!("Approval saved, but writing the audit entry failed: {}", e))?;
+        }
+        log::info!("Claim {} approved by {}", hash, approver.name);
+        Ok(review)
+    })
+    .await
+}
+
+/// Claims waiting for a second reviewer, oldest request first
+#[tauri::command]
+pub async fn list_pending_approvals() -> Result<Vec<PendingApproval>, String> {
+    run_blocking(|| {
+        let claims = if mock::is_active() {
+            mock::claims()
+        } else {
+            let truth_path = repo_state::ready_path()?;
+            index::list_claims(&truth_path, None).claims
+        };
+        let current = identity::current();
+        let changed_at = identity::changed_at();
+        let mut reviews = reviews::load_reviews()?;
+        let mut pending: Vec<PendingApproval> = claims
+            .into_iter()
+            .filter_map(|claim| {
+                let hash = claim.get("$hash")?.as_str()?.to_string();
+                let review = reviews.remove(&hash)?;
+                let request = review.pending_approval.as_ref()?;
+                let can_approve = current
+                    .as_ref()
+                    .is_some_and(|c| refusal(request, c, changed_at.as_deref()).is_none());
+                Some(PendingApproval {
+                    claim,
+                    review,
+                    can_approve,
+                })
+            })
+            .collect();
+        pending.sort_by_key(|p| p.review.pending_approval.as_ref().map(|r| r.requested_at.clone()));
+        Ok(pending)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(name: &str, email: Option<&str>, key: &str) -> Actor {
+        Actor {
+            name: name.to_string(),
+            email: email.map(str::to_string),
+            key_fingerprint: Some(format!("SHA256:{}", key)),
+        }
+    }
+
+    #[test]
+    fn test_policy_applies() {
+        let policy = ApprovalPolicy {
+            domains: vec!["Healthcare".to_string()],
+            min_risk_profile: Some("high".to_string()),
+        };
+        assert!(policy.applies(" healthcare", None));
+        assert!(policy.applies("finance", Some("high")));
+        assert!(!policy.applies("finance", Some("medium")));
+        assert!(!policy.applies("finance", None));
+        assert!(!ApprovalPolicy::default().applies("healthcare", Some("high")));
+    }
+
+    #[test]
+    fn test_sign_off_needs_second_person() {
+        let ana = actor("Ana", Some("ana@example.org"), "aa");
+        let ben = actor("Ben", Some("ben@example.org"), "bb");
+        let mut review = ClaimReview::default();
+        reviews::transition(&mut review, ReviewState::InReview, &ana, None).unwrap();
+        assert!(sign_off(&mut review, &ana, None, true, None).is_err());
+
+        review.pending_approval = Some(ApprovalRequest {
+            requested_by: ana.clone(),
+            requested_at: "2026-01-01T00:00:00Z".to_string(),
+            note: None,
+        });
+        // Same email, or same key under another name and email
+        let also_ana = actor("ana r.", Some("ANA@example.org"), "cc");
+        assert!(sign_off(&mut review, &also_ana, None, true, None).is_err());
+        let renamed_ana = actor("Ben", Some("ben@example.org"), "aa");
+        assert!(sign_off(&mut review, &renamed_ana, None, true, None).is_err());
+        // No email to tell them apart
+        assert!(sign_off(&mut review, &actor("Ben", None, "bb"), None, true, None).is_err());
+        // An identity changed after the request
+        assert!(sign_off(&mut review, &ben, Some("2026-01-02T00:00:00Z"), true, None).is_err());
+        assert!(review.pending_approval.is_some());
+
+        sign_off(&mut review, &ben, Some("2025-12-01T00:00:00Z"), true, None).unwrap();
+        assert_eq!(review.state, ReviewState::Approved);
+        assert_eq!(review.pending_approval, None);
+        let step = review.history.last().unwrap();
+        assert_eq!(step.reviewer, ben);
+        assert_eq!(step.requested_by.as_ref(), Some(&ana));
+    }
+}
@@ -0,0 +1,237 @@
+// ==================== IN-TOTO ATTESTATIONS ====================
+//
+// Exports a claim's latest verification as an in-toto Statement (v1) whose
+// subject is the claim hash and whose predicate carries the verdict,
+// confidence, validators and the versions of the tooling that produced it.
+// By default the statement is wrapped in a DSSE envelope signed with the
+// receipt key (see receipts.rs), which is what policy engines and
+// supply-chain tooling expect to consume.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::Signer;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::identity::{self, Actor};
+use crate::verification::Verification;
+use crate::{engine, read_verifications, receipts, sanitize_error, SETTINGS};
+
+const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+const PREDICATE_TYPE: &str = "https://lumensyntax.org/truthgit/verification/v1";
+const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subject {
+    pub name: String,
+    pub digest: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tooling {
+    pub app: String,
+    pub app_version: String,
+    pub backend_mode: String,
+    /// `truthgit --version`, when the engine reports one
+    pub engine_version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationPredicate {
+    /// Hash of the verification object
+    pub verification: String,
+    pub claim: String,
+    pub domain: String,
+    pub verdict: String,
+    pub confidence: Option<f64>,
+    pub validators: Vec<String>,
+    pub verified_at: String,
+    pub tooling: Tooling,
+    pub exported_by: Option<Actor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statement {
+    #[serde(rename = "_type")]
+    pub statement_type: String,
+    pub subject: Vec<Subject>,
+    #[serde(rename = "predicateType")]
+    pub predicate_type: String,
+    pub predicate: VerificationPredicate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeSignature {
+    pub keyid: String,
+    pub sig: String,
+}
+
+/// DSSE envelope around the JSON of a statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Envelope {
+    pub payload_type: String,
+    pub payload: String,
+    pub signatures: Vec<EnvelopeSignature>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttestationExport {
+    pub path: String,
+    pub verification: String,
+    /// Fingerprint of the signing key; None for unsigned statements
+    pub key_id: Option<String>,
+}
+
+/// DSSE pre-authentication encoding of a payload
+fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut out = format!("DSSEv1 {} {} {} ", payload_type.len(), payload_type, payload.len()).into_bytes();
+    out.extend_from_slice(payload);
+    out
+}
+
+fn statement(verification: &Verification, claim_hash: &str, tooling: Tooling, actor: Option<Actor>) -> Statement {
+    // Claim hashes are SHA-256; anything else is carried under a private algorithm name
+    let algorithm = if claim_hash.len() == 64 { "sha256" } else { "truthgit" };
+    Statement {
+        statement_type: STATEMENT_TYPE.to_string(),
+        subject: vec![Subject {
+            name: format!("claim:{}", claim_hash),
+            digest: BTreeMap::from([(algorithm.to_string(), claim_hash.to_string())]),
+        }],
+        predicate_type: PREDICATE_TYPE.to_string(),
+        predicate: VerificationPredicate {
+            verification: verification.hash.clone(),
+            claim: verification.claim.clone(),
+            domain: verification.domain.clone(),
+            verdict: verification.verdict.clone(),
+            confidence: verification.confidence,
+            validators: verification.validators.clone(),
+            verified_at: verification.timestamp.clone(),
+            tooling,
+            exported_by: actor,
+        },
+    }
+}
+
+fn envelope(statement: &Statement, key: &ed25519_dalek::SigningKey) -> Result<Envelope, String> {
+    let payload = serde_json::to_vec(statement).map_err(|e| format!("Failed to encode statement: {}", e))?;
+    let signature = key.sign(&pae(PAYLOAD_TYPE, &payload));
+    Ok(Envelope {
+        payload_type: PAYLOAD_TYPE.to_string(),
+        payload: STANDARD.encode(&payload),
+        signatures: vec![EnvelopeSignature {
+            keyid: identity::fingerprint(key.verifying_key().as_bytes()),
+            sig: STANDARD.encode(signature.to_bytes()),
+        }],
+    })
+}
+
+async fn tooling() -> Result<Tooling, String> {
+    let backend_mode = SETTINGS
+        .read()
+        .map_err(|e| format!("Settings lock error: {}", e))?
+        .backend_mode
+        .clone();
+    let engine_version = match engine::run_truthgit(&["--version".to_string()]).await {
+        Ok(output) if output.success() => Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        _ => None,
+    }
+    .filter(|v| !v.is_empty());
+    Ok(Tooling {
+        app: "truthgit-desktop".to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        backend_mode,
+        engine_version,
+    })
+}
+
+/// Write the latest verification of claim `hash` to `path` as an in-toto
+/// statement, DSSE-signed unless `sign` is false
+#[tauri::command]
+pub async fn export_attestation(hash: String, path: String, sign: Option<bool>) -> Result<AttestationExport, String> {
+    let hash = hash.trim().to_lowercase();
+    if hash.len() < 3 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Invalid hash".to_string());
+    }
+    let tooling = tooling().await?;
+
+    crate::run_blocking(move || {
+        // Listings are newest first, so the first match is the latest verification
+        let verification = read_verifications()?
+            .verifications
+            .into_iter()
+            .find(|v| v.claim_hash == hash)
+            .ok_or_else(|| format!("No verification found for claim {}", hash))?;
+        let statement = statement(&verification, &hash, tooling, identity::current());
+        let verification = statement.predicate.verification.clone();
+
+        let (content, key_id) = if sign.unwrap_or(true) {
+            let envelope = envelope(&statement, &receipts::load_or_create_key()?)?;
+            let key_id = envelope.signatures.first().map(|s| s.keyid.clone());
+            (serde_json::to_string_pretty(&envelope), key_id)
+        } else {
+            (serde_json::to_string_pretty(&statement), None)
+        };
+        let content = content.map_err(|e| format!("Failed to encode attestation: {}", e))?;
+        std::fs::write(&path, content).map_err(|e| sanitize_error(&format!("Failed to write attestation: {}", e)))?;
+        log::info!("Exported attestation for claim {}", hash);
+        Ok(AttestationExport {
+            path,
+            verification,
+            key_id,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    fn tooling() -> Tooling {
+        Tooling {
+            app: "truthgit-desktop".to_string(),
+            app_version: "0.0.0".to_string(),
+            backend_mode: "mock".to_string(),
+            engine_version: None,
+        }
+    }
+
+    #[test]
+    fn test_pae() {
+        assert_eq!(
+            pae("http://example.com/HelloWorld", b"hello world"),
+            b"DSSEv1 29 http://example.com/HelloWorld 11 hello world"
+        );
+    }
+
+    #[test]
+    fn test_signed_statement() {
+        let hash = "ffcf02b72b11aa0a75cfa037e7f7f47ea05d1a6f0fa377441b7e9d408a3c6177";
+        let verification = Verification::from_value(serde_json::json!({
+            "$hash": "1f56cb", "claim_hash": hash, "claim": "Water boils at 100°C",
+            "domain": "science", "verdict": "PASSED", "confidence": 0.97, "validators": ["a", "b"],
+        }));
+        let statement = statement(&verification, hash, tooling(), None);
+        assert_eq!(statement.subject[0].digest["sha256"], hash);
+        assert_eq!(statement.predicate.verdict, "PASSED");
+        assert_eq!(statement.predicate.validators, vec!["a", "b"]);
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[3; 32]);
+        let envelope = envelope(&statement, &key).unwrap();
+        let payload = STANDARD.decode(&envelope.payload).unwrap();
+        let decoded: Statement = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(decoded, statement);
+        let sig: [u8; 64] = STANDARD
+            .decode(&envelope.signatures[0].sig)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        key.verifying_key()
+            .verify(&pae(&envelope.payload_type, &payload), &Signature::from_bytes(&sig))
+            .unwrap();
+    }
+}
@@ -0,0 +1,437 @@
+// ==================== AUTHENTICATION ====================
+//
+// OAuth 2.0 device authorization grant (RFC 8628) against the TruthGit API.
+// The user approves the login in their browser while the backend polls for
+// the token. Tokens live in the OS keychain, one entry per remote URL, never
+// in settings.json, and are refreshed shortly before they expire.
+//
+// Remote endpoints:
+//   POST {remote}/api/auth/device/code  -> { device_code, user_code, verification_uri, expires_in, interval }
+//   POST {remote}/api/auth/token        -> { access_token, refresh_token, expires_in } or { error }
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::http_client::{build_client, client_builder};
+use crate::{consent, mock};
+use crate::remotes::resolve_remote_url;
+
+pub const AUTH_LOGIN_EVENT: &str = "auth://login";
+
+const CLIENT_ID: &str = "truthgit-desktop";
+const KEYCHAIN_SERVICE: &str = "truthgit-desktop";
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Refresh this long before the access token actually expires
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// RFC 8628 default when the server doesn't send an interval
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Pending device logins, by remote; a new login replaces the old one
+static LOGINS: std::sync::LazyLock<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Serializes refreshes so concurrent requests don't burn the refresh token twice
+static REFRESH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    verification_uri_complete: Option<String>,
+    expires_in: u64,
+    interval: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenError {
+    error: String,
+    error_description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct StoredToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// RFC 3339; None if the server didn't say
+    expires_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeviceLogin {
+    pub remote: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthStatus {
+    pub remote: String,
+    pub logged_in: bool,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginEvent {
+    pub remote: String,
+    /// "success", "denied", "expired" or "error"
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug)]
+enum PollOutcome {
+    Token(TokenResponse),
+    Pending,
+    SlowDown,
+    Failed { status: &'static str, error: String },
+}
+
+fn classify_token_error(error: TokenError) -> PollOutcome {
+    match error.error.as_str() {
+        "authorization_pending" => PollOutcome::Pending,
+        "slow_down" => PollOutcome::SlowDown,
+        "access_denied" => PollOutcome::Failed {
+            status: "denied",
+            error: "Login was denied".to_string(),
+        },
+        "expired_token" => PollOutcome::Failed {
+            status: "expired",
+            error: "Login code expired; start again".to_string(),
+        },
+        other => PollOutcome::Failed {
+            status: "error",
+            error: error.error_description.unwrap_or_else(|| format!("Login failed: {}", other)),
+        },
+    }
+}
+
+fn stored_token(response: TokenResponse, previous_refresh: Option<String>, now: chrono::DateTime<chrono::Utc>) -> StoredToken {
+    StoredToken {
+        access_token: response.access_token,
+        // Servers that don't rotate refresh tokens omit them on refresh
+        refresh_token: response.refresh_token.or(previous_refresh),
+        expires_at: response
+            .expires_in
+            .map(|secs| (now + chrono::Duration::seconds(secs)).to_rfc3339()),
+    }
+}
+
+fn needs_refresh(token: &StoredToken, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let Some(expires_at) = &token.expires_at else {
+        return false;
+    };
+    match chrono::DateTime::parse_from_rfc3339(expires_at) {
+        Ok(expires_at) => expires_at.with_timezone(&chrono::Utc) - chrono::Duration::seconds(REFRESH_MARGIN_SECS) <= now,
+        Err(_) => true,
+    }
+}
+
+// ---------- keychain ----------
+
+fn keychain_entry(remote: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, remote).map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+fn load_token(remote: &str) -> Result<Option<StoredToken>, String> {
+    match keychain_entry(remote)?.get_password() {
+        Ok(secret) => serde_json::from_str(&secret)
+            .map(Some)
+            .map_err(|e| format!("Stored credential is corrupt: {}", e)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read keychain: {}", e)),
+    }
+}
+
+fn save_token(remote: &str, token: &StoredToken) -> Result<(), String> {
+    let secret = serde_json::to_string(token).map_err(|e| format!("Failed to serialize token: {}", e))?;
+    keychain_entry(remote)?
+        .set_password(&secret)
+        .map_err(|e| format!("Failed to write keychain: {}", e))
+}
+
+fn delete_token(remote: &str) -> Result<(), String> {
+    match keychain_entry(remote)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove credential: {}", e)),
+    }
+}
+
+// ---------- token endpoint ----------
+
+async fn request_token(client: &reqwest::Client, remote: &str, form: &[(&str, &str)]) -> Result<PollOutcome, String> {
+    let response = client
+        .post(format!("{}/api/auth/token", remote))
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to remote: {}", e))?;
+
+    if response.status().is_success() {
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse token response: {}", e))?;
+        return Ok(PollOutcome::Token(token));
+    }
+    let status = response.status();
+    match response.json::<TokenError>().await {
+        Ok(error) => Ok(classify_token_error(error)),
+        Err(_) => Err(format!("Remote returned {} for token request", status)),
+    }
+}
+
+async fn refresh(remote: &str, refresh_token: &str) -> Result<StoredToken, String> {
+    let form = [
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+        ("client_id", CLIENT_ID),
+    ];
+    match request_token(&build_client()?, remote, &form).await? {
+        PollOutcome::Token(response) => {
+            let token = stored_token(response, Some(refresh_token.to_string()), chrono::Utc::now());
+            save_token(remote, &token)?;
+            Ok(token)
+        }
+        PollOutcome::Failed { error, .. } => {
+            // A rejected refresh token won't start working again
+            delete_token(remote)?;
+            Err(format!("Session expired, sign in again ({})", error))
+        }
+        PollOutcome::Pending | PollOutcome::SlowDown => Err("Unexpected response to token refresh".to_string()),
+    }
+}
+
+/// Current access token for `remote`, refreshed if it is about to expire.
+/// None when the user never signed in to this remote.
+pub async fn access_token(remote: &str) -> Result<Option<String>, String> {
+    let Some(token) = load_token(remote)? else {
+        return Ok(None);
+    };
+    if !needs_refresh(&token, chrono::Utc::now()) {
+        return Ok(Some(token.access_token));
+    }
+
+    let _guard = REFRESH_LOCK.lock().await;
+    // Another request may have refreshed while we waited
+    let Some(token) = load_token(remote)? else {
+        return Ok(None);
+    };
+    if !needs_refresh(&token, chrono::Utc::now()) {
+        return Ok(Some(token.access_token));
+    }
+    match token.refresh_token {
+        Some(refresh_token) => Ok(Some(refresh(remote, &refresh_token).await?.access_token)),
+        None => {
+            delete_token(remote)?;
+            Err("Session expired, sign in again".to_string())
+        }
+    }
+}
+
+/// HTTP client (proxy and CA settings applied) that sends the stored bearer
+/// token for `remote`, if any
+pub async fn authorized_client(remote: &str) -> Result<reqwest::Client, String> {
+    consent::require_host(remote).await?;
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(token) = access_token(remote).await? {
+        let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| "Stored access token is not a valid header value".to_string())?;
+        value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+    client_builder()?
+        .default_headers(headers)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+// ---------- device login ----------
+
+fn emit_login(app: &AppHandle, remote: &str, status: &str, error: Option<String>) {
+    let event = LoginEvent {
+        remote: remote.to_string(),
+        status: status.to_string(),
+        error,
+    };
+    if let Err(e) = app.emit(AUTH_LOGIN_EVENT, event) {
+        log::warn!("Failed to emit {}: {}", AUTH_LOGIN_EVENT, e);
+    }
+}
+
+async fn poll_for_token(app: AppHandle, remote: String, device: DeviceCodeResponse) {
+    let client = match build_client() {
+        Ok(client) => client,
+        Err(e) => {
+            emit_login(&app, &remote, "error", Some(e));
+            return;
+        }
+    };
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(device.expires_in);
+    let mut interval = Duration::from_secs(device.interval.unwrap_or(DEFAULT_POLL_INTERVAL_SECS).max(1));
+    let form = [
+        ("grant_type", DEVICE_CODE_GRANT),
+        ("device_code", device.device_code.as_str()),
+        ("client_id", CLIENT_ID),
+    ];
+
+    loop {
+        tokio::time::sleep(interval).await;
+        if tokio::time::Instant::now() >= deadline {
+            emit_login(&app, &remote, "expired", Some("Login code expired; start again".to_string()));
+            return;
+        }
+        match request_token(&client, &remote, &form).await {
+            Ok(PollOutcome::Token(response)) => {
+                let token = stored_token(response, None, chrono::Utc::now());
+                match save_token(&remote, &token) {
+                    Ok(()) => emit_login(&app, &remote, "success", None),
+                    Err(e) => emit_login(&app, &remote, "error", Some(e)),
+                }
+                return;
+            }
+            Ok(PollOutcome::Pending) => {}
+            // RFC 8628 §3.5: back off by 5 seconds
+            Ok(PollOutcome::SlowDown) => interval += Duration::from_secs(5),
+            Ok(PollOutcome::Failed { status, error }) => {
+                emit_login(&app, &remote, status, Some(error));
+                return;
+            }
+            // Transient network errors: keep polling until the code expires
+            Err(e) => log::warn!("Device login poll failed: {}", e),
+        }
+    }
+}
+
+/// Begin a device login; the result is delivered as an `auth://login` event
+#[tauri::command]
+pub async fn start_device_login(app: AppHandle, remote: Option<String>) -> Result<DeviceLogin, String> {
+    if mock::is_active() {
+        return Err("Sign-in is not available with the mock backend".to_string());
+    }
+    let remote = resolve_remote_url(remote, "pull")?;
+    consent::require_host(&remote).await?;
+
+    let response = build_client()?
+        .post(format!("{}/api/auth/device/code", remote))
+        .form(&[("client_id", CLIENT_ID)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to remote: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {} for device login", response.status()));
+    }
+    let device: DeviceCodeResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse device login response: {}", e))?;
+
+    let login = DeviceLogin {
+        remote: remote.clone(),
+        user_code: device.user_code.clone(),
+        verification_uri: device.verification_uri.clone(),
+        verification_uri_complete: device.verification_uri_complete.clone(),
+        expires_in: device.expires_in,
+    };
+
+    let handle = tauri::async_runtime::spawn(poll_for_token(app, remote.clone(), device));
+    let mut logins = LOGINS.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if let Some(previous) = logins.insert(remote, handle) {
+        previous.abort();
+    }
+    Ok(login)
+}
+
+#[tauri::command]
+pub async fn get_auth_status(remote: Option<String>) -> Result<AuthStatus, String> {
+    let remote = resolve_remote_url(remote, "pull")?;
+    let token = load_token(&remote)?;
+    Ok(AuthStatus {
+        logged_in: token.is_some(),
+        expires_at: token.and_then(|t| t.expires_at),
+        remote,
+    })
+}
+
+#[tauri::command]
+pub async fn logout(remote: Option<String>) -> Result<(), String> {
+    let remote = resolve_remote_url(remote, "pull")?;
+    if let Ok(mut logins) = LOGINS.lock() {
+        if let Some(pending) = logins.remove(&remote) {
+            pending.abort();
+        }
+    }
+    delete_token(&remote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_error(error: &str) -> TokenError {
+        TokenError {
+            error: error.to_string(),
+            error_description: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_token_errors() {
+        assert!(matches!(classify_token_error(token_error("authorization_pending")), PollOutcome::Pending));
+        assert!(matches!(classify_token_error(token_error("slow_down")), PollOutcome::SlowDown));
+        assert!(matches!(
+            classify_token_error(token_error("access_denied")),
+            PollOutcome::Failed { status: "denied", .. }
+        ));
+        assert!(matches!(
+            classify_token_error(token_error("expired_token")),
+            PollOutcome::Failed { status: "expired", .. }
+        ));
+        assert!(matches!(
+            classify_token_error(token_error("invalid_grant")),
+            PollOutcome::Failed { status: "error", .. }
+        ));
+    }
+
+    #[test]
+    fn test_refresh_keeps_previous_refresh_token() {
+        let now = chrono::Utc::now();
+        let response = TokenResponse {
+            access_token: "new".to_string(),
+            refresh_token: None,
+            expires_in: Some(3600),
+        };
+        let token = stored_token(response, Some("r1".to_string()), now);
+        assert_eq!(token.refresh_token.as_deref(), Some("r1"));
+        assert!(!needs_refresh(&token, now));
+    }
+
+    #[test]
+    fn test_needs_refresh_near_expiry() {
+        let now = chrono::Utc::now();
+        let mut token = StoredToken {
+            access_token: "a".to_string(),
+            refresh_token: Some("r".to_string()),
+            expires_at: Some((now + chrono::Duration::seconds(REFRESH_MARGIN_SECS / 2)).to_rfc3339()),
+        };
+        assert!(needs_refresh(&token, now));
+        token.expires_at = None;
+        assert!(!needs_refresh(&token, now));
+        token.expires_at = Some("garbage".to_string());
+        assert!(needs_refresh(&token, now));
+    }
+}
@@ -0,0 +1,622 @@
+// ==================== BACKUP & RESTORE ====================
+//
+// Whole-repo backups as a single .tar.gz (or .tar.zst with
+// `compression.backups` set to zstd): objects, HEAD, refs, the audit trail
+// and, for an encrypted repo, encryption.json with its wrapped data key,
+// plus the signing keys only when asked for. The first entry is a
+// manifest with the SHA-256 of every file, which restore checks before
+// anything replaces the current repo. With a passphrase the archive is
+// wrapped in age encryption (.tar.gz.age, .tar.zst.age).
+//
+// Restoring into the active repo extracts to a staging directory first and
+// moves the existing repo aside to `<repo>.pre-restore-<time>` rather than
+// deleting it; restoring to a new location never touches the active repo.
+
+use age::secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::compression::{self, Codec, Encoder};
+use crate::setup::is_truth_repo;
+use crate::tasks::{self, TaskHandle, TASK_CANCELLED_ERROR};
+use crate::repo_crypto::CONFIG_FILE;
+use crate::truth_store::{HEAD_FILE, OBJECTS_DIR, REFS_DIR};
+use crate::{consent, get_truth_path, mock, sanitize_error};
+
+const MANIFEST_NAME: &str = "truthgit-backup.json";
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const AGE_MAGIC: &[u8] = b"age-encryption.org/";
+
+/// Repo entries that make up a backup, relative to the repo root
+const BACKUP_ENTRIES: &[&str] = &[OBJECTS_DIR, REFS_DIR, HEAD_FILE, "audit.json", CONFIG_FILE];
+const KEY_FILES: &[&str] = &["proof.key", "proof.pub"];
+
+const MAX_BACKUP_FILE_SIZE: u64 = 256 * 1024 * 1024;
+const MAX_BACKUP_FILES: usize = 1_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    pub app_version: String,
+    pub includes_keys: bool,
+    pub files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupReport {
+    pub path: String,
+    pub files: usize,
+    /// Size of the archive on disk
+    pub bytes: u64,
+    pub encrypted: bool,
+    pub includes_keys: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreReport {
+    pub path: String,
+    pub files: usize,
+    pub created_at: String,
+    pub includes_keys: bool,
+    /// Where the replaced repo was moved, when restoring over the active one
+    pub previous_repo: Option<String>,
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file =
+        File::open(path).map_err(|e| sanitize_error(&format!("Failed to read {}: {}", path.display(), e)))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).map_err(|e| format!("Failed to hash file: {}", e))?;
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Files to back up as (relative path with `/` separators, absolute path)
+fn collect_files(repo: &Path, include_keys: bool) -> Result<Vec<(String, PathBuf)>, String> {
+    let mut roots: Vec<&str> = BACKUP_ENTRIES.to_vec();
+    if include_keys {
+        roots.extend_from_slice(KEY_FILES);
+    }
+
+    let mut files = Vec::new();
+    for root in roots {
+        // Symlinks are not followed, so a backup never pulls in files from outside the repo
+        for entry in WalkDir::new(repo.join(root)).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(repo) else {
+                continue;
+            };
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((name, entry.path().to_path_buf()));
+            if files.len() > MAX_BACKUP_FILES {
+                return Err("Repository has too many files to back up".to_string());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn append_bytes<W: Write>(builder: &mut tar::Builder<W>, name: &str, bytes: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, name, bytes)
+}
+
+/// Write manifest and files as a compressed tar into `out`, returning `out`
+/// once the stream is finished. Progress continues from the hashing pass,
+/// which covered the first half.
+fn write_archive<W: Write>(
+    out: W,
+    codec: Codec,
+    manifest: &BackupManifest,
+    files: &[(String, PathBuf)],
+    task: &TaskHandle,
+) -> io::Result<W> {
+    let mut builder = tar::Builder::new(Encoder::archive(codec, out)?);
+    let manifest_json = serde_json::to_vec_pretty(manifest).map_err(io::Error::other)?;
+    append_bytes(&mut builder, MANIFEST_NAME, &manifest_json)?;
+    let total = files.len() as u64 * 2;
+    for (i, (name, path)) in files.iter().enumerate() {
+        if task.is_cancelled() {
+            return Err(io::Error::other(TASK_CANCELLED_ERROR));
+        }
+        builder.append_path_with_name(path, name)?;
+        task.progress(files.len() as u64 + i as u64 + 1, total, format!("Archived {}", name));
+    }
+    builder.into_inner()?.finish()
+}
+
+fn backup_file_name(codec: Codec, encrypted: bool) -> String {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let extension = match codec {
+        Codec::Zlib => "tar.gz",
+        Codec::Zstd => "tar.zst",
+    };
+    let age = if encrypted { ".age" } else { "" };
+    format!("truthgit-backup-{}.{}{}", stamp, extension, age)
+}
+
+fn create_backup(
+    repo: &Path,
+    dest: &Path,
+    include_keys: bool,
+    passphrase: Option<String>,
+    codec: Codec,
+    task: &TaskHandle,
+) -> Result<BackupReport, String> {
+    if !is_truth_repo(repo) {
+        return Err("No truth repository to back up".to_string());
+    }
+    let encrypted = passphrase.is_some();
+    let dest = if dest.is_dir() {
+        dest.join(backup_file_name(codec, encrypted))
+    } else {
+        dest.to_path_buf()
+    };
+    if dest.starts_with(repo) {
+        return Err("Backup destination must be outside the repository".to_string());
+    }
+
+    let files = collect_files(repo, include_keys)?;
+    let mut entries = Vec::with_capacity(files.len());
+    let total = files.len() as u64 * 2;
+    for (i, (name, path)) in files.iter().enumerate() {
+        task.check_cancelled()?;
+        let size = fs::metadata(path)
+            .map_err(|e| sanitize_error(&format!("Failed to read {}: {}", name, e)))?
+            .len();
+        if size > MAX_BACKUP_FILE_SIZE {
+            return Err(format!("{} is too large to back up", name));
+        }
+        entries.push(ManifestEntry {
+            path: name.clone(),
+            size,
+            sha256: sha256_file(path)?,
+        });
+        task.progress(i as u64 + 1, total, format!("Hashed {}", name));
+    }
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        includes_keys: include_keys,
+        files: entries,
+    };
+
+    // Written next to the destination and renamed, so a failed backup never
+    // leaves a truncated archive under the final name
+    let tmp = dest.with_extension("partial");
+    let result = (|| -> Result<(), String> {
+        let out =
+            BufWriter::new(File::create(&tmp).map_err(|e| sanitize_error(&format!("Failed to create backup: {}", e)))?);
+        let write_err = |e: io::Error| {
+            if task.is_cancelled() {
+                TASK_CANCELLED_ERROR.to_string()
+            } else {
+                sanitize_error(&format!("Failed to write backup: {}", e))
+            }
+        };
+        let out = match passphrase {
+            Some(passphrase) => {
+                let encryptor = age::Encryptor::with_user_passphrase(SecretString::from(passphrase));
+                let writer = encryptor.wrap_output(out).map_err(write_err)?;
+                write_archive(writer, codec, &manifest, &files, task)
+                    .map_err(write_err)?
+                    .finish()
+                    .map_err(write_err)?
+            }
+            None => write_archive(out, codec, &manifest, &files, task).map_err(write_err)?,
+        };
+        out.into_inner()
+            .map_err(|e| write_err(e.into_error()))?
+            .sync_all()
+            .map_err(write_err)?;
+        fs::rename(&tmp, &dest).map_err(write_err)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result?;
+
+    Ok(BackupReport {
+        path: dest.to_string_lossy().to_string(),
+        files: manifest.files.len(),
+        bytes: fs::metadata(&dest).map(|m| m.len()).unwrap_or(0),
+        encrypted,
+        includes_keys: include_keys,
+    })
+}
+
+/// Archive paths must be plain relative paths inside one of the backup entries
+fn safe_archive_path(name: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    let first = path.components().next()?.as_os_str().to_str()?;
+    if BACKUP_ENTRIES.contains(&first) || KEY_FILES.contains(&first) {
+        Some(path.to_path_buf())
+    } else {
+        None
+    }
+}
+
+fn open_archive(src: &Path, passphrase: Option<String>) -> Result<Box<dyn Read>, String> {
+    let mut file =
+        BufReader::new(File::open(src).map_err(|e| sanitize_error(&format!("Failed to open backup: {}", e)))?);
+    let mut magic = [0u8; AGE_MAGIC.len()];
+    let is_encrypted = file.read_exact(&mut magic).is_ok() && magic == AGE_MAGIC;
+    let file = BufReader::new(File::open(src).map_err(|e| sanitize_error(&format!("Failed to open backup: {}", e)))?);
+
+    let read_err = |e: io::Error| format!("Failed to read backup: {}", e);
+    if !is_encrypted {
+        return compression::archive_decoder(file).map_err(read_err);
+    }
+    let passphrase = passphrase.ok_or("This backup is encrypted; a passphrase is required")?;
+    let decryptor = age::Decryptor::new(file).map_err(|e| format!("Failed to read encrypted backup: {}", e))?;
+    let identity = age::scrypt::Identity::new(SecretString::from(passphrase));
+    let reader = decryptor
+        .decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(|_| "Wrong passphrase, or the backup is damaged".to_string())?;
+    compression::archive_decoder(BufReader::new(reader)).map_err(read_err)
+}
+
+/// Extract and verify an archive into `staging`, which must be empty
+fn extract_verified(
+    src: &Path,
+    passphrase: Option<String>,
+    staging: &Path,
+    task: &TaskHandle,
+) -> Result<BackupManifest, String> {
+    let mut archive = tar::Archive::new(open_archive(src, passphrase)?);
+    let mut entries = archive.entries().map_err(|e| format!("Failed to read backup: {}", e))?;
+    let read_err = |e: io::Error| format!("Failed to read backup: {}", e);
+
+    let mut first = entries.next().ok_or("Backup is empty")?.map_err(read_err)?;
+    if first.path().map_err(read_err)?.to_string_lossy() != MANIFEST_NAME {
+        return Err("Not a TruthGit backup (manifest missing)".to_string());
+    }
+    let mut manifest_json = Vec::new();
+    first
+        .by_ref()
+        .take(16 * 1024 * 1024)
+        .read_to_end(&mut manifest_json)
+        .map_err(read_err)?;
+    let manifest: BackupManifest =
+        serde_json::from_slice(&manifest_json).map_err(|e| format!("Backup manifest is invalid: {}", e))?;
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err("Backup was made by a newer version of TruthGit".to_string());
+    }
+
+    let mut expected: BTreeMap<&str, &ManifestEntry> = manifest.files.iter().map(|f| (f.path.as_str(), f)).collect();
+    let total = manifest.files.len() as u64;
+    for entry in entries {
+        task.check_cancelled()?;
+        let mut entry = entry.map_err(read_err)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path().map_err(read_err)?.to_string_lossy().to_string();
+        let listed = expected
+            .remove(name.as_str())
+            .ok_or_else(|| format!("Backup contains an unlisted file: {}", name))?;
+        let relative = safe_archive_path(&name).ok_or_else(|| format!("Backup contains an unsafe path: {}", name))?;
+
+        let target = staging.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| sanitize_error(&format!("Failed to restore: {}", e)))?;
+        }
+        let mut hasher = Sha256::new();
+        let mut out = File::create(&target).map_err(|e| sanitize_error(&format!("Failed to restore: {}", e)))?;
+        let mut limited = entry.by_ref().take(MAX_BACKUP_FILE_SIZE + 1);
+        let mut buffer = [0u8; 64 * 1024];
+        let mut size = 0u64;
+        loop {
+            let n = limited.read(&mut buffer).map_err(read_err)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            out.write_all(&buffer[..n])
+                .map_err(|e| sanitize_error(&format!("Failed to restore: {}", e)))?;
+            size += n as u64;
+        }
+        if size != listed.size || hex(&hasher.finalize()) != listed.sha256 {
+            return Err(format!("Integrity check failed for {}", name));
+        }
+        task.progress(total - expected.len() as u64, total, format!("Verified {}", name));
+    }
+    if let Some(missing) = expected.keys().next() {
+        return Err(format!("Backup is incomplete: {} is missing", missing));
+    }
+    if !is_truth_repo(staging) {
+        return Err("Backup does not contain a truth repository".to_string());
+    }
+    Ok(manifest)
+}
+
+fn restore_backup(
+    src: &Path,
+    passphrase: Option<String>,
+    repo: &Path,
+    target: Option<PathBuf>,
+    task: &TaskHandle,
+) -> Result<RestoreReport, String> {
+    let in_place = target.is_none();
+    let destination = target.unwrap_or_else(|| repo.to_path_buf());
+    if !in_place
+        && destination.exists()
+        && fs::read_dir(&destination)
+            .map(|mut d| d.next().is_some())
+            .unwrap_or(true)
+    {
+        return Err("Restore location must be a new or empty directory".to_string());
+    }
+    let parent = destination.parent().ok_or("Restore location has no parent directory")?;
+    fs::create_dir_all(parent).map_err(|e| sanitize_error(&format!("Failed to create directory: {}", e)))?;
+
+    let staging = parent.join(format!(".truthgit-restore-{}", uuid::Uuid::new_v4()));
+    fs::create_dir(&staging).map_err(|e| sanitize_error(&format!("Failed to create staging directory: {}", e)))?;
+    let manifest = match extract_verified(src, passphrase, &staging, task) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    // Keys excluded from the backup stay with the repo being replaced, and
+    // so does the encryption key of a backup made without it
+    if in_place {
+        let kept = KEY_FILES.iter().filter(|_| !manifest.includes_keys);
+        for file in kept.chain([&CONFIG_FILE]) {
+            if destination.join(file).is_file() && !staging.join(file).exists() {
+                let _ = fs::copy(destination.join(file), staging.join(file));
+            }
+        }
+    }
+
+    let previous_repo = if in_place && destination.exists() {
+        let name = destination
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let aside = parent.join(format!(
+            "{}.pre-restore-{}",
+            name,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        if let Err(e) = fs::rename(&destination, &aside) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(sanitize_error(&format!(
+                "Failed to move current repository aside: {}",
+                e
+            )));
+        }
+        Some(aside.to_string_lossy().to_string())
+    } else {
+        // Only an empty directory can be here
+        let _ = fs::remove_dir(&destination);
+        None
+    };
+    if let Err(e) = fs::rename(&staging, &destination) {
+        if let Some(previous) = &previous_repo {
+            let _ = fs::rename(previous, &destination);
+        }
+        let _ = fs::remove_dir_all(&staging);
+        return Err(sanitize_error(&format!(
+            "Failed to move restored repository into place: {}",
+            e
+        )));
+    }
+
+    Ok(RestoreReport {
+        path: destination.to_string_lossy().to_string(),
+        files: manifest.files.len(),
+        created_at: manifest.created_at,
+        includes_keys: manifest.includes_keys,
+        previous_repo,
+    })
+}
+
+#[tauri::command]
+pub async fn backup_truth_repo(
+    app: AppHandle,
+    dest: String,
+    include_keys: bool,
+    passphrase: Option<String>,
+    task_id: Option<String>,
+) -> Result<BackupReport, String> {
+    if mock::is_active() {
+        return Err("Backups are disabled in mock mode".to_string());
+    }
+    let repo = get_truth_path().ok_or("Could not find home directory")?;
+    let dest = PathBuf::from(dest.trim());
+    if !dest.is_absolute() {
+        return Err("Backup destination must be an absolute path".to_string());
+    }
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let codec = compression::backup_codec();
+    let report = tasks::run(&app, task_id, "backup", "Backing up repository", |task| async move {
+        tokio::task::spawn_blocking(move || create_backup(&repo, &dest, include_keys, passphrase, codec, &task))
+            .await
+            .map_err(|e| format!("Backup task failed: {}", e))?
+    })
+    .await?;
+    log::info!("Backed up truth repository ({} files)", report.files);
+    Ok(report)
+}
+
+/// Restore a backup over the active repo, or into `target` (a new or empty
+/// directory) to inspect it without touching the active one
+#[tauri::command]
+pub async fn restore_truth_repo(
+    app: AppHandle,
+    src: String,
+    passphrase: Option<String>,
+    target: Option<String>,
+    task_id: Option<String>,
+) -> Result<RestoreReport, String> {
+    if mock::is_active() {
+        return Err("Restore is disabled in mock mode".to_string());
+    }
+    let repo = get_truth_path().ok_or("Could not find home directory")?;
+    let src = PathBuf::from(src.trim());
+    consent::require_read(&src).await?;
+    let target = target
+        .map(|t| PathBuf::from(t.trim()))
+        .filter(|t| !t.as_os_str().is_empty());
+    if target.as_ref().is_some_and(|t| !t.is_absolute()) {
+        return Err("Restore location must be an absolute path".to_string());
+    }
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let report = tasks::run(&app, task_id, "restore", "Restoring repository", |task| async move {
+        tokio::task::spawn_blocking(move || restore_backup(&src, passphrase, &repo, target, &task))
+            .await
+            .map_err(|e| format!("Restore task failed: {}", e))?
+    })
+    .await?;
+    log::info!("Restored truth repository ({} files)", report.files);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    fn sample_repo(root: &Path) -> PathBuf {
+        let repo = root.join(".truth");
+        fs::create_dir_all(repo.join("objects/cl/ab")).unwrap();
+        fs::write(repo.join("objects/cl/ab/cdef01"), b"claim bytes").unwrap();
+        fs::write(repo.join("HEAD"), "abcdef01\n").unwrap();
+        fs::write(repo.join("audit.json"), "[]").unwrap();
+        fs::write(repo.join("proof.key"), "secret").unwrap();
+        repo
+    }
+
+    #[test]
+    fn test_backup_round_trip_to_new_location() {
+        let tmp = TempDir::new("truthgit-backup");
+        let root = tmp.path();
+        let repo = sample_repo(root);
+        for codec in [Codec::Zlib, Codec::Zstd] {
+            let report = create_backup(&repo, root, false, None, codec, &TaskHandle::detached()).unwrap();
+            assert_eq!(report.files, 3);
+
+            let restored = root.join(format!("restored-{:?}/.truth", codec));
+            let result = restore_backup(
+                Path::new(&report.path),
+                None,
+                &repo,
+                Some(restored.clone()),
+                &TaskHandle::detached(),
+            )
+            .unwrap();
+            assert!(result.previous_repo.is_none());
+            assert_eq!(fs::read(restored.join("objects/cl/ab/cdef01")).unwrap(), b"claim bytes");
+            // Keys are opt-in
+            assert!(!restored.join("proof.key").exists());
+        }
+        assert!(repo.join("proof.key").exists());
+    }
+
+    #[test]
+    fn test_encrypted_repo_round_trip() {
+        let tmp = TempDir::new("truthgit-backup");
+        let root = tmp.path();
+        let repo = sample_repo(root);
+        let sealed = b"TGENC1\0 sealed claim bytes";
+        fs::write(repo.join("objects/cl/ab/cdef01"), sealed).unwrap();
+        fs::write(repo.join(CONFIG_FILE), r#"{"version": 1, "wrapped_key": "a2V5"}"#).unwrap();
+        let report = create_backup(&repo, root, false, None, Codec::Zlib, &TaskHandle::detached()).unwrap();
+        assert_eq!(report.files, 4);
+
+        let restored = root.join("restored/.truth");
+        restore_backup(Path::new(&report.path), None, &repo, Some(restored.clone()), &TaskHandle::detached()).unwrap();
+        assert_eq!(fs::read(restored.join("objects/cl/ab/cdef01")).unwrap(), sealed);
+        assert_eq!(
+            fs::read(restored.join(CONFIG_FILE)).unwrap(),
+            fs::read(repo.join(CONFIG_FILE)).unwrap()
+        );
+
+        // In place, the restored repo keeps the key it was sealed with
+        let result = restore_backup(Path::new(&report.path), None, &repo, None, &TaskHandle::detached()).unwrap();
+        assert!(result.previous_repo.is_some());
+        assert_eq!(fs::read(repo.join("objects/cl/ab/cdef01")).unwrap(), sealed);
+        assert!(repo.join(CONFIG_FILE).is_file());
+        assert!(repo.join("proof.key").is_file());
+    }
+
+    #[test]
+    fn test_restore_rejects_tampered_archive() {
+        let tmp = TempDir::new("truthgit-backup");
+        let root = tmp.path();
+        let repo = sample_repo(root);
+        let mut manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_at: String::new(),
+            app_version: String::new(),
+            includes_keys: false,
+            files: Vec::new(),
+        };
+        let files = collect_files(&repo, false).unwrap();
+        for (name, path) in &files {
+            manifest.files.push(ManifestEntry {
+                path: name.clone(),
+                size: fs::metadata(path).unwrap().len(),
+                sha256: "0".repeat(64),
+            });
+        }
+        let archive = root.join("tampered.tar.gz");
+        let out = File::create(&archive).unwrap();
+        write_archive(out, Codec::Zlib, &manifest, &files, &TaskHandle::detached()).unwrap();
+
+        let err = restore_backup(
+            &archive,
+            None,
+            &repo,
+            Some(root.join("restored")),
+            &TaskHandle::detached(),
+        )
+        .unwrap_err();
+        assert!(err.contains("Integrity check failed"));
+        assert!(!root.join("restored").exists());
+    }
+
+    #[test]
+    fn test_safe_archive_path() {
+        assert!(safe_archive_path("objects/cl/ab/cdef01").is_some());
+        assert!(safe_archive_path("../outside").is_none());
+        assert!(safe_archive_path("/etc/passwd").is_none());
+        assert!(safe_archive_path("settings.json").is_none());
+        assert!(safe_archive_path(CONFIG_FILE).is_some());
+    }
+}
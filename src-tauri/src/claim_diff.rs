@@ -0,0 +1,247 @@
+// ==================== CLAIM DIFF ====================
+//
+// Side-by-side comparison of two claims, for deciding between duplicates or
+// whether one claim supersedes another: a word-level diff of the content,
+// the claim fields and metadata that differ, and how each claim fared in
+// verification.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use crate::exports::field;
+use crate::verification::Verification;
+use crate::reviews::validate_hash;
+use crate::{read_claim, read_verifications, run_blocking};
+
+/// Word diffs are quadratic; longer content is shown as a single replacement
+const MAX_DIFF_WORDS: usize = 2000;
+
+/// Claim fields compared besides content and metadata
+const CLAIM_FIELDS: &[&str] = &["domain", "category", "state", "confidence", "$type"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A run of words that is in both claims, only in A (delete) or only in B (insert)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WordChange {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldDiff {
+    /// e.g. "domain" or "metadata.tags"
+    pub field: String,
+    pub a: Option<serde_json::Value>,
+    pub b: Option<serde_json::Value>,
+    pub changed: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VerificationSummary {
+    pub count: usize,
+    /// Latest verification
+    pub verdict: Option<String>,
+    pub confidence: Option<f64>,
+    pub verified_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimDiff {
+    pub hash_a: String,
+    pub hash_b: String,
+    pub content: Vec<WordChange>,
+    /// Share of words the two contents have in common, 0.0-1.0
+    pub similarity: f64,
+    pub fields: Vec<FieldDiff>,
+    pub verification_a: VerificationSummary,
+    pub verification_b: VerificationSummary,
+}
+
+fn push(changes: &mut Vec<WordChange>, op: DiffOp, word: &str) {
+    match changes.last_mut() {
+        Some(last) if last.op == op => {
+            last.text.push(' ');
+            last.text.push_str(word);
+        }
+        _ => changes.push(WordChange {
+            op,
+            text: word.to_string(),
+        }),
+    }
+}
+
+/// Word-level diff from the longest common subsequence of the two word lists
+fn diff_words(a: &str, b: &str) -> Vec<WordChange> {
+    let a: Vec<&str> = a.split_whitespace().collect();
+    let b: Vec<&str> = b.split_whitespace().collect();
+    let mut changes = Vec::new();
+    if a.len() > MAX_DIFF_WORDS || b.len() > MAX_DIFF_WORDS {
+        if a != b {
+            a.iter().for_each(|w| push(&mut changes, DiffOp::Delete, w));
+            b.iter().for_each(|w| push(&mut changes, DiffOp::Insert, w));
+        } else {
+            a.iter().for_each(|w| push(&mut changes, DiffOp::Equal, w));
+        }
+        return changes;
+    }
+
+    // lcs[i][j]: common subsequence length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            push(&mut changes, DiffOp::Equal, a[i]);
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push(&mut changes, DiffOp::Delete, a[i]);
+            i += 1;
+        } else {
+            push(&mut changes, DiffOp::Insert, b[j]);
+            j += 1;
+        }
+    }
+    changes
+}
+
+fn similarity(changes: &[WordChange]) -> f64 {
+    let words = |op: DiffOp| -> usize {
+        changes
+            .iter()
+            .filter(|c| c.op == op)
+            .map(|c| c.text.split_whitespace().count())
+            .sum()
+    };
+    let equal = words(DiffOp::Equal);
+    let total = 2 * equal + words(DiffOp::Delete) + words(DiffOp::Insert);
+    if total == 0 {
+        return 1.0;
+    }
+    2.0 * equal as f64 / total as f64
+}
+
+/// Claim fields, then every metadata key either claim has
+fn diff_fields(a: &serde_json::Value, b: &serde_json::Value) -> Vec<FieldDiff> {
+    let metadata = |claim: &serde_json::Value| {
+        claim
+            .get("metadata")
+            .and_then(|m| m.as_object())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let (meta_a, meta_b) = (metadata(a), metadata(b));
+    let keys: BTreeSet<&String> = meta_a.keys().chain(meta_b.keys()).collect();
+    CLAIM_FIELDS
+        .iter()
+        .map(|name| field_diff(name.to_string(), a.get(*name), b.get(*name)))
+        .chain(
+            keys.into_iter()
+                .map(|key| field_diff(format!("metadata.{}", key), meta_a.get(key), meta_b.get(key))),
+        )
+        .collect()
+}
+
+fn field_diff(field: String, a: Option<&serde_json::Value>, b: Option<&serde_json::Value>) -> FieldDiff {
+    let a = a.filter(|v| !v.is_null()).cloned();
+    let b = b.filter(|v| !v.is_null()).cloned();
+    FieldDiff {
+        field,
+        changed: a != b,
+        a,
+        b,
+    }
+}
+
+/// Listings are newest first, so the first match is the latest verification
+fn summarize(verifications: &[Verification], hash: &str) -> VerificationSummary {
+    let mine: Vec<&Verification> = verifications
+        .iter()
+        .filter(|v| v.claim_hash == hash)
+        .collect();
+    let Some(latest) = mine.first() else {
+        return VerificationSummary::default();
+    };
+    VerificationSummary {
+        count: mine.len(),
+        verdict: Some(latest.verdict.clone()).filter(|v| !v.is_empty()),
+        confidence: latest.confidence,
+        verified_at: Some(latest.timestamp.clone()).filter(|t| !t.is_empty()),
+    }
+}
+
+/// Compare two claims' content, fields and verification outcomes
+#[tauri::command]
+pub async fn diff_claims(hash_a: String, hash_b: String) -> Result<ClaimDiff, String> {
+    let (hash_a, hash_b) = (hash_a.trim().to_lowercase(), hash_b.trim().to_lowercase());
+    validate_hash(&hash_a)?;
+    validate_hash(&hash_b)?;
+    run_blocking(move || {
+        let claim_a = read_claim(hash_a.clone())?;
+        let claim_b = read_claim(hash_b.clone())?;
+        let content = |claim: &serde_json::Value| field(claim, "content").to_string();
+        let changes = diff_words(&content(&claim_a), &content(&claim_b));
+        let verifications = read_verifications()?.verifications;
+        Ok(ClaimDiff {
+            similarity: similarity(&changes),
+            content: changes,
+            fields: diff_fields(&claim_a, &claim_b),
+            verification_a: summarize(&verifications, &hash_a),
+            verification_b: summarize(&verifications, &hash_b),
+            hash_a,
+            hash_b,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_words() {
+        let changes = diff_words(
+            "Water boils at 100°C at sea level",
+            "Water boils at 100°C at  high altitude",
+        );
+        let ops: Vec<(DiffOp, &str)> = changes.iter().map(|c| (c.op, c.text.as_str())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                (DiffOp::Equal, "Water boils at 100°C at"),
+                (DiffOp::Delete, "sea level"),
+                (DiffOp::Insert, "high altitude"),
+            ]
+        );
+        assert!((similarity(&changes) - 5.0 / 7.0).abs() < 1e-9);
+        assert_eq!(similarity(&diff_words("same words", "same  words")), 1.0);
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let a = serde_json::json!({ "domain": "science", "state": "draft", "metadata": { "tags": ["water"] } });
+        let b = serde_json::json!({ "domain": "science", "state": "verified", "metadata": { "language": "en" } });
+        let changed: Vec<String> = diff_fields(&a, &b)
+            .into_iter()
+            .filter(|f| f.changed)
+            .map(|f| f.field)
+            .collect();
+        assert_eq!(changed, vec!["state", "metadata.language", "metadata.tags"]);
+    }
+}
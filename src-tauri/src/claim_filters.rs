@@ -0,0 +1,255 @@
+// ==================== CLAIM FILTERS ====================
+//
+// `query_claims` narrows the claim listing by text, domain, state, type,
+// confidence range and creation date, e.g. "medical, confidence below 0.7,
+// last 30 days". Filter sets an analyst comes back to are saved by name in
+// saved_filters.json in the config directory; `last_days` is kept relative,
+// so a saved "last 30 days" keeps meaning the last 30 days.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{index, read_claims, run_blocking};
+
+const MAX_FILTERS: usize = 100;
+const MAX_NAME_LEN: usize = 80;
+const MAX_LAST_DAYS: u32 = 3650;
+
+/// Serializes read-modify-write cycles on saved_filters.json
+static FILTERS_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClaimQuery {
+    /// Words that must all appear in the content or tags
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// e.g. "verified", "draft" or "contested"
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub ontological_type: Option<String>,
+    /// Inclusive bounds, 0.0-1.0
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+    #[serde(default)]
+    pub max_confidence: Option<f64>,
+    /// Created within this many days of now
+    #[serde(default)]
+    pub last_days: Option<u32>,
+    /// RFC 3339 timestamp or date prefix ("2026-01"), inclusive
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub id: String,
+    pub name: String,
+    pub query: ClaimQuery,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn text<'a>(claim: &'a serde_json::Value, pointer: &str) -> &'a str {
+    claim.pointer(pointer).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+impl ClaimQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        for bound in [self.min_confidence, self.max_confidence].into_iter().flatten() {
+            if !(0.0..=1.0).contains(&bound) {
+                return Err("Confidence bounds must be between 0 and 1".to_string());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_confidence, self.max_confidence) {
+            if min > max {
+                return Err("Minimum confidence is above the maximum".to_string());
+            }
+        }
+        if self.last_days.is_some_and(|d| d == 0 || d > MAX_LAST_DAYS) {
+            return Err(format!("last_days must be between 1 and {}", MAX_LAST_DAYS));
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, claim: &serde_json::Value, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let eq = |wanted: &Option<String>, actual: &str| {
+            non_empty(wanted).is_none_or(|w| actual.trim().eq_ignore_ascii_case(w))
+        };
+        if !eq(&self.domain, text(claim, "/domain")) || !eq(&self.state, text(claim, "/state")) {
+            return false;
+        }
+
+        let confidence = claim.get("confidence").and_then(|c| c.as_f64());
+        let in_bounds =
+            |bound: Option<f64>, ok: fn(f64, f64) -> bool| bound.is_none_or(|b| confidence.is_some_and(|c| ok(c, b)));
+        if !in_bounds(self.min_confidence, |c, b| c >= b) || !in_bounds(self.max_confidence, |c, b| c <= b) {
+            return false;
+        }
+
+        let created = text(claim, "/metadata/created_at");
+        if let Some(days) = self.last_days {
+            let cutoff = now - chrono::Duration::days(i64::from(days));
+            let recent = chrono::DateTime::parse_from_rfc3339(created).is_ok_and(|c| c >= cutoff);
+            if !recent {
+                return false;
+            }
+        }
+        if non_empty(&self.since).is_some_and(|s| created.is_empty() || created < s) {
+            return false;
+        }
+        if non_empty(&self.until).is_some_and(|u| created.is_empty() || created.get(..u.len()).unwrap_or(created) > u) {
+            return false;
+        }
+
+        let Some(words) = non_empty(&self.text) else {
+            return true;
+        };
+        let tags = claim
+            .pointer("/metadata/tags")
+            .and_then(|t| t.as_array())
+            .map(|t| t.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+        let haystack = format!("{} {}", text(claim, "/content"), tags).to_lowercase();
+        words.to_lowercase().split_whitespace().all(|w| haystack.contains(w))
+    }
+}
+
+fn filters_path() -> PathBuf {
+    config_path("saved_filters.json")
+}
+
+/// Claims matching `query`, newest first
+#[tauri::command]
+pub async fn query_claims(query: ClaimQuery) -> Result<index::ClaimListing, String> {
+    query.validate()?;
+    run_blocking(move || {
+        let mut listing = read_claims(non_empty(&query.ontological_type))?;
+        let now = chrono::Utc::now();
+        listing.claims.retain(|claim| query.matches(claim, now));
+        Ok(listing)
+    })
+    .await
+}
+
+/// Save `query` under `name`, replacing a saved filter with the same name
+#[tauri::command]
+pub async fn save_filter(name: String, query: ClaimQuery) -> Result<SavedFilter, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Filter name must be 1-{} characters", MAX_NAME_LEN));
+    }
+    query.validate()?;
+    run_blocking(move || {
+        let path = filters_path();
+        let _guard = FILTERS_LOCK.lock(&path)?;
+        let mut filters: Vec<SavedFilter> = load_json(&path)?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let saved = match filters.iter_mut().find(|f| f.name.eq_ignore_ascii_case(&name)) {
+            Some(existing) => {
+                existing.name = name;
+                existing.query = query;
+                existing.updated_at = now;
+                existing.clone()
+            }
+            None => {
+                if filters.len() >= MAX_FILTERS {
+                    return Err(format!("At most {} filters can be saved", MAX_FILTERS));
+                }
+                let filter = SavedFilter {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name,
+                    query,
+                    created_at: now.clone(),
+                    updated_at: now,
+                };
+                filters.push(filter.clone());
+                filter
+            }
+        };
+        save_json(&path, &filters)?;
+        Ok(saved)
+    })
+    .await
+}
+
+/// Saved filters, by name
+#[tauri::command]
+pub async fn list_saved_filters() -> Result<Vec<SavedFilter>, String> {
+    run_blocking(|| {
+        let _guard = FILTERS_LOCK.lock(&filters_path())?;
+        let mut filters: Vec<SavedFilter> = load_json(&filters_path())?;
+        filters.sort_by_key(|f| f.name.to_lowercase());
+        Ok(filters)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn delete_filter(id: String) -> Result<(), String> {
+    run_blocking(move || {
+        let path = filters_path();
+        let _guard = FILTERS_LOCK.lock(&path)?;
+        let mut filters: Vec<SavedFilter> = load_json(&path)?;
+        let before = filters.len();
+        filters.retain(|f| f.id != id);
+        if filters.len() == before {
+            return Err(format!("Saved filter not found: {}", id));
+        }
+        save_json(&path, &filters)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_matches() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-31T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let claim = |domain: &str, confidence: f64, created_at: &str| {
+            serde_json::json!({
+                "content": "Aspirin reduces fever", "domain": domain, "confidence": confidence,
+                "state": "draft", "metadata": { "created_at": created_at, "tags": ["pharmacology"] },
+            })
+        };
+        let query = ClaimQuery {
+            domain: Some("Medical".to_string()),
+            max_confidence: Some(0.7),
+            last_days: Some(30),
+            ..Default::default()
+        };
+        assert!(query.validate().is_ok());
+        assert!(query.matches(&claim("medical", 0.6, "2026-03-20T00:00:00Z"), now));
+        assert!(!query.matches(&claim("medical", 0.8, "2026-03-20T00:00:00Z"), now));
+        assert!(!query.matches(&claim("medical", 0.6, "2026-02-01T00:00:00Z"), now));
+        assert!(!query.matches(&claim("science", 0.6, "2026-03-20T00:00:00Z"), now));
+
+        let text = ClaimQuery {
+            text: Some("PHARMACOLOGY fever".to_string()),
+            until: Some("2026-03".to_string()),
+            ..Default::default()
+        };
+        assert!(text.matches(&claim("medical", 0.6, "2026-03-20T00:00:00Z"), now));
+        assert!(!text.matches(&claim("medical", 0.6, "2026-04-02T00:00:00Z"), now));
+
+        let inverted = ClaimQuery {
+            min_confidence: Some(0.9),
+            max_confidence: Some(0.5),
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+    }
+}
@@ -0,0 +1,180 @@
+// ==================== CLAIM SCHEMA ====================
+//
+// Claim objects are written by the `truthgit` CLI and read here, and the two
+// ship separately. Newer CLIs stamp `$schema_version` on each claim; claims
+// without one predate versioning and count as version 1. `validate_claim`
+// checks a claim's shape field by field, and listings warn about claims whose
+// version is outside the range this app reads, so format drift shows up as a
+// warning instead of a claim that silently loses fields.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::objects::{ObjectKind, ObjectWarning};
+
+/// Newest claim schema this app reads
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+/// Oldest claim schema still read without loss
+pub const MIN_SCHEMA_VERSION: u32 = 1;
+const STATES: &[&str] = &["draft", "verified", "contested"];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaIssue {
+    /// JSON pointer to the field, e.g. "/metadata/tags"
+    pub field: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ClaimValidation {
+    pub valid: bool,
+    pub schema_version: u32,
+    /// The claim can't be read as a claim
+    pub errors: Vec<SchemaIssue>,
+    /// Readable, but not as written by this schema version
+    pub warnings: Vec<SchemaIssue>,
+}
+
+/// The stated `$schema_version`, 1 for unversioned claims, None if unreadable
+pub fn schema_version(claim: &Value) -> Option<u32> {
+    match claim.get("$schema_version") {
+        None | Some(Value::Null) => Some(1),
+        Some(Value::Number(n)) => n.as_u64().and_then(|v| u32::try_from(v).ok()),
+        Some(Value::String(s)) => s.trim().parse().ok(),
+        Some(_) => None,
+    }
+}
+
+/// Why a claim written in another schema version may be misread, if it is
+fn drift(version: u32) -> Option<String> {
+    if version > CURRENT_SCHEMA_VERSION {
+        Some(format!(
+            "Written with claim schema v{} by a newer truthgit; this app reads up to v{} and may miss fields",
+            version, CURRENT_SCHEMA_VERSION
+        ))
+    } else if version < MIN_SCHEMA_VERSION {
+        Some(format!(
+            "Written with claim schema v{} by an older truthgit; this app reads v{} and later",
+            version, MIN_SCHEMA_VERSION
+        ))
+    } else {
+        None
+    }
+}
+
+/// A listing warning for a claim outside the supported schema range
+pub fn drift_warning(object: &str, claim: &Value) -> Option<ObjectWarning> {
+    let message = match schema_version(claim) {
+        Some(version) => drift(version)?,
+        None => "Claim has an unreadable $schema_version".to_string(),
+    };
+    Some(ObjectWarning {
+        object: object.to_string(),
+        kind: Some(ObjectKind::Claim),
+        message,
+    })
+}
+
+pub fn validate(claim: &Value) -> ClaimValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let issue = |field: &str, message: &str| SchemaIssue {
+        field: field.to_string(),
+        message: message.to_string(),
+    };
+
+    let Some(object) = claim.as_object() else {
+        return ClaimValidation {
+            errors: vec![issue("", "A claim must be a JSON object")],
+            ..Default::default()
+        };
+    };
+    let version = schema_version(claim);
+    match version {
+        None => errors.push(issue("/$schema_version", "Must be a whole number")),
+        Some(version) => warnings.extend(drift(version).map(|m| issue("/$schema_version", &m))),
+    }
+
+    match object.get("$type").map(|t| t.as_str()) {
+        None | Some(Some("claim")) => {}
+        _ => errors.push(issue("/$type", "Must be \"claim\"")),
+    }
+    match object.get("content").map(|c| c.as_str().map(str::trim)) {
+        Some(Some(content)) if !content.is_empty() => {}
+        Some(Some(_)) | None => errors.push(issue("/content", "A claim needs its text")),
+        Some(None) => errors.push(issue("/content", "Must be a string")),
+    }
+    match object.get("confidence") {
+        None | Some(Value::Null) => {}
+        Some(c) if c.as_f64().is_some_and(|c| (0.0..=1.0).contains(&c)) => {}
+        Some(_) => errors.push(issue("/confidence", "Must be a number between 0 and 1")),
+    }
+    for field in ["$hash", "domain", "category"] {
+        if object.get(field).is_some_and(|v| !v.is_string()) {
+            errors.push(issue(&format!("/{}", field), "Must be a string"));
+        }
+    }
+    match object.get("state") {
+        None => {}
+        Some(Value::String(state)) if STATES.contains(&state.as_str()) => {}
+        Some(Value::String(_)) => warnings.push(issue("/state", "Unknown state; shown as written")),
+        Some(_) => errors.push(issue("/state", "Must be a string")),
+    }
+
+    match object.get("metadata") {
+        None => {}
+        Some(Value::Object(metadata)) => {
+            let tags = metadata.get("tags");
+            if tags.is_some_and(|t| !t.as_array().is_some_and(|t| t.iter().all(Value::is_string))) {
+                errors.push(issue("/metadata/tags", "Must be a list of strings"));
+            }
+            match metadata.get("created_at") {
+                None => warnings.push(issue("/metadata/created_at", "Missing; the claim sorts last")),
+                Some(Value::String(at)) if chrono::DateTime::parse_from_rfc3339(at).is_ok() => {}
+                Some(_) => errors.push(issue("/metadata/created_at", "Must be an RFC 3339 timestamp")),
+            }
+        }
+        Some(_) => errors.push(issue("/metadata", "Must be an object")),
+    }
+
+    ClaimValidation {
+        valid: errors.is_empty(),
+        schema_version: version.unwrap_or(0),
+        errors,
+        warnings,
+    }
+}
+
+/// Check a claim object against the claim schema
+#[tauri::command]
+pub async fn validate_claim(json: Value) -> Result<ClaimValidation, String> {
+    Ok(validate(&json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_claim() {
+        let claim = serde_json::json!({
+            "$type": "claim", "content": "Water boils at 100°C", "confidence": 0.97, "state": "verified",
+            "metadata": { "tags": ["physics"], "created_at": "2026-01-14T09:12:00Z" },
+        });
+        let result = validate(&claim);
+        assert!(result.valid && result.warnings.is_empty());
+        assert_eq!(result.schema_version, 1);
+        assert!(drift_warning("cl/ab/cd", &claim).is_none());
+
+        let broken = serde_json::json!({
+            "$schema_version": 3, "content": "", "confidence": 1.5, "metadata": { "tags": "physics" },
+        });
+        let result = validate(&broken);
+        assert!(!result.valid);
+        let fields: Vec<&str> = result.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["/content", "/confidence", "/metadata/tags"]);
+        assert_eq!(result.warnings[0].field, "/$schema_version");
+        assert!(drift_warning("cl/ab/cd", &broken).unwrap().message.contains("newer"));
+        assert!(drift_warning("cl/ab/cd", &serde_json::json!({ "$schema_version": 0 })).is_some());
+    }
+}
@@ -0,0 +1,302 @@
+// ==================== CLAIM TEMPLATES ====================
+//
+// Reusable claim wording with `{Variable}` placeholders, e.g. "Product
+// {Product} complies with regulation {Regulation}", stored per domain in
+// claim_templates.json. `instantiate_claim_template` fills in the variables
+// and either hands the text back for the claim form or verifies it straight
+// away. `{{` and `}}` stand for literal braces.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::settings_validation::RISK_PROFILES;
+use crate::{domain_usage, domains, run_blocking, GovernanceResult};
+
+const MAX_NAME_LEN: usize = 100;
+const MAX_TEXT_LEN: usize = 2000;
+const MAX_VARIABLE_NAME_LEN: usize = 40;
+const MAX_VALUE_LEN: usize = 500;
+
+/// Serializes read-modify-write cycles on claim_templates.json
+static TEMPLATES_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClaimTemplate {
+    /// Assigned on first save
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub domain: String,
+    pub text: String,
+    /// Overrides the domain's default risk profile
+    #[serde(default)]
+    pub risk_profile: Option<String>,
+    /// Placeholder names in order of appearance, filled in when listed
+    #[serde(default, skip_deserializing)]
+    pub variables: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstantiatedClaim {
+    pub template_id: String,
+    pub claim: String,
+    pub domain: String,
+    pub risk_profile: Option<String>,
+    /// Set when the claim was verified right away
+    pub result: Option<GovernanceResult>,
+}
+
+fn templates_path() -> std::path::PathBuf {
+    config_path("claim_templates.json")
+}
+
+fn load_templates() -> Result<Vec<ClaimTemplate>, String> {
+    let _guard = TEMPLATES_LOCK.lock(&templates_path())?;
+    load_json(&templates_path())
+}
+
+fn update_templates<T>(change: impl FnOnce(&mut Vec<ClaimTemplate>) -> Result<T, String>) -> Result<T, String> {
+    let _guard = TEMPLATES_LOCK.lock(&templates_path())?;
+    let mut templates: Vec<ClaimTemplate> = load_json(&templates_path())?;
+    let result = change(&mut templates)?;
+    save_json(&templates_path(), &templates)?;
+    Ok(result)
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+/// Split template text into literal runs and `{Variable}` placeholders
+fn parse(text: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find(['{', '}']) {
+        segments.push(Segment::Text(&rest[..pos]));
+        let brace = &rest[pos..pos + 1];
+        let after = &rest[pos + 1..];
+        if after.starts_with(brace) {
+            segments.push(Segment::Text(brace));
+            rest = &after[1..];
+            continue;
+        }
+        if brace == "}" {
+            return Err("Unmatched \"}\" in template; write \"}}\" for a literal brace".to_string());
+        }
+        let end = after
+            .find('}')
+            .ok_or("Unclosed \"{\" in template; write \"{{\" for a literal brace")?;
+        let name = after[..end].trim();
+        if name.is_empty()
+            || name.chars().count() > MAX_VARIABLE_NAME_LEN
+            || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == ' ')
+        {
+            return Err(format!("Invalid variable name: {{{}}}", &after[..end]));
+        }
+        segments.push(Segment::Variable(name));
+        rest = &after[end + 1..];
+    }
+    segments.push(Segment::Text(rest));
+    Ok(segments)
+}
+
+fn variables(text: &str) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = Vec::new();
+    for segment in parse(text)? {
+        if let Segment::Variable(name) = segment {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Fill in every placeholder. Missing, empty and unknown variables are
+/// errors, so a typo in a variable name can't produce a half-filled claim.
+fn substitute(text: &str, values: &BTreeMap<String, String>) -> Result<String, String> {
+    let expected = variables(text)?;
+    if let Some(unknown) = values.keys().find(|k| !expected.iter().any(|e| e == k.trim())) {
+        return Err(format!("Template has no variable named {}", unknown));
+    }
+    let mut claim = String::new();
+    for segment in parse(text)? {
+        match segment {
+            Segment::Text(text) => claim.push_str(text),
+            Segment::Variable(name) => {
+                let value = values
+                    .iter()
+                    .find(|(k, _)| k.trim() == name)
+                    .map(|(_, v)| v.trim())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| format!("Missing value for {{{}}}", name))?;
+                if value.chars().count() > MAX_VALUE_LEN || value.contains(['\n', '\r']) {
+                    return Err(format!(
+                        "Value for {{{}}} must be a single line under {} characters",
+                        name, MAX_VALUE_LEN
+                    ));
+                }
+                claim.push_str(value);
+            }
+        }
+    }
+    Ok(claim)
+}
+
+fn validate(template: ClaimTemplate) -> Result<ClaimTemplate, String> {
+    let name = template.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Template name must be 1-{} characters", MAX_NAME_LEN));
+    }
+    let text = template.text.trim().to_string();
+    if text.is_empty() || text.chars().count() > MAX_TEXT_LEN {
+        return Err(format!("Template text must be 1-{} characters", MAX_TEXT_LEN));
+    }
+    let variables = variables(&text)?;
+    let risk_profile = template.risk_profile.filter(|r| !r.trim().is_empty());
+    if let Some(risk) = &risk_profile {
+        if !RISK_PROFILES.contains(&risk.as_str()) {
+            return Err(format!(
+                "Invalid risk profile: {} (use {})",
+                risk,
+                RISK_PROFILES.join(", ")
+            ));
+        }
+    }
+    let domain = match domains::resolve_domain(&template.domain)? {
+        Some(registered) => registered.name,
+        None => template.domain.trim().to_string(),
+    };
+    if domain.is_empty() {
+        return Err("Template needs a domain".to_string());
+    }
+    Ok(ClaimTemplate {
+        id: template.id,
+        name,
+        domain,
+        text,
+        risk_profile,
+        variables,
+    })
+}
+
+/// Templates, optionally only those for `domain`, sorted by domain and name
+#[tauri::command]
+pub async fn list_claim_templates(domain: Option<String>) -> Result<Vec<ClaimTemplate>, String> {
+    let domain = domain.map(|d| d.trim().to_lowercase()).filter(|d| !d.is_empty());
+    run_blocking(move || {
+        let mut templates: Vec<ClaimTemplate> = load_templates()?
+            .into_iter()
+            .filter(|t| domain.as_ref().is_none_or(|d| t.domain.to_lowercase() == *d))
+            .map(|mut t| {
+                t.variables = variables(&t.text).unwrap_or_default();
+                t
+            })
+            .collect();
+        templates.sort_by(|a, b| (&a.domain, &a.name).cmp(&(&b.domain, &b.name)));
+        Ok(templates)
+    })
+    .await
+}
+
+/// Create a template, or replace the one with the same ID
+#[tauri::command]
+pub async fn save_claim_template(template: ClaimTemplate) -> Result<ClaimTemplate, String> {
+    run_blocking(move || {
+        let mut template = validate(template)?;
+        if template.id.trim().is_empty() {
+            template.id = uuid::Uuid::new_v4().to_string();
+        }
+        update_templates(|templates| {
+            match templates.iter_mut().find(|t| t.id == template.id) {
+                Some(existing) => *existing = template.clone(),
+                None => templates.push(template.clone()),
+            }
+            Ok(())
+        })?;
+        log::info!("Saved claim template {} ({})", template.name, template.domain);
+        Ok(template)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn delete_claim_template(id: String) -> Result<(), String> {
+    run_blocking(move || {
+        update_templates(|templates| {
+            let before = templates.len();
+            templates.retain(|t| t.id != id);
+            if templates.len() == before {
+                return Err("Claim template not found".to_string());
+            }
+            Ok(())
+        })
+    })
+    .await
+}
+
+/// Fill in a template's variables. With `verify` the claim is verified right
+/// away, like one typed into the Verify panel; otherwise the text is returned
+/// for the claim form.
+#[tauri::command]
+pub async fn instantiate_claim_template(
+    id: String,
+    vars: BTreeMap<String, String>,
+    verify: Option<bool>,
+) -> Result<InstantiatedClaim, String> {
+    let template = run_blocking(load_templates)
+        .await?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| "Claim template not found".to_string())?;
+    let claim = substitute(&template.text, &vars)?;
+
+    let result = if verify.unwrap_or(false) {
+        let risk_profile = match template.risk_profile.clone() {
+            Some(risk_profile) => risk_profile,
+            None => domain_usage::suggested_risk_profile(&template.domain)?,
+        };
+        Some(crate::governance_verify(claim.clone(), template.domain.clone(), risk_profile, None, None).await?)
+    } else {
+        None
+    };
+    Ok(InstantiatedClaim {
+        template_id: template.id,
+        claim,
+        domain: template.domain,
+        risk_profile: template.risk_profile,
+        result,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_substitute() {
+        let text = "Product {X} complies with regulation {Y} ({{since}} {X})";
+        assert_eq!(variables(text).unwrap(), vec!["X", "Y"]);
+        assert_eq!(
+            substitute(text, &vars(&[("X", "Widget 3"), ("Y", " GDPR ")])).unwrap(),
+            "Product Widget 3 complies with regulation GDPR ({since} Widget 3)"
+        );
+        assert!(substitute(text, &vars(&[("X", "Widget 3")])).is_err());
+        assert!(substitute(text, &vars(&[("X", "a"), ("Y", "b"), ("Z", "c")])).is_err());
+        assert!(substitute(text, &vars(&[("X", "a"), ("Y", "  ")])).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(variables("Unclosed {X").is_err());
+        assert!(variables("Stray } brace").is_err());
+        assert!(variables("Bad {name!}").is_err());
+        assert_eq!(variables("No variables").unwrap(), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,114 @@
+// ==================== CLIPBOARD VERIFICATION ====================
+//
+// One-keystroke check of whatever statement is on the clipboard: read it,
+// run governance verification, and report the verdict as a notification
+// (always shown, since the user asked for it). Bound to the quick_verify
+// global shortcut and the tray's "Verify Clipboard Text" item.
+
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::{notifications, recent_verifications, GovernanceResult, SETTINGS};
+
+pub const CLIPBOARD_VERIFIED_EVENT: &str = "clipboard://verified";
+
+const DEFAULT_DOMAIN: &str = "general";
+const MAX_CLIPBOARD_CLAIM_LEN: usize = 10_000;
+
+/// The clipboard text as a claim, or why it can't be one
+fn clipboard_claim(text: &str) -> Result<String, String> {
+    let claim = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if claim.is_empty() {
+        return Err("Clipboard is empty".to_string());
+    }
+    if claim.len() > MAX_CLIPBOARD_CLAIM_LEN {
+        return Err("Clipboard text is too long to verify as one claim".to_string());
+    }
+    Ok(claim)
+}
+
+fn verdict_title(result: &GovernanceResult) -> String {
+    format!("{} — {:.0}% confidence", result.status, result.confidence * 100.0)
+}
+
+#[tauri::command]
+pub async fn verify_clipboard(
+    app: AppHandle,
+    domain: Option<String>,
+    risk: Option<String>,
+) -> Result<GovernanceResult, String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| format!("Failed to read clipboard: {}", e))?;
+    let claim = match clipboard_claim(&text) {
+        Ok(claim) => claim,
+        Err(e) => {
+            notifications::show("Nothing to verify", &e);
+            return Err(e);
+        }
+    };
+
+    let domain = domain
+        .filter(|d| !d.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_DOMAIN.to_string());
+    let risk = match risk {
+        Some(risk) => risk,
+        None => SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.default_risk_profile.clone(),
+    };
+
+    let outcome = crate::run_governance_verify(claim.clone(), domain.clone(), risk.clone(), None).await;
+    recent_verifications::record(&claim, &domain, &risk, None, &outcome).await;
+    match outcome {
+        Ok(result) => {
+            notifications::show(&verdict_title(&result), &notifications::excerpt(&claim));
+            let payload = serde_json::json!({ "claim": claim, "result": &result });
+            if let Err(e) = app.emit(CLIPBOARD_VERIFIED_EVENT, payload) {
+                log::warn!("Failed to emit {}: {}", CLIPBOARD_VERIFIED_EVENT, e);
+            }
+            Ok(result)
+        }
+        Err(e) => {
+            notifications::show("Verification failed", &e);
+            Err(e)
+        }
+    }
+}
+
+/// Run a clipboard verification in the background with default options
+pub fn spawn_verify(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = verify_clipboard(app, None, None).await {
+            log::warn!("Clipboard verification failed: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_claim_normalizes_whitespace() {
+        assert_eq!(
+            clipboard_claim("  Water boils\n at 100°C \t").unwrap(),
+            "Water boils at 100°C"
+        );
+        assert!(clipboard_claim(" \n ").is_err());
+        assert!(clipboard_claim(&"x".repeat(MAX_CLIPBOARD_CLAIM_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_verdict_title() {
+        let result = GovernanceResult {
+            status: "PASSED".to_string(),
+            action: "accept".to_string(),
+            confidence: 0.874,
+            reason: String::new(),
+            audit_ref: String::new(),
+            ..Default::default()
+        };
+        assert_eq!(verdict_title(&result), "PASSED — 87% confidence");
+    }
+}
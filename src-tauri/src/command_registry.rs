@@ -0,0 +1,959 @@
+// ==================== COMMAND REGISTRY ====================
+//
+// Describes every command in `generate_handler!` (id, title, category and
+// arguments) so the frontend can build a command palette, and other clients
+// (local API, future MCP server) can enumerate what the app can do without a
+// hardcoded list. Argument names are the ones `invoke` expects, i.e. Tauri's
+// camelCase form of the Rust parameter. A test keeps this table in step with
+// the handler list.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandArg {
+    pub name: &'static str,
+    /// "string", "number", "boolean", "string[]" or "object"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppCommand {
+    /// Name passed to `invoke`
+    pub id: &'static str,
+    pub title: &'static str,
+    pub category: &'static str,
+    pub args: &'static [CommandArg],
+    /// User-facing action worth listing in the palette, as opposed to data
+    /// fetches the UI makes on its own
+    pub palette: bool,
+}
+
+const fn arg(name: &'static str, kind: &'static str, description: &'static str) -> CommandArg {
+    CommandArg {
+        name,
+        kind,
+        required: true,
+        description,
+    }
+}
+
+const fn optional(name: &'static str, kind: &'static str, description: &'static str) -> CommandArg {
+    CommandArg {
+        name,
+        kind,
+        required: false,
+        description,
+    }
+}
+
+const fn command(
+    id: &'static str,
+    title: &'static str,
+    category: &'static str,
+    args: &'static [CommandArg],
+    palette: bool,
+) -> AppCommand {
+    AppCommand {
+        id,
+        title,
+        category,
+        args,
+        palette,
+    }
+}
+
+const REMOTE: CommandArg = optional("remote", "string", "Remote URL; defaults to the configured API URL");
+const PATH: CommandArg = arg("path", "string", "Absolute path");
+const ONTOLOGICAL_TYPE: CommandArg =
+    optional("ontologicalType", "string", "Only claims of this type, e.g. \"empirical\"");
+const TASK_ID: CommandArg = optional("taskId", "string", "Id for task://progress events and cancel_task");
+
+pub const APP_COMMANDS: &[AppCommand] = &[
+    // Settings
+    command("get_settings", "Get settings", "Settings", &[], false),
+    command("update_settings", "Save settings", "Settings", &[arg("newSettings", "object", "Complete settings object")], false),
+    command("update_settings_partial", "Update settings", "Settings", &[arg("patch", "object", "Settings fields to change")], false),
+    command("validate_settings", "Validate settings", "Settings", &[arg("settings", "object", "Settings to check")], false),
+    command("get_engine_info", "Show engine info", "Settings", &[], true),
+    command("get_identity", "Show identity", "Settings", &[], true),
+    command("export_settings", "Export settings", "Settings", &[PATH], true),
+    command("import_settings", "Import settings", "Settings", &[PATH], true),
+    command(
+        "get_message_catalog",
+        "Get message catalog",
+        "Settings",
+        &[optional("locale", "string", "Locale code, e.g. \"es\" (default: the settings locale)")],
+        false,
+    ),
+    command("get_usage_metrics", "Show my usage metrics", "Settings", &[], true),
+    command("export_usage_metrics", "Export usage metrics", "Settings", &[PATH], true),
+    command("clear_usage_metrics", "Clear usage metrics", "Settings", &[], true),
+    command("list_hooks", "List installed hooks", "Settings", &[], true),
+    command("list_plugins", "List plugins", "Settings", &[], true),
+    command(
+        "set_plugin_enabled",
+        "Enable or disable plugin",
+        "Settings",
+        &[
+            arg("id", "string", "Plugin ID"),
+            arg("enabled", "boolean", "Whether the plugin may run"),
+            optional(
+                "permissions",
+                "string[]",
+                "read_claims, write_annotations, emit_events (default: what the manifest asks for)",
+            ),
+        ],
+        false,
+    ),
+    command(
+        "run_plugin",
+        "Run plugin",
+        "Settings",
+        &[arg("id", "string", "Plugin ID"), arg("input", "object", "JSON passed to the plugin")],
+        false,
+    ),
+    command(
+        "get_plugin_annotations",
+        "Show plugin annotations",
+        "Truth Repository",
+        &[arg("hash", "string", "Claim hash")],
+        false,
+    ),
+    // Permissions
+    command("list_granted_permissions", "List granted permissions", "Permissions", &[], true),
+    command(
+        "revoke_permission",
+        "Revoke permission",
+        "Permissions",
+        &[
+            arg("kind", "string", "\"shell\", \"read_path\" or \"remote_host\""),
+            arg("scope", "string", "Program, directory or host"),
+        ],
+        true,
+    ),
+    command(
+        "get_security_events",
+        "Show security events",
+        "Permissions",
+        &[
+            optional("kind", "string", "e.g. \"blocked_command\" or \"path_traversal\""),
+            optional("limit", "number", "Maximum events, newest first (default 200)"),
+        ],
+        true,
+    ),
+    // Profiles
+    command("list_profiles", "List profiles", "Profiles", &[], false),
+    command("save_profile", "Save current settings as profile", "Profiles", &[arg("name", "string", "Profile name")], true),
+    command("delete_profile", "Delete profile", "Profiles", &[arg("name", "string", "Profile name")], true),
+    command("switch_profile", "Switch profile", "Profiles", &[arg("name", "string", "Profile name")], true),
+    // Domains
+    command("list_domains", "List domains", "Domains", &[], true),
+    command(
+        "save_domain",
+        "Save domain",
+        "Domains",
+        &[arg("domain", "object", "{ name, description, default_risk_profile, parent, keywords }")],
+        false,
+    ),
+    command("delete_domain", "Delete domain", "Domains", &[arg("name", "string", "Domain name")], false),
+    command("get_domain_stats", "Show domain statistics", "Domains", &[], true),
+    command("list_ontological_types", "List ontological types", "Domains", &[], true),
+    command(
+        "get_frequent_domains",
+        "Show frequently used domains",
+        "Domains",
+        &[optional("limit", "number", "Most domains to return (default 5)")],
+        true,
+    ),
+    command(
+        "suggest_domain",
+        "Suggest a domain for a claim",
+        "Domains",
+        &[arg("text", "string", "Claim text")],
+        true,
+    ),
+    command(
+        "get_ontological_type_stats",
+        "Show ontological type statistics",
+        "Domains",
+        &[optional("domain", "string", "Only claims in this domain")],
+        true,
+    ),
+    // Setup
+    command("get_first_run_state", "Get first-run state", "Setup", &[], false),
+    command("complete_first_run", "Finish first-run setup", "Setup", &[], false),
+    command("detect_obsidian_vaults", "Detect Obsidian vaults", "Setup", &[], true),
+    command("detect_truth_repos", "Detect truth repositories", "Setup", &[], true),
+    command("initialize_truth_repo", "Initialize truth repository", "Setup", &[PATH], false),
+    // Repositories
+    command("prepare_truth_repo_init", "Prepare repository creation", "Repositories", &[PATH], false),
+    command(
+        "init_truth_repo",
+        "Create truth repository",
+        "Repositories",
+        &[PATH, arg("confirmationToken", "string", "Token from prepare_truth_repo_init")],
+        false,
+    ),
+    command("list_truth_repos", "List repositories", "Repositories", &[], false),
+    command(
+        "add_truth_repo",
+        "Add repository",
+        "Repositories",
+        &[PATH, optional("name", "string", "Display name")],
+        true,
+    ),
+    command("remove_truth_repo", "Remove repository", "Repositories", &[PATH], true),
+    command("switch_truth_repo", "Switch repository", "Repositories", &[PATH], true),
+    command(
+        "backup_truth_repo",
+        "Back up repository",
+        "Repositories",
+        &[
+            arg("dest", "string", "Archive file, or a folder to create it in"),
+            arg("includeKeys", "boolean", "Include the signing key pair"),
+            optional("passphrase", "string", "Encrypt the archive with this passphrase"),
+            TASK_ID,
+        ],
+        true,
+    ),
+    command(
+        "restore_truth_repo",
+        "Restore repository from backup",
+        "Repositories",
+        &[
+            arg("src", "string", "Backup archive"),
+            optional("passphrase", "string", "Passphrase for encrypted backups"),
+            optional("target", "string", "New or empty folder; omit to replace the active repository"),
+            TASK_ID,
+        ],
+        true,
+    ),
+    command(
+        "import_truth_repo_archive",
+        "Import repository from zip archive",
+        "Repositories",
+        &[
+            arg("path", "string", "Zip archive holding a .truth directory"),
+            arg("mode", "string", "adopt (make it the active repository) or merge (into the active one)"),
+            optional("target", "string", "New or empty folder for the adopted repository"),
+            TASK_ID,
+        ],
+        true,
+    ),
+    command(
+        "create_share_bundle",
+        "Share selected claims as a bundle",
+        "Repositories",
+        &[
+            arg("hashes", "string[]", "Claim hashes"),
+            arg("includeVerifications", "boolean", "Include their verifications and cited evidence"),
+            arg("path", "string", "Bundle file, or a folder to create it in"),
+        ],
+        false,
+    ),
+    command(
+        "import_share_bundle",
+        "Import share bundle",
+        "Repositories",
+        &[arg("path", "string", "Share bundle (.zip)")],
+        true,
+    ),
+    command("compact_repo", "Compact repository", "Repositories", &[TASK_ID], true),
+    command("get_last_compaction", "Show last repository compaction", "Repositories", &[], true),
+    command("get_repo_state", "Check repository state", "Repositories", &[], true),
+    command("get_encryption_status", "Show repository encryption status", "Repositories", &[], true),
+    command(
+        "enable_repo_encryption",
+        "Encrypt repository",
+        "Repositories",
+        &[
+            arg("passphrase", "string", "Passphrase that protects the repository key"),
+            arg("remember", "boolean", "Keep the key in the OS keychain"),
+            TASK_ID,
+        ],
+        true,
+    ),
+    command("encrypt_repo_objects", "Encrypt new objects", "Repositories", &[TASK_ID], true),
+    command(
+        "unlock_repo",
+        "Unlock repository",
+        "Repositories",
+        &[
+            arg("passphrase", "string", "Repository passphrase"),
+            arg("remember", "boolean", "Keep the key in the OS keychain"),
+        ],
+        true,
+    ),
+    command("lock_repo", "Lock repository", "Repositories", &[], true),
+    command(
+        "change_repo_passphrase",
+        "Change repository passphrase",
+        "Repositories",
+        &[
+            arg("current", "string", "Current passphrase"),
+            arg("newPassphrase", "string", "New passphrase"),
+        ],
+        true,
+    ),
+    // Sync
+    command("push_claims", "Push claims", "Sync", &[REMOTE, TASK_ID], true),
+    command("pull_claims", "Pull claims", "Sync", &[REMOTE, TASK_ID], true),
+    command("get_sync_log", "Show sync log", "Sync", &[], true),
+    command("get_sync_conflicts", "Show sync conflicts", "Sync", &[], true),
+    command(
+        "resolve_sync_conflict",
+        "Resolve sync conflicts",
+        "Sync",
+        &[
+            REMOTE,
+            arg("strategy", "string", "\"prefer-local\", \"prefer-remote\" or \"merge\""),
+            TASK_ID,
+        ],
+        true,
+    ),
+    // Remote
+    command("list_remotes", "List remotes", "Remote", &[], false),
+    command("add_remote", "Add remote", "Remote", &[arg("remote", "object", "Remote name and URL")], true),
+    command("remove_remote", "Remove remote", "Remote", &[arg("name", "string", "Remote name")], true),
+    command(
+        "browse_remote_claims",
+        "Browse remote claims",
+        "Remote",
+        &[
+            REMOTE,
+            optional("filter", "object", "Domain, status and text filters"),
+            optional("page", "number", "Page number, from 1"),
+        ],
+        true,
+    ),
+    command(
+        "fetch_remote_claim",
+        "Fetch remote claim",
+        "Remote",
+        &[REMOTE, arg("hash", "string", "Claim hash")],
+        false,
+    ),
+    command(
+        "import_remote_history",
+        "Import remote history",
+        "Remote",
+        &[
+            REMOTE,
+            optional("since", "string", "RFC 3339 timestamp; defaults to the last import from this remote"),
+            TASK_ID,
+        ],
+        true,
+    ),
+    command("get_live_events_status", "Show live updates status", "Remote", &[], false),
+    command("get_api_quota", "Show API quota", "Remote", &[REMOTE], true),
+    // Account
+    command("start_device_login", "Sign in to remote", "Account", &[REMOTE], true),
+    command("get_auth_status", "Show sign-in status", "Account", &[REMOTE], false),
+    command("logout", "Sign out of remote", "Account", &[REMOTE], true),
+    // Integrations
+    command("get_local_api_info", "Show local API info", "Integrations", &[], true),
+    command("rotate_local_api_token", "Rotate local API token", "Integrations", &[], true),
+    command("take_pending_deep_links", "Take pending links", "Integrations", &[], false),
+    command(
+        "intake_files",
+        "Extract claims from files",
+        "Integrations",
+        &[arg("paths", "string[]", "Markdown, text or PDF files"), TASK_ID],
+        true,
+    ),
+    command("get_shortcut_status", "Show global shortcuts", "Integrations", &[], false),
+    command(
+        "check_shortcut_conflicts",
+        "Check shortcut conflicts",
+        "Integrations",
+        &[arg("shortcuts", "object", "Candidate shortcut bindings")],
+        false,
+    ),
+    command(
+        "save_session",
+        "Save session",
+        "Session",
+        &[arg("session", "object", "Open notes, last claim, terminal folders and filters")],
+        false,
+    ),
+    command("restore_session", "Restore session", "Session", &[], false),
+    command("run_diagnostics", "Run diagnostics", "Support", &[], true),
+    command(
+        "get_recent_logs",
+        "Show recent logs",
+        "Support",
+        &[
+            optional("level", "string", "Minimum level: \"error\", \"warn\", \"info\" or \"debug\""),
+            optional("limit", "number", "Most recent records to return (default 200)"),
+        ],
+        true,
+    ),
+    command(
+        "export_logs",
+        "Export logs bundle",
+        "Support",
+        &[arg("dest", "string", "Archive file, or a folder to create it in"), TASK_ID],
+        true,
+    ),
+    command("get_environment_info", "Show environment and version info", "Support", &[], true),
+    command("check_for_updates", "Check for updates", "Support", &[], true),
+    command("download_update", "Download update", "Support", &[TASK_ID], true),
+    command("install_update", "Install update and restart", "Support", &[], true),
+    command("get_update_status", "Show update status", "Support", &[], false),
+    command("list_crash_reports", "List crash reports", "Support", &[], true),
+    command(
+        "export_crash_report",
+        "Export crash report",
+        "Support",
+        &[optional("id", "string", "Crash report ID (default: the latest)"), PATH],
+        true,
+    ),
+    command("list_tasks", "Show running tasks", "Support", &[], true),
+    command(
+        "cancel_task",
+        "Cancel task",
+        "Support",
+        &[arg("taskId", "string", "Id of a running task")],
+        false,
+    ),
+    command("get_task_queue", "Show verification queue", "Support", &[], true),
+    command(
+        "reprioritize_queue_item",
+        "Reprioritize queued verification",
+        "Support",
+        &[
+            arg("id", "string", "Queue item ID"),
+            arg("priority", "number", "-100 to 100; higher runs first"),
+        ],
+        false,
+    ),
+    command(
+        "set_queue_item_paused",
+        "Pause or resume queued verification",
+        "Support",
+        &[
+            arg("id", "string", "Queue item ID"),
+            arg("paused", "boolean", "Whether the item is held back"),
+        ],
+        false,
+    ),
+    command(
+        "drop_queue_item",
+        "Drop queued verification",
+        "Support",
+        &[arg("id", "string", "Queue item ID")],
+        false,
+    ),
+    // Search
+    command(
+        "global_search",
+        "Search everything",
+        "Search",
+        &[
+            arg("query", "string", "Words to find in claims, notes and the audit trail"),
+            optional("limit", "number", "Most results to return (default 30)"),
+        ],
+        true,
+    ),
+    // Governance
+    command(
+        "governance_verify",
+        "Verify claim",
+        "Governance",
+        &[
+            arg("claim", "string", "Statement to verify"),
+            arg("domain", "string", "Knowledge domain"),
+            arg("riskProfile", "string", "\"low\", \"medium\" or \"high\""),
+            REMOTE,
+            optional("notePath", "string", "Note the claim is from; fills an empty domain or risk profile"),
+        ],
+        true,
+    ),
+    command(
+        "governance_verify_batch",
+        "Verify claims in batch",
+        "Governance",
+        &[
+            arg("claims", "object", "List of { claim, domain, note_path? }"),
+            arg("riskProfile", "string", "\"low\", \"medium\" or \"high\""),
+            REMOTE,
+            TASK_ID,
+        ],
+        false,
+    ),
+    command(
+        "verify_clipboard",
+        "Verify clipboard text",
+        "Governance",
+        &[
+            optional("domain", "string", "Knowledge domain; defaults to \"general\""),
+            optional("risk", "string", "Risk profile; defaults to the configured one"),
+        ],
+        true,
+    ),
+    command("list_recent_verifications", "Show recent verifications", "Governance", &[], true),
+    command(
+        "rerun_verification",
+        "Re-run verification",
+        "Governance",
+        &[arg("id", "string", "Recent verification ID")],
+        false,
+    ),
+    command("clear_recent_verifications", "Clear recent verifications", "Governance", &[], true),
+    command(
+        "list_claim_templates",
+        "List claim templates",
+        "Governance",
+        &[optional("domain", "string", "Only templates for this domain")],
+        true,
+    ),
+    command(
+        "save_claim_template",
+        "Save claim template",
+        "Governance",
+        &[arg("template", "object", "{ id, name, domain, text, risk_profile }; text uses {Variable} placeholders")],
+        false,
+    ),
+    command("delete_claim_template", "Delete claim template", "Governance", &[arg("id", "string", "Template ID")], false),
+    command(
+        "instantiate_claim_template",
+        "Fill in claim template",
+        "Governance",
+        &[
+            arg("id", "string", "Template ID"),
+            arg("vars", "object", "Value for each {Variable}"),
+            optional("verify", "boolean", "Verify the resulting claim right away"),
+        ],
+        false,
+    ),
+    command(
+        "verify_claim_local",
+        "Verify claim with local CLI",
+        "Governance",
+        &[arg("claim", "string", "Statement to verify"), arg("domain", "string", "Knowledge domain")],
+        false,
+    ),
+    // Truth Repository
+    command("list_claims", "List claims", "Truth Repository", &[ONTOLOGICAL_TYPE], true),
+    command(
+        "stream_claims",
+        "Stream claims",
+        "Truth Repository",
+        &[
+            TASK_ID,
+            optional("chunkSize", "number", "Claims per claims://chunk event (default 100)"),
+            ONTOLOGICAL_TYPE,
+        ],
+        false,
+    ),
+    command("get_claim", "Open claim", "Truth Repository", &[arg("hash", "string", "Claim hash")], true),
+    command(
+        "query_claims",
+        "Filter claims",
+        "Truth Repository",
+        &[arg(
+            "query",
+            "object",
+            "{ text, domain, state, ontological_type, min_confidence, max_confidence, last_days, since, until }",
+        )],
+        false,
+    ),
+    command(
+        "save_filter",
+        "Save claim filter",
+        "Truth Repository",
+        &[
+            arg("name", "string", "Filter name; an existing filter with this name is replaced"),
+            arg("query", "object", "Same fields as query_claims"),
+        ],
+        true,
+    ),
+    command("list_saved_filters", "List saved claim filters", "Truth Repository", &[], true),
+    command(
+        "validate_claim",
+        "Validate claim object",
+        "Truth Repository",
+        &[arg("json", "object", "Claim object as stored")],
+        false,
+    ),
+    command(
+        "delete_filter",
+        "Delete saved claim filter",
+        "Truth Repository",
+        &[arg("id", "string", "Saved filter ID")],
+        true,
+    ),
+    command(
+        "get_truth_status",
+        "Show repository status",
+        "Truth Repository",
+        &[optional("refresh", "boolean", "Recount claim objects instead of using the cached count")],
+        true,
+    ),
+    command(
+        "run_truthgit_command",
+        "Run truthgit command",
+        "Truth Repository",
+        &[arg("args", "string[]", "Arguments after `truthgit`")],
+        false,
+    ),
+    command("list_verifications", "List verifications", "Truth Repository", &[], true),
+    command(
+        "diff_claims",
+        "Compare claims",
+        "Truth Repository",
+        &[arg("hashA", "string", "First claim hash"), arg("hashB", "string", "Second claim hash")],
+        true,
+    ),
+    command(
+        "add_claim_source",
+        "Add claim source",
+        "Truth Repository",
+        &[
+            arg("hash", "string", "Claim hash"),
+            arg("source", "object", "{ url, title?, accessed? (YYYY-MM-DD), archived_hash? }"),
+        ],
+        false,
+    ),
+    command(
+        "remove_claim_source",
+        "Remove claim source",
+        "Truth Repository",
+        &[arg("hash", "string", "Claim hash"), arg("url", "string", "Source URL")],
+        false,
+    ),
+    command("list_claim_sources", "List claim sources", "Truth Repository", &[arg("hash", "string", "Claim hash")], false),
+    command("unsourced_claims_report", "Show claims without sources", "Truth Repository", &[], true),
+    command("archive_url", "Archive web page", "Truth Repository", &[arg("url", "string", "http(s) page to snapshot")], true),
+    command(
+        "get_archived_url",
+        "Open archived page",
+        "Truth Repository",
+        &[arg("hash", "string", "Snapshot hash returned by archive_url")],
+        false,
+    ),
+    // Reviews
+    command(
+        "set_claim_review_state",
+        "Set claim review state",
+        "Reviews",
+        &[
+            arg("hash", "string", "Claim hash"),
+            arg("state", "string", "\"draft\", \"in-review\", \"approved\" or \"retired\""),
+            optional("note", "string", "Reason recorded with the transition"),
+        ],
+        false,
+    ),
+    command("get_claim_review", "Show claim review", "Reviews", &[arg("hash", "string", "Claim hash")], false),
+    command(
+        "list_claims_by_review_state",
+        "List claims by review state",
+        "Reviews",
+        &[arg("state", "string", "\"draft\", \"in-review\", \"approved\" or \"retired\"")],
+        true,
+    ),
+    command(
+        "request_approval",
+        "Request claim approval",
+        "Reviews",
+        &[
+            arg("hash", "string", "Claim hash"),
+            optional("note", "string", "Message for the second reviewer"),
+        ],
+        false,
+    ),
+    command(
+        "approve",
+        "Approve claim",
+        "Reviews",
+        &[
+            arg("hash", "string", "Claim hash"),
+            optional("note", "string", "Reason recorded with the approval"),
+        ],
+        false,
+    ),
+    command("list_pending_approvals", "List pending approvals", "Reviews", &[], true),
+    // Watchlist
+    command("pin_claim", "Pin claim", "Watchlist", &[arg("hash", "string", "Claim hash")], false),
+    command("unpin_claim", "Unpin claim", "Watchlist", &[arg("hash", "string", "Claim hash")], false),
+    command("list_watchlist", "Show pinned claims", "Watchlist", &[], true),
+    command("recheck_watchlist", "Re-check pinned claims", "Watchlist", &[TASK_ID], true),
+    // Feeds
+    command(
+        "add_feed_source",
+        "Watch feed or page",
+        "Feeds",
+        &[arg("url", "string", "RSS/Atom feed or web page URL")],
+        false,
+    ),
+    command("remove_feed_source", "Stop watching source", "Feeds", &[arg("id", "string", "Source id")], false),
+    command("list_feed_sources", "Show watched sources", "Feeds", &[], true),
+    command("check_feed_sources", "Check watched sources now", "Feeds", &[TASK_ID], true),
+    command("get_inbox", "Show claim inbox", "Feeds", &[], true),
+    command("dismiss_inbox_item", "Dismiss inbox claim", "Feeds", &[arg("id", "string", "Inbox item id")], false),
+    // Edit
+    command(
+        "list_changes",
+        "Show recent changes",
+        "Edit",
+        &[optional("limit", "number", "Most recent changes to return (default 50)")],
+        true,
+    ),
+    command("undo_last_change", "Undo last change", "Edit", &[], true),
+    // Audit
+    command("get_audit_trail", "Show audit trail", "Audit", &[], true),
+    command("add_audit_entry", "Add audit entry", "Audit", &[arg("entry", "object", "Audit entry")], false),
+    command("apply_retention", "Archive expired audit entries", "Audit", &[], true),
+    command("list_archives", "Show retention archives", "Audit", &[], true),
+    command(
+        "restore_from_archive",
+        "Restore audit entries from archive",
+        "Audit",
+        &[arg("fileName", "string", "Archive file name")],
+        false,
+    ),
+    command("generate_receipt", "Generate signed receipt", "Audit", &[arg("auditId", "string", "Audit entry ID")], false),
+    command(
+        "verify_receipt",
+        "Verify receipt",
+        "Audit",
+        &[arg("receipt", "string", "Receipt text (tgr1.… or JSON)")],
+        false,
+    ),
+    command(
+        "attach_evidence",
+        "Attach evidence to verification",
+        "Audit",
+        &[
+            arg("auditId", "string", "Audit entry ID"),
+            arg("fileOrUrl", "string", "Path to a file, or an http(s) URL"),
+        ],
+        false,
+    ),
+    command("list_evidence", "List evidence", "Audit", &[arg("auditId", "string", "Audit entry ID")], false),
+    command("remove_evidence", "Remove evidence", "Audit", &[arg("id", "string", "Evidence ID")], false),
+    command("list_claim_evidence", "List claim evidence", "Audit", &[arg("hash", "string", "Claim hash")], false),
+    command("get_claim_timeline", "Show claim timeline", "Audit", &[arg("hash", "string", "Claim hash")], false),
+    command(
+        "compare_repo_snapshots",
+        "Compare repo between dates",
+        "Audit",
+        &[
+            arg("dateA", "string", "YYYY-MM-DD (end of day) or RFC 3339"),
+            arg("dateB", "string", "YYYY-MM-DD (end of day) or RFC 3339"),
+        ],
+        false,
+    ),
+    command(
+        "generate_digest",
+        "Generate digest",
+        "Audit",
+        &[arg("period", "string", "day, week or month")],
+        false,
+    ),
+    command(
+        "get_process_metrics",
+        "Show process metrics",
+        "Audit",
+        &[optional("range", "object", "{from, to}: YYYY-MM-DD or RFC 3339; open-ended by default")],
+        true,
+    ),
+    command(
+        "get_activity_heatmap",
+        "Show activity heatmap",
+        "Audit",
+        &[optional("range", "object", "{from, to}: YYYY-MM-DD or RFC 3339; the past year by default")],
+        false,
+    ),
+    command(
+        "export_attestation",
+        "Export in-toto attestation",
+        "Audit",
+        &[
+            arg("hash", "string", "Claim hash"),
+            PATH,
+            optional("sign", "boolean", "Wrap in a signed DSSE envelope (default true)"),
+        ],
+        true,
+    ),
+    command(
+        "generate_report",
+        "Generate governance report",
+        "Audit",
+        &[
+            optional("filter", "object", "{ title, domain, verdict, since, until }"),
+            arg("format", "string", "\"html\" or \"pdf\""),
+            PATH,
+            optional("template", "string", "HTML template file; defaults to the customized or built-in one"),
+        ],
+        true,
+    ),
+    command(
+        "export_verifications",
+        "Export verifications",
+        "Audit",
+        &[
+            optional("filter", "object", "{ domain, verdict, since, until }"),
+            PATH,
+            optional("columns", "string[]", "claim, claim_hash, domain, status, confidence, timestamp, audit_ref, hash, validators"),
+            optional("format", "string", "\"csv\" or \"xlsx\"; defaults to the file extension"),
+        ],
+        true,
+    ),
+    // Knowledge Base
+    command("get_vault_status", "Show vault status", "Knowledge Base", &[], false),
+    command(
+        "list_vault_directory",
+        "Browse vault",
+        "Knowledge Base",
+        &[optional("relativePath", "string", "Folder inside the vault")],
+        false,
+    ),
+    command(
+        "read_note",
+        "Open note",
+        "Knowledge Base",
+        &[arg("relativePath", "string", "Note path inside the vault")],
+        true,
+    ),
+    command("search_notes", "Search notes", "Knowledge Base", &[arg("query", "string", "Search text")], true),
+    command(
+        "resolve_note",
+        "Find note by name",
+        "Knowledge Base",
+        &[
+            arg("name", "string", "Note name, alias or wikilink target"),
+            optional("limit", "number", "Most matches to return (default 10)"),
+        ],
+        true,
+    ),
+    command("get_coverage_report", "Show note verification coverage", "Knowledge Base", &[], true),
+    command(
+        "get_folder_defaults",
+        "Show folder defaults",
+        "Knowledge Base",
+        &[arg("path", "string", "Note or folder, relative to the vault")],
+        false,
+    ),
+    command(
+        "set_folder_defaults",
+        "Set folder defaults",
+        "Knowledge Base",
+        &[
+            arg("folder", "string", "Folder relative to the vault (\"\" for the root)"),
+            arg("defaults", "object", "{ domain?, risk_profile? }; empty removes .truthgit.toml"),
+        ],
+        false,
+    ),
+    command(
+        "annotate_note_with_result",
+        "Write verification result into note",
+        "Knowledge Base",
+        &[
+            arg("path", "string", "Note path relative to the vault"),
+            arg("auditId", "string", "Audit entry of the verification"),
+            arg("style", "string", "\"callout\" or \"frontmatter\""),
+        ],
+        false,
+    ),
+    command(
+        "export_annotated_notes",
+        "Export notes with verification badges",
+        "Knowledge Base",
+        &[
+            arg("folder", "string", "Folder relative to the vault (\"\" for the whole vault)"),
+            arg("dest", "string", "File to write"),
+            optional("format", "string", "\"html\" or \"pdf\" (default: from the file extension)"),
+        ],
+        false,
+    ),
+    command(
+        "quick_capture",
+        "Capture text to inbox note",
+        "Knowledge Base",
+        &[
+            arg("text", "string", "Text to capture"),
+            optional("target", "string", "Note relative to the vault; defaults to the inbox note"),
+            optional("extract", "boolean", "Offer claims in the text for verification (default: from settings)"),
+        ],
+        true,
+    ),
+    command("get_encrypted_notes_status", "Show encrypted notes status", "Knowledge Base", &[], false),
+    command(
+        "unlock_encrypted_notes",
+        "Unlock encrypted notes",
+        "Knowledge Base",
+        &[arg("secret", "string", "Passphrase or age identity (AGE-SECRET-KEY-...)")],
+        true,
+    ),
+    command("lock_encrypted_notes", "Lock encrypted notes", "Knowledge Base", &[], true),
+    command(
+        "save_encrypted_note",
+        "Save encrypted note",
+        "Knowledge Base",
+        &[
+            arg("relativePath", "string", "Note path in the vault, ending in .age"),
+            arg("content", "string", "Note text"),
+        ],
+        false,
+    ),
+    // Terminal
+    command("check_command_safety", "Check command safety", "Terminal", &[arg("command", "string", "Shell command")], false),
+    command(
+        "execute_shell",
+        "Run shell command",
+        "Terminal",
+        &[arg("command", "string", "Shell command"), optional("cwd", "string", "Working directory")],
+        false,
+    ),
+    command("get_shell_suggestions", "Suggest shell commands", "Terminal", &[arg("prefix", "string", "Typed prefix")], false),
+    // Registry
+    command("list_app_commands", "List commands", "Registry", &[], false),
+];
+
+#[tauri::command]
+pub async fn list_app_commands() -> Result<Vec<AppCommand>, String> {
+    Ok(APP_COMMANDS.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Command names registered in `generate_handler!`, read from lib.rs
+    fn registered_commands() -> Vec<String> {
+        let source = include_str!("lib.rs");
+        let start = source.find("generate_handler![").expect("handler list") + "generate_handler![".len();
+        let end = start + source[start..].find(']').expect("end of handler list");
+        source[start..end]
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or("").trim().trim_end_matches(','))
+            .filter(|name| !name.is_empty())
+            .map(|name| name.rsplit("::").next().unwrap_or(name).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_matches_handler_list() {
+        let registered = registered_commands();
+        for name in &registered {
+            assert!(APP_COMMANDS.iter().any(|c| c.id == name), "{} is not in APP_COMMANDS", name);
+        }
+        for command in APP_COMMANDS {
+            assert!(registered.iter().any(|r| r == command.id), "{} is not registered", command.id);
+        }
+        assert_eq!(registered.len(), APP_COMMANDS.len());
+    }
+
+    #[test]
+    fn test_argument_names_are_camel_case() {
+        for command in APP_COMMANDS {
+            for arg in command.args {
+                assert!(!arg.name.contains('_'), "{}: {} should be camelCase", command.id, arg.name);
+            }
+        }
+    }
+}
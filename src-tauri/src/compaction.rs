@@ -0,0 +1,374 @@
+// ==================== COMPACTION ====================
+//
+// Housekeeping for a truth repo that has been in use for a while:
+// - objects stored with another codec than `compression.objects` are
+//   recompressed with it (only replaced once the new copy decodes to the
+//   same bytes, and sealed again if they were sealed);
+// - temp files left by interrupted writes are removed, along with empty
+//   objects/<kind>/<xx> directories;
+// - the claim index is rebuilt from the objects.
+// Objects stay one file each: the `truthgit` CLI and remotes read them by
+// path, so packing them into a single file would cut both off. The app keeps
+// its stores in JSON files rather than a database, so there is nothing to
+// vacuum beyond the temp files. `compact_repo` runs it on demand; with
+// `compaction.interval_days` set it also runs in the background once the
+// last compaction (recorded in compaction.json in the repo) is that old.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::compression::{self, Codec, Encoder};
+use crate::json_store::{self, load_json, save_json};
+use crate::repo_crypto::Binding;
+use crate::tasks::{self, TaskHandle};
+use crate::truth_store::{self, ObjectKind, StoredEntry, TruthStore};
+use crate::{index, mock, repo_crypto, repo_state, run_blocking, SETTINGS};
+
+const STATUS_FILE: &str = "compaction.json";
+const SCHEDULER_TICK: Duration = Duration::from_secs(6 * 60 * 60);
+/// Let startup indexing finish before the first scheduled pass
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(10 * 60);
+/// Younger temp files may belong to a write still in progress
+const STALE_TEMP_AGE: Duration = Duration::from_secs(60 * 60);
+/// Larger objects are left as they are
+const MAX_REPACK_SIZE: usize = 64 * 1024 * 1024;
+const PROGRESS_EVERY: usize = 500;
+
+/// Held while a compaction runs, so a scheduled one can't overlap a manual one
+static COMPACT_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompactionSettings {
+    /// Compact in the background once the last compaction is this many days
+    /// old; 0 only compacts on demand
+    pub interval_days: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompactionReport {
+    /// RFC 3339
+    pub started_at: String,
+    pub duration_ms: u64,
+    /// Objects looked at
+    pub objects: usize,
+    /// Objects recompressed with the configured codec
+    pub repacked: usize,
+    /// Objects that couldn't be read (locked, corrupt or too large)
+    pub skipped: usize,
+    pub temp_files_removed: usize,
+    pub empty_dirs_removed: usize,
+    pub claims_indexed: usize,
+    /// Size of the repo's files before and after
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+fn repo_size(root: &Path) -> u64 {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Temp file names used by `json_store::write_atomic`, `TruthStore::write_raw`
+/// and `TruthStore::set_head`
+fn is_temp_name(name: &str) -> bool {
+    name == "HEAD.tmp" || (name.starts_with('.') && (name.ends_with(".tmp") || name.contains(".tmp-")))
+}
+
+fn is_stale(path: &Path, now: SystemTime) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age >= STALE_TEMP_AGE)
+}
+
+/// Where temp files are looked for, with how deep: anywhere in the repo,
+/// and directly in the app's config and cache directories
+fn temp_dirs(root: &Path) -> Vec<(PathBuf, usize)> {
+    vec![
+        (root.to_path_buf(), usize::MAX),
+        (json_store::config_dir(), 1),
+        (json_store::cache_path(""), 1),
+    ]
+}
+
+fn remove_stale_temps(dirs: &[(PathBuf, usize)], now: SystemTime) -> usize {
+    let mut removed = 0;
+    for (dir, depth) in dirs {
+        let stale: Vec<PathBuf> = WalkDir::new(dir)
+            .max_depth(*depth)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && is_temp_name(&e.file_name().to_string_lossy()))
+            .map(|e| e.into_path())
+            .filter(|path| is_stale(path, now))
+            .collect();
+        for path in stale {
+            match fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => log::warn!("Failed to remove temp file {}: {}", path.display(), e),
+            }
+        }
+    }
+    removed
+}
+
+/// Rewrite one object with `codec`. Returns false if it already uses it.
+fn repack_object(root: &Path, store: &TruthStore, entry: &StoredEntry, codec: Codec) -> Result<bool, String> {
+    let raw = fs::read(&entry.path).map_err(|e| format!("Failed to read: {}", e))?;
+    let sealed = repo_crypto::is_sealed(&raw);
+    let binding = Binding::Object(entry.kind, &entry.id);
+    let compressed = if sealed { repo_crypto::open(root, binding, &raw)? } else { raw };
+    if compression::codec_of(&compressed) == codec {
+        return Ok(false);
+    }
+    let body = truth_store::inflate(&compressed, MAX_REPACK_SIZE).map_err(|e| e.to_string())?;
+
+    let compress_err = |e: std::io::Error| format!("Failed to compress: {}", e);
+    let mut encoder = Encoder::object(codec, Vec::new()).map_err(compress_err)?;
+    encoder.write_all(&body).map_err(compress_err)?;
+    let recompressed = encoder.finish().map_err(compress_err)?;
+    if truth_store::inflate(&recompressed, MAX_REPACK_SIZE).ok().as_ref() != Some(&body) {
+        return Err("Recompressed copy doesn't match the original".to_string());
+    }
+
+    let bytes = if sealed {
+        let resealed = repo_crypto::seal(root, binding, recompressed)?;
+        if !repo_crypto::is_sealed(&resealed) {
+            return Err("Sealed object in a repo that isn't encrypted".to_string());
+        }
+        resealed
+    } else {
+        recompressed
+    };
+    store.write_raw(entry.kind, &entry.id, &bytes)?;
+    Ok(true)
+}
+
+fn repack(root: &Path, codec: Codec, task: &TaskHandle, report: &mut CompactionReport) -> Result<(), String> {
+    let store = TruthStore::new(root);
+    let entries: Vec<StoredEntry> = [ObjectKind::Claim, ObjectKind::Verification, ObjectKind::Evidence]
+        .into_iter()
+        .flat_map(|kind| store.iter(kind))
+        .collect();
+    let total = entries.len();
+    for (i, entry) in entries.iter().enumerate() {
+        if i % PROGRESS_EVERY == 0 {
+            task.check_cancelled()?;
+            task.progress(
+                i as u64,
+                total as u64,
+                format!("Repacking objects ({} of {})", i, total),
+            );
+        }
+        report.objects += 1;
+        match repack_object(root, &store, entry, codec) {
+            Ok(true) => report.repacked += 1,
+            Ok(false) => {}
+            Err(e) => {
+                log::warn!("Not repacking {}: {}", entry.object_name(), e);
+                report.skipped += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Remove objects/<kind>/<xx> directories left empty; non-empty ones are
+/// refused by the file system
+fn remove_empty_dirs(store: &TruthStore) -> usize {
+    [ObjectKind::Claim, ObjectKind::Verification, ObjectKind::Evidence]
+        .into_iter()
+        .filter_map(|kind| fs::read_dir(store.kind_dir(kind)).ok())
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().len() == 2 && e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| fs::remove_dir(e.path()).is_ok())
+        .count()
+}
+
+fn compact(root: &Path, codec: Codec, task: &TaskHandle) -> Result<CompactionReport, String> {
+    let store = TruthStore::new(root);
+    if !store.is_repo() {
+        return Err("The active truth repository has no objects directory".to_string());
+    }
+    let _guard = COMPACT_LOCK
+        .try_lock()
+        .map_err(|_| "A compaction is already running".to_string())?;
+    let started = Instant::now();
+    let mut report = CompactionReport {
+        started_at: Utc::now().to_rfc3339(),
+        bytes_before: repo_size(root),
+        ..Default::default()
+    };
+
+    report.temp_files_removed = remove_stale_temps(&temp_dirs(root), SystemTime::now());
+    repack(root, codec, task, &mut report)?;
+    report.empty_dirs_removed = remove_empty_dirs(&store);
+
+    task.progress(0, 0, "Rebuilding the claim index");
+    report.claims_indexed = index::rebuild_claim_index(root, &mut |_, _| !task.is_cancelled())
+        .ok_or_else(|| tasks::TASK_CANCELLED_ERROR.to_string())?;
+
+    report.bytes_after = repo_size(root);
+    report.reclaimed_bytes = report.bytes_before.saturating_sub(report.bytes_after);
+    report.duration_ms = started.elapsed().as_millis() as u64;
+    save_json(&root.join(STATUS_FILE), &report)?;
+    Ok(report)
+}
+
+fn last_compaction(root: &Path) -> Result<Option<CompactionReport>, String> {
+    let path = root.join(STATUS_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    load_json(&path).map(Some)
+}
+
+/// Whether the last compaction is `interval_days` old, or there was none
+fn is_due(last: Option<&CompactionReport>, interval_days: u32, now: DateTime<Utc>) -> bool {
+    let Some(last) = last.and_then(|r| DateTime::parse_from_rfc3339(&r.started_at).ok()) else {
+        return true;
+    };
+    now.signed_duration_since(last) >= chrono::Duration::days(i64::from(interval_days))
+}
+
+async fn run_compaction(app: &AppHandle, task_id: Option<String>) -> Result<CompactionReport, String> {
+    let root = repo_state::ready_path()?;
+    let codec = compression::object_codec();
+    let report = tasks::run(app, task_id, "compaction", "Compacting repository", |task| async move {
+        tokio::task::spawn_blocking(move || compact(&root, codec, &task))
+            .await
+            .map_err(|e| format!("Compaction task failed: {}", e))?
+    })
+    .await?;
+    log::info!(
+        "Compacted truth repository: {} of {} objects repacked, {} temp files removed, {} bytes reclaimed",
+        report.repacked,
+        report.objects,
+        report.temp_files_removed,
+        report.reclaimed_bytes
+    );
+    Ok(report)
+}
+
+/// Compact the repo in the background whenever `compaction.interval_days`
+/// has passed since the last compaction
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_RUN_DELAY).await;
+        loop {
+            let interval_days = SETTINGS.read().map(|s| s.compaction.interval_days).unwrap_or(0);
+            if interval_days > 0 && !mock::is_active() {
+                let due = run_blocking(move || {
+                    let root = repo_state::ready_path()?;
+                    Ok(is_due(last_compaction(&root)?.as_ref(), interval_days, Utc::now()))
+                })
+                .await;
+                match due {
+                    Ok(true) => {
+                        if let Err(e) = run_compaction(&app, None).await {
+                            log::warn!("Scheduled compaction failed: {}", e);
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => log::warn!("Scheduled compaction skipped: {}", e),
+                }
+            }
+            tokio::time::sleep(SCHEDULER_TICK).await;
+        }
+    });
+}
+
+/// Recompress objects with the configured codec, clear leftover temp files
+/// and rebuild the claim index, reporting the space reclaimed
+#[tauri::command]
+pub async fn compact_repo(app: AppHandle, task_id: Option<String>) -> Result<CompactionReport, String> {
+    if mock::is_active() {
+        return Err("Compaction is disabled in mock mode".to_string());
+    }
+    run_compaction(&app, task_id).await
+}
+
+/// The report of the last compaction of the active repo, if any
+#[tauri::command]
+pub async fn get_last_compaction() -> Result<Option<CompactionReport>, String> {
+    if mock::is_active() {
+        return Ok(None);
+    }
+    run_blocking(|| {
+        let root = repo_state::ready_path()?;
+        last_compaction(&root)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use crate::truth_store::ObjectId;
+
+    #[test]
+    fn test_compact_repacks_and_cleans_up() {
+        let tmp = TempDir::new("truthgit-compact");
+        let root = tmp.path();
+        let store = TruthStore::new(root);
+        store.create().unwrap();
+        let id = ObjectId::parse("ab12cd34").unwrap();
+        let body = br#"{"$hash":"ab12cd34","content":"Water boils at 100 C at sea level"}"#.repeat(50);
+        let mut encoder = Encoder::object(Codec::Zlib, Vec::new()).unwrap();
+        encoder.write_all(&body).unwrap();
+        store
+            .write_raw(ObjectKind::Claim, &id, &encoder.finish().unwrap())
+            .unwrap();
+        fs::create_dir_all(store.kind_dir(ObjectKind::Verification).join("ef")).unwrap();
+        let temp = store.kind_dir(ObjectKind::Claim).join("ab").join(".12cd34.tmp-1");
+        fs::write(&temp, b"partial").unwrap();
+
+        // A fresh temp file may still be in use
+        let dirs = [(root.to_path_buf(), usize::MAX)];
+        assert_eq!(remove_stale_temps(&dirs, SystemTime::now()), 0);
+        assert_eq!(remove_stale_temps(&dirs, SystemTime::now() + STALE_TEMP_AGE), 1);
+        assert!(!temp.exists());
+        assert!(!is_temp_name("ab12cd") && is_temp_name(".claims.json.0f1e.tmp"));
+
+        let mut report = CompactionReport::default();
+        repack(root, Codec::Zstd, &TaskHandle::detached(), &mut report).unwrap();
+        assert_eq!((report.objects, report.repacked, report.skipped), (1, 1, 0));
+        let raw = store.read_raw(ObjectKind::Claim, &id).unwrap();
+        assert_eq!(compression::codec_of(&raw), Codec::Zstd);
+        assert_eq!(truth_store::inflate(&raw, MAX_REPACK_SIZE).unwrap(), body);
+        // Already in the configured codec: nothing to do
+        let entry = store.iter(ObjectKind::Claim).next().unwrap();
+        assert!(!repack_object(root, &store, &entry, Codec::Zstd).unwrap());
+
+        assert_eq!(remove_empty_dirs(&store), 1);
+        assert!(store.contains(ObjectKind::Claim, &id));
+
+        let now = Utc::now();
+        let last = CompactionReport {
+            started_at: (now - chrono::Duration::days(3)).to_rfc3339(),
+            ..Default::default()
+        };
+        assert!(is_due(None, 7, now));
+        assert!(!is_due(Some(&last), 7, now));
+        assert!(is_due(Some(&last), 2, now));
+    }
+}
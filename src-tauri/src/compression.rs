@@ -0,0 +1,158 @@
+// ==================== COMPRESSION ====================
+//
+// Objects and backups were always deflate: zlib for objects, gzip for backup
+// archives. Zstandard compresses large repos noticeably smaller and inflates
+// faster, so new objects and backups can use it instead (settings
+// `compression.objects` and `compression.backups`). Reading never depends on
+// the setting: zstd data is recognized by its frame magic, anything else is
+// read as before, so repos and backups mixing both keep working. The
+// `truthgit` CLI and remotes receive objects as they are stored and need zstd
+// support to read the ones written with it.
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Read, Write};
+
+use crate::SETTINGS;
+
+/// Every zstd frame starts with this
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Codec {
+    /// zlib for objects, gzip for backups
+    #[default]
+    Zlib,
+    Zstd,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionSettings {
+    /// Codec for objects written from now on (evidence attachments)
+    pub objects: Codec,
+    /// Codec for new backup archives
+    pub backups: Codec,
+}
+
+pub fn object_codec() -> Codec {
+    SETTINGS.read().map(|s| s.compression.objects).unwrap_or_default()
+}
+
+pub fn backup_codec() -> Codec {
+    SETTINGS.read().map(|s| s.compression.backups).unwrap_or_default()
+}
+
+/// A compressing writer; `finish` completes the stream and returns `out`
+pub enum Encoder<W: Write> {
+    Zlib(ZlibEncoder<W>),
+    Gzip(GzEncoder<W>),
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+impl<W: Write> Encoder<W> {
+    /// Encoder for an object body
+    pub fn object(codec: Codec, out: W) -> io::Result<Self> {
+        Ok(match codec {
+            Codec::Zlib => Self::Zlib(ZlibEncoder::new(out, Compression::default())),
+            Codec::Zstd => Self::Zstd(zstd::stream::write::Encoder::new(out, zstd::DEFAULT_COMPRESSION_LEVEL)?),
+        })
+    }
+
+    /// Encoder for a backup archive
+    pub fn archive(codec: Codec, out: W) -> io::Result<Self> {
+        Ok(match codec {
+            Codec::Zlib => Self::Gzip(GzEncoder::new(out, Compression::default())),
+            Codec::Zstd => Self::Zstd(zstd::stream::write::Encoder::new(out, zstd::DEFAULT_COMPRESSION_LEVEL)?),
+        })
+    }
+
+    pub fn finish(self) -> io::Result<W> {
+        match self {
+            Self::Zlib(e) => e.finish(),
+            Self::Gzip(e) => e.finish(),
+            Self::Zstd(e) => e.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Zlib(e) => e.write(buf),
+            Self::Gzip(e) => e.write(buf),
+            Self::Zstd(e) => e.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Zlib(e) => e.flush(),
+            Self::Gzip(e) => e.flush(),
+            Self::Zstd(e) => e.flush(),
+        }
+    }
+}
+
+fn is_zstd(bytes: &[u8]) -> bool {
+    bytes.starts_with(&ZSTD_MAGIC)
+}
+
+/// Codec a stored object was compressed with
+pub fn codec_of(compressed: &[u8]) -> Codec {
+    if is_zstd(compressed) {
+        Codec::Zstd
+    } else {
+        Codec::Zlib
+    }
+}
+
+/// Decoder for a stored object, zstd or zlib
+pub fn object_decoder(compressed: &[u8]) -> io::Result<Box<dyn Read + '_>> {
+    if is_zstd(compressed) {
+        Ok(Box::new(zstd::stream::read::Decoder::with_buffer(compressed)?))
+    } else {
+        Ok(Box::new(ZlibDecoder::new(compressed)))
+    }
+}
+
+/// Decoder for a backup archive stream, zstd or gzip
+pub fn archive_decoder<'a, R: BufRead + 'a>(mut reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    if is_zstd(reader.fill_buf()?) {
+        Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?))
+    } else {
+        Ok(Box::new(GzDecoder::new(reader)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_detects_codec() {
+        let body = b"claim 42\0{\"content\":\"Water boils at 100 C at sea level\"}".repeat(20);
+        for codec in [Codec::Zlib, Codec::Zstd] {
+            let mut encoder = Encoder::object(codec, Vec::new()).unwrap();
+            encoder.write_all(&body).unwrap();
+            let compressed = encoder.finish().unwrap();
+            assert_eq!(is_zstd(&compressed), codec == Codec::Zstd);
+            let mut decoded = Vec::new();
+            object_decoder(&compressed).unwrap().read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, body);
+
+            let mut encoder = Encoder::archive(codec, Vec::new()).unwrap();
+            encoder.write_all(&body).unwrap();
+            let compressed = encoder.finish().unwrap();
+            let mut decoded = Vec::new();
+            archive_decoder(&compressed[..])
+                .unwrap()
+                .read_to_end(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, body);
+        }
+    }
+}
@@ -0,0 +1,384 @@
+// ==================== CONSENT ====================
+//
+// The first time the app runs a shell program, reads a file outside the vault
+// and truth repo, or contacts a host it hasn't talked to before, the user is
+// asked first. Because the vault and truth repo are read without asking,
+// pointing either setting at a new folder asks too. The question is a native dialog rather than something in the
+// webview, so no script running there can answer it for the user. Operations
+// asking for the same permission wait on one dialog; no answer within two
+// minutes counts as a denial for them, and the dialog's answer still applies
+// to later ones. Grants are kept in permissions.json until revoked. Denials
+// only last for the session, so a background job that keeps retrying (live
+// events, scheduled syncs) doesn't keep prompting.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::sync::oneshot;
+
+use crate::identity::{self, Actor};
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::security_events::{self, SecurityEventKind};
+use crate::{get_truth_path, get_vault_path, run_blocking, AppSettings, SETTINGS};
+
+const CONSENT_TIMEOUT: Duration = Duration::from_secs(120);
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static PERMISSIONS_LOCK: StoreLock = StoreLock::new();
+/// Permissions with a dialog open, each with the operations waiting on it
+static PENDING: LazyLock<Mutex<HashMap<Permission, Vec<oneshot::Sender<bool>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+static DENIED: LazyLock<Mutex<HashSet<Permission>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionKind {
+    Shell,
+    ReadPath,
+    RemoteHost,
+    /// Use a folder as the vault or truth repo, which the app reads freely
+    TrustDir,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Permission {
+    pub kind: PermissionKind,
+    /// Program name, directory or host[:port]
+    pub scope: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Grant {
+    #[serde(flatten)]
+    pub permission: Permission,
+    pub granted_at: String,
+    #[serde(default)]
+    pub granted_by: Option<Actor>,
+}
+
+impl Permission {
+    fn describe(&self) -> String {
+        match self.kind {
+            PermissionKind::Shell => format!("Run the shell program \"{}\"", self.scope),
+            PermissionKind::ReadPath => format!("Read files in {}", self.scope),
+            PermissionKind::RemoteHost => format!("Connect to {}", self.scope),
+            PermissionKind::TrustDir => format!(
+                "Use {} as the vault or truth repository, reading anything in it without asking",
+                self.scope
+            ),
+        }
+    }
+
+    /// A grant for this permission also allows `wanted`: same program or
+    /// host, or a file anywhere under a granted directory
+    fn covers(&self, wanted: &Permission) -> bool {
+        self.kind == wanted.kind
+            && match self.kind {
+                PermissionKind::ReadPath => Path::new(&wanted.scope).starts_with(&self.scope),
+                _ => self.scope == wanted.scope,
+            }
+    }
+}
+
+fn permissions_path() -> PathBuf {
+    config_path("permissions.json")
+}
+
+fn load_grants() -> Result<Vec<Grant>, String> {
+    let _guard = PERMISSIONS_LOCK.lock(&permissions_path())?;
+    load_json(&permissions_path())
+}
+
+fn is_granted(permission: &Permission) -> Result<bool, String> {
+    Ok(load_grants()?.iter().any(|g| g.permission.covers(permission)))
+}
+
+fn record_grant(permission: Permission) -> Result<(), String> {
+    let path = permissions_path();
+    let _guard = PERMISSIONS_LOCK.lock(&path)?;
+    let mut grants: Vec<Grant> = load_json(&path)?;
+    grants.retain(|g| g.permission != permission);
+    log::info!("Granted permission: {}", permission.describe());
+    grants.push(Grant {
+        permission,
+        granted_at: chrono::Utc::now().to_rfc3339(),
+        granted_by: identity::current(),
+    });
+    save_json(&path, &grants)
+}
+
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+/// Wait until the user allows `permission`, asking them the first time
+pub async fn require(permission: Permission) -> Result<(), String> {
+    let denied = || format!("Permission denied: {}", permission.describe());
+    let check = permission.clone();
+    if run_blocking(move || is_granted(&check)).await? {
+        return Ok(());
+    }
+    if DENIED
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .contains(&permission)
+    {
+        return Err(denied());
+    }
+    let app = APP.get().ok_or_else(denied)?;
+
+    let (tx, rx) = oneshot::channel();
+    let first = {
+        let mut pending = PENDING.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let waiters = pending.entry(permission.clone()).or_default();
+        waiters.push(tx);
+        waiters.len() == 1
+    };
+    if first {
+        ask(app, permission.clone());
+    }
+
+    match tokio::time::timeout(CONSENT_TIMEOUT, rx).await {
+        Ok(Ok(true)) => Ok(()),
+        Ok(_) => Err(denied()),
+        Err(_) => {
+            let detail = format!("{} (no answer)", permission.describe());
+            security_events::record(SecurityEventKind::PermissionDenied, "consent", &detail);
+            Err(format!("{} (no answer)", denied()))
+        }
+    }
+}
+
+pub async fn require_shell(program: &str) -> Result<(), String> {
+    let name = Path::new(program)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| program.to_string());
+    require(Permission {
+        kind: PermissionKind::Shell,
+        scope: name,
+    })
+    .await
+}
+
+/// host[:port] of an http(s) URL; the port only when it isn't the default
+fn host_scope(url: &str) -> Result<String, String> {
+    let url = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+    let host = url.host_str().ok_or("URL has no host")?.to_ascii_lowercase();
+    Ok(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host,
+    })
+}
+
+/// Whether `url`'s host was allowed before, without asking; for checks that
+/// shouldn't prompt, like diagnostics
+pub fn is_host_allowed(url: &str) -> bool {
+    host_scope(url).is_ok_and(|scope| {
+        is_granted(&Permission {
+            kind: PermissionKind::RemoteHost,
+            scope,
+        })
+        .unwrap_or(false)
+    })
+}
+
+pub async fn require_host(url: &str) -> Result<(), String> {
+    require(Permission {
+        kind: PermissionKind::RemoteHost,
+        scope: host_scope(url)?,
+    })
+    .await
+}
+
+/// Directories the app reads without asking: the vault and the truth repo.
+/// Not the config directory, which holds the local API token and the grants.
+fn trusted_dirs() -> Vec<PathBuf> {
+    [get_vault_path(), get_truth_path()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.canonicalize().unwrap_or(dir))
+        .collect()
+}
+
+/// The directory a read of `path` needs permission for, or None if it is
+/// inside a trusted directory
+fn read_scope(path: &Path, trusted: &[PathBuf]) -> Option<String> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if trusted.iter().any(|dir| path.starts_with(dir)) {
+        return None;
+    }
+    let dir = if path.is_dir() {
+        path.as_path()
+    } else {
+        path.parent().unwrap_or(&path)
+    };
+    Some(dir.to_string_lossy().to_string())
+}
+
+pub async fn require_read(path: &Path) -> Result<(), String> {
+    match read_scope(path, &trusted_dirs()) {
+        Some(scope) => {
+            require(Permission {
+                kind: PermissionKind::ReadPath,
+                scope,
+            })
+            .await
+        }
+        None => Ok(()),
+    }
+}
+
+/// What `new` needs the user to allow: each of the vault and truth repo
+/// paths that differs from `current`
+fn path_changes(current: &AppSettings, new: &AppSettings) -> Vec<Permission> {
+    [
+        (&current.vault_path, &new.vault_path),
+        (&current.truth_repo_path, &new.truth_repo_path),
+    ]
+    .into_iter()
+    .filter(|(old, new)| old.trim() != new.trim() && !new.trim().is_empty())
+    .map(|(_, new)| {
+        let dir = PathBuf::from(new.trim());
+        Permission {
+            kind: PermissionKind::TrustDir,
+            scope: dir.canonicalize().unwrap_or(dir).to_string_lossy().to_string(),
+        }
+    })
+    .collect()
+}
+
+/// Ask before saving `new` if it moves the vault or truth repo. Call without
+/// holding the settings lock; `check_path_settings` enforces the answer.
+pub async fn require_path_settings(new: &AppSettings) -> Result<(), String> {
+    let current = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+    for permission in path_changes(&current, new) {
+        require(permission).await?;
+    }
+    Ok(())
+}
+
+/// Refuse `new` unless the user allowed each folder it moves the vault or
+/// truth repo to
+pub fn check_path_settings(current: &AppSettings, new: &AppSettings) -> Result<(), String> {
+    for permission in path_changes(current, new) {
+        if !is_granted(&permission)? {
+            return Err(format!("Permission needed: {}", permission.describe()));
+        }
+    }
+    Ok(())
+}
+
+/// Ask the user about `permission` in a native dialog and settle it with
+/// the answer
+fn ask(app: &AppHandle, permission: Permission) {
+    let message = format!(
+        "{}?\n\nAllowing is remembered until you revoke it in Settings; \
+        denying lasts until TruthGit Desktop restarts.",
+        permission.describe()
+    );
+    app.dialog()
+        .message(message)
+        .title("Allow access?")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom("Allow".to_string(), "Deny".to_string()))
+        .show(move |allow| {
+            tauri::async_runtime::spawn_blocking(move || answer(permission, allow));
+        });
+}
+
+/// Settle a permission for everything waiting on it. Allowing records a
+/// grant; denying lasts until the app restarts.
+fn answer(permission: Permission, allow: bool) {
+    if allow {
+        if let Err(e) = record_grant(permission.clone()) {
+            log::warn!("Failed to record permission: {}", e);
+        }
+    } else {
+        security_events::record(SecurityEventKind::PermissionDenied, "consent", &permission.describe());
+        if let Ok(mut denied) = DENIED.lock() {
+            denied.insert(permission.clone());
+        }
+    }
+    let waiters = PENDING
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.remove(&permission))
+        .unwrap_or_default();
+    for waiter in waiters {
+        let _ = waiter.send(allow);
+    }
+}
+
+#[tauri::command]
+pub async fn list_granted_permissions() -> Result<Vec<Grant>, String> {
+    run_blocking(load_grants).await
+}
+
+#[tauri::command]
+pub async fn revoke_permission(kind: PermissionKind, scope: String) -> Result<Vec<Grant>, String> {
+    run_blocking(move || {
+        let path = permissions_path();
+        let _guard = PERMISSIONS_LOCK.lock(&path)?;
+        let mut grants: Vec<Grant> = load_json(&path)?;
+        let before = grants.len();
+        grants.retain(|g| !(g.permission.kind == kind && g.permission.scope == scope));
+        if grants.len() == before {
+            return Err("No such permission".to_string());
+        }
+        save_json(&path, &grants)?;
+        log::info!("Revoked permission: {:?} {}", kind, scope);
+        Ok(grants)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_scopes() {
+        assert_eq!(host_scope("https://API.truthgit.io/v1").unwrap(), "api.truthgit.io");
+        assert_eq!(host_scope("http://localhost:8080").unwrap(), "localhost:8080");
+
+        let tmp = TempDir::new("truthgit-consent");
+        let root = tmp.path();
+        let vault = root.join("vault");
+        let outside = root.join("outside");
+        std::fs::create_dir_all(&vault).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(vault.join("a.md"), "").unwrap();
+        std::fs::write(outside.join("b.pdf"), "").unwrap();
+        let trusted = vec![vault.canonicalize().unwrap()];
+
+        assert_eq!(read_scope(&vault.join("a.md"), &trusted), None);
+        let scope = read_scope(&outside.join("b.pdf"), &trusted).unwrap();
+        let grant = Permission {
+            kind: PermissionKind::ReadPath,
+            scope: scope.clone(),
+        };
+        let nested = Permission {
+            kind: PermissionKind::ReadPath,
+            scope: format!("{}/sub", scope),
+        };
+        assert!(grant.covers(&nested));
+        assert!(!nested.covers(&grant));
+
+        let current = AppSettings::default();
+        assert!(path_changes(&current, &current).is_empty());
+        let moved = AppSettings {
+            vault_path: " / ".to_string(),
+            truth_repo_path: String::new(),
+            ..current.clone()
+        };
+        let changes = path_changes(&current, &moved);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].kind, changes[0].scope.as_str()), (PermissionKind::TrustDir, "/"));
+    }
+}
@@ -0,0 +1,222 @@
+// ==================== NOTE COVERAGE ====================
+//
+// How much of each vault note has been checked: candidate claims are pulled
+// from the note with the same heuristic as file intake, and a candidate
+// counts as verified when its text matches a verification, a verified claim
+// object or an audit entry (ignoring case, spacing and final punctuation).
+// The vault records no view counts, so a note's traffic is the number of
+// other notes linking to it; linked-to notes with nothing verified are
+// flagged as gaps and listed first. Encrypted notes aren't read.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::file_intake::extract_claims;
+use crate::note_links::{link_target, normalize};
+use crate::{
+    get_vault_path, mock, note_name, paths, read_audit_trail, read_claims, read_verifications, run_blocking,
+    MAX_VAULT_FILES,
+};
+
+/// Notes linked from at least this many others count as high-traffic
+const HIGH_TRAFFIC_LINKS: usize = 3;
+/// Unverified candidates returned per note, as examples
+const MAX_UNVERIFIED_SHOWN: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteCoverage {
+    /// Relative to the vault, with `/` separators
+    pub path: String,
+    pub name: String,
+    pub candidates: usize,
+    pub verified: usize,
+    /// verified / candidates, 0.0-1.0
+    pub coverage: f64,
+    /// Other notes linking here
+    pub inbound_links: usize,
+    /// High-traffic with no candidate verified
+    pub gap: bool,
+    /// The first few candidates still to verify, in note order
+    pub unverified: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CoverageReport {
+    /// Gaps first, then by coverage, least covered first
+    pub notes: Vec<NoteCoverage>,
+    pub notes_scanned: usize,
+    /// Notes read that had no candidate claims, and aren't listed
+    pub notes_without_claims: usize,
+    pub candidates: usize,
+    pub verified: usize,
+    /// The vault had more files than are scanned
+    pub truncated: bool,
+}
+
+/// Claim text as compared: normalized, without trailing punctuation
+pub fn claim_key(text: &str) -> String {
+    normalize(text.trim_end_matches(['.', '!', ';'])).trim().to_string()
+}
+
+/// Link targets in `content`, keyed like `normalize(note name)`
+fn linked_notes(content: &str) -> HashSet<String> {
+    let mut targets = HashSet::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else {
+            break;
+        };
+        let target = link_target(&rest[..end]);
+        let name = target.rsplit('/').next().unwrap_or(target);
+        if !name.is_empty() {
+            targets.insert(normalize(name));
+        }
+        rest = &rest[end + 2..];
+    }
+    targets
+}
+
+/// Texts of everything verified so far, as `claim_key`s
+fn verified_texts() -> Result<HashSet<String>, String> {
+    let verifications = read_verifications()?.verifications;
+    let verified_hashes: HashSet<&str> = verifications.iter().map(|v| v.claim_hash.as_str()).collect();
+    let mut texts: HashSet<String> = verifications.iter().map(|v| claim_key(&v.claim)).collect();
+    for claim in read_claims(None)?.claims {
+        let hash = claim.get("$hash").and_then(|h| h.as_str()).unwrap_or_default();
+        if verified_hashes.contains(hash) {
+            texts.insert(claim_key(
+                claim.get("content").and_then(|c| c.as_str()).unwrap_or_default(),
+            ));
+        }
+    }
+    if !mock::is_active() {
+        texts.extend(read_audit_trail()?.iter().map(|e| claim_key(&e.claim)));
+    }
+    texts.remove("");
+    Ok(texts)
+}
+
+fn build_report(vault: &Path, verified: &HashSet<String>) -> CoverageReport {
+    let mut report = CoverageReport::default();
+    let mut notes = Vec::new();
+    let mut inbound: HashMap<String, usize> = HashMap::new();
+    let mut files = 0;
+    for entry in WalkDir::new(vault)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        files += 1;
+        if files > MAX_VAULT_FILES {
+            report.truncated = true;
+            break;
+        }
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let name = note_name(path).unwrap_or_default();
+        report.notes_scanned += 1;
+        for target in linked_notes(&content) {
+            if target != normalize(&name) {
+                *inbound.entry(target).or_default() += 1;
+            }
+        }
+        let candidates = extract_claims(&content);
+        if candidates.is_empty() {
+            report.notes_without_claims += 1;
+            continue;
+        }
+        let (checked, unchecked): (Vec<String>, Vec<String>) =
+            candidates.into_iter().partition(|c| verified.contains(&claim_key(c)));
+        let total = checked.len() + unchecked.len();
+        notes.push(NoteCoverage {
+            path: paths::relative_to(vault, path).unwrap_or_default(),
+            name,
+            candidates: total,
+            verified: checked.len(),
+            coverage: checked.len() as f64 / total as f64,
+            inbound_links: 0,
+            gap: false,
+            unverified: unchecked.into_iter().take(MAX_UNVERIFIED_SHOWN).collect(),
+        });
+    }
+
+    for note in &mut notes {
+        note.inbound_links = inbound.get(&normalize(&note.name)).copied().unwrap_or(0);
+        note.gap = note.verified == 0 && note.inbound_links >= HIGH_TRAFFIC_LINKS;
+        report.candidates += note.candidates;
+        report.verified += note.verified;
+    }
+    notes.sort_by(|a, b| {
+        b.gap
+            .cmp(&a.gap)
+            .then(a.coverage.total_cmp(&b.coverage))
+            .then(b.inbound_links.cmp(&a.inbound_links))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    report.notes = notes;
+    report
+}
+
+/// Vault notes by the share of their candidate claims that are verified
+#[tauri::command]
+pub async fn get_coverage_report() -> Result<CoverageReport, String> {
+    run_blocking(|| {
+        let vault = get_vault_path()
+            .filter(|p| p.exists())
+            .ok_or_else(|| "Vault not found".to_string())?;
+        Ok(build_report(&vault, &verified_texts()?))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_coverage_report() {
+        let tmp = TempDir::new("truthgit-coverage");
+        let vault = tmp.path();
+        fs::create_dir_all(vault.join("Topics")).unwrap();
+        fs::write(
+            vault.join("Topics/Water.md"),
+            "Water boils at 100 degrees at sea level.\n\nIce is less dense than liquid water.\n",
+        )
+        .unwrap();
+        fs::write(vault.join("Sun.md"), "The sun is a main sequence star of type G.\n").unwrap();
+        for i in 0..3 {
+            fs::write(
+                vault.join(format!("Link {}.md", i)),
+                "See [[sun]] and [[Topics/Water#Boiling|water]].",
+            )
+            .unwrap();
+        }
+        fs::write(vault.join("Empty.md"), "# Heading only").unwrap();
+
+        let verified = HashSet::from([claim_key("water  boils at 100 degrees at sea level")]);
+        let report = build_report(vault, &verified);
+        assert_eq!((report.notes_scanned, report.notes_without_claims), (6, 4));
+        assert_eq!((report.candidates, report.verified), (3, 1));
+
+        let sun = &report.notes[0];
+        assert_eq!((sun.name.as_str(), sun.inbound_links, sun.gap), ("Sun", 3, true));
+        let water = &report.notes[1];
+        assert_eq!(
+            (water.path.as_str(), water.verified, water.coverage),
+            ("Topics/Water.md", 1, 0.5)
+        );
+        assert!(!water.gap);
+        assert_eq!(water.unverified, ["Ice is less dense than liquid water."]);
+    }
+}
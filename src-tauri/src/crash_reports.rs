@@ -0,0 +1,241 @@
+// ==================== CRASH REPORTS ====================
+//
+// A panic hook that writes what we know at the moment of a crash (message,
+// location, backtrace, the last log lines, app and OS versions, settings
+// with secrets removed) to crashes/ in the config directory before the
+// default hook runs. Everything is sanitized like error strings, so a report
+// can be exported with `export_crash_report` and attached to a bug report
+// as-is. Only the most recent reports are kept.
+
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::json_store::config_path;
+use crate::logging::{self, LogRecord};
+use crate::{run_blocking, sanitize_error, settings_bundle, SETTINGS};
+
+const MAX_CRASH_REPORTS: usize = 10;
+/// Log lines before the crash included in a report
+const RECENT_LOG_LINES: usize = 100;
+const MAX_REPORT_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Log directory, resolved at startup: the hook can't ask the app for it
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub at: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: String,
+    pub message: String,
+    /// file:line of the panic
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Newest first
+    pub recent_logs: Vec<LogRecord>,
+    /// Settings with secrets removed; None if they couldn't be read
+    pub settings: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CrashSummary {
+    pub id: String,
+    pub at: String,
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl From<CrashReport> for CrashSummary {
+    fn from(report: CrashReport) -> Self {
+        CrashSummary {
+            id: report.id,
+            at: report.at,
+            message: report.message,
+            location: report.location,
+        }
+    }
+}
+
+fn crash_dir() -> PathBuf {
+    config_path("crashes")
+}
+
+fn report_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("crash-{}.json", id))
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+fn build_report(info: &PanicHookInfo) -> CrashReport {
+    let now = chrono::Utc::now();
+    let recent_logs = LOG_DIR
+        .get()
+        .map(|dir| logging::recent_records(&logging::log_files(dir), log::Level::Trace, RECENT_LOG_LINES))
+        .unwrap_or_default();
+    // try_read: the panicking thread may be the one holding the settings lock
+    let settings = SETTINGS
+        .try_read()
+        .ok()
+        .and_then(|s| settings_bundle::sanitized_settings(&s).ok());
+    CrashReport {
+        id: now.format("%Y%m%d-%H%M%S-%3f").to_string(),
+        at: now.to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+        message: sanitize_error(&panic_message(info)),
+        location: info
+            .location()
+            .map(|l| sanitize_error(&format!("{}:{}", l.file(), l.line()))),
+        backtrace: sanitize_error(&Backtrace::force_capture().to_string()),
+        recent_logs,
+        settings,
+    }
+}
+
+/// Report files, newest first (IDs sort by time)
+fn report_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("crash-") && n.ends_with(".json"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files.reverse();
+    files
+}
+
+fn save_report(dir: &Path, report: &CrashReport) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let content = serde_json::to_vec_pretty(report).map_err(std::io::Error::other)?;
+    fs::write(report_path(dir, &report.id), content)?;
+    for old in report_files(dir).into_iter().skip(MAX_CRASH_REPORTS) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(())
+}
+
+fn load_report(path: &Path) -> Result<CrashReport, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| sanitize_error(&format!("Failed to read crash report: {}", e)))?
+        .len();
+    if size > MAX_REPORT_SIZE {
+        return Err("Crash report is too large".to_string());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| sanitize_error(&format!("Failed to read crash report: {}", e)))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid crash report: {}", e))
+}
+
+/// Install the panic hook. Call once at startup, after logging is set up.
+pub fn install(app: &tauri::AppHandle) {
+    if let Ok(dir) = logging::log_dir(app) {
+        let _ = LOG_DIR.set(dir);
+    }
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = build_report(info);
+        match save_report(&crash_dir(), &report) {
+            Ok(()) => log::error!("Crashed: {} (crash report {})", report.message, report.id),
+            Err(e) => log::error!("Crashed: {} (failed to save crash report: {})", report.message, e),
+        }
+        default_hook(info);
+    }));
+}
+
+/// Saved crash reports, newest first
+#[tauri::command]
+pub async fn list_crash_reports() -> Result<Vec<CrashSummary>, String> {
+    run_blocking(|| {
+        Ok(report_files(&crash_dir())
+            .iter()
+            .filter_map(|path| load_report(path).ok())
+            .map(CrashSummary::from)
+            .collect())
+    })
+    .await
+}
+
+/// Copy a crash report (the latest when `id` is omitted) to `path`
+#[tauri::command]
+pub async fn export_crash_report(id: Option<String>, path: String) -> Result<CrashSummary, String> {
+    run_blocking(move || {
+        let dir = crash_dir();
+        let source = match id.as_deref().map(str::trim) {
+            Some(id) => {
+                if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                    return Err("Invalid crash report ID".to_string());
+                }
+                Some(report_path(&dir, id)).filter(|p| p.exists())
+            }
+            None => report_files(&dir).into_iter().next(),
+        }
+        .ok_or("No crash report found")?;
+        let report = load_report(&source)?;
+        let content =
+            serde_json::to_string_pretty(&report).map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+        fs::write(&path, content).map_err(|e| sanitize_error(&format!("Failed to write crash report: {}", e)))?;
+        log::info!("Exported crash report {}", report.id);
+        Ok(report.into())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    fn report(id: &str) -> CrashReport {
+        CrashReport {
+            id: id.to_string(),
+            at: "2026-03-01T12:00:00Z".to_string(),
+            app_version: "0.0.0".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            thread: "main".to_string(),
+            message: "boom".to_string(),
+            location: None,
+            backtrace: String::new(),
+            recent_logs: Vec::new(),
+            settings: None,
+        }
+    }
+
+    #[test]
+    fn test_keeps_newest_reports() {
+        let tmp = TempDir::new("truthgit-crashes");
+        let dir = tmp.path();
+        for i in 0..MAX_CRASH_REPORTS + 3 {
+            save_report(dir, &report(&format!("20260301-1200{:02}-000", i))).unwrap();
+        }
+        let files = report_files(dir);
+        assert_eq!(files.len(), MAX_CRASH_REPORTS);
+        let newest = load_report(&files[0]).unwrap();
+        assert_eq!(newest.id, format!("20260301-1200{:02}-000", MAX_CRASH_REPORTS + 2));
+    }
+}
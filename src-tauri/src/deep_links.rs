@@ -0,0 +1,164 @@
+// ==================== DEEP LINKS ====================
+//
+// Handles truthgit:// links clicked in a browser, Obsidian or chat:
+//   truthgit://claim/<hash>                              open a claim
+//   truthgit://verify?claim=...&domain=...&risk=...      pre-fill the verify form
+// Links are parsed and validated here, then handed to the frontend as a
+// `deep-link://open` event. Nothing is verified or fetched automatically;
+// a link only ever navigates.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::truth_store::ObjectId;
+
+pub const DEEP_LINK_SCHEME: &str = "truthgit";
+pub const DEEP_LINK_EVENT: &str = "deep-link://open";
+
+const MAX_LINK_CLAIM_LEN: usize = 10_000;
+const MAX_PENDING_LINKS: usize = 16;
+
+/// Links that arrived before the frontend was listening (e.g. the link that
+/// launched the app); drained by `take_pending_deep_links`
+static PENDING: Mutex<Vec<DeepLinkAction>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeepLinkAction {
+    Claim {
+        hash: String,
+    },
+    Verify {
+        claim: String,
+        domain: Option<String>,
+        risk_profile: Option<String>,
+    },
+}
+
+pub fn parse_deep_link(link: &str) -> Result<DeepLinkAction, String> {
+    let url = reqwest::Url::parse(link.trim()).map_err(|e| format!("Invalid link: {}", e))?;
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Not a {}:// link", DEEP_LINK_SCHEME));
+    }
+
+    match url.host_str() {
+        Some("claim") => {
+            let hash = url.path().trim_matches('/');
+            if !ObjectId::is_valid(hash) {
+                return Err("Link does not contain a valid claim hash".to_string());
+            }
+            Ok(DeepLinkAction::Claim { hash: hash.to_string() })
+        }
+        Some("verify") => {
+            let param = |name: &str| {
+                url.query_pairs()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let claim = param("claim").ok_or("Link has no claim to verify")?;
+            if claim.len() > MAX_LINK_CLAIM_LEN {
+                return Err("Claim in link is too long".to_string());
+            }
+            let risk_profile = param("risk").filter(|r| ["low", "medium", "high"].contains(&r.as_str()));
+            Ok(DeepLinkAction::Verify {
+                claim,
+                domain: param("domain"),
+                risk_profile,
+            })
+        }
+        _ => Err("Unknown link type".to_string()),
+    }
+}
+
+/// Validate incoming links, bring the window forward and notify the frontend
+pub fn handle_links(app: &AppHandle, links: &[String]) {
+    for link in links {
+        let action = match parse_deep_link(link) {
+            Ok(action) => action,
+            Err(e) => {
+                log::warn!("Ignoring deep link: {}", e);
+                continue;
+            }
+        };
+        if let Ok(mut pending) = PENDING.lock() {
+            if pending.len() < MAX_PENDING_LINKS {
+                pending.push(action.clone());
+            }
+        }
+        if let Err(e) = app.emit(DEEP_LINK_EVENT, action) {
+            log::warn!("Failed to emit {}: {}", DEEP_LINK_EVENT, e);
+        }
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Register the URL scheme and start listening for links
+pub fn init(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    // macOS registers schemes from the bundle; Linux and Windows need it at runtime
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    app.deep_link().register_all()?;
+
+    if let Some(urls) = app.deep_link().get_current()? {
+        let links: Vec<String> = urls.iter().map(|u| u.to_string()).collect();
+        handle_links(app, &links);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        let links: Vec<String> = event.urls().iter().map(|u| u.to_string()).collect();
+        handle_links(&handle, &links);
+    });
+    Ok(())
+}
+
+/// Links received before the frontend subscribed to `deep-link://open`.
+/// Events for these were also emitted, so the frontend should call this once
+/// at startup and rely on the event afterwards.
+#[tauri::command]
+pub async fn take_pending_deep_links() -> Result<Vec<DeepLinkAction>, String> {
+    let mut pending = PENDING.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(std::mem::take(&mut *pending))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_claim_link() {
+        assert_eq!(
+            parse_deep_link("truthgit://claim/ab12cd34ef").unwrap(),
+            DeepLinkAction::Claim {
+                hash: "ab12cd34ef".to_string()
+            }
+        );
+        assert!(parse_deep_link("truthgit://claim/../../etc").is_err());
+        assert!(parse_deep_link("https://claim/ab12cd34ef").is_err());
+    }
+
+    #[test]
+    fn test_parse_verify_link() {
+        let action = parse_deep_link("truthgit://verify?claim=Water%20boils%20at%20100%C2%B0C&domain=physics&risk=high").unwrap();
+        assert_eq!(
+            action,
+            DeepLinkAction::Verify {
+                claim: "Water boils at 100°C".to_string(),
+                domain: Some("physics".to_string()),
+                risk_profile: Some("high".to_string()),
+            }
+        );
+        // Unknown risk profiles are dropped rather than passed through
+        let action = parse_deep_link("truthgit://verify?claim=x&risk=extreme").unwrap();
+        assert!(matches!(action, DeepLinkAction::Verify { risk_profile: None, domain: None, .. }));
+        assert!(parse_deep_link("truthgit://verify?domain=physics").is_err());
+    }
+}
@@ -0,0 +1,476 @@
+// ==================== DIAGNOSTICS ====================
+//
+// "Repo doctor": one report covering everything the app depends on, each
+// check with a status and, when something is off, a suggested fix. Meant to
+// be pasted into support requests, so paths are sanitized like error
+// messages. Checks never modify anything.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::engine::{BACKEND_CLI, BACKEND_EMBEDDED, BACKEND_MOCK};
+use crate::setup::is_truth_repo;
+use crate::{
+    consent, execute_with_timeout, http_client, repo_crypto, sanitize_error, AppSettings, AuditEntry, SETTINGS,
+};
+
+/// Free space below this on the repo's disk is a warning
+const LOW_DISK_SPACE: u64 = 500 * 1024 * 1024;
+const API_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    pub id: String,
+    pub label: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    pub generated_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub backend_mode: String,
+    pub api_mode: String,
+    /// Worst status across all checks
+    pub overall: CheckStatus,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+fn check(id: &str, label: &str, status: CheckStatus, detail: impl Into<String>, fix: Option<&str>) -> DiagnosticCheck {
+    DiagnosticCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        status,
+        detail: sanitize_error(&detail.into()),
+        fix: fix.map(|f| f.to_string()),
+    }
+}
+
+fn overall(checks: &[DiagnosticCheck]) -> CheckStatus {
+    if checks.iter().any(|c| c.status == CheckStatus::Error) {
+        CheckStatus::Error
+    } else if checks.iter().any(|c| c.status == CheckStatus::Warning) {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Ok
+    }
+}
+
+fn check_vault(settings: &AppSettings) -> DiagnosticCheck {
+    let vault = Path::new(&settings.vault_path);
+    let label = "Obsidian vault";
+    if !vault.is_dir() {
+        return check(
+            "vault_path",
+            label,
+            CheckStatus::Warning,
+            format!("{} does not exist", vault.display()),
+            Some("Set the vault path in Settings, or ignore if you don't use Obsidian"),
+        );
+    }
+    match fs::read_dir(vault) {
+        Ok(_) => check(
+            "vault_path",
+            label,
+            CheckStatus::Ok,
+            format!("{} is readable", vault.display()),
+            None,
+        ),
+        Err(e) => check(
+            "vault_path",
+            label,
+            CheckStatus::Error,
+            format!("{} is not readable: {}", vault.display(), e),
+            Some("Check the folder's permissions"),
+        ),
+    }
+}
+
+/// Can we create files in `dir`? Probed with a temp file that is removed again
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".truthgit-write-test-{}", uuid::Uuid::new_v4()));
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+    writable
+}
+
+fn check_repo(repo: &Path) -> DiagnosticCheck {
+    let label = "Truth repository";
+    if !repo.exists() {
+        return check(
+            "truth_repo",
+            label,
+            CheckStatus::Error,
+            format!("{} does not exist", repo.display()),
+            Some("Create a repository from the setup wizard, or point Settings at an existing one"),
+        );
+    }
+    if !is_truth_repo(repo) {
+        return check(
+            "truth_repo",
+            label,
+            CheckStatus::Error,
+            format!("{} has no objects/ directory", repo.display()),
+            Some("Point Settings at the .truth directory itself, not its parent"),
+        );
+    }
+    if !is_writable(repo) {
+        return check(
+            "truth_repo",
+            label,
+            CheckStatus::Error,
+            format!("{} is read-only", repo.display()),
+            Some("Fix the directory's permissions; claims and audit entries can't be saved"),
+        );
+    }
+    check(
+        "truth_repo",
+        label,
+        CheckStatus::Ok,
+        format!("{} is a writable repository", repo.display()),
+        None,
+    )
+}
+
+fn check_keys(repo: &Path) -> DiagnosticCheck {
+    let label = "Signing keys";
+    let private = repo.join("proof.key");
+    let public = repo.join("proof.pub");
+    match (private.is_file(), public.is_file()) {
+        (true, true) => {}
+        (false, false) => {
+            return check(
+                "keys",
+                label,
+                CheckStatus::Warning,
+                "No key pair (proof.key / proof.pub)",
+                Some(
+                    "Run `truthgit init` or restore the keys from a backup; verifications can't be signed without them",
+                ),
+            )
+        }
+        _ => {
+            return check(
+                "keys",
+                label,
+                CheckStatus::Error,
+                "Only half of the key pair is present",
+                Some("Restore the missing key file from a backup"),
+            )
+        }
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = fs::metadata(&private) {
+            if metadata.permissions().mode() & 0o077 != 0 {
+                return check(
+                    "keys",
+                    label,
+                    CheckStatus::Warning,
+                    "proof.key is readable by other users",
+                    Some("chmod 600 proof.key"),
+                );
+            }
+        }
+    }
+    check("keys", label, CheckStatus::Ok, "Key pair present", None)
+}
+
+fn check_audit(repo: &Path) -> DiagnosticCheck {
+    let label = "Audit trail";
+    let path = repo.join("audit.json");
+    if !path.exists() {
+        return check("audit", label, CheckStatus::Ok, "No audit entries yet", None);
+    }
+    let content = match fs::read(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            return check(
+                "audit",
+                label,
+                CheckStatus::Error,
+                format!("audit.json is not readable: {}", e),
+                Some("Check the file's permissions"),
+            )
+        }
+    };
+    let content = match repo_crypto::open(repo, repo_crypto::AUDIT, &content) {
+        Ok(content) => content,
+        Err(e) => {
+            return check(
+                "audit",
+                label,
+                CheckStatus::Warning,
+                format!("audit.json is encrypted and can't be checked: {}", e),
+                Some("Unlock the repo to check the audit trail"),
+            )
+        }
+    };
+    match serde_json::from_slice::<Vec<AuditEntry>>(&content) {
+        Ok(entries) => check(
+            "audit",
+            label,
+            CheckStatus::Ok,
+            format!("{} entries, all readable", entries.len()),
+            None,
+        ),
+        Err(e) => check(
+            "audit",
+            label,
+            CheckStatus::Error,
+            format!("audit.json is corrupt: {}", e),
+            Some("Restore audit.json from a backup; new entries would overwrite it"),
+        ),
+    }
+}
+
+fn check_index() -> DiagnosticCheck {
+    check(
+        "index",
+        "Claim index",
+        CheckStatus::Skipped,
+        "Claims are read directly from objects/; there is no index to go stale",
+        None,
+    )
+}
+
+/// Free space on the disk holding `path`, from the longest matching mount point
+fn available_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| d.available_space())
+}
+
+fn check_disk(repo: &Path) -> DiagnosticCheck {
+    let label = "Disk space";
+    let target = if repo.exists() {
+        repo.to_path_buf()
+    } else {
+        repo.parent().map(PathBuf::from).unwrap_or_default()
+    };
+    match available_space(&target) {
+        Some(free) if free < LOW_DISK_SPACE => check(
+            "disk",
+            label,
+            CheckStatus::Warning,
+            format!("{} MB free", free / (1024 * 1024)),
+            Some("Free up space; sync and backups need room for new objects"),
+        ),
+        Some(free) => check(
+            "disk",
+            label,
+            CheckStatus::Ok,
+            format!("{} MB free", free / (1024 * 1024)),
+            None,
+        ),
+        None => check(
+            "disk",
+            label,
+            CheckStatus::Skipped,
+            "Could not determine free space",
+            None,
+        ),
+    }
+}
+
+async fn check_engine(settings: &AppSettings) -> DiagnosticCheck {
+    let label = "TruthGit engine";
+    match settings.backend_mode.as_str() {
+        BACKEND_MOCK => check(
+            "engine",
+            label,
+            CheckStatus::Warning,
+            "Mock backend: results are demo fixtures",
+            Some("Switch the backend to \"cli\" for real verifications"),
+        ),
+        BACKEND_EMBEDDED if cfg!(feature = "embedded-engine") => {
+            check("engine", label, CheckStatus::Ok, "Embedded engine", None)
+        }
+        BACKEND_EMBEDDED => check(
+            "engine",
+            label,
+            CheckStatus::Error,
+            "Embedded engine selected but not compiled in",
+            Some("Set the backend to \"cli\""),
+        ),
+        BACKEND_CLI => match execute_with_timeout("truthgit", &["--version".to_string()], None).await {
+            Ok(output) if output.status.success() => {
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                check(
+                    "engine",
+                    label,
+                    CheckStatus::Ok,
+                    format!("truthgit CLI: {}", version),
+                    None,
+                )
+            }
+            Ok(output) => check(
+                "engine",
+                label,
+                CheckStatus::Error,
+                format!(
+                    "truthgit --version failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Some("Reinstall with `pip install --upgrade truthgit`"),
+            ),
+            Err(e) => check(
+                "engine",
+                label,
+                CheckStatus::Error,
+                format!("truthgit CLI not found: {}", e),
+                Some("Install it with `pip install truthgit` and make sure it is on PATH"),
+            ),
+        },
+        other => check(
+            "engine",
+            label,
+            CheckStatus::Error,
+            format!("Unknown backend \"{}\"", other),
+            Some("Set the backend to \"cli\" in Settings"),
+        ),
+    }
+}
+
+async fn check_api(settings: &AppSettings) -> DiagnosticCheck {
+    let label = "Remote API";
+    if settings.api_mode != "remote" {
+        return check("api", label, CheckStatus::Skipped, "Local mode; no API in use", None);
+    }
+    if !consent::is_host_allowed(&settings.api_url) {
+        return check(
+            "api",
+            label,
+            CheckStatus::Skipped,
+            format!("{} not contacted: the app hasn't been allowed to connect to it yet", settings.api_url),
+            None,
+        );
+    }
+    let client = match http_client::client_builder()
+        .and_then(|b| b.timeout(API_CHECK_TIMEOUT).build().map_err(|e| e.to_string()))
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return check(
+                "api",
+                label,
+                CheckStatus::Error,
+                format!("HTTP client could not be built: {}", e),
+                Some("Check the proxy and CA bundle settings"),
+            )
+        }
+    };
+    match client.get(&settings.api_url).send().await {
+        Ok(response) if response.status().is_server_error() => check(
+            "api",
+            label,
+            CheckStatus::Warning,
+            format!("{} answered {}", settings.api_url, response.status()),
+            Some("The server is having problems; try again later"),
+        ),
+        Ok(response) => check(
+            "api",
+            label,
+            CheckStatus::Ok,
+            format!("{} reachable ({})", settings.api_url, response.status()),
+            None,
+        ),
+        Err(e) => check(
+            "api",
+            label,
+            CheckStatus::Error,
+            format!("{} unreachable: {}", settings.api_url, e),
+            Some("Check the API URL, your connection and proxy settings"),
+        ),
+    }
+}
+
+#[tauri::command]
+pub async fn run_diagnostics() -> Result<DiagnosticsReport, String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+    let repo = PathBuf::from(&settings.truth_repo_path);
+
+    let mut checks = {
+        let repo = repo.clone();
+        let settings = settings.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut checks = vec![check_vault(&settings), check_repo(&repo)];
+            if is_truth_repo(&repo) {
+                checks.push(check_keys(&repo));
+                checks.push(check_audit(&repo));
+                checks.push(check_index());
+            }
+            checks.push(check_disk(&repo));
+            checks
+        })
+        .await
+        .map_err(|e| format!("Diagnostics task failed: {}", e))?
+    };
+    let (engine, api) = tokio::join!(check_engine(&settings), check_api(&settings));
+    checks.push(engine);
+    checks.push(api);
+
+    Ok(DiagnosticsReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        backend_mode: settings.backend_mode.clone(),
+        api_mode: settings.api_mode.clone(),
+        overall: overall(&checks),
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_repo_checks() {
+        let tmp = TempDir::new("truthgit-doctor");
+        let repo = tmp.path();
+        assert_eq!(check_repo(repo).status, CheckStatus::Error);
+        fs::create_dir_all(repo.join("objects")).unwrap();
+        assert_eq!(check_repo(repo).status, CheckStatus::Ok);
+
+        assert_eq!(check_keys(repo).status, CheckStatus::Warning);
+        fs::write(repo.join("proof.pub"), "pub").unwrap();
+        assert_eq!(check_keys(repo).status, CheckStatus::Error);
+
+        assert_eq!(check_audit(repo).status, CheckStatus::Ok);
+        fs::write(repo.join("audit.json"), "[{\"id\": ").unwrap();
+        let audit = check_audit(repo);
+        assert_eq!(audit.status, CheckStatus::Error);
+        assert!(audit.fix.is_some());
+    }
+
+    #[test]
+    fn test_overall_is_worst_status() {
+        let ok = check("a", "A", CheckStatus::Ok, "", None);
+        let skipped = check("b", "B", CheckStatus::Skipped, "", None);
+        let warning = check("c", "C", CheckStatus::Warning, "", None);
+        assert_eq!(overall(&[ok.clone(), skipped.clone()]), CheckStatus::Ok);
+        assert_eq!(overall(&[ok, warning, skipped]), CheckStatus::Warning);
+    }
+}
@@ -0,0 +1,397 @@
+// ==================== DIGESTS ====================
+//
+// A short summary of the last day, week or month in the truth repo, for
+// pasting into an email or a chat: new claims, verdict changes, escalated
+// verifications, and claims whose newest verification is more than
+// STALE_DAYS old. The digest is written as markdown and rendered to HTML from
+// it. With `digest.schedule` set, one is generated each period, saved as
+// digests/digest-<date>.md in the truth repo and handed to `digest` hooks,
+// which can post it wherever the team reads it.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use pulldown_cmark::{html, Event, Parser};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::exports::field;
+use crate::hooks::{self, HookEvent};
+use crate::json_store::write_atomic;
+use crate::snapshots::{diff, instant, verdicts_at, NewClaim, VerdictChange};
+use crate::verification::Verification;
+use crate::{mock, read_audit_trail, read_claims, read_verifications, repo_state, run_blocking, AuditEntry, SETTINGS};
+
+/// A claim whose newest verification is older than this is stale
+const STALE_DAYS: i64 = 90;
+/// Items listed per section; the rest are counted
+const MAX_LISTED: usize = 20;
+/// How often the scheduler looks for a digest that is due
+const SCHEDULER_TICK: Duration = Duration::from_secs(60 * 60);
+const DIGESTS_DIR: &str = "digests";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl DigestPeriod {
+    fn days(self) -> i64 {
+        match self {
+            Self::Day => 1,
+            Self::Week => 7,
+            Self::Month => 30,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Day => "Daily digest",
+            Self::Week => "Weekly digest",
+            Self::Month => "Monthly digest",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestSettings {
+    /// Period of automatic digests; None turns them off
+    pub schedule: Option<DigestPeriod>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Escalation {
+    pub claim: String,
+    pub domain: String,
+    pub status: String,
+    pub confidence: f64,
+    pub at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleClaim {
+    pub hash: String,
+    pub claim: String,
+    pub verdict: String,
+    pub last_verified: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    pub period: DigestPeriod,
+    /// The window covered, RFC 3339
+    pub from: String,
+    pub to: String,
+    /// Oldest first
+    pub new_claims: Vec<NewClaim>,
+    pub verdict_changes: Vec<VerdictChange>,
+    /// Oldest first
+    pub escalations: Vec<Escalation>,
+    /// Longest unverified first
+    pub stale_claims: Vec<StaleClaim>,
+    pub markdown: String,
+    pub html: String,
+}
+
+/// Backslash-escape characters that would turn claim text into markup
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.split_whitespace().collect::<Vec<_>>().join(" ").chars() {
+        if matches!(c, '\\' | '*' | '_' | '[' | ']' | '<' | '>' | '`' | '#' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn day(at: &str) -> String {
+    instant(at).map_or_else(|| at.to_string(), |t| t.format("%Y-%m-%d").to_string())
+}
+
+fn percent(confidence: f64) -> String {
+    format!("{:.0}%", confidence * 100.0)
+}
+
+/// A section listing at most MAX_LISTED items; nothing if there are none
+fn section<T>(out: &mut String, heading: &str, items: &[T], line: impl Fn(&T) -> String) {
+    if items.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n## {}\n\n", heading));
+    for item in items.iter().take(MAX_LISTED) {
+        out.push_str(&format!("- {}\n", line(item)));
+    }
+    if items.len() > MAX_LISTED {
+        out.push_str(&format!("- …and {} more\n", items.len() - MAX_LISTED));
+    }
+}
+
+fn count(n: usize, one: &str, many: &str) -> String {
+    format!("{} {}", n, if n == 1 { one } else { many })
+}
+
+fn render_markdown(digest: &Digest) -> String {
+    let mut out = format!(
+        "# {}: {} to {}\n\n**{} · {} · {} · {}**\n",
+        digest.period.title(),
+        day(&digest.from),
+        day(&digest.to),
+        count(digest.new_claims.len(), "new claim", "new claims"),
+        count(digest.verdict_changes.len(), "verdict change", "verdict changes"),
+        count(digest.escalations.len(), "escalation", "escalations"),
+        count(digest.stale_claims.len(), "stale claim", "stale claims"),
+    );
+    section(&mut out, "New claims", &digest.new_claims, |c| {
+        match c.domain.as_str() {
+            "" => escape_markdown(&c.content),
+            domain => format!("{} ({})", escape_markdown(&c.content), escape_markdown(domain)),
+        }
+    });
+    section(&mut out, "Verdict changes", &digest.verdict_changes, |c| {
+        let confidence = c.confidence.map(|v| format!(", {}", percent(v))).unwrap_or_default();
+        match &c.from {
+            Some(from) => format!("{}: {} → **{}**{}", escape_markdown(&c.claim), from, c.to, confidence),
+            None => format!(
+                "{}: **{}**{} (first verdict)",
+                escape_markdown(&c.claim),
+                c.to,
+                confidence
+            ),
+        }
+    });
+    section(&mut out, "Escalations", &digest.escalations, |e| {
+        format!(
+            "{}: {}, {} on {}",
+            escape_markdown(&e.claim),
+            e.status,
+            percent(e.confidence),
+            day(&e.at)
+        )
+    });
+    section(&mut out, "Stale claims", &digest.stale_claims, |s| {
+        format!(
+            "{}: {}, last verified {}",
+            escape_markdown(&s.claim),
+            s.verdict,
+            day(&s.last_verified)
+        )
+    });
+    let quiet = digest.new_claims.is_empty()
+        && digest.verdict_changes.is_empty()
+        && digest.escalations.is_empty()
+        && digest.stale_claims.is_empty();
+    if quiet {
+        out.push_str("\nNothing happened in this period.\n");
+    }
+    out
+}
+
+/// HTML for the digest's markdown; raw HTML in it is shown as text
+fn render_html(markdown: &str) -> String {
+    let events = Parser::new(markdown).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+fn build(
+    claims: &[serde_json::Value],
+    verifications: &[Verification],
+    audit: &[AuditEntry],
+    period: DigestPeriod,
+    to: DateTime<Utc>,
+) -> Digest {
+    let from = to - chrono::Duration::days(period.days());
+    let changes = diff(claims, verifications, audit, from, to);
+
+    let mut escalations: Vec<Escalation> = audit
+        .iter()
+        .filter(|e| e.result_action == "escalate")
+        .filter(|e| instant(&e.timestamp).is_some_and(|t| t > from && t <= to))
+        .map(|e| Escalation {
+            claim: e.claim.clone(),
+            domain: e.domain.clone(),
+            status: e.result_status.clone(),
+            confidence: e.confidence,
+            at: e.timestamp.clone(),
+        })
+        .collect();
+    escalations.sort_by_key(|e| instant(&e.at));
+
+    let stale_before = to - chrono::Duration::days(STALE_DAYS);
+    let latest = verdicts_at(verifications, to);
+    let mut stale: Vec<(DateTime<Utc>, StaleClaim)> = claims
+        .iter()
+        .filter_map(|claim| {
+            let hash = field(claim, "$hash");
+            let (verified, v) = latest.get(hash).filter(|(at, _)| *at < stale_before)?;
+            let claim = match field(claim, "content") {
+                "" => v.claim.clone(),
+                content => content.to_string(),
+            };
+            let stale = StaleClaim {
+                hash: hash.to_string(),
+                claim,
+                verdict: v.verdict.clone(),
+                last_verified: v.timestamp.clone(),
+            };
+            Some((*verified, stale))
+        })
+        .collect();
+    stale.sort_by_key(|(at, _)| *at);
+
+    let mut digest = Digest {
+        period,
+        from: changes.from,
+        to: changes.to,
+        new_claims: changes.new_claims,
+        verdict_changes: changes.verdict_changes,
+        escalations,
+        stale_claims: stale.into_iter().map(|(_, s)| s).collect(),
+        markdown: String::new(),
+        html: String::new(),
+    };
+    digest.markdown = render_markdown(&digest);
+    digest.html = render_html(&digest.markdown);
+    digest
+}
+
+fn generate(period: DigestPeriod, to: DateTime<Utc>) -> Result<Digest, String> {
+    let claims = read_claims(None)?.claims;
+    let verifications = read_verifications()?.verifications;
+    let audit = if mock::is_active() {
+        Vec::new()
+    } else {
+        read_audit_trail()?
+    };
+    Ok(build(&claims, &verifications, &audit, period, to))
+}
+
+fn digests_dir() -> Result<PathBuf, String> {
+    let truth_path = repo_state::ready_path()?;
+    Ok(truth_path.join(DIGESTS_DIR))
+}
+
+/// Date of the newest digest-<date>.md in `dir`
+fn last_digest(dir: &Path) -> Option<NaiveDate> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let date = name.strip_prefix("digest-")?.strip_suffix(".md")?.to_string();
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()
+        })
+        .max()
+}
+
+/// Write a digest for the period just ended if the last one is a period old
+fn write_due_digest(period: DigestPeriod) -> Result<Option<Digest>, String> {
+    let dir = digests_dir()?;
+    let now = Utc::now();
+    let today = now.date_naive();
+    if last_digest(&dir).is_some_and(|last| (today - last).num_days() < period.days()) {
+        return Ok(None);
+    }
+    let digest = generate(period, now)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create digests folder: {}", e))?;
+    let path = dir.join(format!("digest-{}.md", today.format("%Y-%m-%d")));
+    write_atomic(&path, digest.markdown.as_bytes()).map_err(|e| format!("Failed to write digest: {}", e))?;
+    log::info!("Wrote {}", path.display());
+    Ok(Some(digest))
+}
+
+/// Start the background loop that writes scheduled digests
+pub fn start() {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCHEDULER_TICK).await;
+            let schedule = SETTINGS.read().ok().and_then(|s| s.digest.schedule);
+            let Some(period) = schedule.filter(|_| !mock::is_active()) else {
+                continue;
+            };
+            let digest = match run_blocking(move || write_due_digest(period)).await {
+                Ok(Some(digest)) => digest,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Scheduled digest failed: {}", e);
+                    continue;
+                }
+            };
+            let payload = serde_json::json!({
+                "period": digest.period,
+                "from": digest.from,
+                "to": digest.to,
+                "markdown": digest.markdown,
+                "html": digest.html,
+            });
+            if let Err(e) = hooks::run(HookEvent::Digest, payload).await {
+                log::warn!("digest hooks: {}", e);
+            }
+        }
+    });
+}
+
+/// A digest of the `period` up to now, as markdown and HTML
+#[tauri::command]
+pub async fn generate_digest(period: DigestPeriod) -> Result<Digest, String> {
+    run_blocking(move || generate(period, Utc::now())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_weekly_digest() {
+        let claims = vec![
+            json!({"$hash": "aa", "content": "Old <b>claim</b>", "metadata": {"created_at": "2025-01-01T00:00:00Z"}}),
+            json!({"$hash": "bb", "content": "New claim", "domain": "physics",
+                   "metadata": {"created_at": "2026-03-05T00:00:00Z"}}),
+        ];
+        let verifications = [
+            json!({"claim_hash": "aa", "verdict": "PASSED", "timestamp": "2025-06-01T00:00:00Z"}),
+            json!({"claim_hash": "bb", "verdict": "MYSTERY", "confidence": 0.4, "timestamp": "2026-03-06T00:00:00Z"}),
+        ]
+        .map(Verification::from);
+        let audit = vec![AuditEntry {
+            claim: "New claim".to_string(),
+            result_status: "MYSTERY".to_string(),
+            result_action: "escalate".to_string(),
+            confidence: 0.4,
+            timestamp: "2026-03-06T00:00:00Z".to_string(),
+            ..Default::default()
+        }];
+        let to = instant("2026-03-08T12:00:00Z").unwrap();
+
+        let digest = build(&claims, &verifications, &audit, DigestPeriod::Week, to);
+        assert_eq!((digest.new_claims.len(), digest.verdict_changes.len()), (1, 1));
+        assert_eq!(digest.escalations.len(), 1);
+        assert_eq!(digest.stale_claims[0].hash, "aa");
+        assert!(digest
+            .markdown
+            .starts_with("# Weekly digest: 2026-03-01 to 2026-03-08\n"));
+        assert!(digest
+            .markdown
+            .contains("**1 new claim · 1 verdict change · 1 escalation · 1 stale claim**"));
+        assert!(digest
+            .markdown
+            .contains("- New claim: **MYSTERY**, 40% (first verdict)\n"));
+        assert!(digest
+            .markdown
+            .contains("- Old \\<b\\>claim\\</b\\>: PASSED, last verified 2025-06-01\n"));
+        assert!(digest.html.contains("<h2>Stale claims</h2>") && digest.html.contains("Old &lt;b&gt;claim"));
+
+        let quiet = build(&[], &[], &[], DigestPeriod::Day, to);
+        assert!(quiet.markdown.ends_with("Nothing happened in this period.\n"));
+    }
+}
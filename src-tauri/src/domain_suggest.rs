@@ -0,0 +1,357 @@
+// ==================== DOMAIN SUGGESTIONS ====================
+//
+// Proposes a domain for a claim before it is verified, so the verify form
+// can preselect one instead of always starting from "general". Two local
+// signals are added up for each candidate domain:
+// - rules: the claim's words matched against the domain's name, keywords and
+//   description, plus a built-in keyword list for the form's stock domains
+//   (science, medical, ...);
+// - history: words the claim shares with claims already filed under the
+//   domain, weighted so words common to every domain count for nothing.
+// Candidates are the registered domains, or while the registry is empty the
+// stock domains and any domain found in the repo. Everything runs on this
+// machine; the app has no embeddings or LLM backend to refine the guess with.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::domains::{self, Domain};
+use crate::exports::field;
+use crate::{read_claims, run_blocking};
+
+const MAX_TEXT_LEN: usize = 10_000;
+const MAX_ALTERNATIVES: usize = 3;
+/// Past claims read per domain; enough to learn its vocabulary
+const MAX_HISTORY_CLAIMS: usize = 500;
+const NAME_WEIGHT: f64 = 2.0;
+const KEYWORD_WEIGHT: f64 = 1.5;
+const DESCRIPTION_WEIGHT: f64 = 0.5;
+/// Keeps a single weak match from looking certain
+const SMOOTHING: f64 = 1.0;
+
+const STOCK_KEYWORDS: &[(&str, &[&str])] = &[
+    (
+        "science",
+        &[
+            "physics",
+            "chemistry",
+            "biology",
+            "atom",
+            "molecule",
+            "energy",
+            "temperature",
+            "boils",
+            "gravity",
+            "species",
+            "evolution",
+            "experiment",
+            "theory",
+            "planet",
+            "climate",
+            "carbon",
+            "oxygen",
+            "water",
+        ],
+    ),
+    (
+        "medical",
+        &[
+            "disease",
+            "treatment",
+            "cure",
+            "cures",
+            "patient",
+            "patients",
+            "drug",
+            "dose",
+            "vaccine",
+            "cancer",
+            "symptoms",
+            "clinical",
+            "therapy",
+            "diagnosis",
+            "virus",
+            "infection",
+            "health",
+            "doctor",
+        ],
+    ),
+    (
+        "financial",
+        &[
+            "revenue",
+            "profit",
+            "market",
+            "stock",
+            "shares",
+            "investment",
+            "interest",
+            "inflation",
+            "bank",
+            "price",
+            "prices",
+            "tax",
+            "budget",
+            "dividend",
+            "earnings",
+            "loan",
+            "currency",
+            "gdp",
+        ],
+    ),
+    (
+        "legal",
+        &[
+            "law",
+            "legal",
+            "court",
+            "contract",
+            "liability",
+            "regulation",
+            "statute",
+            "compliance",
+            "rights",
+            "lawsuit",
+            "judge",
+            "gdpr",
+            "license",
+            "patent",
+            "copyright",
+            "illegal",
+            "jurisdiction",
+        ],
+    ),
+    (
+        "engineering",
+        &[
+            "api",
+            "server",
+            "database",
+            "latency",
+            "retry",
+            "deploy",
+            "software",
+            "code",
+            "bug",
+            "performance",
+            "memory",
+            "cpu",
+            "network",
+            "protocol",
+            "build",
+            "system",
+            "cache",
+            "timeout",
+        ],
+    ),
+];
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "was", "were", "that", "this", "with", "from", "has", "have", "had", "its", "not",
+    "but", "can", "will", "would", "should", "could", "than", "then", "there", "their", "they", "been", "being",
+    "into", "onto", "over", "under", "more", "most", "less", "all", "any", "each", "per", "our", "your", "his", "her",
+    "who", "what", "when", "where", "which", "while", "about", "also", "only", "very",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainCandidate {
+    pub domain: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainSuggestion {
+    /// None when nothing in the text points anywhere
+    pub domain: Option<String>,
+    /// 0.0-1.0; how far the best domain is ahead of the rest
+    pub confidence: f64,
+    /// The registered domain's default risk profile
+    pub risk_profile: Option<String>,
+    /// Words that matched the suggested domain's rules
+    pub matched: Vec<String>,
+    /// Next best domains, highest score first
+    pub alternatives: Vec<DomainCandidate>,
+}
+
+fn words(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.chars().count() >= 3 && !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+fn stock_keywords(domain: &str) -> &'static [&'static str] {
+    STOCK_KEYWORDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(domain.trim()))
+        .map_or(&[], |(_, keywords)| *keywords)
+}
+
+/// (score, matched words) of the rules for `domain`
+fn rule_score(domain: &Domain, text: &BTreeSet<String>) -> (f64, Vec<String>) {
+    let name = words(&domain.name);
+    let keywords: BTreeSet<String> = domain
+        .keywords
+        .iter()
+        .flat_map(|k| words(k))
+        .chain(stock_keywords(&domain.name).iter().map(|k| k.to_string()))
+        .collect();
+    let description = words(&domain.description);
+
+    let mut score = 0.0;
+    let mut matched = Vec::new();
+    for word in text {
+        let weight = if name.contains(word) {
+            NAME_WEIGHT
+        } else if keywords.contains(word) {
+            KEYWORD_WEIGHT
+        } else if description.contains(word) {
+            DESCRIPTION_WEIGHT
+        } else {
+            continue;
+        };
+        score += weight;
+        matched.push(word.clone());
+    }
+    (score, matched)
+}
+
+fn suggest(text: &str, candidates: &[Domain], history: &BTreeMap<String, BTreeSet<String>>) -> DomainSuggestion {
+    let text = words(text);
+    // Words filed under every domain tell them apart no better than chance
+    let mut spread: HashMap<&str, usize> = HashMap::new();
+    for vocabulary in history.values() {
+        for word in vocabulary {
+            *spread.entry(word.as_str()).or_default() += 1;
+        }
+    }
+    let domains_with_history = history.len().max(1) as f64;
+
+    let mut scored: Vec<(f64, Vec<String>, &Domain)> = candidates
+        .iter()
+        .map(|domain| {
+            let (rules, matched) = rule_score(domain, &text);
+            let learned: f64 = history
+                .get(&domain.name.to_lowercase())
+                .map(|vocabulary| {
+                    text.iter()
+                        .filter(|w| vocabulary.contains(*w))
+                        .map(|w| (domains_with_history / spread[w.as_str()] as f64).ln())
+                        .sum()
+                })
+                .unwrap_or(0.0);
+            (rules + learned, matched, domain)
+        })
+        .filter(|(score, _, _)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.2.name.cmp(&b.2.name)));
+
+    let mut ranked = scored.into_iter();
+    let Some((top, matched, best)) = ranked.next() else {
+        return DomainSuggestion {
+            domain: None,
+            confidence: 0.0,
+            risk_profile: None,
+            matched: Vec::new(),
+            alternatives: Vec::new(),
+        };
+    };
+    let alternatives: Vec<DomainCandidate> = ranked
+        .take(MAX_ALTERNATIVES)
+        .map(|(score, _, domain)| DomainCandidate {
+            domain: domain.name.clone(),
+            score,
+        })
+        .collect();
+    let runner_up = alternatives.first().map_or(0.0, |a| a.score);
+    DomainSuggestion {
+        domain: Some(best.name.clone()),
+        confidence: top / (top + runner_up + SMOOTHING),
+        risk_profile: best.default_risk_profile.clone(),
+        matched,
+        alternatives,
+    }
+}
+
+fn stock_domain(name: &str) -> Domain {
+    Domain {
+        name: name.to_string(),
+        description: String::new(),
+        default_risk_profile: None,
+        parent: None,
+        keywords: Vec::new(),
+    }
+}
+
+/// Propose a domain for the claim `text`
+#[tauri::command]
+pub async fn suggest_domain(text: String) -> Result<DomainSuggestion, String> {
+    if text.len() > MAX_TEXT_LEN {
+        return Err("The text is too long to suggest a domain for".to_string());
+    }
+    let registered = domains::list_domains().await?;
+    run_blocking(move || {
+        let claims = read_claims(None)?.claims;
+        let mut history: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut filed: HashMap<String, usize> = HashMap::new();
+        for claim in &claims {
+            let domain = field(claim, "domain").trim().to_lowercase();
+            let count = filed.entry(domain.clone()).or_default();
+            if domain.is_empty() || *count >= MAX_HISTORY_CLAIMS {
+                continue;
+            }
+            *count += 1;
+            history
+                .entry(domain)
+                .or_default()
+                .extend(words(field(claim, "content")));
+        }
+
+        let candidates = if registered.is_empty() {
+            let mut names: BTreeSet<String> = STOCK_KEYWORDS.iter().map(|(name, _)| name.to_string()).collect();
+            names.extend(history.keys().cloned());
+            names.iter().map(|name| stock_domain(name)).collect()
+        } else {
+            registered
+        };
+        Ok(suggest(&text, &candidates, &history))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_domain() {
+        let candidates = vec![
+            Domain {
+                keywords: vec!["Clinical trial".to_string()],
+                default_risk_profile: Some("high".to_string()),
+                ..stock_domain("medical")
+            },
+            stock_domain("astronomy"),
+            stock_domain("science"),
+        ];
+        let mut history = BTreeMap::new();
+        history.insert(
+            "astronomy".to_string(),
+            words("The telescope observed a distant galaxy"),
+        );
+        history.insert("science".to_string(), words("A distant star emits light"));
+
+        let suggestion = suggest("This treatment cures cancer in clinical trials", &candidates, &history);
+        assert_eq!(suggestion.domain.as_deref(), Some("medical"));
+        assert_eq!(suggestion.risk_profile.as_deref(), Some("high"));
+        assert_eq!(suggestion.matched, ["cancer", "clinical", "cures", "treatment"]);
+        assert!(suggestion.confidence > 0.8);
+
+        // Only history speaks for astronomy; "distant" is filed under both
+        let suggestion = suggest("A new galaxy was found, very distant", &candidates, &history);
+        assert_eq!(suggestion.domain.as_deref(), Some("astronomy"));
+        assert!(suggestion.alternatives.is_empty());
+
+        assert!(suggest("Lorem ipsum dolor", &candidates, &history).domain.is_none());
+    }
+}
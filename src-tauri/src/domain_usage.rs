@@ -0,0 +1,168 @@
+// ==================== DOMAIN USAGE ====================
+//
+// Which domains and risk profiles the user actually verifies with, counted
+// per manual verification in domain_usage.json. `get_frequent_domains`
+// ranks domains by frecency (use count, fading with a 30-day half-life) so
+// the verify form can preselect the likely domain and that domain's usual
+// risk profile instead of always starting from `default_risk_profile`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{domains, run_blocking, SETTINGS};
+
+const HALF_LIFE_DAYS: f64 = 30.0;
+const DEFAULT_LIMIT: usize = 5;
+/// Domains not used for this long are forgotten
+const FORGET_AFTER_DAYS: i64 = 365;
+
+/// Serializes read-modify-write cycles on domain_usage.json
+static USAGE_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainUsage {
+    pub count: u32,
+    pub last_used: String,
+    /// Verifications per risk profile in this domain
+    pub risk_profiles: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FrequentDomain {
+    pub domain: String,
+    pub count: u32,
+    pub last_used: String,
+    /// The risk profile most used with this domain
+    pub risk_profile: String,
+    pub score: f64,
+}
+
+fn usage_path() -> PathBuf {
+    config_path("domain_usage.json")
+}
+
+fn load_usage() -> Result<BTreeMap<String, DomainUsage>, String> {
+    let _guard = USAGE_LOCK.lock(&usage_path())?;
+    load_json(&usage_path())
+}
+
+fn days_since(timestamp: &str, now: DateTime<Utc>) -> f64 {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| (now - t.with_timezone(&Utc)).num_seconds().max(0) as f64 / 86_400.0)
+        .unwrap_or(f64::MAX)
+}
+
+fn frecency(usage: &DomainUsage, now: DateTime<Utc>) -> f64 {
+    usage.count as f64 * 0.5_f64.powf(days_since(&usage.last_used, now) / HALF_LIFE_DAYS)
+}
+
+/// Most used risk profile; ties go to the stricter one
+fn favourite_risk(usage: &DomainUsage) -> Option<String> {
+    let strictness = |risk: &str| ["low", "medium", "high"].iter().position(|r| *r == risk);
+    usage
+        .risk_profiles
+        .iter()
+        .max_by_key(|(risk, count)| (**count, strictness(risk)))
+        .map(|(risk, _)| risk.clone())
+}
+
+fn add_use(usage: &mut BTreeMap<String, DomainUsage>, domain: &str, risk_profile: &str, now: DateTime<Utc>) {
+    let entry = usage.entry(domain.to_string()).or_default();
+    entry.count = entry.count.saturating_add(1);
+    entry.last_used = now.to_rfc3339();
+    *entry.risk_profiles.entry(risk_profile.to_string()).or_default() += 1;
+    usage.retain(|_, u| days_since(&u.last_used, now) < FORGET_AFTER_DAYS as f64);
+}
+
+fn rank(usage: BTreeMap<String, DomainUsage>, now: DateTime<Utc>, limit: usize) -> Vec<FrequentDomain> {
+    let mut frequent: Vec<FrequentDomain> = usage
+        .into_iter()
+        .filter_map(|(domain, usage)| {
+            Some(FrequentDomain {
+                risk_profile: favourite_risk(&usage)?,
+                score: frecency(&usage, now),
+                count: usage.count,
+                last_used: usage.last_used,
+                domain,
+            })
+        })
+        .collect();
+    frequent.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.domain.cmp(&b.domain)));
+    frequent.truncate(limit);
+    frequent
+}
+
+/// Count a manual verification. Failing to write is logged, not returned.
+pub fn record(domain: &str, risk_profile: &str) {
+    let (domain, risk_profile) = (domain.trim().to_lowercase(), risk_profile.trim().to_lowercase());
+    if domain.is_empty() || risk_profile.is_empty() {
+        return;
+    }
+    let result = (|| {
+        let _guard = USAGE_LOCK.lock(&usage_path())?;
+        let mut usage: BTreeMap<String, DomainUsage> = load_json(&usage_path())?;
+        add_use(&mut usage, &domain, &risk_profile, Utc::now());
+        save_json(&usage_path(), &usage)
+    })();
+    if let Err(e) = result {
+        log::warn!("Failed to record domain usage: {}", e);
+    }
+}
+
+/// Risk profile to start from for `domain`: the one the user usually picks
+/// there, else the domain's default, else `default_risk_profile`
+pub fn suggested_risk_profile(domain: &str) -> Result<String, String> {
+    let used = load_usage()?
+        .get(&domain.trim().to_lowercase())
+        .and_then(favourite_risk);
+    if let Some(risk) = used {
+        return Ok(risk);
+    }
+    if let Some(risk) = domains::resolve_domain(domain)?.and_then(|d| d.default_risk_profile) {
+        return Ok(risk);
+    }
+    Ok(SETTINGS
+        .read()
+        .map_err(|e| format!("Settings lock error: {}", e))?
+        .default_risk_profile
+        .clone())
+}
+
+/// Domains the user verifies in most, best first
+#[tauri::command]
+pub async fn get_frequent_domains(limit: Option<usize>) -> Result<Vec<FrequentDomain>, String> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).max(1);
+    run_blocking(move || Ok(rank(load_usage()?, Utc::now(), limit))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frecency_ranking() {
+        let now = Utc::now();
+        let long_ago = now - chrono::Duration::days(90);
+        let mut usage = BTreeMap::new();
+        for _ in 0..6 {
+            add_use(&mut usage, "science", "medium", long_ago);
+        }
+        add_use(&mut usage, "medical", "high", now);
+        add_use(&mut usage, "medical", "low", now);
+        add_use(&mut usage, "ancient", "low", now - chrono::Duration::days(400));
+        // Forgotten once a use is recorded more than a year later
+        add_use(&mut usage, "engineering", "low", now);
+        assert!(!usage.contains_key("ancient"));
+
+        let ranked = rank(usage, now, 2);
+        let domains: Vec<&str> = ranked.iter().map(|d| d.domain.as_str()).collect();
+        // 6 uses 90 days ago (0.75) rank below 2 uses today
+        assert_eq!(domains, vec!["medical", "engineering"]);
+        // One use each: the stricter profile wins
+        assert_eq!(ranked[0].risk_profile, "high");
+        assert_eq!(ranked[0].count, 2);
+    }
+}
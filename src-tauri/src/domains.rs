@@ -0,0 +1,351 @@
+// ==================== DOMAIN TAXONOMY ====================
+//
+// A registry of knowledge domains (name, description, default risk profile,
+// parent) stored in domains.json. Once it has entries, verification calls
+// must name a registered domain; names match case-insensitively and resolve
+// to the registered spelling, so "Healthcare" and "healthcare " both become
+// "healthcare". An empty registry accepts any domain, as before. Keywords
+// are optional hints for suggesting a domain from claim text (see
+// domain_suggest.rs).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::settings_validation::RISK_PROFILES;
+use crate::{index, mock, read_audit_trail, repo_state, run_blocking};
+
+const MAX_DOMAIN_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 500;
+const MAX_KEYWORDS: usize = 50;
+/// Registered names listed in an "unknown domain" error
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Serializes read-modify-write cycles on domains.json
+static DOMAINS_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Domain {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Used when a verification doesn't specify a risk profile
+    #[serde(default)]
+    pub default_risk_profile: Option<String>,
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Words that suggest this domain for a claim, lowercase
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DomainRegistry {
+    domains: Vec<Domain>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DomainStats {
+    pub domain: String,
+    /// False for domains that only appear in claims or the audit trail
+    pub registered: bool,
+    pub claims: usize,
+    pub verified_claims: usize,
+    pub average_confidence: Option<f64>,
+    /// Audit trail entries for this domain
+    pub verifications: usize,
+    pub last_verified_at: Option<String>,
+}
+
+fn domains_path() -> std::path::PathBuf {
+    config_path("domains.json")
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+fn load_registry() -> Result<DomainRegistry, String> {
+    let _guard = DOMAINS_LOCK.lock(&domains_path())?;
+    load_json(&domains_path())
+}
+
+fn validate_domain(domain: Domain, existing: &[Domain]) -> Result<Domain, String> {
+    let name = domain.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Domain name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_DOMAIN_NAME_LEN {
+        return Err(format!("Domain name too long (max {} characters)", MAX_DOMAIN_NAME_LEN));
+    }
+    if name.chars().any(|c| c.is_control()) {
+        return Err("Domain name contains control characters".to_string());
+    }
+    let description = domain.description.trim().to_string();
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        return Err(format!("Description too long (max {} characters)", MAX_DESCRIPTION_LEN));
+    }
+    let default_risk_profile = domain.default_risk_profile.filter(|r| !r.trim().is_empty());
+    if let Some(risk) = &default_risk_profile {
+        if !RISK_PROFILES.contains(&risk.as_str()) {
+            return Err(format!(
+                "Invalid risk profile: {} (use {})",
+                risk,
+                RISK_PROFILES.join(", ")
+            ));
+        }
+    }
+
+    let mut keywords: Vec<String> = Vec::new();
+    for keyword in &domain.keywords {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.chars().count() > MAX_DOMAIN_NAME_LEN || keyword.chars().any(|c| c.is_control()) {
+            return Err(format!("Invalid keyword: {}", keyword));
+        }
+        if !keyword.is_empty() && !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+    }
+    if keywords.len() > MAX_KEYWORDS {
+        return Err(format!("Too many keywords (max {})", MAX_KEYWORDS));
+    }
+
+    let parent = match domain.parent.filter(|p| !p.trim().is_empty()) {
+        None => None,
+        Some(parent) => {
+            let found = existing
+                .iter()
+                .find(|d| same_name(&d.name, &parent))
+                .ok_or_else(|| format!("Parent domain not found: {}", parent))?;
+            // Walk up from the parent; reaching `name` would close a loop
+            let mut ancestor = Some(found);
+            let mut depth = 0;
+            while let Some(d) = ancestor {
+                if same_name(&d.name, &name) || depth > existing.len() {
+                    return Err(format!("{} cannot be its own ancestor", name));
+                }
+                ancestor = d
+                    .parent
+                    .as_deref()
+                    .and_then(|p| existing.iter().find(|e| same_name(&e.name, p)));
+                depth += 1;
+            }
+            Some(found.name.clone())
+        }
+    };
+
+    Ok(Domain {
+        name,
+        description,
+        default_risk_profile,
+        parent,
+        keywords,
+    })
+}
+
+/// The registered domain matching `name`. Ok(None) when the registry is
+/// empty, so unconfigured installs keep accepting free-text domains.
+pub fn resolve_domain(name: &str) -> Result<Option<Domain>, String> {
+    let registry = load_registry()?;
+    find_domain(&registry.domains, name).map(|d| d.cloned())
+}
+
+fn find_domain<'a>(domains: &'a [Domain], name: &str) -> Result<Option<&'a Domain>, String> {
+    if domains.is_empty() {
+        return Ok(None);
+    }
+    if let Some(domain) = domains.iter().find(|d| same_name(&d.name, name)) {
+        return Ok(Some(domain));
+    }
+    let mut known: Vec<&str> = domains.iter().map(|d| d.name.as_str()).collect();
+    known.sort_unstable();
+    let more = if known.len() > MAX_SUGGESTIONS { ", …" } else { "" };
+    known.truncate(MAX_SUGGESTIONS);
+    Err(format!(
+        "Unknown domain: {}. Register it first or use one of: {}{}",
+        name.trim(),
+        known.join(", "),
+        more
+    ))
+}
+
+#[tauri::command]
+pub async fn list_domains() -> Result<Vec<Domain>, String> {
+    let mut domains = load_registry()?.domains;
+    domains.sort_by_key(|d| d.name.to_lowercase());
+    Ok(domains)
+}
+
+/// Add a domain, or update the one with the same name (case-insensitive)
+#[tauri::command]
+pub async fn save_domain(domain: Domain) -> Result<Vec<Domain>, String> {
+    {
+        let _guard = DOMAINS_LOCK.lock(&domains_path())?;
+        let mut registry: DomainRegistry = load_json(&domains_path())?;
+        let domain = validate_domain(domain, &registry.domains)?;
+        match registry.domains.iter_mut().find(|d| same_name(&d.name, &domain.name)) {
+            Some(existing) => *existing = domain,
+            None => registry.domains.push(domain),
+        }
+        save_json(&domains_path(), &registry)?;
+    }
+    list_domains().await
+}
+
+/// Remove a domain. Domains that are still a parent of others can't be removed.
+#[tauri::command]
+pub async fn delete_domain(name: String) -> Result<Vec<Domain>, String> {
+    {
+        let _guard = DOMAINS_LOCK.lock(&domains_path())?;
+        let mut registry: DomainRegistry = load_json(&domains_path())?;
+        let children: Vec<&str> = registry
+            .domains
+            .iter()
+            .filter(|d| d.parent.as_deref().is_some_and(|p| same_name(p, &name)))
+            .map(|d| d.name.as_str())
+            .collect();
+        if !children.is_empty() {
+            return Err(format!("{} is the parent of {}", name, children.join(", ")));
+        }
+        let before = registry.domains.len();
+        registry.domains.retain(|d| !same_name(&d.name, &name));
+        if registry.domains.len() == before {
+            return Err(format!("Domain not found: {}", name));
+        }
+        save_json(&domains_path(), &registry)?;
+    }
+    list_domains().await
+}
+
+/// Stats plus the running confidence sum and count for one domain
+type StatsSlot = (DomainStats, f64, usize);
+
+/// The slot for `name`, grouped under the registered spelling where one matches
+fn stats_slot<'a>(stats: &'a mut BTreeMap<String, StatsSlot>, domains: &[Domain], name: &str) -> &'a mut StatsSlot {
+    let registered = domains.iter().find(|d| same_name(&d.name, name));
+    let key = registered.map_or_else(|| name.trim().to_string(), |d| d.name.clone());
+    stats.entry(key.clone()).or_insert_with(|| {
+        let stats = DomainStats {
+            domain: key,
+            registered: registered.is_some(),
+            ..Default::default()
+        };
+        (stats, 0.0, 0)
+    })
+}
+
+fn domain_stats(domains: &[Domain], claims: &[serde_json::Value], audit: &[crate::AuditEntry]) -> Vec<DomainStats> {
+    let mut stats = BTreeMap::new();
+    for domain in domains {
+        stats_slot(&mut stats, domains, &domain.name);
+    }
+    for claim in claims {
+        let Some(name) = claim.get("domain").and_then(|d| d.as_str()) else {
+            continue;
+        };
+        let (stats, confidence_sum, confidence_count) = stats_slot(&mut stats, domains, name);
+        stats.claims += 1;
+        if claim.get("state").and_then(|s| s.as_str()) == Some("verified") {
+            stats.verified_claims += 1;
+        }
+        if let Some(confidence) = claim.get("confidence").and_then(|c| c.as_f64()) {
+            *confidence_sum += confidence;
+            *confidence_count += 1;
+        }
+    }
+    for audit_entry in audit {
+        let (stats, _, _) = stats_slot(&mut stats, domains, &audit_entry.domain);
+        stats.verifications += 1;
+        if stats.last_verified_at.as_deref() < Some(audit_entry.timestamp.as_str()) {
+            stats.last_verified_at = Some(audit_entry.timestamp.clone());
+        }
+    }
+
+    stats
+        .into_values()
+        .map(|(mut stats, sum, count)| {
+            stats.average_confidence = (count > 0).then(|| sum / count as f64);
+            stats
+        })
+        .collect()
+}
+
+/// Claim and verification counts per domain, including unregistered
+/// spellings found in the repo so they can be cleaned up
+#[tauri::command]
+pub async fn get_domain_stats() -> Result<Vec<DomainStats>, String> {
+    run_blocking(|| {
+        let domains = load_registry()?.domains;
+        let claims = if mock::is_active() {
+            mock::claims()
+        } else {
+            let truth_path = repo_state::ready_path()?;
+            index::list_claims(&truth_path, None).claims
+        };
+        let audit = read_audit_trail()?;
+        Ok(domain_stats(&domains, &claims, &audit))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domain(name: &str, parent: Option<&str>) -> Domain {
+        Domain {
+            name: name.to_string(),
+            description: String::new(),
+            default_risk_profile: None,
+            parent: parent.map(str::to_string),
+            keywords: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate_domain() {
+        let existing = vec![domain("science", None), domain("biology", Some("science"))];
+        let saved = validate_domain(domain(" Genetics ", Some("BIOLOGY")), &existing).unwrap();
+        assert_eq!(saved.name, "Genetics");
+        assert_eq!(saved.parent.as_deref(), Some("biology"));
+
+        assert!(validate_domain(domain("", None), &existing).is_err());
+        assert!(validate_domain(domain("x", Some("missing")), &existing).is_err());
+        // Re-parenting science under its own child would form a loop
+        assert!(validate_domain(domain("science", Some("biology")), &existing).is_err());
+        let bad_risk = Domain {
+            default_risk_profile: Some("extreme".to_string()),
+            ..domain("x", None)
+        };
+        assert!(validate_domain(bad_risk, &existing).is_err());
+    }
+
+    #[test]
+    fn test_find_domain() {
+        assert_eq!(find_domain(&[], "anything").unwrap(), None);
+        let domains = vec![domain("healthcare", None)];
+        assert_eq!(
+            find_domain(&domains, " Healthcare ").unwrap().unwrap().name,
+            "healthcare"
+        );
+        let err = find_domain(&domains, "health care").unwrap_err();
+        assert!(err.contains("healthcare"), "{}", err);
+    }
+
+    #[test]
+    fn test_domain_stats_groups_spellings() {
+        let domains = vec![domain("healthcare", None)];
+        let claims = vec![
+            serde_json::json!({ "domain": "Healthcare", "state": "verified", "confidence": 0.8 }),
+            serde_json::json!({ "domain": "healthcare", "state": "draft", "confidence": 0.6 }),
+            serde_json::json!({ "domain": "health-care" }),
+        ];
+        let stats = domain_stats(&domains, &claims, &[]);
+        assert_eq!(stats.len(), 2);
+        let health = stats.iter().find(|s| s.domain == "healthcare").unwrap();
+        assert!(health.registered);
+        assert_eq!((health.claims, health.verified_claims), (2, 1));
+        assert!((health.average_confidence.unwrap() - 0.7).abs() < 1e-9);
+        assert!(!stats.iter().find(|s| s.domain == "health-care").unwrap().registered);
+    }
+}
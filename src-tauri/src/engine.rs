@@ -0,0 +1,188 @@
+// ==================== TRUTHGIT ENGINE BACKENDS ====================
+//
+// Every local TruthGit operation goes through `run_truthgit`, which dispatches
+// to the configured backend:
+// - "cli":      spawn the `truthgit` binary (one subprocess per call)
+// - "embedded": load the TruthGit core in-process (requires the
+//               `embedded-engine` feature); no subprocess is spawned, so it
+//               also works in sandboxed/packaged environments
+// - "mock":     answer from bundled fixtures (see mock.rs)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{execute_with_timeout, repo_crypto, SETTINGS};
+
+pub const BACKEND_CLI: &str = "cli";
+pub const BACKEND_EMBEDDED: &str = "embedded";
+pub const BACKEND_MOCK: &str = "mock";
+
+/// Captured result of a single TruthGit invocation, independent of backend
+#[derive(Debug)]
+pub struct EngineOutput {
+    pub exit_code: i32,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl EngineOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EngineInfo {
+    pub backend_mode: String,
+    pub embedded_available: bool,
+}
+
+/// Default backend: the CLI, also in builds with the embedded engine, which
+/// users opt into by setting `backend_mode` to "embedded"
+pub fn default_backend_mode() -> String {
+    BACKEND_CLI.to_string()
+}
+
+/// Run a TruthGit subcommand through the configured backend.
+/// Callers are responsible for validating `args` beforehand. The engine
+/// reads the repo itself, so nothing but `--version` runs on an encrypted one.
+pub async fn run_truthgit(args: &[String]) -> Result<EngineOutput, String> {
+    // Read settings in a block to ensure lock is released before any await
+    let backend_mode = {
+        let settings = SETTINGS.read().map_err(|e| format!("Settings lock error: {}", e))?;
+        settings.backend_mode.clone()
+    };
+
+    if backend_mode != BACKEND_MOCK && args != ["--version"] {
+        repo_crypto::require_plain_repo("the TruthGit engine")?;
+    }
+    match backend_mode.as_str() {
+        BACKEND_EMBEDDED => run_embedded(args).await,
+        BACKEND_MOCK => Ok(crate::mock::run_truthgit(args)),
+        _ => run_cli(args).await,
+    }
+}
+
+async fn run_cli(args: &[String]) -> Result<EngineOutput, String> {
+    let output = execute_with_timeout("truthgit", args, None).await?;
+    Ok(EngineOutput {
+        exit_code: output.status.code().unwrap_or(-1),
+        stdout: output.stdout,
+        stderr: output.stderr,
+    })
+}
+
+/// The timeout only stops waiting: a Python call can't be interrupted, so one
+/// that hangs keeps its blocking thread, and the engine lock, until it returns.
+#[cfg(feature = "embedded-engine")]
+async fn run_embedded(args: &[String]) -> Result<EngineOutput, String> {
+    use crate::{sanitize_error, SUBPROCESS_TIMEOUT_SECS};
+    use std::time::Duration;
+
+    let args = args.to_vec();
+    let result = tokio::time::timeout(
+        Duration::from_secs(SUBPROCESS_TIMEOUT_SECS),
+        tokio::task::spawn_blocking(move || embedded::run(&args)),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(output)) => output.map_err(|e| sanitize_error(&e)),
+        Ok(Err(e)) => Err(sanitize_error(&format!("Task execution error: {}", e))),
+        Err(_) => Err(format!(
+            "Embedded TruthGit engine timed out after {} seconds.",
+            SUBPROCESS_TIMEOUT_SECS
+        )),
+    }
+}
+
+#[cfg(not(feature = "embedded-engine"))]
+async fn run_embedded(_args: &[String]) -> Result<EngineOutput, String> {
+    Err("Embedded TruthGit engine is not available in this build. \
+        Set backend_mode to \"cli\" or rebuild with the `embedded-engine` feature."
+        .to_string())
+}
+
+#[cfg(feature = "embedded-engine")]
+mod embedded {
+    use super::EngineOutput;
+    use pyo3::prelude::*;
+    use pyo3::sync::GILOnceCell;
+    use std::sync::Mutex;
+
+    // Runs the `truthgit` console-script entry point in-process with captured
+    // stdio, so output is byte-for-byte what the CLI would have printed.
+    const RUNNER: &std::ffi::CStr = cr#"
+import contextlib
+import io
+import sys
+from importlib.metadata import entry_points
+
+
+def run(argv):
+    eps = list(entry_points(group="console_scripts", name="truthgit"))
+    if not eps:
+        raise RuntimeError("the truthgit package is not installed for the embedded interpreter")
+    main = eps[0].load()
+
+    out, err = io.StringIO(), io.StringIO()
+    code = 0
+    saved_argv = sys.argv
+    sys.argv = ["truthgit", *argv]
+    try:
+        with contextlib.redirect_stdout(out), contextlib.redirect_stderr(err):
+            try:
+                main()
+            except SystemExit as exc:
+                if exc.code is None:
+                    code = 0
+                elif isinstance(exc.code, int):
+                    code = exc.code
+                else:
+                    print(exc.code, file=sys.stderr)
+                    code = 1
+    finally:
+        sys.argv = saved_argv
+    return code, out.getvalue(), err.getvalue()
+"#;
+
+    static RUNNER_MODULE: GILOnceCell<Py<PyModule>> = GILOnceCell::new();
+
+    /// The runner swaps the interpreter-wide `sys.argv` and stdio, and the GIL
+    /// is released whenever the CLI does I/O, so calls must not overlap.
+    /// Taken before the GIL, never while holding it.
+    static ENGINE_LOCK: Mutex<()> = Mutex::new(());
+
+    pub fn run(args: &[String]) -> Result<EngineOutput, String> {
+        let _guard = ENGINE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Python::with_gil(|py| {
+            let module = RUNNER_MODULE
+                .get_or_try_init(py, || {
+                    PyModule::from_code(py, RUNNER, c"truthgit_embedded.py", c"truthgit_embedded")
+                        .map(|m| m.unbind())
+                })
+                .map_err(|e| format!("Failed to load embedded TruthGit engine: {}", e))?;
+
+            let (exit_code, stdout, stderr): (i32, String, String) = module
+                .bind(py)
+                .getattr("run")
+                .and_then(|run| run.call1((args.to_vec(),)))
+                .and_then(|result| result.extract())
+                .map_err(|e| format!("Embedded TruthGit engine error: {}", e))?;
+
+            Ok(EngineOutput {
+                exit_code,
+                stdout: stdout.into_bytes(),
+                stderr: stderr.into_bytes(),
+            })
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn get_engine_info() -> Result<EngineInfo, String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(EngineInfo {
+        backend_mode: settings.backend_mode.clone(),
+        embedded_available: cfg!(feature = "embedded-engine"),
+    })
+}
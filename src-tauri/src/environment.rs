@@ -0,0 +1,231 @@
+// ==================== ENVIRONMENT INFO ====================
+//
+// Every version that matters for support triage in one call: the app, the
+// truthgit CLI, the truth repo format, the cached index schema, the settings
+// schema and the OS. Combinations known not to work together come back as
+// warnings, so a bug report can start from `get_environment_info` instead
+// of a round of questions.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::diagnostics::CheckStatus;
+use crate::engine::{self, BACKEND_CLI, BACKEND_EMBEDDED};
+use crate::{get_truth_path, index, mock, run_blocking, sanitize_error, settings_migrations, SETTINGS};
+
+/// Newest repo format this build reads. Repos without a recorded format use
+/// the original layout, format 1.
+pub const SUPPORTED_REPO_FORMAT: u32 = 1;
+/// Oldest CLI whose `safe-verify --json` output verification parses
+const MIN_CLI_VERSION: &str = "0.3.0";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompatibilityWarning {
+    /// "cli", "repo", "index", "engine" or "settings"
+    pub component: String,
+    pub severity: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnvironmentInfo {
+    pub app_version: String,
+    pub os: String,
+    pub os_family: String,
+    pub arch: String,
+    pub backend_mode: String,
+    pub embedded_available: bool,
+    /// As reported by `truthgit --version`
+    pub cli_version: Option<String>,
+    pub repo_path: Option<String>,
+    pub repo_format_version: Option<u32>,
+    pub supported_repo_format: u32,
+    pub index_schema_version: u32,
+    /// Schema of the index cached on disk; None when there is no cache yet
+    pub cached_index_schema: Option<u32>,
+    pub settings_schema_version: u32,
+    pub warnings: Vec<CompatibilityWarning>,
+}
+
+#[derive(Deserialize)]
+struct RepoConfig {
+    #[serde(default = "original_repo_format")]
+    format_version: u32,
+}
+
+fn original_repo_format() -> u32 {
+    1
+}
+
+/// Format recorded in the repo's config.json; 1 when there's none
+fn repo_format(repo: &Path) -> Result<u32, String> {
+    let path = repo.join("config.json");
+    if !path.exists() {
+        return Ok(original_repo_format());
+    }
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| sanitize_error(&format!("Failed to read repo config: {}", e)))?;
+    serde_json::from_str::<RepoConfig>(&content)
+        .map(|c| c.format_version)
+        .map_err(|e| format!("Invalid repo config: {}", e))
+}
+
+/// "truthgit, version 0.4.1" -> [0, 4, 1]
+fn parse_version(text: &str) -> Option<Vec<u32>> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == 'v')
+        .find(|word| {
+            word.contains('.')
+                && word
+                    .split('.')
+                    .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|word| word.split('.').filter_map(|p| p.parse().ok()).collect())
+}
+
+fn warning(component: &str, severity: CheckStatus, message: impl Into<String>) -> CompatibilityWarning {
+    CompatibilityWarning {
+        component: component.to_string(),
+        severity,
+        message: message.into(),
+    }
+}
+
+/// Known-incompatible combinations in `info`
+fn compatibility_warnings(info: &EnvironmentInfo) -> Vec<CompatibilityWarning> {
+    let mut warnings = Vec::new();
+    if info.backend_mode == BACKEND_CLI {
+        match info.cli_version.as_deref().map(|v| (v, parse_version(v))) {
+            None => warnings.push(warning("cli", CheckStatus::Error, "truthgit CLI not found")),
+            Some((_, Some(version))) if version < parse_version(MIN_CLI_VERSION).unwrap_or_default() => {
+                warnings.push(warning(
+                    "cli",
+                    CheckStatus::Error,
+                    format!(
+                        "truthgit CLI is older than {}; upgrade with `pip install --upgrade truthgit`",
+                        MIN_CLI_VERSION
+                    ),
+                ))
+            }
+            Some((raw, None)) => warnings.push(warning(
+                "cli",
+                CheckStatus::Warning,
+                format!("Couldn't read the truthgit CLI version from \"{}\"", raw),
+            )),
+            _ => {}
+        }
+    }
+    if info.backend_mode == BACKEND_EMBEDDED && !info.embedded_available {
+        warnings.push(warning(
+            "engine",
+            CheckStatus::Error,
+            "Embedded engine selected but not compiled into this build",
+        ));
+    }
+    if let Some(format) = info.repo_format_version.filter(|f| *f > SUPPORTED_REPO_FORMAT) {
+        warnings.push(warning(
+            "repo",
+            CheckStatus::Error,
+            format!(
+                "Truth repo format {} was written by a newer truthgit; this app reads up to format {}. Update the app.",
+                format, SUPPORTED_REPO_FORMAT
+            ),
+        ));
+    }
+    if info.settings_schema_version > settings_migrations::SETTINGS_SCHEMA_VERSION {
+        warnings.push(warning(
+            "settings",
+            CheckStatus::Warning,
+            format!(
+                "Settings were saved by a newer app (schema {}); fields this version doesn't know are dropped on save",
+                info.settings_schema_version
+            ),
+        ));
+    }
+    if let Some(schema) = info.cached_index_schema.filter(|s| *s != info.index_schema_version) {
+        warnings.push(warning(
+            "index",
+            CheckStatus::Warning,
+            format!("Cached claim index has schema {}; it will be rebuilt", schema),
+        ));
+    }
+    warnings
+}
+
+/// App, CLI, repo, index and OS versions, with known incompatibilities
+#[tauri::command]
+pub async fn get_environment_info() -> Result<EnvironmentInfo, String> {
+    let (backend_mode, settings_schema_version) = {
+        let settings = SETTINGS.read().map_err(|e| format!("Settings lock error: {}", e))?;
+        (settings.backend_mode.clone(), settings.schema_version)
+    };
+    let cli_version = match engine::run_truthgit(&["--version".to_string()]).await {
+        Ok(output) if output.success() => Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        _ => None,
+    };
+    let (repo_path, repo_format_version, cached_index_schema) = run_blocking(|| {
+        if mock::is_active() {
+            return Ok((None, None, None));
+        }
+        let repo = get_truth_path().filter(|p| p.exists());
+        let format = repo.as_deref().map(repo_format).transpose()?;
+        Ok((
+            repo.map(|p| sanitize_error(&p.to_string_lossy())),
+            format,
+            index::cached_schema_version(),
+        ))
+    })
+    .await?;
+
+    let mut info = EnvironmentInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_family: std::env::consts::FAMILY.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        embedded_available: cfg!(feature = "embedded-engine"),
+        cli_version,
+        repo_path,
+        repo_format_version,
+        supported_repo_format: SUPPORTED_REPO_FORMAT,
+        index_schema_version: index::INDEX_SCHEMA_VERSION,
+        cached_index_schema,
+        settings_schema_version,
+        backend_mode,
+        warnings: Vec::new(),
+    };
+    info.warnings = compatibility_warnings(&info);
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("truthgit, version 0.4.1"), Some(vec![0, 4, 1]));
+        assert_eq!(parse_version("truthgit v1.2"), Some(vec![1, 2]));
+        assert_eq!(parse_version("truthgit (mock backend)"), None);
+    }
+
+    #[test]
+    fn test_compatibility_warnings() {
+        let info = EnvironmentInfo {
+            app_version: "0.2.6".to_string(),
+            os: "linux".to_string(),
+            os_family: "unix".to_string(),
+            arch: "x86_64".to_string(),
+            backend_mode: BACKEND_CLI.to_string(),
+            embedded_available: false,
+            cli_version: Some("truthgit 0.2.9".to_string()),
+            repo_path: None,
+            repo_format_version: Some(SUPPORTED_REPO_FORMAT + 1),
+            supported_repo_format: SUPPORTED_REPO_FORMAT,
+            index_schema_version: 1,
+            cached_index_schema: Some(1),
+            settings_schema_version: 1,
+            warnings: Vec::new(),
+        };
+        let components: Vec<String> = compatibility_warnings(&info).into_iter().map(|w| w.component).collect();
+        assert_eq!(components, vec!["cli", "repo"]);
+    }
+}
@@ -0,0 +1,311 @@
+// ==================== EVIDENCE ====================
+//
+// Supporting material attached to a verification's audit entry, so a
+// verdict can be defended later. Files are stored as content-addressed blob
+// objects under objects/ev/ (named by the SHA-256 of the file, so a copy can
+// be checked with any sha256 tool); URLs are only recorded. The list of
+// attachments lives in evidence.json at the repo root and is shown in the
+// audit trail and claim details.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::identity::{self, Actor};
+use crate::journal::{self, Store};
+use crate::json_store::{load_json, save_json, StoreLock};
+use crate::truth_store::{ObjectId, ObjectKind, TruthStore};
+use crate::consent;
+use crate::{mock, read_audit_trail, read_claim, read_verifications, repo_state, run_blocking, sanitize_error};
+
+const MAX_EVIDENCE_SIZE: u64 = 50 * 1024 * 1024;
+const MAX_URL_LEN: usize = 2048;
+
+/// Serializes read-modify-write cycles on evidence.json
+static EVIDENCE_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    File,
+    Url,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evidence {
+    pub id: String,
+    /// The audit entry (verification) the evidence supports
+    pub audit_id: String,
+    pub kind: EvidenceKind,
+    /// Original file name, or the URL
+    pub name: String,
+    /// SHA-256 of the file contents; the blob sits at objects/ev/<hash>
+    #[serde(default)]
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    pub attached_at: String,
+    #[serde(default)]
+    pub attached_by: Option<Actor>,
+}
+
+fn evidence_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("evidence.json")
+}
+
+fn load_evidence(truth_path: &Path) -> Result<Vec<Evidence>, String> {
+    let _guard = EVIDENCE_LOCK.lock(&evidence_path(truth_path))?;
+    load_json(&evidence_path(truth_path))
+}
+
+/// Compress `bytes` into a blob object with a "blob <len>\0" header. Objects
+/// are content-addressed, so an existing blob is left as is.
+pub(crate) fn store_blob(truth_path: &Path, bytes: &[u8]) -> Result<String, String> {
+    let hash = format!("{:x}", Sha256::digest(bytes));
+    let header = format!("blob {}\0", bytes.len());
+    TruthStore::new(truth_path).write(ObjectKind::Evidence, &ObjectId::parse(&hash)?, &[header.as_bytes(), bytes])?;
+    Ok(hash)
+}
+
+/// An http(s) URL, or None if `input` should be treated as a file path
+fn parse_url(input: &str) -> Result<Option<String>, String> {
+    let lower = input.to_ascii_lowercase();
+    if !lower.starts_with("http://") && !lower.starts_with("https://") {
+        return Ok(None);
+    }
+    if input.len() > MAX_URL_LEN {
+        return Err(format!("URL is too long (max {} characters)", MAX_URL_LEN));
+    }
+    let url = reqwest::Url::parse(input).map_err(|e| format!("Invalid URL: {}", e))?;
+    if url.host_str().is_none() {
+        return Err("URL has no host".to_string());
+    }
+    Ok(Some(url.to_string()))
+}
+
+/// The attachment, and whether it is new rather than one already listed
+fn attach(truth_path: &Path, audit_id: String, file_or_url: &str) -> Result<(Evidence, bool), String> {
+    let (kind, name, hash, size) = match parse_url(file_or_url)? {
+        Some(url) => (EvidenceKind::Url, url, None, None),
+        None => {
+            let path = Path::new(file_or_url);
+            let metadata =
+                fs::metadata(path).map_err(|e| sanitize_error(&format!("Failed to read evidence file: {}", e)))?;
+            if !metadata.is_file() {
+                return Err("Evidence must be a file or an http(s) URL".to_string());
+            }
+            if metadata.len() > MAX_EVIDENCE_SIZE {
+                return Err("Evidence file is too large (max 50 MB)".to_string());
+            }
+            let bytes = fs::read(path).map_err(|e| sanitize_error(&format!("Failed to read evidence file: {}", e)))?;
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "evidence".to_string());
+            let size = bytes.len() as u64;
+            (
+                EvidenceKind::File,
+                name,
+                Some(store_blob(truth_path, &bytes)?),
+                Some(size),
+            )
+        }
+    };
+
+    let _guard = EVIDENCE_LOCK.lock(&evidence_path(truth_path))?;
+    let mut attached: Vec<Evidence> = load_json(&evidence_path(truth_path))?;
+    // Attaching the same file or URL twice returns the first attachment
+    if let Some(existing) = attached.iter().find(|e| {
+        e.audit_id == audit_id && e.kind == kind && if hash.is_some() { e.hash == hash } else { e.name == name }
+    }) {
+        return Ok((existing.clone(), false));
+    }
+    let evidence = Evidence {
+        id: uuid::Uuid::new_v4().to_string(),
+        audit_id,
+        kind,
+        name,
+        hash,
+        size,
+        attached_at: chrono::Utc::now().to_rfc3339(),
+        attached_by: identity::current(),
+    };
+    attached.push(evidence.clone());
+    save_json(&evidence_path(truth_path), &attached)?;
+    Ok((evidence, true))
+}
+
+/// Put an attachment back as it was, or drop it if it didn't exist (undo).
+/// Blobs are content-addressed and stay in objects/ev/ either way.
+pub(crate) fn restore(id: &str, before: Option<serde_json::Value>) -> Result<(), String> {
+    let truth_path = repo_state::ready_path()?;
+    let before: Option<Evidence> = before
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| format!("Invalid journal entry: {}", e))?;
+    let _guard = EVIDENCE_LOCK.lock(&evidence_path(&truth_path))?;
+    let mut attached: Vec<Evidence> = load_json(&evidence_path(&truth_path))?;
+    attached.retain(|e| e.id != id);
+    attached.extend(before);
+    attached.sort_by(|a, b| a.attached_at.cmp(&b.attached_at));
+    save_json(&evidence_path(&truth_path), &attached)
+}
+
+/// Attach a file (copied into the repo) or an http(s) URL to the audit entry
+/// of a verification
+#[tauri::command]
+pub async fn attach_evidence(audit_id: String, file_or_url: String) -> Result<Evidence, String> {
+    if mock::is_active() {
+        return Err("Evidence attachments are disabled in mock mode".to_string());
+    }
+    let audit_id = audit_id.trim().to_string();
+    let file_or_url = file_or_url.trim().to_string();
+    if file_or_url.is_empty() {
+        return Err("Choose a file or enter a URL".to_string());
+    }
+    if parse_url(&file_or_url)?.is_none() {
+        consent::require_read(Path::new(&file_or_url)).await?;
+    }
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        if !read_audit_trail()?.iter().any(|e| e.id == audit_id) {
+            return Err(format!("Audit entry not found: {}", audit_id));
+        }
+        let (evidence, created) = attach(&truth_path, audit_id, &file_or_url)?;
+        if created {
+            let description = format!("Attached {} to audit entry {}", evidence.name, evidence.audit_id);
+            journal::record::<Evidence>(Store::Evidence, &evidence.id, description, None);
+        }
+        log::info!(
+            "Attached {:?} evidence to audit entry {}",
+            evidence.kind,
+            evidence.audit_id
+        );
+        Ok(evidence)
+    })
+    .await
+}
+
+/// Detach evidence from its audit entry; `undo_last_change` re-attaches it
+#[tauri::command]
+pub async fn remove_evidence(id: String) -> Result<(), String> {
+    if mock::is_active() {
+        return Err("Evidence attachments are disabled in mock mode".to_string());
+    }
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        let removed = {
+            let _guard = EVIDENCE_LOCK.lock(&evidence_path(&truth_path))?;
+            let mut attached: Vec<Evidence> = load_json(&evidence_path(&truth_path))?;
+            let pos = attached
+                .iter()
+                .position(|e| e.id == id)
+                .ok_or_else(|| "Evidence not found".to_string())?;
+            let removed = attached.remove(pos);
+            save_json(&evidence_path(&truth_path), &attached)?;
+            removed
+        };
+        let description = format!("Removed {} from audit entry {}", removed.name, removed.audit_id);
+        journal::record(Store::Evidence, &id, description, Some(&removed));
+        Ok(())
+    })
+    .await
+}
+
+/// Evidence attached to one audit entry, oldest first
+#[tauri::command]
+pub async fn list_evidence(audit_id: String) -> Result<Vec<Evidence>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        Ok(load_evidence(&truth_path)?
+            .into_iter()
+            .filter(|e| e.audit_id == audit_id)
+            .collect())
+    })
+    .await
+}
+
+/// Evidence for every verification of a claim: audit entries referenced by
+/// the claim's verification objects, plus entries recorded for its text
+pub(crate) fn claim_evidence(truth_path: &Path, hash: &str) -> Result<Vec<Evidence>, String> {
+    let attached = load_evidence(truth_path)?;
+    if attached.is_empty() {
+        return Ok(attached);
+    }
+    let content = read_claim(hash.to_string())?
+        .get("content")
+        .and_then(|c| c.as_str())
+        .map(str::to_string);
+    let mut audit_ids: Vec<String> = read_verifications()?
+        .verifications
+        .iter()
+        .filter(|v| v.claim_hash == hash)
+        .filter_map(|v| v.audit_ref.clone())
+        .collect();
+    if let Some(content) = content {
+        audit_ids.extend(
+            read_audit_trail()?
+                .into_iter()
+                .filter(|e| e.claim == content)
+                .map(|e| e.id),
+        );
+    }
+    Ok(attached
+        .into_iter()
+        .filter(|e| audit_ids.contains(&e.audit_id))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn list_claim_evidence(hash: String) -> Result<Vec<Evidence>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        claim_evidence(&truth_path, &hash)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use crate::truth_store::inflate_object;
+
+    #[test]
+    fn test_attach_file_and_url() {
+        let tmp = TempDir::new("truthgit-evidence");
+        let root = tmp.path();
+        fs::create_dir_all(root).unwrap();
+        let file = root.join("report.pdf");
+        fs::write(&file, b"%PDF-1.4 lab report").unwrap();
+
+        let (first, created) = attach(root, "audit-1".to_string(), file.to_str().unwrap()).unwrap();
+        assert!(created);
+        assert_eq!(first.kind, EvidenceKind::File);
+        assert_eq!(first.name, "report.pdf");
+        let hash = first.hash.clone().unwrap();
+        let blob = TruthStore::new(root).object_path(ObjectKind::Evidence, &ObjectId::parse(&hash).unwrap());
+        let stored = inflate_object(&blob, 1024).unwrap();
+        assert_eq!(stored, b"blob 19\0%PDF-1.4 lab report");
+
+        // Same file again is the same attachment
+        let (again, created) = attach(root, "audit-1".to_string(), file.to_str().unwrap()).unwrap();
+        assert_eq!(again.id, first.id);
+        assert!(!created);
+
+        let (url, _) = attach(root, "audit-1".to_string(), "https://example.org/study").unwrap();
+        assert_eq!(url.kind, EvidenceKind::Url);
+        assert_eq!(url.hash, None);
+        assert_eq!(load_evidence(root).unwrap().len(), 2);
+
+        assert!(attach(root, "audit-1".to_string(), root.to_str().unwrap()).is_err());
+        assert!(parse_url("https://").is_err());
+    }
+}
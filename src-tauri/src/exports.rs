@@ -0,0 +1,285 @@
+// ==================== VERIFICATION EXPORT ====================
+//
+// Writes verification objects (objects/vf) to CSV or XLSX for analysis in
+// spreadsheets, with a choice of columns. `VerificationFilter` is shared with
+// governance reports so both select verifications the same way.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::verification::Verification;
+use crate::{metrics, read_verifications, run_blocking, sanitize_error};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerificationFilter {
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// e.g. "PASSED"
+    #[serde(default)]
+    pub verdict: Option<String>,
+    /// RFC 3339 timestamp or date prefix ("2026-01"), inclusive
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+impl VerificationFilter {
+    /// Whether `timestamp` falls in the range; bounds may be prefixes
+    pub fn in_range(&self, timestamp: &str) -> bool {
+        non_empty(&self.since).is_none_or(|s| timestamp >= s)
+            && non_empty(&self.until).is_none_or(|u| timestamp.get(..u.len()).unwrap_or(timestamp) <= u)
+    }
+
+    pub fn matches_domain(&self, domain: &str) -> bool {
+        non_empty(&self.domain).is_none_or(|d| domain.trim().eq_ignore_ascii_case(d))
+    }
+
+    pub fn matches(&self, verification: &Verification) -> bool {
+        self.matches_domain(&verification.domain)
+            && non_empty(&self.verdict).is_none_or(|v| verification.verdict.eq_ignore_ascii_case(v))
+            && self.in_range(&verification.timestamp)
+    }
+}
+
+pub fn field<'a>(value: &'a serde_json::Value, name: &str) -> &'a str {
+    value.get(name).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Claim,
+    ClaimHash,
+    Domain,
+    Status,
+    Confidence,
+    Timestamp,
+    AuditRef,
+    /// Hash of the verification object itself
+    Hash,
+    Validators,
+}
+
+const DEFAULT_COLUMNS: &[Column] = &[
+    Column::Claim,
+    Column::Domain,
+    Column::Status,
+    Column::Confidence,
+    Column::Timestamp,
+    Column::AuditRef,
+];
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Claim => "Claim",
+            Self::ClaimHash => "Claim hash",
+            Self::Domain => "Domain",
+            Self::Status => "Status",
+            Self::Confidence => "Confidence",
+            Self::Timestamp => "Timestamp",
+            Self::AuditRef => "Audit ref",
+            Self::Hash => "Verification hash",
+            Self::Validators => "Validators",
+        }
+    }
+}
+
+enum Cell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+fn cell(verification: &Verification, column: Column) -> Cell {
+    let text = |value: &str| Cell::Text(value.to_string());
+    match column {
+        Column::Claim => text(&verification.claim),
+        Column::ClaimHash => text(&verification.claim_hash),
+        Column::Domain => text(&verification.domain),
+        Column::Status => text(&verification.verdict),
+        Column::Confidence => verification.confidence.map_or(Cell::Empty, Cell::Number),
+        Column::Timestamp => text(&verification.timestamp),
+        Column::AuditRef => text(verification.audit_ref.as_deref().unwrap_or("")),
+        Column::Hash => text(&verification.hash),
+        Column::Validators => text(&verification.validators.join(", ")),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub format: ExportFormat,
+    pub rows: usize,
+}
+
+/// Spreadsheets run text starting with these as formulas
+fn defuse_formula(text: String) -> String {
+    if text.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", text)
+    } else {
+        text
+    }
+}
+
+fn write_csv(path: &Path, columns: &[Column], rows: &[Verification]) -> Result<(), String> {
+    let error = |e: csv::Error| sanitize_error(&format!("Failed to write CSV: {}", e));
+    let mut writer = csv::Writer::from_path(path).map_err(error)?;
+    writer.write_record(columns.iter().map(|c| c.header())).map_err(error)?;
+    for verification in rows {
+        let record = columns.iter().map(|&column| match cell(verification, column) {
+            Cell::Text(text) => defuse_formula(text),
+            Cell::Number(number) => number.to_string(),
+            Cell::Empty => String::new(),
+        });
+        writer.write_record(record).map_err(error)?;
+    }
+    writer
+        .flush()
+        .map_err(|e| sanitize_error(&format!("Failed to write CSV: {}", e)))
+}
+
+fn write_xlsx(path: &Path, columns: &[Column], rows: &[Verification]) -> Result<(), String> {
+    use rust_xlsxwriter::{Format, Workbook};
+
+    let error = |e: rust_xlsxwriter::XlsxError| sanitize_error(&format!("Failed to write XLSX: {}", e));
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Verifications").map_err(error)?;
+    let bold = Format::new().set_bold();
+    let percent = Format::new().set_num_format("0.0%");
+    for (col, column) in columns.iter().enumerate() {
+        sheet
+            .write_string_with_format(0, col as u16, column.header(), &bold)
+            .map_err(error)?;
+    }
+    for (row, verification) in rows.iter().enumerate() {
+        let row = row as u32 + 1;
+        for (col, &column) in columns.iter().enumerate() {
+            let col = col as u16;
+            match cell(verification, column) {
+                Cell::Text(text) => sheet.write_string(row, col, text).map(|_| ()),
+                Cell::Number(number) => sheet.write_number_with_format(row, col, number, &percent).map(|_| ()),
+                Cell::Empty => Ok(()),
+            }
+            .map_err(error)?;
+        }
+    }
+    sheet.set_freeze_panes(1, 0).map_err(error)?;
+    workbook.save(path).map_err(error)
+}
+
+fn format_for(path: &str, format: Option<ExportFormat>) -> Result<ExportFormat, String> {
+    if let Some(format) = format {
+        return Ok(format);
+    }
+    match Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("csv") => Ok(ExportFormat::Csv),
+        Some("xlsx") => Ok(ExportFormat::Xlsx),
+        _ => Err("Can't tell the export format from the file name; use .csv or .xlsx".to_string()),
+    }
+}
+
+/// Write the verifications matching `filter`, newest first, to `path`.
+/// The format defaults to the file extension; columns default to claim,
+/// domain, status, confidence, timestamp and audit ref.
+#[tauri::command]
+pub async fn export_verifications(
+    filter: Option<VerificationFilter>,
+    path: String,
+    columns: Option<Vec<Column>>,
+    format: Option<ExportFormat>,
+) -> Result<ExportSummary, String> {
+    let format = format_for(&path, format)?;
+    let columns = columns
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| DEFAULT_COLUMNS.to_vec());
+    let filter = filter.unwrap_or_default();
+
+    let started = Instant::now();
+    let summary = run_blocking(move || {
+        let rows: Vec<Verification> = read_verifications()?
+            .verifications
+            .into_iter()
+            .filter(|v| filter.matches(v))
+            .collect();
+        match format {
+            ExportFormat::Csv => write_csv(Path::new(&path), &columns, &rows)?,
+            ExportFormat::Xlsx => write_xlsx(Path::new(&path), &columns, &rows)?,
+        }
+        log::info!("Exported {} verifications as {:?}", rows.len(), format);
+        Ok(ExportSummary {
+            path,
+            format,
+            rows: rows.len(),
+        })
+    })
+    .await;
+    metrics::record("export", started, summary.is_ok());
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_filter() {
+        let verification = Verification::from_value(serde_json::json!({
+            "domain": "Science", "status": "PASSED", "timestamp": "2026-01-31T10:00:00Z",
+        }));
+        let filter = |domain: &str, verdict: &str, until: &str| VerificationFilter {
+            domain: Some(domain.to_string()),
+            verdict: Some(verdict.to_string()),
+            since: None,
+            until: Some(until.to_string()),
+        };
+        assert!(filter("science", "passed", "2026-01").matches(&verification));
+        assert!(filter("", "", "2026-01-31").matches(&verification));
+        assert!(!filter("legal", "", "").matches(&verification));
+        assert!(!filter("", "", "2025-12").matches(&verification));
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let tmp = TempDir::new("truthgit-export");
+        let path = tmp.path().join("export.csv");
+        let rows = vec![
+            serde_json::json!({ "claim": "=HYPERLINK(\"x\")", "confidence": 0.5, "validators": ["a", "b"] }),
+            serde_json::json!({ "claim": "Water, at sea level", "verdict": "PASSED" }),
+        ]
+        .into_iter()
+        .map(Verification::from)
+        .collect::<Vec<_>>();
+        write_csv(
+            &path,
+            &[Column::Claim, Column::Status, Column::Confidence, Column::Validators],
+            &rows,
+        )
+        .unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(lines[0], "Claim,Status,Confidence,Validators");
+        assert_eq!(lines[1], "\"'=HYPERLINK(\"\"x\"\")\",,0.5,\"a, b\"");
+        assert_eq!(lines[2], "\"Water, at sea level\",PASSED,,");
+    }
+}
@@ -0,0 +1,579 @@
+// ==================== FEEDS ====================
+//
+// Publications watched for claims to verify. A source is an RSS or Atom
+// feed, or a plain web page; a background loop fetches each one every
+// `feeds.check_hours`, pulls candidate claims out of what it hasn't seen
+// before (feed entries by id, pages claim by claim) with the file-intake
+// heuristic, and files them in a review inbox. Sources and the inbox live
+// in feeds.json in the truth repo. Adding a source asks for consent to
+// reach its host; scheduled fetches only go to hosts already allowed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::coverage::claim_key;
+use crate::file_intake::extract_claims;
+use crate::identity::{self, Actor};
+use crate::json_store::{load_json, save_json, StoreLock};
+use crate::tasks::{self, TaskHandle};
+use crate::web_archive::readable_text;
+use crate::{consent, http_client, mock, repo_state, run_blocking, SETTINGS};
+
+pub const INBOX_CHANGED_EVENT: &str = "feeds://inbox";
+
+/// How often the scheduler looks for sources that are due
+const SCHEDULER_TICK: Duration = Duration::from_secs(15 * 60);
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_FETCH_SIZE: usize = 5 * 1024 * 1024;
+const MAX_URL_LEN: usize = 2048;
+const MAX_SOURCES: usize = 100;
+/// Newest feed entries read per check
+const MAX_ENTRIES_PER_CHECK: usize = 25;
+/// Candidates taken from one entry, or from one check of a page
+const MAX_CLAIMS_PER_ITEM: usize = 10;
+/// Entry ids and page claims remembered per source
+const MAX_SEEN: usize = 1000;
+/// Oldest inbox items are dropped past this
+const MAX_INBOX: usize = 500;
+
+/// Serializes read-modify-write cycles on feeds.json
+static FEEDS_LOCK: StoreLock = StoreLock::new();
+
+/// Set while sources are being checked, so the scheduler and a manual run don't overlap
+static CHECK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeedSettings {
+    /// Hours between fetches of each source; 0 turns scheduled checks off
+    pub check_hours: u32,
+}
+
+impl Default for FeedSettings {
+    fn default() -> Self {
+        Self { check_hours: 6 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Feed,
+    Page,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchedSource {
+    pub id: String,
+    pub url: String,
+    /// From the feed or page, once fetched
+    pub title: Option<String>,
+    /// None until the first successful fetch
+    pub kind: Option<SourceKind>,
+    pub added_at: String,
+    pub added_by: Option<Actor>,
+    pub checked_at: Option<String>,
+    /// Why the last check failed, cleared by the next successful one
+    pub error: Option<String>,
+    /// Entry ids (feeds) or claim keys (pages) already taken, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub seen: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboxItem {
+    pub id: String,
+    pub source_id: String,
+    pub source_url: String,
+    pub claim: String,
+    /// The feed entry or page the claim was found in
+    #[serde(default)]
+    pub item_title: Option<String>,
+    #[serde(default)]
+    pub item_url: Option<String>,
+    #[serde(default)]
+    pub published: Option<String>,
+    pub found_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct FeedStore {
+    sources: Vec<WatchedSource>,
+    inbox: Vec<InboxItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedCheck {
+    pub sources_checked: usize,
+    pub failed: usize,
+    /// Items added to the inbox
+    pub new_items: usize,
+}
+
+/// A fetched source, reduced to text
+#[derive(Debug)]
+struct Fetched {
+    kind: SourceKind,
+    title: Option<String>,
+    entries: Vec<FetchedEntry>,
+}
+
+#[derive(Debug)]
+struct FetchedEntry {
+    /// Feed entry id; empty for a page
+    key: String,
+    title: Option<String>,
+    link: Option<String>,
+    published: Option<String>,
+    text: String,
+}
+
+/// Clears the running flag however the check ends
+struct CheckGuard;
+
+impl CheckGuard {
+    fn acquire() -> Result<Self, String> {
+        if CHECK_RUNNING.swap(true, Ordering::SeqCst) {
+            return Err("Sources are already being checked".to_string());
+        }
+        Ok(CheckGuard)
+    }
+}
+
+impl Drop for CheckGuard {
+    fn drop(&mut self) {
+        CHECK_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+fn feeds_path() -> Result<PathBuf, String> {
+    let truth_path = repo_state::ready_path()?;
+    Ok(truth_path.join("feeds.json"))
+}
+
+fn load_store() -> Result<FeedStore, String> {
+    let path = feeds_path()?;
+    let _guard = FEEDS_LOCK.lock(&path)?;
+    load_json(&path)
+}
+
+fn update_store<T>(change: impl FnOnce(&mut FeedStore) -> Result<T, String>) -> Result<T, String> {
+    let path = feeds_path()?;
+    let _guard = FEEDS_LOCK.lock(&path)?;
+    let mut store = load_json(&path)?;
+    let result = change(&mut store)?;
+    save_json(&path, &store)?;
+    Ok(result)
+}
+
+fn validate_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    if url.len() > MAX_URL_LEN {
+        return Err(format!("URL is too long (max {} characters)", MAX_URL_LEN));
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Only http(s) sources can be watched".to_string());
+    }
+    Ok(parsed.to_string())
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Readable text of a feed field that may hold HTML
+fn feed_text(text: &str) -> String {
+    if text.contains('<') {
+        readable_text(text).1
+    } else {
+        text.trim().to_string()
+    }
+}
+
+fn parse_source(content_type: &str, body: &[u8]) -> Result<Fetched, String> {
+    let page = |title: Option<String>, text: String| Fetched {
+        kind: SourceKind::Page,
+        title,
+        entries: vec![FetchedEntry {
+            key: String::new(),
+            title: None,
+            link: None,
+            published: None,
+            text,
+        }],
+    };
+    if content_type.starts_with("text/html") || content_type.contains("xhtml") {
+        let (title, text) = readable_text(&String::from_utf8_lossy(body));
+        return Ok(page(title, text));
+    }
+    if content_type.starts_with("text/plain") {
+        return Ok(page(None, String::from_utf8_lossy(body).trim().to_string()));
+    }
+
+    let feed = feed_rs::parser::parse(body).map_err(|e| format!("Not an RSS or Atom feed: {}", e))?;
+    let entries = feed
+        .entries
+        .into_iter()
+        .take(MAX_ENTRIES_PER_CHECK)
+        .map(|entry| {
+            let title = entry
+                .title
+                .map(|t| one_line(&feed_text(&t.content)))
+                .filter(|t| !t.is_empty());
+            let body = entry
+                .content
+                .and_then(|c| c.body)
+                .or_else(|| entry.summary.map(|s| s.content))
+                .unwrap_or_default();
+            FetchedEntry {
+                key: entry.id,
+                link: entry.links.into_iter().next().map(|l| l.href),
+                published: entry.published.or(entry.updated).map(|t| t.to_rfc3339()),
+                text: feed_text(&body),
+                title,
+            }
+        })
+        .collect();
+    Ok(Fetched {
+        kind: SourceKind::Feed,
+        title: feed.title.map(|t| one_line(&t.content)).filter(|t| !t.is_empty()),
+        entries,
+    })
+}
+
+/// Inbox items for what `source` hasn't seen in `fetched`, marking it seen
+fn collect_new(source: &mut WatchedSource, fetched: Fetched, now: &str) -> Vec<InboxItem> {
+    let mut seen: HashSet<String> = source.seen.iter().cloned().collect();
+    let mut items = Vec::new();
+    for entry in fetched.entries {
+        if fetched.kind == SourceKind::Feed && !seen.insert(entry.key.clone()) {
+            continue;
+        }
+        if fetched.kind == SourceKind::Feed {
+            source.seen.push(entry.key.clone());
+        }
+        // Pages are re-read whole; only sentences not taken before are new
+        let claims: Vec<String> = extract_claims(&entry.text)
+            .into_iter()
+            .filter(|claim| fetched.kind == SourceKind::Feed || !seen.contains(&claim_key(claim)))
+            .take(MAX_CLAIMS_PER_ITEM)
+            .collect();
+        for claim in claims {
+            if fetched.kind == SourceKind::Page {
+                seen.insert(claim_key(&claim));
+                source.seen.push(claim_key(&claim));
+            }
+            items.push(InboxItem {
+                id: uuid::Uuid::new_v4().to_string(),
+                source_id: source.id.clone(),
+                source_url: source.url.clone(),
+                claim,
+                item_title: entry.title.clone().or_else(|| fetched.title.clone()),
+                item_url: entry.link.clone().or_else(|| Some(source.url.clone())),
+                published: entry.published.clone(),
+                found_at: now.to_string(),
+            });
+        }
+    }
+    let excess = source.seen.len().saturating_sub(MAX_SEEN);
+    source.seen.drain(..excess);
+    if source.title.is_none() {
+        source.title = fetched.title;
+    }
+    source.kind = Some(fetched.kind);
+    items
+}
+
+async fn fetch_source(url: &str) -> Result<Fetched, String> {
+    if !consent::is_host_allowed(url) {
+        return Err("Fetching from this host hasn't been allowed".to_string());
+    }
+    let fetched = http_client::fetch_allowed(url, "source", FETCH_TIMEOUT, MAX_FETCH_SIZE).await?;
+    parse_source(&fetched.content_type, &fetched.bytes)
+}
+
+fn is_due(source: &WatchedSource, now: chrono::DateTime<chrono::Utc>, check_hours: u32) -> bool {
+    match source.checked_at.as_deref().map(chrono::DateTime::parse_from_rfc3339) {
+        Some(Ok(checked)) => now - checked.with_timezone(&chrono::Utc) >= chrono::Duration::hours(check_hours.into()),
+        _ => true,
+    }
+}
+
+/// Fetch the sources with the given ids and file what's new in the inbox
+async fn check(app: &AppHandle, task: &TaskHandle, ids: Vec<String>) -> Result<FeedCheck, String> {
+    let _running = CheckGuard::acquire()?;
+    let sources: Vec<WatchedSource> = run_blocking(load_store)
+        .await?
+        .sources
+        .into_iter()
+        .filter(|s| ids.contains(&s.id))
+        .collect();
+    let total = sources.len() as u64;
+    let mut result = FeedCheck {
+        sources_checked: 0,
+        failed: 0,
+        new_items: 0,
+    };
+    for (done, source) in sources.into_iter().enumerate() {
+        task.check_cancelled()?;
+        let fetched = fetch_source(&source.url).await;
+        if let Err(e) = &fetched {
+            log::warn!("Check of source {} failed: {}", source.url, e);
+            result.failed += 1;
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        let added = run_blocking(move || {
+            update_store(|store| {
+                // The source may have been removed while it was fetched
+                let Some(current) = store.sources.iter_mut().find(|s| s.id == source.id) else {
+                    return Ok(0);
+                };
+                current.checked_at = Some(now.clone());
+                let items = match fetched {
+                    Ok(fetched) => {
+                        current.error = None;
+                        collect_new(current, fetched, &now)
+                    }
+                    Err(e) => {
+                        current.error = Some(e);
+                        Vec::new()
+                    }
+                };
+                let added = items.len();
+                store.inbox.extend(items);
+                let excess = store.inbox.len().saturating_sub(MAX_INBOX);
+                store.inbox.drain(..excess);
+                Ok(added)
+            })
+        })
+        .await?;
+        result.sources_checked += 1;
+        result.new_items += added;
+        let done = done as u64 + 1;
+        task.progress(done, total, format!("Checked {} of {} sources", done, total));
+    }
+    if result.new_items > 0 {
+        if let Err(e) = app.emit(INBOX_CHANGED_EVENT, result.new_items) {
+            log::warn!("Failed to emit inbox event: {}", e);
+        }
+    }
+    Ok(result)
+}
+
+/// Start the background loop that fetches sources as they come due
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCHEDULER_TICK).await;
+            let check_hours = SETTINGS.read().map(|s| s.feeds.check_hours).unwrap_or(0);
+            if check_hours == 0 || mock::is_active() || CHECK_RUNNING.load(Ordering::SeqCst) {
+                continue;
+            }
+            let now = chrono::Utc::now();
+            let due: Vec<String> = match run_blocking(load_store).await {
+                Ok(store) => store
+                    .sources
+                    .into_iter()
+                    .filter(|source| is_due(source, now, check_hours))
+                    .map(|source| source.id)
+                    .collect(),
+                Err(e) => {
+                    log::warn!("Failed to read feeds: {}", e);
+                    continue;
+                }
+            };
+            if due.is_empty() {
+                continue;
+            }
+            let handle = app.clone();
+            let result = tasks::run(&app, None, "feeds", "Checking sources", |task| async move {
+                check(&handle, &task, due).await
+            })
+            .await;
+            if let Err(e) = result {
+                log::warn!("Scheduled check of sources stopped: {}", e);
+            }
+        }
+    });
+}
+
+/// Watch a feed or page; asks for consent to reach its host
+#[tauri::command]
+pub async fn add_feed_source(url: String) -> Result<WatchedSource, String> {
+    if mock::is_active() {
+        return Err("Sources are disabled in mock mode".to_string());
+    }
+    let url = validate_url(&url)?;
+    consent::require_host(&url).await?;
+    run_blocking(move || {
+        let source = update_store(|store| {
+            if let Some(existing) = store.sources.iter().find(|s| s.url == url) {
+                return Ok(WatchedSource {
+                    seen: Vec::new(),
+                    ..existing.clone()
+                });
+            }
+            if store.sources.len() >= MAX_SOURCES {
+                return Err(format!("Too many sources (max {})", MAX_SOURCES));
+            }
+            let source = WatchedSource {
+                id: uuid::Uuid::new_v4().to_string(),
+                url: url.clone(),
+                added_at: chrono::Utc::now().to_rfc3339(),
+                added_by: identity::current(),
+                ..Default::default()
+            };
+            store.sources.push(source.clone());
+            Ok(source)
+        })?;
+        log::info!("Watching source {}", source.url);
+        Ok(source)
+    })
+    .await
+}
+
+/// Stop watching a source; claims it already filed stay in the inbox
+#[tauri::command]
+pub async fn remove_feed_source(id: String) -> Result<bool, String> {
+    run_blocking(move || {
+        update_store(|store| {
+            let before = store.sources.len();
+            store.sources.retain(|s| s.id != id);
+            Ok(store.sources.len() < before)
+        })
+    })
+    .await
+}
+
+/// Watched sources, in the order they were added
+#[tauri::command]
+pub async fn list_feed_sources() -> Result<Vec<WatchedSource>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(|| {
+        let mut sources = load_store()?.sources;
+        for source in &mut sources {
+            source.seen.clear();
+        }
+        Ok(sources)
+    })
+    .await
+}
+
+/// Fetch every source now, whether or not it is due
+#[tauri::command]
+pub async fn check_feed_sources(app: AppHandle, task_id: Option<String>) -> Result<FeedCheck, String> {
+    if mock::is_active() {
+        return Err("Sources are disabled in mock mode".to_string());
+    }
+    let ids: Vec<String> = run_blocking(load_store)
+        .await?
+        .sources
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    let handle = app.clone();
+    tasks::run(&app, task_id, "feeds", "Checking sources", |task| async move {
+        check(&handle, &task, ids).await
+    })
+    .await
+}
+
+/// Claims found in watched sources and not yet dismissed, newest first
+#[tauri::command]
+pub async fn get_inbox() -> Result<Vec<InboxItem>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(|| {
+        let mut inbox = load_store()?.inbox;
+        inbox.reverse();
+        Ok(inbox)
+    })
+    .await
+}
+
+/// Remove an item from the inbox; the claim won't be filed again
+#[tauri::command]
+pub async fn dismiss_inbox_item(id: String) -> Result<bool, String> {
+    run_blocking(move || {
+        update_store(|store| {
+            let before = store.inbox.len();
+            store.inbox.retain(|item| item.id != id);
+            Ok(store.inbox.len() < before)
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+        <rss version="2.0"><channel><title>Science Daily</title>
+        <item><guid>a1</guid><title>Boiling points</title><link>https://example.org/a1</link>
+        <description>&lt;p&gt;Water boils at 100 degrees at sea level. Short.&lt;/p&gt;</description></item>
+        <item><guid>b2</guid><title>Density</title>
+        <description>Ice is less dense than liquid water.</description></item>
+        </channel></rss>"#;
+
+    #[test]
+    fn test_feed_entries_are_taken_once() {
+        let mut source = WatchedSource {
+            id: "s".to_string(),
+            url: "https://example.org/feed".to_string(),
+            ..Default::default()
+        };
+        let fetched = parse_source("application/rss+xml", RSS.as_bytes()).unwrap();
+        let items = collect_new(&mut source, fetched, "2026-03-01T00:00:00Z");
+        let claims: Vec<&str> = items.iter().map(|i| i.claim.as_str()).collect();
+        assert_eq!(
+            claims,
+            [
+                "Water boils at 100 degrees at sea level.",
+                "Ice is less dense than liquid water."
+            ]
+        );
+        assert_eq!(items[0].item_title.as_deref(), Some("Boiling points"));
+        assert_eq!(items[0].item_url.as_deref(), Some("https://example.org/a1"));
+        assert_eq!(
+            (source.title.as_deref(), source.kind),
+            (Some("Science Daily"), Some(SourceKind::Feed))
+        );
+
+        let again = parse_source("application/rss+xml", RSS.as_bytes()).unwrap();
+        assert!(collect_new(&mut source, again, "2026-03-02T00:00:00Z").is_empty());
+        assert!(parse_source("application/xml", b"not a feed").is_err());
+    }
+
+    #[test]
+    fn test_pages_yield_new_sentences_only() {
+        let mut source = WatchedSource::default();
+        let page = |body: &str| parse_source("text/html; charset=utf-8", body.as_bytes()).unwrap();
+        let first = collect_new(
+            &mut source,
+            page("<title>Facts</title><p>The sun is a main sequence star of type G.</p>"),
+            "t1",
+        );
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].item_title.as_deref(), Some("Facts"));
+
+        let second = collect_new(
+            &mut source,
+            page("<p>The sun is a main sequence star of type G.</p><p>Mars has two small moons orbiting it.</p>"),
+            "t2",
+        );
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].claim, "Mars has two small moons orbiting it.");
+    }
+}
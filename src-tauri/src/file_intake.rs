@@ -0,0 +1,326 @@
+// ==================== FILE INTAKE ====================
+//
+// Files dropped on the window (or picked via `intake_files`) are read, their
+// text extracted, and candidate claims pulled out for batch verification.
+// Claim extraction is a local heuristic: declarative sentences of reasonable
+// length, skipping headings, code, questions and front matter. The frontend
+// shows the candidates for review before anything is verified. Files inside
+// the vault come with their folder's default domain and risk profile.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::{consent, folder_defaults, sanitize_error};
+use crate::tasks::{self, TaskHandle};
+
+pub const INTAKE_EVENT: &str = "intake://documents";
+
+const SUPPORTED_EXTENSIONS: &[&str] = &["md", "markdown", "txt", "pdf"];
+const MAX_INTAKE_FILE_SIZE: u64 = 20 * 1024 * 1024;
+const MAX_INTAKE_FILES: usize = 20;
+const MAX_CLAIMS_PER_DOCUMENT: usize = 200;
+const MIN_CLAIM_LEN: usize = 20;
+const MAX_CLAIM_LEN: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntakeDocument {
+    pub path: String,
+    pub file_name: String,
+    /// "markdown", "text" or "pdf"
+    pub kind: String,
+    pub claims: Vec<String>,
+    /// More candidate claims were found than are returned
+    pub truncated: bool,
+    pub error: Option<String>,
+    /// Folder defaults, for files inside the vault
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub risk_profile: Option<String>,
+}
+
+fn document_kind(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    if !SUPPORTED_EXTENSIONS.contains(&extension.as_str()) {
+        return None;
+    }
+    Some(match extension.as_str() {
+        "md" | "markdown" => "markdown",
+        "pdf" => "pdf",
+        _ => "text",
+    })
+}
+
+fn extract_pdf_text(bytes: &[u8]) -> Result<String, String> {
+    // Malformed PDFs can panic inside the parser; treat that as a read error
+    std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(bytes))
+        .map_err(|_| "PDF could not be parsed".to_string())?
+        .map_err(|e| format!("Failed to extract PDF text: {}", e))
+}
+
+fn read_document_text(path: &Path, kind: &str) -> Result<String, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| sanitize_error(&format!("Failed to read file: {}", e)))?
+        .len();
+    if size > MAX_INTAKE_FILE_SIZE {
+        return Err("File is too large (max 20 MB)".to_string());
+    }
+    let bytes = fs::read(path).map_err(|e| sanitize_error(&format!("Failed to read file: {}", e)))?;
+    match kind {
+        "pdf" => extract_pdf_text(&bytes),
+        _ => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+    }
+}
+
+/// Drop YAML front matter, fenced code, headings, tables and markdown markup,
+/// leaving prose
+fn prose_lines(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    let mut body = text;
+    if let Some(rest) = text.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---") {
+            body = &rest[end + 4..];
+        }
+    }
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || trimmed.starts_with('#') || trimmed.starts_with('|') || trimmed.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        let stripped = trimmed
+            .trim_start_matches(['>', '-', '*', '+', ' '])
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(['.', ')', ' ']);
+        lines.push(strip_inline_markup(stripped));
+    }
+    lines
+}
+
+/// `[text](url)` -> `text`, `[[note|alias]]` -> `alias`, and emphasis markers removed
+fn strip_inline_markup(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start..];
+        if let Some(inner) = after.strip_prefix("[[") {
+            if let Some(end) = inner.find("]]") {
+                let target = &inner[..end];
+                out.push_str(target.rsplit('|').next().unwrap_or(target));
+                rest = &inner[end + 2..];
+                continue;
+            }
+        } else if let Some(end) = after.find("](") {
+            if let Some(close) = after[end..].find(')') {
+                out.push_str(&after[1..end]);
+                rest = &after[end + close + 1..];
+                continue;
+            }
+        }
+        out.push('[');
+        rest = &after[1..];
+    }
+    out.push_str(rest);
+    out.replace("**", "").replace("__", "").replace('`', "")
+}
+
+fn split_sentences(paragraph: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = paragraph.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') && chars.peek().map(|n| n.is_whitespace()).unwrap_or(true) {
+            sentences.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+    sentences
+}
+
+fn is_candidate_claim(sentence: &str) -> bool {
+    let len = sentence.chars().count();
+    (MIN_CLAIM_LEN..=MAX_CLAIM_LEN).contains(&len)
+        && !sentence.ends_with('?')
+        && !sentence.ends_with(':')
+        && sentence.split_whitespace().count() >= 4
+        && sentence.chars().next().map(|c| c.is_alphanumeric()).unwrap_or(false)
+}
+
+/// Candidate claims in document order, deduplicated
+pub fn extract_claims(text: &str) -> Vec<String> {
+    let lines = prose_lines(text);
+    let mut claims: Vec<String> = Vec::new();
+    // Rejoin wrapped lines into paragraphs before splitting sentences
+    for paragraph in lines.split(|l| l.is_empty()) {
+        let paragraph = paragraph.join(" ");
+        for sentence in split_sentences(&paragraph) {
+            let sentence = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+            if is_candidate_claim(&sentence) && !claims.contains(&sentence) {
+                claims.push(sentence);
+            }
+        }
+    }
+    claims
+}
+
+fn empty_document(path: &Path) -> IntakeDocument {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    IntakeDocument {
+        path: path.to_string_lossy().to_string(),
+        file_name,
+        kind: String::new(),
+        claims: Vec::new(),
+        truncated: false,
+        error: None,
+        domain: None,
+        risk_profile: None,
+    }
+}
+
+fn set_claims(document: &mut IntakeDocument, text: &str) {
+    let mut claims = extract_claims(text);
+    document.truncated = claims.len() > MAX_CLAIMS_PER_DOCUMENT;
+    claims.truncate(MAX_CLAIMS_PER_DOCUMENT);
+    document.claims = claims;
+}
+
+fn apply_folder_defaults(document: &mut IntakeDocument) {
+    match folder_defaults::resolve(&document.path) {
+        Ok(defaults) => {
+            document.domain = defaults.domain;
+            document.risk_profile = defaults.risk_profile;
+        }
+        Err(e) => log::debug!("No folder defaults for dropped file: {}", e),
+    }
+}
+
+fn intake_document(path: &Path) -> IntakeDocument {
+    let mut document = empty_document(path);
+    let Some(kind) = document_kind(path) else {
+        document.error = Some("Unsupported file type (use .md, .txt or .pdf)".to_string());
+        return document;
+    };
+    document.kind = kind.to_string();
+
+    match read_document_text(path, kind) {
+        Ok(text) => set_claims(&mut document, &text),
+        Err(e) => document.error = Some(e),
+    }
+    apply_folder_defaults(&mut document);
+    document
+}
+
+/// Candidates from `text` written into the note at `path`, so captured text
+/// goes through the same review as dropped files
+pub fn captured_document(path: &Path, text: &str) -> IntakeDocument {
+    let mut document = empty_document(path);
+    document.kind = "markdown".to_string();
+    set_claims(&mut document, text);
+    apply_folder_defaults(&mut document);
+    document
+}
+
+fn intake_paths(paths: &[PathBuf], task: &TaskHandle) -> Result<Vec<IntakeDocument>, String> {
+    if paths.len() > MAX_INTAKE_FILES {
+        return Err(format!("Too many files at once (max {})", MAX_INTAKE_FILES));
+    }
+    let mut documents = Vec::with_capacity(paths.len());
+    for (i, path) in paths.iter().enumerate() {
+        task.check_cancelled()?;
+        documents.push(intake_document(path));
+        task.progress(i as u64 + 1, paths.len() as u64, format!("Read {} of {} files", i + 1, paths.len()));
+    }
+    Ok(documents)
+}
+
+async fn run_intake(
+    app: &AppHandle,
+    task_id: Option<String>,
+    paths: Vec<PathBuf>,
+) -> Result<Vec<IntakeDocument>, String> {
+    tasks::run(app, task_id, "intake", "Reading files", |task| async move {
+        tokio::task::spawn_blocking(move || intake_paths(&paths, &task))
+            .await
+            .map_err(|e| format!("Intake task failed: {}", e))?
+    })
+    .await
+}
+
+/// Window drop handler: extract off the UI thread, then notify the frontend
+pub fn handle_drop(app: &AppHandle, paths: Vec<PathBuf>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match run_intake(&app, None, paths).await {
+            Ok(documents) => {
+                if let Err(e) = app.emit(INTAKE_EVENT, documents) {
+                    log::warn!("Failed to emit {}: {}", INTAKE_EVENT, e);
+                }
+            }
+            Err(e) => log::warn!("File drop ignored: {}", e),
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn intake_files(
+    app: AppHandle,
+    paths: Vec<String>,
+    task_id: Option<String>,
+) -> Result<Vec<IntakeDocument>, String> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    for path in &paths {
+        consent::require_read(path).await?;
+    }
+    run_intake(&app, task_id, paths).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_claims_from_markdown() {
+        let text = "---\ntags: [physics]\n---\n# Boiling\n\nWater boils at 100 degrees Celsius at sea level. \
+                    Is that always true?\nIt depends on\natmospheric pressure.\n\n```\nlet x = 1; // not a claim at all.\n```\n\
+                    - See [the source](https://example.com) for the measured values.\n";
+        let claims = extract_claims(text);
+        assert_eq!(
+            claims,
+            vec![
+                "Water boils at 100 degrees Celsius at sea level.",
+                "It depends on atmospheric pressure.",
+                "See the source for the measured values.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_inline_markup() {
+        assert_eq!(strip_inline_markup("As [[Physics Notes|noted]], **heat** rises"), "As noted, heat rises");
+        assert_eq!(strip_inline_markup("An [unclosed bracket"), "An [unclosed bracket");
+    }
+
+    #[test]
+    fn test_unsupported_files_are_reported() {
+        let document = intake_document(Path::new("/tmp/picture.png"));
+        assert!(document.error.is_some());
+        assert!(document.claims.is_empty());
+        assert_eq!(document_kind(Path::new("Notes.MD")), Some("markdown"));
+    }
+}
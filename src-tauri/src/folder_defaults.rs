@@ -0,0 +1,239 @@
+// ==================== FOLDER DEFAULTS ====================
+//
+// Vault folders can carry a default domain and risk profile in a
+// `.truthgit.toml`:
+//
+//     domain = "physics"
+//     risk_profile = "high"
+//
+// Verifying a note, or claims extracted from one, with the domain or risk
+// profile left empty fills them in from the nearest folder that sets them,
+// walking up to the vault root; a field a folder leaves out is inherited
+// from its parents. Anything typed explicitly wins over the folder.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::settings_validation::RISK_PROFILES;
+use crate::{get_vault_path, paths, run_blocking, sanitize_error, validate_path_within_base};
+
+pub const DEFAULTS_FILE: &str = ".truthgit.toml";
+const MAX_DEFAULTS_FILE_SIZE: u64 = 16 * 1024;
+const MAX_DOMAIN_LEN: usize = 100;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FolderDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ResolvedDefaults {
+    pub domain: Option<String>,
+    pub risk_profile: Option<String>,
+    /// Folders whose .truthgit.toml supplied a field, relative to the vault
+    /// ("" for the root), nearest first
+    pub sources: Vec<String>,
+}
+
+impl FolderDefaults {
+    fn normalized(self) -> Self {
+        let clean = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        Self {
+            domain: clean(self.domain),
+            risk_profile: clean(self.risk_profile).map(|r| r.to_lowercase()),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.domain.as_ref().is_some_and(|d| d.chars().count() > MAX_DOMAIN_LEN) {
+            return Err(format!("Domain must be under {} characters", MAX_DOMAIN_LEN));
+        }
+        if let Some(risk) = &self.risk_profile {
+            if !RISK_PROFILES.contains(&risk.as_str()) {
+                return Err(format!(
+                    "Invalid risk profile: {} (use {})",
+                    risk,
+                    RISK_PROFILES.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_defaults(folder: &Path) -> Result<Option<FolderDefaults>, String> {
+    let path = folder.join(DEFAULTS_FILE);
+    let Ok(metadata) = fs::metadata(&path) else {
+        return Ok(None);
+    };
+    if metadata.len() > MAX_DEFAULTS_FILE_SIZE {
+        return Err(format!("{} is too large", DEFAULTS_FILE));
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| sanitize_error(&format!("Failed to read {}: {}", DEFAULTS_FILE, e)))?;
+    let defaults = toml::from_str::<FolderDefaults>(&content)
+        .map_err(|e| format!("Invalid {}: {}", DEFAULTS_FILE, e.message()))?
+        .normalized();
+    defaults.validate()?;
+    Ok(Some(defaults))
+}
+
+/// Defaults for `target` (a note or folder) inside the canonical `vault`
+fn resolve_in(vault: &Path, target: &Path) -> Result<ResolvedDefaults, String> {
+    let mut resolved = ResolvedDefaults::default();
+    let mut folder = if target.is_dir() { Some(target) } else { target.parent() };
+    while let Some(dir) = folder.filter(|d| d.starts_with(vault)) {
+        let relative = paths::relative_to(vault, dir).unwrap_or_default();
+        let defaults = read_defaults(dir).map_err(|e| format!("{} (in \"{}\")", e, relative))?;
+        if let Some(defaults) = defaults {
+            let mut used = false;
+            if resolved.domain.is_none() && defaults.domain.is_some() {
+                resolved.domain = defaults.domain;
+                used = true;
+            }
+            if resolved.risk_profile.is_none() && defaults.risk_profile.is_some() {
+                resolved.risk_profile = defaults.risk_profile;
+                used = true;
+            }
+            if used {
+                resolved.sources.push(relative);
+            }
+        }
+        if resolved.domain.is_some() && resolved.risk_profile.is_some() {
+            break;
+        }
+        folder = dir.parent();
+    }
+    Ok(resolved)
+}
+
+fn canonical_vault() -> Result<PathBuf, String> {
+    let vault = get_vault_path()
+        .filter(|p| p.exists())
+        .ok_or_else(|| "Vault not found".to_string())?;
+    fs::canonicalize(&vault).map_err(|e| sanitize_error(&format!("Failed to resolve vault path: {}", e)))
+}
+
+/// Defaults for a note given relative to the vault, or by absolute path.
+/// Absolute paths outside the vault have no defaults.
+pub fn resolve(note_path: &str) -> Result<ResolvedDefaults, String> {
+    let vault = canonical_vault()?;
+    let target = if Path::new(note_path).is_absolute() {
+        match fs::canonicalize(note_path) {
+            Ok(path) if path.starts_with(&vault) => path,
+            _ => return Ok(ResolvedDefaults::default()),
+        }
+    } else {
+        validate_path_within_base(&vault, note_path)?
+    };
+    resolve_in(&vault, &target)
+}
+
+/// Fill an empty `domain` or `risk_profile` from the folder defaults of
+/// `note_path`, if one is given
+pub fn apply(note_path: Option<&str>, domain: String, risk_profile: String) -> Result<(String, String), String> {
+    let Some(note_path) = note_path.map(str::trim).filter(|p| !p.is_empty()) else {
+        return Ok((domain, risk_profile));
+    };
+    if !domain.trim().is_empty() && !risk_profile.trim().is_empty() {
+        return Ok((domain, risk_profile));
+    }
+    let defaults = resolve(note_path)?;
+    let domain = match defaults.domain {
+        Some(folder) if domain.trim().is_empty() => folder,
+        _ => domain,
+    };
+    let risk_profile = match defaults.risk_profile {
+        Some(folder) if risk_profile.trim().is_empty() => folder,
+        _ => risk_profile,
+    };
+    Ok((domain, risk_profile))
+}
+
+/// The domain and risk profile a note or folder inherits
+#[tauri::command]
+pub async fn get_folder_defaults(path: String) -> Result<ResolvedDefaults, String> {
+    run_blocking(move || resolve(&path)).await
+}
+
+/// Write `folder`'s .truthgit.toml, or remove it when `defaults` is empty
+#[tauri::command]
+pub async fn set_folder_defaults(folder: String, defaults: FolderDefaults) -> Result<(), String> {
+    let defaults = defaults.normalized();
+    defaults.validate()?;
+    run_blocking(move || {
+        let vault = canonical_vault()?;
+        let dir = if folder.trim().is_empty() {
+            vault
+        } else {
+            validate_path_within_base(&vault, &folder)?
+        };
+        if !dir.is_dir() {
+            return Err("Not a folder".to_string());
+        }
+        let path = dir.join(DEFAULTS_FILE);
+        if defaults == FolderDefaults::default() {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(sanitize_error(&format!("Failed to remove {}: {}", DEFAULTS_FILE, e)))
+                }
+                _ => Ok(()),
+            };
+        }
+        let content = toml::to_string(&defaults).map_err(|e| format!("Failed to write defaults: {}", e))?;
+        fs::write(&path, content).map_err(|e| sanitize_error(&format!("Failed to write {}: {}", DEFAULTS_FILE, e)))?;
+        log::info!("Folder defaults set for \"{}\"", folder);
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_nearest_folder_wins() {
+        let tmp = TempDir::new("truthgit-folder-defaults");
+        let vault = tmp.path();
+        fs::create_dir_all(vault.join("Science/Physics")).unwrap();
+        let vault = fs::canonicalize(vault).unwrap();
+        fs::write(vault.join(DEFAULTS_FILE), "risk_profile = \"medium\"\n").unwrap();
+        fs::write(
+            vault.join("Science").join(DEFAULTS_FILE),
+            "domain = \"science\"\nrisk_profile = \"HIGH\"\n",
+        )
+        .unwrap();
+        fs::write(
+            vault.join("Science/Physics").join(DEFAULTS_FILE),
+            "domain = \" physics \"\n",
+        )
+        .unwrap();
+        let note = vault.join("Science/Physics/Heat.md");
+        fs::write(&note, "").unwrap();
+
+        let resolved = resolve_in(&vault, &note).unwrap();
+        assert_eq!(resolved.domain.as_deref(), Some("physics"));
+        assert_eq!(resolved.risk_profile.as_deref(), Some("high"));
+        assert_eq!(resolved.sources, ["Science/Physics", "Science"]);
+
+        let root = resolve_in(&vault, &vault.join("Top.md")).unwrap();
+        assert_eq!((root.domain, root.risk_profile.as_deref()), (None, Some("medium")));
+        assert_eq!(root.sources, [""]);
+
+        fs::write(
+            vault.join("Science").join(DEFAULTS_FILE),
+            "risk_profile = \"extreme\"\n",
+        )
+        .unwrap();
+        assert!(resolve_in(&vault, &note).unwrap_err().contains("Science"));
+        fs::write(vault.join("Science").join(DEFAULTS_FILE), "domian = \"typo\"\n").unwrap();
+        assert!(resolve_in(&vault, &note).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+// ==================== GLOBAL SEARCH ====================
+//
+// One query across claims (the claim index), notes (the vault) and the
+// audit trail, run concurrently and merged into a single ranked list for the
+// omnibox. A source that fails is reported next to the results instead of
+// failing the whole search.
+
+use serde::Serialize;
+use std::time::Instant;
+
+use crate::{metrics, read_audit_trail, read_claims, run_blocking, search_vault};
+
+const MAX_QUERY_LEN: usize = 200;
+const DEFAULT_LIMIT: usize = 30;
+const MAX_LIMIT: usize = 200;
+const SNIPPET_LEN: usize = 140;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitKind {
+    Claim,
+    Note,
+    Audit,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub kind: HitKind,
+    /// Claim hash, note path or audit entry ID
+    pub id: String,
+    pub title: String,
+    pub snippet: Option<String>,
+    /// Domain for claims and audit entries
+    pub domain: Option<String>,
+    /// 0.0-1.0; results are sorted by it
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GlobalSearch {
+    pub query: String,
+    pub hits: Vec<SearchHit>,
+    /// Sources that couldn't be searched, e.g. "notes: Vault not found"
+    pub errors: Vec<String>,
+}
+
+/// How well `text` matches: the whole query as a phrase ranks above all its
+/// words appearing separately; 0.0 when any word is missing
+fn score(text: &str, query: &str, terms: &[String]) -> f64 {
+    let text = text.to_lowercase();
+    if text.is_empty() || terms.iter().any(|t| !text.contains(t.as_str())) {
+        return 0.0;
+    }
+    if text == query {
+        1.0
+    } else if text.starts_with(query) {
+        0.9
+    } else if text.contains(query) {
+        0.75
+    } else {
+        0.5
+    }
+}
+
+fn snippet(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn claim_hits(query: &str, terms: &[String]) -> Result<Vec<SearchHit>, String> {
+    let text = |claim: &serde_json::Value, key: &str| claim.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    Ok(read_claims(None)?
+        .claims
+        .iter()
+        .filter_map(|claim| {
+            let content = text(claim, "content");
+            let tags = claim
+                .pointer("/metadata/tags")
+                .and_then(|t| t.as_array())
+                .map(|t| t.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>().join(" "))
+                .unwrap_or_default();
+            let score = score(&content, query, terms).max(0.8 * score(&format!("{} {}", content, tags), query, terms));
+            (score > 0.0).then(|| SearchHit {
+                kind: HitKind::Claim,
+                id: text(claim, "$hash"),
+                title: snippet(&content),
+                snippet: None,
+                domain: Some(text(claim, "domain")).filter(|d| !d.is_empty()),
+                score,
+            })
+        })
+        .collect())
+}
+
+fn note_hits(query: &str, terms: &[String]) -> Result<Vec<SearchHit>, String> {
+    // The vault search matches the whole query per line; notes matching only
+    // on their name are ranked by name
+    Ok(search_vault(query.to_string())?
+        .into_iter()
+        .map(|note| {
+            let name_score = score(&note.name, query, terms);
+            let line_score = 0.6 + 0.03 * note.matches.len().min(10) as f64;
+            SearchHit {
+                kind: HitKind::Note,
+                title: note.name,
+                snippet: note.matches.first().map(|m| snippet(m)),
+                id: note.path,
+                domain: None,
+                score: name_score.max(line_score),
+            }
+        })
+        .collect())
+}
+
+fn audit_hits(query: &str, terms: &[String]) -> Result<Vec<SearchHit>, String> {
+    Ok(read_audit_trail()?
+        .into_iter()
+        .filter_map(|entry| {
+            let id_match = entry.id.to_lowercase() == query;
+            let score = if id_match {
+                1.0
+            } else {
+                0.9 * score(&entry.claim, query, terms)
+            };
+            (score > 0.0).then(|| SearchHit {
+                kind: HitKind::Audit,
+                snippet: Some(format!(
+                    "{} · {} · {}",
+                    entry.result_status, entry.result_action, entry.timestamp
+                )),
+                title: snippet(&entry.claim),
+                id: entry.id,
+                domain: Some(entry.domain),
+                score,
+            })
+        })
+        .collect())
+}
+
+/// Best hits first; ties keep claims ahead of notes ahead of audit entries
+fn rank(mut hits: Vec<SearchHit>, limit: usize) -> Vec<SearchHit> {
+    let order = |kind: HitKind| match kind {
+        HitKind::Claim => 0,
+        HitKind::Note => 1,
+        HitKind::Audit => 2,
+    };
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| order(a.kind).cmp(&order(b.kind)))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+/// Search claims, notes and the audit trail at once
+#[tauri::command]
+pub async fn global_search(query: String, limit: Option<usize>) -> Result<GlobalSearch, String> {
+    let started = Instant::now();
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err("Enter something to search for".to_string());
+    }
+    if query.len() > MAX_QUERY_LEN {
+        return Err(format!("Search query too long (max {} characters)", MAX_QUERY_LEN));
+    }
+    let terms: Vec<String> = query.split_whitespace().map(str::to_string).collect();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let search = |source: fn(&str, &[String]) -> Result<Vec<SearchHit>, String>| {
+        let (query, terms) = (query.clone(), terms.clone());
+        run_blocking(move || source(&query, &terms))
+    };
+    let (claims, notes, audit) = tokio::join!(search(claim_hits), search(note_hits), search(audit_hits));
+
+    let mut hits = Vec::new();
+    let mut errors = Vec::new();
+    for (label, result) in [("claims", claims), ("notes", notes), ("audit", audit)] {
+        match result {
+            Ok(found) => hits.extend(found),
+            Err(e) => errors.push(format!("{}: {}", label, e)),
+        }
+    }
+    metrics::record("global_search", started, errors.is_empty());
+    Ok(GlobalSearch {
+        hits: rank(hits, limit),
+        query,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(query: &str) -> Vec<String> {
+        query.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_score_prefers_phrases() {
+        let query = "water boils";
+        let t = terms(query);
+        assert_eq!(score("Water boils", query, &t), 1.0);
+        assert_eq!(score("Water boils at 100°C", query, &t), 0.9);
+        assert_eq!(score("Pure water boils at 100°C", query, &t), 0.75);
+        assert_eq!(score("Boils: salt water", query, &t), 0.5);
+        assert_eq!(score("Ice melts", query, &t), 0.0);
+    }
+
+    #[test]
+    fn test_rank_orders_and_limits() {
+        let hit = |kind, score| SearchHit {
+            kind,
+            id: String::new(),
+            title: String::new(),
+            snippet: None,
+            domain: None,
+            score,
+        };
+        let ranked = rank(
+            vec![
+                hit(HitKind::Audit, 0.9),
+                hit(HitKind::Note, 0.5),
+                hit(HitKind::Claim, 0.9),
+            ],
+            2,
+        );
+        let kinds: Vec<HitKind> = ranked.iter().map(|h| h.kind).collect();
+        assert_eq!(kinds, vec![HitKind::Claim, HitKind::Audit]);
+    }
+}
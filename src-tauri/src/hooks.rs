@@ -0,0 +1,378 @@
+// ==================== HOOKS ====================
+//
+// Executables under hooks/<event>/ in the config directory run on
+// pre_verification, post_verification, claim_created and digest, in
+// file-name order, so an organization can bolt its own policy onto the app
+// or post digests where the team reads them. Each hook gets
+// `{event, payload, actor, app_version}` as JSON on stdin and may print
+// `{"allow": false, "reason": "..."}` to veto or `{"annotations": {...}}` to
+// attach data to the result; printing nothing allows. Only pre_verification
+// can veto, and there a hook that exits non-zero, times out or can't be
+// started also blocks: a policy that didn't run must not pass silently.
+// A `.wasm` file there runs as a sandboxed module instead (see plugins.rs):
+// the event goes to its `run` export and the response comes back from it,
+// with the plugin fuel budget in place of the timeout and no host
+// permissions. Hooks are off until `hooks.enabled` is set.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::identity;
+use crate::json_store::config_path;
+use crate::plugins;
+use crate::{run_blocking, sanitize_error, SETTINGS};
+
+const MAX_OUTPUT: u64 = 64 * 1024;
+pub const MAX_TIMEOUT_SECS: u32 = 300;
+/// claim_created events fired for one index refresh; a large pull skips hooks
+const MAX_CREATED_EVENTS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    PreVerification,
+    PostVerification,
+    ClaimCreated,
+    /// A scheduled digest was written (see digest.rs)
+    Digest,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 4] = [
+        Self::PreVerification,
+        Self::PostVerification,
+        Self::ClaimCreated,
+        Self::Digest,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PreVerification => "pre_verification",
+            Self::PostVerification => "post_verification",
+            Self::ClaimCreated => "claim_created",
+            Self::Digest => "digest",
+        }
+    }
+
+    fn can_veto(self) -> bool {
+        self == Self::PreVerification
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HookSettings {
+    pub enabled: bool,
+    /// Seconds each hook may run before it is killed
+    pub timeout_secs: u32,
+}
+
+impl Default for HookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout_secs: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookInfo {
+    pub event: HookEvent,
+    pub name: String,
+    pub path: String,
+}
+
+/// What a hook printed; every field is optional
+#[derive(Debug, Default, Deserialize)]
+struct HookResponse {
+    allow: Option<bool>,
+    reason: Option<String>,
+    annotations: Option<serde_json::Value>,
+}
+
+/// Annotations from every hook that ran, keyed by hook name
+pub type Annotations = BTreeMap<String, serde_json::Value>;
+
+fn hooks_dir() -> PathBuf {
+    config_path("hooks")
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn is_wasm(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|e| e.eq_ignore_ascii_case("wasm"))
+}
+
+/// Executable and `.wasm` hooks for `event` under `dir`, by file name; dotfiles are skipped
+fn hooks_in(dir: &Path, event: HookEvent) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir.join(event.as_str())) else {
+        return Vec::new();
+    };
+    let mut hooks: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| !p.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')))
+        .filter(|p| is_wasm(p) || is_executable(p))
+        .collect();
+    hooks.sort();
+    hooks
+}
+
+fn hook_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Run one hook on `input` and parse what it printed
+fn run_hook(path: &Path, input: &[u8], timeout: Duration) -> Result<HookResponse, String> {
+    let name = hook_name(path);
+    let stdout = if is_wasm(path) {
+        plugins::run_hook_module(path, &name, input)?
+    } else {
+        run_executable(path, &name, input, timeout)?
+    };
+    if stdout.iter().all(u8::is_ascii_whitespace) {
+        return Ok(HookResponse::default());
+    }
+    serde_json::from_slice(&stdout).map_err(|e| format!("Hook {} printed invalid JSON: {}", name, e))
+}
+
+/// Run an executable hook with `input` on stdin, killing it after `timeout`;
+/// returns its stdout. The timeout covers reading its output too: a process
+/// the hook left running in the background can hold the pipes open after the
+/// hook exits, and the reader threads are then abandoned rather than joined.
+fn run_executable(path: &Path, name: &str, input: &[u8], timeout: Duration) -> Result<Vec<u8>, String> {
+    let mut child = Command::new(path)
+        .current_dir(path.parent().unwrap_or(path))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| sanitize_error(&format!("Hook {} could not start: {}", name, e)))?;
+
+    let mut stdin = child.stdin.take();
+    let input = input.to_vec();
+    std::thread::spawn(move || stdin.as_mut().map(|s| s.write_all(&input)));
+    let capture = |pipe: Option<Box<dyn Read + Send>>| {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(pipe) = pipe {
+                let _ = pipe.take(MAX_OUTPUT).read_to_end(&mut buf);
+            }
+            let _ = tx.send(buf);
+        });
+        rx
+    };
+    let stdout = capture(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = capture(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(20)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Hook {} timed out after {} seconds", name, timeout.as_secs()));
+            }
+            Err(e) => return Err(format!("Hook {} failed: {}", name, e)),
+        }
+    };
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    let stderr = stderr.recv_timeout(remaining()).unwrap_or_default();
+
+    if !status.success() {
+        let message = String::from_utf8_lossy(&stderr).trim().to_string();
+        let message = if message.is_empty() {
+            format!("exited with {}", status)
+        } else {
+            message
+        };
+        return Err(format!("Hook {} failed: {}", name, message));
+    }
+    stdout.recv_timeout(remaining()).map_err(|_| {
+        format!(
+            "Hook {} timed out after {} seconds: a process it started still holds its output open",
+            name,
+            timeout.as_secs()
+        )
+    })
+}
+
+/// Run the hooks for `event` in `dir`. A veto, or any failure of a hook that
+/// can veto, stops the run with an error; other failures are logged.
+fn run_in(dir: &Path, event: HookEvent, payload: &serde_json::Value, timeout: Duration) -> Result<Annotations, String> {
+    let mut annotations = Annotations::new();
+    let hooks = hooks_in(dir, event);
+    if hooks.is_empty() {
+        return Ok(annotations);
+    }
+    let input = serde_json::to_vec(&serde_json::json!({
+        "event": event.as_str(),
+        "payload": payload,
+        "actor": identity::current(),
+        "app_version": env!("CARGO_PKG_VERSION"),
+    }))
+    .map_err(|e| format!("Failed to encode hook event: {}", e))?;
+
+    for path in hooks {
+        let name = hook_name(&path);
+        let response = match run_hook(&path, &input, timeout) {
+            Ok(response) => response,
+            Err(e) if event.can_veto() => return Err(format!("Blocked: {}", e)),
+            Err(e) => {
+                log::warn!("{}", e);
+                continue;
+            }
+        };
+        if response.allow == Some(false) {
+            let reason = response.reason.unwrap_or_else(|| "no reason given".to_string());
+            if event.can_veto() {
+                return Err(format!("Blocked by hook {}: {}", name, reason));
+            }
+            log::warn!(
+                "Hook {} can't veto {} events; ignoring ({})",
+                name,
+                event.as_str(),
+                reason
+            );
+        }
+        if let Some(annotation) = response.annotations.filter(|a| !a.is_null()) {
+            annotations.insert(name, annotation);
+        }
+    }
+    Ok(annotations)
+}
+
+fn settings() -> HookSettings {
+    SETTINGS.read().map(|s| s.hooks.clone()).unwrap_or_default()
+}
+
+/// Run the hooks for `event` if hooks are enabled
+pub async fn run(event: HookEvent, payload: serde_json::Value) -> Result<Annotations, String> {
+    let settings = settings();
+    if !settings.enabled {
+        return Ok(Annotations::new());
+    }
+    let timeout = Duration::from_secs(u64::from(settings.timeout_secs));
+    run_blocking(move || run_in(&hooks_dir(), event, &payload, timeout)).await
+}
+
+/// Fire claim_created for claims the index found since its last refresh,
+/// without holding up the caller
+pub fn claims_created(claims: Vec<(String, serde_json::Value)>) {
+    let settings = settings();
+    if !settings.enabled || claims.is_empty() || hooks_in(&hooks_dir(), HookEvent::ClaimCreated).is_empty() {
+        return;
+    }
+    if claims.len() > MAX_CREATED_EVENTS {
+        log::warn!("{} new claims at once; skipping claim_created hooks", claims.len());
+        return;
+    }
+    let timeout = Duration::from_secs(u64::from(settings.timeout_secs));
+    std::thread::spawn(move || {
+        for (hash, claim) in claims {
+            let payload = serde_json::json!({ "hash": hash, "claim": claim });
+            if let Err(e) = run_in(&hooks_dir(), HookEvent::ClaimCreated, &payload, timeout) {
+                log::warn!("claim_created hooks for {}: {}", hash, e);
+            }
+        }
+    });
+}
+
+/// Installed hooks, by event and run order
+#[tauri::command]
+pub async fn list_hooks() -> Result<Vec<HookInfo>, String> {
+    run_blocking(|| {
+        let dir = hooks_dir();
+        Ok(HookEvent::ALL
+            .into_iter()
+            .flat_map(|event| {
+                hooks_in(&dir, event).into_iter().map(move |path| HookInfo {
+                    event,
+                    name: hook_name(&path),
+                    path: path.to_string_lossy().to_string(),
+                })
+            })
+            .collect())
+    })
+    .await
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn install(dir: &Path, event: HookEvent, name: &str, script: &str) {
+        let path = dir.join(event.as_str()).join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_hooks_annotate_and_veto() {
+        let tmp = TempDir::new("truthgit-hooks");
+        let dir = tmp.path();
+        let timeout = Duration::from_secs(5);
+        let payload = serde_json::json!({ "claim": "Water boils at 100 C" });
+        let tag = r#"cat >/dev/null; echo '{"annotations": {"team": "ops"}}'"#;
+        install(dir, HookEvent::PreVerification, "10-tag", tag);
+        install(dir, HookEvent::PreVerification, "20-silent", "cat >/dev/null");
+        let annotations = run_in(dir, HookEvent::PreVerification, &payload, timeout).unwrap();
+        assert_eq!(annotations.get("10-tag"), Some(&serde_json::json!({ "team": "ops" })));
+        assert_eq!(annotations.len(), 1);
+
+        let policy = r#"grep -q Water && echo '{"allow": false, "reason": "no water"}'"#;
+        install(dir, HookEvent::PreVerification, "30-policy", policy);
+        let error = run_in(dir, HookEvent::PreVerification, &payload, timeout).unwrap_err();
+        assert_eq!(error, "Blocked by hook 30-policy: no water");
+
+        // Post hooks can't veto, and their failures don't stop the others
+        install(dir, HookEvent::PostVerification, "10-fail", "exit 3");
+        install(
+            dir,
+            HookEvent::PostVerification,
+            "20-veto",
+            r#"echo '{"allow": false, "annotations": 1}'"#,
+        );
+        let annotations = run_in(dir, HookEvent::PostVerification, &payload, timeout).unwrap();
+        assert_eq!(annotations.get("20-veto"), Some(&serde_json::json!(1)));
+    }
+
+    #[test]
+    fn test_background_child_doesnt_hang_the_hook() {
+        let tmp = TempDir::new("truthgit-hooks-background");
+        let path = tmp.path().join("detach");
+        std::fs::write(&path, "#!/bin/sh
+sleep 5 &
+echo '{}'
+").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let started = Instant::now();
+        let error = run_executable(&path, "detach", b"{}", Duration::from_secs(1)).unwrap_err();
+        assert!(error.contains("still holds its output open"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
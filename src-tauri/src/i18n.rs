@@ -0,0 +1,229 @@
+// ==================== I18N ====================
+//
+// Catalog of backend-generated messages keyed by stable codes, in every
+// supported locale. Commands render messages with `tr` in the locale picked
+// in settings; the frontend fetches the catalog with `get_message_catalog`
+// so it can render the same codes itself. `{name}` placeholders are filled
+// in from the arguments; locales missing a message fall back to English.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::SETTINGS;
+
+pub const DEFAULT_LOCALE: &str = "en";
+/// Supported locales, in `CATALOG` column order
+pub const LOCALES: &[&str] = &["en", "es"];
+
+/// (code, [English, Spanish])
+const CATALOG: &[(&str, [&str; 2])] = &[
+    (
+        "blocked.path_traversal",
+        [
+            "🚫 BLOCKED: Path contains '..' (directory traversal attempt)",
+            "🚫 BLOQUEADO: La ruta contiene '..' (intento de salir del directorio)",
+        ],
+    ),
+    (
+        "blocked.absolute_path",
+        [
+            "🚫 BLOCKED: Absolute paths are not allowed",
+            "🚫 BLOQUEADO: No se permiten rutas absolutas",
+        ],
+    ),
+    (
+        "blocked.null_byte",
+        [
+            "🚫 BLOCKED: Path contains null byte",
+            "🚫 BLOQUEADO: La ruta contiene un byte nulo",
+        ],
+    ),
+    (
+        "blocked.path_escapes",
+        [
+            "🚫 BLOCKED: Path escapes allowed directory (directory traversal attempt)",
+            "🚫 BLOQUEADO: La ruta sale del directorio permitido (intento de salir del directorio)",
+        ],
+    ),
+    (
+        "blocked.truthgit_subcommand",
+        [
+            "🚫 BLOCKED: TruthGit subcommand '{subcommand}' is not allowed. Allowed: {allowed}",
+            "🚫 BLOQUEADO: El subcomando de TruthGit '{subcommand}' no está permitido. Permitidos: {allowed}",
+        ],
+    ),
+    (
+        "blocked.argument_pattern",
+        [
+            "🚫 BLOCKED: Argument contains forbidden pattern '{pattern}'. Shell injection attempt detected.",
+            "🚫 BLOQUEADO: El argumento contiene el patrón prohibido '{pattern}'. \
+             Se detectó un intento de inyección de comandos.",
+        ],
+    ),
+    (
+        "blocked.dangerous_pattern",
+        [
+            "🚫 BLOCKED: Command contains dangerous pattern '{pattern}'",
+            "🚫 BLOQUEADO: El comando contiene el patrón peligroso '{pattern}'",
+        ],
+    ),
+    (
+        "blocked.command_not_allowed",
+        [
+            "🚫 BLOCKED: Command '{command}' is not in the allowed list. \
+             Only truthgit and safe read-only commands are permitted.",
+            "🚫 BLOQUEADO: El comando '{command}' no está en la lista permitida. \
+             Solo se permiten truthgit y comandos seguros de solo lectura.",
+        ],
+    ),
+    (
+        "warning.command_not_allowed",
+        [
+            "⚠️ Command '{command}' is not in the allowed list. Only safe commands are permitted.",
+            "⚠️ El comando '{command}' no está en la lista permitida. Solo se permiten comandos seguros.",
+        ],
+    ),
+    ("report.summary", ["Summary", "Resumen"]),
+    ("report.verdicts", ["Verdicts", "Veredictos"]),
+    (
+        "report.audit",
+        ["Recent audit entries", "Entradas de auditoría recientes"],
+    ),
+    (
+        "report.generated",
+        ["Generated {at}{by} · {repo}", "Generado {at}{by} · {repo}"],
+    ),
+    ("report.generated_by", [" by {name}", " por {name}"]),
+    (
+        "report.counts",
+        [
+            "{verifications} verifications of {claims} claims",
+            "{verifications} verificaciones de {claims} afirmaciones",
+        ],
+    ),
+    (
+        "report.average_confidence",
+        [", average confidence {percent}%", ", confianza media {percent}%"],
+    ),
+];
+
+#[derive(Debug, Serialize)]
+pub struct MessageCatalog {
+    pub locale: String,
+    pub available: Vec<String>,
+    /// Code -> template with `{name}` placeholders
+    pub messages: BTreeMap<String, String>,
+}
+
+pub fn default_locale() -> String {
+    DEFAULT_LOCALE.to_string()
+}
+
+fn column(locale: &str) -> usize {
+    LOCALES.iter().position(|l| *l == locale).unwrap_or(0)
+}
+
+fn template(locale: &str, code: &str) -> Option<&'static str> {
+    let (_, texts) = CATALOG.iter().find(|(c, _)| *c == code)?;
+    Some(texts[column(locale)]).filter(|t| !t.is_empty()).or(Some(texts[0]))
+}
+
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Render `code` in `locale`; unknown codes render as the code itself
+pub fn tr_in(locale: &str, code: &str, args: &[(&str, &str)]) -> String {
+    match template(locale, code) {
+        Some(template) => fill(template, args),
+        None => {
+            log::warn!("Missing message: {}", code);
+            code.to_string()
+        }
+    }
+}
+
+/// Render `code` in the locale picked in settings
+pub fn tr(code: &str, args: &[(&str, &str)]) -> String {
+    // try_read: messages are also built while settings are being written
+    let locale = SETTINGS
+        .try_read()
+        .map(|s| s.locale.clone())
+        .unwrap_or_else(|_| default_locale());
+    tr_in(&locale, code, args)
+}
+
+/// Every message in `locale` (the settings locale when omitted)
+#[tauri::command]
+pub async fn get_message_catalog(locale: Option<String>) -> Result<MessageCatalog, String> {
+    let locale = match locale {
+        Some(locale) => locale.trim().to_lowercase(),
+        None => SETTINGS
+            .read()
+            .map_err(|e| format!("Settings lock error: {}", e))?
+            .locale
+            .clone(),
+    };
+    if !LOCALES.contains(&locale.as_str()) {
+        return Err(format!(
+            "Unsupported locale '{}' (available: {})",
+            locale,
+            LOCALES.join(", ")
+        ));
+    }
+    let messages = CATALOG
+        .iter()
+        .filter_map(|(code, _)| Some((code.to_string(), template(&locale, code)?.to_string())))
+        .collect();
+    Ok(MessageCatalog {
+        locale,
+        available: LOCALES.iter().map(|l| l.to_string()).collect(),
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalog_is_consistent() {
+        let codes: BTreeSet<&str> = CATALOG.iter().map(|(code, _)| *code).collect();
+        assert_eq!(codes.len(), CATALOG.len(), "duplicate message codes");
+        for (code, texts) in CATALOG {
+            for text in &texts[1..] {
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(texts[0]),
+                    "placeholders differ in {}",
+                    code
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tr_in() {
+        let args = [("pattern", "&&")];
+        assert_eq!(
+            tr_in("es", "blocked.dangerous_pattern", &args),
+            "🚫 BLOQUEADO: El comando contiene el patrón peligroso '&&'"
+        );
+        assert_eq!(
+            tr_in("xx", "blocked.dangerous_pattern", &args),
+            "🚫 BLOCKED: Command contains dangerous pattern '&&'"
+        );
+        assert_eq!(tr_in("en", "no.such.code", &[]), "no.such.code");
+    }
+}
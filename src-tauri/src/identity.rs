@@ -0,0 +1,186 @@
+// ==================== IDENTITY ====================
+//
+// Who is using this desktop, so actions on shared repos can be attributed.
+// The display name and email come from settings (the name falls back to the
+// OS user); the key fingerprint is that of the user's own signing key in the
+// config dir (the one receipts and share bundles are signed with), created at
+// startup, so two people on one shared repo get different fingerprints.
+// The resulting `Actor` is stamped into audit entries, verification results
+// and review transitions.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::{receipts, SETTINGS};
+
+/// Hex digits of the SHA-256 of a public key shown as its fingerprint
+const FINGERPRINT_LEN: usize = 32;
+const MAX_NAME_LEN: usize = 100;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdentitySettings {
+    /// Empty uses the OS user name
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub email: String,
+    /// When the name or email last changed. Set by the backend on every
+    /// change, whatever the frontend sends, so approvals can tell whether an
+    /// identity was switched after a request.
+    #[serde(default)]
+    pub changed_at: Option<String>,
+}
+
+impl IdentitySettings {
+    fn same_identity(&self, other: &IdentitySettings) -> bool {
+        self.display_name.trim() == other.display_name.trim() && self.email.trim() == other.email.trim()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Actor {
+    pub name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    /// "SHA256:<hex>" of the user's signing key, when it could be loaded
+    #[serde(default)]
+    pub key_fingerprint: Option<String>,
+}
+
+fn os_user() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+}
+
+/// "SHA256:<hex>" of a public key, as shown to users
+pub fn fingerprint(key: &[u8]) -> String {
+    let digest = format!("{:x}", Sha256::digest(key));
+    format!("SHA256:{}", &digest[..FINGERPRINT_LEN])
+}
+
+/// Fingerprint of a repo's proof.pub, if it has one
+pub fn key_fingerprint(truth_path: &Path) -> Option<String> {
+    std::fs::read(truth_path.join("proof.pub")).ok().map(|key| fingerprint(&key))
+}
+
+/// Fingerprint of this user's signing key
+fn own_key_fingerprint() -> Option<String> {
+    receipts::own_fingerprint().unwrap_or_else(|e| {
+        log::warn!("Failed to read the signing key: {}", e);
+        None
+    })
+}
+
+/// Create this user's signing key if there isn't one yet, so actions are
+/// attributed to a key from the first one on
+pub fn init() {
+    if let Err(e) = receipts::load_or_create_key() {
+        log::warn!("Failed to create the signing key: {}", e);
+    }
+}
+
+fn resolve(settings: &IdentitySettings, key_fingerprint: Option<String>) -> Option<Actor> {
+    let name = Some(settings.display_name.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .or_else(os_user)?;
+    let name: String = name.chars().take(MAX_NAME_LEN).collect();
+    let email = Some(settings.email.trim().to_string()).filter(|e| !e.is_empty());
+    Some(Actor {
+        name,
+        email,
+        key_fingerprint,
+    })
+}
+
+/// Stamp `changed_at` on `new` if its name or email differs from `current`,
+/// else carry over the current stamp
+pub fn stamp_change(current: &IdentitySettings, new: &mut IdentitySettings) {
+    new.changed_at = if current.same_identity(new) {
+        current.changed_at.clone()
+    } else {
+        Some(chrono::Utc::now().to_rfc3339())
+    };
+}
+
+/// When the current user's name or email last changed, if ever
+pub fn changed_at() -> Option<String> {
+    SETTINGS.read().ok()?.identity.changed_at.clone()
+}
+
+/// The current user, or None if neither settings nor the OS provide a name
+pub fn current() -> Option<Actor> {
+    let settings = SETTINGS.read().ok()?.identity.clone();
+    resolve(&settings, own_key_fingerprint())
+}
+
+/// Like `current`, for actions that must be attributed
+pub fn require() -> Result<Actor, String> {
+    current().ok_or_else(|| "Set your display name in Settings → Identity first".to_string())
+}
+
+/// Who actions from this desktop are attributed to; null if unknown
+#[tauri::command]
+pub async fn get_identity() -> Result<Option<Actor>, String> {
+    crate::run_blocking(|| Ok(current())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_resolve_identity() {
+        let settings = IdentitySettings {
+            display_name: "  Ana Ruiz ".to_string(),
+            email: "ana@example.com".to_string(),
+            changed_at: None,
+        };
+        let key = ed25519_dalek::SigningKey::from_bytes(&[4; 32]);
+        let own = fingerprint(key.verifying_key().as_bytes());
+        let actor = resolve(&settings, Some(own.clone())).unwrap();
+        assert_eq!(actor.name, "Ana Ruiz");
+        assert_eq!(actor.email.as_deref(), Some("ana@example.com"));
+        assert_eq!(actor.key_fingerprint.as_deref(), Some(own.as_str()));
+        assert!(own.starts_with("SHA256:"));
+        assert_eq!(own.len(), "SHA256:".len() + FINGERPRINT_LEN);
+        assert_eq!(resolve(&settings, None).unwrap().key_fingerprint, None);
+    }
+
+    #[test]
+    fn test_stamp_change() {
+        let current = IdentitySettings {
+            display_name: "Ana".to_string(),
+            email: "ana@example.com".to_string(),
+            changed_at: Some("2026-01-01T00:00:00Z".to_string()),
+        };
+        // A stamp sent by the frontend is ignored
+        let mut same = IdentitySettings {
+            display_name: " Ana ".to_string(),
+            changed_at: None,
+            ..current.clone()
+        };
+        stamp_change(&current, &mut same);
+        assert_eq!(same.changed_at, current.changed_at);
+
+        let mut renamed = IdentitySettings {
+            email: "ben@example.com".to_string(),
+            ..current.clone()
+        };
+        stamp_change(&current, &mut renamed);
+        assert!(renamed.changed_at.is_some() && renamed.changed_at != current.changed_at);
+    }
+
+    #[test]
+    fn test_repo_key_fingerprint() {
+        let tmp = TempDir::new("truthgit-identity");
+        let dir = tmp.path();
+        assert_eq!(key_fingerprint(dir), None);
+        std::fs::write(dir.join("proof.pub"), b"public key").unwrap();
+        assert_eq!(key_fingerprint(dir), Some(fingerprint(b"public key")));
+    }
+}
@@ -0,0 +1,696 @@
+// ==================== INDEXES ====================
+//
+// Decoded claims and vault counts, kept in memory and in the cache dir so the
+// first screen doesn't wait for every object to be inflated. On launch the
+// cached copy is loaded and a background task refreshes it, emitting
+// `index://progress` and finally `index://ready`; until then listings serve
+// the cached copy flagged `stale: true`. Claim objects are content-addressed
+// and never change, so a refresh only decodes files the index hasn't seen and
+// is cheap enough to run on every listing once the startup pass is done.
+// The claim count shown in status is cached separately and recounted only
+// after a watcher on objects/cl reports a change.
+
+use serde::{Deserialize, Serialize};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+use crate::claim_schema;
+use crate::hooks;
+use crate::json_store::{cache_path, load_json, save_json};
+use crate::objects::{self, ObjectKind, ObjectWarning};
+use crate::ontology;
+use crate::tasks::{self, TaskHandle};
+use crate::truth_store::{self, TruthStore};
+use crate::{count_claims, count_vault_entries, get_vault_path, repo_state, run_blocking};
+
+pub const INDEX_PROGRESS_EVENT: &str = "index://progress";
+pub const INDEX_READY_EVENT: &str = "index://ready";
+pub const CLAIMS_CHUNK_EVENT: &str = "claims://chunk";
+
+/// Emit `index://progress` every this many claims
+const PROGRESS_EVERY: usize = 200;
+const DEFAULT_CHUNK_SIZE: usize = 100;
+const MAX_CHUNK_SIZE: usize = 1000;
+/// Layout of the cached claim index; a cache with another version is rebuilt
+pub const INDEX_SCHEMA_VERSION: u32 = 1;
+
+static CACHE: Mutex<IndexCache> = Mutex::new(IndexCache {
+    claims: None,
+    vault: None,
+});
+/// Held while an index is being refreshed; listings that find it taken serve
+/// the cached copy instead of waiting
+static REFRESH_LOCK: Mutex<()> = Mutex::new(());
+static CLAIM_COUNT: Mutex<Option<ClaimCount>> = Mutex::new(None);
+/// Set by the objects/cl watcher; the next count lookup rescans
+static COUNT_DIRTY: AtomicBool = AtomicBool::new(false);
+
+struct IndexCache {
+    claims: Option<ClaimIndex>,
+    vault: Option<VaultCounts>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ClaimIndex {
+    #[serde(default)]
+    schema: u32,
+    repo: String,
+    built_at: Option<String>,
+    /// Decoded claims keyed by object path ("ab/cdef…")
+    claims: BTreeMap<String, serde_json::Value>,
+    /// Objects under cl/ that aren't readable claims, by the same key
+    #[serde(default)]
+    warnings: BTreeMap<String, ObjectWarning>,
+    /// Normalized ontological type of each typed claim, by the same key
+    #[serde(default)]
+    types: BTreeMap<String, String>,
+}
+
+struct ClaimCount {
+    repo: PathBuf,
+    count: usize,
+    /// None if objects/cl couldn't be watched; the count is then never trusted
+    watcher: Option<RecommendedWatcher>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultCounts {
+    pub vault: String,
+    pub built_at: Option<String>,
+    pub file_count: usize,
+    pub folder_count: usize,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimListing {
+    /// Newest first
+    pub claims: Vec<serde_json::Value>,
+    /// Served from the cache while the startup index is still being built
+    pub stale: bool,
+    pub indexed_at: Option<String>,
+    /// Claim objects that couldn't be read, or that use a claim schema
+    /// version this app doesn't read as-is
+    pub warnings: Vec<ObjectWarning>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ClaimChunk {
+    task_id: String,
+    claims: Vec<serde_json::Value>,
+    /// Claims sent so far, including these
+    sent: usize,
+    /// Claim objects in the repo; unreadable ones are skipped
+    total: usize,
+    stale: bool,
+    /// Last chunk of the stream
+    done: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClaimStreamSummary {
+    pub task_id: String,
+    pub sent: usize,
+    pub stale: bool,
+    pub warnings: Vec<ObjectWarning>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct IndexProgress {
+    /// "claims" or "vault"
+    phase: &'static str,
+    done: usize,
+    /// 0 while unknown
+    total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct IndexReady {
+    claims_count: usize,
+    file_count: usize,
+    duration_ms: u64,
+}
+
+fn claim_index_path() -> PathBuf {
+    cache_path("claim-index.json")
+}
+
+/// Schema version of the claim index cached on disk, if there is one
+pub fn cached_schema_version() -> Option<u32> {
+    #[derive(Deserialize)]
+    struct Header {
+        #[serde(default)]
+        schema: u32,
+    }
+    let content = std::fs::read_to_string(claim_index_path()).ok()?;
+    serde_json::from_str::<Header>(&content).ok().map(|h| h.schema)
+}
+
+fn vault_index_path() -> PathBuf {
+    cache_path("vault-index.json")
+}
+
+fn cache() -> MutexGuard<'static, IndexCache> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Claim objects keyed by "<prefix>/<rest>"
+fn claim_files(truth_path: &Path) -> Vec<(String, PathBuf)> {
+    TruthStore::new(truth_path)
+        .iter(truth_store::ObjectKind::Claim)
+        .map(|entry| (entry.key(), entry.path))
+        .collect()
+}
+
+/// Called after each object with its claim (None if it couldn't be read) and
+/// the done/total object counts; returning false stops the refresh
+type OnClaim<'a> = &'a mut dyn FnMut(Option<&serde_json::Value>, usize, usize) -> bool;
+
+/// Bring `previous` up to date with the repo, decoding only new objects.
+/// Objects are immutable, so known warnings are kept without a second read.
+/// Stopping early discards the work.
+fn refresh_claims(truth_path: &Path, previous: Option<ClaimIndex>, on_claim: OnClaim) -> Option<ClaimIndex> {
+    let repo = truth_path.to_string_lossy().to_string();
+    let (mut known, mut known_warnings) = previous
+        .filter(|p| p.repo == repo)
+        .map(|p| (p.claims, p.warnings))
+        .unwrap_or_default();
+    let files = claim_files(truth_path);
+    let total = files.len();
+    let mut claims = BTreeMap::new();
+    let mut warnings = BTreeMap::new();
+    for (i, (key, path)) in files.into_iter().enumerate() {
+        let claim = match (known.remove(&key), known_warnings.remove(&key)) {
+            (Some(claim), _) => Ok(claim),
+            (None, Some(warning)) => Err(warning),
+            (None, None) => objects::read_expected(&path, &format!("cl/{}", key), ObjectKind::Claim)
+                .inspect_err(|w| log::warn!("Skipping claim object {}: {}", w.object, w.message)),
+        };
+        let keep_going = on_claim(claim.as_ref().ok(), i + 1, total);
+        match claim {
+            Ok(claim) => {
+                claims.insert(key, claim);
+            }
+            Err(warning) => {
+                warnings.insert(key, warning);
+            }
+        }
+        if !keep_going {
+            return None;
+        }
+    }
+    let types = claims
+        .iter()
+        .filter_map(|(key, claim)| Some((key.clone(), ontology::claim_type(claim)?)))
+        .collect();
+    Some(ClaimIndex {
+        schema: INDEX_SCHEMA_VERSION,
+        repo,
+        built_at: Some(chrono::Utc::now().to_rfc3339()),
+        claims,
+        warnings,
+        types,
+    })
+}
+
+pub fn created_at(claim: &serde_json::Value) -> &str {
+    claim
+        .get("metadata")
+        .and_then(|m| m.get("created_at"))
+        .and_then(|t| t.as_str())
+        .unwrap_or("")
+}
+
+/// Unreadable objects, then claims written in an unsupported schema version
+fn index_warnings(index: &ClaimIndex) -> Vec<ObjectWarning> {
+    let drift = index
+        .claims
+        .iter()
+        .filter_map(|(key, claim)| claim_schema::drift_warning(&format!("cl/{}", key), claim));
+    index.warnings.values().cloned().chain(drift).collect()
+}
+
+/// Claims in `index`, newest first, limited to `ontological_type` if given
+fn listing(index: &ClaimIndex, stale: bool, ontological_type: Option<&str>) -> ClaimListing {
+    let wanted = ontological_type.map(ontology::normalize);
+    let mut claims: Vec<serde_json::Value> = index
+        .claims
+        .iter()
+        .filter(|(key, _)| wanted.is_none() || index.types.get(*key) == wanted.as_ref())
+        .map(|(_, claim)| claim.clone())
+        .collect();
+    claims.sort_by(|a, b| created_at(b).cmp(created_at(a)));
+    ClaimListing {
+        claims,
+        stale,
+        indexed_at: index.built_at.clone(),
+        warnings: index_warnings(index),
+    }
+}
+
+/// Refresh the claim index under `REFRESH_LOCK` and store it, writing the
+/// cache file only when something changed. Returns the number of claims.
+fn refresh_and_store(truth_path: &Path, on_claim: OnClaim) -> Option<usize> {
+    let fingerprint = |i: &ClaimIndex| {
+        let keys = i.claims.keys().chain(i.warnings.keys()).cloned().collect::<Vec<_>>();
+        (i.repo.clone(), keys)
+    };
+    let previous = cache().claims.clone();
+    let before = previous.as_ref().map(fingerprint);
+    let index = refresh_claims(truth_path, previous, on_claim)?;
+    if before != Some(fingerprint(&index)) {
+        if let Err(e) = save_json(&claim_index_path(), &index) {
+            log::warn!("Failed to save claim index: {}", e);
+        }
+        // Claims new since the last refresh; a first build has nothing to compare against
+        if let Some((_, keys)) = before.filter(|(repo, _)| *repo == index.repo) {
+            let known: HashSet<String> = keys.into_iter().collect();
+            let created = index
+                .claims
+                .iter()
+                .filter(|(key, _)| !known.contains(*key))
+                .map(|(key, claim)| (key.replace('/', ""), claim.clone()))
+                .collect();
+            hooks::claims_created(created);
+        }
+    }
+    let count = index.claims.len();
+    cache().claims = Some(index);
+    Some(count)
+}
+
+/// Decode every claim object again instead of trusting the index, e.g.
+/// after objects were rewritten. `keep_going` gets the done/total object
+/// counts; stopping early keeps the previous index. Returns the number of
+/// claims.
+pub fn rebuild_claim_index(truth_path: &Path, keep_going: &mut dyn FnMut(usize, usize) -> bool) -> Option<usize> {
+    let _guard = REFRESH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = cache().claims.take();
+    let count = refresh_and_store(truth_path, &mut |_, done, total| keep_going(done, total));
+    if count.is_none() {
+        cache().claims = previous;
+    }
+    claims_count(truth_path, true);
+    count
+}
+
+/// Take `REFRESH_LOCK` for a refresh. If the startup build holds it and
+/// there is a cached index for `truth_path`, returns that index instead.
+fn lock_or_cached(truth_path: &Path) -> Result<MutexGuard<'static, ()>, Box<ClaimIndex>> {
+    match REFRESH_LOCK.try_lock() {
+        Ok(guard) => Ok(guard),
+        Err(TryLockError::Poisoned(e)) => Ok(e.into_inner()),
+        Err(TryLockError::WouldBlock) => {
+            let repo = truth_path.to_string_lossy();
+            if let Some(index) = cache().claims.clone().filter(|i| i.repo == repo) {
+                return Err(Box::new(index));
+            }
+            Ok(REFRESH_LOCK.lock().unwrap_or_else(|e| e.into_inner()))
+        }
+    }
+}
+
+/// All claims in `truth_path`, newest first, optionally of one ontological
+/// type. While the startup index is being built this returns the cached copy
+/// straight away, if there is one for this repo.
+pub fn list_claims(truth_path: &Path, ontological_type: Option<&str>) -> ClaimListing {
+    let _guard = match lock_or_cached(truth_path) {
+        Ok(guard) => guard,
+        Err(cached) => return listing(&cached, true, ontological_type),
+    };
+    refresh_and_store(truth_path, &mut |_, _, _| true);
+    cache()
+        .claims
+        .as_ref()
+        .map(|index| listing(index, false, ontological_type))
+        .unwrap_or(ClaimListing {
+            claims: Vec::new(),
+            stale: false,
+            indexed_at: None,
+            warnings: Vec::new(),
+        })
+}
+
+/// Send claims in chunks of `chunk_size` as they are decoded (or straight
+/// from the cache during the startup build), skipping claims of other
+/// ontological types if `ontological_type` is given. Claims arrive in storage
+/// order, not sorted. None if `send` asked to stop.
+fn stream(
+    truth_path: &Path,
+    task_id: &str,
+    chunk_size: usize,
+    ontological_type: Option<&str>,
+    send: &mut dyn FnMut(ClaimChunk) -> bool,
+) -> Option<ClaimStreamSummary> {
+    let wanted = ontological_type.map(ontology::normalize);
+    let wanted = |claim: &serde_json::Value| wanted.is_none() || ontology::claim_type(claim) == wanted;
+    let chunk = |claims: Vec<serde_json::Value>, sent: usize, total: usize, stale: bool, done: bool| ClaimChunk {
+        task_id: task_id.to_string(),
+        claims,
+        sent,
+        total,
+        stale,
+        done,
+    };
+    let summary = |sent: usize, stale: bool, warnings: Vec<ObjectWarning>| ClaimStreamSummary {
+        task_id: task_id.to_string(),
+        sent,
+        stale,
+        warnings,
+    };
+
+    let _guard = match lock_or_cached(truth_path) {
+        Ok(guard) => guard,
+        Err(cached) => {
+            let warnings = index_warnings(&cached);
+            let claims: Vec<serde_json::Value> = cached.claims.into_values().filter(|c| wanted(c)).collect();
+            let total = claims.len();
+            let mut sent = 0;
+            for part in claims.chunks(chunk_size) {
+                sent += part.len();
+                if !send(chunk(part.to_vec(), sent, total, true, sent == total)) {
+                    return None;
+                }
+            }
+            if total == 0 {
+                send(chunk(Vec::new(), 0, 0, true, true));
+            }
+            return Some(summary(sent, true, warnings));
+        }
+    };
+
+    let mut buffer = Vec::with_capacity(chunk_size);
+    let mut sent = 0;
+    let mut finished = false;
+    let mut on_claim = |claim: Option<&serde_json::Value>, done: usize, total: usize| {
+        if let Some(claim) = claim.filter(|c| wanted(c)) {
+            buffer.push(claim.clone());
+        }
+        if buffer.len() < chunk_size && done < total {
+            return true;
+        }
+        sent += buffer.len();
+        // Unreadable objects are skipped, so the end is counted in objects
+        finished = done == total;
+        send(chunk(std::mem::take(&mut buffer), sent, total, false, finished))
+    };
+    refresh_and_store(truth_path, &mut on_claim)?;
+    if !finished {
+        send(chunk(Vec::new(), 0, 0, false, true));
+    }
+    let warnings = cache()
+        .claims
+        .as_ref()
+        .map(index_warnings)
+        .unwrap_or_default();
+    Some(summary(sent, false, warnings))
+}
+
+fn watch_claims(truth_path: &Path) -> Option<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(|result: notify::Result<notify::Event>| match result {
+        Ok(event) if !event.kind.is_access() => COUNT_DIRTY.store(true, Ordering::Relaxed),
+        Ok(_) => {}
+        Err(e) => {
+            log::warn!("Claim watcher error: {}", e);
+            COUNT_DIRTY.store(true, Ordering::Relaxed);
+        }
+    })
+    .inspect_err(|e| log::warn!("Claim count watcher disabled: {}", e))
+    .ok()?;
+    watcher
+        .watch(&TruthStore::new(truth_path).kind_dir(truth_store::ObjectKind::Claim), RecursiveMode::Recursive)
+        .inspect_err(|e| log::debug!("Not watching claims in {}: {}", truth_path.display(), e))
+        .ok()?;
+    Some(watcher)
+}
+
+/// Number of claim objects in `truth_path`. Served from memory unless
+/// `refresh` is set, the repo changed, or the watcher saw a change.
+pub fn claims_count(truth_path: &Path, refresh: bool) -> usize {
+    let mut slot = CLAIM_COUNT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = slot.as_mut().filter(|c| c.repo == truth_path && c.watcher.is_some()) {
+        // Clear the flag before counting so changes made meanwhile trigger another rescan
+        if COUNT_DIRTY.swap(false, Ordering::Relaxed) || refresh {
+            entry.count = count_claims(truth_path);
+        }
+        return entry.count;
+    }
+    COUNT_DIRTY.store(false, Ordering::Relaxed);
+    let watcher = watch_claims(truth_path);
+    let count = count_claims(truth_path);
+    *slot = Some(ClaimCount {
+        repo: truth_path.to_path_buf(),
+        count,
+        watcher,
+    });
+    count
+}
+
+fn count_vault(vault: &Path) -> VaultCounts {
+    let (file_count, folder_count, truncated) = count_vault_entries(vault);
+    VaultCounts {
+        vault: vault.to_string_lossy().to_string(),
+        built_at: Some(chrono::Utc::now().to_rfc3339()),
+        file_count,
+        folder_count,
+        truncated,
+    }
+}
+
+/// File and folder counts for `vault`, and whether they came from the cache
+/// because the startup index is still being built
+pub fn vault_counts(vault: &Path) -> (VaultCounts, bool) {
+    if matches!(REFRESH_LOCK.try_lock(), Err(TryLockError::WouldBlock)) {
+        let key = vault.to_string_lossy();
+        if let Some(counts) = cache().vault.clone().filter(|c| c.vault == key) {
+            return (counts, true);
+        }
+    }
+    let counts = count_vault(vault);
+    cache().vault = Some(counts.clone());
+    (counts, false)
+}
+
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        log::warn!("Failed to emit {}: {}", event, e);
+    }
+}
+
+/// Load the cached indexes from disk, then refresh them
+fn build(app: &AppHandle, task: &TaskHandle) -> Result<(), String> {
+    let _guard = REFRESH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let started = Instant::now();
+    {
+        let mut cache = cache();
+        if cache.claims.is_none() {
+            cache.claims = load_json::<ClaimIndex>(&claim_index_path())
+                .inspect_err(|e| log::warn!("Ignoring cached claim index: {}", e))
+                .ok()
+                .filter(|i| !i.repo.is_empty() && i.schema == INDEX_SCHEMA_VERSION);
+        }
+        if cache.vault.is_none() {
+            cache.vault = load_json::<VaultCounts>(&vault_index_path())
+                .inspect_err(|e| log::warn!("Ignoring cached vault index: {}", e))
+                .ok()
+                .filter(|c| !c.vault.is_empty());
+        }
+    }
+
+    let truth_path = repo_state::ready_path()?;
+    let mut on_claim = |_: Option<&serde_json::Value>, done: usize, total: usize| {
+        if done % PROGRESS_EVERY == 0 || done == total {
+            emit(
+                app,
+                INDEX_PROGRESS_EVENT,
+                IndexProgress {
+                    phase: "claims",
+                    done,
+                    total,
+                },
+            );
+        }
+        task.progress(
+            done as u64,
+            total as u64,
+            format!("Indexed {} of {} claims", done, total),
+        );
+        !task.is_cancelled()
+    };
+    let claims_count = refresh_and_store(&truth_path, &mut on_claim).ok_or(tasks::TASK_CANCELLED_ERROR)?;
+
+    let vault = get_vault_path().ok_or("Could not find home directory")?;
+    emit(
+        app,
+        INDEX_PROGRESS_EVENT,
+        IndexProgress {
+            phase: "vault",
+            done: 0,
+            total: 0,
+        },
+    );
+    let counts = count_vault(&vault);
+    if let Err(e) = save_json(&vault_index_path(), &counts) {
+        log::warn!("Failed to save vault index: {}", e);
+    }
+    let file_count = counts.file_count;
+    cache().vault = Some(counts);
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+    log::info!(
+        "Indexed {} claims and {} vault files in {} ms",
+        claims_count,
+        file_count,
+        duration_ms
+    );
+    emit(
+        app,
+        INDEX_READY_EVENT,
+        IndexReady {
+            claims_count,
+            file_count,
+            duration_ms,
+        },
+    );
+    Ok(())
+}
+
+/// Start the background index refresh; called once from setup
+pub fn start(app: &AppHandle) {
+    if crate::mock::is_active() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let handle = app.clone();
+        let result = tasks::run(&app, None, "index", "Indexing claims and notes", |task| {
+            run_blocking(move || build(&handle, &task))
+        })
+        .await;
+        if let Err(e) = result {
+            log::warn!("Startup indexing stopped: {}", e);
+        }
+    });
+}
+
+/// Stream claims as `claims://chunk` events tagged with the returned task
+/// id, so the first page can render before the whole repo is decoded. The
+/// last chunk has `done: true`.
+#[tauri::command]
+pub async fn stream_claims(
+    app: AppHandle,
+    task_id: Option<String>,
+    chunk_size: Option<usize>,
+    ontological_type: Option<String>,
+) -> Result<ClaimStreamSummary, String> {
+    let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).clamp(1, MAX_CHUNK_SIZE);
+    let handle = app.clone();
+    tasks::run(&app, task_id, "list_claims", "Loading claims", |task| {
+        run_blocking(move || {
+            let mut send = |chunk: ClaimChunk| {
+                task.progress(
+                    chunk.sent as u64,
+                    chunk.total as u64,
+                    format!("Loaded {} claims", chunk.sent),
+                );
+                emit(&handle, CLAIMS_CHUNK_EVENT, chunk);
+                !task.is_cancelled()
+            };
+            if crate::mock::is_active() {
+                let wanted = ontological_type.as_deref().map(ontology::normalize);
+                let claims: Vec<serde_json::Value> = crate::mock::claims()
+                    .into_iter()
+                    .filter(|c| wanted.is_none() || ontology::claim_type(c) == wanted)
+                    .collect();
+                let sent = claims.len();
+                send(ClaimChunk {
+                    task_id: task.id().to_string(),
+                    claims,
+                    sent,
+                    total: sent,
+                    stale: false,
+                    done: true,
+                });
+                return Ok(ClaimStreamSummary {
+                    task_id: task.id().to_string(),
+                    sent,
+                    stale: false,
+                    warnings: Vec::new(),
+                });
+            }
+            let truth_path = repo_state::ready_path()?;
+            stream(&truth_path, task.id(), chunk_size, ontological_type.as_deref(), &mut send)
+                .ok_or_else(|| tasks::TASK_CANCELLED_ERROR.to_string())
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn write_claim(repo: &Path, hash: &str, created_at: &str) {
+        let dir = repo.join("objects/cl").join(&hash[..2]);
+        std::fs::create_dir_all(&dir).unwrap();
+        let claim = serde_json::json!({ "$hash": hash, "metadata": { "created_at": created_at } });
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(claim.to_string().as_bytes()).unwrap();
+        std::fs::write(dir.join(&hash[2..]), encoder.finish().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_refresh_reuses_known_claims() {
+        let tmp = TempDir::new("truthgit-index");
+        let repo = tmp.path();
+        write_claim(repo, "aa1111", "2024-01-01T00:00:00Z");
+        write_claim(repo, "bb2222", "2024-06-01T00:00:00Z");
+
+        let first = refresh_claims(repo, None, &mut |_, _, _| true).unwrap();
+        assert_eq!(first.claims.len(), 2);
+        let sorted = listing(&first, false, None);
+        assert_eq!(sorted.claims[0]["$hash"], "bb2222");
+
+        // A known entry is reused rather than decoded again
+        let mut previous = first.clone();
+        previous
+            .claims
+            .insert("aa/1111".to_string(), serde_json::json!({ "$hash": "cached" }));
+        std::fs::remove_file(repo.join("objects/cl/bb/2222")).unwrap();
+        let second = refresh_claims(repo, Some(previous), &mut |_, _, _| true).unwrap();
+        assert_eq!(second.claims.len(), 1);
+        assert_eq!(second.claims["aa/1111"]["$hash"], "cached");
+
+        // An object that isn't a claim shows up as a warning, not a claim
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"\x89PNG\r\n").unwrap();
+        std::fs::create_dir_all(repo.join("objects/cl/cc")).unwrap();
+        std::fs::write(repo.join("objects/cl/cc/3333"), encoder.finish().unwrap()).unwrap();
+        let third = refresh_claims(repo, Some(second), &mut |_, _, _| true).unwrap();
+        assert_eq!(third.claims.len(), 1);
+        assert_eq!(third.warnings["cc/3333"].kind, Some(ObjectKind::Blob));
+        assert_eq!(listing(&third, false, None).warnings[0].object, "cl/cc/3333");
+
+        // Stopping discards the partial index
+        assert!(refresh_claims(repo, None, &mut |_, _, _| false).is_none());
+    }
+
+    #[test]
+    fn test_claims_count_refresh() {
+        let tmp = TempDir::new("truthgit-count");
+        let repo = tmp.path();
+        write_claim(repo, "aa1111", "2024-01-01T00:00:00Z");
+        assert_eq!(claims_count(repo, false), 1);
+        write_claim(repo, "bb2222", "2024-06-01T00:00:00Z");
+        assert_eq!(claims_count(repo, true), 2);
+    }
+}
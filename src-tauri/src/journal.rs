@@ -0,0 +1,286 @@
+// ==================== CHANGE JOURNAL ====================
+//
+// Curation edits (review states, pins, claim sources, evidence) are recorded
+// in journal.json in the truth repo together with the item's state before
+// the edit, so `undo_last_change` can put it back after a mis-click.
+// Removing an item is a soft delete: its last state stays in the journal
+// until the entry ages out, and aged-out entries are written to a retention
+// archive first. The audit trail itself is append-only and is never rolled
+// back: every undo is appended to it as a new entry, and undoing a review
+// change is recorded as a review-state transition.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::identity::{self, Actor};
+use crate::json_store::{load_json, save_json, StoreLock};
+use crate::retention::{self, ArchiveKind};
+use crate::{append_audit_entry, evidence, mock, read_claim, repo_state, reviews, run_blocking, sources, watchlist};
+use crate::AuditEntry;
+
+/// Oldest changes are archived and dropped past this; they can no longer be
+/// undone
+const MAX_JOURNAL_ENTRIES: usize = 500;
+
+/// Serializes read-modify-write cycles on journal.json
+static JOURNAL_LOCK: StoreLock = StoreLock::new();
+
+/// The store a change was made in; each restores its own items on undo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Store {
+    Reviews,
+    Watchlist,
+    Sources,
+    Evidence,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    pub id: String,
+    pub at: String,
+    #[serde(default)]
+    pub actor: Option<Actor>,
+    pub store: Store,
+    /// Item within the store: a claim hash, or an evidence ID
+    pub key: String,
+    pub description: String,
+    /// The item before the change; None if the change created it
+    pub before: Option<serde_json::Value>,
+    #[serde(default)]
+    pub undone_at: Option<String>,
+}
+
+fn journal_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("journal.json")
+}
+
+/// Journal an edit. Called after the store was written; failing to journal
+/// is logged rather than failing the edit.
+pub(crate) fn record<T: Serialize>(store: Store, key: &str, description: String, before: Option<&T>) {
+    let result = (|| {
+        let truth_path = repo_state::ready_path()?;
+        let before = before
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize change: {}", e))?;
+        let change = Change {
+            id: uuid::Uuid::new_v4().to_string(),
+            at: chrono::Utc::now().to_rfc3339(),
+            actor: identity::current(),
+            store,
+            key: key.to_string(),
+            description,
+            before,
+            undone_at: None,
+        };
+        let _guard = JOURNAL_LOCK.lock(&journal_path(&truth_path))?;
+        let mut journal: Vec<Change> = load_json(&journal_path(&truth_path))?;
+        let overflow = push(&mut journal, change);
+        if !overflow.is_empty() {
+            archive_overflow(&truth_path, &mut journal, overflow);
+        }
+        save_json(&journal_path(&truth_path), &journal)
+    })();
+    if let Err(e) = result {
+        log::warn!("Failed to journal change: {}", e);
+    }
+}
+
+/// Put a keyed item back as it was before a change, removing it if the
+/// change created it
+pub(crate) fn restore_entry<T: DeserializeOwned>(
+    items: &mut BTreeMap<String, T>,
+    key: &str,
+    before: Option<serde_json::Value>,
+) -> Result<(), String> {
+    match before {
+        Some(value) => {
+            let item = serde_json::from_value(value).map_err(|e| format!("Invalid journal entry: {}", e))?;
+            items.insert(key.to_string(), item);
+        }
+        None => {
+            items.remove(key);
+        }
+    }
+    Ok(())
+}
+
+/// Changes to one item (a claim hash or an evidence ID), newest first
+pub(crate) fn changes_for(truth_path: &Path, key: &str) -> Result<Vec<Change>, String> {
+    let _guard = JOURNAL_LOCK.lock(&journal_path(truth_path))?;
+    let journal: Vec<Change> = load_json(&journal_path(truth_path))?;
+    Ok(journal.into_iter().filter(|c| c.key == key).collect())
+}
+
+/// Newest first, capped at `MAX_JOURNAL_ENTRIES`. Returns the changes that
+/// fell off the end.
+fn push(journal: &mut Vec<Change>, change: Change) -> Vec<Change> {
+    journal.insert(0, change);
+    journal.split_off(journal.len().min(MAX_JOURNAL_ENTRIES))
+}
+
+/// Archive changes past the cap before they're dropped. If that fails they
+/// stay in the journal and the next change tries again.
+fn archive_overflow(truth_path: &Path, journal: &mut Vec<Change>, overflow: Vec<Change>) {
+    let records: Result<Vec<_>, _> = overflow.iter().map(serde_json::to_value).collect();
+    let archived = records
+        .map_err(|e| format!("Failed to serialize changes: {}", e))
+        .and_then(|records| {
+            retention::archive_records(truth_path, ArchiveKind::Journal, "changes past the undo journal limit", records)
+        });
+    if let Err(e) = archived {
+        log::warn!("Failed to archive old journal entries, keeping them: {}", e);
+        journal.extend(overflow);
+    }
+}
+
+/// The most recent change that hasn't been undone yet
+fn last_open(journal: &[Change]) -> Option<&Change> {
+    journal.iter().find(|c| c.undone_at.is_none())
+}
+
+/// Put back the item `change` touched and return the audit entry for it
+fn restore(change: &Change, actor: &Actor) -> Result<AuditEntry, String> {
+    let before = change.before.clone();
+    let claim = match change.store {
+        Store::Evidence => serde_json::Value::Null,
+        _ => read_claim(change.key.clone()).unwrap_or(serde_json::Value::Null),
+    };
+    match change.store {
+        Store::Reviews => {
+            let step = reviews::restore(&change.key, before, actor)?;
+            let mut entry = reviews::audit_entry(&claim, &change.key, &step);
+            entry.result_action.push_str(&format!(" (undo of: {})", change.description));
+            return Ok(entry);
+        }
+        Store::Watchlist => watchlist::restore(&change.key, before)?,
+        Store::Sources => sources::restore(&change.key, before)?,
+        Store::Evidence => evidence::restore(&change.key, before)?,
+    }
+    Ok(undo_audit_entry(change, &claim, actor, &chrono::Utc::now().to_rfc3339()))
+}
+
+/// The audit entry for undoing a change outside the review workflow
+fn undo_audit_entry(change: &Change, claim: &serde_json::Value, actor: &Actor, at: &str) -> AuditEntry {
+    let entry = reviews::claim_audit_entry(claim, &change.key, actor, at);
+    AuditEntry {
+        action: "undo".to_string(),
+        result_status: if change.before.is_some() { "restored" } else { "removed" }.to_string(),
+        result_action: format!("Undid: {}", change.description),
+        claim_hash: (change.store != Store::Evidence).then(|| change.key.clone()),
+        ..entry
+    }
+}
+
+/// Recent curation changes, newest first
+#[tauri::command]
+pub async fn list_changes(limit: Option<usize>) -> Result<Vec<Change>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        let _guard = JOURNAL_LOCK.lock(&journal_path(&truth_path))?;
+        let mut journal: Vec<Change> = load_json(&journal_path(&truth_path))?;
+        journal.truncate(limit.unwrap_or(50));
+        Ok(journal)
+    })
+    .await
+}
+
+/// Put back the item touched by the most recent change that wasn't undone,
+/// attributed to the current identity in the audit trail. Returns that
+/// change, or None when there is nothing left to undo.
+#[tauri::command]
+pub async fn undo_last_change() -> Result<Option<Change>, String> {
+    if mock::is_active() {
+        return Err("Undo is disabled in mock mode".to_string());
+    }
+    run_blocking(|| {
+        let actor = identity::require()?;
+        let truth_path = repo_state::ready_path()?;
+        let _guard = JOURNAL_LOCK.lock(&journal_path(&truth_path))?;
+        let mut journal: Vec<Change> = load_json(&journal_path(&truth_path))?;
+        let Some(change) = last_open(&journal).cloned() else {
+            return Ok(None);
+        };
+        // Stores never take the journal lock while holding their own, so
+        // restoring under it can't deadlock
+        let audit = restore(&change, &actor)?;
+        let undone_at = chrono::Utc::now().to_rfc3339();
+        if let Some(entry) = journal.iter_mut().find(|c| c.id == change.id) {
+            entry.undone_at = Some(undone_at.clone());
+        }
+        save_json(&journal_path(&truth_path), &journal)?;
+        append_audit_entry(audit).map_err(|e| format!("Change undone, but writing the audit entry failed: {}", e))?;
+        log::info!("Undid change: {} by {}", change.description, actor.name);
+        Ok(Some(Change {
+            undone_at: Some(undone_at),
+            ..change
+        }))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(id: &str, undone: bool) -> Change {
+        Change {
+            id: id.to_string(),
+            at: "2026-03-01T12:00:00Z".to_string(),
+            actor: None,
+            store: Store::Watchlist,
+            key: "abc123".to_string(),
+            description: format!("change {}", id),
+            before: None,
+            undone_at: undone.then(|| "2026-03-01T12:05:00Z".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_undo_skips_undone_changes() {
+        let mut journal = Vec::new();
+        push(&mut journal, change("a", false));
+        push(&mut journal, change("b", true));
+        assert_eq!(last_open(&journal).map(|c| c.id.as_str()), Some("a"));
+
+        let mut overflow = Vec::new();
+        for i in 0..MAX_JOURNAL_ENTRIES {
+            overflow.extend(push(&mut journal, change(&i.to_string(), true)));
+        }
+        assert_eq!(journal.len(), MAX_JOURNAL_ENTRIES);
+        assert_eq!(overflow.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        assert!(last_open(&journal).is_none());
+    }
+
+    #[test]
+    fn test_undo_audit_entry() {
+        let actor = Actor {
+            name: "Ana".to_string(),
+            email: None,
+            key_fingerprint: None,
+        };
+        let claim = serde_json::json!({"content": "Water boils at 100C", "domain": "physics"});
+        let entry = undo_audit_entry(&change("a", false), &claim, &actor, "2026-03-01T12:10:00Z");
+        assert_eq!(entry.action, "undo");
+        assert_eq!(entry.result_status, "removed");
+        assert_eq!(entry.result_action, "Undid: change a");
+        assert_eq!(entry.claim, "Water boils at 100C");
+        assert_eq!(entry.claim_hash.as_deref(), Some("abc123"));
+        assert_eq!(entry.actor.map(|a| a.name).as_deref(), Some("Ana"));
+
+        let evidence = Change {
+            store: Store::Evidence,
+            before: Some(serde_json::json!({})),
+            ..change("b", false)
+        };
+        let entry = undo_audit_entry(&evidence, &serde_json::Value::Null, &actor, "2026-03-01T12:10:00Z");
+        assert_eq!(entry.result_status, "restored");
+        assert_eq!(entry.claim_hash, None);
+    }
+}
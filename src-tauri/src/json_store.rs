@@ -0,0 +1,184 @@
+// ==================== JSON STORES ====================
+//
+// Small helpers for the JSON files the app keeps in its config directory
+// (~/.config/truthgit/) and truth repo. A missing file reads as the type's
+// default. Writes go to a temp file that is renamed over the original, so a
+// crash or a concurrent reader never sees half a file, and `StoreLock`
+// serializes read-modify-write cycles across threads and app instances.
+
+use fs2::FileExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Guards one store: an in-process mutex, plus an advisory lock on
+/// `<file>.lock` next to the store so a second app instance waits its turn
+pub struct StoreLock(Mutex<()>);
+
+/// Held for the duration of a read-modify-write cycle; dropping it releases
+/// both locks
+pub struct StoreGuard<'a> {
+    _file: File,
+    _guard: MutexGuard<'a, ()>,
+}
+
+impl StoreLock {
+    pub const fn new() -> Self {
+        StoreLock(Mutex::new(()))
+    }
+
+    pub fn lock(&self, path: &Path) -> Result<StoreGuard<'_>, String> {
+        let guard = self.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let file = lock_file(path)?;
+        Ok(StoreGuard {
+            _file: file,
+            _guard: guard,
+        })
+    }
+}
+
+/// Take the cross-process lock for `path`, blocking until it is free. The
+/// lock is released when the returned file is closed.
+pub fn lock_file(path: &Path) -> Result<File, String> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir for {}: {}", file_label(path), e))?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_file_name(name))
+        .map_err(|e| format!("Failed to open lock for {}: {}", file_label(path), e))?;
+    file.lock_exclusive()
+        .map_err(|e| format!("Failed to lock {}: {}", file_label(path), e))?;
+    Ok(file)
+}
+
+/// Replace `path` with `bytes` atomically: write a temp file in the same
+/// directory, flush it to disk, then rename it over the original
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+    let tmp = dir.join(name);
+    let result = (|| {
+        let mut file = File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Stands in for the user's config and cache directories while tests drive
+/// the command layer; set once per process by `test_support`
+#[cfg(feature = "test-support")]
+pub(crate) static SANDBOX: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+fn user_dir(dir: Option<PathBuf>, _sandboxed: &str) -> PathBuf {
+    #[cfg(feature = "test-support")]
+    if let Some(root) = SANDBOX.get() {
+        return root.join(_sandboxed);
+    }
+    dir.unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// The app's config directory (~/.config/truthgit on Linux)
+pub fn config_dir() -> PathBuf {
+    user_dir(dirs::config_dir(), "config").join("truthgit")
+}
+
+pub fn config_path(file_name: &str) -> PathBuf {
+    config_dir().join(file_name)
+}
+
+/// Cache directory (~/.cache/truthgit on Linux), for data that can be rebuilt
+pub fn cache_path(file_name: &str) -> PathBuf {
+    user_dir(dirs::cache_dir(), "cache").join("truthgit").join(file_name)
+}
+
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", file_label(path), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", file_label(path), e))
+}
+
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", file_label(path), e))?;
+    write_atomic(path, content.as_bytes()).map_err(|e| format!("Failed to write {}: {}", file_label(path), e))
+}
+
+/// File name only, so errors don't leak the full path
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "file".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_missing_file_loads_default() {
+        let tmp = TempDir::new("truthgit-json-store");
+        let path = tmp.path().join("missing.json");
+        let value: BTreeMap<String, u32> = load_json(&path).unwrap();
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let tmp = TempDir::new("truthgit-json-store");
+        let path = tmp.path().join("roundtrip.json");
+        let mut value = BTreeMap::new();
+        value.insert("a".to_string(), 1u32);
+        save_json(&path, &value).unwrap();
+        let loaded: BTreeMap<String, u32> = load_json(&path).unwrap();
+        assert_eq!(loaded, value);
+    }
+
+    #[test]
+    fn test_store_lock_serializes_writers() {
+        static LOCK: StoreLock = StoreLock::new();
+        let tmp = TempDir::new("truthgit-json-store");
+        let dir = tmp.path();
+        let path = dir.join("counter.json");
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        let _guard = LOCK.lock(&path).unwrap();
+                        let count: u32 = load_json(&path).unwrap();
+                        save_json(&path, &(count + 1)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(load_json::<u32>(&path).unwrap(), 80);
+        // Only the store and its lock file are left: no temp files
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 2);
+    }
+}
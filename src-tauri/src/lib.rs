@@ -1,13 +1,113 @@
-use flate2::read::ZlibDecoder;
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
-use std::io::Read;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::RwLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tauri::Manager;
 use walkdir::WalkDir;
 
+use security_events::SecurityEventKind;
+use truth_store::{ObjectId, ObjectKind, TruthStore};
+use verification::Verification;
+
+mod activity_heatmap;
+mod approvals;
+mod attestations;
+mod auth;
+mod backup;
+mod claim_diff;
+mod claim_filters;
+mod claim_schema;
+mod claim_templates;
+mod clipboard_verify;
+mod command_registry;
+mod compaction;
+mod compression;
+mod consent;
+mod coverage;
+mod crash_reports;
+mod deep_links;
+mod diagnostics;
+mod digest;
+mod domain_suggest;
+mod domain_usage;
+mod domains;
+mod engine;
+mod environment;
+mod evidence;
+mod exports;
+mod feeds;
+mod file_intake;
+mod folder_defaults;
+mod global_search;
+mod hooks;
+mod http_client;
+mod i18n;
+mod identity;
+mod index;
+mod journal;
+mod json_store;
+mod live_events;
+mod local_api;
+mod logging;
+mod metrics;
+mod mock;
+mod note_annotations;
+mod note_links;
+mod notes_export;
+mod notifications;
+mod objects;
+mod ontology;
+mod paths;
+mod plugins;
+mod process_metrics;
+mod profiles;
+mod quick_capture;
+mod rate_limit;
+mod receipts;
+mod recent_verifications;
+mod reports;
+mod remote_browse;
+mod remote_history;
+mod remotes;
+mod repo_crypto;
+mod repo_import;
+mod repo_init;
+mod repo_state;
+mod repos;
+mod retention;
+mod reviews;
+mod security_events;
+mod session;
+mod settings_bundle;
+mod settings_events;
+mod settings_migrations;
+mod settings_validation;
+mod setup;
+mod share_bundles;
+mod shortcuts;
+mod single_instance;
+mod snapshots;
+mod sources;
+mod sync;
+mod sync_conflicts;
+mod tasks;
+#[cfg(any(test, feature = "test-support"))]
+mod test_dirs;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+mod timeline;
+mod tray;
+mod truth_store;
+mod updates;
+mod vault_crypto;
+mod verification;
+mod watchlist;
+mod web_archive;
+mod work_queue;
+
 // ==================== SECURITY LIMITS ====================
 
 /// Maximum files to traverse in vault operations (DoS prevention)
@@ -31,7 +131,8 @@ const SUBPROCESS_TIMEOUT_SECS: u64 = 30;
 /// - Username from paths
 /// - Other potentially sensitive information
 fn sanitize_error(error: &str) -> String {
-    let mut result = error.to_string();
+    // Show Windows long paths (\\?\C:\...) in their usual form
+    let mut result = paths::strip_verbatim(error);
 
     // Replace home directory with ~
     if let Some(home) = dirs::home_dir() {
@@ -54,15 +155,78 @@ fn sanitize_error(error: &str) -> String {
 
 // ==================== APP SETTINGS (CONFIGURABLE) ====================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppSettings {
+    #[serde(default = "settings_migrations::current_schema_version")]
+    pub schema_version: u32,
     pub vault_path: String,
     pub truth_repo_path: String,
     pub api_mode: String,  // "remote" or "local"
+    #[serde(default = "engine::default_backend_mode")]
+    pub backend_mode: String,  // "cli", "embedded" or "mock"
     pub api_url: String,
     pub default_risk_profile: String,
     pub terminal_font_size: u32,
     pub auto_save_audit: bool,
+    /// Empty: use the system proxy environment variables
+    #[serde(default)]
+    pub proxy_url: String,
+    #[serde(default)]
+    pub no_proxy: String,
+    /// Extra PEM root certificates, for TLS-intercepting proxies
+    #[serde(default)]
+    pub ca_bundle_path: String,
+    /// Opt-in HTTP API on 127.0.0.1 for external tools
+    #[serde(default)]
+    pub local_api_enabled: bool,
+    #[serde(default = "local_api::default_local_api_port")]
+    pub local_api_port: u16,
+    /// Closing the window hides it to the tray; background work keeps running
+    #[serde(default)]
+    pub minimize_to_tray: bool,
+    #[serde(default)]
+    pub notifications: notifications::NotificationSettings,
+    #[serde(default)]
+    pub shortcuts: shortcuts::ShortcutSettings,
+    /// Who audit entries, verifications and reviews are attributed to
+    #[serde(default)]
+    pub identity: identity::IdentitySettings,
+    /// Which claims need a second reviewer before they're approved
+    #[serde(default)]
+    pub approval_policy: approvals::ApprovalPolicy,
+    /// How often pinned claims are re-verified
+    #[serde(default)]
+    pub watchlist: watchlist::WatchlistSettings,
+    /// How often watched feeds and pages are fetched for new claims
+    #[serde(default)]
+    pub feeds: feeds::FeedSettings,
+    /// Period of automatically written digests
+    #[serde(default)]
+    pub digest: digest::DigestSettings,
+    /// Inbox note for captured text and whether claims are pulled from it
+    #[serde(default)]
+    pub quick_capture: quick_capture::QuickCaptureSettings,
+    /// How long audit entries are kept and where removed records are archived
+    #[serde(default)]
+    pub retention: retention::RetentionSettings,
+    /// Codecs for new objects and backups
+    #[serde(default)]
+    pub compression: compression::CompressionSettings,
+    /// How often the truth repo is compacted in the background
+    #[serde(default)]
+    pub compaction: compaction::CompactionSettings,
+    /// Executables run before and after verification and on new claims
+    #[serde(default)]
+    pub hooks: hooks::HookSettings,
+    /// Language for backend messages and reports
+    #[serde(default = "i18n::default_locale")]
+    pub locale: String,
+    /// Count feature use and latency locally (never sent anywhere)
+    #[serde(default)]
+    pub usage_metrics: bool,
+    /// Release channel for app updates: "stable" or "beta"
+    #[serde(default = "updates::default_update_channel")]
+    pub update_channel: String,
 }
 
 impl Default for AppSettings {
@@ -72,18 +236,41 @@ impl Default for AppSettings {
         // Use standard XDG-like paths that work for any user
         // Users should configure these in Settings on first run
         Self {
+            schema_version: settings_migrations::SETTINGS_SCHEMA_VERSION,
             // Default: ~/Documents/Obsidian (common location)
             vault_path: home.join("Documents/Obsidian").to_string_lossy().to_string(),
             // Default: ~/.truth (standard location in home directory)
             truth_repo_path: home.join(".truth").to_string_lossy().to_string(),
             // LOCAL-FIRST by default - no remote API calls unless explicitly enabled
             api_mode: "local".to_string(),
+            backend_mode: engine::default_backend_mode(),
             // SECURITY: Default to localhost - user must explicitly configure remote API
             // This prevents accidental data leakage to remote endpoints
             api_url: "http://localhost:8000".to_string(),
             default_risk_profile: "medium".to_string(),
             terminal_font_size: 14,
             auto_save_audit: true,
+            proxy_url: String::new(),
+            no_proxy: String::new(),
+            ca_bundle_path: String::new(),
+            local_api_enabled: false,
+            local_api_port: local_api::DEFAULT_LOCAL_API_PORT,
+            minimize_to_tray: false,
+            notifications: notifications::NotificationSettings::default(),
+            shortcuts: shortcuts::ShortcutSettings::default(),
+            identity: identity::IdentitySettings::default(),
+            approval_policy: approvals::ApprovalPolicy::default(),
+            watchlist: watchlist::WatchlistSettings::default(),
+            feeds: feeds::FeedSettings::default(),
+            digest: digest::DigestSettings::default(),
+            quick_capture: quick_capture::QuickCaptureSettings::default(),
+            retention: retention::RetentionSettings::default(),
+            compression: compression::CompressionSettings::default(),
+            compaction: compaction::CompactionSettings::default(),
+            hooks: hooks::HookSettings::default(),
+            locale: i18n::default_locale(),
+            usage_metrics: false,
+            update_channel: updates::default_update_channel(),
         }
     }
 }
@@ -94,19 +281,51 @@ static SETTINGS: std::sync::LazyLock<RwLock<AppSettings>> = std::sync::LazyLock:
 });
 
 fn get_settings_path() -> PathBuf {
-    dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("truthgit")
-        .join("settings.json")
+    json_store::config_path("settings.json")
 }
 
 fn load_settings_from_file() -> Option<AppSettings> {
     let path = get_settings_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).ok()?;
-        serde_json::from_str(&content).ok()
-    } else {
-        None
+    if !path.exists() {
+        return None;
+    }
+
+    let content = fs::read_to_string(&path).ok()?;
+    let raw: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(raw) => raw,
+        Err(e) => {
+            // Keep the unreadable file around instead of silently overwriting it later
+            let backup = path.with_extension("json.corrupt.bak");
+            let _ = fs::copy(&path, &backup);
+            log::error!("settings.json is not valid JSON ({}); using defaults", e);
+            return None;
+        }
+    };
+
+    match settings_migrations::migrate_settings(raw) {
+        Ok(outcome) => {
+            if !outcome.reset_fields.is_empty() {
+                log::warn!(
+                    "settings.json: invalid values reset to defaults: {}",
+                    outcome.reset_fields.join(", ")
+                );
+            }
+            if outcome.changed {
+                // Preserve the pre-migration file so a bad migration is recoverable
+                let backup = path.with_extension(format!("json.v{}.bak", outcome.from_version));
+                if let Err(e) = fs::copy(&path, &backup) {
+                    log::warn!("Failed to back up settings before migration: {}", e);
+                }
+                if let Err(e) = save_settings_to_file(&outcome.settings) {
+                    log::warn!("Failed to write migrated settings: {}", e);
+                }
+            }
+            Some(outcome.settings)
+        }
+        Err(e) => {
+            log::error!("Failed to migrate settings.json: {}; using defaults", e);
+            None
+        }
     }
 }
 
@@ -117,7 +336,9 @@ fn save_settings_to_file(settings: &AppSettings) -> Result<(), String> {
     }
     let content = serde_json::to_string_pretty(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    fs::write(&path, content).map_err(|e| format!("Failed to write settings: {}", e))
+    // Another app instance may be saving at the same moment
+    let _lock = json_store::lock_file(&path)?;
+    json_store::write_atomic(&path, content.as_bytes()).map_err(|e| format!("Failed to write settings: {}", e))
 }
 
 #[tauri::command]
@@ -126,15 +347,90 @@ async fn get_settings() -> Result<AppSettings, String> {
     Ok(settings.clone())
 }
 
-#[tauri::command]
-async fn update_settings(new_settings: AppSettings) -> Result<(), String> {
+/// Validate, persist and publish new settings.
+/// Callers hold the write lock so concurrent updates are serialized, and ask
+/// with `consent::require_path_settings` first if the paths may change.
+fn commit_settings(current: &mut AppSettings, mut new_settings: AppSettings) -> Result<(), String> {
+    // Whatever the frontend sent is now written in the current schema
+    new_settings.schema_version = new_settings
+        .schema_version
+        .max(settings_migrations::SETTINGS_SCHEMA_VERSION);
+
+    identity::stamp_change(&current.identity, &mut new_settings.identity);
+    consent::check_path_settings(current, &new_settings)?;
+
+    let validation = settings_validation::validate(&new_settings);
+    if !validation.valid {
+        return Err(sanitize_error(&format!("Invalid settings: {}", validation.error_summary())));
+    }
+    if new_settings.truth_repo_path != current.truth_repo_path {
+        settings_validation::probe_truth_repo(&new_settings.truth_repo_path).map_err(|e| sanitize_error(&e))?;
+    }
+
     save_settings_to_file(&new_settings)?;
-    let mut settings = SETTINGS.write().map_err(|e| format!("Lock error: {}", e))?;
-    *settings = new_settings;
+    *current = new_settings;
     Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[tauri::command]
+async fn update_settings(app: tauri::AppHandle, new_settings: AppSettings) -> Result<(), String> {
+    consent::require_path_settings(&new_settings).await?;
+    let updated = {
+        let mut settings = SETTINGS.write().map_err(|e| format!("Lock error: {}", e))?;
+        commit_settings(&mut settings, new_settings)?;
+        settings.clone()
+    };
+    settings_events::emit_changed(&app, "app", &updated);
+    Ok(())
+}
+
+/// Overlay the keys present in `patch` onto `current`.
+/// Unknown keys and values of the wrong type are rejected, not ignored.
+fn merge_settings_patch(current: &AppSettings, patch: serde_json::Value) -> Result<AppSettings, String> {
+    let patch = match patch {
+        serde_json::Value::Object(patch) => patch,
+        _ => return Err("Settings patch must be a JSON object".to_string()),
+    };
+
+    let mut merged = match serde_json::to_value(current) {
+        Ok(serde_json::Value::Object(merged)) => merged,
+        _ => return Err("Failed to serialize current settings".to_string()),
+    };
+
+    for (key, value) in patch {
+        if !merged.contains_key(&key) {
+            return Err(format!("Unknown setting '{}'", key));
+        }
+        merged.insert(key, value);
+    }
+
+    serde_json::from_value(serde_json::Value::Object(merged))
+        .map_err(|e| format!("Invalid settings patch: {}", e))
+}
+
+#[tauri::command]
+async fn update_settings_partial(
+    app: tauri::AppHandle,
+    patch: serde_json::Value,
+) -> Result<AppSettings, String> {
+    let preview = {
+        let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+        merge_settings_patch(&settings, patch.clone())?
+    };
+    consent::require_path_settings(&preview).await?;
+    // Read-merge-write under one write lock so two views saving different
+    // fields can't overwrite each other with stale copies
+    let updated = {
+        let mut settings = SETTINGS.write().map_err(|e| format!("Lock error: {}", e))?;
+        let new_settings = merge_settings_patch(&settings, patch)?;
+        commit_settings(&mut settings, new_settings)?;
+        settings.clone()
+    };
+    settings_events::emit_changed(&app, "app", &updated);
+    Ok(updated)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct GovernanceResult {
     pub status: String,
     pub action: String,
@@ -142,6 +438,25 @@ pub struct GovernanceResult {
     pub reason: String,
     pub audit_ref: String,
     pub ontological_type: Option<String>,
+    /// Who requested the verification from this desktop
+    #[serde(default)]
+    pub actor: Option<identity::Actor>,
+    /// Claim object the verification is about, when the engine reports it
+    #[serde(default)]
+    pub claim_hash: Option<String>,
+    /// Verification object the engine wrote, when it reports it
+    #[serde(default)]
+    pub verification_hash: Option<String>,
+    /// Where the verdict came from: "cli", "embedded", "mock" or "api"
+    #[serde(default)]
+    pub backend: Option<String>,
+    /// Domain the claim was verified against: the registered name when the
+    /// one given was an alias or spelled differently
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// What pre- and post-verification hooks attached, by hook name
+    #[serde(default, skip_serializing_if = "hooks::Annotations::is_empty")]
+    pub annotations: hooks::Annotations,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -171,6 +486,10 @@ pub struct ClaimMetadata {
     pub tags: Option<Vec<String>>,
     pub created_at: Option<String>,
     pub created_by: Option<String>,
+    pub ontological_type: Option<String>,
+    /// Where the claim came from; more can be added from the app (sources.rs)
+    #[serde(default)]
+    pub sources: Vec<sources::ClaimSource>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -186,7 +505,19 @@ fn get_truth_path() -> Option<PathBuf> {
     // Use configurable path from settings
     let settings = SETTINGS.read().ok()?;
     let path = PathBuf::from(&settings.truth_repo_path);
-    Some(path)
+    Some(paths::long_path(path))
+}
+
+/// Run filesystem work on the blocking thread pool, so a large repo or vault
+/// scan doesn't hold up every other command on the async runtime
+async fn run_blocking<T, F>(work: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| format!("Background task failed: {}", e))?
 }
 
 /// Execute a command with timeout (prevents hanging on blocked processes)
@@ -230,7 +561,7 @@ fn get_vault_path() -> Option<PathBuf> {
     // Use configurable path from settings
     let settings = SETTINGS.read().ok()?;
     let path = PathBuf::from(&settings.vault_path);
-    Some(path)
+    Some(paths::long_path(path))
 }
 
 // ====== SECURITY: Path traversal prevention ======
@@ -238,23 +569,28 @@ fn get_vault_path() -> Option<PathBuf> {
 /// Validates that a path is safely within a base directory.
 /// Prevents directory traversal attacks (e.g., "../../etc/passwd")
 fn validate_path_within_base(base: &PathBuf, relative: &str) -> Result<PathBuf, String> {
+    let blocked = |key: &str| {
+        security_events::record(SecurityEventKind::PathTraversal, "path_validation", relative);
+        i18n::tr(key, &[])
+    };
+
     // Reject obviously malicious patterns early
     if relative.contains("..") {
-        return Err("🚫 BLOCKED: Path contains '..' (directory traversal attempt)".to_string());
+        return Err(blocked("blocked.path_traversal"));
     }
 
     // Reject absolute paths
     if relative.starts_with('/') || relative.starts_with('\\') {
-        return Err("🚫 BLOCKED: Absolute paths are not allowed".to_string());
+        return Err(blocked("blocked.absolute_path"));
     }
 
     // Reject paths with null bytes (can bypass checks in some systems)
     if relative.contains('\0') {
-        return Err("🚫 BLOCKED: Path contains null byte".to_string());
+        return Err(blocked("blocked.null_byte"));
     }
 
     // Construct the target path
-    let target = base.join(relative);
+    let target = paths::join(base, relative);
 
     // Canonicalize both paths to resolve symlinks and normalize
     let canonical_base = fs::canonicalize(base)
@@ -265,65 +601,152 @@ fn validate_path_within_base(base: &PathBuf, relative: &str) -> Result<PathBuf,
 
     // SECURITY: Ensure the target is within the base directory
     if !canonical_target.starts_with(&canonical_base) {
-        return Err("🚫 BLOCKED: Path escapes allowed directory (directory traversal attempt)".to_string());
+        return Err(blocked("blocked.path_escapes"));
     }
 
     Ok(canonical_target)
 }
 
-fn decompress_object(path: &PathBuf) -> Result<serde_json::Value, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-
-    let mut decoder = ZlibDecoder::new(file);
-
-    // SECURITY: Read with size limit to prevent OOM from malicious compressed data
-    let mut decompressed = Vec::new();
-    let mut buffer = [0u8; 8192];
-    let mut total_read = 0usize;
+/// Why a stored object could not be read. Converts to the usual `String`
+/// error at command boundaries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ObjectError {
+    Io { message: String },
+    /// Inflates past `limit` bytes (oversized evidence or a decompression bomb)
+    TooLarge { limit: usize },
+    Corrupt { message: String },
+    InvalidJson { message: String },
+    /// Sealed by repo encryption and the repo is locked, or the key is wrong
+    Encrypted { message: String },
+}
 
-    loop {
-        match decoder.read(&mut buffer) {
-            Ok(0) => break, // EOF
-            Ok(n) => {
-                total_read += n;
-                if total_read > MAX_DECOMPRESSED_SIZE {
-                    return Err(format!(
-                        "Decompressed data exceeds size limit ({} bytes). Possible decompression bomb.",
-                        MAX_DECOMPRESSED_SIZE
-                    ));
-                }
-                decompressed.extend_from_slice(&buffer[..n]);
-            }
-            Err(e) => return Err(format!("Failed to decompress: {}", e)),
+impl std::fmt::Display for ObjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { message } => write!(f, "Failed to open file: {}", message),
+            Self::TooLarge { limit } => write!(
+                f,
+                "Object too large: decompressed data exceeds size limit ({} bytes). Possible decompression bomb.",
+                limit
+            ),
+            Self::Corrupt { message } => write!(f, "Failed to decompress: {}", message),
+            Self::InvalidJson { message } => write!(f, "Failed to parse JSON: {}", message),
+            Self::Encrypted { message } => write!(f, "Failed to decrypt: {}", message),
         }
     }
+}
 
-    let decompressed_str = String::from_utf8(decompressed)
-        .map_err(|e| format!("Invalid UTF-8 in decompressed data: {}", e))?;
-
-    serde_json::from_str(&decompressed_str)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))
+impl From<ObjectError> for String {
+    fn from(e: ObjectError) -> Self {
+        e.to_string()
+    }
 }
 
+/// Verify a claim entered by the user; the parameters are kept in recent verifications.
+/// With `note_path`, an empty domain or risk profile comes from the note's folder defaults.
 #[tauri::command]
 async fn governance_verify(
     claim: String,
     domain: String,
     risk_profile: String,
+    remote: Option<String>,
+    note_path: Option<String>,
+) -> Result<GovernanceResult, String> {
+    let (domain, risk_profile) =
+        run_blocking(move || folder_defaults::apply(note_path.as_deref(), domain, risk_profile)).await?;
+    let started = Instant::now();
+    let outcome = run_governance_verify(claim.clone(), domain.clone(), risk_profile.clone(), remote.clone()).await;
+    metrics::record("verify", started, outcome.is_ok());
+    let domain = resolved_domain(&outcome, &domain);
+    recent_verifications::record(&claim, &domain, &risk_profile, remote.as_deref(), &outcome).await;
+    outcome
+}
+
+/// Verify a claim and write its audit entry. Every way of verifying goes
+/// through here (manual, batch, clipboard, watchlist, local API), so none of
+/// them can skip the audit trail; only manual runs are kept in recents.
+async fn run_governance_verify(
+    claim: String,
+    domain: String,
+    risk_profile: String,
+    remote: Option<String>,
+) -> Result<GovernanceResult, String> {
+    let started = Instant::now();
+    // Registered domains resolve to their canonical spelling and may supply the risk profile
+    let (domain, risk_profile) = match domains::resolve_domain(&domain)? {
+        Some(registered) if risk_profile.trim().is_empty() => {
+            let risk = registered.default_risk_profile.unwrap_or(risk_profile);
+            (registered.name, risk)
+        }
+        Some(registered) => (registered.name, risk_profile),
+        None => (domain, risk_profile),
+    };
+
+    let outcome = verify_with_hooks(claim.clone(), domain.clone(), risk_profile.clone(), remote)
+        .await
+        .map(|result| GovernanceResult {
+            domain: Some(domain.clone()),
+            ..result
+        });
+    record_verification_audit(&claim, &domain, &risk_profile, &outcome, started).await;
+    outcome
+}
+
+/// The domain a verification ran against, or `given` if it failed before resolving one
+fn resolved_domain(outcome: &Result<GovernanceResult, String>, given: &str) -> String {
+    outcome
+        .as_ref()
+        .ok()
+        .and_then(|result| result.domain.clone())
+        .unwrap_or_else(|| given.to_string())
+}
+
+/// Run the pre-verification hooks, the backend and the post-verification hooks
+async fn verify_with_hooks(
+    claim: String,
+    domain: String,
+    risk_profile: String,
+    remote: Option<String>,
+) -> Result<GovernanceResult, String> {
+    let request = serde_json::json!({ "claim": claim, "domain": domain, "risk_profile": risk_profile });
+    let mut annotations = hooks::run(hooks::HookEvent::PreVerification, request.clone()).await?;
+    let mut result = verify_on_backend(claim, domain, risk_profile, remote).await?;
+    let mut payload = request;
+    payload["result"] = serde_json::to_value(&result).unwrap_or_default();
+    annotations.extend(hooks::run(hooks::HookEvent::PostVerification, payload).await?);
+    result.annotations = annotations;
+    Ok(result)
+}
+
+/// Verify with the local engine, or the remote API in remote mode
+async fn verify_on_backend(
+    claim: String,
+    domain: String,
+    risk_profile: String,
+    remote: Option<String>,
 ) -> Result<GovernanceResult, String> {
     // Read settings in a block to ensure lock is released before any await
-    let (api_mode, api_url) = {
+    let (api_mode, backend_mode) = {
         let settings = SETTINGS.read().map_err(|e| format!("Settings lock error: {}", e))?;
-        (settings.api_mode.clone(), settings.api_url.clone())
+        (settings.api_mode.clone(), settings.backend_mode.clone())
     };
+    let actor = identity::current();
 
     // LOCAL-FIRST: Use TruthGit CLI when api_mode is "local"
-    if api_mode == "local" {
-        return governance_verify_local(&claim, &domain, &risk_profile).await;
+    // Mock backend never touches the network, whatever api_mode says
+    if api_mode == "local" || mock::is_active() {
+        let mut result = governance_verify_local(&claim, &domain, &risk_profile).await?;
+        result.actor = actor;
+        result.backend = Some(if mock::is_active() { engine::BACKEND_MOCK.to_string() } else { backend_mode });
+        notify_if_escalated(&claim, &result);
+        return Ok(result);
     }
 
-    // Remote API mode
-    let client = reqwest::Client::new();
+    // Remote API mode: a named remote or URL if given, else the default remote / api_url
+    let api_url = remotes::resolve_remote_url(remote, "pull")?;
+    rate_limit::pace(&api_url).await?;
+    let client = auth::authorized_client(&api_url).await?;
 
     let response = client
         .post(format!("{}/api/governance/verify", api_url))
@@ -331,24 +754,146 @@ async fn governance_verify(
             "claim": claim,
             "domain": domain,
             "risk_profile": risk_profile,
+            "actor": actor,
         }))
         .send()
         .await
         .map_err(|e| format!("Failed to connect to TruthGit API: {}", e))?;
+    rate_limit::record(&api_url, &response);
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(rate_limit::RATE_LIMITED_ERROR.to_string());
+    }
 
     let result: TruthGitResponse = response
         .json()
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    if let Some(data) = result.data {
+    if let Some(mut data) = result.data {
+        data.actor = actor;
+        data.backend = Some("api".to_string());
+        notify_if_escalated(&claim, &data);
         Ok(data)
     } else {
         Err(result.error.unwrap_or_else(|| "Unknown error".to_string()))
     }
 }
 
-// Local governance verification using TruthGit CLI
+fn notify_if_escalated(claim: &str, result: &GovernanceResult) {
+    if result.action == "escalate" {
+        notifications::notify(
+            notifications::NotificationKind::Escalation,
+            "Claim escalated for review",
+            &notifications::excerpt(claim),
+        );
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchClaim {
+    pub claim: String,
+    pub domain: String,
+    /// Note the claim was extracted from, for its folder defaults
+    #[serde(default)]
+    pub note_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchVerifyItem {
+    pub claim: String,
+    pub domain: String,
+    pub result: Option<GovernanceResult>,
+    pub error: Option<String>,
+}
+
+const MAX_BATCH_CLAIMS: usize = 500;
+
+/// Audit actions only the backend writes; `add_audit_entry` refuses them
+const APP_AUDIT_ACTIONS: &[&str] = &[
+    "governance_verify",
+    "review",
+    "approval_request",
+    retention::ARCHIVE_ACTION,
+    retention::RESTORE_ACTION,
+];
+const MAX_AUDIT_CLAIM_LEN: usize = 10_000;
+const MAX_AUDIT_FIELD_LEN: usize = 100;
+
+/// Verify claims one after another. In remote mode requests are paced by the
+/// API's rate-limit headers, and a claim rejected with 429 is retried once
+/// after the quota resets, so a long batch doesn't fail halfway through.
+/// Claims wait in the work queue, where they can be reprioritized, paused or
+/// dropped; results come back in the order the claims ran, without dropped
+/// ones. Cancelling the task stops before the next claim and returns the
+/// items verified so far.
+#[tauri::command]
+async fn governance_verify_batch(
+    app: tauri::AppHandle,
+    claims: Vec<BatchClaim>,
+    risk_profile: String,
+    remote: Option<String>,
+    task_id: Option<String>,
+) -> Result<Vec<BatchVerifyItem>, String> {
+    if claims.len() > MAX_BATCH_CLAIMS {
+        return Err(format!("Too many claims in one batch (max {})", MAX_BATCH_CLAIMS));
+    }
+
+    let started = Instant::now();
+    let outcome = tasks::run(&app, task_id, "batch_verify", "Verifying claims", |task| async move {
+        let total = claims.len() as u64;
+        let mut items = Vec::with_capacity(claims.len());
+        let queued = run_blocking(move || {
+            claims
+                .into_iter()
+                .map(|BatchClaim { claim, domain, note_path }| {
+                    let (domain, risk_profile) =
+                        folder_defaults::apply(note_path.as_deref(), domain, risk_profile.clone())?;
+                    Ok(work_queue::NewItem {
+                        claim,
+                        claim_hash: None,
+                        domain,
+                        risk_profile,
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()
+        })
+        .await?;
+        let _queue = work_queue::enqueue(&task, work_queue::QueueSource::Batch, queued)?;
+        while let Some(item) = work_queue::next(&task).await? {
+            let work_queue::QueueItem { claim, domain, risk_profile, .. } = item;
+            let verify = || run_governance_verify(claim.clone(), domain.clone(), risk_profile.clone(), remote.clone());
+            let mut outcome = verify().await;
+            if matches!(&outcome, Err(e) if e == rate_limit::RATE_LIMITED_ERROR) {
+                outcome = verify().await;
+            }
+            let domain = resolved_domain(&outcome, &domain);
+            let (result, error) = match outcome {
+                Ok(result) => (Some(result), None),
+                Err(e) => (None, Some(e)),
+            };
+            items.push(BatchVerifyItem {
+                claim,
+                domain,
+                result,
+                error,
+            });
+            task.progress(items.len() as u64, total, format!("Verified {} of {} claims", items.len(), total));
+        }
+
+        let failed = items.iter().filter(|i| i.error.is_some()).count();
+        notifications::notify(
+            notifications::NotificationKind::VerificationComplete,
+            "Batch verification finished",
+            &format!("{} verified, {} failed", items.len() - failed, failed),
+        );
+        Ok(items)
+    })
+    .await;
+    metrics::record("batch_verify", started, outcome.is_ok());
+    outcome
+}
+
+// Local governance verification using the TruthGit engine (CLI or embedded)
 async fn governance_verify_local(claim: &str, domain: &str, risk_profile: &str) -> Result<GovernanceResult, String> {
     let args = vec![
         "safe-verify".to_string(),
@@ -360,10 +905,11 @@ async fn governance_verify_local(claim: &str, domain: &str, risk_profile: &str)
         "--json".to_string(),
     ];
 
-    let output = execute_with_timeout("truthgit", &args, None).await
+    repo_state::ready_path()?;
+    let output = engine::run_truthgit(&args).await
         .map_err(|e| format!("{}. Is TruthGit installed?", e))?;
 
-    if output.status.success() {
+    if output.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
 
         // Parse JSON output from TruthGit CLI
@@ -393,6 +939,16 @@ async fn governance_verify_local(claim: &str, domain: &str, risk_profile: &str)
             ontological_type: parsed.get("ontological_type")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
+            actor: None,
+            claim_hash: parsed.get("claim_hash")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            verification_hash: parsed.get("verification_hash")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            backend: None,
+            domain: None,
+            annotations: hooks::Annotations::new(),
         })
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -400,68 +956,68 @@ async fn governance_verify_local(claim: &str, domain: &str, risk_profile: &str)
     }
 }
 
+/// Claims newest first, optionally of one ontological type; `stale` while
+/// the startup index is still being built
 #[tauri::command]
-async fn list_claims() -> Result<Vec<serde_json::Value>, String> {
-    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
-    let claims_dir = truth_path.join("objects/cl");
-
-    if !claims_dir.exists() {
-        return Ok(vec![]);
-    }
-
-    let mut claims = Vec::new();
+async fn list_claims(ontological_type: Option<String>) -> Result<index::ClaimListing, String> {
+    run_blocking(move || read_claims(ontological_type.as_deref())).await
+}
 
-    for entry in WalkDir::new(&claims_dir)
-        .min_depth(2)
-        .max_depth(2)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            match decompress_object(&entry.path().to_path_buf()) {
-                Ok(claim) => claims.push(claim),
-                Err(e) => log::warn!("Failed to read claim {}: {}", entry.path().display(), e),
-            }
-        }
+fn read_claims(ontological_type: Option<&str>) -> Result<index::ClaimListing, String> {
+    if mock::is_active() {
+        let wanted = ontological_type.map(ontology::normalize);
+        let claims = mock::claims()
+            .into_iter()
+            .filter(|c| wanted.is_none() || ontology::claim_type(c) == wanted)
+            .collect();
+        return Ok(index::ClaimListing {
+            claims,
+            stale: false,
+            indexed_at: None,
+            warnings: Vec::new(),
+        });
     }
 
-    // Sort by created_at descending (newest first)
-    claims.sort_by(|a, b| {
-        let a_time = a.get("metadata")
-            .and_then(|m| m.get("created_at"))
-            .and_then(|t| t.as_str())
-            .unwrap_or("");
-        let b_time = b.get("metadata")
-            .and_then(|m| m.get("created_at"))
-            .and_then(|t| t.as_str())
-            .unwrap_or("");
-        b_time.cmp(a_time)
-    });
-
-    Ok(claims)
+    let truth_path = repo_state::ready_path()?;
+    Ok(index::list_claims(&truth_path, ontological_type))
 }
 
 #[tauri::command]
 async fn get_claim(hash: String) -> Result<serde_json::Value, String> {
-    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+    run_blocking(move || read_claim(hash)).await
+}
 
-    if hash.len() < 3 {
-        return Err("Invalid hash".to_string());
+fn read_claim(hash: String) -> Result<serde_json::Value, String> {
+    if mock::is_active() {
+        return mock::claim(&hash);
     }
 
-    let prefix = &hash[..2];
-    let suffix = &hash[2..];
-    let claim_path = truth_path.join("objects/cl").join(prefix).join(suffix);
-
-    if !claim_path.exists() {
+    let store = TruthStore::active()?;
+    let id = ObjectId::parse(&hash).map_err(|_| "Invalid hash".to_string())?;
+    if !store.contains(ObjectKind::Claim, &id) {
         return Err(format!("Claim not found: {}", hash));
     }
 
-    decompress_object(&claim_path)
+    Ok(store.read_json(ObjectKind::Claim, &id)?)
 }
 
+/// Number of claim objects in a truth repo
+fn count_claims(truth_path: &Path) -> usize {
+    TruthStore::new(truth_path).count(ObjectKind::Claim)
+}
+
+/// Repository status. The claim count comes from the index unless `refresh`
+/// forces a rescan of objects/.
 #[tauri::command]
-async fn get_truth_status() -> Result<TruthRepoStatus, String> {
+async fn get_truth_status(refresh: Option<bool>) -> Result<TruthRepoStatus, String> {
+    run_blocking(move || read_truth_status(refresh.unwrap_or(false))).await
+}
+
+fn read_truth_status(refresh: bool) -> Result<TruthRepoStatus, String> {
+    if mock::is_active() {
+        return Ok(mock::truth_status());
+    }
+
     let truth_path = get_truth_path().ok_or("Could not find home directory")?;
 
     if !truth_path.exists() {
@@ -474,23 +1030,9 @@ async fn get_truth_status() -> Result<TruthRepoStatus, String> {
         });
     }
 
-    // Count claims
-    let claims_dir = truth_path.join("objects/cl");
-    let claims_count = if claims_dir.exists() {
-        WalkDir::new(&claims_dir)
-            .min_depth(2)
-            .max_depth(2)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .count()
-    } else {
-        0
-    };
+    let claims_count = index::claims_count(&truth_path, refresh);
 
-    // Read HEAD
-    let head_path = truth_path.join("HEAD");
-    let head_ref = fs::read_to_string(&head_path).ok();
+    let head_ref = TruthStore::new(&truth_path).head();
 
     // Check for keys
     let has_keys = truth_path.join("proof.key").exists()
@@ -544,10 +1086,10 @@ fn validate_truthgit_args(args: &[String]) -> Result<(), String> {
     // First arg must be an allowed subcommand
     let subcommand = &args[0];
     if !ALLOWED_TRUTHGIT_SUBCOMMANDS.contains(&subcommand.as_str()) {
-        return Err(format!(
-            "🚫 BLOCKED: TruthGit subcommand '{}' is not allowed. \
-            Allowed: {:?}",
-            subcommand, ALLOWED_TRUTHGIT_SUBCOMMANDS
+        security_events::record(SecurityEventKind::BlockedCommand, "run_truthgit_command", subcommand);
+        return Err(i18n::tr(
+            "blocked.truthgit_subcommand",
+            &[("subcommand", subcommand), ("allowed", &format!("{:?}", ALLOWED_TRUTHGIT_SUBCOMMANDS))],
         ));
     }
 
@@ -555,11 +1097,8 @@ fn validate_truthgit_args(args: &[String]) -> Result<(), String> {
     for arg in args {
         for pattern in BLOCKED_ARG_PATTERNS {
             if arg.contains(pattern) {
-                return Err(format!(
-                    "🚫 BLOCKED: Argument contains forbidden pattern '{}'. \
-                    Shell injection attempt detected.",
-                    pattern
-                ));
+                security_events::record(SecurityEventKind::InjectionAttempt, "run_truthgit_command", arg);
+                return Err(i18n::tr("blocked.argument_pattern", &[("pattern", pattern)]));
             }
         }
     }
@@ -573,9 +1112,9 @@ async fn run_truthgit_command(args: Vec<String>) -> Result<String, String> {
     validate_truthgit_args(&args)?;
     // ====== END SECURITY CHECK ======
 
-    let output = execute_with_timeout("truthgit", &args, None).await?;
+    let output = engine::run_truthgit(&args).await?;
 
-    if output.status.success() {
+    if output.success() {
         String::from_utf8(output.stdout)
             .map_err(|e| format!("Invalid UTF-8 output: {}", e))
     } else {
@@ -594,9 +1133,10 @@ async fn verify_claim_local(claim: String, domain: String) -> Result<String, Str
         "--json".to_string(),
     ];
 
-    let output = execute_with_timeout("truthgit", &args, None).await?;
+    repo_state::ready_path()?;
+    let output = engine::run_truthgit(&args).await?;
 
-    if output.status.success() {
+    if output.success() {
         String::from_utf8(output.stdout)
             .map_err(|e| format!("Invalid UTF-8 output: {}", e))
     } else {
@@ -605,46 +1145,52 @@ async fn verify_claim_local(claim: String, domain: String) -> Result<String, Str
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct VerificationListing {
+    /// Newest first
+    pub verifications: Vec<Verification>,
+    /// Verification objects that couldn't be read
+    pub warnings: Vec<objects::ObjectWarning>,
+}
+
 #[tauri::command]
-async fn list_verifications() -> Result<Vec<serde_json::Value>, String> {
-    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
-    let verifications_dir = truth_path.join("objects/vf");
+async fn list_verifications() -> Result<VerificationListing, String> {
+    run_blocking(read_verifications).await
+}
 
-    if !verifications_dir.exists() {
-        return Ok(vec![]);
+fn read_verifications() -> Result<VerificationListing, String> {
+    if mock::is_active() {
+        return Ok(VerificationListing {
+            verifications: mock::verifications().into_iter().map(Verification::from).collect(),
+            warnings: Vec::new(),
+        });
     }
 
-    let mut verifications = Vec::new();
+    let store = TruthStore::active()?;
 
-    for entry in WalkDir::new(&verifications_dir)
-        .min_depth(2)
-        .max_depth(2)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            match decompress_object(&entry.path().to_path_buf()) {
-                Ok(vf) => verifications.push(vf),
-                Err(e) => log::warn!("Failed to read verification {}: {}", entry.path().display(), e),
+    let mut verifications = Vec::new();
+    let mut warnings = Vec::new();
+
+    for entry in store.iter(ObjectKind::Verification) {
+        match objects::read_expected(&entry.path, &entry.object_name(), objects::ObjectKind::Verification) {
+            Ok(vf) => verifications.push(Verification::from(vf)),
+            Err(warning) => {
+                log::warn!("Skipping verification object {}: {}", warning.object, warning.message);
+                warnings.push(warning);
             }
         }
     }
 
     // Sort by timestamp descending
-    verifications.sort_by(|a, b| {
-        let a_time = a.get("timestamp")
-            .and_then(|t| t.as_str())
-            .unwrap_or("");
-        let b_time = b.get("timestamp")
-            .and_then(|t| t.as_str())
-            .unwrap_or("");
-        b_time.cmp(a_time)
-    });
+    verifications.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
 
-    Ok(verifications)
+    Ok(VerificationListing { verifications, warnings })
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Serializes read-modify-write cycles on audit.json
+static AUDIT_LOCK: json_store::StoreLock = json_store::StoreLock::new();
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct AuditEntry {
     pub id: String,
     pub timestamp: String,
@@ -655,35 +1201,184 @@ pub struct AuditEntry {
     pub result_status: String,
     pub result_action: String,
     pub confidence: f64,
+    /// None for entries written before identities were recorded
+    #[serde(default)]
+    pub actor: Option<identity::Actor>,
+    /// Where the action happened when it wasn't this app, e.g. "web" or "ci";
+    /// set on entries imported from a remote's history
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Claim object the entry is about
+    #[serde(default)]
+    pub claim_hash: Option<String>,
+    /// Verification object written for this entry
+    #[serde(default)]
+    pub verification_hash: Option<String>,
+    /// Engine that produced the verdict: "cli", "embedded", "mock" or "api"
+    #[serde(default)]
+    pub backend: Option<String>,
+    /// Version of the app that wrote the entry
+    #[serde(default)]
+    pub app_version: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+}
+
+/// Audit entry for a verification run from this app
+fn verification_audit_entry(
+    claim: &str,
+    domain: &str,
+    risk_profile: &str,
+    result: &GovernanceResult,
+    duration: Duration,
+) -> AuditEntry {
+    AuditEntry {
+        id: Some(result.audit_ref.clone())
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        action: "governance_verify".to_string(),
+        claim: claim.to_string(),
+        domain: domain.to_string(),
+        risk_profile: risk_profile.to_string(),
+        result_status: result.status.clone(),
+        result_action: result.action.clone(),
+        confidence: result.confidence,
+        actor: result.actor.clone(),
+        source: None,
+        claim_hash: result.claim_hash.clone(),
+        verification_hash: result.verification_hash.clone(),
+        backend: result.backend.clone(),
+        app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        duration_ms: Some(duration.as_millis() as u64),
+    }
+}
+
+/// Write the audit entry for a verification run from the app, if
+/// `auto_save_audit` is on. Failures are logged: the verdict is still returned.
+async fn record_verification_audit(
+    claim: &str,
+    domain: &str,
+    risk_profile: &str,
+    outcome: &Result<GovernanceResult, String>,
+    started: Instant,
+) {
+    let Ok(result) = outcome else {
+        return;
+    };
+    let auto_save = SETTINGS.read().map(|s| s.auto_save_audit).unwrap_or(true);
+    if !auto_save || mock::is_active() {
+        return;
+    }
+    let entry = verification_audit_entry(claim, domain, risk_profile, result, started.elapsed());
+    if let Err(e) = run_blocking(move || append_audit_entry(entry)).await {
+        log::warn!("Failed to write audit entry: {}", e);
+    }
 }
 
 #[tauri::command]
 async fn get_audit_trail() -> Result<Vec<AuditEntry>, String> {
-    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+    run_blocking(read_audit_trail).await
+}
+
+fn read_audit_trail() -> Result<Vec<AuditEntry>, String> {
+    let truth_path = repo_state::ready_path()?;
     let audit_file = truth_path.join("audit.json");
 
     if !audit_file.exists() {
         return Ok(vec![]);
     }
 
-    let content = fs::read_to_string(&audit_file)
+    let content = fs::read(&audit_file)
         .map_err(|e| format!("Failed to read audit file: {}", e))?;
+    let content = repo_crypto::open(&truth_path, repo_crypto::AUDIT, &content)?;
 
-    let entries: Vec<AuditEntry> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse audit file: {}", e))?;
+    let entries: Vec<AuditEntry> = serde_json::from_slice(&content).map_err(|e| {
+        let detail = format!("audit.json doesn't parse: {}", e);
+        repo_state::report_corrupt(&detail);
+        format!("Failed to parse audit file: {}", e)
+    })?;
 
     Ok(entries)
 }
 
+/// Check an entry sent by the frontend and keep only what it may set: the
+/// action, claim and verdict fields. ID, time, attribution and object links
+/// are filled in here, and actions the backend records itself are refused,
+/// so a webview script can neither forge verifications nor rewrite entries
+/// that receipts and evidence point to.
+fn validate_manual_audit_entry(entry: AuditEntry) -> Result<AuditEntry, String> {
+    let action = entry.action.trim();
+    if action.is_empty()
+        || action.len() > MAX_AUDIT_FIELD_LEN
+        || !action.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err("Invalid audit action".to_string());
+    }
+    if APP_AUDIT_ACTIONS.contains(&action) {
+        return Err(format!("'{}' audit entries are written by the app itself", action));
+    }
+    if entry.claim.trim().is_empty() || entry.claim.chars().count() > MAX_AUDIT_CLAIM_LEN {
+        return Err(format!("Audit claim must be 1 to {} characters", MAX_AUDIT_CLAIM_LEN));
+    }
+    for (name, value) in [
+        ("domain", &entry.domain),
+        ("result_status", &entry.result_status),
+        ("result_action", &entry.result_action),
+    ] {
+        if value.chars().count() > MAX_AUDIT_FIELD_LEN {
+            return Err(format!("Audit {} too long (max {} characters)", name, MAX_AUDIT_FIELD_LEN));
+        }
+    }
+    if !entry.risk_profile.is_empty() && !settings_validation::RISK_PROFILES.contains(&entry.risk_profile.as_str()) {
+        return Err(format!("Invalid risk profile '{}'", entry.risk_profile));
+    }
+    if !(0.0..=1.0).contains(&entry.confidence) {
+        return Err("Audit confidence must be between 0 and 1".to_string());
+    }
+    Ok(AuditEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        action: action.to_string(),
+        claim: entry.claim.trim().to_string(),
+        actor: identity::current(),
+        app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        domain: entry.domain,
+        risk_profile: entry.risk_profile,
+        result_status: entry.result_status,
+        result_action: entry.result_action,
+        confidence: entry.confidence,
+        ..Default::default()
+    })
+}
+
+/// Record an entry for something the backend doesn't log on its own;
+/// verifications and reviews write their entries themselves
 #[tauri::command]
-async fn add_audit_entry(entry: AuditEntry) -> Result<(), String> {
-    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+async fn add_audit_entry(entry: AuditEntry) -> Result<String, String> {
+    if mock::is_active() {
+        return Err("Audit writes are disabled in mock mode".to_string());
+    }
+    let entry = validate_manual_audit_entry(entry)?;
+    let id = entry.id.clone();
+    run_blocking(move || append_audit_entry(entry)).await?;
+    Ok(id)
+}
+
+fn append_audit_entry(entry: AuditEntry) -> Result<(), String> {
+    let truth_path = repo_state::ready_path()?;
     let audit_file = truth_path.join("audit.json");
+    let _guard = AUDIT_LOCK.lock(&audit_file)?;
 
     let mut entries: Vec<AuditEntry> = if audit_file.exists() {
-        let content = fs::read_to_string(&audit_file)
+        let content = fs::read(&audit_file)
             .map_err(|e| format!("Failed to read audit file: {}", e))?;
-        serde_json::from_str(&content).unwrap_or_default()
+        // Never write over a trail that doesn't parse; it is all the history there is
+        serde_json::from_slice(&repo_crypto::open(&truth_path, repo_crypto::AUDIT, &content)?).map_err(|e| {
+            let detail = format!("audit.json doesn't parse: {}", e);
+            repo_state::report_corrupt(&detail);
+            format!("Failed to parse audit file: {}", e)
+        })?
     } else {
         vec![]
     };
@@ -693,12 +1388,38 @@ async fn add_audit_entry(entry: AuditEntry) -> Result<(), String> {
     let content = serde_json::to_string_pretty(&entries)
         .map_err(|e| format!("Failed to serialize audit: {}", e))?;
 
-    fs::write(&audit_file, content)
+    let content = repo_crypto::seal(&truth_path, repo_crypto::AUDIT, content.into_bytes())?;
+    json_store::write_atomic(&audit_file, &content)
         .map_err(|e| format!("Failed to write audit file: {}", e))?;
 
     Ok(())
 }
 
+/// Merge entries recorded elsewhere into the audit trail, skipping IDs it
+/// already holds and keeping it newest first. Returns how many were added.
+fn import_audit_entries(imported: Vec<AuditEntry>) -> Result<usize, String> {
+    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+    let audit_file = truth_path.join("audit.json");
+    let _guard = AUDIT_LOCK.lock(&audit_file)?;
+
+    let mut entries = read_audit_trail()?;
+    let mut known: HashSet<String> = entries.iter().map(|e| e.id.clone()).collect();
+    let before = entries.len();
+    entries.extend(imported.into_iter().filter(|e| known.insert(e.id.clone())));
+    let added = entries.len() - before;
+    if added == 0 {
+        return Ok(0);
+    }
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+    let content = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Failed to serialize audit: {}", e))?;
+    let content = repo_crypto::seal(&truth_path, repo_crypto::AUDIT, content.into_bytes())?;
+    json_store::write_atomic(&audit_file, &content)
+        .map_err(|e| format!("Failed to write audit file: {}", e))?;
+    Ok(added)
+}
+
 // ==================== KNOWLEDGE BASE COMMANDS ====================
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -715,6 +1436,9 @@ pub struct VaultNote {
     pub name: String,
     pub content: String,
     pub modified: Option<String>,
+    /// Decrypted from an age file
+    #[serde(default)]
+    pub encrypted: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -727,6 +1451,10 @@ pub struct SearchResult {
 
 #[tauri::command]
 async fn get_vault_status() -> Result<serde_json::Value, String> {
+    run_blocking(read_vault_status).await
+}
+
+fn read_vault_status() -> Result<serde_json::Value, String> {
     let vault_path = get_vault_path().ok_or("Could not find home directory")?;
 
     if !vault_path.exists() {
@@ -736,15 +1464,29 @@ async fn get_vault_status() -> Result<serde_json::Value, String> {
             "file_count": 0,
             "folder_count": 0,
             "truncated": false,
+            "stale": false,
         }));
     }
 
+    let (counts, stale) = index::vault_counts(&vault_path);
+    Ok(serde_json::json!({
+        "exists": true,
+        "path": vault_path.to_string_lossy().to_string(),
+        "file_count": counts.file_count,
+        "folder_count": counts.folder_count,
+        "truncated": counts.truncated,
+        "stale": stale,
+    }))
+}
+
+/// Files and folders in the vault, up to `MAX_VAULT_FILES` entries in total
+fn count_vault_entries(vault_path: &Path) -> (usize, usize, bool) {
     let mut file_count = 0;
     let mut folder_count = 0;
     let mut truncated = false;
 
     // SECURITY: Limit traversal to prevent DoS on large vaults
-    for entry in WalkDir::new(&vault_path)
+    for entry in WalkDir::new(vault_path)
         .min_depth(1)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -763,29 +1505,33 @@ async fn get_vault_status() -> Result<serde_json::Value, String> {
         }
     }
 
-    Ok(serde_json::json!({
-        "exists": true,
-        "path": vault_path.to_string_lossy().to_string(),
-        "file_count": file_count,
-        "folder_count": folder_count,
-        "truncated": truncated,
-    }))
+    (file_count, folder_count, truncated)
 }
 
 #[tauri::command]
 async fn list_vault_directory(relative_path: Option<String>) -> Result<Vec<VaultFile>, String> {
+    run_blocking(move || read_vault_directory(relative_path)).await
+}
+
+fn read_vault_directory(relative_path: Option<String>) -> Result<Vec<VaultFile>, String> {
     let vault_path = get_vault_path().ok_or("Could not find home directory")?;
+    list_vault_entries(&vault_path, relative_path.as_deref())
+}
+
+fn list_vault_entries(vault_path: &Path, relative_path: Option<&str>) -> Result<Vec<VaultFile>, String> {
+    // Validated paths are canonical, so strip entries against the canonical vault
+    let vault_path = fs::canonicalize(vault_path).unwrap_or_else(|_| vault_path.to_path_buf());
 
     // ====== SECURITY: Validate path to prevent directory traversal ======
     let target_path = match relative_path {
-        Some(ref p) if !p.is_empty() => {
+        Some(p) if !p.is_empty() => {
             validate_path_within_base(&vault_path, p)?
         },
         _ => vault_path.clone(),
     };
 
     if !target_path.exists() {
-        return Err(format!("Directory not found: {}", target_path.display()));
+        return Err(format!("Directory not found: {}", paths::display_path(&target_path)));
     }
 
     let mut files = Vec::new();
@@ -809,10 +1555,7 @@ async fn list_vault_directory(relative_path: Option<String>) -> Result<Vec<Vault
             path.extension().map(|e| e.to_string_lossy().to_string())
         };
 
-        let relative = path
-            .strip_prefix(&vault_path)
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|_| name.clone());
+        let relative = paths::relative_to(&vault_path, &path).unwrap_or_else(|| name.clone());
 
         files.push(VaultFile {
             name,
@@ -836,18 +1579,24 @@ async fn list_vault_directory(relative_path: Option<String>) -> Result<Vec<Vault
 
 #[tauri::command]
 async fn read_note(relative_path: String) -> Result<VaultNote, String> {
+    run_blocking(move || read_note_file(relative_path)).await
+}
+
+fn read_note_file(relative_path: String) -> Result<VaultNote, String> {
     let vault_path = get_vault_path().ok_or("Could not find home directory")?;
 
     // ====== SECURITY: Validate path to prevent directory traversal ======
     let note_path = validate_path_within_base(&vault_path, &relative_path)?;
 
-    let content = fs::read_to_string(&note_path)
-        .map_err(|e| format!("Failed to read note: {}", e))?;
+    let encrypted = vault_crypto::is_encrypted(&note_path);
+    let content = if encrypted {
+        vault_crypto::read_note(&note_path)?
+    } else {
+        fs::read_to_string(&note_path)
+            .map_err(|e| format!("Failed to read note: {}", e))?
+    };
 
-    let name = note_path
-        .file_stem()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| relative_path.clone());
+    let name = note_name(&note_path).unwrap_or_else(|| relative_path.clone());
 
     let modified = fs::metadata(&note_path)
         .ok()
@@ -862,13 +1611,80 @@ async fn read_note(relative_path: String) -> Result<VaultNote, String> {
         name,
         content,
         modified,
+        encrypted,
     })
 }
 
+/// File stem, looking through an age extension: "Plan.md" and "Plan.md.age"
+/// are both "Plan"
+fn note_name(path: &Path) -> Option<String> {
+    let path = match path.extension() {
+        Some(ext) if ext == "age" => path.with_extension(""),
+        _ => path.to_path_buf(),
+    };
+    path.file_stem().map(|s| s.to_string_lossy().to_string())
+}
+
+/// Lines of `content` containing `query_lower`, truncated, with their
+/// 1-based line numbers
+fn matching_lines(content: &str, query_lower: &str) -> (Vec<String>, Vec<usize>) {
+    let mut matches = Vec::new();
+    let mut line_numbers = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        if line.to_lowercase().contains(query_lower) {
+            // SECURITY: Limit matches per file
+            if matches.len() >= MAX_MATCHES_PER_FILE {
+                break;
+            }
+
+            // Truncate long lines
+            let truncated = if line.len() > 100 {
+                format!("{}...", &line[..100])
+            } else {
+                line.to_string()
+            };
+            matches.push(truncated);
+            line_numbers.push(i + 1);
+        }
+    }
+    (matches, line_numbers)
+}
+
 #[tauri::command]
 async fn search_notes(query: String) -> Result<Vec<SearchResult>, String> {
+    let started = Instant::now();
+    let results = run_blocking(move || search_vault(query)).await;
+    metrics::record("note_search", started, results.is_ok());
+    results
+}
+
+fn search_vault(query: String) -> Result<Vec<SearchResult>, String> {
     let vault_path = get_vault_path().ok_or("Could not find home directory")?;
+    let mut results = search_vault_files(&vault_path, &query)?;
+    let query_lower = query.to_lowercase();
+
+    // Encrypted notes, searched through their own index while unlocked
+    for (relative, content) in vault_crypto::searchable_notes(&vault_path)? {
+        let (matches, line_numbers) = matching_lines(&content, &query_lower);
+        if !matches.is_empty() && results.len() < MAX_SEARCH_RESULTS {
+            results.push(SearchResult {
+                name: note_name(Path::new(&relative)).unwrap_or_default(),
+                path: relative,
+                matches,
+                line_numbers,
+            });
+        }
+    }
+
+    // Sort by number of matches (descending)
+    results.sort_by(|a, b| b.matches.len().cmp(&a.matches.len()));
+
+    Ok(results)
+}
 
+/// Plain markdown notes under `vault_path` containing `query`
+fn search_vault_files(vault_path: &Path, query: &str) -> Result<Vec<SearchResult>, String> {
     if !vault_path.exists() {
         return Ok(vec![]);
     }
@@ -883,7 +1699,7 @@ async fn search_notes(query: String) -> Result<Vec<SearchResult>, String> {
     let mut files_searched = 0;
 
     // SECURITY: Limit file traversal
-    for entry in WalkDir::new(&vault_path)
+    for entry in WalkDir::new(vault_path)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
@@ -912,32 +1728,10 @@ async fn search_notes(query: String) -> Result<Vec<SearchResult>, String> {
         }
 
         if let Ok(content) = fs::read_to_string(path) {
-            let mut matches = Vec::new();
-            let mut line_numbers = Vec::new();
-
-            for (i, line) in content.lines().enumerate() {
-                if line.to_lowercase().contains(&query_lower) {
-                    // SECURITY: Limit matches per file
-                    if matches.len() >= MAX_MATCHES_PER_FILE {
-                        break;
-                    }
-
-                    // Truncate long lines
-                    let truncated = if line.len() > 100 {
-                        format!("{}...", &line[..100])
-                    } else {
-                        line.to_string()
-                    };
-                    matches.push(truncated);
-                    line_numbers.push(i + 1);
-                }
-            }
+            let (matches, line_numbers) = matching_lines(&content, &query_lower);
 
             if !matches.is_empty() {
-                let relative = path
-                    .strip_prefix(&vault_path)
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_default();
+                let relative = paths::relative_to(vault_path, path).unwrap_or_default();
 
                 let name = path
                     .file_stem()
@@ -954,9 +1748,6 @@ async fn search_notes(query: String) -> Result<Vec<SearchResult>, String> {
         }
     }
 
-    // Sort by number of matches (descending)
-    results.sort_by(|a, b| b.matches.len().cmp(&a.matches.len()));
-
     Ok(results)
 }
 
@@ -1084,10 +1875,7 @@ async fn check_command_safety(command: String) -> Result<CommandCheck, String> {
     if let Some(pattern) = contains_dangerous_pattern(&command) {
         return Ok(CommandCheck {
             is_dangerous: true,
-            warning: Some(format!(
-                "🚫 BLOCKED: Command contains dangerous pattern '{}'",
-                pattern
-            )),
+            warning: Some(i18n::tr("blocked.dangerous_pattern", &[("pattern", pattern)])),
         });
     }
 
@@ -1095,9 +1883,9 @@ async fn check_command_safety(command: String) -> Result<CommandCheck, String> {
     if !is_command_allowed(&command) {
         return Ok(CommandCheck {
             is_dangerous: true,
-            warning: Some(format!(
-                "⚠️ Command '{}' is not in the allowed list. Only safe commands are permitted.",
-                command.split_whitespace().next().unwrap_or(&command)
+            warning: Some(i18n::tr(
+                "warning.command_not_allowed",
+                &[("command", command.split_whitespace().next().unwrap_or(&command))],
             )),
         });
     }
@@ -1154,18 +1942,16 @@ async fn execute_shell(command: String, cwd: Option<String>) -> Result<ShellOutp
     // ====== SECURITY: Server-side enforcement ======
     // Check for dangerous patterns FIRST
     if let Some(pattern) = contains_dangerous_pattern(&command) {
-        return Err(format!(
-            "🚫 BLOCKED: Command contains dangerous pattern '{}'. Execution denied.",
-            pattern
-        ));
+        security_events::record(SecurityEventKind::InjectionAttempt, "execute_shell", &command);
+        return Err(i18n::tr("blocked.dangerous_pattern", &[("pattern", pattern)]));
     }
 
     // Check if command is in whitelist
     if !is_command_allowed(&command) {
-        return Err(format!(
-            "🚫 BLOCKED: Command '{}' is not in the allowed list. \
-            Only truthgit and safe read-only commands are permitted.",
-            command.split_whitespace().next().unwrap_or(&command)
+        security_events::record(SecurityEventKind::BlockedCommand, "execute_shell", &command);
+        return Err(i18n::tr(
+            "blocked.command_not_allowed",
+            &[("command", command.split_whitespace().next().unwrap_or(&command))],
         ));
     }
     // ====== END SECURITY CHECK ======
@@ -1173,6 +1959,7 @@ async fn execute_shell(command: String, cwd: Option<String>) -> Result<ShellOutp
     // ====== SECURITY: Direct execution without shell ======
     // Parse command into program and args (no shell interpolation)
     let (program, args) = parse_command(&command)?;
+    consent::require_shell(&program).await?;
 
     // Use configurable working directory from settings
     let working_dir = cwd.unwrap_or_else(|| {
@@ -1237,14 +2024,171 @@ async fn get_shell_suggestions(prefix: String) -> Result<Vec<String>, String> {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .plugin(single_instance::plugin())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
         .invoke_handler(tauri::generate_handler![
             // Settings
             get_settings,
             update_settings,
+            update_settings_partial,
+            settings_validation::validate_settings,
+            engine::get_engine_info,
+            profiles::list_profiles,
+            profiles::save_profile,
+            profiles::delete_profile,
+            profiles::switch_profile,
+            identity::get_identity,
+            domains::list_domains,
+            domains::save_domain,
+            domains::delete_domain,
+            domains::get_domain_stats,
+            ontology::list_ontological_types,
+            ontology::get_ontological_type_stats,
+            reviews::set_claim_review_state,
+            reviews::get_claim_review,
+            reviews::list_claims_by_review_state,
+            approvals::request_approval,
+            approvals::approve,
+            approvals::list_pending_approvals,
+            watchlist::pin_claim,
+            watchlist::unpin_claim,
+            watchlist::list_watchlist,
+            watchlist::recheck_watchlist,
+            feeds::add_feed_source,
+            feeds::remove_feed_source,
+            feeds::list_feed_sources,
+            feeds::check_feed_sources,
+            feeds::get_inbox,
+            feeds::dismiss_inbox_item,
+            receipts::generate_receipt,
+            receipts::verify_receipt,
+            attestations::export_attestation,
+            reports::generate_report,
+            exports::export_verifications,
+            settings_bundle::export_settings,
+            settings_bundle::import_settings,
+            setup::get_first_run_state,
+            setup::complete_first_run,
+            setup::detect_obsidian_vaults,
+            setup::detect_truth_repos,
+            setup::initialize_truth_repo,
+            repo_init::prepare_truth_repo_init,
+            repo_init::init_truth_repo,
+            repos::list_truth_repos,
+            repos::add_truth_repo,
+            repos::remove_truth_repo,
+            repos::switch_truth_repo,
+            sync::push_claims,
+            sync::pull_claims,
+            sync::get_sync_log,
+            sync_conflicts::get_sync_conflicts,
+            sync_conflicts::resolve_sync_conflict,
+            remotes::list_remotes,
+            remotes::add_remote,
+            remotes::remove_remote,
+            remote_browse::browse_remote_claims,
+            remote_browse::fetch_remote_claim,
+            remote_history::import_remote_history,
+            live_events::get_live_events_status,
+            auth::start_device_login,
+            auth::get_auth_status,
+            auth::logout,
+            rate_limit::get_api_quota,
+            local_api::get_local_api_info,
+            local_api::rotate_local_api_token,
+            deep_links::take_pending_deep_links,
+            file_intake::intake_files,
+            shortcuts::get_shortcut_status,
+            shortcuts::check_shortcut_conflicts,
+            session::save_session,
+            session::restore_session,
+            backup::backup_truth_repo,
+            backup::restore_truth_repo,
+            repo_import::import_truth_repo_archive,
+            share_bundles::create_share_bundle,
+            share_bundles::import_share_bundle,
+            diagnostics::run_diagnostics,
+            logging::get_recent_logs,
+            logging::export_logs,
+            tasks::cancel_task,
+            tasks::list_tasks,
             // Governance
             governance_verify,
+            governance_verify_batch,
+            recent_verifications::list_recent_verifications,
+            recent_verifications::rerun_verification,
+            recent_verifications::clear_recent_verifications,
+            claim_templates::list_claim_templates,
+            claim_templates::save_claim_template,
+            claim_templates::delete_claim_template,
+            claim_templates::instantiate_claim_template,
+            evidence::attach_evidence,
+            evidence::list_evidence,
+            evidence::list_claim_evidence,
+            sources::add_claim_source,
+            sources::list_claim_sources,
+            sources::unsourced_claims_report,
+            web_archive::archive_url,
+            web_archive::get_archived_url,
+            claim_diff::diff_claims,
+            evidence::remove_evidence,
+            sources::remove_claim_source,
+            journal::list_changes,
+            journal::undo_last_change,
+            global_search::global_search,
+            domain_usage::get_frequent_domains,
+            domain_suggest::suggest_domain,
+            i18n::get_message_catalog,
+            metrics::get_usage_metrics,
+            metrics::export_usage_metrics,
+            metrics::clear_usage_metrics,
+            hooks::list_hooks,
+            plugins::list_plugins,
+            plugins::set_plugin_enabled,
+            plugins::run_plugin,
+            plugins::get_plugin_annotations,
+            work_queue::get_task_queue,
+            work_queue::reprioritize_queue_item,
+            work_queue::set_queue_item_paused,
+            work_queue::drop_queue_item,
+            crash_reports::list_crash_reports,
+            crash_reports::export_crash_report,
+            updates::check_for_updates,
+            updates::download_update,
+            updates::install_update,
+            updates::get_update_status,
+            environment::get_environment_info,
+            timeline::get_claim_timeline,
+            snapshots::compare_repo_snapshots,
+            digest::generate_digest,
+            process_metrics::get_process_metrics,
+            activity_heatmap::get_activity_heatmap,
+            repo_crypto::get_encryption_status,
+            repo_crypto::enable_repo_encryption,
+            repo_crypto::encrypt_repo_objects,
+            repo_crypto::unlock_repo,
+            repo_crypto::lock_repo,
+            repo_crypto::change_repo_passphrase,
+            vault_crypto::get_encrypted_notes_status,
+            vault_crypto::unlock_encrypted_notes,
+            vault_crypto::lock_encrypted_notes,
+            vault_crypto::save_encrypted_note,
+            consent::list_granted_permissions,
+            consent::revoke_permission,
+            security_events::get_security_events,
+            clipboard_verify::verify_clipboard,
             list_claims,
+            index::stream_claims,
+            claim_filters::query_claims,
+            claim_filters::save_filter,
+            claim_filters::list_saved_filters,
+            claim_filters::delete_filter,
+            claim_schema::validate_claim,
             get_claim,
             get_truth_status,
             run_truthgit_command,
@@ -1253,24 +2197,63 @@ pub fn run() {
             // Audit
             get_audit_trail,
             add_audit_entry,
+            retention::apply_retention,
+            retention::list_archives,
+            retention::restore_from_archive,
+            compaction::compact_repo,
+            compaction::get_last_compaction,
+            repo_state::get_repo_state,
             // Knowledge Base
             get_vault_status,
             list_vault_directory,
             read_note,
             search_notes,
+            note_links::resolve_note,
+            coverage::get_coverage_report,
+            folder_defaults::get_folder_defaults,
+            folder_defaults::set_folder_defaults,
+            note_annotations::annotate_note_with_result,
+            notes_export::export_annotated_notes,
+            quick_capture::quick_capture,
             // Terminal
             check_command_safety,
             execute_shell,
             get_shell_suggestions,
+            // Registry
+            command_registry::list_app_commands,
         ])
-        .setup(|app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                file_intake::handle_drop(window.app_handle(), paths.clone());
+            }
+            tauri::WindowEvent::CloseRequested { api, .. } if window.label() == "main" && tray::hide_on_close() => {
+                api.prevent_close();
+                let _ = window.hide();
             }
+            _ => {}
+        })
+        .setup(|app| {
+            app.handle().plugin(logging::plugin())?;
+            crash_reports::install(app.handle());
+            identity::init();
+            index::start(app.handle());
+            settings_events::spawn_settings_watcher(app.handle().clone());
+            live_events::start(app.handle());
+            local_api::start(app.handle());
+            deep_links::init(app.handle())?;
+            tray::init(app.handle())?;
+            notifications::init(app.handle());
+            consent::init(app.handle());
+            plugins::init(app.handle());
+            app.handle().plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
+            shortcuts::init(app.handle());
+            watchlist::start(app.handle());
+            feeds::start(app.handle());
+            digest::start();
+            retention::start();
+            compaction::start(app.handle());
+            repo_state::init(app.handle());
+            updates::start(app.handle());
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -1282,6 +2265,7 @@ pub fn run() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_dirs::TempDir;
 
     // ====== is_command_allowed tests ======
 
@@ -1583,6 +2567,30 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_list_and_search_long_nested_paths() {
+        let tmp = TempDir::new("truthgit-long-paths");
+        let vault = tmp.path();
+        // Well past MAX_PATH (260) once joined
+        let segments: Vec<String> = (0..12).map(|i| format!("{:02}-{}", i, "nested-folder".repeat(2))).collect();
+        let relative = segments.join("/");
+        let deep = paths::long_path(paths::join(vault, &relative));
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("Deep note.md"), "# Deep\nThe long path holds.\n").unwrap();
+        assert!(deep.join("Deep note.md").to_string_lossy().len() > 300);
+
+        let listed = list_vault_entries(vault, Some(&relative)).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].path, format!("{}/Deep note.md", relative));
+        let parent = segments[..11].join("/");
+        assert_eq!(list_vault_entries(vault, Some(&parent)).unwrap()[0].path, relative);
+
+        let results = search_vault_files(vault, "long path").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, format!("{}/Deep note.md", relative));
+        assert_eq!(results[0].line_numbers, vec![2]);
+    }
+
     // ====== Security limits tests ======
 
     #[test]
@@ -1644,18 +2652,60 @@ mod tests {
         assert!(settings.api_url.contains("localhost"));
     }
 
+    #[test]
+    fn test_settings_patch_merges_only_given_keys() {
+        let current = AppSettings::default();
+        let patch = serde_json::json!({ "terminal_font_size": 18, "default_risk_profile": "high" });
+        let merged = merge_settings_patch(&current, patch).unwrap();
+        assert_eq!(merged.terminal_font_size, 18);
+        assert_eq!(merged.default_risk_profile, "high");
+        assert_eq!(merged.vault_path, current.vault_path);
+        assert_eq!(merged.api_url, current.api_url);
+    }
+
+    #[test]
+    fn test_settings_patch_rejects_unknown_key() {
+        let patch = serde_json::json!({ "vault_pth": "/typo" });
+        let result = merge_settings_patch(&AppSettings::default(), patch);
+        assert!(result.unwrap_err().contains("vault_pth"));
+    }
+
+    #[test]
+    fn test_settings_patch_rejects_wrong_type() {
+        let patch = serde_json::json!({ "terminal_font_size": "large" });
+        assert!(merge_settings_patch(&AppSettings::default(), patch).is_err());
+        assert!(merge_settings_patch(&AppSettings::default(), serde_json::json!([1])).is_err());
+    }
+
+    #[test]
+    fn test_settings_without_backend_mode_still_load() {
+        // Settings files written before backend_mode existed must not be discarded
+        let json = r#"{
+            "vault_path": "/tmp/vault",
+            "truth_repo_path": "/tmp/.truth",
+            "api_mode": "local",
+            "api_url": "http://localhost:8000",
+            "default_risk_profile": "medium",
+            "terminal_font_size": 14,
+            "auto_save_audit": true
+        }"#;
+        let settings: AppSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.backend_mode, engine::default_backend_mode());
+    }
+
     // ====== Integration tests for security limits ======
 
     #[test]
     fn test_decompression_limit_enforced() {
+        use crate::truth_store::decompress_object;
         use flate2::write::ZlibEncoder;
         use flate2::Compression;
         use std::io::Write;
 
         // Create a compressed file that would decompress to more than MAX_DECOMPRESSED_SIZE
         // We'll create a file with repeated data that compresses well
-        let temp_dir = std::env::temp_dir();
-        let test_file = temp_dir.join("test_bomb.zlib");
+        let tmp = TempDir::new("truthgit-bomb");
+        let test_file = tmp.path().join("test_bomb.zlib");
 
         // Create compressed data
         let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
@@ -1674,21 +2724,21 @@ mod tests {
         let result = decompress_object(&test_file);
         assert!(result.is_err());
         let err = result.unwrap_err();
+        assert_eq!(err, ObjectError::TooLarge { limit: MAX_DECOMPRESSED_SIZE });
+        let err = err.to_string();
         assert!(err.contains("exceeds size limit") || err.contains("decompression bomb"),
             "Error should mention size limit, got: {}", err);
-
-        // Cleanup
-        let _ = std::fs::remove_file(&test_file);
     }
 
     #[test]
     fn test_decompression_valid_small_object() {
+        use crate::truth_store::decompress_object;
         use flate2::write::ZlibEncoder;
         use flate2::Compression;
         use std::io::Write;
 
-        let temp_dir = std::env::temp_dir();
-        let test_file = temp_dir.join("test_valid.zlib");
+        let tmp = TempDir::new("truthgit-valid-object");
+        let test_file = tmp.path().join("test_valid.zlib");
 
         // Create a valid small compressed JSON object
         let json_data = r#"{"test": "value", "number": 42}"#;
@@ -1705,9 +2755,6 @@ mod tests {
         let value = result.unwrap();
         assert_eq!(value.get("test").and_then(|v| v.as_str()), Some("value"));
         assert_eq!(value.get("number").and_then(|v| v.as_i64()), Some(42));
-
-        // Cleanup
-        let _ = std::fs::remove_file(&test_file);
     }
 
     #[test]
@@ -1727,4 +2774,62 @@ mod tests {
         assert_eq!(MAX_DECOMPRESSED_SIZE, 10 * 1024 * 1024);
         assert_eq!(SUBPROCESS_TIMEOUT_SECS, 30);
     }
+
+    #[test]
+    fn test_audit_entry_details() {
+        // Entries written before the details existed still load
+        let old: AuditEntry = serde_json::from_str(
+            r#"{"id": "a1", "timestamp": "2026-01-01T00:00:00Z", "action": "governance_verify",
+                "claim": "c", "domain": "science", "risk_profile": "low", "result_status": "PASSED",
+                "result_action": "proceed", "confidence": 0.9}"#,
+        )
+        .unwrap();
+        assert!(old.claim_hash.is_none() && old.backend.is_none() && old.duration_ms.is_none());
+
+        let result = GovernanceResult {
+            status: "PASSED".to_string(),
+            audit_ref: "ref-1".to_string(),
+            claim_hash: Some("abc123".to_string()),
+            backend: Some(engine::BACKEND_CLI.to_string()),
+            ..Default::default()
+        };
+        let entry = verification_audit_entry("c", "science", "low", &result, Duration::from_millis(1500));
+        assert_eq!(entry.id, "ref-1");
+        assert_eq!(entry.claim_hash.as_deref(), Some("abc123"));
+        assert_eq!(entry.backend.as_deref(), Some("cli"));
+        assert_eq!(entry.app_version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+        assert_eq!(entry.duration_ms, Some(1500));
+
+        let resolved = GovernanceResult {
+            domain: Some("healthcare".to_string()),
+            ..result
+        };
+        assert_eq!(resolved_domain(&Ok(resolved), "Health Care"), "healthcare");
+        assert_eq!(resolved_domain(&Err("offline".to_string()), "Health Care"), "Health Care");
+    }
+
+    #[test]
+    fn test_manual_audit_entry_validation() {
+        let entry = |action: &str, confidence: f64| AuditEntry {
+            id: "forged".to_string(),
+            timestamp: "2000-01-01T00:00:00Z".to_string(),
+            action: action.to_string(),
+            claim: " Water is wet ".to_string(),
+            risk_profile: "low".to_string(),
+            confidence,
+            source: Some("web".to_string()),
+            verification_hash: Some("abc".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_manual_audit_entry(entry("governance_verify", 0.5)).is_err());
+        assert!(validate_manual_audit_entry(entry("note; rm", 0.5)).is_err());
+        assert!(validate_manual_audit_entry(entry("note", 1.5)).is_err());
+        assert!(validate_manual_audit_entry(entry("note", f64::NAN)).is_err());
+
+        let checked = validate_manual_audit_entry(entry("note", 0.5)).unwrap();
+        assert_ne!(checked.id, "forged");
+        assert_ne!(checked.timestamp, "2000-01-01T00:00:00Z");
+        assert_eq!(checked.claim, "Water is wet");
+        assert!(checked.source.is_none() && checked.verification_hash.is_none());
+    }
 }
@@ -0,0 +1,305 @@
+// ==================== LIVE REMOTE EVENTS ====================
+//
+// In remote mode the app subscribes to the API's Server-Sent Events stream
+// and re-emits claim and verification events as Tauri events, so teammates
+// see each other's verifications appear without refreshing. SSE goes through
+// reqwest, so it shares proxy/TLS handling with every other API call.
+//
+// Remote endpoint:
+//   GET {remote}/api/events  (text/event-stream; `event:` is "claim" or "verification")
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Listener};
+
+use crate::auth::authorized_client;
+use crate::notifications::{self, NotificationKind};
+use crate::remotes::resolve_remote_url;
+use crate::settings_events::{SettingsChanged, SETTINGS_CHANGED_EVENT};
+use crate::{mock, SETTINGS};
+
+pub const REMOTE_CLAIM_EVENT: &str = "remote://claim";
+pub const REMOTE_VERIFICATION_EVENT: &str = "remote://verification";
+pub const REMOTE_OTHER_EVENT: &str = "remote://event";
+pub const REMOTE_STATUS_EVENT: &str = "remote://status";
+
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Guard against a misbehaving server streaming one endless event
+const MAX_EVENT_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiveEventsStatus {
+    /// "disabled", "connecting", "connected" or "reconnecting"
+    pub state: String,
+    pub remote: Option<String>,
+    pub last_event_at: Option<String>,
+    pub error: Option<String>,
+}
+
+struct Subscription {
+    handle: tauri::async_runtime::JoinHandle<()>,
+    /// (api_mode, api_url) the subscription was started for
+    key: (String, String),
+}
+
+static SUBSCRIPTION: Mutex<Option<Subscription>> = Mutex::new(None);
+
+static STATUS: std::sync::LazyLock<RwLock<LiveEventsStatus>> = std::sync::LazyLock::new(|| {
+    RwLock::new(LiveEventsStatus {
+        state: "disabled".to_string(),
+        ..Default::default()
+    })
+});
+
+#[derive(Debug, Clone, PartialEq)]
+struct SseEvent {
+    event: String,
+    data: String,
+}
+
+/// Incremental text/event-stream parser. Buffers bytes, not text, so a
+/// multi-byte character split across chunks is decoded intact.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Result<Vec<SseEvent>, String> {
+        self.buffer.extend_from_slice(chunk);
+        if self.buffer.len() > MAX_EVENT_SIZE {
+            return Err("Event stream line too long".to_string());
+        }
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: self.event.take().unwrap_or_else(|| "message".to_string()),
+                        data: self.data.join("\n"),
+                    });
+                }
+                self.event = None;
+                self.data.clear();
+                continue;
+            }
+            if line.starts_with(':') {
+                continue; // comment / keep-alive
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn set_status(app: &AppHandle, update: impl FnOnce(&mut LiveEventsStatus)) {
+    let status = {
+        let Ok(mut status) = STATUS.write() else {
+            return;
+        };
+        update(&mut status);
+        status.clone()
+    };
+    if let Err(e) = app.emit(REMOTE_STATUS_EVENT, status) {
+        log::warn!("Failed to emit {}: {}", REMOTE_STATUS_EVENT, e);
+    }
+}
+
+fn dispatch(app: &AppHandle, event: SseEvent) {
+    let payload: serde_json::Value = match serde_json::from_str(&event.data) {
+        Ok(payload) => payload,
+        Err(e) => {
+            log::warn!("Ignoring malformed remote event: {}", e);
+            return;
+        }
+    };
+    let name = match event.event.as_str() {
+        "claim" => REMOTE_CLAIM_EVENT,
+        "verification" => REMOTE_VERIFICATION_EVENT,
+        _ => REMOTE_OTHER_EVENT,
+    };
+    if name == REMOTE_VERIFICATION_EVENT {
+        let claim = payload.get("claim").and_then(|c| c.as_str()).unwrap_or("A claim was verified");
+        notifications::notify(
+            NotificationKind::VerificationComplete,
+            "New verification on the remote",
+            &notifications::excerpt(claim),
+        );
+    }
+    if let Err(e) = app.emit(name, payload) {
+        log::warn!("Failed to emit {}: {}", name, e);
+    }
+    set_status(app, |s| s.last_event_at = Some(chrono::Utc::now().to_rfc3339()));
+}
+
+/// Stream events until the connection drops
+async fn stream_events(app: &AppHandle, client: &reqwest::Client, remote: &str) -> Result<(), String> {
+    let mut response = client
+        .get(format!("{}/api/events", remote))
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {} for events", response.status()));
+    }
+
+    set_status(app, |s| {
+        s.state = "connected".to_string();
+        s.error = None;
+    });
+
+    let mut parser = SseParser::default();
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Stream error: {}", e))? {
+        for event in parser.feed(&chunk)? {
+            dispatch(app, event);
+        }
+    }
+    Err("Server closed the event stream".to_string())
+}
+
+async fn run_subscription(app: AppHandle, remote: String) {
+    let mut delay = MIN_RECONNECT_DELAY;
+    loop {
+        let connected_at = std::time::Instant::now();
+        // Rebuilt on every attempt so a refreshed token is picked up
+        let result = match authorized_client(&remote).await {
+            Ok(client) => stream_events(&app, &client, &remote).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::warn!("Live events disconnected: {}", e);
+            set_status(&app, |s| {
+                s.state = "reconnecting".to_string();
+                s.error = Some(e);
+            });
+        }
+        // A connection that stayed up for a while resets the backoff
+        if connected_at.elapsed() > MAX_RECONNECT_DELAY {
+            delay = MIN_RECONNECT_DELAY;
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+/// Start, stop or restart the subscription to match the current settings
+fn apply(app: &AppHandle, api_mode: &str, api_url: &str) {
+    let Ok(mut subscription) = SUBSCRIPTION.lock() else {
+        return;
+    };
+    let key = (api_mode.to_string(), api_url.to_string());
+    if subscription.as_ref().map(|s| &s.key) == Some(&key) {
+        return;
+    }
+    if let Some(previous) = subscription.take() {
+        previous.handle.abort();
+    }
+
+    if api_mode != "remote" || mock::is_active() {
+        set_status(app, |s| *s = LiveEventsStatus { state: "disabled".to_string(), ..Default::default() });
+        return;
+    }
+    let remote = match resolve_remote_url(None, "pull") {
+        Ok(remote) => remote,
+        Err(e) => {
+            set_status(app, |s| {
+                s.state = "disabled".to_string();
+                s.error = Some(e);
+            });
+            return;
+        }
+    };
+
+    set_status(app, |s| {
+        *s = LiveEventsStatus {
+            state: "connecting".to_string(),
+            remote: Some(remote.clone()),
+            ..Default::default()
+        }
+    });
+    let handle = tauri::async_runtime::spawn(run_subscription(app.clone(), remote));
+    *subscription = Some(Subscription { handle, key });
+}
+
+/// Subscribe according to current settings and follow later settings changes
+pub fn start(app: &AppHandle) {
+    if let Ok(settings) = SETTINGS.read().map(|s| s.clone()) {
+        apply(app, &settings.api_mode, &settings.api_url);
+    }
+
+    let handle = app.clone();
+    app.listen(SETTINGS_CHANGED_EVENT, move |event| {
+        if let Ok(changed) = serde_json::from_str::<SettingsChanged>(event.payload()) {
+            apply(&handle, &changed.settings.api_mode, &changed.settings.api_url);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_live_events_status() -> Result<LiveEventsStatus, String> {
+    Ok(STATUS.read().map_err(|e| format!("Lock error: {}", e))?.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser_handles_split_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b"event: verifi").unwrap().is_empty());
+        assert!(parser.feed(b"cation\ndata: {\"a\":").unwrap().is_empty());
+        let events = parser.feed(b"1}\n\n").unwrap();
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: "verification".to_string(),
+                data: "{\"a\":1}".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_sse_parser_multiline_data_and_comments() {
+        let mut parser = SseParser::default();
+        let events = parser.feed(b": keep-alive\r\ndata: line1\r\ndata: line2\r\n\r\n").unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[0].data, "line1\nline2");
+    }
+
+    #[test]
+    fn test_sse_parser_keeps_split_utf8_intact() {
+        let mut parser = SseParser::default();
+        let bytes = "data: 100°C\n\n".as_bytes();
+        let split = bytes.iter().position(|&b| b == 0xC2).unwrap() + 1;
+        assert!(parser.feed(&bytes[..split]).unwrap().is_empty());
+        let events = parser.feed(&bytes[split..]).unwrap();
+        assert_eq!(events[0].data, "100°C");
+    }
+
+    #[test]
+    fn test_sse_parser_rejects_oversized_lines() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed("x".repeat(MAX_EVENT_SIZE + 1).as_bytes()).is_err());
+    }
+}
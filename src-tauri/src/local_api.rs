@@ -0,0 +1,313 @@
+// ==================== LOCAL API SERVER ====================
+//
+// Optional HTTP server so external tools (an Obsidian plugin, scripts, CI
+// jobs) can drive the same local-first engine as the desktop UI. Off by
+// default; when enabled it binds to 127.0.0.1 only and every request except
+// /api/health needs `Authorization: Bearer <token>`. The token lives in
+// ~/.config/truthgit/local-api-token (mode 0600) so scripts can read it.
+//
+// Endpoints (responses are { success, data, error }):
+//   GET  /api/health
+//   GET  /api/claims
+//   GET  /api/claims/{hash}
+//   POST /api/verify   { claim, domain, risk_profile? }
+//   GET  /api/audit
+
+use axum::extract::{Path as UrlPath, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::{AppHandle, Listener};
+
+use crate::json_store::config_path;
+use crate::security_events::{self, SecurityEventKind};
+use crate::settings_events::{SettingsChanged, SETTINGS_CHANGED_EVENT};
+use crate::SETTINGS;
+
+pub const DEFAULT_LOCAL_API_PORT: u16 = 47821;
+
+pub fn default_local_api_port() -> u16 {
+    DEFAULT_LOCAL_API_PORT
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalApiInfo {
+    pub enabled: bool,
+    pub running: bool,
+    pub url: Option<String>,
+    /// Included so the Settings UI can show it for copying into other tools
+    pub token: Option<String>,
+    pub error: Option<String>,
+}
+
+struct Server {
+    handle: tauri::async_runtime::JoinHandle<()>,
+    port: u16,
+}
+
+static SERVER: Mutex<Option<Server>> = Mutex::new(None);
+
+static LAST_ERROR: RwLock<Option<String>> = RwLock::new(None);
+
+#[derive(Serialize)]
+struct ApiBody<T: Serialize> {
+    success: bool,
+    data: Option<T>,
+    error: Option<String>,
+}
+
+fn respond<T: Serialize>(result: Result<T, String>) -> Response {
+    match result {
+        Ok(data) => Json(ApiBody {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+        .into_response(),
+        Err(error) => failure(StatusCode::BAD_REQUEST, error),
+    }
+}
+
+fn failure(status: StatusCode, error: String) -> Response {
+    let body: ApiBody<()> = ApiBody {
+        success: false,
+        data: None,
+        error: Some(error),
+    };
+    (status, Json(body)).into_response()
+}
+
+// ---------- token ----------
+
+fn token_path() -> PathBuf {
+    config_path("local-api-token")
+}
+
+fn write_token(token: &str) -> Result<(), String> {
+    let path = token_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .map_err(|e| format!("Failed to write local API token: {}", e))?;
+    std::io::Write::write_all(&mut file, token.as_bytes()).map_err(|e| format!("Failed to write local API token: {}", e))
+}
+
+fn load_or_create_token() -> Result<String, String> {
+    match fs::read_to_string(token_path()) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => {
+            let token = uuid::Uuid::new_v4().simple().to_string();
+            write_token(&token)?;
+            Ok(token)
+        }
+    }
+}
+
+/// Constant-time comparison so the token can't be guessed byte by byte
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|given| tokens_match(given.trim(), token))
+        .unwrap_or(false)
+}
+
+async fn require_token(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    if is_authorized(request.headers(), &token) {
+        next.run(request).await
+    } else {
+        security_events::record(SecurityEventKind::AuthFailure, "local_api", request.uri().path());
+        failure(StatusCode::UNAUTHORIZED, "Missing or invalid token".to_string())
+    }
+}
+
+// ---------- handlers ----------
+
+#[derive(Debug, Deserialize)]
+struct VerifyRequest {
+    claim: String,
+    domain: String,
+    risk_profile: Option<String>,
+}
+
+async fn health() -> Response {
+    respond(Ok(serde_json::json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") })))
+}
+
+async fn claims() -> Response {
+    respond(crate::list_claims(None).await.map(|listing| listing.claims))
+}
+
+async fn claim(UrlPath(hash): UrlPath<String>) -> Response {
+    respond(crate::get_claim(hash).await)
+}
+
+async fn verify(Json(request): Json<VerifyRequest>) -> Response {
+    let risk_profile = match request.risk_profile {
+        Some(risk_profile) => risk_profile,
+        None => match SETTINGS.read() {
+            Ok(settings) => settings.default_risk_profile.clone(),
+            Err(e) => return failure(StatusCode::INTERNAL_SERVER_ERROR, format!("Lock error: {}", e)),
+        },
+    };
+    respond(crate::run_governance_verify(request.claim, request.domain, risk_profile, None).await)
+}
+
+async fn audit() -> Response {
+    respond(crate::get_audit_trail().await)
+}
+
+fn router(token: String) -> Router {
+    let protected = Router::new()
+        .route("/api/claims", get(claims))
+        .route("/api/claims/{hash}", get(claim))
+        .route("/api/verify", post(verify))
+        .route("/api/audit", get(audit))
+        .route_layer(middleware::from_fn_with_state(Arc::new(token), require_token));
+    Router::new().route("/api/health", get(health)).merge(protected)
+}
+
+// ---------- lifecycle ----------
+
+fn set_error(error: Option<String>) {
+    if let Ok(mut last) = LAST_ERROR.write() {
+        *last = error;
+    }
+}
+
+async fn serve(port: u16, token: String) {
+    // SECURITY: loopback only; this API is for tools on this machine
+    let listener = match tokio::net::TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::warn!("Local API failed to bind port {}: {}", port, e);
+            set_error(Some(format!("Failed to bind 127.0.0.1:{}: {}", port, e)));
+            return;
+        }
+    };
+    set_error(None);
+    log::info!("Local API listening on http://127.0.0.1:{}", port);
+    if let Err(e) = axum::serve(listener, router(token)).await {
+        log::warn!("Local API stopped: {}", e);
+        set_error(Some(format!("Server stopped: {}", e)));
+    }
+}
+
+/// Start, stop or restart the server to match the settings
+fn apply(enabled: bool, port: u16) {
+    let Ok(mut server) = SERVER.lock() else {
+        return;
+    };
+    if enabled && server.as_ref().map(|s| s.port) == Some(port) {
+        return;
+    }
+    if let Some(previous) = server.take() {
+        previous.handle.abort();
+    }
+    if !enabled {
+        set_error(None);
+        return;
+    }
+
+    let token = match load_or_create_token() {
+        Ok(token) => token,
+        Err(e) => {
+            set_error(Some(e));
+            return;
+        }
+    };
+    let handle = tauri::async_runtime::spawn(serve(port, token));
+    *server = Some(Server { handle, port });
+}
+
+/// Apply the current settings and follow later changes
+pub fn start(app: &AppHandle) {
+    if let Ok(settings) = SETTINGS.read().map(|s| s.clone()) {
+        apply(settings.local_api_enabled, settings.local_api_port);
+    }
+    app.listen(SETTINGS_CHANGED_EVENT, |event| {
+        if let Ok(changed) = serde_json::from_str::<SettingsChanged>(event.payload()) {
+            apply(changed.settings.local_api_enabled, changed.settings.local_api_port);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_local_api_info() -> Result<LocalApiInfo, String> {
+    let (enabled, port) = {
+        let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+        (settings.local_api_enabled, settings.local_api_port)
+    };
+    let running = SERVER
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .as_ref()
+        .map(|s| s.port == port)
+        .unwrap_or(false);
+    let error = LAST_ERROR.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+
+    Ok(LocalApiInfo {
+        enabled,
+        running: running && error.is_none(),
+        url: enabled.then(|| format!("http://127.0.0.1:{}", port)),
+        token: if enabled { Some(load_or_create_token()?) } else { None },
+        error,
+    })
+}
+
+/// Issue a new token; tools holding the old one stop working immediately
+#[tauri::command]
+pub async fn rotate_local_api_token() -> Result<LocalApiInfo, String> {
+    write_token(&uuid::Uuid::new_v4().simple().to_string())?;
+    let (enabled, port) = {
+        let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+        (settings.local_api_enabled, settings.local_api_port)
+    };
+    // Restart so the running server picks up the new token
+    apply(false, port);
+    apply(enabled, port);
+    get_local_api_info().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc124", "abc123"));
+        assert!(!tokens_match("abc", "abc123"));
+        assert!(!tokens_match("", "abc123"));
+    }
+
+    #[test]
+    fn test_bearer_header_required() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_authorized(&headers, "secret"));
+        headers.insert(header::AUTHORIZATION, "Basic secret".parse().unwrap());
+        assert!(!is_authorized(&headers, "secret"));
+        headers.insert(header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+        assert!(is_authorized(&headers, "secret"));
+    }
+}
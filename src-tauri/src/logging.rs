@@ -0,0 +1,259 @@
+// ==================== LOGGING ====================
+//
+// Always-on logging to a rotating file in the OS log directory
+// (~/.local/share/<identifier>/logs on Linux), one JSON object per line so
+// the app can read its own logs back. Messages are sanitized like error
+// strings before they are written, so an exported bundle doesn't carry home
+// directory paths. Debug builds also log to stdout.
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+use crate::tasks::{self, TaskHandle};
+use crate::{diagnostics, sanitize_error};
+
+const LOG_FILE_NAME: &str = "truthgit";
+const MAX_LOG_FILE_SIZE: u128 = 5 * 1024 * 1024;
+/// Rotated files kept besides the current one
+const KEEP_LOG_FILES: usize = 5;
+const DEFAULT_LOG_LIMIT: usize = 200;
+const MAX_LOG_LIMIT: usize = 5000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogRecord {
+    pub ts: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogExport {
+    pub path: String,
+    pub files: usize,
+}
+
+fn format_record(record: &log::Record, message: &std::fmt::Arguments) -> String {
+    let line = LogRecord {
+        ts: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        level: record.level().to_string(),
+        target: record.target().to_string(),
+        message: sanitize_error(&message.to_string()),
+    };
+    serde_json::to_string(&line).unwrap_or_default()
+}
+
+pub fn plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    let file = Target::new(TargetKind::LogDir {
+        file_name: Some(LOG_FILE_NAME.to_string()),
+    })
+    .format(|out, message, record| out.finish(format_args!("{}", format_record(record, message))));
+
+    let mut builder = tauri_plugin_log::Builder::new()
+        .clear_targets()
+        .target(file)
+        .level(log::LevelFilter::Info)
+        // HTTP internals are noise at info level
+        .level_for("hyper", log::LevelFilter::Warn)
+        .level_for("reqwest", log::LevelFilter::Warn)
+        .max_file_size(MAX_LOG_FILE_SIZE)
+        .rotation_strategy(RotationStrategy::KeepSome(KEEP_LOG_FILES));
+    if cfg!(debug_assertions) {
+        builder = builder.target(Target::new(TargetKind::Stdout));
+    }
+    builder.build()
+}
+
+pub(crate) fn log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_log_dir()
+        .map_err(|e| format!("Could not find log directory: {}", e))
+}
+
+/// Current and rotated log files, newest first
+pub(crate) fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    let name = p
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    name.starts_with(LOG_FILE_NAME) && name.ends_with(".log")
+                })
+                .map(|p| {
+                    (
+                        fs::metadata(&p)
+                            .and_then(|m| m.modified())
+                            .unwrap_or(std::time::UNIX_EPOCH),
+                        p,
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_key(|f| std::cmp::Reverse(f.0));
+    files.into_iter().map(|(_, p)| p).collect()
+}
+
+fn parse_level(level: Option<&str>) -> Result<log::Level, String> {
+    match level {
+        None => Ok(log::Level::Trace),
+        Some(level) => level.parse().map_err(|_| format!("Unknown log level '{}'", level)),
+    }
+}
+
+/// Newest records at or above `min_level`, newest first
+pub(crate) fn recent_records(files: &[PathBuf], min_level: log::Level, limit: usize) -> Vec<LogRecord> {
+    let mut records = Vec::new();
+    for file in files {
+        let Ok(content) = fs::read_to_string(file) else {
+            continue;
+        };
+        for line in content.lines().rev() {
+            let Ok(record) = serde_json::from_str::<LogRecord>(line) else {
+                continue;
+            };
+            if record.level.parse::<log::Level>().is_ok_and(|l| l <= min_level) {
+                records.push(record);
+                if records.len() >= limit {
+                    return records;
+                }
+            }
+        }
+    }
+    records
+}
+
+fn write_bundle(dest: &Path, files: &[PathBuf], diagnostics: &[u8], task: &TaskHandle) -> std::io::Result<()> {
+    let out = BufWriter::new(File::create(dest)?);
+    let mut builder = tar::Builder::new(GzEncoder::new(out, Compression::default()));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(diagnostics.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, "diagnostics.json", diagnostics)?;
+    for (i, file) in files.iter().enumerate() {
+        if task.is_cancelled() {
+            return Err(std::io::Error::other(tasks::TASK_CANCELLED_ERROR));
+        }
+        if let Some(name) = file.file_name() {
+            builder.append_path_with_name(file, Path::new("logs").join(name))?;
+        }
+        task.progress(i as u64 + 1, files.len() as u64, format!("Added {} of {} log files", i + 1, files.len()));
+    }
+    builder
+        .into_inner()?
+        .finish()?
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()
+}
+
+/// Log records, newest first. `level` is the minimum severity ("error",
+/// "warn", "info", "debug"); all levels when omitted.
+#[tauri::command]
+pub async fn get_recent_logs(
+    app: AppHandle,
+    level: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<LogRecord>, String> {
+    let min_level = parse_level(level.as_deref())?;
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT).clamp(1, MAX_LOG_LIMIT);
+    let files = log_files(&log_dir(&app)?);
+    tokio::task::spawn_blocking(move || recent_records(&files, min_level, limit))
+        .await
+        .map_err(|e| format!("Log task failed: {}", e))
+}
+
+/// Logs plus a diagnostics report in one .tar.gz, for attaching to support requests
+#[tauri::command]
+pub async fn export_logs(app: AppHandle, dest: String, task_id: Option<String>) -> Result<LogExport, String> {
+    let dest = PathBuf::from(dest.trim());
+    if !dest.is_absolute() {
+        return Err("Export destination must be an absolute path".to_string());
+    }
+    let dest = if dest.is_dir() {
+        dest.join(format!(
+            "truthgit-logs-{}.tar.gz",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    } else {
+        dest
+    };
+    let files = log_files(&log_dir(&app)?);
+    let count = files.len();
+    let path = dest.clone();
+    tasks::run(&app, task_id, "export_logs", "Exporting logs", |task| async move {
+        task.progress(0, 0, "Running diagnostics");
+        let report = diagnostics::run_diagnostics().await?;
+        let report =
+            serde_json::to_vec_pretty(&report).map_err(|e| format!("Failed to serialize diagnostics: {}", e))?;
+        task.check_cancelled()?;
+        tokio::task::spawn_blocking(move || {
+            write_bundle(&path, &files, &report, &task).map_err(|e| {
+                let _ = fs::remove_file(&path);
+                if task.is_cancelled() {
+                    tasks::TASK_CANCELLED_ERROR.to_string()
+                } else {
+                    sanitize_error(&format!("Failed to write log bundle: {}", e))
+                }
+            })
+        })
+        .await
+        .map_err(|e| format!("Export task failed: {}", e))?
+    })
+    .await?;
+    log::info!("Exported {} log files", count);
+    Ok(LogExport {
+        path: dest.to_string_lossy().to_string(),
+        files: count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_recent_records_filters_and_limits() {
+        let tmp = TempDir::new("truthgit-logs");
+        let dir = tmp.path();
+        fs::create_dir_all(dir).unwrap();
+        let line = |level: &str, message: &str| {
+            serde_json::to_string(&LogRecord {
+                ts: String::new(),
+                level: level.to_string(),
+                target: "app_lib".to_string(),
+                message: message.to_string(),
+            })
+            .unwrap()
+        };
+        let content = [
+            line("INFO", "first"),
+            "not json".to_string(),
+            line("ERROR", "broke"),
+            line("WARN", "last"),
+        ];
+        fs::write(dir.join("truthgit.log"), content.join("\n")).unwrap();
+
+        let files = log_files(dir);
+        assert_eq!(files.len(), 1);
+        let warnings = recent_records(&files, parse_level(Some("warn")).unwrap(), 10);
+        let messages: Vec<&str> = warnings.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["last", "broke"]);
+        assert_eq!(recent_records(&files, log::Level::Trace, 1).len(), 1);
+        assert!(parse_level(Some("loud")).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+// ==================== USAGE METRICS ====================
+//
+// Opt-in counts of which features are used and how long they take, kept in
+// usage_metrics.json in the config directory. Nothing here is ever sent
+// anywhere: `get_usage_metrics` feeds the personal activity view and
+// `export_usage_metrics` writes a copy only when the user asks for one.
+// Recording is a no-op unless `usage_metrics` is enabled in settings.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{run_blocking, sanitize_error, SETTINGS};
+
+/// Days of daily activity kept
+const MAX_DAYS: usize = 365;
+/// Days of daily activity returned by `get_usage_metrics`
+const ACTIVITY_DAYS: usize = 30;
+
+/// Serializes read-modify-write cycles on usage_metrics.json
+static METRICS_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureUsage {
+    pub count: u64,
+    pub errors: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+    pub last_used: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageMetrics {
+    /// When counting started (first recorded use since the last clear)
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub features: BTreeMap<String, FeatureUsage>,
+    /// Uses per day (YYYY-MM-DD)
+    #[serde(default)]
+    pub days: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeatureStats {
+    pub feature: String,
+    pub count: u64,
+    pub errors: u64,
+    pub average_ms: u64,
+    pub max_ms: u64,
+    pub last_used: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayActivity {
+    pub date: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageReport {
+    pub enabled: bool,
+    pub since: Option<String>,
+    pub total: u64,
+    /// Most used first
+    pub features: Vec<FeatureStats>,
+    /// Last 30 days with any activity, oldest first
+    pub days: Vec<DayActivity>,
+}
+
+fn metrics_path() -> PathBuf {
+    config_path("usage_metrics.json")
+}
+
+fn enabled() -> bool {
+    SETTINGS.read().map(|s| s.usage_metrics).unwrap_or(false)
+}
+
+fn load_metrics() -> Result<UsageMetrics, String> {
+    let _guard = METRICS_LOCK.lock(&metrics_path())?;
+    load_json(&metrics_path())
+}
+
+fn add_sample(
+    metrics: &mut UsageMetrics,
+    feature: &str,
+    elapsed_ms: u64,
+    ok: bool,
+    now: chrono::DateTime<chrono::Utc>,
+) {
+    let timestamp = now.to_rfc3339();
+    metrics.since.get_or_insert_with(|| timestamp.clone());
+    let usage = metrics.features.entry(feature.to_string()).or_default();
+    usage.count += 1;
+    usage.errors += u64::from(!ok);
+    usage.total_ms = usage.total_ms.saturating_add(elapsed_ms);
+    usage.max_ms = usage.max_ms.max(elapsed_ms);
+    usage.last_used = timestamp;
+
+    *metrics.days.entry(now.format("%Y-%m-%d").to_string()).or_default() += 1;
+    while metrics.days.len() > MAX_DAYS {
+        metrics.days.pop_first();
+    }
+}
+
+fn report(metrics: UsageMetrics, enabled: bool) -> UsageReport {
+    let mut features: Vec<FeatureStats> = metrics
+        .features
+        .into_iter()
+        .map(|(feature, usage)| FeatureStats {
+            average_ms: usage.total_ms / usage.count.max(1),
+            count: usage.count,
+            errors: usage.errors,
+            max_ms: usage.max_ms,
+            last_used: usage.last_used,
+            feature,
+        })
+        .collect();
+    features.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.feature.cmp(&b.feature)));
+    let skip = metrics.days.len().saturating_sub(ACTIVITY_DAYS);
+    UsageReport {
+        enabled,
+        since: metrics.since,
+        total: features.iter().map(|f| f.count).sum(),
+        features,
+        days: metrics
+            .days
+            .into_iter()
+            .skip(skip)
+            .map(|(date, count)| DayActivity { date, count })
+            .collect(),
+    }
+}
+
+/// Count one use of `feature` that started at `started`. Does nothing unless
+/// metrics are enabled; the write happens in the background and failures
+/// are logged.
+pub fn record(feature: &'static str, started: Instant, ok: bool) {
+    if !enabled() {
+        return;
+    }
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = (|| {
+            let _guard = METRICS_LOCK.lock(&metrics_path())?;
+            let mut metrics: UsageMetrics = load_json(&metrics_path())?;
+            add_sample(&mut metrics, feature, elapsed_ms, ok, chrono::Utc::now());
+            save_json(&metrics_path(), &metrics)
+        })();
+        if let Err(e) = result {
+            log::warn!("Failed to record usage metrics: {}", e);
+        }
+    });
+}
+
+/// Feature usage and latencies recorded on this machine
+#[tauri::command]
+pub async fn get_usage_metrics() -> Result<UsageReport, String> {
+    run_blocking(|| Ok(report(load_metrics()?, enabled()))).await
+}
+
+/// Write the recorded metrics to `path` as JSON
+#[tauri::command]
+pub async fn export_usage_metrics(path: String) -> Result<(), String> {
+    run_blocking(move || {
+        let metrics = load_metrics()?;
+        let content =
+            serde_json::to_string_pretty(&metrics).map_err(|e| format!("Failed to serialize metrics: {}", e))?;
+        fs::write(&path, content).map_err(|e| sanitize_error(&format!("Failed to write metrics: {}", e)))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn clear_usage_metrics() -> Result<(), String> {
+    run_blocking(|| {
+        let _guard = METRICS_LOCK.lock(&metrics_path())?;
+        save_json(&metrics_path(), &UsageMetrics::default())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_report() {
+        let mut metrics = UsageMetrics::default();
+        let day = |d: i64| chrono::Utc::now() - chrono::Duration::days(d);
+        add_sample(&mut metrics, "verify", 100, true, day(1));
+        add_sample(&mut metrics, "verify", 300, false, day(0));
+        add_sample(&mut metrics, "global_search", 20, true, day(0));
+        for d in 0..MAX_DAYS as i64 + 10 {
+            add_sample(&mut metrics, "export", 5, true, day(d + 2));
+        }
+        assert_eq!(metrics.days.len(), MAX_DAYS);
+
+        let report = report(metrics, true);
+        let verify = report.features.iter().find(|f| f.feature == "verify").unwrap();
+        assert_eq!(
+            (verify.count, verify.errors, verify.average_ms, verify.max_ms),
+            (2, 1, 200, 300)
+        );
+        assert_eq!(report.features[0].feature, "export");
+        assert_eq!(report.days.len(), ACTIVITY_DAYS);
+        assert_eq!(report.days.last().unwrap().count, 2);
+    }
+}
@@ -0,0 +1,296 @@
+// ==================== NOTE ANNOTATIONS ====================
+//
+// Writes a verification result back into the vault note the claim came from,
+// so it is visible to anyone reading the vault in Obsidian. Two styles:
+//
+//     > [!truthgit] PASSED · 92% confidence
+//     > Water boils at 100°C at sea level
+//     > Verified 2026-01-14 · audit 3f2a9c1e
+//     > %% truthgit:ab12cd34ef56 %%
+//
+// or an entry in a `truthgit` list in the frontmatter. Each block is keyed
+// by the claim (its hash when the audit entry has one), so annotating the
+// same claim again replaces its block instead of adding another, and
+// annotating with the same result leaves the file untouched. Encrypted
+// notes aren't annotated.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+
+use crate::json_store::write_atomic;
+use crate::reviews::short_hash;
+use crate::{
+    get_vault_path, read_audit_trail, run_blocking, sanitize_error, validate_path_within_base, vault_crypto, AuditEntry,
+};
+
+const CALLOUT_HEADER: &str = "> [!truthgit]";
+const FRONTMATTER_KEY: &str = "truthgit:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationStyle {
+    Callout,
+    Frontmatter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationOutcome {
+    Added,
+    Updated,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteAnnotation {
+    pub path: String,
+    pub outcome: AnnotationOutcome,
+}
+
+/// Identifies the claim's block in a note
+fn claim_key(entry: &AuditEntry) -> String {
+    match entry.claim_hash.as_deref().filter(|h| !h.is_empty()) {
+        Some(hash) => hash.chars().take(12).collect(),
+        None => format!("{:x}", Sha256::digest(entry.claim.trim().as_bytes()))[..12].to_string(),
+    }
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn verified_on(entry: &AuditEntry) -> String {
+    chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| entry.timestamp.clone())
+}
+
+fn callout(entry: &AuditEntry, key: &str) -> Vec<String> {
+    vec![
+        format!(
+            "{} {} · {:.0}% confidence",
+            CALLOUT_HEADER,
+            entry.result_status,
+            entry.confidence * 100.0
+        ),
+        format!("> {}", one_line(&entry.claim)),
+        format!("> Verified {} · audit {}", verified_on(entry), short_hash(&entry.id)),
+        format!("> %% truthgit:{} %%", key),
+    ]
+}
+
+/// Replace the callout keyed `key`, or append `block` at the end
+fn upsert_callout(content: &str, key: &str, block: &[String]) -> (String, bool) {
+    let lines: Vec<&str> = content.lines().collect();
+    let marker = format!("> %% truthgit:{} %%", key);
+    let mut i = 0;
+    while i < lines.len() {
+        if !lines[i].starts_with(CALLOUT_HEADER) {
+            i += 1;
+            continue;
+        }
+        let end = (i + 1..lines.len())
+            .find(|&j| !lines[j].starts_with('>'))
+            .unwrap_or(lines.len());
+        if lines[i..end].iter().any(|l| l.trim_end() == marker) {
+            let mut out: Vec<String> = lines[..i].iter().map(|l| l.to_string()).collect();
+            out.extend(block.iter().cloned());
+            out.extend(lines[end..].iter().map(|l| l.to_string()));
+            return (join_lines(&out, content), true);
+        }
+        i = end;
+    }
+    let mut out = content.trim_end().to_string();
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    out.push_str(&block.join("\n"));
+    out.push('\n');
+    (out, false)
+}
+
+fn join_lines(lines: &[String], original: &str) -> String {
+    let mut out = lines.join("\n");
+    if original.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn frontmatter_entry(entry: &AuditEntry, key: &str) -> Vec<String> {
+    let quoted = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    vec![
+        format!("  - key: {}", key),
+        format!("    claim: {}", quoted(&one_line(&entry.claim))),
+        format!("    verdict: {}", quoted(&entry.result_status)),
+        format!("    confidence: {:.2}", entry.confidence),
+        format!("    date: {}", verified_on(entry)),
+        format!("    audit: {}", quoted(&entry.id)),
+    ]
+}
+
+/// Replace the `truthgit` frontmatter entry keyed `key`, or add it, creating
+/// the frontmatter if the note has none
+fn upsert_frontmatter(content: &str, key: &str, entry: &[String]) -> Result<(String, bool), String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let close = match lines.first() {
+        Some(first) if first.trim_end() == "---" => lines.iter().skip(1).position(|l| l.trim_end() == "---"),
+        _ => None,
+    };
+    let Some(close) = close.map(|c| c + 1) else {
+        let mut out = vec!["---".to_string(), FRONTMATTER_KEY.to_string()];
+        out.extend(entry.iter().cloned());
+        out.push("---".to_string());
+        out.push(content.to_string());
+        return Ok((out.join("\n"), false));
+    };
+
+    let owned = |range: &[&str]| range.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    let Some(start) = (1..close).find(|&i| lines[i].starts_with(FRONTMATTER_KEY)) else {
+        let mut out = owned(&lines[..close]);
+        out.push(FRONTMATTER_KEY.to_string());
+        out.extend(entry.iter().cloned());
+        out.extend(owned(&lines[close..]));
+        return Ok((join_lines(&out, content), false));
+    };
+    let foreign = || "The note's `truthgit` frontmatter field wasn't written by this app".to_string();
+    if lines[start].trim_end() != FRONTMATTER_KEY {
+        return Err(foreign());
+    }
+    let end = (start + 1..close)
+        .find(|&i| !lines[i].starts_with(' ') && !lines[i].is_empty())
+        .unwrap_or(close);
+
+    // Split the list into entries; each starts with its `- key:` line
+    let mut entries: Vec<Vec<String>> = Vec::new();
+    for line in &lines[start + 1..end] {
+        if line.starts_with("  - key: ") {
+            entries.push(vec![line.to_string()]);
+        } else if let Some(current) = entries
+            .last_mut()
+            .filter(|_| line.starts_with("    ") || line.is_empty())
+        {
+            current.push(line.to_string());
+        } else {
+            return Err(foreign());
+        }
+    }
+    let own_key = format!("  - key: {}", key);
+    let existing = entries.iter().position(|e| e[0].trim_end() == own_key);
+    match existing {
+        Some(i) => entries[i] = entry.to_vec(),
+        None => entries.push(entry.to_vec()),
+    }
+    let mut out = owned(&lines[..=start]);
+    out.extend(entries.into_iter().flatten());
+    out.extend(owned(&lines[end..]));
+    Ok((join_lines(&out, content), existing.is_some()))
+}
+
+/// Record the result of audit entry `audit_id` in the note at `path`
+/// (relative to the vault)
+#[tauri::command]
+pub async fn annotate_note_with_result(
+    path: String,
+    audit_id: String,
+    style: AnnotationStyle,
+) -> Result<NoteAnnotation, String> {
+    run_blocking(move || {
+        let vault = get_vault_path().ok_or("Could not find home directory")?;
+        let note_path = validate_path_within_base(&vault, &path)?;
+        if note_path.extension().is_none_or(|ext| ext != "md") {
+            return Err("Only markdown notes can be annotated".to_string());
+        }
+        if vault_crypto::is_encrypted(&note_path) {
+            return Err("Encrypted notes can't be annotated".to_string());
+        }
+        let entry = read_audit_trail()?
+            .into_iter()
+            .find(|e| e.id == audit_id)
+            .ok_or_else(|| format!("No audit entry {}", audit_id))?;
+        let content =
+            fs::read_to_string(&note_path).map_err(|e| sanitize_error(&format!("Failed to read note: {}", e)))?;
+
+        let key = claim_key(&entry);
+        let (annotated, replaced) = match style {
+            AnnotationStyle::Callout => upsert_callout(&content, &key, &callout(&entry, &key)),
+            AnnotationStyle::Frontmatter => upsert_frontmatter(&content, &key, &frontmatter_entry(&entry, &key))?,
+        };
+        let outcome = if annotated == content {
+            AnnotationOutcome::Unchanged
+        } else if replaced {
+            AnnotationOutcome::Updated
+        } else {
+            AnnotationOutcome::Added
+        };
+        if outcome != AnnotationOutcome::Unchanged {
+            write_atomic(&note_path, annotated.as_bytes())
+                .map_err(|e| sanitize_error(&format!("Failed to write note: {}", e)))?;
+            log::info!("Annotated {} with audit entry {}", path, audit_id);
+        }
+        Ok(NoteAnnotation { path, outcome })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, status: &str, confidence: f64) -> AuditEntry {
+        AuditEntry {
+            id: id.to_string(),
+            timestamp: "2026-01-14T09:12:00Z".to_string(),
+            claim: "Water boils at\n100°C".to_string(),
+            result_status: status.to_string(),
+            confidence,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_callout_is_idempotent() {
+        let note = "# Water\n\nWater boils at 100°C.\n";
+        let first = entry("3f2a9c1e-aaaa", "PASSED", 0.92);
+        let key = claim_key(&first);
+        let (once, replaced) = upsert_callout(note, &key, &callout(&first, &key));
+        assert!(!replaced);
+        assert!(once.contains("> [!truthgit] PASSED · 92% confidence\n> Water boils at 100°C\n"));
+        assert!(once.contains("> Verified 2026-01-14 · audit 3f2a9c1e\n"));
+        assert_eq!(upsert_callout(&once, &key, &callout(&first, &key)).0, once);
+
+        let rerun = entry("77aa0000-bbbb", "FAILED", 0.4);
+        let (twice, replaced) = upsert_callout(&format!("{}\nMore text\n", once), &key, &callout(&rerun, &key));
+        assert!(replaced);
+        assert_eq!(twice.matches(CALLOUT_HEADER).count(), 1);
+        assert!(twice.contains("FAILED · 40%") && twice.ends_with("More text\n"));
+    }
+
+    #[test]
+    fn test_frontmatter_entries() {
+        let first = entry("a1", "PASSED", 0.9);
+        let key = claim_key(&first);
+        let (created, _) = upsert_frontmatter("Body\n", &key, &frontmatter_entry(&first, &key)).unwrap();
+        assert!(created.starts_with("---\ntruthgit:\n  - key: "));
+        assert!(created.ends_with("    audit: \"a1\"\n---\nBody\n"));
+
+        let note = "---\ntags: [water]\n---\nBody\n";
+        let (added, replaced) = upsert_frontmatter(note, &key, &frontmatter_entry(&first, &key)).unwrap();
+        assert!(!replaced);
+        let mut other = entry("b2", "PASSED", 0.5);
+        other.claim_hash = Some("ffee".to_string());
+        let (both, _) = upsert_frontmatter(&added, "ffee", &frontmatter_entry(&other, "ffee")).unwrap();
+        let rerun = entry("c3", "FAILED", 0.3);
+        let (updated, replaced) = upsert_frontmatter(&both, &key, &frontmatter_entry(&rerun, &key)).unwrap();
+        assert!(replaced);
+        assert_eq!(updated.matches("  - key: ").count(), 2);
+        assert!(updated.contains("verdict: \"FAILED\"") && !updated.contains("audit: \"a1\""));
+        assert!(updated.starts_with("---\ntags: [water]\ntruthgit:\n") && updated.ends_with("---\nBody\n"));
+
+        let foreign = "---\ntruthgit: yes please\n---\n";
+        assert!(upsert_frontmatter(foreign, &key, &frontmatter_entry(&first, &key)).is_err());
+        let foreign_list = "---\ntruthgit:\n  - something\n---\n";
+        assert!(upsert_frontmatter(foreign_list, &key, &frontmatter_entry(&first, &key)).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+// ==================== NOTE LOOKUP ====================
+//
+// Resolves a note by name the way Obsidian does, for wikilinks and the
+// quick-open box: `[[my note]]` finds `My Note.md`, `[[Folder/Note#Section]]`
+// finds the note in that folder, and names listed under `aliases` in a
+// note's frontmatter count as well. When nothing matches exactly, close
+// names (prefix, substring, a typo or two, or the letters in order) are
+// returned ranked, so a dead link can still offer where it probably meant.
+//
+// Names and aliases are kept in memory per vault; each lookup only rereads
+// the frontmatter of notes modified since the last one.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+use crate::{get_vault_path, note_name, paths, run_blocking, MAX_VAULT_FILES};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+const MAX_NAME_LEN: usize = 200;
+/// Frontmatter is only looked for in this much of a note
+const FRONTMATTER_BYTES: u64 = 8 * 1024;
+/// Below this score a candidate isn't worth offering
+const MIN_SCORE: f64 = 0.3;
+
+static CATALOG: Mutex<Option<Catalog>> = Mutex::new(None);
+
+#[derive(Debug, Default)]
+struct Catalog {
+    vault: String,
+    /// By path relative to the vault
+    notes: HashMap<String, CatalogNote>,
+}
+
+#[derive(Debug, Clone)]
+struct CatalogNote {
+    name: String,
+    aliases: Vec<String>,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteMatch {
+    /// Relative to the vault, with `/` separators
+    pub path: String,
+    pub name: String,
+    /// The alias that matched, when it wasn't the name
+    pub alias: Option<String>,
+    /// 0.0-1.0; results are sorted by it
+    pub score: f64,
+    /// Same name, alias or path apart from case, spacing and extension
+    pub exact: bool,
+}
+
+/// Lowercased, with `-`, `_` and runs of whitespace folded into one space
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Path without `.md` / `.md.age`, for matching `[[Folder/Note]]`
+fn path_stem(path: &str) -> &str {
+    let path = path.strip_suffix(".age").unwrap_or(path);
+    path.strip_suffix(".md").unwrap_or(path)
+}
+
+/// Link target with any `|display`, `#heading` or `^block` part and a `.md`
+/// extension removed
+pub fn link_target(name: &str) -> &str {
+    let name = name.split('|').next().unwrap_or(name);
+    let name = name.split(['#', '^']).next().unwrap_or(name).trim();
+    path_stem(name)
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+/// `aliases` (or `alias`) from YAML frontmatter, written inline
+/// (`aliases: [a, b]` or `aliases: a`) or as a block list
+fn frontmatter_aliases(content: &str) -> Vec<String> {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return Vec::new();
+    };
+    let mut aliases = Vec::new();
+    let mut in_list = false;
+    for line in rest.lines() {
+        if line.trim_end() == "---" {
+            break;
+        }
+        if in_list {
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                aliases.push(unquote(item).to_string());
+                continue;
+            }
+            in_list = false;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !matches!(key.trim(), "aliases" | "alias") {
+            continue;
+        }
+        let value = value.trim();
+        if value.is_empty() {
+            in_list = true;
+        } else if let Some(inline) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            aliases.extend(inline.split(',').map(|a| unquote(a).to_string()));
+        } else {
+            aliases.push(unquote(value).to_string());
+        }
+    }
+    aliases.retain(|a| !a.is_empty());
+    aliases
+}
+
+fn read_aliases(path: &Path) -> Vec<String> {
+    // Encrypted notes keep their frontmatter to themselves
+    if path.extension().is_some_and(|ext| ext == "age") {
+        return Vec::new();
+    }
+    let mut head = Vec::new();
+    let read = fs::File::open(path).and_then(|f| f.take(FRONTMATTER_BYTES).read_to_end(&mut head));
+    match read {
+        Ok(_) => frontmatter_aliases(&String::from_utf8_lossy(&head)),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn is_note(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".md") || name.ends_with(".md.age")
+}
+
+/// Bring the catalog up to date with the vault
+fn refresh(catalog: &mut Catalog, vault: &Path) {
+    let vault_key = paths::display_path(vault);
+    if catalog.vault != vault_key {
+        *catalog = Catalog {
+            vault: vault_key,
+            notes: HashMap::new(),
+        };
+    }
+    let mut seen = HashMap::with_capacity(catalog.notes.len());
+    for entry in WalkDir::new(vault)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .take(MAX_VAULT_FILES)
+        .filter(|e| e.file_type().is_file() && is_note(e.path()))
+    {
+        let Some(relative) = paths::relative_to(vault, entry.path()) else {
+            continue;
+        };
+        let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+        let note = match catalog.notes.remove(&relative) {
+            Some(note) if note.modified == modified => note,
+            _ => CatalogNote {
+                name: note_name(entry.path()).unwrap_or_default(),
+                aliases: read_aliases(entry.path()),
+                modified,
+            },
+        };
+        seen.insert(relative, note);
+    }
+    catalog.notes = seen;
+}
+
+/// Levenshtein distance, giving up once it exceeds `max`
+fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            current[j + 1] = (previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().min().is_some_and(|&m| m > max) {
+            return None;
+        }
+        previous = current;
+    }
+    Some(previous[b.len()]).filter(|&d| d <= max)
+}
+
+/// Whether the letters of `query` appear in `text` in order, and how
+/// tightly: 1.0 when they are adjacent
+fn subsequence_score(query: &str, text: &str) -> Option<f64> {
+    let mut chars = text.char_indices();
+    let (mut first, mut last) = (None, 0);
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let (index, _) = chars.find(|&(_, c)| c == q)?;
+        first.get_or_insert(index);
+        last = index;
+    }
+    let span = (last - first?) as f64 + 1.0;
+    Some(query.chars().filter(|c| !c.is_whitespace()).count() as f64 / span)
+}
+
+/// How well `wanted` (normalized) names `candidate`; 1.0 is an exact match
+fn score(wanted: &str, candidate: &str) -> f64 {
+    let candidate = normalize(candidate);
+    if candidate.is_empty() {
+        0.0
+    } else if candidate == wanted {
+        1.0
+    } else if candidate.replace(' ', "") == wanted.replace(' ', "") {
+        0.95
+    } else if candidate.starts_with(wanted) {
+        0.8
+    } else if candidate.contains(wanted) {
+        0.7
+    } else if let Some(distance) = edit_distance(wanted, &candidate, (wanted.chars().count() / 4).min(2)) {
+        0.65 - 0.05 * distance as f64
+    } else {
+        subsequence_score(wanted, &candidate).map_or(0.0, |tightness| 0.3 + 0.25 * tightness)
+    }
+}
+
+fn rank(catalog: &Catalog, name: &str, limit: usize) -> Vec<NoteMatch> {
+    let target = link_target(name);
+    let wanted = normalize(target);
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let by_path = target.contains('/');
+
+    let mut matches: Vec<NoteMatch> = catalog
+        .notes
+        .iter()
+        .filter_map(|(path, note)| {
+            let mut best = (score(&wanted, &note.name), None);
+            for alias in &note.aliases {
+                // An alias ranks just below the same text as a name
+                let alias_score = score(&wanted, alias) * 0.98;
+                if alias_score > best.0 {
+                    best = (alias_score, Some(alias.clone()));
+                }
+            }
+            if by_path {
+                let path_score = score(&wanted, path_stem(path));
+                if path_score >= best.0 {
+                    best = (path_score, None);
+                }
+            }
+            let (score, alias) = best;
+            (score >= MIN_SCORE).then(|| NoteMatch {
+                path: path.clone(),
+                name: note.name.clone(),
+                exact: score >= 0.95 * 0.98,
+                alias,
+                score,
+            })
+        })
+        .collect();
+    // Best first; among equals the shallower path, as Obsidian prefers
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.matches('/').count().cmp(&b.path.matches('/').count()))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Notes `name` may refer to, best first: a wikilink target (`My Note`,
+/// `Folder/Note#Heading`) or what was typed into quick open
+#[tauri::command]
+pub async fn resolve_note(name: String, limit: Option<usize>) -> Result<Vec<NoteMatch>, String> {
+    if name.len() > MAX_NAME_LEN {
+        return Err(format!("Note name too long (max {} characters)", MAX_NAME_LEN));
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    run_blocking(move || {
+        let vault = get_vault_path()
+            .filter(|p| p.exists())
+            .ok_or_else(|| "Vault not found".to_string())?;
+        let mut catalog = CATALOG.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let catalog = catalog.get_or_insert_with(Catalog::default);
+        refresh(catalog, &vault);
+        Ok(rank(catalog, &name, limit))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_frontmatter_aliases() {
+        let inline = "---\ntitle: X\naliases: [Photo Synthesis, \"PS\"]\n---\nbody\naliases: ignored";
+        assert_eq!(frontmatter_aliases(inline), vec!["Photo Synthesis", "PS"]);
+        let block = "---\naliases:\n  - First\n  - 'Second one'\ntags: [a]\n---\n";
+        assert_eq!(frontmatter_aliases(block), vec!["First", "Second one"]);
+        assert_eq!(frontmatter_aliases("---\nalias: Solo\n---"), vec!["Solo"]);
+        assert!(frontmatter_aliases("no frontmatter\naliases: [x]").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_in_vault() {
+        let tmp = TempDir::new("truthgit-note-links");
+        let vault = tmp.path();
+        fs::create_dir_all(vault.join("Physics")).unwrap();
+        fs::create_dir_all(vault.join(".obsidian")).unwrap();
+        fs::write(vault.join("My Note.md"), "plain").unwrap();
+        fs::write(
+            vault.join("Physics/Heat Transfer.md"),
+            "---\naliases: [Thermodynamics]\n---\n",
+        )
+        .unwrap();
+        fs::write(vault.join("Archive.md"), "").unwrap();
+        fs::write(vault.join(".obsidian/Hidden.md"), "").unwrap();
+        let mut catalog = Catalog::default();
+        refresh(&mut catalog, vault);
+        assert_eq!(catalog.notes.len(), 3);
+
+        let best = |name: &str| rank(&catalog, name, 5).into_iter().next();
+        let note = best("my note").unwrap();
+        assert_eq!((note.path.as_str(), note.exact), ("My Note.md", true));
+        assert_eq!(best("my-note#Intro|see").unwrap().path, "My Note.md");
+        let alias = best("thermodynamics").unwrap();
+        assert_eq!(alias.path, "Physics/Heat Transfer.md");
+        assert_eq!(alias.alias.as_deref(), Some("Thermodynamics"));
+        assert_eq!(best("physics/heat transfer").unwrap().path, "Physics/Heat Transfer.md");
+
+        let typo = best("heat transfr").unwrap();
+        assert_eq!(typo.path, "Physics/Heat Transfer.md");
+        assert!(!typo.exact);
+        assert_eq!(best("arcv").unwrap().path, "Archive.md");
+        assert!(best("zzzz").is_none());
+        assert!(best("hidden").is_none());
+    }
+}
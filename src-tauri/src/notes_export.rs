@@ -0,0 +1,538 @@
+// ==================== ANNOTATED NOTES EXPORT ====================
+//
+// Renders a vault folder to one HTML or PDF file for people who don't run
+// the app: every note in path order, each candidate claim (found with the
+// file intake heuristic) followed by a badge with its current verdict, and
+// an appendix listing the claims with links back to them in TruthGit. A
+// claim's verdict is the newest verification or audit entry whose text
+// matches it, compared the way the coverage report does. Notes go through
+// Markdown with raw HTML escaped; wikilinks become their display text.
+// Encrypted notes are left out.
+
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use walkdir::WalkDir;
+
+use crate::coverage::claim_key;
+use crate::file_intake::extract_claims;
+use crate::identity::{self, Actor};
+use crate::reports::{layout_pdf, wrap, PdfLine, ReportFormat, PDF_WRAP};
+use crate::verification::Verification;
+use crate::{
+    get_vault_path, metrics, mock, note_name, paths, read_audit_trail, read_claims, read_verifications, run_blocking,
+    sanitize_error, validate_path_within_base, vault_crypto, AuditEntry, MAX_VAULT_FILES,
+};
+
+const TEMPLATE: &str = include_str!("../templates/annotated_notes.html");
+const MAX_EXPORT_NOTES: usize = 500;
+/// Marks where a claim's badge goes until the note is rendered
+const MARK_START: char = '\u{E000}';
+const MARK_END: char = '\u{E001}';
+
+#[derive(Debug, Clone, PartialEq)]
+struct Verdict {
+    verdict: String,
+    confidence: Option<f64>,
+    at: String,
+    claim_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedClaim {
+    /// Element ID of the claim's appendix row
+    pub anchor: String,
+    /// Note path relative to the vault
+    pub note: String,
+    pub claim: String,
+    pub claim_hash: Option<String>,
+    /// None while unverified
+    pub verdict: Option<String>,
+    pub confidence: Option<f64>,
+    pub verified_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExportedNote {
+    path: String,
+    name: String,
+    html: String,
+    /// Plain text blocks for the PDF, with badges inline: (text, heading)
+    #[serde(skip)]
+    blocks: Vec<(String, bool)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportSummary {
+    pub notes: usize,
+    pub claims: usize,
+    pub verified: usize,
+    pub by_verdict: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnnotatedExport {
+    title: String,
+    generated_at: String,
+    generated_by: Option<Actor>,
+    folder: String,
+    notes: Vec<ExportedNote>,
+    claims: Vec<ExportedClaim>,
+    summary: ExportSummary,
+    /// Encrypted notes left out
+    skipped: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedNotes {
+    pub path: String,
+    pub summary: ExportSummary,
+    pub skipped: usize,
+}
+
+fn instant(at: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(at).ok().map(|t| t.to_utc())
+}
+
+/// Newest verdict per claim text, from verifications and audit entries
+fn verdicts(
+    verifications: &[Verification],
+    claims: &[serde_json::Value],
+    audit: &[AuditEntry],
+) -> HashMap<String, Verdict> {
+    let content: HashMap<&str, &str> = claims
+        .iter()
+        .filter_map(|c| Some((c.get("$hash")?.as_str()?, c.get("content")?.as_str()?)))
+        .collect();
+    let mut found: HashMap<String, Verdict> = HashMap::new();
+    let mut offer = |text: &str, verdict: Verdict| {
+        let key = claim_key(text);
+        if key.is_empty() || verdict.verdict.is_empty() {
+            return;
+        }
+        let newer = found
+            .get(&key)
+            .is_none_or(|seen| instant(&verdict.at) > instant(&seen.at));
+        if newer {
+            found.insert(key, verdict);
+        }
+    };
+    for v in verifications {
+        let hash = Some(v.claim_hash.clone()).filter(|h| !h.is_empty());
+        let verdict = Verdict {
+            verdict: v.verdict.clone(),
+            confidence: v.confidence,
+            at: v.timestamp.clone(),
+            claim_hash: hash,
+        };
+        match content.get(v.claim_hash.as_str()) {
+            Some(text) if v.claim.is_empty() => offer(text, verdict),
+            _ => offer(&v.claim, verdict),
+        }
+    }
+    for entry in audit {
+        let verdict = Verdict {
+            verdict: entry.result_status.clone(),
+            confidence: Some(entry.confidence),
+            at: entry.timestamp.clone(),
+            claim_hash: entry.claim_hash.clone(),
+        };
+        offer(&entry.claim, verdict);
+    }
+    found
+}
+
+fn strip_frontmatter(content: &str) -> &str {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return content;
+    };
+    match rest.find("\n---") {
+        Some(end) => rest[end + 4..].trim_start_matches(['\r', '\n']),
+        None => content,
+    }
+}
+
+/// `[[note|shown]]` -> `shown`, `[[Folder/Note#Heading]]` -> `Note`; embeds likewise
+fn resolve_wikilinks(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        let Some(end) = rest[start..].find("]]") else {
+            break;
+        };
+        let before = rest[..start].strip_suffix('!').unwrap_or(&rest[..start]);
+        out.push_str(before);
+        let inner = &rest[start + 2..start + end];
+        match inner.split_once('|') {
+            Some((_, shown)) => out.push_str(shown),
+            None => {
+                let target = inner.split(['#', '^']).next().unwrap_or(inner);
+                out.push_str(target.rsplit('/').next().unwrap_or(target));
+            }
+        }
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn badge_label(claim: &ExportedClaim) -> String {
+    let verdict = claim.verdict.as_deref().unwrap_or("UNVERIFIED");
+    match claim.confidence {
+        Some(confidence) => format!("{} {:.0}%", verdict, confidence * 100.0),
+        None => verdict.to_string(),
+    }
+}
+
+fn badge_html(claim: &ExportedClaim) -> String {
+    let class: String = claim
+        .verdict
+        .as_deref()
+        .unwrap_or("UNVERIFIED")
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+    format!(
+        "<a class=\"badge {}\" href=\"#{}\">{}</a>",
+        class,
+        claim.anchor,
+        escape(&badge_label(claim))
+    )
+}
+
+/// Swap each badge mark in `text` for `badge(claim)`
+fn replace_marks(text: &str, claims: &[ExportedClaim], badge: impl Fn(&ExportedClaim) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(MARK_START) {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + MARK_START.len_utf8()..];
+        let Some(end) = after.find(MARK_END) else {
+            rest = after;
+            break;
+        };
+        if let Some(claim) = after[..end].parse::<usize>().ok().and_then(|i| claims.get(i)) {
+            out.push_str(&badge(claim));
+        }
+        rest = &after[end + MARK_END.len_utf8()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Render `markdown` (badge marks in place) to HTML and to plain text blocks
+fn render_markdown(markdown: &str, claims: &[ExportedClaim]) -> (String, Vec<(String, bool)>) {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events: Vec<Event> = Parser::new_ext(markdown, options)
+        .map(|event| match event {
+            // Notes are shared as documents, not pages: raw HTML shows as text
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            other => other,
+        })
+        .collect();
+
+    let mut markup = String::new();
+    html::push_html(&mut markup, events.iter().cloned());
+
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    let mut heading = false;
+    for event in &events {
+        match event {
+            Event::Text(text) | Event::Code(text) => current.push_str(text),
+            Event::SoftBreak => current.push(' '),
+            Event::Start(Tag::Heading { .. }) => heading = true,
+            Event::Start(Tag::Item) => current.push_str("• "),
+            Event::HardBreak
+            | Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::HtmlBlock
+                | TagEnd::TableRow,
+            ) => {
+                let text = current.trim().to_string();
+                if !text.is_empty() {
+                    blocks.push((
+                        replace_marks(&text, claims, |c| format!(" [{}]", badge_label(c))),
+                        heading,
+                    ));
+                }
+                current.clear();
+                heading = false;
+            }
+            _ => {}
+        }
+    }
+    let markup = replace_marks(&markup, claims, badge_html);
+    (markup, blocks)
+}
+
+/// One note with its claims appended to `claims`
+fn export_note(
+    relative: &str,
+    name: String,
+    content: &str,
+    verdicts: &HashMap<String, Verdict>,
+    claims: &mut Vec<ExportedClaim>,
+) -> ExportedNote {
+    let mut markdown = resolve_wikilinks(strip_frontmatter(content));
+    let mut marks = Vec::new();
+    for text in extract_claims(&markdown) {
+        let index = claims.len();
+        let verdict = verdicts.get(&claim_key(&text));
+        claims.push(ExportedClaim {
+            anchor: format!("claim-{}", index + 1),
+            note: relative.to_string(),
+            claim_hash: verdict.and_then(|v| v.claim_hash.clone()),
+            verdict: verdict.map(|v| v.verdict.clone()),
+            confidence: verdict.and_then(|v| v.confidence),
+            verified_at: verdict.map(|v| v.at.clone()),
+            claim: text.clone(),
+        });
+        // Claims whose text was reflowed or had markup removed get no badge
+        // inline, only their row in the appendix
+        if let Some(at) = markdown.find(&text) {
+            marks.push((at + text.len(), index));
+        }
+    }
+    marks.sort_by(|a, b| b.cmp(a));
+    for (at, index) in marks {
+        markdown.insert_str(at, &format!("{}{}{}", MARK_START, index, MARK_END));
+    }
+    let (html, blocks) = render_markdown(&markdown, claims);
+    ExportedNote {
+        path: relative.to_string(),
+        name,
+        html,
+        blocks,
+    }
+}
+
+fn build_export(folder: &Path, vault: &Path, verdicts: &HashMap<String, Verdict>) -> Result<AnnotatedExport, String> {
+    let mut paths_found = Vec::new();
+    let mut skipped = 0;
+    for entry in WalkDir::new(folder)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .take(MAX_VAULT_FILES)
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".md.age") || (name.ends_with(".md") && vault_crypto::is_encrypted(path)) {
+            skipped += 1;
+        } else if name.ends_with(".md") {
+            paths_found.push(path.to_path_buf());
+        }
+    }
+    if paths_found.len() > MAX_EXPORT_NOTES {
+        return Err(format!(
+            "The folder has {} notes; export a section of at most {}",
+            paths_found.len(),
+            MAX_EXPORT_NOTES
+        ));
+    }
+    paths_found.sort();
+
+    let mut notes = Vec::new();
+    let mut claims = Vec::new();
+    for path in &paths_found {
+        let content = fs::read_to_string(path).map_err(|e| sanitize_error(&format!("Failed to read note: {}", e)))?;
+        let relative = paths::relative_to(vault, path).unwrap_or_default();
+        let name = note_name(path).unwrap_or_else(|| relative.clone());
+        notes.push(export_note(&relative, name, &content, verdicts, &mut claims));
+    }
+
+    let mut summary = ExportSummary {
+        notes: notes.len(),
+        claims: claims.len(),
+        ..Default::default()
+    };
+    for verdict in claims.iter().filter_map(|c| c.verdict.as_ref()) {
+        summary.verified += 1;
+        *summary.by_verdict.entry(verdict.clone()).or_default() += 1;
+    }
+    let folder_name = paths::relative_to(vault, folder).unwrap_or_default();
+    Ok(AnnotatedExport {
+        title: match folder.file_name() {
+            Some(name) if !folder_name.is_empty() => name.to_string_lossy().to_string(),
+            _ => "Vault".to_string(),
+        },
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        generated_by: identity::current(),
+        folder: folder_name,
+        notes,
+        claims,
+        summary,
+        skipped,
+    })
+}
+
+fn render_html(export: &AnnotatedExport) -> Result<String, String> {
+    let mut env = minijinja::Environment::new();
+    env.add_template("annotated_notes.html", TEMPLATE)
+        .map_err(|e| format!("Export template error: {}", e))?;
+    env.get_template("annotated_notes.html")
+        .and_then(|t| t.render(export))
+        .map_err(|e| format!("Export template error: {}", e))
+}
+
+fn render_pdf(export: &AnnotatedExport) -> Result<Vec<u8>, String> {
+    let mut lines: Vec<PdfLine> = vec![(export.title.clone(), 18.0, true, 0.0)];
+    lines.push((
+        format!(
+            "Generated {} · {} of {} claims verified",
+            export.generated_at, export.summary.verified, export.summary.claims
+        ),
+        9.0,
+        false,
+        2.0,
+    ));
+    for note in &export.notes {
+        lines.push((note.name.clone(), 14.0, true, 10.0));
+        lines.push((note.path.clone(), 8.0, false, 0.0));
+        for (text, heading) in &note.blocks {
+            for (i, line) in wrap(text, PDF_WRAP).into_iter().enumerate() {
+                lines.push((line, 10.0, *heading, if i == 0 { 3.0 } else { 0.0 }));
+            }
+        }
+    }
+    lines.push(("Verification summary".to_string(), 14.0, true, 10.0));
+    for (verdict, count) in &export.summary.by_verdict {
+        lines.push((format!("{}: {}", verdict, count), 10.0, false, 0.0));
+    }
+    for claim in &export.claims {
+        let header = format!("{} · {}", badge_label(claim), claim.note);
+        lines.push((header, 9.0, true, 3.0));
+        for line in wrap(&claim.claim, PDF_WRAP) {
+            lines.push((line, 9.0, false, 0.0));
+        }
+        if let Some(hash) = &claim.claim_hash {
+            lines.push((format!("truthgit://claim/{}", hash), 7.0, false, 0.0));
+        }
+    }
+    layout_pdf(&export.title, lines)
+}
+
+/// Render the notes under `folder` (relative to the vault, "" for all of it)
+/// with verification badges to `dest`. The format defaults to PDF for a
+/// `.pdf` destination and HTML otherwise.
+#[tauri::command]
+pub async fn export_annotated_notes(
+    folder: String,
+    dest: String,
+    format: Option<ReportFormat>,
+) -> Result<ExportedNotes, String> {
+    let started = Instant::now();
+    let exported = run_blocking(move || {
+        let vault = get_vault_path()
+            .filter(|p| p.exists())
+            .ok_or_else(|| "Vault not found".to_string())?;
+        let vault = fs::canonicalize(&vault).map_err(|e| sanitize_error(&format!("Failed to resolve vault: {}", e)))?;
+        let dir = if folder.trim().is_empty() {
+            vault.clone()
+        } else {
+            validate_path_within_base(&vault, &folder)?
+        };
+        if !dir.is_dir() {
+            return Err("Not a folder".to_string());
+        }
+
+        let audit = if mock::is_active() {
+            Vec::new()
+        } else {
+            read_audit_trail()?
+        };
+        let verdicts = verdicts(&read_verifications()?.verifications, &read_claims(None)?.claims, &audit);
+        let export = build_export(&dir, &vault, &verdicts)?;
+
+        let format = format.unwrap_or(if dest.to_lowercase().ends_with(".pdf") {
+            ReportFormat::Pdf
+        } else {
+            ReportFormat::Html
+        });
+        let bytes = match format {
+            ReportFormat::Html => render_html(&export)?.into_bytes(),
+            ReportFormat::Pdf => render_pdf(&export)?,
+        };
+        fs::write(&dest, bytes).map_err(|e| sanitize_error(&format!("Failed to write export: {}", e)))?;
+        log::info!("Exported {} annotated notes as {:?}", export.summary.notes, format);
+        Ok(ExportedNotes {
+            path: dest,
+            summary: export.summary,
+            skipped: export.skipped,
+        })
+    })
+    .await;
+    metrics::record("export_notes", started, exported.is_ok());
+    exported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use serde_json::json;
+
+    #[test]
+    fn test_export_marks_claims() {
+        let tmp = TempDir::new("truthgit-notes-export");
+        let vault = tmp.path();
+        fs::create_dir_all(vault.join("Science")).unwrap();
+        let vault = fs::canonicalize(vault).unwrap();
+        fs::write(
+            vault.join("Science/Water.md"),
+            "---\ntags: [water]\n---\n# Water\n\nWater boils at 100 degrees at sea level. \
+             Ice floats because it is less dense.\n\n<script>alert(1)</script>\n\nSee [[Physics/Heat|heat notes]].\n",
+        )
+        .unwrap();
+        fs::write(vault.join("Science/Locked.md.age"), "age-encryption.org/v1").unwrap();
+
+        let verifications = [json!({
+            "claim_hash": "ab12", "claim": "water boils at 100 degrees at sea level", "verdict": "PASSED",
+            "confidence": 0.92, "timestamp": "2026-01-14T09:00:00Z",
+        })]
+        .map(Verification::from);
+        let verdicts = verdicts(&verifications, &[], &[]);
+        let export = build_export(&vault.join("Science"), &vault, &verdicts).unwrap();
+        assert_eq!(
+            (export.summary.notes, export.skipped, export.folder.as_str()),
+            (1, 1, "Science")
+        );
+        assert_eq!((export.summary.claims, export.summary.verified), (2, 1));
+        assert_eq!(export.claims[0].claim_hash.as_deref(), Some("ab12"));
+
+        let html = &export.notes[0].html;
+        assert!(html.contains("sea level.<a class=\"badge PASSED\" href=\"#claim-1\">PASSED 92%</a>"));
+        assert!(html.contains("<a class=\"badge UNVERIFIED\" href=\"#claim-2\">UNVERIFIED</a>"));
+        assert!(html.contains("&lt;script&gt;") && !html.contains("<script>"));
+        assert!(html.contains("See heat notes.") && !html.contains("tags:"));
+        assert!(export.notes[0]
+            .blocks
+            .iter()
+            .any(|(t, _)| t.ends_with("sea level. [PASSED 92%] Ice floats because it is less dense. [UNVERIFIED]")));
+
+        let page = render_html(&export).unwrap();
+        assert!(page.contains("id=\"claim-2\""));
+        assert!(render_pdf(&export).unwrap().starts_with(b"%PDF"));
+    }
+}
@@ -0,0 +1,110 @@
+// ==================== NOTIFICATIONS ====================
+//
+// OS notifications for governance events that happen while the user is
+// looking elsewhere. Each event type is opt-in through `notifications` in
+// settings; nothing is shown by default.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::SETTINGS;
+
+/// Longest claim excerpt shown in a notification body
+const MAX_EXCERPT_CHARS: usize = 120;
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Batch verifications finishing, and verifications arriving from the remote
+    pub verification_complete: bool,
+    /// A verification whose governance action is "escalate"
+    pub escalation: bool,
+    pub sync_complete: bool,
+    /// A pinned claim's status or confidence changed on re-verification
+    pub watchlist_change: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    VerificationComplete,
+    Escalation,
+    SyncComplete,
+    WatchlistChange,
+}
+
+fn enabled(settings: &NotificationSettings, kind: NotificationKind) -> bool {
+    match kind {
+        NotificationKind::VerificationComplete => settings.verification_complete,
+        NotificationKind::Escalation => settings.escalation,
+        NotificationKind::SyncComplete => settings.sync_complete,
+        NotificationKind::WatchlistChange => settings.watchlist_change,
+    }
+}
+
+pub fn excerpt(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= MAX_EXCERPT_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(MAX_EXCERPT_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+/// Show a notification if the user opted in to this kind of event
+pub fn notify(kind: NotificationKind, title: &str, body: &str) {
+    let Some(app) = APP.get() else {
+        return;
+    };
+    let opted_in = SETTINGS.read().map(|s| enabled(&s.notifications, kind)).unwrap_or(false);
+    if !opted_in {
+        return;
+    }
+    show_with(app, title, body);
+}
+
+/// Show a notification unconditionally, for results the user explicitly asked for
+pub fn show(title: &str, body: &str) {
+    if let Some(app) = APP.get() {
+        show_with(app, title, body);
+    }
+}
+
+fn show_with(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("Failed to show notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_are_opt_in() {
+        let settings = NotificationSettings::default();
+        assert!(!enabled(&settings, NotificationKind::Escalation));
+        let settings = NotificationSettings {
+            escalation: true,
+            ..Default::default()
+        };
+        assert!(enabled(&settings, NotificationKind::Escalation));
+        assert!(!enabled(&settings, NotificationKind::SyncComplete));
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("Water  boils\nat 100°C"), "Water boils at 100°C");
+        let long = "word ".repeat(100);
+        let short = excerpt(&long);
+        assert!(short.chars().count() <= MAX_EXCERPT_CHARS);
+        assert!(short.ends_with('…'));
+    }
+}
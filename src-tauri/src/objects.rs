@@ -0,0 +1,192 @@
+// ==================== OBJECT TYPES ====================
+//
+// Objects under .truth/objects/ are zlib-compressed and normally JSON, but a
+// repo can also hold binary evidence or objects written by newer tools. The
+// type comes from an optional git-style header ("<type> <len>\0"), then the
+// JSON `$type` field, then the directory the object sits in (cl/, vf/).
+// Listings report objects they can't use as warnings instead of dropping them
+// with only a log line.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::truth_store::inflate_object;
+use crate::MAX_DECOMPRESSED_SIZE;
+
+/// Longest header accepted before the NUL ("verification 10485760")
+const MAX_HEADER_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectKind {
+    Claim,
+    Verification,
+    Blob,
+    Unknown,
+}
+
+impl ObjectKind {
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag.to_ascii_lowercase().as_str() {
+            "claim" | "cl" => Some(Self::Claim),
+            "verification" | "vf" => Some(Self::Verification),
+            "blob" => Some(Self::Blob),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Claim => "claim",
+            Self::Verification => "verification",
+            Self::Blob => "binary object",
+            Self::Unknown => "object of unknown type",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum StoredObject {
+    Claim(serde_json::Value),
+    Verification(serde_json::Value),
+    /// Not JSON, e.g. an evidence attachment
+    Blob {
+        size: usize,
+    },
+    /// JSON of a type the app doesn't know
+    Unknown(serde_json::Value),
+}
+
+impl StoredObject {
+    pub fn kind(&self) -> ObjectKind {
+        match self {
+            Self::Claim(_) => ObjectKind::Claim,
+            Self::Verification(_) => ObjectKind::Verification,
+            Self::Blob { .. } => ObjectKind::Blob,
+            Self::Unknown(_) => ObjectKind::Unknown,
+        }
+    }
+}
+
+/// An object a listing skipped, shown to the user alongside the results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectWarning {
+    /// Path under objects/, e.g. "cl/ab/cdef…"
+    pub object: String,
+    /// What the object turned out to be; None if it couldn't be read at all
+    pub kind: Option<ObjectKind>,
+    pub message: String,
+}
+
+/// Split a "<type> <len>\0" header off `bytes`. Objects without one (the
+/// usual case) come back whole with no tag.
+pub fn split_header(bytes: &[u8]) -> (Option<&str>, &[u8]) {
+    let Some(nul) = bytes.iter().take(MAX_HEADER_LEN).position(|&b| b == 0) else {
+        return (None, bytes);
+    };
+    let Ok(header) = std::str::from_utf8(&bytes[..nul]) else {
+        return (None, bytes);
+    };
+    match header.split_once(' ') {
+        Some((tag, len))
+            if !tag.is_empty()
+                && tag.chars().all(|c| c.is_ascii_alphabetic())
+                && !len.is_empty()
+                && len.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            (Some(tag), &bytes[nul + 1..])
+        }
+        _ => (None, bytes),
+    }
+}
+
+/// The kind implied by the directory an object sits in (objects/cl/ab/…)
+fn directory_kind(path: &Path) -> ObjectKind {
+    let dir = path.parent().and_then(Path::parent).and_then(Path::file_name);
+    dir.and_then(|d| ObjectKind::from_tag(&d.to_string_lossy()))
+        .filter(|k| *k != ObjectKind::Blob)
+        .unwrap_or(ObjectKind::Unknown)
+}
+
+/// Type inflated object bytes; `fallback` applies when neither a header nor
+/// a `$type` field names a known kind
+pub fn classify(bytes: &[u8], fallback: ObjectKind) -> StoredObject {
+    let (tag, body) = split_header(bytes);
+    let tagged = tag.map(|t| ObjectKind::from_tag(t).unwrap_or(ObjectKind::Unknown));
+    if tagged == Some(ObjectKind::Blob) {
+        return StoredObject::Blob { size: body.len() };
+    }
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return StoredObject::Blob { size: body.len() };
+    };
+    let declared = value
+        .get("$type")
+        .and_then(|t| t.as_str())
+        .and_then(ObjectKind::from_tag);
+    match tagged.or(declared).unwrap_or(fallback) {
+        ObjectKind::Claim => StoredObject::Claim(value),
+        ObjectKind::Verification => StoredObject::Verification(value),
+        ObjectKind::Blob | ObjectKind::Unknown => StoredObject::Unknown(value),
+    }
+}
+
+pub fn read_object(path: &Path) -> Result<StoredObject, crate::ObjectError> {
+    let bytes = inflate_object(path, MAX_DECOMPRESSED_SIZE)?;
+    Ok(classify(&bytes, directory_kind(path)))
+}
+
+/// Read an object that a listing expects to be of kind `expected`, turning
+/// anything else into a warning about `object` (its path under objects/)
+pub fn read_expected(path: &Path, object: &str, expected: ObjectKind) -> Result<serde_json::Value, ObjectWarning> {
+    let warning = |kind: Option<ObjectKind>, message: String| ObjectWarning {
+        object: object.to_string(),
+        kind,
+        message,
+    };
+    match read_object(path) {
+        Ok(StoredObject::Claim(value)) if expected == ObjectKind::Claim => Ok(value),
+        Ok(StoredObject::Verification(value)) if expected == ObjectKind::Verification => Ok(value),
+        Ok(StoredObject::Blob { size }) => Err(warning(
+            Some(ObjectKind::Blob),
+            format!("Binary object ({} bytes), expected a {}", size, expected.label()),
+        )),
+        Ok(other) => Err(warning(
+            Some(other.kind()),
+            format!("Found a {}, expected a {}", other.kind().label(), expected.label()),
+        )),
+        Err(e) => Err(warning(None, e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_header() {
+        assert_eq!(split_header(b"blob 3\0abc"), (Some("blob"), &b"abc"[..]));
+        assert_eq!(split_header(b"{\"a\":1}"), (None, &b"{\"a\":1}"[..]));
+        // A NUL inside binary data is not a header
+        assert_eq!(split_header(b"\x89PNG\0\x01").0, None);
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify(b"\x89PNG\r\n", ObjectKind::Claim),
+            StoredObject::Blob { size: 6 }
+        );
+        assert_eq!(
+            classify(b"blob 2\0{}", ObjectKind::Claim),
+            StoredObject::Blob { size: 2 }
+        );
+        assert_eq!(classify(b"{}", ObjectKind::Claim).kind(), ObjectKind::Claim);
+        assert_eq!(
+            classify(br#"{"$type": "verification"}"#, ObjectKind::Claim).kind(),
+            ObjectKind::Verification
+        );
+        assert_eq!(classify(b"claim 2\0{}", ObjectKind::Unknown).kind(), ObjectKind::Claim);
+        assert_eq!(classify(b"{}", ObjectKind::Unknown).kind(), ObjectKind::Unknown);
+    }
+}
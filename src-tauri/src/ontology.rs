@@ -0,0 +1,174 @@
+// ==================== ONTOLOGICAL TYPES ====================
+//
+// The kinds of statement the governance engine distinguishes, as returned in
+// `GovernanceResult.ontological_type`. Claims carry their type as
+// `ontological_type`, top level or under `metadata`; the claim index records
+// it so listings can filter by type and stats can group by it. Types outside
+// this registry are kept and reported as unregistered.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::{index, mock, repo_state, run_blocking};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct OntologicalType {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const ONTOLOGICAL_TYPES: &[OntologicalType] = &[
+    OntologicalType {
+        name: "empirical",
+        description: "Can be checked against observation, measurement or records",
+    },
+    OntologicalType {
+        name: "normative",
+        description: "Says what should be done or valued; a policy or value judgement",
+    },
+    OntologicalType {
+        name: "definitional",
+        description: "True by the meaning of its terms or by convention",
+    },
+    OntologicalType {
+        name: "speculative",
+        description: "About the future or otherwise not yet checkable",
+    },
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TypeStats {
+    pub name: String,
+    /// None for types outside the registry
+    pub description: Option<String>,
+    pub registered: bool,
+    pub claims: usize,
+    pub verified_claims: usize,
+    pub average_confidence: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OntologicalTypeStats {
+    pub types: Vec<TypeStats>,
+    /// Claims without an ontological type
+    pub untyped: usize,
+}
+
+pub fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// A claim's ontological type, normalized; None if it doesn't declare one
+pub fn claim_type(claim: &serde_json::Value) -> Option<String> {
+    claim
+        .get("ontological_type")
+        .or_else(|| claim.get("metadata").and_then(|m| m.get("ontological_type")))
+        .and_then(|t| t.as_str())
+        .map(normalize)
+        .filter(|t| !t.is_empty())
+}
+
+fn type_stats(claims: &[serde_json::Value], domain: Option<&str>) -> OntologicalTypeStats {
+    let mut stats: BTreeMap<String, (TypeStats, f64, usize)> = ONTOLOGICAL_TYPES
+        .iter()
+        .map(|t| {
+            let stats = TypeStats {
+                name: t.name.to_string(),
+                description: Some(t.description.to_string()),
+                registered: true,
+                ..Default::default()
+            };
+            (t.name.to_string(), (stats, 0.0, 0))
+        })
+        .collect();
+    let mut untyped = 0;
+
+    let in_domain = |claim: &serde_json::Value| {
+        domain.is_none_or(|d| claim.get("domain").and_then(|c| c.as_str()).map(normalize) == Some(normalize(d)))
+    };
+    for claim in claims.iter().filter(|c| in_domain(c)) {
+        let Some(name) = claim_type(claim) else {
+            untyped += 1;
+            continue;
+        };
+        let (stats, confidence_sum, confidence_count) = stats.entry(name.clone()).or_insert_with(|| {
+            let stats = TypeStats {
+                name,
+                ..Default::default()
+            };
+            (stats, 0.0, 0)
+        });
+        stats.claims += 1;
+        if claim.get("state").and_then(|s| s.as_str()) == Some("verified") {
+            stats.verified_claims += 1;
+        }
+        if let Some(confidence) = claim.get("confidence").and_then(|c| c.as_f64()) {
+            *confidence_sum += confidence;
+            *confidence_count += 1;
+        }
+    }
+
+    let types = stats
+        .into_values()
+        .map(|(mut stats, sum, count)| {
+            stats.average_confidence = (count > 0).then(|| sum / count as f64);
+            stats
+        })
+        .collect();
+    OntologicalTypeStats { types, untyped }
+}
+
+#[tauri::command]
+pub async fn list_ontological_types() -> Result<Vec<OntologicalType>, String> {
+    Ok(ONTOLOGICAL_TYPES.to_vec())
+}
+
+/// Claim counts per ontological type, optionally within one domain
+#[tauri::command]
+pub async fn get_ontological_type_stats(domain: Option<String>) -> Result<OntologicalTypeStats, String> {
+    run_blocking(move || {
+        let claims = if mock::is_active() {
+            mock::claims()
+        } else {
+            let truth_path = repo_state::ready_path()?;
+            index::list_claims(&truth_path, None).claims
+        };
+        Ok(type_stats(&claims, domain.as_deref()))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_type() {
+        assert_eq!(
+            claim_type(&serde_json::json!({ "ontological_type": " Empirical " })),
+            Some("empirical".to_string())
+        );
+        assert_eq!(
+            claim_type(&serde_json::json!({ "metadata": { "ontological_type": "normative" } })),
+            Some("normative".to_string())
+        );
+        assert_eq!(claim_type(&serde_json::json!({ "ontological_type": "" })), None);
+    }
+
+    #[test]
+    fn test_type_stats() {
+        let claims = vec![
+            serde_json::json!({ "domain": "science", "ontological_type": "empirical", "state": "verified" }),
+            serde_json::json!({ "domain": "science", "ontological_type": "mythical" }),
+            serde_json::json!({ "domain": "science" }),
+            serde_json::json!({ "domain": "legal", "ontological_type": "normative" }),
+        ];
+        let stats = type_stats(&claims, Some("Science"));
+        assert_eq!(stats.untyped, 1);
+        let empirical = stats.types.iter().find(|t| t.name == "empirical").unwrap();
+        assert_eq!((empirical.claims, empirical.verified_claims), (1, 1));
+        let mythical = stats.types.iter().find(|t| t.name == "mythical").unwrap();
+        assert!(!mythical.registered);
+        assert_eq!(stats.types.iter().find(|t| t.name == "normative").unwrap().claims, 0);
+    }
+}
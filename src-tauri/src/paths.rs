@@ -0,0 +1,128 @@
+// ==================== PATHS ====================
+//
+// Windows limits ordinary paths to MAX_PATH (260 characters), which deep
+// vault hierarchies and claim objects in a nested repo easily exceed, and
+// network shares (\\server\share) need their own form of the long-path
+// prefix. The vault and truth repo paths from settings go through
+// `long_path`, which gives them the `\\?\` (or `\\?\UNC\`) form that lifts
+// the limit; relative paths coming from the frontend are added with `join`,
+// and paths sent back use `relative_to` so they always use `/`.
+// `strip_verbatim` removes the prefix again from anything shown to the user.
+// On other platforms these leave paths as they are.
+
+use std::path::{Component, Path, PathBuf};
+
+const VERBATIM: &str = r"\\?\";
+const VERBATIM_UNC: &str = r"\\?\UNC\";
+const DEVICE: &str = r"\\.\";
+
+/// `path` in verbatim form: `C:\a` becomes `\\?\C:\a` and `\\server\share\a`
+/// becomes `\\?\UNC\server\share\a`. Windows passes verbatim paths to the
+/// file system as they are, so separators are normalized to `\` and `.` and
+/// `..` resolved here. None for relative paths and paths that already have a
+/// `\\?\` or `\\.\` prefix.
+fn to_verbatim(path: &str) -> Option<String> {
+    if path.starts_with(VERBATIM) || path.starts_with(DEVICE) {
+        return None;
+    }
+    let path = path.replace('/', "\\");
+    let (prefix, rest) = if let Some(unc) = path.strip_prefix(r"\\") {
+        let mut parts = unc.splitn(3, '\\');
+        let server = parts.next().filter(|s| !s.is_empty())?;
+        let share = parts.next().filter(|s| !s.is_empty())?;
+        (
+            format!(r"{}{}\{}", VERBATIM_UNC, server, share),
+            parts.next().unwrap_or(""),
+        )
+    } else {
+        let bytes = path.as_bytes();
+        if bytes.len() < 3 || !bytes[0].is_ascii_alphabetic() || bytes[1] != b':' || bytes[2] != b'\\' {
+            return None;
+        }
+        (format!("{}{}", VERBATIM, &path[..2]), &path[3..])
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in rest.split('\\') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(format!(r"{}\{}", prefix, parts.join("\\")))
+}
+
+/// `path` in a form that isn't limited to MAX_PATH on Windows
+pub fn long_path(path: PathBuf) -> PathBuf {
+    if !cfg!(windows) {
+        return path;
+    }
+    match path.to_str().and_then(to_verbatim) {
+        Some(verbatim) => PathBuf::from(verbatim),
+        None => path,
+    }
+}
+
+/// `text` with Windows long-path prefixes removed, for messages and anything
+/// else the user reads
+pub fn strip_verbatim(text: &str) -> String {
+    text.replace(VERBATIM_UNC, r"\\").replace(VERBATIM, "")
+}
+
+pub fn display_path(path: &Path) -> String {
+    strip_verbatim(&path.to_string_lossy())
+}
+
+/// `base` joined with a relative path from the frontend, which may use `/`
+/// or, on Windows, `\`. Callers validate `relative` first; `.` segments are
+/// skipped here because verbatim paths don't resolve them.
+pub fn join(base: &Path, relative: &str) -> PathBuf {
+    let separators: &[char] = if cfg!(windows) { &['/', '\\'] } else { &['/'] };
+    let mut path = base.to_path_buf();
+    for part in relative.split(separators).filter(|p| !p.is_empty() && *p != ".") {
+        path.push(part);
+    }
+    path
+}
+
+/// `path` relative to `base` with `/` separators, as the frontend expects
+pub fn relative_to(base: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    Some(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_verbatim() {
+        assert_eq!(to_verbatim(r"C:\Users\ana\vault").unwrap(), r"\\?\C:\Users\ana\vault");
+        assert_eq!(to_verbatim("d:/notes/./a/../b").unwrap(), r"\\?\d:\notes\b");
+        assert_eq!(to_verbatim(r"C:\").unwrap(), r"\\?\C:\");
+        assert_eq!(
+            to_verbatim(r"\\nas\team\project\.truth").unwrap(),
+            r"\\?\UNC\nas\team\project\.truth"
+        );
+        assert_eq!(to_verbatim("//nas/team").unwrap(), r"\\?\UNC\nas\team\");
+        assert_eq!(to_verbatim(r"\\?\C:\already"), None);
+        assert_eq!(to_verbatim(r"\\.\pipe\x"), None);
+        assert_eq!(to_verbatim(r"relative\dir"), None);
+        assert_eq!(to_verbatim(r"\\server"), None);
+
+        assert_eq!(
+            strip_verbatim(r"Failed to read \\?\UNC\nas\team\a and \\?\C:\b"),
+            r"Failed to read \\nas\team\a and C:\b"
+        );
+    }
+}
@@ -0,0 +1,624 @@
+// ==================== WASM PLUGINS ====================
+//
+// Third-party claim processors (extractors, exporters, data providers for
+// visualizations) ship as WebAssembly modules in plugins/<id>/ under the
+// config directory, next to a plugin.json manifest naming the permissions
+// they want. Modules run in wasmtime with no WASI: no files, network or
+// clock, a memory cap and a fuel budget per call. All they can reach is the
+// small host API below, and each call checks the permissions the user
+// granted when enabling the plugin, which may be fewer than requested.
+//
+// ABI: the module exports `memory`, `alloc(len) -> ptr` and
+// `run(ptr, len) -> i64`; `run` receives JSON and returns its JSON output as
+// `(ptr << 32) | len`. Host functions in the "truthgit" import module take
+// `(ptr, len)` of a UTF-8 argument:
+//   log(ptr, len)
+//   read_claims(query) -> i64      read_claims; query as for query_claims
+//   read_claim(hash) -> i64        read_claims
+//   write_annotation(json) -> i32  write_annotations; {claim_hash, value}
+//   emit_event(json) -> i32        emit_events; re-emitted as plugin://event
+// Results returned as i64 are packed the same way, in memory from `alloc`;
+// 0 means failure and -1 a missing permission. i32 results are 0 on success.
+// `.wasm` hooks (see hooks.rs) use the same ABI without any permission.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, OnceLock};
+use tauri::{AppHandle, Emitter};
+use wasmtime::{Caller, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::claim_filters::ClaimQuery;
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{read_claim, read_claims, run_blocking, sanitize_error};
+
+pub const PLUGIN_EVENT: &str = "plugin://event";
+
+const MANIFEST_FILE: &str = "plugin.json";
+/// Roughly a few seconds of work; a module that runs out is stopped
+const FUEL_PER_CALL: u64 = 2_000_000_000;
+const MAX_MEMORY: usize = 64 * 1024 * 1024;
+/// Largest argument or result passed across the boundary
+const MAX_TRANSFER: usize = 8 * 1024 * 1024;
+const MAX_ANNOTATION_SIZE: usize = 64 * 1024;
+const MAX_ID_LEN: usize = 64;
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+/// Serializes read-modify-write cycles on plugins.json and plugin_annotations.json
+static PLUGINS_LOCK: StoreLock = StoreLock::new();
+static ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).unwrap_or_default()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginPermission {
+    ReadClaims,
+    WriteAnnotations,
+    EmitEvents,
+}
+
+impl PluginPermission {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadClaims => "read_claims",
+            Self::WriteAnnotations => "write_annotations",
+            Self::EmitEvents => "emit_events",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginKind {
+    /// Finds candidate claims in text
+    Extractor,
+    /// Renders claims into another format
+    Exporter,
+    /// Computes data for a visualization
+    DataProvider,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Same as the plugin's directory name
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub kind: PluginKind,
+    /// What the plugin asks for; the user may grant less
+    #[serde(default)]
+    pub permissions: Vec<PluginPermission>,
+    /// Module file in the plugin directory
+    #[serde(default = "default_entry")]
+    pub entry: String,
+}
+
+fn default_entry() -> String {
+    "plugin.wasm".to_string()
+}
+
+/// What the user allowed, by plugin ID, in plugins.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PluginGrant {
+    enabled: bool,
+    permissions: Vec<PluginPermission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PluginInfo {
+    #[serde(flatten)]
+    pub manifest: PluginManifest,
+    pub enabled: bool,
+    pub granted: Vec<PluginPermission>,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PluginListing {
+    pub plugins: Vec<PluginInfo>,
+    /// Plugin directories that couldn't be loaded
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginAnnotation {
+    pub value: serde_json::Value,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PluginEvent {
+    plugin: String,
+    payload: serde_json::Value,
+}
+
+/// State a module's host calls can reach
+struct Host {
+    plugin: String,
+    granted: Vec<PluginPermission>,
+    limits: StoreLimits,
+}
+
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+fn plugins_dir() -> PathBuf {
+    config_path("plugins")
+}
+
+fn grants_path() -> PathBuf {
+    config_path("plugins.json")
+}
+
+fn annotations_path() -> PathBuf {
+    config_path("plugin_annotations.json")
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn load_manifest(dir: &Path) -> Result<PluginManifest, String> {
+    let content = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|e| sanitize_error(&format!("Can't read {}: {}", MANIFEST_FILE, e)))?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if !valid_id(&manifest.id) || manifest.id != dir_name {
+        return Err(format!(
+            "Plugin ID \"{}\" must match its directory name and use a-z, 0-9, - and _",
+            manifest.id
+        ));
+    }
+    let entry = Path::new(&manifest.entry);
+    if entry.components().count() != 1 || entry.extension().and_then(|e| e.to_str()) != Some("wasm") {
+        return Err(format!(
+            "Entry \"{}\" must be a .wasm file in the plugin directory",
+            manifest.entry
+        ));
+    }
+    Ok(manifest)
+}
+
+fn discover(dir: &Path, grants: &BTreeMap<String, PluginGrant>) -> PluginListing {
+    let mut listing = PluginListing::default();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return listing;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        match load_manifest(&dir) {
+            Ok(manifest) => {
+                let grant = grants.get(&manifest.id).cloned().unwrap_or_default();
+                listing.plugins.push(PluginInfo {
+                    path: dir.join(&manifest.entry).to_string_lossy().to_string(),
+                    manifest,
+                    enabled: grant.enabled,
+                    granted: grant.permissions,
+                });
+            }
+            Err(e) => {
+                let name = dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                log::warn!("Skipping plugin {}: {}", name, e);
+                listing.warnings.push(format!("{}: {}", name, e));
+            }
+        }
+    }
+    listing
+}
+
+fn find_plugin(id: &str) -> Result<PluginInfo, String> {
+    let grants: BTreeMap<String, PluginGrant> = load_json(&grants_path())?;
+    discover(&plugins_dir(), &grants)
+        .plugins
+        .into_iter()
+        .find(|p| p.manifest.id == id)
+        .ok_or_else(|| format!("Plugin not found: {}", id))
+}
+
+fn read_guest(caller: &mut Caller<'_, Host>, ptr: i32, len: i32) -> Option<Vec<u8>> {
+    let (ptr, len) = (usize::try_from(ptr).ok()?, usize::try_from(len).ok()?);
+    if len > MAX_TRANSFER {
+        return None;
+    }
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let mut buf = vec![0; len];
+    memory.read(&*caller, ptr, &mut buf).ok()?;
+    Some(buf)
+}
+
+fn pack(ptr: i32, len: usize) -> i64 {
+    ((ptr as u32 as i64) << 32) | len as i64
+}
+
+/// Copy `bytes` into memory from the module's `alloc`; 0 on failure
+fn write_guest(caller: &mut Caller<'_, Host>, bytes: &[u8]) -> i64 {
+    let write = |caller: &mut Caller<'_, Host>| -> Option<i64> {
+        let len = i32::try_from(bytes.len())
+            .ok()
+            .filter(|_| bytes.len() <= MAX_TRANSFER)?;
+        let alloc = caller
+            .get_export("alloc")?
+            .into_func()?
+            .typed::<i32, i32>(&*caller)
+            .ok()?;
+        let ptr = alloc.call(&mut *caller, len).ok()?;
+        let memory = caller.get_export("memory")?.into_memory()?;
+        memory.write(&mut *caller, usize::try_from(ptr).ok()?, bytes).ok()?;
+        Some(pack(ptr, bytes.len()))
+    };
+    write(caller).unwrap_or(0)
+}
+
+fn allowed(caller: &Caller<'_, Host>, permission: PluginPermission) -> bool {
+    caller.data().granted.contains(&permission)
+}
+
+fn query_claims(arg: &[u8]) -> Result<Vec<serde_json::Value>, String> {
+    let query: ClaimQuery = if arg.is_empty() {
+        ClaimQuery::default()
+    } else {
+        serde_json::from_slice(arg).map_err(|e| format!("Invalid query: {}", e))?
+    };
+    query.validate()?;
+    let now = chrono::Utc::now();
+    let mut claims = read_claims(None)?.claims;
+    claims.retain(|claim| query.matches(claim, now));
+    Ok(claims)
+}
+
+#[derive(Deserialize)]
+struct AnnotationRequest {
+    claim_hash: String,
+    value: serde_json::Value,
+}
+
+fn write_annotation(plugin: &str, arg: &[u8]) -> Result<(), String> {
+    if arg.len() > MAX_ANNOTATION_SIZE {
+        return Err("Annotation too large".to_string());
+    }
+    let request: AnnotationRequest = serde_json::from_slice(arg).map_err(|e| format!("Invalid annotation: {}", e))?;
+    let hash = request.claim_hash.trim().to_lowercase();
+    if !crate::truth_store::ObjectId::is_valid(&hash) {
+        return Err("Invalid claim hash".to_string());
+    }
+    let path = annotations_path();
+    let _guard = PLUGINS_LOCK.lock(&path)?;
+    let mut annotations: BTreeMap<String, BTreeMap<String, PluginAnnotation>> = load_json(&path)?;
+    let annotation = PluginAnnotation {
+        value: request.value,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    annotations
+        .entry(hash)
+        .or_default()
+        .insert(plugin.to_string(), annotation);
+    save_json(&path, &annotations)
+}
+
+fn linker() -> Result<Linker<Host>, String> {
+    let mut linker = Linker::new(&ENGINE);
+    let error = |e: wasmtime::Error| format!("Failed to set up the plugin host: {}", e);
+    linker
+        .func_wrap("truthgit", "log", |mut caller: Caller<'_, Host>, ptr: i32, len: i32| {
+            if let Some(bytes) = read_guest(&mut caller, ptr, len) {
+                log::info!("[plugin {}] {}", caller.data().plugin, String::from_utf8_lossy(&bytes));
+            }
+        })
+        .map_err(error)?;
+    linker
+        .func_wrap(
+            "truthgit",
+            "read_claims",
+            |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> i64 {
+                if !allowed(&caller, PluginPermission::ReadClaims) {
+                    return -1;
+                }
+                let claims = read_guest(&mut caller, ptr, len).map(|arg| query_claims(&arg));
+                match claims.and_then(|c| c.ok()).and_then(|c| serde_json::to_vec(&c).ok()) {
+                    Some(json) => write_guest(&mut caller, &json),
+                    None => 0,
+                }
+            },
+        )
+        .map_err(error)?;
+    linker
+        .func_wrap(
+            "truthgit",
+            "read_claim",
+            |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> i64 {
+                if !allowed(&caller, PluginPermission::ReadClaims) {
+                    return -1;
+                }
+                let hash = read_guest(&mut caller, ptr, len).map(|h| String::from_utf8_lossy(&h).trim().to_lowercase());
+                let claim = hash
+                    .filter(|h| crate::truth_store::ObjectId::is_valid(h))
+                    .and_then(|h| read_claim(h).ok());
+                match claim.and_then(|c| serde_json::to_vec(&c).ok()) {
+                    Some(json) => write_guest(&mut caller, &json),
+                    None => 0,
+                }
+            },
+        )
+        .map_err(error)?;
+    linker
+        .func_wrap(
+            "truthgit",
+            "write_annotation",
+            |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> i32 {
+                if !allowed(&caller, PluginPermission::WriteAnnotations) {
+                    return -1;
+                }
+                let Some(arg) = read_guest(&mut caller, ptr, len) else {
+                    return -2;
+                };
+                match write_annotation(&caller.data().plugin, &arg) {
+                    Ok(()) => 0,
+                    Err(e) => {
+                        log::warn!("[plugin {}] {}", caller.data().plugin, e);
+                        -2
+                    }
+                }
+            },
+        )
+        .map_err(error)?;
+    linker
+        .func_wrap(
+            "truthgit",
+            "emit_event",
+            |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> i32 {
+                if !allowed(&caller, PluginPermission::EmitEvents) {
+                    return -1;
+                }
+                let Some(payload) = read_guest(&mut caller, ptr, len).and_then(|a| serde_json::from_slice(&a).ok())
+                else {
+                    return -2;
+                };
+                if let Some(app) = APP.get() {
+                    let event = PluginEvent {
+                        plugin: caller.data().plugin.clone(),
+                        payload,
+                    };
+                    let _ = app.emit(PLUGIN_EVENT, event);
+                }
+                0
+            },
+        )
+        .map_err(error)?;
+    Ok(linker)
+}
+
+/// Run `module`'s `run` export on `input`, returning the bytes it produced
+fn call(module: &Module, plugin: &str, granted: Vec<PluginPermission>, input: &[u8]) -> Result<Vec<u8>, String> {
+    let host = Host {
+        plugin: plugin.to_string(),
+        granted,
+        limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY).instances(1).build(),
+    };
+    let mut store = Store::new(&ENGINE, host);
+    store.limiter(|host| &mut host.limits);
+    store
+        .set_fuel(FUEL_PER_CALL)
+        .map_err(|e| format!("Failed to set up plugin {}: {}", plugin, e))?;
+    let failed = |e: wasmtime::Error| {
+        let out_of_fuel = e.downcast_ref::<wasmtime::Trap>() == Some(&wasmtime::Trap::OutOfFuel);
+        if out_of_fuel {
+            format!("Plugin {} ran too long and was stopped", plugin)
+        } else {
+            format!("Plugin {} failed: {}", plugin, e)
+        }
+    };
+
+    let instance = linker()?.instantiate(&mut store, module).map_err(failed)?;
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or_else(|| format!("Plugin {} doesn't export its memory", plugin))?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut store, "alloc")
+        .map_err(failed)?;
+    let run = instance
+        .get_typed_func::<(i32, i32), i64>(&mut store, "run")
+        .map_err(failed)?;
+
+    let len = i32::try_from(input.len())
+        .ok()
+        .filter(|_| input.len() <= MAX_TRANSFER)
+        .ok_or_else(|| format!("Input for plugin {} is too large", plugin))?;
+    let ptr = alloc.call(&mut store, len).map_err(failed)?;
+    memory
+        .write(&mut store, ptr as u32 as usize, input)
+        .map_err(|e| format!("Plugin {} returned a bad buffer: {}", plugin, e))?;
+    let packed = run.call(&mut store, (ptr, len)).map_err(failed)? as u64;
+
+    let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize);
+    if out_len > MAX_TRANSFER {
+        return Err(format!("Plugin {} returned too much data", plugin));
+    }
+    let mut output = vec![0; out_len];
+    memory
+        .read(&store, out_ptr, &mut output)
+        .map_err(|e| format!("Plugin {} returned a bad buffer: {}", plugin, e))?;
+    Ok(output)
+}
+
+fn compile(path: &Path) -> Result<Module, String> {
+    Module::from_file(&ENGINE, path).map_err(|e| sanitize_error(&format!("Failed to load {}: {}", path.display(), e)))
+}
+
+/// Run a `.wasm` hook: same ABI as plugins, no host permissions
+pub fn run_hook_module(path: &Path, name: &str, input: &[u8]) -> Result<Vec<u8>, String> {
+    call(&compile(path)?, &format!("hook:{}", name), Vec::new(), input)
+}
+
+#[tauri::command]
+pub async fn list_plugins() -> Result<PluginListing, String> {
+    run_blocking(|| {
+        let grants: BTreeMap<String, PluginGrant> = load_json(&grants_path())?;
+        Ok(discover(&plugins_dir(), &grants))
+    })
+    .await
+}
+
+/// Enable or disable a plugin. `permissions` defaults to everything the
+/// manifest asks for and can't go beyond it.
+#[tauri::command]
+pub async fn set_plugin_enabled(
+    id: String,
+    enabled: bool,
+    permissions: Option<Vec<PluginPermission>>,
+) -> Result<PluginInfo, String> {
+    run_blocking(move || {
+        let mut plugin = find_plugin(&id)?;
+        let mut permissions = permissions.unwrap_or_else(|| plugin.manifest.permissions.clone());
+        if let Some(extra) = permissions.iter().find(|p| !plugin.manifest.permissions.contains(p)) {
+            return Err(format!("Plugin {} didn't ask for the {} permission", id, extra.as_str()));
+        }
+        permissions.sort();
+        permissions.dedup();
+
+        let path = grants_path();
+        let _guard = PLUGINS_LOCK.lock(&path)?;
+        let mut grants: BTreeMap<String, PluginGrant> = load_json(&path)?;
+        let grant = PluginGrant { enabled, permissions };
+        grants.insert(id.clone(), grant.clone());
+        save_json(&path, &grants)?;
+        log::info!(
+            "Plugin {} {} with {:?}",
+            id,
+            if enabled { "enabled" } else { "disabled" },
+            grant.permissions
+        );
+        plugin.enabled = grant.enabled;
+        plugin.granted = grant.permissions;
+        Ok(plugin)
+    })
+    .await
+}
+
+/// Run an enabled plugin on `input` and return its JSON output
+#[tauri::command]
+pub async fn run_plugin(id: String, input: serde_json::Value) -> Result<serde_json::Value, String> {
+    run_blocking(move || {
+        let plugin = find_plugin(&id)?;
+        if !plugin.enabled {
+            return Err(format!("Plugin {} is not enabled", id));
+        }
+        let input = serde_json::to_vec(&input).map_err(|e| format!("Failed to encode plugin input: {}", e))?;
+        let output = call(&compile(Path::new(&plugin.path))?, &id, plugin.granted, &input)?;
+        if output.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        serde_json::from_slice(&output).map_err(|e| format!("Plugin {} returned invalid JSON: {}", id, e))
+    })
+    .await
+}
+
+/// Annotations plugins wrote for a claim, by plugin ID
+#[tauri::command]
+pub async fn get_plugin_annotations(hash: String) -> Result<BTreeMap<String, PluginAnnotation>, String> {
+    run_blocking(move || {
+        let path = annotations_path();
+        let _guard = PLUGINS_LOCK.lock(&path)?;
+        let mut annotations: BTreeMap<String, BTreeMap<String, PluginAnnotation>> = load_json(&path)?;
+        Ok(annotations.remove(&hash.trim().to_lowercase()).unwrap_or_default())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    /// Emits its input as an event and echoes it back, or returns "denied"
+    /// when the host refuses
+    const ECHO: &str = r#"
+        (module
+          (import "truthgit" "emit_event" (func $emit (param i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "\"denied\"")
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "run") (param $ptr i32) (param $len i32) (result i64)
+            (if (result i64) (i32.eq (call $emit (local.get $ptr) (local.get $len)) (i32.const -1))
+              (then (i64.const 8))
+              (else (i64.or (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                            (i64.extend_i32_u (local.get $len)))))))
+    "#;
+
+    const SPIN: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "run") (param i32 i32) (result i64) (loop $l (br $l)) (i64.const 0)))
+    "#;
+
+    #[test]
+    fn test_call_checks_permissions_and_fuel() {
+        let echo = Module::new(&ENGINE, ECHO).unwrap();
+        let input = br#"{"claim":"x"}"#;
+        assert_eq!(call(&echo, "echo", Vec::new(), input).unwrap(), br#""denied""#);
+        assert_eq!(
+            call(&echo, "echo", vec![PluginPermission::EmitEvents], input).unwrap(),
+            input
+        );
+
+        let spin = Module::new(&ENGINE, SPIN).unwrap();
+        let error = call(&spin, "spin", Vec::new(), b"{}").unwrap_err();
+        assert!(error.contains("ran too long"), "{}", error);
+    }
+
+    #[test]
+    fn test_manifest_must_match_directory() {
+        let tmp = TempDir::new("truthgit-plugins");
+        let root = tmp.path();
+        let write = |dir: &str, manifest: serde_json::Value| {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join(MANIFEST_FILE), manifest.to_string()).unwrap();
+        };
+        let manifest = |id: &str, entry: &str| {
+            serde_json::json!({ "id": id, "name": "Word count", "version": "1.0.0", "kind": "data_provider",
+                "permissions": ["read_claims"], "entry": entry })
+        };
+        write("word-count", manifest("word-count", "plugin.wasm"));
+        write("renamed", manifest("word-count", "plugin.wasm"));
+        write("escape", manifest("escape", "../other/plugin.wasm"));
+
+        let grants = BTreeMap::from([(
+            "word-count".to_string(),
+            PluginGrant {
+                enabled: true,
+                permissions: vec![PluginPermission::ReadClaims],
+            },
+        )]);
+        let listing = discover(root, &grants);
+        assert_eq!(listing.plugins.len(), 1);
+        assert!(listing.plugins[0].enabled);
+        assert_eq!(listing.plugins[0].manifest.kind, PluginKind::DataProvider);
+        assert_eq!(listing.warnings.len(), 2);
+    }
+}
@@ -0,0 +1,298 @@
+// ==================== PROCESS METRICS ====================
+//
+// How the verification process is running, for the operations dashboard:
+// how long claims wait between creation and their first verification, how
+// often verifications escalate in each domain, and how many review steps
+// each reviewer takes. Worked out from the audit trail, with verification
+// objects filling in first verifications made outside the app and claim
+// objects giving creation times. A claim counts towards time-to-verify in
+// the range its first verification falls in.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::exports::field;
+use crate::index::created_at;
+use crate::snapshots::{instant, parse_moment};
+use crate::verification::Verification;
+use crate::{mock, read_audit_trail, read_claims, read_verifications, run_blocking, AuditEntry};
+
+pub const VERIFY_ACTION: &str = "governance_verify";
+const REVIEW_ACTION: &str = "review";
+
+/// Either end may be left out: from the first record, up to now
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MetricsRange {
+    /// YYYY-MM-DD (end of that day) or RFC 3339
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimeToVerify {
+    /// Claims first verified in the range
+    pub claims: usize,
+    pub median_hours: Option<f64>,
+    pub p90_hours: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainEscalations {
+    /// Empty for verifications without a domain
+    pub domain: String,
+    pub verifications: usize,
+    pub escalations: usize,
+    /// escalations / verifications, 0.0-1.0
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewerThroughput {
+    pub reviewer: String,
+    /// Review transitions made
+    pub reviews: usize,
+    pub approvals: usize,
+    pub per_week: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessMetrics {
+    /// The range measured, RFC 3339; `from` is None when nothing was recorded
+    pub from: Option<String>,
+    pub to: String,
+    pub verifications: usize,
+    pub escalations: usize,
+    pub escalation_rate: f64,
+    pub time_to_verify: TimeToVerify,
+    /// Highest rate first
+    pub domains: Vec<DomainEscalations>,
+    /// Busiest first
+    pub reviewers: Vec<ReviewerThroughput>,
+}
+
+fn rate(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Nearest-rank percentile of sorted `values`
+fn percentile(values: &[f64], p: f64) -> Option<f64> {
+    let rank = ((p * values.len() as f64).ceil() as usize).max(1);
+    values.get(rank - 1).copied()
+}
+
+fn compute(
+    claims: &[serde_json::Value],
+    verifications: &[Verification],
+    audit: &[AuditEntry],
+    from: Option<DateTime<Utc>>,
+    to: DateTime<Utc>,
+) -> ProcessMetrics {
+    let in_range = |at: DateTime<Utc>| from.is_none_or(|from| at > from) && at <= to;
+    let dated: Vec<(DateTime<Utc>, &AuditEntry)> = audit
+        .iter()
+        .filter_map(|e| Some((instant(&e.timestamp)?, e)))
+        .filter(|(at, _)| in_range(*at))
+        .collect();
+    // An open range starts at the first record in it
+    let start = from.or_else(|| dated.iter().map(|(at, _)| *at).min());
+
+    let mut escalations = 0;
+    let mut domains: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    let mut reviewers: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for (_, entry) in &dated {
+        match entry.action.as_str() {
+            VERIFY_ACTION => {
+                let escalated = entry.result_action == "escalate";
+                let counts = domains.entry(entry.domain.as_str()).or_default();
+                counts.0 += 1;
+                if escalated {
+                    counts.1 += 1;
+                    escalations += 1;
+                }
+            }
+            REVIEW_ACTION => {
+                let Some(actor) = &entry.actor else {
+                    continue;
+                };
+                let counts = reviewers.entry(actor.name.as_str()).or_default();
+                counts.0 += 1;
+                if entry.result_status == "approved" {
+                    counts.1 += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    let verification_count: usize = domains.values().map(|(n, _)| n).sum();
+
+    // First verification of each claim, from the audit trail and verification objects
+    let mut first: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    let audit_checks = audit
+        .iter()
+        .filter(|e| e.action == VERIFY_ACTION)
+        .filter_map(|e| Some((e.claim_hash.as_deref()?, e.timestamp.as_str())));
+    let object_checks = verifications
+        .iter()
+        .map(|v| (v.claim_hash.as_str(), v.timestamp.as_str()));
+    for (hash, at) in audit_checks.chain(object_checks) {
+        let Some(at) = instant(at).filter(|_| !hash.is_empty()) else {
+            continue;
+        };
+        let earliest = first.entry(hash).or_insert(at);
+        *earliest = (*earliest).min(at);
+    }
+    let mut hours: Vec<f64> = claims
+        .iter()
+        .filter_map(|claim| {
+            let created = instant(created_at(claim))?;
+            let verified = *first.get(field(claim, "$hash"))?;
+            (in_range(verified) && verified >= created).then(|| (verified - created).num_seconds() as f64 / 3600.0)
+        })
+        .collect();
+    hours.sort_by(f64::total_cmp);
+
+    let weeks = start.map_or(1.0, |from| {
+        ((to - from).num_seconds() as f64 / (7.0 * 86400.0)).max(1.0 / 7.0)
+    });
+    let mut domains: Vec<DomainEscalations> = domains
+        .into_iter()
+        .map(|(domain, (verifications, escalations))| DomainEscalations {
+            domain: domain.to_string(),
+            verifications,
+            escalations,
+            rate: rate(escalations, verifications),
+        })
+        .collect();
+    domains.sort_by(|a, b| b.rate.total_cmp(&a.rate).then(b.verifications.cmp(&a.verifications)));
+    let mut reviewers: Vec<ReviewerThroughput> = reviewers
+        .into_iter()
+        .map(|(reviewer, (reviews, approvals))| ReviewerThroughput {
+            reviewer: reviewer.to_string(),
+            reviews,
+            approvals,
+            per_week: reviews as f64 / weeks,
+        })
+        .collect();
+    reviewers.sort_by_key(|r| std::cmp::Reverse(r.reviews));
+
+    ProcessMetrics {
+        from: start.map(|t| t.to_rfc3339()),
+        to: to.to_rfc3339(),
+        verifications: verification_count,
+        escalations,
+        escalation_rate: rate(escalations, verification_count),
+        time_to_verify: TimeToVerify {
+            claims: hours.len(),
+            median_hours: percentile(&hours, 0.5),
+            p90_hours: percentile(&hours, 0.9),
+        },
+        domains,
+        reviewers,
+    }
+}
+
+/// Time-to-verify, escalation rates and reviewer throughput over `range`
+#[tauri::command]
+pub async fn get_process_metrics(range: Option<MetricsRange>) -> Result<ProcessMetrics, String> {
+    let range = range.unwrap_or_default();
+    let parse = |value: Option<String>| {
+        value
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| parse_moment(&v))
+            .transpose()
+    };
+    let from = parse(range.from)?;
+    let to = parse(range.to)?.unwrap_or_else(Utc::now);
+    if from.is_some_and(|from| from >= to) {
+        return Err("The range must start before it ends".to_string());
+    }
+    run_blocking(move || {
+        let claims = read_claims(None)?.claims;
+        let verifications = read_verifications()?.verifications;
+        let audit = if mock::is_active() {
+            Vec::new()
+        } else {
+            read_audit_trail()?
+        };
+        Ok(compute(&claims, &verifications, &audit, from, to))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::Actor;
+    use serde_json::json;
+
+    fn verify(domain: &str, action: &str, hash: &str, at: &str) -> AuditEntry {
+        AuditEntry {
+            action: VERIFY_ACTION.to_string(),
+            domain: domain.to_string(),
+            result_action: action.to_string(),
+            claim_hash: Some(hash.to_string()),
+            timestamp: at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn review(name: &str, to: &str, at: &str) -> AuditEntry {
+        AuditEntry {
+            action: REVIEW_ACTION.to_string(),
+            result_status: to.to_string(),
+            actor: Some(Actor {
+                name: name.to_string(),
+                email: None,
+                key_fingerprint: None,
+            }),
+            timestamp: at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_process_metrics() {
+        let claims = vec![
+            json!({"$hash": "aa", "metadata": {"created_at": "2026-03-01T00:00:00Z"}}),
+            json!({"$hash": "bb", "metadata": {"created_at": "2026-03-01T00:00:00Z"}}),
+            json!({"$hash": "cc", "metadata": {"created_at": "2026-03-01T00:00:00Z"}}),
+        ];
+        // cc was first verified by the CLI, before the app saw it
+        let verifications = [json!({"claim_hash": "cc", "verdict": "PASSED", "timestamp": "2026-03-01T02:00:00Z"})]
+            .map(Verification::from);
+        let audit = vec![
+            verify("legal", "escalate", "aa", "2026-03-01T10:00:00Z"),
+            verify("legal", "proceed", "aa", "2026-03-02T00:00:00Z"),
+            verify("physics", "proceed", "bb", "2026-03-02T00:00:00Z"),
+            verify("physics", "proceed", "cc", "2026-03-03T00:00:00Z"),
+            review("Ana", "in-review", "2026-03-04T00:00:00Z"),
+            review("Ana", "approved", "2026-03-05T00:00:00Z"),
+            review("Ben", "approved", "2026-03-05T00:00:00Z"),
+            verify("legal", "escalate", "aa", "2026-04-01T00:00:00Z"),
+        ];
+        let from = parse_moment("2026-02-28").unwrap();
+        let to = parse_moment("2026-03-14").unwrap();
+
+        let metrics = compute(&claims, &verifications, &audit, Some(from), to);
+        assert_eq!((metrics.verifications, metrics.escalations), (4, 1));
+        assert_eq!(metrics.time_to_verify.claims, 3);
+        assert_eq!(metrics.time_to_verify.median_hours, Some(10.0));
+        assert_eq!(metrics.time_to_verify.p90_hours, Some(24.0));
+        assert_eq!(
+            (metrics.domains[0].domain.as_str(), metrics.domains[0].rate),
+            ("legal", 0.5)
+        );
+        let ana = &metrics.reviewers[0];
+        assert_eq!((ana.reviewer.as_str(), ana.reviews, ana.approvals), ("Ana", 2, 1));
+        assert_eq!(ana.per_week, 1.0);
+
+        let open = compute(&claims, &verifications, &audit, None, to);
+        assert_eq!(open.from.as_deref(), Some("2026-03-01T10:00:00+00:00"));
+    }
+}
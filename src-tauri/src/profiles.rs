@@ -0,0 +1,202 @@
+// ==================== SETTINGS PROFILES ====================
+//
+// Named bundles of the repo-specific settings (vault, truth repo, API,
+// risk profile) so users juggling several repositories can switch with one
+// action. Stored in profiles.json next to settings.json.
+
+use serde::{Deserialize, Serialize};
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{commit_settings, consent, settings_events, AppSettings, SETTINGS};
+
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+/// Serializes read-modify-write cycles on profiles.json
+static PROFILES_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    pub vault_path: String,
+    pub truth_repo_path: String,
+    pub api_mode: String,
+    pub api_url: String,
+    pub default_risk_profile: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProfilesState {
+    pub active: Option<String>,
+    pub profiles: Vec<SettingsProfile>,
+}
+
+fn profiles_path() -> std::path::PathBuf {
+    config_path("profiles.json")
+}
+
+fn validate_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(format!("Profile name too long (max {} characters)", MAX_PROFILE_NAME_LEN));
+    }
+    Ok(name.to_string())
+}
+
+pub fn capture_profile(name: &str, settings: &AppSettings) -> SettingsProfile {
+    SettingsProfile {
+        name: name.to_string(),
+        vault_path: settings.vault_path.clone(),
+        truth_repo_path: settings.truth_repo_path.clone(),
+        api_mode: settings.api_mode.clone(),
+        api_url: settings.api_url.clone(),
+        default_risk_profile: settings.default_risk_profile.clone(),
+    }
+}
+
+/// Settings with the profile's fields applied; everything else is kept
+pub fn apply_profile(settings: &AppSettings, profile: &SettingsProfile) -> AppSettings {
+    AppSettings {
+        vault_path: profile.vault_path.clone(),
+        truth_repo_path: profile.truth_repo_path.clone(),
+        api_mode: profile.api_mode.clone(),
+        api_url: profile.api_url.clone(),
+        default_risk_profile: profile.default_risk_profile.clone(),
+        ..settings.clone()
+    }
+}
+
+pub fn load_profiles() -> Result<ProfilesState, String> {
+    let _guard = PROFILES_LOCK.lock(&profiles_path())?;
+    load_json(&profiles_path())
+}
+
+#[tauri::command]
+pub async fn list_profiles() -> Result<ProfilesState, String> {
+    load_profiles()
+}
+
+/// Add or replace profiles by name, leaving the active profile unchanged.
+/// Returns the number of profiles written.
+pub fn merge_profiles(imported: Vec<SettingsProfile>) -> Result<usize, String> {
+    let _guard = PROFILES_LOCK.lock(&profiles_path())?;
+    let mut state: ProfilesState = load_json(&profiles_path())?;
+    let count = imported.len();
+    for profile in imported {
+        let name = validate_profile_name(&profile.name)?;
+        let profile = SettingsProfile { name, ..profile };
+        match state.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => state.profiles.push(profile),
+        }
+    }
+    save_json(&profiles_path(), &state)?;
+    Ok(count)
+}
+
+/// Save the current settings as a named profile (overwrites an existing one)
+#[tauri::command]
+pub async fn save_profile(name: String) -> Result<ProfilesState, String> {
+    let name = validate_profile_name(&name)?;
+    let profile = {
+        let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+        capture_profile(&name, &settings)
+    };
+
+    let _guard = PROFILES_LOCK.lock(&profiles_path())?;
+    let mut state: ProfilesState = load_json(&profiles_path())?;
+    match state.profiles.iter_mut().find(|p| p.name == name) {
+        Some(existing) => *existing = profile,
+        None => state.profiles.push(profile),
+    }
+    state.active = Some(name);
+    save_json(&profiles_path(), &state)?;
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn delete_profile(name: String) -> Result<ProfilesState, String> {
+    let _guard = PROFILES_LOCK.lock(&profiles_path())?;
+    let mut state: ProfilesState = load_json(&profiles_path())?;
+    let before = state.profiles.len();
+    state.profiles.retain(|p| p.name != name);
+    if state.profiles.len() == before {
+        return Err(format!("Profile not found: {}", name));
+    }
+    if state.active.as_deref() == Some(name.as_str()) {
+        state.active = None;
+    }
+    save_json(&profiles_path(), &state)?;
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn switch_profile(app: tauri::AppHandle, name: String) -> Result<AppSettings, String> {
+    let find = |state: &ProfilesState| {
+        state
+            .profiles
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+            .ok_or_else(|| format!("Profile not found: {}", name))
+    };
+    let preview = {
+        let profile = find(&load_profiles()?)?;
+        let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+        apply_profile(&settings, &profile)
+    };
+    consent::require_path_settings(&preview).await?;
+
+    let _guard = PROFILES_LOCK.lock(&profiles_path())?;
+    let mut state: ProfilesState = load_json(&profiles_path())?;
+    let profile = find(&state)?;
+
+    let updated = {
+        let mut settings = SETTINGS.write().map_err(|e| format!("Lock error: {}", e))?;
+        let new_settings = apply_profile(&settings, &profile);
+        commit_settings(&mut settings, new_settings)?;
+        settings.clone()
+    };
+
+    state.active = Some(profile.name);
+    save_json(&profiles_path(), &state)?;
+
+    settings_events::emit_changed(&app, "profile", &updated);
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_round_trip_through_settings() {
+        let settings = AppSettings {
+            vault_path: "/work/vault".to_string(),
+            truth_repo_path: "/work/.truth".to_string(),
+            default_risk_profile: "high".to_string(),
+            ..AppSettings::default()
+        };
+        let profile = capture_profile("work", &settings);
+
+        let other = AppSettings {
+            terminal_font_size: 20,
+            ..AppSettings::default()
+        };
+        let applied = apply_profile(&other, &profile);
+        assert_eq!(applied.vault_path, "/work/vault");
+        assert_eq!(applied.truth_repo_path, "/work/.truth");
+        assert_eq!(applied.default_risk_profile, "high");
+        // Fields outside the profile are untouched
+        assert_eq!(applied.terminal_font_size, 20);
+    }
+
+    #[test]
+    fn test_profile_name_validation() {
+        assert!(validate_profile_name("   ").is_err());
+        assert!(validate_profile_name(&"x".repeat(MAX_PROFILE_NAME_LEN + 1)).is_err());
+        assert_eq!(validate_profile_name("  client A ").unwrap(), "client A");
+    }
+}
@@ -0,0 +1,222 @@
+// ==================== QUICK CAPTURE ====================
+//
+// Drops a statement into an inbox note in the vault to verify later, without
+// opening the app: each capture is appended as a timestamped list item to
+// the note configured under `quick_capture` in settings (created if it
+// doesn't exist yet; its folder must). With claim extraction on, candidate
+// claims in the captured text are sent to the frontend as an
+// `intake://documents` event, so they go through the same review as dropped
+// files. The tray's "Capture Clipboard Text" item and the quick_capture
+// shortcut capture the clipboard. Encrypted inbox notes aren't written to.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::file_intake::{self, INTAKE_EVENT};
+use crate::json_store::write_atomic;
+use crate::{
+    get_vault_path, notifications, paths, run_blocking, sanitize_error, tray, validate_path_within_base, vault_crypto,
+    SETTINGS,
+};
+
+const MAX_CAPTURE_LEN: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuickCaptureSettings {
+    /// Markdown note captures are appended to, relative to the vault
+    pub inbox_note: String,
+    /// Offer the claims found in captured text for verification
+    pub extract_claims: bool,
+}
+
+impl Default for QuickCaptureSettings {
+    fn default() -> Self {
+        Self {
+            inbox_note: "Inbox.md".to_string(),
+            extract_claims: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Capture {
+    /// The note written to, relative to the vault
+    pub path: String,
+    /// The lines appended
+    pub entry: String,
+    /// Candidate claims offered for verification
+    pub claims: Vec<String>,
+}
+
+/// A list item stamped with `at`; further lines are indented under it
+fn format_entry(text: &str, at: &str) -> String {
+    let mut lines = text.trim().lines().map(str::trim_end);
+    let mut entry = format!("- {} — {}", at, lines.next().unwrap_or_default());
+    for line in lines {
+        entry.push('\n');
+        if !line.is_empty() {
+            entry.push_str("  ");
+            entry.push_str(line);
+        }
+    }
+    entry
+}
+
+fn append_entry(content: &str, entry: &str) -> String {
+    let mut out = content.trim_end().to_string();
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(entry);
+    out.push('\n');
+    out
+}
+
+/// The inbox note's path; its folder must exist inside the vault
+fn inbox_path(vault: &PathBuf, note: &str) -> Result<PathBuf, String> {
+    let note = note.trim().trim_start_matches("./");
+    if !note.ends_with(".md") {
+        return Err("The inbox note must be a markdown (.md) note".to_string());
+    }
+    let relative = Path::new(note);
+    let file_name = relative.file_name().ok_or("The inbox note needs a file name")?;
+    let folder = relative
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = validate_path_within_base(vault, &folder)?.join(file_name);
+    if path.exists() {
+        return validate_path_within_base(vault, note);
+    }
+    Ok(path)
+}
+
+fn capture(vault: &PathBuf, note: &str, text: &str, extract: bool) -> Result<(Capture, Option<PathBuf>), String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Nothing to capture".to_string());
+    }
+    if text.len() > MAX_CAPTURE_LEN {
+        return Err("The text is too long to capture".to_string());
+    }
+    let path = inbox_path(vault, note)?;
+    if vault_crypto::is_encrypted(&path) {
+        return Err("The inbox note is encrypted; choose another note".to_string());
+    }
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(sanitize_error(&format!("Failed to read inbox note: {}", e))),
+    };
+    let entry = format_entry(text, &Local::now().format("%Y-%m-%d %H:%M").to_string());
+    write_atomic(&path, append_entry(&content, &entry).as_bytes())
+        .map_err(|e| sanitize_error(&format!("Failed to write inbox note: {}", e)))?;
+
+    let claims = if extract {
+        file_intake::extract_claims(text)
+    } else {
+        Vec::new()
+    };
+    let capture = Capture {
+        path: paths::relative_to(vault, &path).unwrap_or_else(|| note.to_string()),
+        entry,
+        claims,
+    };
+    Ok((capture, extract.then_some(path)))
+}
+
+/// Append `text` to the inbox note, or to `target` (relative to the vault)
+/// instead; `extract` overrides the claim extraction setting
+#[tauri::command]
+pub async fn quick_capture(
+    app: AppHandle,
+    text: String,
+    target: Option<String>,
+    extract: Option<bool>,
+) -> Result<Capture, String> {
+    let settings = SETTINGS
+        .read()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .quick_capture
+        .clone();
+    let note = target.filter(|t| !t.trim().is_empty()).unwrap_or(settings.inbox_note);
+    let extract = extract.unwrap_or(settings.extract_claims);
+    let captured = text.clone();
+    let (capture, note_path) = run_blocking(move || {
+        let vault = get_vault_path().ok_or("Could not find home directory")?;
+        capture(&vault, &note, &captured, extract)
+    })
+    .await?;
+    log::info!("Captured {} characters to {}", text.trim().len(), capture.path);
+
+    if let Some(path) = note_path.filter(|_| !capture.claims.is_empty()) {
+        let document = file_intake::captured_document(&path, &text);
+        if let Err(e) = app.emit(INTAKE_EVENT, vec![document]) {
+            log::warn!("Failed to emit {}: {}", INTAKE_EVENT, e);
+        }
+    }
+    Ok(capture)
+}
+
+/// Capture the clipboard in the background, reporting via notification and
+/// showing the window when there are claims to review
+pub fn spawn_capture(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let text = match app.clipboard().read_text() {
+            Ok(text) => text,
+            Err(e) => {
+                notifications::show("Nothing to capture", &format!("Failed to read clipboard: {}", e));
+                return;
+            }
+        };
+        match quick_capture(app.clone(), text, None, None).await {
+            Ok(capture) => {
+                notifications::show(
+                    &format!("Captured to {}", capture.path),
+                    &notifications::excerpt(&capture.entry),
+                );
+                if !capture.claims.is_empty() {
+                    tray::show_main_window(&app);
+                }
+            }
+            Err(e) => notifications::show("Capture failed", &e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_capture_appends_entries() {
+        let entry = format_entry("  Water boils at 100°C.\n\nAt sea level.  \n", "2026-10-16 09:30");
+        assert_eq!(entry, "- 2026-10-16 09:30 — Water boils at 100°C.\n\n  At sea level.");
+        assert_eq!(append_entry("", "- a"), "- a\n");
+        assert_eq!(append_entry("# Inbox\n\n- a\n\n", "- b"), "# Inbox\n\n- a\n- b\n");
+
+        let tmp = TempDir::new("truthgit-capture");
+        let vault = tmp.path().to_path_buf();
+        fs::create_dir_all(vault.join("Inbox")).unwrap();
+        let text = "The Eiffel Tower is 330 metres tall.";
+        let (first, path) = capture(&vault, "Inbox/Claims.md", text, true).unwrap();
+        assert_eq!(first.path, "Inbox/Claims.md");
+        assert_eq!(first.claims, vec![text.to_string()]);
+        assert!(path.is_some());
+        let (second, path) = capture(&vault, "Inbox/Claims.md", "Check this later", false).unwrap();
+        assert!(second.claims.is_empty() && path.is_none());
+        let content = fs::read_to_string(vault.join("Inbox/Claims.md")).unwrap();
+        assert_eq!(content.lines().count(), 2);
+
+        assert!(capture(&vault, "Missing/Claims.md", text, false).is_err());
+        assert!(capture(&vault, "../Claims.md", text, false).is_err());
+        assert!(capture(&vault, "Claims.txt", text, false).is_err());
+    }
+}
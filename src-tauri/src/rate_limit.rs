@@ -0,0 +1,213 @@
+// ==================== RATE LIMITS ====================
+//
+// Tracks the quota each remote reports in its rate-limit headers so batch
+// work (batch verification, sync transfers) slows down as the quota runs
+// low instead of failing halfway through. Both the common `X-RateLimit-*`
+// headers and the IETF `RateLimit-*` draft headers are understood, plus
+// `Retry-After` on 429 responses.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+
+use crate::remotes::resolve_remote_url;
+
+/// Start spreading requests out once this fraction of the quota is left
+const LOW_QUOTA_FRACTION: f64 = 0.1;
+const MIN_LOW_QUOTA: u64 = 5;
+
+/// Waiting longer than this is worse than failing with a clear error
+const MAX_PACE_WAIT: Duration = Duration::from_secs(300);
+
+/// Returned when the API answers 429, so batch callers can retry
+pub const RATE_LIMITED_ERROR: &str = "TruthGit API rate limit reached";
+
+/// Reset values above this are Unix timestamps rather than seconds from now
+const EPOCH_THRESHOLD: i64 = 1_000_000_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiQuota {
+    pub remote: String,
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// RFC 3339 time the quota resets
+    pub reset_at: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct QuotaState {
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    reset_at: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+}
+
+static QUOTAS: std::sync::LazyLock<RwLock<HashMap<String, QuotaState>>> =
+    std::sync::LazyLock::new(|| RwLock::new(HashMap::new()));
+
+fn header_i64(headers: &HeaderMap, names: &[&str]) -> Option<i64> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn parse_quota(headers: &HeaderMap, rate_limited: bool, now: DateTime<Utc>) -> Option<QuotaState> {
+    let limit = header_i64(headers, &["x-ratelimit-limit", "ratelimit-limit"]).and_then(|v| u64::try_from(v).ok());
+    let mut remaining =
+        header_i64(headers, &["x-ratelimit-remaining", "ratelimit-remaining"]).and_then(|v| u64::try_from(v).ok());
+    let mut reset_at = header_i64(headers, &["x-ratelimit-reset", "ratelimit-reset"]).and_then(|reset| {
+        if reset > EPOCH_THRESHOLD {
+            DateTime::from_timestamp(reset, 0)
+        } else {
+            Some(now + chrono::Duration::seconds(reset.max(0)))
+        }
+    });
+
+    if rate_limited {
+        remaining = Some(0);
+        if let Some(retry_after) = header_i64(headers, &["retry-after"]) {
+            reset_at = Some(now + chrono::Duration::seconds(retry_after.max(0)));
+        }
+    }
+
+    if limit.is_none() && remaining.is_none() && reset_at.is_none() {
+        return None;
+    }
+    Some(QuotaState {
+        limit,
+        remaining,
+        reset_at,
+        updated_at: now,
+    })
+}
+
+/// Record the quota reported by a response from `remote`
+pub fn record(remote: &str, response: &reqwest::Response) {
+    let rate_limited = response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    if let Some(state) = parse_quota(response.headers(), rate_limited, Utc::now()) {
+        if let Ok(mut quotas) = QUOTAS.write() {
+            quotas.insert(remote.to_string(), state);
+        }
+    }
+}
+
+/// How long to wait before the next request so the quota lasts until reset
+fn pacing_delay(state: &QuotaState, now: DateTime<Utc>) -> Duration {
+    let (Some(remaining), Some(reset_at)) = (state.remaining, state.reset_at) else {
+        return Duration::ZERO;
+    };
+    let Ok(until_reset) = (reset_at - now).to_std() else {
+        return Duration::ZERO;
+    };
+    if remaining == 0 {
+        return until_reset;
+    }
+    let low = state
+        .limit
+        .map(|limit| ((limit as f64 * LOW_QUOTA_FRACTION) as u64).max(MIN_LOW_QUOTA))
+        .unwrap_or(MIN_LOW_QUOTA);
+    if remaining > low {
+        return Duration::ZERO;
+    }
+    until_reset.div_f64((remaining + 1) as f64)
+}
+
+/// Wait as needed before sending a request to `remote`
+pub async fn pace(remote: &str) -> Result<(), String> {
+    let state = QUOTAS.read().ok().and_then(|q| q.get(remote).copied());
+    let Some(state) = state else {
+        return Ok(());
+    };
+    let delay = pacing_delay(&state, Utc::now());
+    if delay > MAX_PACE_WAIT {
+        let reset = state.reset_at.map(|r| r.to_rfc3339()).unwrap_or_default();
+        return Err(format!("API rate limit exhausted; quota resets at {}", reset));
+    }
+    if !delay.is_zero() {
+        log::info!("Pacing requests to {} for {:?} (rate limit)", remote, delay);
+        tokio::time::sleep(delay).await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_api_quota(remote: Option<String>) -> Result<Option<ApiQuota>, String> {
+    let remote = resolve_remote_url(remote, "pull")?;
+    let quotas = QUOTAS.read().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(quotas.get(&remote).map(|state| ApiQuota {
+        remote: remote.clone(),
+        limit: state.limit,
+        remaining: state.remaining,
+        reset_at: state.reset_at.map(|r| r.to_rfc3339()),
+        updated_at: state.updated_at.to_rfc3339(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_quota_headers() {
+        let now = Utc::now();
+        let state = parse_quota(
+            &headers(&[("x-ratelimit-limit", "100"), ("x-ratelimit-remaining", "42"), ("x-ratelimit-reset", "30")]),
+            false,
+            now,
+        )
+        .unwrap();
+        assert_eq!(state.limit, Some(100));
+        assert_eq!(state.remaining, Some(42));
+        assert_eq!(state.reset_at, Some(now + chrono::Duration::seconds(30)));
+
+        // Epoch reset and IETF header names
+        let state = parse_quota(&headers(&[("ratelimit-remaining", "1"), ("ratelimit-reset", "1900000000")]), false, now)
+            .unwrap();
+        assert_eq!(state.reset_at, DateTime::from_timestamp(1_900_000_000, 0));
+
+        assert!(parse_quota(&HeaderMap::new(), false, now).is_none());
+    }
+
+    #[test]
+    fn test_429_uses_retry_after() {
+        let now = Utc::now();
+        let state = parse_quota(&headers(&[("retry-after", "12")]), true, now).unwrap();
+        assert_eq!(state.remaining, Some(0));
+        assert_eq!(pacing_delay(&state, now), Duration::from_secs(12));
+    }
+
+    #[test]
+    fn test_pacing_only_when_quota_is_low() {
+        let now = Utc::now();
+        let mut state = QuotaState {
+            limit: Some(100),
+            remaining: Some(50),
+            reset_at: Some(now + chrono::Duration::seconds(60)),
+            updated_at: now,
+        };
+        assert_eq!(pacing_delay(&state, now), Duration::ZERO);
+
+        state.remaining = Some(5);
+        assert_eq!(pacing_delay(&state, now), Duration::from_secs(10));
+
+        // A reset in the past means the quota has been replenished
+        state.remaining = Some(0);
+        state.reset_at = Some(now - chrono::Duration::seconds(1));
+        assert_eq!(pacing_delay(&state, now), Duration::ZERO);
+    }
+}
@@ -0,0 +1,287 @@
+// ==================== VERIFICATION RECEIPTS ====================
+//
+// A receipt is a signed summary of one audit entry (claim hash, verdict,
+// confidence, timestamp) that can be pasted into a ticket or email, or shown
+// as a QR code, and checked by anyone with the app. Receipts are signed with
+// an Ed25519 key kept in the config directory and generated on first use; the
+// public key travels inside the receipt, so verification proves the receipt
+// is intact and names the signer by key fingerprint.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::json_store::config_path;
+use crate::security_events::{self, SecurityEventKind};
+use crate::{identity, read_audit_trail, run_blocking, AuditEntry};
+
+/// Prefix of the compact text form, also the format version
+const RECEIPT_PREFIX: &str = "tgr1.";
+/// Receipts longer than this are not ours; also keeps the QR code scannable
+const MAX_RECEIPT_LEN: usize = 2048;
+
+/// The signed part of a receipt. Field order is the signing order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptBody {
+    pub audit_id: String,
+    /// SHA-256 of the claim text, hex
+    pub claim_hash: String,
+    pub domain: String,
+    /// Governance status, e.g. "PASSED"
+    pub verdict: String,
+    pub action: String,
+    pub confidence: f64,
+    /// When the verification ran
+    pub timestamp: String,
+    pub issued_at: String,
+    /// Display name of whoever issued the receipt
+    #[serde(default)]
+    pub signer: Option<String>,
+    pub signer_fingerprint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Receipt {
+    #[serde(flatten)]
+    pub body: ReceiptBody,
+    /// Ed25519 public key, base64url
+    pub public_key: String,
+    /// Ed25519 signature over the JSON of `body`, base64url
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedReceipt {
+    pub receipt: Receipt,
+    /// "tgr1.<base64url JSON>", for pasting or QR codes
+    pub encoded: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReceiptCheck {
+    pub valid: bool,
+    /// The receipt as read, even when the signature doesn't match
+    pub receipt: Receipt,
+    /// Signed with this desktop's own receipt key
+    pub signed_by_me: bool,
+    pub message: String,
+}
+
+fn key_path() -> PathBuf {
+    config_path("receipt.key")
+}
+
+fn write_key(key: &SigningKey) -> Result<(), String> {
+    let path = key_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .map_err(|e| format!("Failed to write receipt key: {}", e))?;
+    let encoded = URL_SAFE_NO_PAD.encode(key.to_bytes());
+    std::io::Write::write_all(&mut file, encoded.as_bytes()).map_err(|e| format!("Failed to write receipt key: {}", e))
+}
+
+/// The receipt key, or None if this desktop hasn't signed a receipt yet
+fn read_key() -> Result<Option<SigningKey>, String> {
+    let encoded = match fs::read_to_string(key_path()) {
+        Ok(encoded) => encoded,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read receipt key: {}", e)),
+    };
+    let seed: [u8; 32] = URL_SAFE_NO_PAD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Receipt key file is corrupt")?;
+    Ok(Some(SigningKey::from_bytes(&seed)))
+}
+
+pub(crate) fn load_or_create_key() -> Result<SigningKey, String> {
+    if let Some(key) = read_key()? {
+        return Ok(key);
+    }
+    let key = SigningKey::generate(&mut rand_core::OsRng);
+    write_key(&key)?;
+    log::info!("Created receipt signing key");
+    Ok(key)
+}
+
+/// Fingerprint of this desktop's receipt key, if it has one
+pub(crate) fn own_fingerprint() -> Result<Option<String>, String> {
+    Ok(read_key()?.map(|k| identity::fingerprint(k.verifying_key().as_bytes())))
+}
+
+fn signing_bytes(body: &ReceiptBody) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(body).map_err(|e| format!("Failed to encode receipt: {}", e))
+}
+
+fn sign(entry: &AuditEntry, key: &SigningKey, signer: Option<String>) -> Result<Receipt, String> {
+    let public_key = key.verifying_key().to_bytes();
+    let body = ReceiptBody {
+        audit_id: entry.id.clone(),
+        claim_hash: format!("{:x}", Sha256::digest(entry.claim.as_bytes())),
+        domain: entry.domain.clone(),
+        verdict: entry.result_status.clone(),
+        action: entry.result_action.clone(),
+        confidence: entry.confidence,
+        timestamp: entry.timestamp.clone(),
+        issued_at: chrono::Utc::now().to_rfc3339(),
+        signer,
+        signer_fingerprint: identity::fingerprint(&public_key),
+    };
+    let signature = key.sign(&signing_bytes(&body)?);
+    Ok(Receipt {
+        body,
+        public_key: URL_SAFE_NO_PAD.encode(public_key),
+        signature: URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+    })
+}
+
+fn encode(receipt: &Receipt) -> Result<String, String> {
+    let json = serde_json::to_vec(receipt).map_err(|e| format!("Failed to encode receipt: {}", e))?;
+    Ok(format!("{}{}", RECEIPT_PREFIX, URL_SAFE_NO_PAD.encode(json)))
+}
+
+/// Parse either the compact "tgr1." form or the receipt's JSON
+fn parse(text: &str) -> Result<Receipt, String> {
+    let text = text.trim();
+    if text.len() > MAX_RECEIPT_LEN * 2 {
+        return Err("Receipt is too long".to_string());
+    }
+    let json = match text.strip_prefix(RECEIPT_PREFIX) {
+        Some(encoded) => URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| "Receipt is not valid base64".to_string())?,
+        None if text.starts_with('{') => text.as_bytes().to_vec(),
+        None => return Err("Not a TruthGit receipt".to_string()),
+    };
+    serde_json::from_slice(&json).map_err(|e| format!("Receipt is malformed: {}", e))
+}
+
+/// Check the signature and that the fingerprint belongs to the embedded key
+fn check_signature(receipt: &Receipt) -> Result<(), String> {
+    let public_key: [u8; 32] = URL_SAFE_NO_PAD
+        .decode(&receipt.public_key)
+        .ok()
+        .and_then(|k| k.try_into().ok())
+        .ok_or("Receipt public key is malformed")?;
+    let signature: [u8; 64] = URL_SAFE_NO_PAD
+        .decode(&receipt.signature)
+        .ok()
+        .and_then(|s| s.try_into().ok())
+        .ok_or("Receipt signature is malformed")?;
+    if identity::fingerprint(&public_key) != receipt.body.signer_fingerprint {
+        return Err("Signer fingerprint doesn't match the receipt's key".to_string());
+    }
+    let key = VerifyingKey::from_bytes(&public_key).map_err(|_| "Receipt public key is invalid".to_string())?;
+    key.verify(&signing_bytes(&receipt.body)?, &Signature::from_bytes(&signature))
+        .map_err(|_| "Signature doesn't match: the receipt was altered or signed with another key".to_string())
+}
+
+/// Sign a receipt for an audit trail entry
+#[tauri::command]
+pub async fn generate_receipt(audit_id: String) -> Result<GeneratedReceipt, String> {
+    run_blocking(move || {
+        let entry = read_audit_trail()?
+            .into_iter()
+            .find(|e| e.id == audit_id)
+            .ok_or_else(|| format!("Audit entry not found: {}", audit_id))?;
+        let key = load_or_create_key()?;
+        let receipt = sign(&entry, &key, identity::current().map(|a| a.name))?;
+        let encoded = encode(&receipt)?;
+        if encoded.len() > MAX_RECEIPT_LEN {
+            return Err("Receipt is too large to share; shorten the domain or display name".to_string());
+        }
+        Ok(GeneratedReceipt { receipt, encoded })
+    })
+    .await
+}
+
+/// Check a receipt pasted as "tgr1.…" or JSON. Invalid signatures are
+/// reported in the result rather than as an error.
+#[tauri::command]
+pub async fn verify_receipt(receipt: String) -> Result<ReceiptCheck, String> {
+    run_blocking(move || {
+        let receipt = parse(&receipt)?;
+        let own_fingerprint = own_fingerprint()?;
+        let (valid, message) = match check_signature(&receipt) {
+            Ok(()) => (
+                true,
+                format!("Valid receipt signed by {}", receipt.body.signer_fingerprint),
+            ),
+            Err(e) => {
+                security_events::record(SecurityEventKind::SignatureFailure, "verify_receipt", &e);
+                (false, e)
+            }
+        };
+        Ok(ReceiptCheck {
+            valid,
+            signed_by_me: valid && own_fingerprint.as_deref() == Some(receipt.body.signer_fingerprint.as_str()),
+            receipt,
+            message,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> AuditEntry {
+        AuditEntry {
+            id: "audit-1".to_string(),
+            timestamp: "2026-03-01T12:00:00Z".to_string(),
+            action: "governance_verify".to_string(),
+            claim: "Water boils at 100°C at sea level".to_string(),
+            domain: "science".to_string(),
+            risk_profile: "medium".to_string(),
+            result_status: "PASSED".to_string(),
+            result_action: "proceed".to_string(),
+            confidence: 0.874,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_receipt_round_trip() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let receipt = sign(&entry(), &key, Some("Ana".to_string())).unwrap();
+        let encoded = encode(&receipt).unwrap();
+        assert!(encoded.starts_with(RECEIPT_PREFIX));
+        assert!(encoded.len() <= MAX_RECEIPT_LEN);
+
+        let parsed = parse(&encoded).unwrap();
+        assert_eq!(parsed, receipt);
+        check_signature(&parsed).unwrap();
+        let json = serde_json::to_string(&receipt).unwrap();
+        check_signature(&parse(&json).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_receipt_fails() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let mut receipt = sign(&entry(), &key, None).unwrap();
+        receipt.body.verdict = "FAILED".to_string();
+        assert!(check_signature(&receipt).is_err());
+
+        // Re-signing with another key doesn't pass under the original fingerprint
+        let mut forged = sign(&entry(), &SigningKey::from_bytes(&[8; 32]), None).unwrap();
+        forged.body.signer_fingerprint = receipt.body.signer_fingerprint.clone();
+        assert!(check_signature(&forged).is_err());
+        assert!(parse("hello").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+// ==================== RECENT VERIFICATIONS ====================
+//
+// The parameters of verifications the user started by hand (Verify panel,
+// clipboard shortcut), newest first, so yesterday's check can be repeated
+// with `rerun_verification` instead of retyping the claim. Batch runs,
+// watchlist re-checks and local API calls aren't recorded. Repeating the
+// same claim, domain and risk moves the existing entry to the top.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::domain_usage;
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{run_blocking, GovernanceResult};
+
+const MAX_RECENT: usize = 50;
+
+/// Serializes read-modify-write cycles on recent_verifications.json
+static RECENT_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentVerification {
+    pub id: String,
+    pub claim: String,
+    pub domain: String,
+    pub risk_profile: String,
+    /// Remote name or URL the verification was sent to, if not the default
+    #[serde(default)]
+    pub remote: Option<String>,
+    pub verified_at: String,
+    /// Outcome of the last run; None when it failed
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub confidence: Option<f64>,
+}
+
+fn recent_path() -> PathBuf {
+    config_path("recent_verifications.json")
+}
+
+/// Put a run at the top of `recent`, replacing an entry with the same parameters
+fn remember(recent: &mut Vec<RecentVerification>, mut run: RecentVerification) {
+    if let Some(pos) = recent.iter().position(|r| {
+        r.claim == run.claim && r.domain == run.domain && r.risk_profile == run.risk_profile && r.remote == run.remote
+    }) {
+        run.id = recent.remove(pos).id;
+    }
+    recent.insert(0, run);
+    recent.truncate(MAX_RECENT);
+}
+
+/// Record a manual verification and its outcome. Failing to write the store
+/// is logged rather than failing the verification itself.
+pub async fn record(
+    claim: &str,
+    domain: &str,
+    risk_profile: &str,
+    remote: Option<&str>,
+    outcome: &Result<GovernanceResult, String>,
+) {
+    let run = RecentVerification {
+        id: uuid::Uuid::new_v4().to_string(),
+        claim: claim.trim().to_string(),
+        domain: domain.to_string(),
+        risk_profile: risk_profile.to_string(),
+        remote: remote.map(str::to_string),
+        verified_at: chrono::Utc::now().to_rfc3339(),
+        status: outcome.as_ref().ok().map(|r| r.status.clone()),
+        confidence: outcome.as_ref().ok().map(|r| r.confidence),
+    };
+    if run.claim.is_empty() {
+        return;
+    }
+    let saved = run_blocking(move || {
+        domain_usage::record(&run.domain, &run.risk_profile);
+        let _guard = RECENT_LOCK.lock(&recent_path())?;
+        let mut recent: Vec<RecentVerification> = load_json(&recent_path())?;
+        remember(&mut recent, run);
+        save_json(&recent_path(), &recent)
+    })
+    .await;
+    if let Err(e) = saved {
+        log::warn!("Failed to record recent verification: {}", e);
+    }
+}
+
+fn load_recent() -> Result<Vec<RecentVerification>, String> {
+    let _guard = RECENT_LOCK.lock(&recent_path())?;
+    load_json(&recent_path())
+}
+
+/// Manual verifications, newest first
+#[tauri::command]
+pub async fn list_recent_verifications() -> Result<Vec<RecentVerification>, String> {
+    run_blocking(load_recent).await
+}
+
+/// Verify a recent entry's claim again with the same domain, risk and remote
+#[tauri::command]
+pub async fn rerun_verification(id: String) -> Result<GovernanceResult, String> {
+    let run = run_blocking(load_recent)
+        .await?
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| "Recent verification not found".to_string())?;
+    let outcome = crate::run_governance_verify(
+        run.claim.clone(),
+        run.domain.clone(),
+        run.risk_profile.clone(),
+        run.remote.clone(),
+    )
+    .await;
+    record(
+        &run.claim,
+        &crate::resolved_domain(&outcome, &run.domain),
+        &run.risk_profile,
+        run.remote.as_deref(),
+        &outcome,
+    )
+    .await;
+    outcome
+}
+
+#[tauri::command]
+pub async fn clear_recent_verifications() -> Result<(), String> {
+    run_blocking(|| {
+        let _guard = RECENT_LOCK.lock(&recent_path())?;
+        save_json(&recent_path(), &Vec::<RecentVerification>::new())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, claim: &str, risk: &str) -> RecentVerification {
+        RecentVerification {
+            id: id.to_string(),
+            claim: claim.to_string(),
+            domain: "science".to_string(),
+            risk_profile: risk.to_string(),
+            remote: None,
+            verified_at: "2026-03-01T12:00:00Z".to_string(),
+            status: Some("PASSED".to_string()),
+            confidence: Some(0.9),
+        }
+    }
+
+    #[test]
+    fn test_remember_moves_repeats_to_top() {
+        let mut recent = vec![run("a", "Water boils", "medium"), run("b", "Ice melts", "medium")];
+        remember(&mut recent, run("c", "Ice melts", "medium"));
+        let ids: Vec<&str> = recent.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+
+        remember(&mut recent, run("d", "Ice melts", "high"));
+        assert_eq!(recent[0].id, "d");
+        assert_eq!(recent.len(), 3);
+
+        for i in 0..MAX_RECENT {
+            remember(&mut recent, run(&i.to_string(), &format!("claim {}", i), "low"));
+        }
+        assert_eq!(recent.len(), MAX_RECENT);
+    }
+}
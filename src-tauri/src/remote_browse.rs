@@ -0,0 +1,185 @@
+// ==================== REMOTE CLAIM BROWSING ====================
+//
+// Look at a remote's claims page by page without syncing the whole repo,
+// and fetch individual claims into the local store on demand.
+//
+// Remote endpoint:
+//   GET {remote}/api/claims?q=&domain=&page=&page_size=  -> { claims, total }
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::authorized_client;
+use crate::rate_limit;
+use crate::remotes::resolve_remote_url;
+use crate::sync::{download_object, sync_preconditions, ObjectRef};
+use crate::truth_store::{ObjectId, ObjectKind, TruthStore};
+
+pub const REMOTE_PAGE_SIZE: u32 = 50;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteClaimFilter {
+    pub query: Option<String>,
+    pub domain: Option<String>,
+    /// Also list claims that already exist locally
+    #[serde(default)]
+    pub include_local: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    data: Option<T>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteClaimsPage {
+    claims: Vec<serde_json::Value>,
+    total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteClaimSummary {
+    pub hash: String,
+    pub content: String,
+    pub domain: Option<String>,
+    pub confidence: Option<f64>,
+    pub created_at: Option<String>,
+    pub local: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteClaimsResult {
+    pub remote: String,
+    pub page: u32,
+    pub page_size: u32,
+    /// Total matches on the remote, including claims we have locally
+    pub total: usize,
+    /// Claims present locally are dropped unless `include_local` is set,
+    /// so a page may hold fewer than `page_size` entries
+    pub claims: Vec<RemoteClaimSummary>,
+}
+
+fn summarize(claim: &serde_json::Value, local: bool) -> Option<RemoteClaimSummary> {
+    let hash = claim.get("$hash")?.as_str()?.to_string();
+    if !ObjectId::is_valid(&hash) {
+        return None;
+    }
+    Some(RemoteClaimSummary {
+        hash,
+        content: claim.get("content").and_then(|c| c.as_str()).unwrap_or("").to_string(),
+        domain: claim.get("domain").and_then(|d| d.as_str()).map(|d| d.to_string()),
+        confidence: claim.get("confidence").and_then(|c| c.as_f64()),
+        created_at: claim
+            .get("metadata")
+            .and_then(|m| m.get("created_at"))
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string()),
+        local,
+    })
+}
+
+fn claim_ref(hash: ObjectId) -> ObjectRef {
+    ObjectRef {
+        kind: ObjectKind::Claim,
+        hash,
+    }
+}
+
+#[tauri::command]
+pub async fn browse_remote_claims(
+    remote: Option<String>,
+    filter: Option<RemoteClaimFilter>,
+    page: Option<u32>,
+) -> Result<RemoteClaimsResult, String> {
+    let store = TruthStore::new(sync_preconditions()?);
+    let remote = resolve_remote_url(remote, "pull")?;
+    let filter = filter.unwrap_or_default();
+    let page = page.unwrap_or(1).max(1);
+
+    let mut query: Vec<(&str, String)> = vec![("page", page.to_string()), ("page_size", REMOTE_PAGE_SIZE.to_string())];
+    if let Some(q) = filter.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        query.push(("q", q.to_string()));
+    }
+    if let Some(domain) = filter.domain.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        query.push(("domain", domain.to_string()));
+    }
+
+    let response = authorized_client(&remote)
+        .await?
+        .get(format!("{}/api/claims", remote))
+        .query(&query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to remote: {}", e))?;
+    rate_limit::record(&remote, &response);
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {} for claims", response.status()));
+    }
+    let result: ApiResponse<RemoteClaimsPage> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse remote claims: {}", e))?;
+    let data = result
+        .data
+        .ok_or_else(|| result.error.unwrap_or_else(|| "Remote returned no claims".to_string()))?;
+
+    let claims = data
+        .claims
+        .iter()
+        .filter_map(|claim| {
+            let hash = claim.get("$hash")?.as_str()?;
+            let local = ObjectId::parse(hash).is_ok_and(|id| store.contains(ObjectKind::Claim, &id));
+            summarize(claim, local)
+        })
+        .filter(|summary| filter.include_local || !summary.local)
+        .collect();
+
+    Ok(RemoteClaimsResult {
+        remote,
+        page,
+        page_size: REMOTE_PAGE_SIZE,
+        total: data.total,
+        claims,
+    })
+}
+
+/// Copy a single claim from the remote into the local store
+#[tauri::command]
+pub async fn fetch_remote_claim(remote: Option<String>, hash: String) -> Result<serde_json::Value, String> {
+    let truth_path = sync_preconditions()?;
+    let remote = resolve_remote_url(remote, "pull")?;
+    let id = ObjectId::parse(&hash).map_err(|_| "Invalid hash".to_string())?;
+
+    let store = TruthStore::new(&truth_path);
+    if !store.contains(ObjectKind::Claim, &id) {
+        download_object(&authorized_client(&remote).await?, &remote, &truth_path, &claim_ref(id.clone())).await?;
+    }
+    Ok(store.read_json(ObjectKind::Claim, &id)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_reads_claim_fields() {
+        let claim = serde_json::json!({
+            "$hash": "ab12cd34ef",
+            "content": "Water boils at 100°C at sea level",
+            "domain": "physics",
+            "confidence": 0.95,
+            "metadata": { "created_at": "2025-01-01T00:00:00Z" }
+        });
+        let summary = summarize(&claim, true).unwrap();
+        assert_eq!(summary.hash, "ab12cd34ef");
+        assert_eq!(summary.domain.as_deref(), Some("physics"));
+        assert_eq!(summary.created_at.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert!(summary.local);
+    }
+
+    #[test]
+    fn test_summarize_rejects_bad_hashes() {
+        assert!(summarize(&serde_json::json!({ "content": "no hash" }), false).is_none());
+        assert!(summarize(&serde_json::json!({ "$hash": "../../x" }), false).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+// ==================== REMOTE HISTORY IMPORT ====================
+//
+// Backfills verifications and audit entries recorded on a remote (web UI,
+// CI runs) into the local repo and audit trail, so the app shows everything
+// that happened to the repo rather than only what was done from this
+// machine. Records are de-duplicated by audit ref: an audit entry whose ID
+// is already in the trail is skipped, and so is a verification whose object
+// or audit ref is already present locally. The newest imported timestamp is
+// kept per remote so the next import only asks for what's new.
+//
+// Remote endpoint:
+//   GET {remote}/api/history?since=&page=  -> { records: [{ audit, verification, source }], next_page }
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::auth::authorized_client;
+use crate::json_store::{load_json, save_json};
+use crate::rate_limit;
+use crate::remotes::resolve_remote_url;
+use crate::sync::{
+    local_inventory, sync_preconditions, transfer_objects, ObjectRef, SyncFailure, SyncGuard,
+};
+use crate::tasks::{self, TaskHandle};
+use crate::truth_store::{ObjectId, ObjectKind};
+use crate::{import_audit_entries, read_verifications, run_blocking, AuditEntry};
+
+/// Stop paging after this many pages; the rest is picked up by the next import
+const MAX_PAGES: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    data: Option<T>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryPage {
+    records: Vec<HistoryRecord>,
+    #[serde(default)]
+    next_page: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryRecord {
+    /// The entry's ID is the audit ref verifications point back to
+    audit: AuditEntry,
+    /// Hash of the verification object the action produced, if any
+    #[serde(default)]
+    verification: Option<String>,
+    /// e.g. "web" or "ci"
+    #[serde(default)]
+    source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryImport {
+    pub remote: String,
+    pub since: Option<String>,
+    /// Records the remote returned
+    pub fetched: usize,
+    pub imported_audit_entries: usize,
+    pub imported_verifications: usize,
+    /// Records already present locally
+    pub duplicates: usize,
+    pub failed: Vec<SyncFailure>,
+}
+
+/// Newest imported timestamp per remote URL
+fn cursor_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("history-imports.json")
+}
+
+/// What is already known locally: audit entry IDs, verification objects
+/// and the audit refs those verifications carry
+struct LocalHistory {
+    audit_ids: HashSet<String>,
+    audit_refs: HashSet<String>,
+    objects: HashSet<ObjectRef>,
+}
+
+fn local_history(truth_path: &Path) -> Result<LocalHistory, String> {
+    let audit_ids = crate::read_audit_trail()?.into_iter().map(|e| e.id).collect();
+    let audit_refs = read_verifications()?
+        .verifications
+        .iter()
+        .filter_map(|v| v.audit_ref.clone())
+        .collect();
+    Ok(LocalHistory {
+        audit_ids,
+        audit_refs,
+        objects: local_inventory(truth_path),
+    })
+}
+
+/// Split fetched records into audit entries and verification objects that
+/// are new locally, and count the rest as duplicates
+fn plan_import(records: Vec<HistoryRecord>, local: &LocalHistory) -> (Vec<AuditEntry>, Vec<ObjectRef>, usize) {
+    let mut entries = Vec::new();
+    let mut objects = Vec::new();
+    let mut seen = HashSet::new();
+    let mut duplicates = 0;
+    for record in records {
+        let audit_ref = record.audit.id.clone();
+        if audit_ref.trim().is_empty() || !seen.insert(audit_ref.clone()) {
+            duplicates += 1;
+            continue;
+        }
+        let verification = record
+            .verification
+            .map(|hash| hash.to_ascii_lowercase())
+            .and_then(|hash| ObjectId::parse(&hash).ok())
+            .map(|hash| ObjectRef {
+                kind: ObjectKind::Verification,
+                hash,
+            })
+            .filter(|object| !local.objects.contains(object) && !local.audit_refs.contains(&audit_ref));
+        let new_entry = !local.audit_ids.contains(&audit_ref);
+        if !new_entry && verification.is_none() {
+            duplicates += 1;
+        }
+        objects.extend(verification);
+        if new_entry {
+            entries.push(AuditEntry {
+                source: record.source.or_else(|| Some("remote".to_string())),
+                ..record.audit
+            });
+        }
+    }
+    (entries, objects, duplicates)
+}
+
+/// Fetch history pages in order. The flag is false when paging stopped at
+/// `MAX_PAGES` before the remote ran out of records.
+async fn fetch_history(
+    client: &reqwest::Client,
+    remote: &str,
+    since: Option<&str>,
+) -> Result<(Vec<HistoryRecord>, bool), String> {
+    let mut records = Vec::new();
+    let mut page = 1;
+    loop {
+        let mut query = vec![("page", page.to_string())];
+        if let Some(since) = since {
+            query.push(("since", since.to_string()));
+        }
+        rate_limit::pace(remote).await?;
+        let response = client
+            .get(format!("{}/api/history", remote))
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to remote: {}", e))?;
+        rate_limit::record(remote, &response);
+        if !response.status().is_success() {
+            return Err(format!("Remote returned {} for history", response.status()));
+        }
+        let result: ApiResponse<HistoryPage> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse remote history: {}", e))?;
+        let data = result
+            .data
+            .ok_or_else(|| result.error.unwrap_or_else(|| "Remote returned no history".to_string()))?;
+        records.extend(data.records);
+        match data.next_page {
+            Some(_) if page >= MAX_PAGES => return Ok((records, false)),
+            Some(next) if next > page => page = next,
+            _ => return Ok((records, true)),
+        }
+    }
+}
+
+async fn run_import(
+    app: AppHandle,
+    task: TaskHandle,
+    remote: Option<String>,
+    since: Option<String>,
+) -> Result<HistoryImport, String> {
+    let truth_path = sync_preconditions()?;
+    let remote = resolve_remote_url(remote, "pull")?;
+    let _guard = SyncGuard::acquire()?;
+
+    let cursors: BTreeMap<String, String> = load_json(&cursor_path(&truth_path))?;
+    let since = since
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| cursors.get(&remote).cloned());
+
+    let client = authorized_client(&remote).await?;
+    let (records, complete) = fetch_history(&client, &remote, since.as_deref()).await?;
+    task.check_cancelled()?;
+    let fetched = records.len();
+    let newest = records.iter().map(|r| r.audit.timestamp.clone()).max();
+
+    let (entries, objects, duplicates) = {
+        let truth_path = truth_path.clone();
+        run_blocking(move || Ok(plan_import(records, &local_history(&truth_path)?))).await?
+    };
+    let (imported, failed) = transfer_objects(&app, &task, &client, &remote, &truth_path, &objects, "pull").await;
+    let imported_verifications = imported.len();
+    task.check_cancelled()?;
+    let imported_audit_entries = run_blocking(move || import_audit_entries(entries)).await?;
+
+    // Only move the cursor once everything up to it arrived, so failures are retried
+    if failed.is_empty() && complete {
+        if let Some(newest) = newest.filter(|n| since.as_ref().is_none_or(|s| n > s)) {
+            let mut cursors = cursors;
+            cursors.insert(remote.clone(), newest);
+            save_json(&cursor_path(&truth_path), &cursors)?;
+        }
+    }
+    log::info!(
+        "Imported {} audit entries and {} verifications from {}",
+        imported_audit_entries,
+        imported_verifications,
+        remote
+    );
+    Ok(HistoryImport {
+        remote,
+        since,
+        fetched,
+        imported_audit_entries,
+        imported_verifications,
+        duplicates,
+        failed,
+    })
+}
+
+/// Backfill verifications and audit entries recorded on the remote since
+/// `since` (RFC 3339), or since the last import from that remote
+#[tauri::command]
+pub async fn import_remote_history(
+    app: AppHandle,
+    remote: Option<String>,
+    since: Option<String>,
+    task_id: Option<String>,
+) -> Result<HistoryImport, String> {
+    let handle = app.clone();
+    tasks::run(&app, task_id, "sync", "Importing remote history", |task| {
+        run_import(handle, task, remote, since)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, verification: Option<&str>) -> HistoryRecord {
+        serde_json::from_value(serde_json::json!({
+            "audit": {
+                "id": id, "timestamp": "2026-03-01T12:00:00Z", "action": "governance_verify",
+                "claim": "Water boils at 100°C", "domain": "science", "risk_profile": "medium",
+                "result_status": "PASSED", "result_action": "proceed", "confidence": 0.9,
+            },
+            "verification": verification,
+            "source": "ci",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_import_skips_known_refs() {
+        let local = LocalHistory {
+            audit_ids: HashSet::from(["known".to_string(), "known-vf".to_string()]),
+            audit_refs: HashSet::from(["ref-only".to_string()]),
+            objects: HashSet::from([ObjectRef {
+                kind: ObjectKind::Verification,
+                hash: ObjectId::parse("aaaa11").unwrap(),
+            }]),
+        };
+        let records = vec![
+            record("new", Some("BBBB22")),
+            record("new", Some("bbbb22")),
+            record("known", Some("aaaa11")),
+            record("ref-only", Some("cccc33")),
+            record("known-vf", Some("dddd44")),
+        ];
+        let (entries, objects, duplicates) = plan_import(records, &local);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "new");
+        assert_eq!(entries[0].source.as_deref(), Some("ci"));
+        assert_eq!(entries[1].id, "ref-only");
+        let hashes: Vec<&str> = objects.iter().map(|o| o.hash.as_str()).collect();
+        assert_eq!(hashes, vec!["bbbb22", "dddd44"]);
+        assert_eq!(duplicates, 2);
+    }
+}
@@ -0,0 +1,204 @@
+// ==================== REMOTES ====================
+//
+// Named remote endpoints, analogous to git remotes. Sync and remote
+// verification accept either a remote name or a URL; with neither they use
+// the default remote for the operation, then `api_url` from settings.
+
+use serde::{Deserialize, Serialize};
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::settings_validation::is_loopback_host;
+use crate::SETTINGS;
+
+const MAX_REMOTE_NAME_LEN: usize = 64;
+
+static REMOTES_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteConfig {
+    pub name: String,
+    pub url: String,
+    /// Name of the stored credential to authenticate with, if any.
+    /// Never the secret itself.
+    #[serde(default)]
+    pub auth_ref: Option<String>,
+    /// Used by push when no remote is given
+    #[serde(default)]
+    pub default_push: bool,
+    /// Used by pull and verification when no remote is given
+    #[serde(default)]
+    pub default_pull: bool,
+}
+
+fn remotes_path() -> std::path::PathBuf {
+    config_path("remotes.json")
+}
+
+pub fn load_remotes() -> Result<Vec<RemoteConfig>, String> {
+    let _guard = REMOTES_LOCK.lock(&remotes_path())?;
+    load_json(&remotes_path())
+}
+
+fn validate_remote_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_REMOTE_NAME_LEN {
+        return Err(format!("Remote name must be 1-{} characters", MAX_REMOTE_NAME_LEN));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+        return Err("Remote name may only contain letters, digits, '-', '_' and '.'".to_string());
+    }
+    Ok(())
+}
+
+/// SECURITY: Claims only leave this machine over TLS (plain http to loopback is allowed)
+pub fn validate_remote_url(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid remote URL: {}", e))?;
+    let loopback = parsed.host_str().map(is_loopback_host).unwrap_or(false);
+    match parsed.scheme() {
+        "https" => {}
+        "http" if loopback => {}
+        _ => return Err("Remote must use https:// (plain http is only allowed for localhost)".to_string()),
+    }
+    Ok(url.trim().trim_end_matches('/').to_string())
+}
+
+/// Pick the remote for an operation: an explicit name or URL, else the
+/// default remote for `direction` ("push" or "pull"), else `api_url`
+fn select_remote(remotes: &[RemoteConfig], remote: Option<&str>, direction: &str, api_url: &str) -> Result<String, String> {
+    if let Some(requested) = remote.map(str::trim).filter(|r| !r.is_empty()) {
+        if let Some(named) = remotes.iter().find(|r| r.name == requested) {
+            return validate_remote_url(&named.url);
+        }
+        if requested.contains("://") {
+            return validate_remote_url(requested);
+        }
+        return Err(format!("Unknown remote '{}'", requested));
+    }
+
+    let default = remotes.iter().find(|r| match direction {
+        "push" => r.default_push,
+        _ => r.default_pull,
+    });
+    match default {
+        Some(remote) => validate_remote_url(&remote.url),
+        None => validate_remote_url(api_url),
+    }
+}
+
+pub fn resolve_remote_url(remote: Option<String>, direction: &str) -> Result<String, String> {
+    let api_url = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.api_url.clone();
+    select_remote(&load_remotes()?, remote.as_deref(), direction, &api_url)
+}
+
+#[tauri::command]
+pub async fn list_remotes() -> Result<Vec<RemoteConfig>, String> {
+    load_remotes()
+}
+
+#[tauri::command]
+pub async fn add_remote(remote: RemoteConfig) -> Result<Vec<RemoteConfig>, String> {
+    let name = remote.name.trim().to_string();
+    validate_remote_name(&name)?;
+    let remote = RemoteConfig {
+        name,
+        url: validate_remote_url(&remote.url)?,
+        ..remote
+    };
+
+    let _guard = REMOTES_LOCK.lock(&remotes_path())?;
+    let mut remotes: Vec<RemoteConfig> = load_json(&remotes_path())?;
+    if remotes.iter().any(|r| r.name == remote.name) {
+        return Err(format!("Remote '{}' already exists", remote.name));
+    }
+    // Only one default per direction
+    for existing in remotes.iter_mut() {
+        existing.default_push &= !remote.default_push;
+        existing.default_pull &= !remote.default_pull;
+    }
+    remotes.push(remote);
+    save_json(&remotes_path(), &remotes)?;
+    Ok(remotes)
+}
+
+/// Add or replace remotes by name (settings import). Imported defaults win.
+pub fn merge_remotes(imported: Vec<RemoteConfig>) -> Result<usize, String> {
+    let _guard = REMOTES_LOCK.lock(&remotes_path())?;
+    let mut remotes: Vec<RemoteConfig> = load_json(&remotes_path())?;
+    let count = imported.len();
+    for remote in imported {
+        validate_remote_name(&remote.name)?;
+        let remote = RemoteConfig {
+            url: validate_remote_url(&remote.url)?,
+            ..remote
+        };
+        for existing in remotes.iter_mut() {
+            existing.default_push &= !remote.default_push;
+            existing.default_pull &= !remote.default_pull;
+        }
+        match remotes.iter_mut().find(|r| r.name == remote.name) {
+            Some(existing) => *existing = remote,
+            None => remotes.push(remote),
+        }
+    }
+    save_json(&remotes_path(), &remotes)?;
+    Ok(count)
+}
+
+#[tauri::command]
+pub async fn remove_remote(name: String) -> Result<Vec<RemoteConfig>, String> {
+    let _guard = REMOTES_LOCK.lock(&remotes_path())?;
+    let mut remotes: Vec<RemoteConfig> = load_json(&remotes_path())?;
+    let before = remotes.len();
+    remotes.retain(|r| r.name != name);
+    if remotes.len() == before {
+        return Err(format!("Remote '{}' not found", name));
+    }
+    save_json(&remotes_path(), &remotes)?;
+    Ok(remotes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(name: &str, url: &str, default_push: bool, default_pull: bool) -> RemoteConfig {
+        RemoteConfig {
+            name: name.to_string(),
+            url: url.to_string(),
+            auth_ref: None,
+            default_push,
+            default_pull,
+        }
+    }
+
+    #[test]
+    fn test_remote_url_must_be_tls_unless_loopback() {
+        assert_eq!(validate_remote_url("https://api.example.com/").unwrap(), "https://api.example.com");
+        assert!(validate_remote_url("http://127.0.0.1:8000").is_ok());
+        assert!(validate_remote_url("http://api.example.com").is_err());
+        assert!(validate_remote_url("ftp://api.example.com").is_err());
+    }
+
+    #[test]
+    fn test_select_remote() {
+        let remotes = vec![
+            remote("team", "https://team.example.com", true, false),
+            remote("archive", "https://archive.example.com", false, true),
+        ];
+        let api = "http://localhost:8000";
+        assert_eq!(select_remote(&remotes, Some("archive"), "push", api).unwrap(), "https://archive.example.com");
+        assert_eq!(select_remote(&remotes, None, "push", api).unwrap(), "https://team.example.com");
+        assert_eq!(select_remote(&remotes, None, "pull", api).unwrap(), "https://archive.example.com");
+        assert_eq!(select_remote(&[], None, "pull", api).unwrap(), api);
+        assert_eq!(select_remote(&remotes, Some("https://other.example.com"), "pull", api).unwrap(), "https://other.example.com");
+        assert!(select_remote(&remotes, Some("missing"), "pull", api).is_err());
+    }
+
+    #[test]
+    fn test_remote_name_validation() {
+        assert!(validate_remote_name("origin").is_ok());
+        assert!(validate_remote_name("team.eu-1").is_ok());
+        assert!(validate_remote_name("").is_err());
+        assert!(validate_remote_name("a b").is_err());
+        assert!(validate_remote_name("../x").is_err());
+    }
+}
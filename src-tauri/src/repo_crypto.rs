@@ -0,0 +1,544 @@
+// ==================== REPO ENCRYPTION ====================
+//
+// Optional encryption at rest for the truth repo's objects and audit.json.
+// A random data key seals each file with XChaCha20-Poly1305; the data key is
+// wrapped with a key derived from the user's passphrase (scrypt) and kept in
+// .truth/encryption.json, so a passphrase never touches the objects
+// themselves. The unwrapped key lives in memory while the repo is unlocked
+// and, if the user asks, in the OS keychain so the repo unlocks on its own.
+//
+// Sealed files start with a magic prefix; reading (`inflate_object`, the
+// audit trail) decrypts them transparently and passes plain files through,
+// so a repo can be part-encrypted. Each file is sealed with what it is (an
+// object's kind and hash, or a file name) as associated data, so a sealed
+// file copied over another one fails to open rather than being read as it.
+//
+// Only the app can read a sealed repo. The TruthGit engine, CLI or
+// embedded, reads objects itself, and remotes would receive ciphertext they
+// can't check, so engine commands and sync are refused while the active repo
+// is encrypted, and encryption can't be turned on while the CLI backend is
+// selected or sync remotes are configured. Objects the engine wrote before
+// are plain until `encrypt_repo_objects` seals them.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::engine::BACKEND_CLI;
+use crate::json_store::{load_json, save_json, write_atomic};
+use crate::truth_store::{ObjectId, ObjectKind, TruthStore};
+use crate::{get_truth_path, mock, paths, remotes, run_blocking, sanitize_error, tasks, AUDIT_LOCK, SETTINGS};
+
+/// Prefix of every sealed file, followed by the nonce and the ciphertext
+const MAGIC: &[u8] = b"TGENC1\0";
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+/// scrypt cost: 2^15 iterations with r = 8 takes ~32 MB and a fraction of a second
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const MIN_PASSPHRASE_LEN: usize = 12;
+pub const CONFIG_FILE: &str = "encryption.json";
+const WRAPPED_KEY_AAD: &[u8] = b"truthgit/data-key";
+const KEYCHAIN_SERVICE: &str = "truthgit-desktop-repo-key";
+
+type DataKey = [u8; KEY_LEN];
+
+/// What a sealed file is, bound into it as associated data
+#[derive(Debug, Clone, Copy)]
+pub enum Binding<'a> {
+    Object(ObjectKind, &'a ObjectId),
+    /// A file by name: audit.json, or a retention archive
+    File(&'a str),
+}
+
+pub const AUDIT: Binding<'static> = Binding::File("audit.json");
+
+impl Binding<'_> {
+    fn aad(&self) -> Vec<u8> {
+        match self {
+            Binding::Object(kind, id) => format!("truthgit/object/{}/{}", kind.dir(), id),
+            Binding::File(name) => format!("truthgit/file/{}", name),
+        }
+        .into_bytes()
+    }
+}
+
+/// The repo the key cache was resolved for, and its key if it is unlocked.
+/// Resolving again on a repo switch means a remembered key is looked up once
+/// per repo rather than on every object read.
+struct Unlocked {
+    repo: PathBuf,
+    key: Option<DataKey>,
+}
+
+static UNLOCKED: Mutex<Option<Unlocked>> = Mutex::new(None);
+
+/// .truth/encryption.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct EncryptionConfig {
+    version: u32,
+    cipher: String,
+    kdf: String,
+    /// base64
+    salt: String,
+    log_n: u8,
+    /// Data key sealed with the passphrase key, base64
+    wrapped_key: String,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+    /// The key is stored in the OS keychain
+    pub remembered: bool,
+    /// Objects not sealed yet, e.g. written by the CLI since the last pass
+    pub plain_objects: usize,
+    pub audit_encrypted: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EncryptionReport {
+    pub sealed: usize,
+    pub already_sealed: usize,
+    pub audit_sealed: bool,
+}
+
+fn config_path(repo: &Path) -> PathBuf {
+    repo.join(CONFIG_FILE)
+}
+
+fn load_config(repo: &Path) -> Result<Option<EncryptionConfig>, String> {
+    let path = config_path(repo);
+    if !path.exists() {
+        return Ok(None);
+    }
+    load_json(&path).map(Some)
+}
+
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+fn seal_with(key: &DataKey, aad: &[u8], plain: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = XChaCha20Poly1305::new(key.into())
+        .encrypt(XNonce::from_slice(&nonce), Payload { msg: plain, aad })
+        .map_err(|_| "Encryption failed".to_string())?;
+    let mut sealed = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+fn open_with(key: &DataKey, aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let body = sealed
+        .strip_prefix(MAGIC)
+        .filter(|b| b.len() > NONCE_LEN)
+        .ok_or("Not an encrypted file")?;
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| "Decryption failed: wrong key, or the file is damaged or misplaced".to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8) -> Result<DataKey, String> {
+    let params = scrypt::Params::new(log_n, SCRYPT_R, SCRYPT_P, KEY_LEN)
+        .map_err(|e| format!("Invalid KDF parameters: {}", e))?;
+    let mut key = [0u8; KEY_LEN];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn new_config(passphrase: &str, data_key: &DataKey, log_n: u8) -> Result<EncryptionConfig, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let wrapping_key = derive_key(passphrase, &salt, log_n)?;
+    Ok(EncryptionConfig {
+        version: 1,
+        cipher: "xchacha20poly1305".to_string(),
+        kdf: "scrypt".to_string(),
+        salt: STANDARD.encode(salt),
+        log_n,
+        wrapped_key: STANDARD.encode(seal_with(&wrapping_key, WRAPPED_KEY_AAD, data_key)?),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+fn unwrap_key(config: &EncryptionConfig, passphrase: &str) -> Result<DataKey, String> {
+    let salt = STANDARD
+        .decode(&config.salt)
+        .map_err(|e| format!("Invalid encryption config: {}", e))?;
+    let wrapped = STANDARD
+        .decode(&config.wrapped_key)
+        .map_err(|e| format!("Invalid encryption config: {}", e))?;
+    let wrapping_key = derive_key(passphrase, &salt, config.log_n)?;
+    open_with(&wrapping_key, WRAPPED_KEY_AAD, &wrapped)
+        .map_err(|_| "Wrong passphrase".to_string())?
+        .try_into()
+        .map_err(|_| "Invalid encryption config: bad key length".to_string())
+}
+
+// ---------- keychain ----------
+
+fn keychain_entry(repo: &Path) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &paths::display_path(repo))
+        .map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+fn remembered_key(repo: &Path) -> Result<Option<DataKey>, String> {
+    match keychain_entry(repo)?.get_password() {
+        Ok(secret) => STANDARD
+            .decode(secret)
+            .ok()
+            .and_then(|bytes| DataKey::try_from(bytes).ok())
+            .map(Some)
+            .ok_or_else(|| "Stored repo key is corrupt".to_string()),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read keychain: {}", e)),
+    }
+}
+
+fn remember_key(repo: &Path, key: &DataKey) -> Result<(), String> {
+    keychain_entry(repo)?
+        .set_password(&STANDARD.encode(key))
+        .map_err(|e| format!("Failed to write keychain: {}", e))
+}
+
+fn forget_key(repo: &Path) -> Result<(), String> {
+    match keychain_entry(repo)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove repo key: {}", e)),
+    }
+}
+
+// ---------- key cache ----------
+
+fn set_key(repo: &Path, key: Option<DataKey>) -> Result<(), String> {
+    *UNLOCKED.lock().map_err(|e| format!("Lock error: {}", e))? = Some(Unlocked {
+        repo: repo.to_path_buf(),
+        key,
+    });
+    Ok(())
+}
+
+/// The data key for `repo`, looked up in the keychain the first time
+fn key_for(repo: &Path) -> Result<Option<DataKey>, String> {
+    let mut unlocked = UNLOCKED.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if let Some(cached) = unlocked.as_ref().filter(|u| u.repo == repo) {
+        return Ok(cached.key);
+    }
+    let key = if config_path(repo).exists() {
+        remembered_key(repo).unwrap_or_else(|e| {
+            log::warn!("Couldn't load the remembered repo key: {}", e);
+            None
+        })
+    } else {
+        None
+    };
+    *unlocked = Some(Unlocked {
+        repo: repo.to_path_buf(),
+        key,
+    });
+    Ok(key)
+}
+
+fn require_key(repo: &Path) -> Result<DataKey, String> {
+    key_for(repo)?.ok_or_else(|| "The truth repo is encrypted and locked; unlock it first".to_string())
+}
+
+/// Whether `repo` has encryption turned on
+pub fn is_enabled(repo: &Path) -> bool {
+    config_path(repo).exists()
+}
+
+/// Decrypt `bytes` read from `repo` as `binding` if they are sealed; plain
+/// files pass through
+pub fn open(repo: &Path, binding: Binding, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    if !is_sealed(bytes) {
+        return Ok(bytes.to_vec());
+    }
+    open_with(&require_key(repo)?, &binding.aad(), bytes)
+}
+
+/// Seal `bytes` for writing into `repo` as `binding` if it is encrypted;
+/// otherwise return them unchanged
+pub fn seal(repo: &Path, binding: Binding, bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    if !is_enabled(repo) {
+        return Ok(bytes);
+    }
+    seal_with(&require_key(repo)?, &binding.aad(), &bytes)
+}
+
+/// Refuse work that would need the engine or a remote to read the active
+/// repo while it is encrypted
+pub fn require_plain_repo(what: &str) -> Result<(), String> {
+    match get_truth_path() {
+        Some(repo) if is_enabled(&repo) => Err(format!(
+            "The truth repo is encrypted, and {} can't read encrypted objects",
+            what
+        )),
+        _ => Ok(()),
+    }
+}
+
+// ---------- sealing existing files ----------
+
+fn object_files(repo: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(TruthStore::new(repo).objects_dir())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.into_path())
+        .collect()
+}
+
+fn file_is_sealed(path: &Path) -> bool {
+    use std::io::Read;
+    let mut prefix = [0u8; MAGIC.len()];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut prefix))
+        .map(|_| is_sealed(&prefix))
+        .unwrap_or(false)
+}
+
+/// Seal every plain object and the audit trail in place
+fn seal_existing(repo: &Path, key: &DataKey, task: &tasks::TaskHandle) -> Result<EncryptionReport, String> {
+    let store = TruthStore::new(repo);
+    let entries: Vec<_> = [ObjectKind::Claim, ObjectKind::Verification, ObjectKind::Evidence]
+        .into_iter()
+        .flat_map(|kind| store.iter(kind))
+        .collect();
+    let mut report = EncryptionReport {
+        sealed: 0,
+        already_sealed: 0,
+        audit_sealed: false,
+    };
+    for (i, entry) in entries.iter().enumerate() {
+        task.check_cancelled()?;
+        let bytes = fs::read(&entry.path).map_err(|e| sanitize_error(&format!("Failed to read object: {}", e)))?;
+        if is_sealed(&bytes) {
+            report.already_sealed += 1;
+        } else {
+            let aad = Binding::Object(entry.kind, &entry.id).aad();
+            write_atomic(&entry.path, &seal_with(key, &aad, &bytes)?)
+                .map_err(|e| sanitize_error(&format!("Failed to write object: {}", e)))?;
+            report.sealed += 1;
+        }
+        if i % 100 == 0 {
+            task.progress(
+                i as u64,
+                entries.len() as u64,
+                format!("Encrypted {} of {} objects", i, entries.len()),
+            );
+        }
+    }
+
+    let audit_file = repo.join("audit.json");
+    let _guard = AUDIT_LOCK.lock(&audit_file)?;
+    if audit_file.exists() {
+        let bytes = fs::read(&audit_file).map_err(|e| format!("Failed to read audit file: {}", e))?;
+        if !is_sealed(&bytes) {
+            write_atomic(&audit_file, &seal_with(key, &AUDIT.aad(), &bytes)?)
+                .map_err(|e| format!("Failed to write audit file: {}", e))?;
+            report.audit_sealed = true;
+        }
+    }
+    Ok(report)
+}
+
+fn active_repo() -> Result<PathBuf, String> {
+    if mock::is_active() {
+        return Err("Repo encryption is disabled in mock mode".to_string());
+    }
+    get_truth_path()
+        .filter(|p| p.exists())
+        .ok_or_else(|| "Truth repository not found".to_string())
+}
+
+fn update_remembered(repo: &Path, key: &DataKey, remember: bool) {
+    let result = if remember {
+        remember_key(repo, key)
+    } else {
+        forget_key(repo)
+    };
+    if let Err(e) = result {
+        log::warn!("{}", e);
+    }
+}
+
+#[tauri::command]
+pub async fn get_encryption_status() -> Result<EncryptionStatus, String> {
+    run_blocking(|| {
+        let Some(repo) = get_truth_path().filter(|p| p.exists() && !mock::is_active()) else {
+            return Ok(EncryptionStatus {
+                enabled: false,
+                unlocked: false,
+                remembered: false,
+                plain_objects: 0,
+                audit_encrypted: false,
+            });
+        };
+        let enabled = config_path(&repo).exists();
+        let audit_file = repo.join("audit.json");
+        Ok(EncryptionStatus {
+            enabled,
+            unlocked: enabled && key_for(&repo)?.is_some(),
+            remembered: enabled && matches!(remembered_key(&repo), Ok(Some(_))),
+            plain_objects: object_files(&repo).iter().filter(|p| !file_is_sealed(p)).count(),
+            audit_encrypted: audit_file.exists() && file_is_sealed(&audit_file),
+        })
+    })
+    .await
+}
+
+/// Turn on encryption for the active repo and seal everything already in it
+#[tauri::command]
+pub async fn enable_repo_encryption(
+    app: AppHandle,
+    passphrase: String,
+    remember: bool,
+    task_id: Option<String>,
+) -> Result<EncryptionReport, String> {
+    let repo = active_repo()?;
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!("Passphrase must be at least {} characters", MIN_PASSPHRASE_LEN));
+    }
+    if config_path(&repo).exists() {
+        return Err("The truth repo is already encrypted".to_string());
+    }
+    let backend_mode = SETTINGS
+        .read()
+        .map_err(|e| format!("Settings lock error: {}", e))?
+        .backend_mode
+        .clone();
+    if backend_mode == BACKEND_CLI {
+        return Err("The truthgit CLI can't read encrypted objects; \
+            switch the engine backend away from \"cli\" before encrypting"
+            .to_string());
+    }
+    if !run_blocking(remotes::load_remotes).await?.is_empty() {
+        return Err("Remotes can't check encrypted objects; remove the sync remotes before encrypting".to_string());
+    }
+    let report = tasks::run(
+        &app,
+        task_id,
+        "repo_encryption",
+        "Encrypting repository",
+        |task| async move {
+            tokio::task::spawn_blocking(move || {
+                let mut key = [0u8; KEY_LEN];
+                OsRng.fill_bytes(&mut key);
+                save_json(&config_path(&repo), &new_config(&passphrase, &key, SCRYPT_LOG_N)?)?;
+                set_key(&repo, Some(key))?;
+                update_remembered(&repo, &key, remember);
+                seal_existing(&repo, &key, &task)
+            })
+            .await
+            .map_err(|e| format!("Encryption task failed: {}", e))?
+        },
+    )
+    .await?;
+    log::info!("Encrypted truth repository ({} objects sealed)", report.sealed);
+    Ok(report)
+}
+
+/// Seal objects written in plain since the last pass, e.g. by the CLI
+#[tauri::command]
+pub async fn encrypt_repo_objects(app: AppHandle, task_id: Option<String>) -> Result<EncryptionReport, String> {
+    let repo = active_repo()?;
+    if !config_path(&repo).exists() {
+        return Err("The truth repo is not encrypted".to_string());
+    }
+    let key = require_key(&repo)?;
+    tasks::run(
+        &app,
+        task_id,
+        "repo_encryption",
+        "Encrypting new objects",
+        |task| async move {
+            tokio::task::spawn_blocking(move || seal_existing(&repo, &key, &task))
+                .await
+                .map_err(|e| format!("Encryption task failed: {}", e))?
+        },
+    )
+    .await
+}
+
+/// Unlock with the passphrase; `remember` keeps the key in the OS keychain
+/// (or removes a stored one when false)
+#[tauri::command]
+pub async fn unlock_repo(passphrase: String, remember: bool) -> Result<(), String> {
+    let repo = active_repo()?;
+    run_blocking(move || {
+        let config = load_config(&repo)?.ok_or("The truth repo is not encrypted")?;
+        let key = unwrap_key(&config, &passphrase)?;
+        set_key(&repo, Some(key))?;
+        update_remembered(&repo, &key, remember);
+        log::info!("Unlocked truth repository");
+        Ok(())
+    })
+    .await
+}
+
+/// Drop the key from memory. A key remembered in the keychain stays there.
+#[tauri::command]
+pub async fn lock_repo() -> Result<(), String> {
+    let repo = active_repo()?;
+    set_key(&repo, None)?;
+    log::info!("Locked truth repository");
+    Ok(())
+}
+
+/// Re-wrap the data key under a new passphrase; objects aren't touched
+#[tauri::command]
+pub async fn change_repo_passphrase(current: String, new_passphrase: String) -> Result<(), String> {
+    let repo = active_repo()?;
+    if new_passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!("Passphrase must be at least {} characters", MIN_PASSPHRASE_LEN));
+    }
+    run_blocking(move || {
+        let config = load_config(&repo)?.ok_or("The truth repo is not encrypted")?;
+        let key = unwrap_key(&config, &current)?;
+        save_json(&config_path(&repo), &new_config(&new_passphrase, &key, config.log_n)?)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_roundtrip_and_key_wrapping() {
+        let key = [7u8; KEY_LEN];
+        let id = ObjectId::parse("abcd01").unwrap();
+        let binding = Binding::Object(ObjectKind::Claim, &id).aad();
+        let sealed = seal_with(&key, &binding, b"claim").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_eq!(open_with(&key, &binding, &sealed).unwrap(), b"claim");
+        assert!(open_with(&[8u8; KEY_LEN], &binding, &sealed).is_err());
+        // Filed under another hash, or as the audit trail, it doesn't open
+        let other = ObjectId::parse("abcd02").unwrap();
+        assert!(open_with(&key, &Binding::Object(ObjectKind::Claim, &other).aad(), &sealed).is_err());
+        assert!(open_with(&key, &AUDIT.aad(), &sealed).is_err());
+
+        // Low cost so the test stays fast
+        let config = new_config("correct horse battery", &key, 4).unwrap();
+        assert_eq!(unwrap_key(&config, "correct horse battery").unwrap(), key);
+        assert_eq!(unwrap_key(&config, "wrong passphrase").unwrap_err(), "Wrong passphrase");
+    }
+}
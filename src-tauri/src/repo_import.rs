@@ -0,0 +1,441 @@
+// ==================== REPO ARCHIVE IMPORT ====================
+//
+// Brings in a truth repo zipped somewhere else (exported from a server,
+// copied from another machine). The repo may sit at the root of the zip or
+// in one folder of it, e.g. .truth/. It is unpacked into a staging folder
+// and checked before anything else is touched: paths must be plain relative
+// paths and no entry may be a link; everything under objects/ must be a
+// well-formed object, claims and verifications must decode, and a `$hash`
+// one carries must be the name it is filed under; the signing keys must be
+// both there or both missing. That catches objects that are misfiled or
+// mangled, not content that was edited: content hashes and verification
+// signatures are the CLI's to check, so the report gives the number of
+// verifications that name a signer and the fingerprint of the archive's
+// proof.pub for the user to compare.
+//
+// "adopt" moves the unpacked repo into a new or empty folder, registers it
+// and makes it the active repo. "merge" copies the objects the active repo
+// lacks into it, counting the ones it already has as duplicates, and adds
+// the audit entries it doesn't have; its HEAD and refs stay as they are.
+// Encrypted repos can't be imported this way; restore a backup instead.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::repo_crypto::Binding;
+use crate::setup::is_truth_repo;
+use crate::tasks::{self, TaskHandle};
+use crate::truth_store::{self, ObjectKind, TruthStore, OBJECTS_DIR};
+use crate::{
+    consent, get_truth_path, identity, import_audit_entries, mock, repo_crypto, repos, sanitize_error, AuditEntry,
+    MAX_DECOMPRESSED_SIZE,
+};
+
+const MAX_ARCHIVE_FILES: usize = 1_000_000;
+const MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+const MAX_ARCHIVE_SIZE: u64 = 8 * 1024 * 1024 * 1024;
+const KEY_FILES: [&str; 2] = ["proof.key", "proof.pub"];
+const OBJECT_KINDS: [ObjectKind; 3] = [ObjectKind::Claim, ObjectKind::Verification, ObjectKind::Evidence];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    Adopt,
+    Merge,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArchiveContents {
+    pub claims: usize,
+    pub verifications: usize,
+    pub evidence: usize,
+    /// Verifications that name a signer
+    pub signed_verifications: usize,
+    /// Of the archive's proof.pub, if it has one
+    pub key_fingerprint: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub mode: ImportMode,
+    /// The repo the archive ended up in
+    pub path: String,
+    pub contents: ArchiveContents,
+    /// Objects new to the repo
+    pub imported: usize,
+    /// Objects the active repo already had (merge only)
+    pub duplicates: usize,
+    /// Audit entries added (merge only)
+    pub audit_entries: usize,
+}
+
+/// Where the repo sits in the archive: the shortest folder holding objects/
+fn repo_prefix<'a>(names: impl Iterator<Item = &'a str>) -> Result<String, String> {
+    let marker = format!("{}/", OBJECTS_DIR);
+    names
+        .filter_map(|name| {
+            let prefix = if name.starts_with(&marker) {
+                ""
+            } else {
+                let at = name.find(&format!("/{}", marker))?;
+                &name[..=at]
+            };
+            Some(prefix.to_string())
+        })
+        .min_by_key(|prefix| prefix.matches('/').count())
+        .ok_or_else(|| "The archive doesn't contain a truth repository (no objects/ folder)".to_string())
+}
+
+/// Archive paths must be plain relative paths
+fn safe_relative_path(name: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    if name.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(path.to_path_buf())
+}
+
+/// Unpack the repo in the zip at `src` into `staging`; returns the number of files
+fn unpack(src: &Path, staging: &Path, task: &TaskHandle) -> Result<usize, String> {
+    let file = File::open(src).map_err(|e| sanitize_error(&format!("Failed to open archive: {}", e)))?;
+    let mut archive =
+        zip::ZipArchive::new(io::BufReader::new(file)).map_err(|e| format!("Not a zip archive: {}", e))?;
+    if archive.len() > MAX_ARCHIVE_FILES {
+        return Err(format!("The archive has too many files (max {})", MAX_ARCHIVE_FILES));
+    }
+    let prefix = repo_prefix(archive.file_names())?;
+    let read_err = |e: io::Error| format!("Failed to read archive: {}", e);
+    let write_err = |e: io::Error| sanitize_error(&format!("Failed to unpack archive: {}", e));
+
+    let total = archive.len() as u64;
+    let mut files = 0;
+    let mut unpacked_size = 0u64;
+    for i in 0..archive.len() {
+        task.check_cancelled()?;
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read archive: {}", e))?;
+        let name = entry.name().to_string();
+        let Some(relative) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        if entry.is_dir() || relative.is_empty() {
+            continue;
+        }
+        if entry.is_symlink() {
+            return Err(format!("The archive contains a link: {}", name));
+        }
+        let relative =
+            safe_relative_path(relative).ok_or_else(|| format!("The archive contains an unsafe path: {}", name))?;
+        // Temporary files left by an interrupted write
+        if relative
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+
+        let target = staging.join(&relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        let mut out = File::create(&target).map_err(write_err)?;
+        let size = io::copy(&mut entry.by_ref().take(MAX_FILE_SIZE + 1), &mut out).map_err(read_err)?;
+        if size > MAX_FILE_SIZE {
+            return Err(format!("{} is too large to import", name));
+        }
+        unpacked_size += size;
+        if unpacked_size > MAX_ARCHIVE_SIZE {
+            return Err("The archive is too large to import".to_string());
+        }
+        files += 1;
+        task.progress(i as u64 + 1, total, format!("Unpacked {}", name));
+    }
+    Ok(files)
+}
+
+/// Check the unpacked repo's structure and objects
+fn validate(repo: &Path, task: &TaskHandle) -> Result<ArchiveContents, String> {
+    if !is_truth_repo(repo) {
+        return Err("The archive doesn't contain a truth repository".to_string());
+    }
+    if repo.join(repo_crypto::CONFIG_FILE).exists() {
+        return Err("The archived repository is encrypted; restore it from a backup instead".to_string());
+    }
+    if KEY_FILES.iter().filter(|key| repo.join(key).is_file()).count() == 1 {
+        return Err("The archive has only half of the signing key pair".to_string());
+    }
+
+    let store = TruthStore::new(repo);
+    let listed: usize = OBJECT_KINDS.iter().map(|kind| store.count(*kind)).sum();
+    let files = WalkDir::new(store.objects_dir())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .count();
+    if files != listed {
+        return Err(format!(
+            "The archive's objects/ folder holds {} files that aren't truth objects",
+            files - listed
+        ));
+    }
+
+    let mut contents = ArchiveContents {
+        key_fingerprint: identity::key_fingerprint(repo),
+        ..Default::default()
+    };
+    let mut checked = 0;
+    for kind in OBJECT_KINDS {
+        for entry in store.iter(kind) {
+            task.check_cancelled()?;
+            let object = entry.object_name();
+            let invalid = |e: String| format!("Invalid object {}: {}", object, e);
+            let raw = fs::read(&entry.path).map_err(|e| invalid(e.to_string()))?;
+            if repo_crypto::is_sealed(&raw) {
+                return Err(invalid("it is encrypted".to_string()));
+            }
+            if kind == ObjectKind::Evidence {
+                truth_store::inflate(&raw, MAX_DECOMPRESSED_SIZE).map_err(|e| invalid(e.into()))?;
+                contents.evidence += 1;
+            } else {
+                let value = truth_store::decode_json(&raw).map_err(|e| invalid(e.into()))?;
+                let embedded = value.get("$hash").and_then(|h| h.as_str());
+                if embedded.is_some_and(|hash| hash != entry.id.as_str()) {
+                    return Err(invalid("its $hash doesn't match the name it is filed under".to_string()));
+                }
+                if kind == ObjectKind::Claim {
+                    contents.claims += 1;
+                } else {
+                    contents.verifications += 1;
+                    let signer = ["signer", "signed_by"]
+                        .iter()
+                        .find_map(|field| value.get(field).and_then(|s| s.as_str()));
+                    if signer.is_some_and(|s| !s.is_empty()) {
+                        contents.signed_verifications += 1;
+                    }
+                }
+            }
+            checked += 1;
+            task.progress(checked, listed as u64, format!("Checked {}", object));
+        }
+    }
+    Ok(contents)
+}
+
+/// Copy objects `repo` lacks from `staging`, sealing them if `repo` is
+/// encrypted; returns (imported, duplicates)
+fn merge_objects(staging: &Path, repo: &Path, task: &TaskHandle) -> Result<(usize, usize), String> {
+    let (from, to) = (TruthStore::new(staging), TruthStore::new(repo));
+    let (mut imported, mut duplicates) = (0, 0);
+    for kind in OBJECT_KINDS {
+        for entry in from.iter(kind) {
+            task.check_cancelled()?;
+            if to.contains(kind, &entry.id) {
+                duplicates += 1;
+                continue;
+            }
+            let bytes = repo_crypto::seal(repo, Binding::Object(kind, &entry.id), from.read_raw(kind, &entry.id)?)?;
+            to.write_raw(kind, &entry.id, &bytes)?;
+            imported += 1;
+        }
+    }
+    Ok((imported, duplicates))
+}
+
+fn staging_dir(next_to: &Path) -> Result<PathBuf, String> {
+    let parent = next_to.parent().ok_or("The destination has no parent folder")?;
+    fs::create_dir_all(parent).map_err(|e| sanitize_error(&format!("Failed to create folder: {}", e)))?;
+    let staging = parent.join(format!(".truthgit-import-{}", uuid::Uuid::new_v4()));
+    fs::create_dir(&staging).map_err(|e| sanitize_error(&format!("Failed to create staging folder: {}", e)))?;
+    Ok(staging)
+}
+
+fn import_archive(
+    src: &Path,
+    mode: ImportMode,
+    repo: &Path,
+    target: Option<PathBuf>,
+    task: &TaskHandle,
+) -> Result<ImportReport, String> {
+    let destination = match mode {
+        ImportMode::Merge => repo.to_path_buf(),
+        ImportMode::Adopt => target.ok_or("Choose a folder for the imported repository")?,
+    };
+    let occupied = fs::read_dir(&destination).is_ok_and(|mut d| d.next().is_some());
+    if mode == ImportMode::Adopt && occupied {
+        return Err("The imported repository needs a new or empty folder".to_string());
+    }
+    if mode == ImportMode::Merge && !is_truth_repo(repo) {
+        return Err("No active truth repository to merge into".to_string());
+    }
+
+    let staging = staging_dir(&destination)?;
+    let result = (|| {
+        unpack(src, &staging, task)?;
+        let contents = validate(&staging, task)?;
+        let objects = contents.claims + contents.verifications + contents.evidence;
+        let path = destination.to_string_lossy().to_string();
+        if mode == ImportMode::Adopt {
+            // Only an empty folder can be here
+            let _ = fs::remove_dir(&destination);
+            fs::rename(&staging, &destination)
+                .map_err(|e| sanitize_error(&format!("Failed to move the repository into place: {}", e)))?;
+            return Ok(ImportReport {
+                mode,
+                path,
+                contents,
+                imported: objects,
+                duplicates: 0,
+                audit_entries: 0,
+            });
+        }
+
+        let (imported, duplicates) = merge_objects(&staging, repo, task)?;
+        let audit_file = staging.join("audit.json");
+        let audit: Vec<AuditEntry> = if audit_file.is_file() {
+            let content = fs::read(&audit_file).map_err(|e| sanitize_error(&format!("Failed to read audit: {}", e)))?;
+            serde_json::from_slice(&content).map_err(|e| format!("The archive's audit trail is invalid: {}", e))?
+        } else {
+            Vec::new()
+        };
+        Ok(ImportReport {
+            mode,
+            path,
+            contents,
+            imported,
+            duplicates,
+            audit_entries: import_audit_entries(audit)?,
+        })
+    })();
+    if staging.exists() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// Import a zipped truth repo, either as a new repo made active (`adopt`,
+/// into `target`) or into the active one (`merge`)
+#[tauri::command]
+pub async fn import_truth_repo_archive(
+    app: AppHandle,
+    path: String,
+    mode: ImportMode,
+    target: Option<String>,
+    task_id: Option<String>,
+) -> Result<ImportReport, String> {
+    if mock::is_active() {
+        return Err("Importing repositories is disabled in mock mode".to_string());
+    }
+    let repo = get_truth_path().ok_or("Could not find home directory")?;
+    let src = PathBuf::from(path.trim());
+    consent::require_read(&src).await?;
+    let target = target
+        .map(|t| PathBuf::from(t.trim()))
+        .filter(|t| !t.as_os_str().is_empty());
+    if target.as_ref().is_some_and(|t| !t.is_absolute()) {
+        return Err("The import folder must be an absolute path".to_string());
+    }
+    let report = tasks::run(&app, task_id, "import", "Importing repository", |task| async move {
+        tokio::task::spawn_blocking(move || import_archive(&src, mode, &repo, target, &task))
+            .await
+            .map_err(|e| format!("Import task failed: {}", e))?
+    })
+    .await?;
+    log::info!(
+        "Imported repository archive ({:?}): {} new objects, {} duplicates",
+        report.mode,
+        report.imported,
+        report.duplicates
+    );
+
+    if report.mode == ImportMode::Adopt {
+        repos::add_truth_repo(report.path.clone(), None).await?;
+        repos::switch_truth_repo(app, report.path.clone()).await?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use crate::truth_store::ObjectId;
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+
+    fn compressed(json: &str) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zip_repo(path: &Path, files: &[(&str, Vec<u8>)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, bytes) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(bytes).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_repo_prefix() {
+        let names = [
+            "export/readme.txt",
+            "export/.truth/HEAD",
+            "export/.truth/objects/cl/ab/cdef",
+        ];
+        assert_eq!(repo_prefix(names.into_iter()).unwrap(), "export/.truth/");
+        assert_eq!(
+            repo_prefix(["objects/cl/ab/cd", "a/objects/x"].into_iter()).unwrap(),
+            ""
+        );
+        assert!(repo_prefix(["notes/objects.md"].into_iter()).is_err());
+        assert!(safe_relative_path("../evil").is_none() && safe_relative_path("/etc/passwd").is_none());
+    }
+
+    #[test]
+    fn test_merge_counts_duplicates() {
+        let tmp = TempDir::new("truthgit-import");
+        let root = tmp.path();
+        let repo = root.join("active");
+        let claim = compressed(r#"{"$hash": "abcd01", "content": "Water boils at 100 C"}"#);
+        TruthStore::new(&repo)
+            .write_raw(ObjectKind::Claim, &ObjectId::parse("abcd01").unwrap(), &claim)
+            .unwrap();
+        let verification = compressed(r#"{"$hash": "ef0123", "claim_hash": "abcd01", "signer": "key-1"}"#);
+        let task = TaskHandle::detached();
+
+        let archive = root.join("repo.zip");
+        zip_repo(
+            &archive,
+            &[
+                (".truth/objects/cl/ab/cd01", claim.clone()),
+                (".truth/objects/vf/ef/0123", verification),
+                (".truth/audit.json", b"[]".to_vec()),
+            ],
+        );
+        let staging = staging_dir(&repo).unwrap();
+        unpack(&archive, &staging, &task).unwrap();
+        let contents = validate(&staging, &task).unwrap();
+        assert_eq!(
+            (contents.claims, contents.verifications, contents.signed_verifications),
+            (1, 1, 1)
+        );
+        assert_eq!(merge_objects(&staging, &repo, &task).unwrap(), (1, 1));
+        assert!(TruthStore::new(&repo).contains(ObjectKind::Verification, &ObjectId::parse("ef0123").unwrap()));
+
+        let tampered = root.join("tampered.zip");
+        zip_repo(&tampered, &[("objects/cl/ff/ff01", claim)]);
+        let staging = staging_dir(&repo).unwrap();
+        unpack(&tampered, &staging, &task).unwrap();
+        assert!(validate(&staging, &task)
+            .unwrap_err()
+            .contains("doesn't match the name it is filed under"));
+    }
+}
@@ -0,0 +1,224 @@
+// ==================== TRUTH REPO INITIALIZATION ====================
+//
+// `truthgit init` is not in the CLI whitelist, so the terminal can never
+// create a repo. Initialization instead goes through a two-step flow:
+// `prepare_truth_repo_init` checks the target and issues a single-use
+// confirmation token, and `init_truth_repo` only runs with that token.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::engine::BACKEND_CLI;
+use crate::setup::is_truth_repo;
+use crate::truth_store::TruthStore;
+use crate::{execute_with_timeout, mock, sanitize_error, SETTINGS};
+
+/// Confirmation tokens expire quickly; the user is looking at the dialog
+const CONFIRMATION_TTL: Duration = Duration::from_secs(120);
+
+struct PendingInit {
+    path: PathBuf,
+    issued_at: Instant,
+}
+
+static PENDING_INITS: std::sync::LazyLock<Mutex<HashMap<String, PendingInit>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitPlan {
+    pub path: String,
+    pub confirmation_token: String,
+    pub expires_in_secs: u64,
+    /// "cli" when `truthgit init` will be used, "native" otherwise
+    pub method: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitResult {
+    pub path: String,
+    pub method: String,
+}
+
+/// Reject anything but an absolute path to a missing or empty directory
+pub fn check_init_target(path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path.trim());
+    if !path.is_absolute() {
+        return Err("Repository path must be absolute".to_string());
+    }
+    if is_truth_repo(&path) {
+        return Err("A truth repository already exists at this path".to_string());
+    }
+    if path.exists() {
+        if !path.is_dir() {
+            return Err("Path exists and is not a directory".to_string());
+        }
+        let mut entries =
+            fs::read_dir(&path).map_err(|e| sanitize_error(&format!("Failed to read directory: {}", e)))?;
+        if entries.next().is_some() {
+            return Err("Directory is not empty; choose an empty or new folder".to_string());
+        }
+    }
+    Ok(path)
+}
+
+/// Create an empty truth repo layout natively
+fn create_repo_structure(path: &Path) -> Result<(), String> {
+    TruthStore::new(path)
+        .create()
+        .map_err(|e| sanitize_error(&format!("Failed to create repository: {}", e)))
+}
+
+/// `truthgit init` creates `.truth` in its working directory, so the CLI can
+/// only be used when the target is named `.truth` and the CLI backend is active
+fn init_method(path: &Path) -> &'static str {
+    let uses_cli = SETTINGS
+        .read()
+        .map(|s| s.backend_mode == BACKEND_CLI)
+        .unwrap_or(false);
+    if uses_cli && path.file_name().map(|n| n == ".truth").unwrap_or(false) {
+        "cli"
+    } else {
+        "native"
+    }
+}
+
+async fn init_with_cli(path: &Path) -> Result<(), String> {
+    let parent = path.parent().ok_or("Repository path has no parent directory")?;
+    fs::create_dir_all(parent).map_err(|e| sanitize_error(&format!("Failed to create directory: {}", e)))?;
+    // An empty target directory would make some init implementations bail out
+    if path.is_dir() {
+        let _ = fs::remove_dir(path);
+    }
+
+    let output = execute_with_timeout("truthgit", &["init".to_string()], Some(&parent.to_string_lossy())).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(sanitize_error(&format!("truthgit init failed: {}", stderr.trim())));
+    }
+    if !is_truth_repo(path) {
+        return Err("truthgit init finished but no repository was created".to_string());
+    }
+    Ok(())
+}
+
+/// Initialize a repo at an already-checked path; shared with the setup wizard
+pub async fn init_repo(path: &Path) -> Result<String, String> {
+    let method = init_method(path);
+    if method == "cli" {
+        init_with_cli(path).await?;
+    } else {
+        create_repo_structure(path)?;
+    }
+    log::info!("Initialized truth repository ({})", method);
+    Ok(method.to_string())
+}
+
+fn take_pending(token: &str, path: &Path) -> Result<(), String> {
+    let mut pending = PENDING_INITS.lock().map_err(|e| format!("Lock error: {}", e))?;
+    pending.retain(|_, p| p.issued_at.elapsed() < CONFIRMATION_TTL);
+    match pending.remove(token) {
+        Some(p) if p.path == path => Ok(()),
+        Some(_) => Err("Confirmation token was issued for a different path".to_string()),
+        None => Err("Invalid or expired confirmation token; confirm again".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn prepare_truth_repo_init(path: String) -> Result<InitPlan, String> {
+    if mock::is_active() {
+        return Err("Repository initialization is disabled in mock mode".to_string());
+    }
+    let path = check_init_target(&path)?;
+    let token = uuid::Uuid::new_v4().to_string();
+    let method = init_method(&path).to_string();
+
+    let mut pending = PENDING_INITS.lock().map_err(|e| format!("Lock error: {}", e))?;
+    pending.retain(|_, p| p.issued_at.elapsed() < CONFIRMATION_TTL);
+    pending.insert(
+        token.clone(),
+        PendingInit {
+            path: path.clone(),
+            issued_at: Instant::now(),
+        },
+    );
+
+    Ok(InitPlan {
+        path: path.to_string_lossy().to_string(),
+        confirmation_token: token,
+        expires_in_secs: CONFIRMATION_TTL.as_secs(),
+        method,
+    })
+}
+
+#[tauri::command]
+pub async fn init_truth_repo(path: String, confirmation_token: String) -> Result<InitResult, String> {
+    if mock::is_active() {
+        return Err("Repository initialization is disabled in mock mode".to_string());
+    }
+    let path = PathBuf::from(path.trim());
+    take_pending(&confirmation_token, &path)?;
+    // The directory may have changed since the token was issued
+    let path = check_init_target(&path.to_string_lossy())?;
+
+    let method = init_repo(&path).await?;
+    Ok(InitResult {
+        path: path.to_string_lossy().to_string(),
+        method,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_check_init_target() {
+        let tmp = TempDir::new("truthgit-init-target");
+        let root = tmp.path();
+        assert!(check_init_target("relative/.truth").is_err());
+        assert!(check_init_target(&root.join("new/.truth").to_string_lossy()).is_ok());
+        // Empty directory is fine
+        assert!(check_init_target(&root.to_string_lossy()).is_ok());
+
+        fs::write(root.join("notes.md"), "x").unwrap();
+        assert!(check_init_target(&root.to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_existing_repo_refused() {
+        let tmp = TempDir::new("truthgit-init-existing");
+        let root = tmp.path();
+        create_repo_structure(root).unwrap();
+        assert!(is_truth_repo(root));
+        assert!(check_init_target(&root.to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_confirmation_token_is_single_use_and_path_bound() {
+        let path = PathBuf::from("/tmp/truthgit-token-test/.truth");
+        let token = "test-token".to_string();
+        let insert = || {
+            PENDING_INITS.lock().unwrap().insert(
+                token.clone(),
+                PendingInit {
+                    path: path.clone(),
+                    issued_at: Instant::now(),
+                },
+            );
+        };
+
+        insert();
+        assert!(take_pending(&token, Path::new("/tmp/elsewhere")).is_err());
+        // A mismatched attempt consumes the token
+        assert!(take_pending(&token, &path).is_err());
+
+        insert();
+        assert!(take_pending(&token, &path).is_ok());
+        assert!(take_pending(&token, &path).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+// ==================== REPO STATE ====================
+//
+// The active truth repo is in one of four states:
+// - missing: `truth_repo_path` doesn't exist;
+// - uninitialized: it exists but has no objects/ directory;
+// - corrupt: objects/ can't be read, or audit.json doesn't parse;
+// - ready.
+// Commands that read or write the repo get its path from `ready_path`
+// (directly or via `TruthStore::active` and the claim and audit helpers), so
+// every command fails with the same message naming the state instead of its own
+// I/O error, and a damaged audit trail is never overwritten by the next
+// append. The presence checks are two stat calls and run on every call; the
+// audit trail is parsed once per repo path, again on `get_repo_state`, and
+// whenever a read finds it unparseable. Each change of state is emitted as
+// `repo://state` so the frontend can show one recovery screen. Setup, repo
+// switching, restore and import don't require a ready repo: they are how one
+// gets out of the other states.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+
+use crate::{get_truth_path, mock, repo_crypto, run_blocking, sanitize_error, AuditEntry};
+
+pub const REPO_STATE_EVENT: &str = "repo://state";
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+/// Last state reported, so only changes are emitted
+static CURRENT: Mutex<Option<RepoStatus>> = Mutex::new(None);
+/// Repo whose audit trail was last parsed, and what was wrong with it
+static CHECKED: Mutex<Option<(PathBuf, Option<String>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepoState {
+    Missing,
+    Uninitialized,
+    Corrupt,
+    Ready,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepoStatus {
+    pub state: RepoState,
+    pub path: String,
+    /// What is wrong, for every state but ready
+    pub detail: Option<String>,
+}
+
+impl RepoStatus {
+    fn new(path: &Path, state: RepoState, detail: Option<String>) -> Self {
+        Self {
+            state,
+            path: path.to_string_lossy().to_string(),
+            detail: detail.map(|d| sanitize_error(&d)),
+        }
+    }
+
+    /// The error commands fail with while the repo isn't ready
+    fn error(&self) -> String {
+        let detail = self.detail.as_deref().unwrap_or_default();
+        match self.state {
+            RepoState::Missing => format!("The truth repository is missing: {}", detail),
+            RepoState::Uninitialized => format!("The truth repository is not initialized: {}", detail),
+            RepoState::Corrupt => format!("The truth repository is damaged: {}", detail),
+            RepoState::Ready => String::new(),
+        }
+    }
+}
+
+/// The cheap checks: does the repo and its objects/ directory exist
+fn presence(path: &Path) -> Option<(RepoState, String)> {
+    if !path.exists() {
+        return Some((RepoState::Missing, format!("{} does not exist", path.display())));
+    }
+    let objects = path.join(crate::truth_store::OBJECTS_DIR);
+    if !objects.is_dir() {
+        return Some((
+            RepoState::Uninitialized,
+            format!("{} has no objects/ directory", path.display()),
+        ));
+    }
+    if let Err(e) = fs::read_dir(&objects) {
+        return Some((RepoState::Corrupt, format!("objects/ can't be read: {}", e)));
+    }
+    None
+}
+
+/// What is wrong with audit.json, if anything. A sealed trail that can't be
+/// opened is locked, not damaged.
+fn audit_damage(path: &Path) -> Option<String> {
+    let audit_file = path.join("audit.json");
+    let content = match fs::read(&audit_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => return Some(format!("audit.json can't be read: {}", e)),
+    };
+    if repo_crypto::is_sealed(&content) {
+        return None;
+    }
+    serde_json::from_slice::<Vec<AuditEntry>>(&content)
+        .err()
+        .map(|e| format!("audit.json doesn't parse: {}", e))
+}
+
+fn status_of(path: &Path, damage: Option<String>) -> RepoStatus {
+    match presence(path) {
+        Some((state, detail)) => RepoStatus::new(path, state, Some(detail)),
+        None => match damage {
+            Some(detail) => RepoStatus::new(path, RepoState::Corrupt, Some(detail)),
+            None => RepoStatus::new(path, RepoState::Ready, None),
+        },
+    }
+}
+
+/// Remember `status` and emit it if it differs from the last one
+fn update(status: &RepoStatus) {
+    let changed = {
+        let mut current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+        let changed = current.as_ref() != Some(status);
+        *current = Some(status.clone());
+        changed
+    };
+    if !changed {
+        return;
+    }
+    if status.state != RepoState::Ready {
+        log::warn!("{}", status.error());
+    }
+    if let Some(app) = APP.get() {
+        if let Err(e) = app.emit(REPO_STATE_EVENT, status) {
+            log::warn!("Failed to emit {}: {}", REPO_STATE_EVENT, e);
+        }
+    }
+}
+
+/// Check the repo at `path`, parsing the audit trail if `full` or if it
+/// hasn't been parsed for this repo yet
+fn check(path: &Path, full: bool) -> RepoStatus {
+    let damage = {
+        let mut checked = CHECKED.lock().unwrap_or_else(|e| e.into_inner());
+        match checked.as_ref().filter(|(p, _)| !full && p == path) {
+            Some((_, damage)) => damage.clone(),
+            None => {
+                if presence(path).is_some() {
+                    None
+                } else {
+                    let damage = audit_damage(path);
+                    *checked = Some((path.to_path_buf(), damage.clone()));
+                    damage
+                }
+            }
+        }
+    };
+    let status = status_of(path, damage);
+    update(&status);
+    status
+}
+
+fn check_active(full: bool) -> Result<RepoStatus, String> {
+    let path = get_truth_path().ok_or("Could not find home directory")?;
+    Ok(check(&path, full))
+}
+
+/// Path of the active repo, or an error naming its state unless it is ready
+pub fn ready_path() -> Result<PathBuf, String> {
+    let path = get_truth_path().ok_or("Could not find home directory")?;
+    if mock::is_active() {
+        return Ok(path);
+    }
+    let status = check(&path, false);
+    if status.state != RepoState::Ready {
+        return Err(status.error());
+    }
+    Ok(path)
+}
+
+/// Record that the active repo turned out to be damaged while reading it
+pub fn report_corrupt(detail: &str) {
+    let Some(path) = get_truth_path() else {
+        return;
+    };
+    *CHECKED.lock().unwrap_or_else(|e| e.into_inner()) = Some((path.clone(), Some(detail.to_string())));
+    update(&status_of(&path, Some(detail.to_string())));
+}
+
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+    if mock::is_active() {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        if let Err(e) = run_blocking(|| check_active(true)).await {
+            log::warn!("Failed to check the truth repository: {}", e);
+        }
+    });
+}
+
+/// State of the active repo, checked from scratch
+#[tauri::command]
+pub async fn get_repo_state() -> Result<RepoStatus, String> {
+    if mock::is_active() {
+        let path = get_truth_path().ok_or("Could not find home directory")?;
+        return Ok(RepoStatus::new(&path, RepoState::Ready, None));
+    }
+    run_blocking(|| check_active(true)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_repo_states() {
+        let tmp = TempDir::new("truthgit-state");
+        let repo = tmp.path().join(".truth");
+        let state = |damage: Option<String>| status_of(&repo, damage).state;
+        assert_eq!(state(None), RepoState::Missing);
+        fs::create_dir_all(&repo).unwrap();
+        assert_eq!(state(None), RepoState::Uninitialized);
+        fs::create_dir_all(repo.join("objects")).unwrap();
+        assert_eq!(state(audit_damage(&repo)), RepoState::Ready);
+
+        fs::write(repo.join("audit.json"), "[{\"id\": ").unwrap();
+        let damaged = status_of(&repo, audit_damage(&repo));
+        assert_eq!(damaged.state, RepoState::Corrupt);
+        assert!(damaged
+            .error()
+            .starts_with("The truth repository is damaged: audit.json doesn't parse"));
+        fs::write(repo.join("audit.json"), "[]").unwrap();
+        assert_eq!(state(audit_damage(&repo)), RepoState::Ready);
+    }
+}
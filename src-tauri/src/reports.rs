@@ -0,0 +1,450 @@
+// ==================== GOVERNANCE REPORTS ====================
+//
+// Renders verifications, summary stats and recent audit entries into a
+// standalone report for stakeholders. HTML goes through a Jinja template
+// (templates/report.html, or report_template.html in the config directory
+// when the user has customized it); PDF is laid out directly with printpdf
+// from the same data. Every report carries a digest of its data signed with
+// the receipt key, so a copy can be tied back to who produced it.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::Signer;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::exports::VerificationFilter;
+use crate::identity::{self, Actor};
+use crate::json_store::config_path;
+use crate::verification::Verification;
+use crate::{
+    get_truth_path, i18n, metrics, mock, read_audit_trail, read_verifications, receipts, run_blocking, sanitize_error,
+    AuditEntry,
+};
+
+const DEFAULT_TEMPLATE: &str = include_str!("../templates/report.html");
+const DEFAULT_TITLE: &str = "TruthGit Governance Report";
+const MAX_TEMPLATE_SIZE: u64 = 1024 * 1024;
+/// Audit entries quoted at the end of a report, newest first
+const AUDIT_EXCERPT_LEN: usize = 20;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportFilter {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub verifications: VerificationFilter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Pdf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerdictRow {
+    pub claim: String,
+    pub claim_hash: String,
+    pub domain: String,
+    pub verdict: String,
+    pub confidence: Option<f64>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReportStats {
+    pub verifications: usize,
+    pub claims: usize,
+    pub average_confidence: Option<f64>,
+    pub by_verdict: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportSignature {
+    pub key_id: String,
+    /// SHA-256 of the report data (everything but this signature), hex
+    pub digest: String,
+    /// Ed25519 signature over the digest, base64url
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportData {
+    pub title: String,
+    pub generated_at: String,
+    pub generated_by: Option<Actor>,
+    /// Repository folder name
+    pub repo: String,
+    pub filter: ReportFilter,
+    pub stats: ReportStats,
+    pub verdicts: Vec<VerdictRow>,
+    pub audit: Vec<AuditEntry>,
+    pub signature: Option<ReportSignature>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedReport {
+    pub path: String,
+    pub claims: usize,
+    pub custom_template: bool,
+    pub key_id: Option<String>,
+}
+
+/// Collect report data from verifications (newest first) and the audit trail
+fn build_report(
+    filter: ReportFilter,
+    verifications: &[Verification],
+    audit: Vec<AuditEntry>,
+    repo: String,
+) -> ReportData {
+    let selection = &filter.verifications;
+    let matching: Vec<&Verification> = verifications.iter().filter(|v| selection.matches(v)).collect();
+
+    let mut stats = ReportStats {
+        verifications: matching.len(),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    let mut verdicts = Vec::new();
+    for verification in &matching {
+        // Newest first, so the first verification of a claim is its current verdict
+        if !seen.insert(verification.claim_hash.as_str()) {
+            continue;
+        }
+        verdicts.push(VerdictRow {
+            claim: verification.claim.clone(),
+            claim_hash: verification.claim_hash.clone(),
+            domain: verification.domain.clone(),
+            verdict: verification.verdict.clone(),
+            confidence: verification.confidence,
+            timestamp: verification.timestamp.clone(),
+        });
+    }
+    stats.claims = verdicts.len();
+    for row in &verdicts {
+        *stats.by_verdict.entry(row.verdict.clone()).or_default() += 1;
+    }
+    let confidences: Vec<f64> = verdicts.iter().filter_map(|r| r.confidence).collect();
+    stats.average_confidence =
+        (!confidences.is_empty()).then(|| confidences.iter().sum::<f64>() / confidences.len() as f64);
+
+    let mut audit: Vec<AuditEntry> = audit
+        .into_iter()
+        .filter(|e| selection.matches_domain(&e.domain) && selection.in_range(&e.timestamp))
+        .collect();
+    audit.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    audit.truncate(AUDIT_EXCERPT_LEN);
+
+    ReportData {
+        title: filter
+            .title
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_TITLE.to_string()),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        generated_by: identity::current(),
+        repo,
+        filter,
+        stats,
+        verdicts,
+        audit,
+        signature: None,
+    }
+}
+
+fn sign_report(report: &mut ReportData, key: &ed25519_dalek::SigningKey) -> Result<(), String> {
+    report.signature = None;
+    let data = serde_json::to_vec(report).map_err(|e| format!("Failed to encode report: {}", e))?;
+    let digest = format!("{:x}", Sha256::digest(&data));
+    report.signature = Some(ReportSignature {
+        key_id: identity::fingerprint(key.verifying_key().as_bytes()),
+        signature: URL_SAFE_NO_PAD.encode(key.sign(digest.as_bytes()).to_bytes()),
+        digest,
+    });
+    Ok(())
+}
+
+/// The template to render with: an explicit path, else the user's
+/// customized template, else the built-in one. The flag is true for custom.
+fn load_template(path: Option<String>) -> Result<(String, bool), String> {
+    let path = match path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let custom = config_path("report_template.html");
+            if !custom.exists() {
+                return Ok((DEFAULT_TEMPLATE.to_string(), false));
+            }
+            custom
+        }
+    };
+    let size = std::fs::metadata(&path)
+        .map_err(|e| sanitize_error(&format!("Failed to read report template: {}", e)))?
+        .len();
+    if size > MAX_TEMPLATE_SIZE {
+        return Err("Report template is too large".to_string());
+    }
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| sanitize_error(&format!("Failed to read report template: {}", e)))?;
+    Ok((source, true))
+}
+
+fn render_html(report: &ReportData, template: &str) -> Result<String, String> {
+    let mut env = minijinja::Environment::new();
+    // The .html name turns on auto-escaping
+    env.add_template("report.html", template)
+        .map_err(|e| format!("Report template error: {}", e))?;
+    env.get_template("report.html")
+        .and_then(|t| t.render(report))
+        .map_err(|e| format!("Report template error: {}", e))
+}
+
+/// Break `text` into lines of at most `width` characters at spaces
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// A line of PDF text: (text, size, bold, space before)
+pub type PdfLine = (String, f32, bool, f32);
+
+/// Characters per line of 10pt text on an A4 page
+pub const PDF_WRAP: usize = 95;
+
+/// Lay `lines` out top to bottom on A4 pages, starting a page when one fills
+pub fn layout_pdf(title: &str, lines: Vec<PdfLine>) -> Result<Vec<u8>, String> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+    const PAGE_WIDTH: f32 = 210.0;
+    const PAGE_HEIGHT: f32 = 297.0;
+    const MARGIN: f32 = 20.0;
+
+    let pdf_error = |e: printpdf::Error| format!("Failed to render PDF: {}", e);
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+    let regular = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(pdf_error)?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(pdf_error)?;
+    let mut layer = doc.get_page(page).get_layer(layer);
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for (text, size, is_bold, before) in lines {
+        let height = size * 0.3528 * 1.4 + before;
+        if y - height < MARGIN {
+            let (page, new_layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+            layer = doc.get_page(page).get_layer(new_layer);
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        y -= height;
+        layer.use_text(text, size, Mm(MARGIN), Mm(y), if is_bold { &bold } else { &regular });
+    }
+    doc.save_to_bytes().map_err(pdf_error)
+}
+
+fn render_pdf(report: &ReportData) -> Result<Vec<u8>, String> {
+    let mut lines: Vec<PdfLine> = vec![(report.title.clone(), 18.0, true, 0.0)];
+    let byline = report
+        .generated_by
+        .as_ref()
+        .map(|a| i18n::tr("report.generated_by", &[("name", &a.name)]))
+        .unwrap_or_default();
+    lines.push((
+        i18n::tr(
+            "report.generated",
+            &[("at", &report.generated_at), ("by", &byline), ("repo", &report.repo)],
+        ),
+        9.0,
+        false,
+        2.0,
+    ));
+
+    lines.push((i18n::tr("report.summary", &[]), 13.0, true, 8.0));
+    let mut summary = i18n::tr(
+        "report.counts",
+        &[
+            ("verifications", &report.stats.verifications.to_string()),
+            ("claims", &report.stats.claims.to_string()),
+        ],
+    );
+    if let Some(average) = report.stats.average_confidence {
+        let percent = format!("{:.0}", average * 100.0);
+        summary.push_str(&i18n::tr("report.average_confidence", &[("percent", &percent)]));
+    }
+    lines.push((summary, 10.0, false, 2.0));
+    for (verdict, count) in &report.stats.by_verdict {
+        lines.push((format!("{}: {}", verdict, count), 10.0, false, 0.0));
+    }
+
+    lines.push((i18n::tr("report.verdicts", &[]), 13.0, true, 8.0));
+    for row in &report.verdicts {
+        let confidence = row
+            .confidence
+            .map(|c| format!(" · {:.0}%", c * 100.0))
+            .unwrap_or_default();
+        lines.push((
+            format!("{}{} · {} · {}", row.verdict, confidence, row.domain, row.timestamp),
+            10.0,
+            true,
+            3.0,
+        ));
+        for line in wrap(&row.claim, PDF_WRAP) {
+            lines.push((line, 10.0, false, 0.0));
+        }
+        lines.push((row.claim_hash.clone(), 7.0, false, 0.0));
+    }
+
+    if !report.audit.is_empty() {
+        lines.push((i18n::tr("report.audit", &[]), 13.0, true, 8.0));
+        for entry in &report.audit {
+            let by = entry
+                .actor
+                .as_ref()
+                .map(|a| format!(" · {}", a.name))
+                .unwrap_or_default();
+            let summary = format!(
+                "{} · {} · {} {}{} · {}",
+                entry.timestamp, entry.action, entry.result_status, entry.result_action, by, entry.claim
+            );
+            for (i, line) in wrap(&summary, PDF_WRAP).into_iter().enumerate() {
+                lines.push((line, 9.0, false, if i == 0 { 2.0 } else { 0.0 }));
+            }
+        }
+    }
+
+    lines.push(("Signature".to_string(), 13.0, true, 8.0));
+    match &report.signature {
+        Some(signature) => {
+            lines.push((format!("Key {}", signature.key_id), 9.0, false, 2.0));
+            lines.push((format!("Digest {}", signature.digest), 7.0, false, 0.0));
+            lines.push((format!("Signature {}", signature.signature), 7.0, false, 0.0));
+        }
+        None => lines.push(("This report is not signed.".to_string(), 9.0, false, 2.0)),
+    }
+    layout_pdf(&report.title, lines)
+}
+
+/// Render a report of the verifications matching `filter` to `path`.
+/// `template` overrides the HTML template for this report only.
+#[tauri::command]
+pub async fn generate_report(
+    filter: Option<ReportFilter>,
+    format: ReportFormat,
+    path: String,
+    template: Option<String>,
+) -> Result<GeneratedReport, String> {
+    let started = Instant::now();
+    let generated = run_blocking(move || {
+        let verifications = read_verifications()?.verifications;
+        let audit = read_audit_trail()?;
+        let repo = if mock::is_active() {
+            "mock".to_string()
+        } else {
+            get_truth_path()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_default()
+        };
+        let mut report = build_report(filter.unwrap_or_default(), &verifications, audit, repo);
+        sign_report(&mut report, &receipts::load_or_create_key()?)?;
+
+        let (bytes, custom_template) = match format {
+            ReportFormat::Html => {
+                let (template, custom) = load_template(template)?;
+                (render_html(&report, &template)?.into_bytes(), custom)
+            }
+            ReportFormat::Pdf => (render_pdf(&report)?, false),
+        };
+        std::fs::write(&path, bytes).map_err(|e| sanitize_error(&format!("Failed to write report: {}", e)))?;
+        log::info!("Generated {:?} report with {} claims", format, report.stats.claims);
+        Ok(GeneratedReport {
+            path,
+            claims: report.stats.claims,
+            custom_template,
+            key_id: report.signature.map(|s| s.key_id),
+        })
+    })
+    .await;
+    metrics::record("report", started, generated.is_ok());
+    generated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifications() -> Vec<Verification> {
+        [
+            serde_json::json!({ "claim_hash": "aa", "claim": "<b>Water</b> boils", "domain": "science",
+                "verdict": "PASSED", "confidence": 0.9, "timestamp": "2026-02-01T10:00:00Z" }),
+            serde_json::json!({ "claim_hash": "aa", "claim": "<b>Water</b> boils", "domain": "science",
+                "verdict": "GAP", "confidence": 0.5, "timestamp": "2026-01-10T10:00:00Z" }),
+            serde_json::json!({ "claim_hash": "bb", "claim": "Contracts need signatures", "domain": "legal",
+                "status": "FAILED", "confidence": 0.3, "timestamp": "2026-01-05T10:00:00Z" }),
+        ]
+        .into_iter()
+        .map(Verification::from)
+        .collect()
+    }
+
+    #[test]
+    fn test_build_report() {
+        let report = build_report(
+            ReportFilter::default(),
+            &verifications(),
+            Vec::new(),
+            "repo".to_string(),
+        );
+        assert_eq!((report.stats.verifications, report.stats.claims), (3, 2));
+        // Latest verdict per claim
+        assert_eq!(report.verdicts[0].verdict, "PASSED");
+        assert_eq!(report.stats.by_verdict.get("FAILED"), Some(&1));
+        assert!((report.stats.average_confidence.unwrap() - 0.6).abs() < 1e-9);
+
+        let filter = ReportFilter {
+            title: None,
+            verifications: VerificationFilter {
+                domain: Some("Science".to_string()),
+                until: Some("2026-01".to_string()),
+                ..Default::default()
+            },
+        };
+        let report = build_report(filter, &verifications(), Vec::new(), "repo".to_string());
+        assert_eq!(report.verdicts.len(), 1);
+        assert_eq!(report.verdicts[0].verdict, "GAP");
+    }
+
+    #[test]
+    fn test_render_report() {
+        let mut report = build_report(
+            ReportFilter::default(),
+            &verifications(),
+            Vec::new(),
+            "repo".to_string(),
+        );
+        sign_report(&mut report, &ed25519_dalek::SigningKey::from_bytes(&[5; 32])).unwrap();
+        let html = render_html(&report, DEFAULT_TEMPLATE).unwrap();
+        assert!(html.contains(DEFAULT_TITLE));
+        assert!(
+            html.contains("&lt;b&gt;Water") && !html.contains("<b>Water"),
+            "claim text must be escaped"
+        );
+        assert!(html.contains(&report.signature.as_ref().unwrap().digest));
+
+        let pdf = render_pdf(&report).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        assert!(render_html(&report, "{% if %}").is_err());
+    }
+}
@@ -0,0 +1,232 @@
+// ==================== TRUTH REPO REGISTRY ====================
+//
+// Several truth repos can be registered (e.g. one per project) and one is
+// active at a time. The active repo is `settings.truth_repo_path`, which every
+// claim, verification and audit command already reads, so switching repos is
+// a settings change. Derived data (caches, indexes) lives per repo under the
+// user cache dir so repos never see each other's state.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::json_store::{cache_path, config_path, load_json, save_json, StoreLock};
+use crate::setup::is_truth_repo;
+use crate::{commit_settings, consent, count_claims, run_blocking, settings_events, AppSettings, SETTINGS};
+
+static REPOS_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredRepo {
+    pub name: String,
+    pub path: String,
+    pub added_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TruthRepoEntry {
+    pub name: String,
+    pub path: String,
+    pub active: bool,
+    pub exists: bool,
+    pub claims_count: usize,
+}
+
+fn repos_path() -> PathBuf {
+    config_path("repos.json")
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn default_repo_name(path: &Path) -> String {
+    // A `.truth` folder is named after the project that contains it
+    let named = if path.file_name().map(|n| n == ".truth").unwrap_or(false) {
+        path.parent()
+    } else {
+        Some(path)
+    };
+    named
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+/// Cache directory for data derived from one repo, keyed by its path
+pub fn repo_cache_dir(repo: &Path) -> PathBuf {
+    let key = repo.canonicalize().unwrap_or_else(|_| repo.to_path_buf());
+    let digest = Sha256::digest(key.to_string_lossy().as_bytes());
+    let id: String = digest.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    cache_path("repos").join(id)
+}
+
+/// Registered repos plus the active one, which is always listed even if it
+/// was configured directly in settings
+fn entries(registered: &[RegisteredRepo], active: &Path) -> Vec<TruthRepoEntry> {
+    let mut entries: Vec<TruthRepoEntry> = registered
+        .iter()
+        .map(|repo| {
+            let path = Path::new(&repo.path);
+            TruthRepoEntry {
+                name: repo.name.clone(),
+                path: repo.path.clone(),
+                active: same_path(path, active),
+                exists: is_truth_repo(path),
+                claims_count: count_claims(path),
+            }
+        })
+        .collect();
+
+    if !entries.iter().any(|e| e.active) {
+        entries.insert(
+            0,
+            TruthRepoEntry {
+                name: default_repo_name(active),
+                path: active.to_string_lossy().to_string(),
+                active: true,
+                exists: is_truth_repo(active),
+                claims_count: count_claims(active),
+            },
+        );
+    }
+    entries
+}
+
+fn active_repo() -> Result<PathBuf, String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(PathBuf::from(&settings.truth_repo_path))
+}
+
+#[tauri::command]
+pub async fn list_truth_repos() -> Result<Vec<TruthRepoEntry>, String> {
+    let registered: Vec<RegisteredRepo> = {
+        let _guard = REPOS_LOCK.lock(&repos_path())?;
+        load_json(&repos_path())?
+    };
+    let active = active_repo()?;
+    // Counting claims walks every repo's objects
+    run_blocking(move || Ok(entries(&registered, &active))).await
+}
+
+#[tauri::command]
+pub async fn add_truth_repo(path: String, name: Option<String>) -> Result<Vec<TruthRepoEntry>, String> {
+    let repo_path = PathBuf::from(path.trim());
+    if !repo_path.is_absolute() {
+        return Err("Repository path must be absolute".to_string());
+    }
+    if !is_truth_repo(&repo_path) {
+        return Err("Not a truth repository (no objects/ directory)".to_string());
+    }
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| default_repo_name(&repo_path));
+
+    let registered = {
+        let _guard = REPOS_LOCK.lock(&repos_path())?;
+        let mut registered: Vec<RegisteredRepo> = load_json(&repos_path())?;
+        if registered.iter().any(|r| same_path(Path::new(&r.path), &repo_path)) {
+            return Err("Repository is already registered".to_string());
+        }
+        registered.push(RegisteredRepo {
+            name,
+            path: repo_path.to_string_lossy().to_string(),
+            added_at: chrono::Utc::now().to_rfc3339(),
+        });
+        save_json(&repos_path(), &registered)?;
+        registered
+    };
+    Ok(entries(&registered, &active_repo()?))
+}
+
+/// Unregister a repo (its files are left alone) and drop its caches
+#[tauri::command]
+pub async fn remove_truth_repo(path: String) -> Result<Vec<TruthRepoEntry>, String> {
+    let repo_path = PathBuf::from(path.trim());
+    if same_path(&repo_path, &active_repo()?) {
+        return Err("Cannot remove the active repository; switch to another one first".to_string());
+    }
+
+    let registered = {
+        let _guard = REPOS_LOCK.lock(&repos_path())?;
+        let mut registered: Vec<RegisteredRepo> = load_json(&repos_path())?;
+        let before = registered.len();
+        registered.retain(|r| !same_path(Path::new(&r.path), &repo_path));
+        if registered.len() == before {
+            return Err("Repository is not registered".to_string());
+        }
+        save_json(&repos_path(), &registered)?;
+        registered
+    };
+
+    let _ = std::fs::remove_dir_all(repo_cache_dir(&repo_path));
+    Ok(entries(&registered, &active_repo()?))
+}
+
+#[tauri::command]
+pub async fn switch_truth_repo(app: tauri::AppHandle, path: String) -> Result<AppSettings, String> {
+    let repo_path = PathBuf::from(path.trim());
+    {
+        let _guard = REPOS_LOCK.lock(&repos_path())?;
+        let registered: Vec<RegisteredRepo> = load_json(&repos_path())?;
+        if !registered.iter().any(|r| same_path(Path::new(&r.path), &repo_path)) {
+            return Err("Repository is not registered; add it first".to_string());
+        }
+    }
+    if !is_truth_repo(&repo_path) {
+        return Err("Repository no longer exists at this path".to_string());
+    }
+    let switched = |settings: &AppSettings| AppSettings {
+        truth_repo_path: repo_path.to_string_lossy().to_string(),
+        ..settings.clone()
+    };
+    let preview = switched(&*SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?);
+    consent::require_path_settings(&preview).await?;
+
+    let updated = {
+        let mut settings = SETTINGS.write().map_err(|e| format!("Lock error: {}", e))?;
+        let new_settings = switched(&settings);
+        commit_settings(&mut settings, new_settings)?;
+        settings.clone()
+    };
+
+    settings_events::emit_changed(&app, "repo", &updated);
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_repo_name() {
+        assert_eq!(default_repo_name(Path::new("/work/acme/.truth")), "acme");
+        assert_eq!(default_repo_name(Path::new("/data/claims-repo")), "claims-repo");
+    }
+
+    #[test]
+    fn test_cache_dirs_are_distinct_per_repo() {
+        let a = repo_cache_dir(Path::new("/work/a/.truth"));
+        let b = repo_cache_dir(Path::new("/work/b/.truth"));
+        assert_ne!(a, b);
+        assert_eq!(a, repo_cache_dir(Path::new("/work/a/.truth")));
+    }
+
+    #[test]
+    fn test_active_repo_always_listed() {
+        let registered = vec![RegisteredRepo {
+            name: "other".to_string(),
+            path: "/work/other/.truth".to_string(),
+            added_at: String::new(),
+        }];
+        let listed = entries(&registered, Path::new("/work/current/.truth"));
+        assert_eq!(listed.len(), 2);
+        assert!(listed[0].active);
+        assert_eq!(listed[0].name, "current");
+        assert!(!listed[1].active);
+    }
+}
@@ -0,0 +1,375 @@
+// ==================== RETENTION ====================
+//
+// Governance data is never dropped without a copy. Before the audit trail
+// is trimmed to `retention.audit_days`, or old changes fall off the end of
+// the undo journal (which holds the last state of removed evidence and
+// sources), the records are written to a dated archive file, and the
+// archival itself is recorded in the audit trail. Archives live in
+// `retention.archive_dir`, by default `archives/` in the truth repo, and are
+// sealed like the rest of the repo when encryption at rest is on.
+// `restore_from_archive` merges an audit archive back into the trail.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::repo_crypto::Binding;
+use crate::{
+    append_audit_entry, get_truth_path, import_audit_entries, json_store, mock, read_audit_trail, repo_crypto,
+    repo_state, run_blocking, sanitize_error, AuditEntry, AUDIT_LOCK, SETTINGS,
+};
+
+pub const ARCHIVE_ACTION: &str = "retention_archive";
+pub const RESTORE_ACTION: &str = "retention_restore";
+
+const ARCHIVE_VERSION: u32 = 1;
+const SCHEDULER_TICK: Duration = Duration::from_secs(6 * 60 * 60);
+/// Let startup settle before the first pass
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    /// Audit entries older than this many days are archived and removed;
+    /// 0 keeps them forever
+    pub audit_days: u32,
+    /// Folder for archive files; empty uses `archives/` in the truth repo
+    pub archive_dir: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveKind {
+    Audit,
+    Journal,
+}
+
+impl ArchiveKind {
+    fn label(self) -> &'static str {
+        match self {
+            ArchiveKind::Audit => "audit",
+            ArchiveKind::Journal => "journal",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Archive {
+    version: u32,
+    kind: ArchiveKind,
+    created_at: String,
+    /// Why the records were removed
+    reason: String,
+    records: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchiveInfo {
+    pub file_name: String,
+    pub kind: ArchiveKind,
+    pub created_at: String,
+    pub reason: String,
+    pub records: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetentionResult {
+    pub archived: usize,
+    pub archive: Option<ArchiveInfo>,
+}
+
+pub fn archive_dir(truth_path: &Path) -> PathBuf {
+    let configured = SETTINGS
+        .read()
+        .map(|s| s.retention.archive_dir.trim().to_string())
+        .unwrap_or_default();
+    if configured.is_empty() {
+        truth_path.join("archives")
+    } else {
+        PathBuf::from(configured)
+    }
+}
+
+/// `audit-20260331T120000Z-1a2b3c4d.json`; the suffix keeps two archives
+/// written in the same second apart
+fn archive_file_name(kind: ArchiveKind, now: chrono::DateTime<chrono::Utc>) -> String {
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    format!("{}-{}-{}.json", kind.label(), now.format("%Y%m%dT%H%M%SZ"), suffix)
+}
+
+/// Archive file names only; anything that could leave the folder is refused
+fn is_archive_name(name: &str) -> bool {
+    (name.starts_with("audit-") || name.starts_with("journal-"))
+        && name.ends_with(".json")
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        && !name.contains("..")
+}
+
+/// Write `records` to a new archive file and return what was written. The
+/// caller removes the records only after this succeeds.
+fn write_archive(
+    truth_path: &Path,
+    kind: ArchiveKind,
+    reason: &str,
+    records: Vec<serde_json::Value>,
+) -> Result<ArchiveInfo, String> {
+    let now = chrono::Utc::now();
+    let dir = archive_dir(truth_path);
+    fs::create_dir_all(&dir).map_err(|e| sanitize_error(&format!("Failed to create archive folder: {}", e)))?;
+    let archive = Archive {
+        version: ARCHIVE_VERSION,
+        kind,
+        created_at: now.to_rfc3339(),
+        reason: reason.to_string(),
+        records,
+    };
+    let json = serde_json::to_vec_pretty(&archive).map_err(|e| format!("Failed to serialize archive: {}", e))?;
+    let file_name = archive_file_name(kind, now);
+    let content = repo_crypto::seal(truth_path, Binding::File(&file_name), json)?;
+    json_store::write_atomic(&dir.join(&file_name), &content)
+        .map_err(|e| sanitize_error(&format!("Failed to write archive: {}", e)))?;
+    log::info!(
+        "Archived {} {} records to {}",
+        archive.records.len(),
+        kind.label(),
+        file_name
+    );
+    Ok(ArchiveInfo {
+        file_name,
+        kind,
+        created_at: archive.created_at,
+        reason: archive.reason,
+        records: archive.records.len(),
+        size_bytes: content.len() as u64,
+    })
+}
+
+fn read_archive(truth_path: &Path, file_name: &str) -> Result<Archive, String> {
+    let path = archive_dir(truth_path).join(file_name);
+    let content = fs::read(path).map_err(|e| sanitize_error(&format!("Failed to read archive: {}", e)))?;
+    let content = repo_crypto::open(truth_path, Binding::File(file_name), &content)?;
+    serde_json::from_slice(&content).map_err(|e| format!("Invalid archive: {}", e))
+}
+
+/// Audit entry recording an archival or restore
+fn audit_record(action: &str, claim: String, archive: &ArchiveInfo) -> AuditEntry {
+    AuditEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        action: action.to_string(),
+        claim,
+        result_status: archive.records.to_string(),
+        result_action: archive.file_name.clone(),
+        actor: crate::identity::current(),
+        app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        ..Default::default()
+    }
+}
+
+/// Archive records about to be dropped from a store other than the audit
+/// trail, and note the archival in the trail
+pub(crate) fn archive_records(
+    truth_path: &Path,
+    kind: ArchiveKind,
+    reason: &str,
+    records: Vec<serde_json::Value>,
+) -> Result<ArchiveInfo, String> {
+    let info = write_archive(truth_path, kind, reason, records)?;
+    let claim = format!("Archived {} {} records: {}", info.records, kind.label(), reason);
+    if let Err(e) = append_audit_entry(audit_record(ARCHIVE_ACTION, claim, &info)) {
+        log::warn!("Failed to record archival in the audit trail: {}", e);
+    }
+    Ok(info)
+}
+
+/// Entries older than the cutoff, and the rest; the trail is newest first
+fn split_expired(entries: Vec<AuditEntry>, cutoff: &str) -> (Vec<AuditEntry>, Vec<AuditEntry>) {
+    entries
+        .into_iter()
+        .partition(|e| e.timestamp.as_str() < cutoff && e.action != ARCHIVE_ACTION)
+}
+
+/// Archive and remove audit entries older than `audit_days`. Archival
+/// records are kept, so the trail always shows where removed entries went.
+fn apply_audit_retention(audit_days: u32) -> Result<RetentionResult, String> {
+    let nothing = RetentionResult {
+        archived: 0,
+        archive: None,
+    };
+    if audit_days == 0 {
+        return Ok(nothing);
+    }
+    let truth_path = repo_state::ready_path()?;
+    let audit_file = truth_path.join("audit.json");
+    let _guard = AUDIT_LOCK.lock(&audit_file)?;
+
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(i64::from(audit_days))).to_rfc3339();
+    let (expired, mut kept) = split_expired(read_audit_trail()?, &cutoff);
+    if expired.is_empty() {
+        return Ok(nothing);
+    }
+    let records = expired
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to serialize audit entries: {}", e))?;
+    let reason = format!("audit entries older than {} days", audit_days);
+    let info = write_archive(&truth_path, ArchiveKind::Audit, &reason, records)?;
+
+    let claim = format!("Archived {} audit entries older than {} days", info.records, audit_days);
+    kept.insert(0, audit_record(ARCHIVE_ACTION, claim, &info));
+    let content = serde_json::to_string_pretty(&kept).map_err(|e| format!("Failed to serialize audit: {}", e))?;
+    let content = repo_crypto::seal(&truth_path, repo_crypto::AUDIT, content.into_bytes())?;
+    json_store::write_atomic(&audit_file, &content).map_err(|e| format!("Failed to write audit file: {}", e))?;
+    Ok(RetentionResult {
+        archived: info.records,
+        archive: Some(info),
+    })
+}
+
+/// Apply the audit retention period every few hours while it is set
+pub fn start() {
+    tauri::async_runtime::spawn(async {
+        tokio::time::sleep(FIRST_RUN_DELAY).await;
+        loop {
+            let audit_days = SETTINGS.read().map(|s| s.retention.audit_days).unwrap_or(0);
+            if audit_days > 0 && !mock::is_active() {
+                match run_blocking(move || apply_audit_retention(audit_days)).await {
+                    Ok(result) if result.archived > 0 => {
+                        log::info!("Retention archived {} audit entries", result.archived)
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("Audit retention failed: {}", e),
+                }
+            }
+            tokio::time::sleep(SCHEDULER_TICK).await;
+        }
+    });
+}
+
+/// Apply the audit retention period now
+#[tauri::command]
+pub async fn apply_retention() -> Result<RetentionResult, String> {
+    if mock::is_active() {
+        return Err("Retention is disabled in mock mode".to_string());
+    }
+    let audit_days = SETTINGS.read().map(|s| s.retention.audit_days).unwrap_or(0);
+    run_blocking(move || apply_audit_retention(audit_days)).await
+}
+
+/// Archive files in the archive folder, newest first
+#[tauri::command]
+pub async fn list_archives() -> Result<Vec<ArchiveInfo>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(|| {
+        let truth_path = repo_state::ready_path()?;
+        let dir = archive_dir(&truth_path);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(Vec::new());
+        };
+        let mut archives: Vec<ArchiveInfo> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| is_archive_name(&e.file_name().to_string_lossy()))
+            .filter_map(|e| {
+                let file_name = e.file_name().to_string_lossy().to_string();
+                match read_archive(&truth_path, &file_name) {
+                    Ok(archive) => Some(ArchiveInfo {
+                        kind: archive.kind,
+                        created_at: archive.created_at,
+                        reason: archive.reason,
+                        records: archive.records.len(),
+                        size_bytes: e.metadata().map(|m| m.len()).unwrap_or(0),
+                        file_name,
+                    }),
+                    Err(err) => {
+                        log::warn!("Skipping unreadable archive {}: {}", file_name, err);
+                        None
+                    }
+                }
+            })
+            .collect();
+        archives.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(archives)
+    })
+    .await
+}
+
+/// Merge an audit archive back into the audit trail; entries already there
+/// are skipped. Returns how many were restored. Journal archives are kept
+/// for the record only: their changes are too old to undo.
+#[tauri::command]
+pub async fn restore_from_archive(file_name: String) -> Result<usize, String> {
+    if mock::is_active() {
+        return Err("Restoring archives is disabled in mock mode".to_string());
+    }
+    if !is_archive_name(&file_name) {
+        return Err("Invalid archive name".to_string());
+    }
+    run_blocking(move || {
+        let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+        let archive = read_archive(&truth_path, &file_name)?;
+        if archive.kind != ArchiveKind::Audit {
+            return Err("Only audit archives can be restored".to_string());
+        }
+        let entries = archive
+            .records
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<AuditEntry>, _>>()
+            .map_err(|e| format!("Invalid audit entry in archive: {}", e))?;
+        let restored = import_audit_entries(entries)?;
+        let info = ArchiveInfo {
+            file_name: file_name.clone(),
+            kind: archive.kind,
+            created_at: archive.created_at,
+            reason: archive.reason,
+            records: restored,
+            size_bytes: 0,
+        };
+        let claim = format!("Restored {} audit entries from {}", restored, file_name);
+        append_audit_entry(audit_record(RESTORE_ACTION, claim, &info))?;
+        log::info!("Restored {} audit entries from {}", restored, file_name);
+        Ok(restored)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_expired_and_names() {
+        let entry = |id: &str, timestamp: &str, action: &str| AuditEntry {
+            id: id.to_string(),
+            timestamp: timestamp.to_string(),
+            action: action.to_string(),
+            ..Default::default()
+        };
+        let entries = vec![
+            entry("new", "2026-03-30T00:00:00+00:00", "governance_verify"),
+            entry("archival", "2025-01-01T00:00:00+00:00", ARCHIVE_ACTION),
+            entry("old", "2025-01-01T00:00:00+00:00", "governance_verify"),
+        ];
+        let (expired, kept) = split_expired(entries, "2026-01-01T00:00:00+00:00");
+        assert_eq!(expired.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["old"]);
+        assert_eq!(
+            kept.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+            ["new", "archival"]
+        );
+
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-31T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let name = archive_file_name(ArchiveKind::Journal, now);
+        assert!(name.starts_with("journal-20260331T120000Z-"));
+        assert!(is_archive_name(&name));
+        assert!(!is_archive_name("../audit-x.json"));
+        assert!(!is_archive_name("audit-x.json.bak"));
+        assert!(!is_archive_name("settings.json"));
+    }
+}
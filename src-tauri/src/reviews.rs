@@ -0,0 +1,338 @@
+// ==================== CLAIM REVIEWS ====================
+//
+// A lightweight review workflow on top of claims: draft → in-review →
+// approved → retired, with a step back allowed at each stage. Claim objects
+// are immutable, so review state lives in reviews.json in the truth repo
+// next to audit.json. Each transition records who made it (the identity from
+// settings, else the OS user) and is also written to the audit trail.
+// Approval of claims covered by the two-person policy goes through
+// approvals.rs instead.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::json_store::{load_json, save_json, StoreLock};
+use crate::approvals::{self, ApprovalRequest};
+use crate::identity::{self, Actor};
+use crate::journal::{self, Store};
+use crate::{append_audit_entry, index, mock, read_claim, repo_state, run_blocking, AuditEntry};
+
+const MAX_NOTE_LEN: usize = 1000;
+const MAX_HASH_LEN: usize = 128;
+
+/// Serializes read-modify-write cycles on reviews.json
+static REVIEWS_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReviewState {
+    #[default]
+    Draft,
+    InReview,
+    Approved,
+    Retired,
+}
+
+impl ReviewState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::InReview => "in-review",
+            Self::Approved => "approved",
+            Self::Retired => "retired",
+        }
+    }
+
+    fn can_move_to(self, to: Self) -> bool {
+        use ReviewState::*;
+        matches!(
+            (self, to),
+            (Draft, InReview)
+                | (Draft, Retired)
+                | (InReview, Approved)
+                | (InReview, Draft)
+                | (Approved, Retired)
+                | (Approved, InReview)
+                | (Retired, Draft)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewTransition {
+    pub from: ReviewState,
+    pub to: ReviewState,
+    pub reviewer: Actor,
+    pub at: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// The first reviewer, on approvals that needed a second one
+    #[serde(default)]
+    pub requested_by: Option<Actor>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClaimReview {
+    pub state: ReviewState,
+    /// Who made the last transition; None for claims never reviewed
+    pub reviewer: Option<Actor>,
+    pub updated_at: Option<String>,
+    /// Oldest first
+    pub history: Vec<ReviewTransition>,
+    /// Waiting for a second reviewer; cleared by any state change
+    #[serde(default)]
+    pub pending_approval: Option<ApprovalRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewedClaim {
+    pub claim: serde_json::Value,
+    pub review: ClaimReview,
+}
+
+fn reviews_path() -> Result<PathBuf, String> {
+    let truth_path = repo_state::ready_path()?;
+    Ok(truth_path.join("reviews.json"))
+}
+
+pub(crate) fn load_reviews() -> Result<BTreeMap<String, ClaimReview>, String> {
+    let path = reviews_path()?;
+    let _guard = REVIEWS_LOCK.lock(&path)?;
+    load_json(&path)
+}
+
+pub(crate) fn validate_hash(hash: &str) -> Result<(), String> {
+    if hash.len() < 3 || hash.len() > MAX_HASH_LEN || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Invalid hash".to_string());
+    }
+    Ok(())
+}
+
+/// Load, change and save the review of `hash` under the reviews lock
+pub(crate) fn update_review(
+    hash: &str,
+    change: impl FnOnce(&mut ClaimReview) -> Result<(), String>,
+) -> Result<ClaimReview, String> {
+    let (before, review) = {
+        let path = reviews_path()?;
+        let _guard = REVIEWS_LOCK.lock(&path)?;
+        let mut reviews: BTreeMap<String, ClaimReview> = load_json(&path)?;
+        let before = reviews.get(hash).cloned();
+        let review = reviews.entry(hash.to_string()).or_default();
+        change(review)?;
+        let review = review.clone();
+        save_json(&path, &reviews)?;
+        (before, review)
+    };
+    let description = format!("Review of claim {} ({})", short_hash(hash), review.state.as_str());
+    journal::record(Store::Reviews, hash, description, before.as_ref());
+    Ok(review)
+}
+
+/// Put a claim's review back as it was (undo). The undo is itself a
+/// transition by `actor`, added to the restored history and returned for the
+/// audit trail; it isn't checked against the allowed moves.
+pub(crate) fn restore(
+    hash: &str,
+    before: Option<serde_json::Value>,
+    actor: &Actor,
+) -> Result<ReviewTransition, String> {
+    let path = reviews_path()?;
+    let _guard = REVIEWS_LOCK.lock(&path)?;
+    let mut reviews: BTreeMap<String, ClaimReview> = load_json(&path)?;
+    let from = reviews.get(hash).map(|r| r.state).unwrap_or_default();
+    journal::restore_entry(&mut reviews, hash, before)?;
+    let review = reviews.entry(hash.to_string()).or_default();
+    let step = ReviewTransition {
+        from,
+        to: review.state,
+        reviewer: actor.clone(),
+        at: chrono::Utc::now().to_rfc3339(),
+        note: Some("Undo".to_string()),
+        requested_by: None,
+    };
+    review.reviewer = Some(actor.clone());
+    review.updated_at = Some(step.at.clone());
+    review.history.push(step.clone());
+    save_json(&path, &reviews)?;
+    Ok(step)
+}
+
+/// First characters of a hash, for messages
+pub(crate) fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
+
+/// Apply a transition to `review`, checking that it is allowed
+pub(crate) fn transition(
+    review: &mut ClaimReview,
+    to: ReviewState,
+    reviewer: &Actor,
+    note: Option<String>,
+) -> Result<(), String> {
+    let from = review.state;
+    if from == to {
+        return Err(format!("Claim is already {}", to.as_str()));
+    }
+    if !from.can_move_to(to) {
+        return Err(format!("A claim can't move from {} to {}", from.as_str(), to.as_str()));
+    }
+    let at = chrono::Utc::now().to_rfc3339();
+    review.state = to;
+    review.reviewer = Some(reviewer.clone());
+    review.updated_at = Some(at.clone());
+    review.pending_approval = None;
+    review.history.push(ReviewTransition {
+        from,
+        to,
+        reviewer: reviewer.clone(),
+        at,
+        note,
+        requested_by: None,
+    });
+    Ok(())
+}
+
+/// An audit entry about `claim` by `actor`, with action and result left empty
+pub(crate) fn claim_audit_entry(claim: &serde_json::Value, hash: &str, actor: &Actor, at: &str) -> AuditEntry {
+    let field = |name: &str| claim.get(name).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let content = field("content");
+    AuditEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: at.to_string(),
+        action: String::new(),
+        claim: if content.is_empty() { hash.to_string() } else { content },
+        domain: field("domain"),
+        risk_profile: String::new(),
+        result_status: String::new(),
+        result_action: String::new(),
+        confidence: claim.get("confidence").and_then(|c| c.as_f64()).unwrap_or(0.0),
+        actor: Some(actor.clone()),
+        source: None,
+        claim_hash: Some(hash.to_string()),
+        verification_hash: None,
+        backend: None,
+        app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        duration_ms: None,
+    }
+}
+
+pub(crate) fn audit_entry(claim: &serde_json::Value, hash: &str, step: &ReviewTransition) -> AuditEntry {
+    let mut summary = format!("{} -> {} by {}", step.from.as_str(), step.to.as_str(), step.reviewer.name);
+    if let Some(requester) = &step.requested_by {
+        summary.push_str(&format!(" (requested by {})", requester.name));
+    }
+    AuditEntry {
+        action: "review".to_string(),
+        result_status: step.to.as_str().to_string(),
+        result_action: summary,
+        ..claim_audit_entry(claim, hash, &step.reviewer, &step.at)
+    }
+}
+
+/// Move a claim to a new review state, attributed to the current identity
+#[tauri::command]
+pub async fn set_claim_review_state(
+    hash: String,
+    state: ReviewState,
+    note: Option<String>,
+) -> Result<ClaimReview, String> {
+    if mock::is_active() {
+        return Err("Claim reviews are disabled in mock mode".to_string());
+    }
+    validate_hash(&hash)?;
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note.as_ref().is_some_and(|n| n.chars().count() > MAX_NOTE_LEN) {
+        return Err(format!("Review note too long (max {} characters)", MAX_NOTE_LEN));
+    }
+
+    run_blocking(move || {
+        let claim = read_claim(hash.clone())?;
+        if state == ReviewState::Approved && approvals::requires_second_reviewer(&claim)? {
+            return Err("This claim needs a second reviewer: request approval instead".to_string());
+        }
+        let reviewer = identity::require()?;
+        let review = update_review(&hash, |review| transition(review, state, &reviewer, note))?;
+        if let Some(step) = review.history.last() {
+            append_audit_entry(audit_entry(&claim, &hash, step))
+                .map_err(|e| format!("Review saved, but writing the audit entry failed: {}", e))?;
+        }
+        log::info!("Claim {} moved to {} by {}", hash, state.as_str(), reviewer.name);
+        Ok(review)
+    })
+    .await
+}
+
+/// Review state of one claim; claims never reviewed are drafts
+#[tauri::command]
+pub async fn get_claim_review(hash: String) -> Result<ClaimReview, String> {
+    validate_hash(&hash)?;
+    run_blocking(move || Ok(load_reviews()?.remove(&hash).unwrap_or_default())).await
+}
+
+/// Claims currently in `state`, newest first
+#[tauri::command]
+pub async fn list_claims_by_review_state(state: ReviewState) -> Result<Vec<ReviewedClaim>, String> {
+    run_blocking(move || {
+        let claims = if mock::is_active() {
+            mock::claims()
+        } else {
+            let truth_path = repo_state::ready_path()?;
+            index::list_claims(&truth_path, None).claims
+        };
+        let mut reviews = load_reviews()?;
+        Ok(claims
+            .into_iter()
+            .filter_map(|claim| {
+                let hash = claim.get("$hash")?.as_str()?.to_string();
+                let review = reviews.remove(&hash).unwrap_or_default();
+                (review.state == state).then_some(ReviewedClaim { claim, review })
+            })
+            .collect())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(name: &str) -> Actor {
+        Actor {
+            name: name.to_string(),
+            email: None,
+            key_fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn test_review_transitions() {
+        let (ana, ben) = (actor("ana"), actor("ben"));
+        let mut review = ClaimReview::default();
+        assert!(transition(&mut review, ReviewState::Approved, &ana, None).is_err());
+        transition(&mut review, ReviewState::InReview, &ana, None).unwrap();
+        transition(
+            &mut review,
+            ReviewState::Approved,
+            &ben,
+            Some("checked sources".to_string()),
+        )
+        .unwrap();
+        assert!(transition(&mut review, ReviewState::Approved, &ben, None).is_err());
+        transition(&mut review, ReviewState::Retired, &ana, None).unwrap();
+
+        assert_eq!(review.state, ReviewState::Retired);
+        assert_eq!(review.reviewer.as_ref(), Some(&ana));
+        assert_eq!(review.history.len(), 3);
+        assert_eq!(review.history[1].from, ReviewState::InReview);
+        assert_eq!(review.history[1].note.as_deref(), Some("checked sources"));
+    }
+
+    #[test]
+    fn test_review_state_names() {
+        assert_eq!(serde_json::to_string(&ReviewState::InReview).unwrap(), "\"in-review\"");
+        let state: ReviewState = serde_json::from_str("\"approved\"").unwrap();
+        assert_eq!(state, ReviewState::Approved);
+    }
+}
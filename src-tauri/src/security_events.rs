@@ -0,0 +1,142 @@
+// ==================== SECURITY EVENTS ====================
+//
+// Requests the app refused for security reasons: blocked shell commands,
+// injection patterns in arguments, path traversal, receipts whose signature
+// doesn't match, permission denials and local API calls with a bad token.
+// They used to surface only as error strings; recording them in
+// security_events.json in the config directory lets an administrator see
+// probing attempts. This is kept apart from the governance audit trail,
+// which records decisions about claims, not attacks on the app.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::{run_blocking, sanitize_error};
+
+/// Oldest events are dropped past this
+const MAX_EVENTS: usize = 1000;
+/// Details are cut to this many characters; they can echo attacker input
+const MAX_DETAIL_LEN: usize = 300;
+
+/// Serializes read-modify-write cycles on security_events.json
+static SECURITY_EVENTS_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityEventKind {
+    BlockedCommand,
+    InjectionAttempt,
+    PathTraversal,
+    SignatureFailure,
+    PermissionDenied,
+    AuthFailure,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityEvent {
+    pub id: String,
+    pub at: String,
+    pub kind: SecurityEventKind,
+    /// Where it was caught, e.g. "execute_shell" or "local_api"
+    pub source: String,
+    pub detail: String,
+}
+
+fn events_path() -> PathBuf {
+    config_path("security_events.json")
+}
+
+fn push_event(events: &mut Vec<SecurityEvent>, event: SecurityEvent) {
+    events.push(event);
+    let excess = events.len().saturating_sub(MAX_EVENTS);
+    events.drain(..excess);
+}
+
+/// Newest first, optionally only one kind
+fn select(events: Vec<SecurityEvent>, kind: Option<SecurityEventKind>, limit: usize) -> Vec<SecurityEvent> {
+    events
+        .into_iter()
+        .rev()
+        .filter(|e| kind.is_none_or(|k| e.kind == k))
+        .take(limit)
+        .collect()
+}
+
+/// Record a refused request. The write happens in the background and
+/// failures are logged; the caller still returns its own error.
+pub fn record(kind: SecurityEventKind, source: &'static str, detail: &str) {
+    let detail: String = sanitize_error(detail).chars().take(MAX_DETAIL_LEN).collect();
+    log::warn!("Security event ({:?}) in {}: {}", kind, source, detail);
+    // Unit tests exercise the checks that call this; keep them off the real store
+    if cfg!(test) {
+        return;
+    }
+    let event = SecurityEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        at: chrono::Utc::now().to_rfc3339(),
+        kind,
+        source: source.to_string(),
+        detail,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = (|| {
+            let _guard = SECURITY_EVENTS_LOCK.lock(&events_path())?;
+            let mut events: Vec<SecurityEvent> = load_json(&events_path())?;
+            push_event(&mut events, event);
+            save_json(&events_path(), &events)
+        })();
+        if let Err(e) = result {
+            log::warn!("Failed to record security event: {}", e);
+        }
+    });
+}
+
+/// Recorded security events, newest first
+#[tauri::command]
+pub async fn get_security_events(
+    kind: Option<SecurityEventKind>,
+    limit: Option<usize>,
+) -> Result<Vec<SecurityEvent>, String> {
+    run_blocking(move || {
+        let _guard = SECURITY_EVENTS_LOCK.lock(&events_path())?;
+        let events: Vec<SecurityEvent> = load_json(&events_path())?;
+        Ok(select(events, kind, limit.unwrap_or(200).min(MAX_EVENTS)))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cap_and_select() {
+        let event = |n: usize, kind| SecurityEvent {
+            id: n.to_string(),
+            at: String::new(),
+            kind,
+            source: "test".to_string(),
+            detail: String::new(),
+        };
+        let mut events = Vec::new();
+        for n in 0..MAX_EVENTS + 5 {
+            let kind = if n % 2 == 0 {
+                SecurityEventKind::PathTraversal
+            } else {
+                SecurityEventKind::BlockedCommand
+            };
+            push_event(&mut events, event(n, kind));
+        }
+        assert_eq!(events.len(), MAX_EVENTS);
+        assert_eq!(events[0].id, "5");
+
+        let latest = select(events.clone(), None, 2);
+        assert_eq!(
+            latest.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+            ["1004", "1003"]
+        );
+        let traversal = select(events, Some(SecurityEventKind::PathTraversal), 1);
+        assert_eq!(traversal[0].id, "1004");
+    }
+}
@@ -0,0 +1,152 @@
+// ==================== SESSION STATE ====================
+//
+// Where the user was when the app closed: open notes, the last claim viewed,
+// terminal working directories and per-view filters. The frontend saves this
+// as it changes and restores it on launch; it lives in session.json in the
+// config dir. Restoring drops whatever no longer exists (deleted notes and
+// folders, or a claim from a different truth repo), so a stale session never
+// fails the launch.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::json_store::{config_path, load_json, save_json, StoreLock};
+use crate::truth_store::ObjectId;
+use crate::{validate_path_within_base, AppSettings, SETTINGS};
+
+const MAX_OPEN_NOTES: usize = 50;
+const MAX_TERMINAL_SESSIONS: usize = 16;
+/// Serialized size limit for `filters`, which is opaque to the backend
+const MAX_FILTERS_SIZE: usize = 64 * 1024;
+
+static SESSION_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Panel that was showing, e.g. "governance" or "knowledge"
+    pub active_view: Option<String>,
+    /// Vault-relative note paths, in tab order
+    pub open_notes: Vec<String>,
+    pub last_viewed_claim: Option<String>,
+    /// Truth repo the claim belongs to; filled in on save
+    pub truth_repo_path: String,
+    /// Working directory of each terminal tab
+    pub terminal_cwds: Vec<String>,
+    /// Filter state keyed by view, stored as the frontend sends it
+    pub filters: BTreeMap<String, serde_json::Value>,
+    pub saved_at: Option<String>,
+}
+
+fn session_path() -> PathBuf {
+    config_path("session.json")
+}
+
+/// Bound the session and check what can be checked without touching disk
+fn normalize(mut session: SessionState, settings: &AppSettings) -> Result<SessionState, String> {
+    session.open_notes.retain(|n| !n.trim().is_empty());
+    session.open_notes.dedup();
+    session.open_notes.truncate(MAX_OPEN_NOTES);
+    session.terminal_cwds.truncate(MAX_TERMINAL_SESSIONS);
+    if session.last_viewed_claim.as_deref().is_some_and(|h| !ObjectId::is_valid(h)) {
+        session.last_viewed_claim = None;
+    }
+    let filters_size = serde_json::to_vec(&session.filters).map(|v| v.len()).unwrap_or(0);
+    if filters_size > MAX_FILTERS_SIZE {
+        return Err("Session filters are too large to save".to_string());
+    }
+    session.truth_repo_path = settings.truth_repo_path.clone();
+    session.saved_at = Some(chrono::Utc::now().to_rfc3339());
+    Ok(session)
+}
+
+/// Drop references that no longer resolve against the current settings
+fn prune(mut session: SessionState, settings: &AppSettings) -> SessionState {
+    let vault = PathBuf::from(&settings.vault_path);
+    session
+        .open_notes
+        .retain(|note| validate_path_within_base(&vault, note).is_ok_and(|p| p.is_file()));
+    session.terminal_cwds.retain(|cwd| Path::new(cwd).is_dir());
+    if session.truth_repo_path != settings.truth_repo_path {
+        session.last_viewed_claim = None;
+    }
+    session
+}
+
+#[tauri::command]
+pub async fn save_session(session: SessionState) -> Result<(), String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+    let session = normalize(session, &settings)?;
+    let _guard = SESSION_LOCK.lock(&session_path())?;
+    save_json(&session_path(), &session)
+}
+
+/// The last saved session, pruned; an unreadable file restores as empty
+#[tauri::command]
+pub async fn restore_session() -> Result<SessionState, String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+    let saved = {
+        let _guard = SESSION_LOCK.lock(&session_path())?;
+        load_json::<SessionState>(&session_path())
+    };
+    match saved {
+        Ok(session) => Ok(prune(session, &settings)),
+        Err(e) => {
+            log::warn!("Ignoring saved session: {}", e);
+            Ok(SessionState::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_normalize_bounds_session() {
+        let settings = AppSettings::default();
+        let session = SessionState {
+            open_notes: vec!["a.md".to_string(), "a.md".to_string(), " ".to_string()],
+            last_viewed_claim: Some("../not-a-hash".to_string()),
+            terminal_cwds: vec!["/tmp".to_string(); MAX_TERMINAL_SESSIONS + 4],
+            ..Default::default()
+        };
+        let session = normalize(session, &settings).unwrap();
+        assert_eq!(session.open_notes, vec!["a.md"]);
+        assert_eq!(session.last_viewed_claim, None);
+        assert_eq!(session.terminal_cwds.len(), MAX_TERMINAL_SESSIONS);
+        assert_eq!(session.truth_repo_path, settings.truth_repo_path);
+
+        let mut filters = BTreeMap::new();
+        filters.insert("claims".to_string(), serde_json::json!("x".repeat(MAX_FILTERS_SIZE)));
+        let session = SessionState {
+            filters,
+            ..Default::default()
+        };
+        assert!(normalize(session, &settings).is_err());
+    }
+
+    #[test]
+    fn test_prune_drops_stale_entries() {
+        let tmp = TempDir::new("truthgit-session-vault");
+        let vault = tmp.path();
+        std::fs::write(vault.join("kept.md"), "# Kept").unwrap();
+        let settings = AppSettings {
+            vault_path: vault.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let session = SessionState {
+            open_notes: vec!["kept.md".to_string(), "deleted.md".to_string()],
+            last_viewed_claim: Some("ab12cd34".to_string()),
+            truth_repo_path: "/some/other/repo/.truth".to_string(),
+            terminal_cwds: vec![vault.to_string_lossy().to_string(), "/no/such/dir".to_string()],
+            ..Default::default()
+        };
+        let session = prune(session, &settings);
+        assert_eq!(session.open_notes, vec!["kept.md"]);
+        assert_eq!(session.terminal_cwds.len(), 1);
+        assert_eq!(session.last_viewed_claim, None);
+    }
+}
@@ -0,0 +1,271 @@
+// ==================== SETTINGS IMPORT / EXPORT ====================
+//
+// A single JSON bundle with the app settings, named profiles and remotes, so
+// a team can standardize configuration across machines. Anything that looks
+// like a credential is stripped on export and ignored on import, and so are
+// the settings that belong to one person or machine: the identity and the
+// vault, truth repo and CA bundle paths. Importing a teammate's bundle keeps
+// your own.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::http_client;
+use crate::profiles::{self, SettingsProfile};
+use crate::remotes::{self, RemoteConfig};
+use crate::{commit_settings, consent, sanitize_error, settings_events, settings_migrations, AppSettings, SETTINGS};
+
+const BUNDLE_FORMAT: &str = "truthgit-settings-bundle";
+const BUNDLE_VERSION: u32 = 1;
+
+/// Bundles are small; anything bigger is not one of ours
+const MAX_BUNDLE_SIZE: u64 = 1024 * 1024;
+
+/// Key fragments that mark a value as a secret
+const SECRET_KEY_MARKERS: &[&str] = &["password", "secret", "token", "api_key", "apikey", "private_key", "credential"];
+
+/// Settings that are neither exported nor imported
+const LOCAL_SETTINGS: &[&str] = &["identity", "vault_path", "truth_repo_path", "ca_bundle_path"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub app_version: String,
+    /// Raw settings document; migrated on import like settings.json
+    pub settings: Value,
+    #[serde(default)]
+    pub profiles: Vec<SettingsProfile>,
+    #[serde(default)]
+    pub remotes: Vec<RemoteConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub settings: AppSettings,
+    pub profiles_imported: usize,
+    pub remotes_imported: usize,
+    /// Secret-looking keys found in the bundle and ignored
+    pub skipped_secrets: Vec<String>,
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Remove secret-looking keys at any depth; returns their dotted paths
+fn strip_secrets(value: &mut Value, prefix: &str, removed: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            let secret_keys: Vec<String> = map.keys().filter(|k| is_secret_key(k)).cloned().collect();
+            for key in secret_keys {
+                map.remove(&key);
+                removed.push(format!("{}{}", prefix, key));
+            }
+            for (key, child) in map.iter_mut() {
+                strip_secrets(child, &format!("{}{}.", prefix, key), removed);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                strip_secrets(item, prefix, removed);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Settings as JSON with secrets removed, safe to hand to someone else
+pub(crate) fn sanitized_settings(settings: &AppSettings) -> Result<Value, String> {
+    let mut settings = serde_json::to_value(settings).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    strip_secrets(&mut settings, "", &mut Vec::new());
+    // Proxy credentials ride inside the URL, where key-based stripping can't see them
+    if let Some(Value::String(proxy_url)) = settings.get_mut("proxy_url") {
+        *proxy_url = http_client::redact_proxy_url(proxy_url);
+    }
+    Ok(settings)
+}
+
+/// Drop the settings in `LOCAL_SETTINGS` from a settings document
+fn strip_local(settings: &mut Value) {
+    if let Value::Object(map) = settings {
+        for key in LOCAL_SETTINGS {
+            map.remove(*key);
+        }
+    }
+}
+
+/// `imported` with the settings in `LOCAL_SETTINGS` taken from `current`
+fn keep_local(current: &AppSettings, imported: AppSettings) -> AppSettings {
+    AppSettings {
+        identity: current.identity.clone(),
+        vault_path: current.vault_path.clone(),
+        truth_repo_path: current.truth_repo_path.clone(),
+        ca_bundle_path: current.ca_bundle_path.clone(),
+        ..imported
+    }
+}
+
+fn build_bundle(
+    settings: &AppSettings,
+    profiles: Vec<SettingsProfile>,
+    remotes: Vec<RemoteConfig>,
+) -> Result<SettingsBundle, String> {
+    let mut settings = sanitized_settings(settings)?;
+    strip_local(&mut settings);
+
+    Ok(SettingsBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        settings,
+        profiles,
+        remotes,
+    })
+}
+
+fn parse_bundle(content: &str) -> Result<SettingsBundle, String> {
+    let bundle: SettingsBundle =
+        serde_json::from_str(content).map_err(|e| format!("Not a valid settings bundle: {}", e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(format!("Not a settings bundle (format '{}')", bundle.format));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Settings bundle version {} is newer than supported ({}); update the app first",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+    Ok(bundle)
+}
+
+#[tauri::command]
+pub async fn export_settings(path: String) -> Result<(), String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+    let profiles = profiles::load_profiles()?.profiles;
+    let remotes = remotes::load_remotes()?;
+    let bundle = build_bundle(&settings, profiles, remotes)?;
+
+    let content =
+        serde_json::to_string_pretty(&bundle).map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+    fs::write(&path, content).map_err(|e| sanitize_error(&format!("Failed to write bundle: {}", e)))
+}
+
+#[tauri::command]
+pub async fn import_settings(app: tauri::AppHandle, path: String) -> Result<ImportSummary, String> {
+    let path = Path::new(&path);
+    consent::require_read(path).await?;
+    let size = fs::metadata(path)
+        .map_err(|e| sanitize_error(&format!("Failed to read bundle: {}", e)))?
+        .len();
+    if size > MAX_BUNDLE_SIZE {
+        return Err("Settings bundle is too large".to_string());
+    }
+    let content = fs::read_to_string(path).map_err(|e| sanitize_error(&format!("Failed to read bundle: {}", e)))?;
+    let mut bundle = parse_bundle(&content)?;
+
+    let mut skipped_secrets = Vec::new();
+    strip_secrets(&mut bundle.settings, "", &mut skipped_secrets);
+    strip_local(&mut bundle.settings);
+
+    // Bundles from older app versions go through the same upgrade path as settings.json
+    let imported = settings_migrations::migrate_settings(bundle.settings)?;
+    if !imported.reset_fields.is_empty() {
+        log::warn!("Settings bundle fields reset to defaults: {:?}", imported.reset_fields);
+    }
+
+    let updated = {
+        let mut settings = SETTINGS.write().map_err(|e| format!("Lock error: {}", e))?;
+        let new_settings = keep_local(&settings, imported.settings);
+        commit_settings(&mut settings, new_settings)?;
+        settings.clone()
+    };
+    let profiles_imported = profiles::merge_profiles(bundle.profiles)?;
+    let remotes_imported = remotes::merge_remotes(bundle.remotes)?;
+
+    settings_events::emit_changed(&app, "import", &updated);
+
+    Ok(ImportSummary {
+        settings: updated,
+        profiles_imported,
+        remotes_imported,
+        skipped_secrets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_secrets_at_any_depth() {
+        let mut value = serde_json::json!({
+            "api_url": "https://api.example.com",
+            "proxy": { "url": "http://proxy:3128", "password": "hunter2" },
+            "webhooks": [{ "url": "https://hooks.example.com", "signing_secret": "s3cr3t" }],
+            "api_token": "abc"
+        });
+        let mut removed = Vec::new();
+        strip_secrets(&mut value, "", &mut removed);
+        removed.sort();
+        assert_eq!(removed, vec!["api_token", "proxy.password", "webhooks.signing_secret"]);
+        assert_eq!(value["proxy"]["url"], "http://proxy:3128");
+        assert_eq!(value["api_url"], "https://api.example.com");
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let settings = AppSettings {
+            default_risk_profile: "high".to_string(),
+            ..AppSettings::default()
+        };
+        let profile = profiles::capture_profile("work", &settings);
+        let bundle = build_bundle(&settings, vec![profile.clone()], Vec::new()).unwrap();
+        let content = serde_json::to_string(&bundle).unwrap();
+
+        let parsed = parse_bundle(&content).unwrap();
+        assert_eq!(parsed.profiles, vec![profile]);
+        let migrated = settings_migrations::migrate_settings(parsed.settings).unwrap();
+        assert_eq!(migrated.settings, settings);
+    }
+
+    #[test]
+    fn test_local_settings_stay_local() {
+        let mut mine = AppSettings {
+            vault_path: "/home/ana/vault".to_string(),
+            ca_bundle_path: "/home/ana/ca.pem".to_string(),
+            ..AppSettings::default()
+        };
+        mine.identity.display_name = "Ana".to_string();
+        let bundle = build_bundle(&mine, Vec::new(), Vec::new()).unwrap();
+        for key in LOCAL_SETTINGS {
+            assert!(bundle.settings.get(*key).is_none(), "{} exported", key);
+        }
+
+        let theirs = AppSettings {
+            vault_path: "/".to_string(),
+            default_risk_profile: "high".to_string(),
+            ..AppSettings::default()
+        };
+        let merged = keep_local(&mine, theirs);
+        assert_eq!(merged.vault_path, "/home/ana/vault");
+        assert_eq!(merged.ca_bundle_path, "/home/ana/ca.pem");
+        assert_eq!(merged.identity.display_name, "Ana");
+        assert_eq!(merged.default_risk_profile, "high");
+    }
+
+    #[test]
+    fn test_rejects_foreign_and_newer_bundles() {
+        assert!(parse_bundle(r#"{"vault_path": "/x"}"#).is_err());
+
+        let settings = AppSettings::default();
+        let mut bundle = build_bundle(&settings, Vec::new(), Vec::new()).unwrap();
+        bundle.version = BUNDLE_VERSION + 1;
+        assert!(parse_bundle(&serde_json::to_string(&bundle).unwrap()).is_err());
+    }
+}
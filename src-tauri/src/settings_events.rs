@@ -0,0 +1,125 @@
+// ==================== SETTINGS CHANGE EVENTS ====================
+//
+// Every settings change is broadcast as `settings://changed` so open views
+// pick up new paths immediately. settings.json is also watched for external
+// edits (e.g. a text editor), which are reloaded and rebroadcast.
+
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::{get_settings_path, identity, load_settings_from_file, AppSettings, SETTINGS};
+
+pub const SETTINGS_CHANGED_EVENT: &str = "settings://changed";
+
+/// Editors often write a file in several steps; wait for them to settle
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsChanged {
+    /// "app" for changes made through commands, "external" for file edits
+    pub source: String,
+    pub settings: AppSettings,
+}
+
+pub fn emit_changed(app: &AppHandle, source: &str, settings: &AppSettings) {
+    let payload = SettingsChanged {
+        source: source.to_string(),
+        settings: settings.clone(),
+    };
+    if let Err(e) = app.emit(SETTINGS_CHANGED_EVENT, payload) {
+        log::warn!("Failed to emit {}: {}", SETTINGS_CHANGED_EVENT, e);
+    }
+}
+
+fn is_settings_file(path: &Path, settings_path: &Path) -> bool {
+    path.file_name() == settings_path.file_name()
+}
+
+/// Reload settings.json if it no longer matches what's in memory.
+/// Our own writes also trigger watch events; those compare equal and are ignored.
+fn reload_from_disk(app: &AppHandle) {
+    let Some(mut loaded) = load_settings_from_file() else {
+        log::warn!("settings.json changed but could not be read; keeping current settings");
+        return;
+    };
+
+    {
+        let Ok(mut settings) = SETTINGS.write() else {
+            return;
+        };
+        identity::stamp_change(&settings.identity, &mut loaded.identity);
+        if *settings == loaded {
+            return;
+        }
+        *settings = loaded.clone();
+    }
+
+    log::info!("settings.json was modified externally; reloaded");
+    emit_changed(app, "external", &loaded);
+}
+
+/// Watch the config directory (not the file itself, so editors that replace
+/// the file via rename are still seen) on a dedicated thread
+pub fn spawn_settings_watcher(app: AppHandle) {
+    let settings_path = get_settings_path();
+    let Some(config_dir) = settings_path.parent().map(|p| p.to_path_buf()) else {
+        return;
+    };
+
+    std::thread::spawn(move || {
+        if let Err(e) = std::fs::create_dir_all(&config_dir) {
+            log::warn!("Settings watcher disabled, cannot create config dir: {}", e);
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                log::warn!("Settings watcher disabled: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&config_dir, RecursiveMode::NonRecursive) {
+            log::warn!("Settings watcher disabled: {}", e);
+            return;
+        }
+
+        while let Ok(result) = rx.recv() {
+            let relevant = match result {
+                Ok(event) => event.paths.iter().any(|p| is_settings_file(p, &settings_path)),
+                Err(e) => {
+                    log::warn!("Settings watcher error: {}", e);
+                    false
+                }
+            };
+            if !relevant {
+                continue;
+            }
+
+            // Coalesce the burst of events a single save produces
+            std::thread::sleep(RELOAD_DEBOUNCE);
+            while rx.try_recv().is_ok() {}
+
+            reload_from_disk(&app);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_is_settings_file() {
+        let settings_path = PathBuf::from("/cfg/truthgit/settings.json");
+        assert!(is_settings_file(Path::new("/cfg/truthgit/settings.json"), &settings_path));
+        assert!(!is_settings_file(Path::new("/cfg/truthgit/settings.json.v1.bak"), &settings_path));
+        assert!(!is_settings_file(Path::new("/cfg/truthgit/other.json"), &settings_path));
+    }
+}
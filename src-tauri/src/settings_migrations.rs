@@ -0,0 +1,186 @@
+// ==================== SETTINGS MIGRATIONS ====================
+//
+// settings.json carries a `schema_version`. Files written by older app
+// versions are upgraded one step at a time before deserialization, and fields
+// that still fail to parse fall back to their defaults individually instead of
+// the whole file being discarded.
+
+use serde_json::{Map, Value};
+
+use crate::AppSettings;
+
+/// Current settings schema. Bump this and append a step to `MIGRATIONS`
+/// whenever a field is renamed, removed or changes meaning. Purely additive
+/// fields only need a `#[serde(default)]` on `AppSettings`.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
+type MigrationStep = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` upgrades a version `i + 1` document to version `i + 2`
+const MIGRATIONS: &[MigrationStep] = &[migrate_v1_to_v2];
+
+/// v1 (no `schema_version`) -> v2: introduce `backend_mode`.
+/// Existing installs were using the CLI, so keep them on it.
+fn migrate_v1_to_v2(settings: &mut Map<String, Value>) {
+    settings
+        .entry("backend_mode")
+        .or_insert_with(|| Value::String("cli".to_string()));
+}
+
+pub fn current_schema_version() -> u32 {
+    SETTINGS_SCHEMA_VERSION
+}
+
+#[derive(Debug)]
+pub struct MigrationOutcome {
+    pub settings: AppSettings,
+    pub from_version: u32,
+    /// True when the on-disk document differs from `settings` and should be rewritten
+    pub changed: bool,
+    /// Fields that could not be parsed and were reset to their defaults
+    pub reset_fields: Vec<String>,
+}
+
+/// Upgrade a raw settings document to the current schema and deserialize it
+pub fn migrate_settings(raw: Value) -> Result<MigrationOutcome, String> {
+    let mut map = match raw {
+        Value::Object(map) => map,
+        _ => return Err("Settings file is not a JSON object".to_string()),
+    };
+
+    let from_version = map
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+        .unwrap_or(1);
+
+    if from_version > SETTINGS_SCHEMA_VERSION {
+        // Written by a newer app: don't downgrade, just read what we understand
+        log::warn!(
+            "settings.json schema v{} is newer than supported v{}; unknown fields are ignored",
+            from_version,
+            SETTINGS_SCHEMA_VERSION
+        );
+    }
+
+    for version in from_version.max(1)..SETTINGS_SCHEMA_VERSION {
+        if let Some(step) = MIGRATIONS.get((version - 1) as usize) {
+            step(&mut map);
+        }
+    }
+
+    if from_version < SETTINGS_SCHEMA_VERSION {
+        map.insert("schema_version".to_string(), Value::from(SETTINGS_SCHEMA_VERSION));
+    }
+
+    let (settings, reset_fields) = deserialize_lenient(map)?;
+
+    Ok(MigrationOutcome {
+        settings,
+        from_version,
+        changed: from_version < SETTINGS_SCHEMA_VERSION || !reset_fields.is_empty(),
+        reset_fields,
+    })
+}
+
+/// Deserialize settings, replacing only the fields that fail to parse with
+/// their defaults. Returns the names of the fields that were reset.
+fn deserialize_lenient(map: Map<String, Value>) -> Result<(AppSettings, Vec<String>), String> {
+    if let Ok(settings) = serde_json::from_value::<AppSettings>(Value::Object(map.clone())) {
+        return Ok((settings, Vec::new()));
+    }
+
+    let defaults = match serde_json::to_value(AppSettings::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => return Err("Failed to serialize default settings".to_string()),
+    };
+
+    let mut merged = defaults.clone();
+    let mut reset_fields = Vec::new();
+
+    for key in defaults.keys() {
+        let Some(user_value) = map.get(key) else {
+            continue;
+        };
+        let mut candidate = merged.clone();
+        candidate.insert(key.clone(), user_value.clone());
+        if serde_json::from_value::<AppSettings>(Value::Object(candidate)).is_ok() {
+            merged.insert(key.clone(), user_value.clone());
+        } else {
+            reset_fields.push(key.clone());
+        }
+    }
+
+    let settings = serde_json::from_value(Value::Object(merged))
+        .map_err(|e| format!("Failed to parse settings: {}", e))?;
+    Ok((settings, reset_fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_settings() -> Value {
+        serde_json::json!({
+            "vault_path": "/data/vault",
+            "truth_repo_path": "/data/.truth",
+            "api_mode": "local",
+            "api_url": "http://localhost:8000",
+            "default_risk_profile": "high",
+            "terminal_font_size": 16,
+            "auto_save_audit": false
+        })
+    }
+
+    #[test]
+    fn test_migrations_cover_every_version() {
+        assert_eq!(MIGRATIONS.len() as u32, SETTINGS_SCHEMA_VERSION - 1);
+    }
+
+    #[test]
+    fn test_v1_settings_migrate_to_current() {
+        let outcome = migrate_settings(v1_settings()).unwrap();
+        assert_eq!(outcome.from_version, 1);
+        assert!(outcome.changed);
+        assert_eq!(outcome.settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(outcome.settings.backend_mode, "cli");
+        // User values survive the upgrade
+        assert_eq!(outcome.settings.vault_path, "/data/vault");
+        assert_eq!(outcome.settings.terminal_font_size, 16);
+        assert!(!outcome.settings.auto_save_audit);
+    }
+
+    #[test]
+    fn test_invalid_field_only_resets_that_field() {
+        let mut raw = v1_settings();
+        raw["terminal_font_size"] = Value::String("huge".to_string());
+        let outcome = migrate_settings(raw).unwrap();
+        assert_eq!(outcome.reset_fields, vec!["terminal_font_size".to_string()]);
+        assert_eq!(outcome.settings.terminal_font_size, AppSettings::default().terminal_font_size);
+        assert_eq!(outcome.settings.vault_path, "/data/vault");
+        assert_eq!(outcome.settings.default_risk_profile, "high");
+    }
+
+    #[test]
+    fn test_current_settings_are_unchanged() {
+        let raw = serde_json::to_value(AppSettings::default()).unwrap();
+        let outcome = migrate_settings(raw).unwrap();
+        assert_eq!(outcome.from_version, SETTINGS_SCHEMA_VERSION);
+        assert!(!outcome.changed);
+    }
+
+    #[test]
+    fn test_newer_schema_is_not_downgraded() {
+        let mut raw = serde_json::to_value(AppSettings::default()).unwrap();
+        raw["schema_version"] = Value::from(SETTINGS_SCHEMA_VERSION + 1);
+        raw["some_future_field"] = Value::Bool(true);
+        let outcome = migrate_settings(raw).unwrap();
+        assert_eq!(outcome.from_version, SETTINGS_SCHEMA_VERSION + 1);
+        assert!(!outcome.changed);
+    }
+
+    #[test]
+    fn test_non_object_rejected() {
+        assert!(migrate_settings(Value::Array(vec![])).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+// ==================== SETTINGS VALIDATION ====================
+//
+// Per-field checks run before settings are persisted. Errors block
+// `update_settings`; warnings are informational (e.g. a path that will be
+// created on first use). The Settings UI calls `validate_settings` to
+// highlight individual fields, so validation only reads metadata and never
+// writes into the user's folders.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::engine::{BACKEND_CLI, BACKEND_EMBEDDED, BACKEND_MOCK};
+use crate::hooks;
+use crate::http_client;
+use crate::i18n;
+use crate::shortcuts;
+use crate::updates;
+use crate::AppSettings;
+
+pub const MIN_TERMINAL_FONT_SIZE: u32 = 8;
+pub const MAX_TERMINAL_FONT_SIZE: u32 = 32;
+
+const MIN_LOCAL_API_PORT: u16 = 1024;
+
+const API_MODES: &[&str] = &["local", "remote"];
+const BACKEND_MODES: &[&str] = &[BACKEND_CLI, BACKEND_EMBEDDED, BACKEND_MOCK];
+pub const RISK_PROFILES: &[&str] = &["low", "medium", "high"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsIssue {
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SettingsValidation {
+    pub valid: bool,
+    pub issues: Vec<SettingsIssue>,
+}
+
+impl SettingsValidation {
+    pub fn errors(&self) -> impl Iterator<Item = &SettingsIssue> {
+        self.issues.iter().filter(|i| i.severity == IssueSeverity::Error)
+    }
+
+    /// One-line summary of blocking errors, for command error strings
+    pub fn error_summary(&self) -> String {
+        self.errors()
+            .map(|i| format!("{}: {}", i.field, i.message))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+struct Issues(Vec<SettingsIssue>);
+
+impl Issues {
+    fn error(&mut self, field: &str, message: impl Into<String>, suggestion: Option<&str>) {
+        self.push(field, IssueSeverity::Error, message.into(), suggestion);
+    }
+
+    fn warning(&mut self, field: &str, message: impl Into<String>, suggestion: Option<&str>) {
+        self.push(field, IssueSeverity::Warning, message.into(), suggestion);
+    }
+
+    fn push(&mut self, field: &str, severity: IssueSeverity, message: String, suggestion: Option<&str>) {
+        self.0.push(SettingsIssue {
+            field: field.to_string(),
+            severity,
+            message,
+            suggestion: suggestion.map(|s| s.to_string()),
+        });
+    }
+}
+
+pub fn validate(settings: &AppSettings) -> SettingsValidation {
+    let mut issues = Issues(Vec::new());
+
+    check_vault_path(&settings.vault_path, &mut issues);
+    check_truth_repo_path(&settings.truth_repo_path, &mut issues);
+    check_api(settings, &mut issues);
+    check_network(settings, &mut issues);
+    check_choice("backend_mode", &settings.backend_mode, BACKEND_MODES, &mut issues);
+    check_choice("default_risk_profile", &settings.default_risk_profile, RISK_PROFILES, &mut issues);
+    check_choice("locale", &settings.locale, i18n::LOCALES, &mut issues);
+    check_choice("update_channel", &settings.update_channel, updates::CHANNELS, &mut issues);
+    if let Some(risk) = &settings.approval_policy.min_risk_profile {
+        check_choice("approval_policy.min_risk_profile", risk, RISK_PROFILES, &mut issues);
+    }
+
+    if settings.backend_mode == BACKEND_EMBEDDED && !cfg!(feature = "embedded-engine") {
+        issues.error(
+            "backend_mode",
+            "The embedded engine is not available in this build",
+            Some("Use \"cli\" or install a build with the embedded-engine feature"),
+        );
+    }
+
+    if settings.local_api_enabled && settings.local_api_port < MIN_LOCAL_API_PORT {
+        issues.error(
+            "local_api_port",
+            format!("Port {} is reserved", settings.local_api_port),
+            Some("Use a port between 1024 and 65535"),
+        );
+    }
+
+    for conflict in shortcuts::find_conflicts(&settings.shortcuts) {
+        issues.error(
+            &format!("shortcuts.{}", conflict.action),
+            conflict.message,
+            Some("Pick another combination, or clear the field to disable it"),
+        );
+    }
+
+    let email = settings.identity.email.trim();
+    if !email.is_empty() && !email.contains('@') {
+        issues.warning(
+            "identity.email",
+            "Email address looks incomplete",
+            Some("Use a full address such as name@example.org, or leave it empty"),
+        );
+    }
+
+    let archive_dir = settings.retention.archive_dir.trim();
+    if !archive_dir.is_empty() && !Path::new(archive_dir).is_absolute() {
+        issues.error(
+            "retention.archive_dir",
+            "Archive folder must be an absolute path",
+            Some("Choose a folder, or leave it empty to keep archives in the truth repository"),
+        );
+    }
+
+    if !(1..=hooks::MAX_TIMEOUT_SECS).contains(&settings.hooks.timeout_secs) {
+        issues.error(
+            "hooks.timeout_secs",
+            format!("Hook timeout must be 1-{} seconds", hooks::MAX_TIMEOUT_SECS),
+            Some("10 seconds is the default"),
+        );
+    }
+
+    if !(MIN_TERMINAL_FONT_SIZE..=MAX_TERMINAL_FONT_SIZE).contains(&settings.terminal_font_size) {
+        issues.error(
+            "terminal_font_size",
+            format!(
+                "Font size {} is out of range ({}-{})",
+                settings.terminal_font_size, MIN_TERMINAL_FONT_SIZE, MAX_TERMINAL_FONT_SIZE
+            ),
+            Some("Use a value between 8 and 32; 14 is the default"),
+        );
+    }
+
+    let issues = issues.0;
+    SettingsValidation {
+        valid: !issues.iter().any(|i| i.severity == IssueSeverity::Error),
+        issues,
+    }
+}
+
+fn check_choice(field: &str, value: &str, allowed: &[&str], issues: &mut Issues) {
+    if !allowed.contains(&value) {
+        issues.error(
+            field,
+            format!("Unknown value '{}'", value),
+            Some(&format!("Expected one of: {}", allowed.join(", "))),
+        );
+    }
+}
+
+fn check_vault_path(vault_path: &str, issues: &mut Issues) {
+    if vault_path.trim().is_empty() {
+        issues.error("vault_path", "Vault path is empty", Some("Choose your Obsidian vault folder"));
+        return;
+    }
+
+    let path = Path::new(vault_path);
+    if path.is_dir() {
+        return;
+    }
+    if path.exists() {
+        issues.error("vault_path", "Vault path is not a directory", Some("Choose a folder, not a file"));
+    } else if path.parent().map(|p| p.is_dir()).unwrap_or(false) {
+        issues.warning(
+            "vault_path",
+            "Vault folder does not exist yet",
+            Some("Create the folder or point to an existing Obsidian vault"),
+        );
+    } else {
+        issues.error(
+            "vault_path",
+            "Vault folder does not exist and its parent directory is missing",
+            Some("Check the path for typos"),
+        );
+    }
+}
+
+fn check_truth_repo_path(truth_repo_path: &str, issues: &mut Issues) {
+    if truth_repo_path.trim().is_empty() {
+        issues.error(
+            "truth_repo_path",
+            "Truth repository path is empty",
+            Some("The default location is ~/.truth"),
+        );
+        return;
+    }
+
+    let path = Path::new(truth_repo_path);
+    if path.is_dir() {
+        if !is_writable_dir(path) {
+            issues.error(
+                "truth_repo_path",
+                "Truth repository is not writable",
+                Some("Fix the folder permissions; the audit trail is written here"),
+            );
+        }
+    } else if path.exists() {
+        issues.error(
+            "truth_repo_path",
+            "Truth repository path is not a directory",
+            Some("Choose the .truth folder, not a file inside it"),
+        );
+    } else {
+        match path.parent() {
+            Some(parent) if parent.is_dir() && is_writable_dir(parent) => issues.warning(
+                "truth_repo_path",
+                "Truth repository does not exist yet",
+                Some("Initialize it with `truthgit init` or pick an existing repository"),
+            ),
+            _ => issues.error(
+                "truth_repo_path",
+                "Truth repository does not exist and cannot be created here",
+                Some("Check the path for typos and that the parent folder is writable"),
+            ),
+        }
+    }
+}
+
+/// Writability as far as the permission bits tell, without touching the
+/// folder; `probe_truth_repo` confirms it when the settings are saved
+fn is_writable_dir(dir: &Path) -> bool {
+    fs::metadata(dir).is_ok_and(|m| !m.permissions().readonly())
+}
+
+/// Check that an existing truth repo can be written by creating and removing
+/// a file in it, since permission bits don't account for ACLs, read-only
+/// mounts or sandboxing. Only run when settings with a new repo path are saved.
+pub fn probe_truth_repo(truth_repo_path: &str) -> Result<(), String> {
+    let path = Path::new(truth_repo_path);
+    if !path.is_dir() {
+        return Ok(());
+    }
+    let probe = path.join(".truthgit-write-test");
+    fs::write(&probe, b"").map_err(|e| format!("Truth repository is not writable: {}", e))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+pub fn is_loopback_host(host: &str) -> bool {
+    matches!(host, "localhost" | "127.0.0.1" | "::1" | "[::1]")
+}
+
+fn check_api(settings: &AppSettings, issues: &mut Issues) {
+    check_choice("api_mode", &settings.api_mode, API_MODES, issues);
+
+    let url = match reqwest::Url::parse(settings.api_url.trim()) {
+        Ok(url) => url,
+        Err(e) => {
+            issues.error(
+                "api_url",
+                format!("API URL is not a valid URL: {}", e),
+                Some("Use a full URL such as https://api.example.com"),
+            );
+            return;
+        }
+    };
+
+    let loopback = url.host_str().map(is_loopback_host).unwrap_or(false);
+    match url.scheme() {
+        "https" => {}
+        // SECURITY: Plain HTTP is only acceptable to this machine
+        "http" if loopback => {}
+        "http" => {
+            let message = "API URL uses plain HTTP to a remote host";
+            let suggestion = Some("Use https:// so claims are not sent unencrypted");
+            if settings.api_mode == "remote" {
+                issues.error("api_url", message, suggestion);
+            } else {
+                issues.warning("api_url", message, suggestion);
+            }
+        }
+        scheme => issues.error(
+            "api_url",
+            format!("Unsupported URL scheme '{}'", scheme),
+            Some("Use https://"),
+        ),
+    }
+}
+
+fn check_network(settings: &AppSettings, issues: &mut Issues) {
+    if !settings.proxy_url.trim().is_empty() {
+        if let Err(e) = http_client::parse_proxy_url(&settings.proxy_url) {
+            issues.error("proxy_url", e, Some("Use a URL such as http://proxy.example.com:3128"));
+        }
+    }
+    if !settings.ca_bundle_path.trim().is_empty() {
+        if let Err(e) = http_client::load_ca_bundle(&settings.ca_bundle_path) {
+            issues.error("ca_bundle_path", e, Some("Point to a PEM file exported from your organization's root CA"));
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn validate_settings(settings: AppSettings) -> Result<SettingsValidation, String> {
+    Ok(validate(&settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    fn valid_settings() -> AppSettings {
+        let tmp = std::env::temp_dir();
+        AppSettings {
+            vault_path: tmp.to_string_lossy().to_string(),
+            truth_repo_path: tmp.to_string_lossy().to_string(),
+            ..AppSettings::default()
+        }
+    }
+
+    fn has_error(validation: &SettingsValidation, field: &str) -> bool {
+        validation.errors().any(|i| i.field == field)
+    }
+
+    #[test]
+    fn test_valid_settings_pass() {
+        let validation = validate(&valid_settings());
+        assert!(validation.valid, "unexpected issues: {:?}", validation.issues);
+    }
+
+    #[test]
+    fn test_font_size_out_of_range() {
+        let mut settings = valid_settings();
+        settings.terminal_font_size = 200;
+        assert!(has_error(&validate(&settings), "terminal_font_size"));
+        settings.terminal_font_size = 4;
+        assert!(has_error(&validate(&settings), "terminal_font_size"));
+    }
+
+    #[test]
+    fn test_invalid_api_url_rejected() {
+        let mut settings = valid_settings();
+        settings.api_url = "not a url".to_string();
+        assert!(has_error(&validate(&settings), "api_url"));
+    }
+
+    #[test]
+    fn test_remote_mode_requires_https() {
+        let mut settings = valid_settings();
+        settings.api_mode = "remote".to_string();
+        settings.api_url = "http://api.example.com".to_string();
+        assert!(has_error(&validate(&settings), "api_url"));
+
+        settings.api_url = "https://api.example.com".to_string();
+        assert!(!has_error(&validate(&settings), "api_url"));
+
+        // Loopback is fine over HTTP
+        settings.api_url = "http://127.0.0.1:8000".to_string();
+        assert!(!has_error(&validate(&settings), "api_url"));
+    }
+
+    #[test]
+    fn test_unknown_choices_rejected() {
+        let mut settings = valid_settings();
+        settings.default_risk_profile = "extreme".to_string();
+        settings.api_mode = "cloud".to_string();
+        let validation = validate(&settings);
+        assert!(has_error(&validation, "default_risk_profile"));
+        assert!(has_error(&validation, "api_mode"));
+    }
+
+    #[test]
+    fn test_missing_path_with_missing_parent_is_error() {
+        let mut settings = valid_settings();
+        settings.vault_path = "/nonexistent-truthgit-test/a/b".to_string();
+        assert!(has_error(&validate(&settings), "vault_path"));
+    }
+
+    #[test]
+    fn test_missing_path_with_existing_parent_is_warning() {
+        let mut settings = valid_settings();
+        settings.truth_repo_path = std::env::temp_dir()
+            .join("truthgit-validation-missing-repo")
+            .to_string_lossy()
+            .to_string();
+        let validation = validate(&settings);
+        assert!(validation.valid);
+        assert!(validation.issues.iter().any(|i| i.field == "truth_repo_path"));
+    }
+
+    #[test]
+    fn test_probe_truth_repo_leaves_nothing_behind() {
+        let tmp = TempDir::new("truthgit-probe");
+        assert!(probe_truth_repo(&tmp.path().to_string_lossy()).is_ok());
+        assert!(!tmp.path().join(".truthgit-write-test").exists());
+        // A repo that doesn't exist yet is created later, not probed
+        assert!(probe_truth_repo("/nonexistent-truthgit-test/.truth").is_ok());
+    }
+
+    #[test]
+    fn test_network_settings_checked() {
+        let mut settings = valid_settings();
+        settings.proxy_url = "ftp://proxy.example.com".to_string();
+        settings.ca_bundle_path = "/nonexistent-truthgit-test/ca.pem".to_string();
+        let validation = validate(&settings);
+        assert!(has_error(&validation, "proxy_url"));
+        assert!(has_error(&validation, "ca_bundle_path"));
+
+        settings.proxy_url = "socks5://127.0.0.1:1080".to_string();
+        settings.ca_bundle_path = String::new();
+        assert!(validate(&settings).valid);
+    }
+
+    #[test]
+    fn test_error_summary_lists_fields() {
+        let mut settings = valid_settings();
+        settings.terminal_font_size = 0;
+        let summary = validate(&settings).error_summary();
+        assert!(summary.contains("terminal_font_size"));
+    }
+}
@@ -0,0 +1,310 @@
+// ==================== FIRST-RUN SETUP ====================
+//
+// Backend for the setup wizard: detects Obsidian vaults and truth repos
+// already on this machine so new users start from real paths instead of
+// defaults that may not exist.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::json_store::{config_path, load_json, save_json};
+use crate::truth_store::TruthStore;
+use crate::{count_claims, get_settings_path, repo_init, SETTINGS};
+
+/// How deep to look below each scan root
+const SCAN_MAX_DEPTH: usize = 3;
+
+/// Large or irrelevant trees that never hold a vault or truth repo
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "Library", "AppData", "venv", "__pycache__", "snap"];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SetupState {
+    completed: bool,
+    completed_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FirstRunState {
+    pub first_run: bool,
+    pub vault_path_exists: bool,
+    pub truth_repo_exists: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedVault {
+    pub path: String,
+    pub name: String,
+    /// "obsidian" when registered in Obsidian's own config, "scan" otherwise
+    pub source: String,
+    /// Last opened in Obsidian (ms since epoch), when known
+    pub last_opened: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedTruthRepo {
+    pub path: String,
+    pub claims_count: usize,
+    pub has_keys: bool,
+    pub is_configured: bool,
+}
+
+fn setup_state_path() -> PathBuf {
+    config_path("setup.json")
+}
+
+/// Installs that predate the wizard have a settings.json but no setup.json;
+/// they are already configured and must not be sent through the wizard.
+fn is_first_run() -> bool {
+    match load_json::<Option<SetupState>>(&setup_state_path()) {
+        Ok(Some(state)) => !state.completed,
+        _ => !get_settings_path().exists(),
+    }
+}
+
+#[tauri::command]
+pub async fn get_first_run_state() -> Result<FirstRunState, String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?.clone();
+    Ok(FirstRunState {
+        first_run: is_first_run(),
+        vault_path_exists: Path::new(&settings.vault_path).is_dir(),
+        truth_repo_exists: is_truth_repo(Path::new(&settings.truth_repo_path)),
+    })
+}
+
+#[tauri::command]
+pub async fn complete_first_run() -> Result<(), String> {
+    save_json(
+        &setup_state_path(),
+        &SetupState {
+            completed: true,
+            completed_at: Some(chrono::Utc::now().to_rfc3339()),
+        },
+    )
+}
+
+// ---------- Obsidian vaults ----------
+
+/// Locations of Obsidian's obsidian.json (native, Flatpak and Snap installs)
+fn obsidian_config_files() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Some(config) = dirs::config_dir() {
+        files.push(config.join("obsidian").join("obsidian.json"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        files.push(home.join(".var/app/md.obsidian.Obsidian/config/obsidian/obsidian.json"));
+        files.push(home.join("snap/obsidian/current/.config/obsidian/obsidian.json"));
+    }
+    files
+}
+
+/// Vaults registered in an obsidian.json: `{"vaults": {"<id>": {"path", "ts"}}}`
+fn parse_obsidian_config(content: &str) -> Vec<(PathBuf, Option<i64>)> {
+    let Ok(config) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(vaults) = config.get("vaults").and_then(|v| v.as_object()) else {
+        return Vec::new();
+    };
+    vaults
+        .values()
+        .filter_map(|vault| {
+            let path = vault.get("path")?.as_str()?;
+            Some((PathBuf::from(path), vault.get("ts").and_then(|t| t.as_i64())))
+        })
+        .collect()
+}
+
+fn vault_scan_roots() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    vec![
+        home.join("Documents"),
+        home.join("Obsidian"),
+        // iCloud-synced vaults on macOS
+        home.join("Library/Mobile Documents/iCloud~md~obsidian/Documents"),
+        home,
+    ]
+}
+
+fn should_descend(entry: &walkdir::DirEntry, keep_hidden: &str) -> bool {
+    if entry.depth() == 0 {
+        return true;
+    }
+    let name = entry.file_name().to_string_lossy();
+    if name.starts_with('.') {
+        return name == keep_hidden;
+    }
+    !SKIP_DIRS.contains(&name.as_ref())
+}
+
+/// Directories below `root` that contain a `.obsidian` folder
+fn scan_for_vaults(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .max_depth(SCAN_MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|e| e.file_type().is_dir() && should_descend(e, ".obsidian"))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == ".obsidian")
+        .filter_map(|e| e.path().parent().map(|p| p.to_path_buf()))
+        .collect()
+}
+
+fn vault_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+fn find_obsidian_vaults() -> Vec<DetectedVault> {
+    let mut seen = HashSet::new();
+    let mut vaults = Vec::new();
+
+    let mut registered: Vec<(PathBuf, Option<i64>)> = obsidian_config_files()
+        .iter()
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .flat_map(|content| parse_obsidian_config(&content))
+        .collect();
+    // Most recently opened first
+    registered.sort_by_key(|v| std::cmp::Reverse(v.1));
+
+    for (path, last_opened) in registered {
+        if path.is_dir() && seen.insert(path.clone()) {
+            vaults.push(DetectedVault {
+                path: path.to_string_lossy().to_string(),
+                name: vault_name(&path),
+                source: "obsidian".to_string(),
+                last_opened,
+            });
+        }
+    }
+
+    for root in vault_scan_roots().iter().filter(|r| r.is_dir()) {
+        for path in scan_for_vaults(root) {
+            if seen.insert(path.clone()) {
+                vaults.push(DetectedVault {
+                    path: path.to_string_lossy().to_string(),
+                    name: vault_name(&path),
+                    source: "scan".to_string(),
+                    last_opened: None,
+                });
+            }
+        }
+    }
+
+    vaults
+}
+
+#[tauri::command]
+pub async fn detect_obsidian_vaults() -> Result<Vec<DetectedVault>, String> {
+    tokio::task::spawn_blocking(find_obsidian_vaults)
+        .await
+        .map_err(|e| format!("Task execution error: {}", e))
+}
+
+// ---------- Truth repos ----------
+
+pub fn is_truth_repo(path: &Path) -> bool {
+    TruthStore::new(path).is_repo()
+}
+
+fn scan_for_truth_repos(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .max_depth(SCAN_MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|e| e.file_type().is_dir() && should_descend(e, ".truth"))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == ".truth" && is_truth_repo(e.path()))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+fn find_truth_repos(configured: PathBuf, vault: PathBuf) -> Vec<DetectedTruthRepo> {
+    let mut candidates = vec![configured.clone()];
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".truth"));
+        candidates.extend(scan_for_truth_repos(&home));
+    }
+    candidates.push(vault.join(".truth"));
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|path| is_truth_repo(path) && seen.insert(path.clone()))
+        .map(|path| DetectedTruthRepo {
+            path: path.to_string_lossy().to_string(),
+            claims_count: count_claims(&path),
+            has_keys: path.join("proof.key").exists() && path.join("proof.pub").exists(),
+            is_configured: path == configured,
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn detect_truth_repos() -> Result<Vec<DetectedTruthRepo>, String> {
+    let (configured, vault) = {
+        let settings = SETTINGS.read().map_err(|e| format!("Lock error: {}", e))?;
+        (PathBuf::from(&settings.truth_repo_path), PathBuf::from(&settings.vault_path))
+    };
+    tokio::task::spawn_blocking(move || find_truth_repos(configured, vault))
+        .await
+        .map_err(|e| format!("Task execution error: {}", e))
+}
+
+/// Wizard-only repo creation; outside first run `init_truth_repo` requires
+/// an explicit confirmation token
+#[tauri::command]
+pub async fn initialize_truth_repo(path: String) -> Result<String, String> {
+    if !is_first_run() {
+        return Err("Setup has already been completed".to_string());
+    }
+    let path = repo_init::check_init_target(&path)?;
+    repo_init::init_repo(&path).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_parse_obsidian_config() {
+        let content = r#"{"vaults": {
+            "a1": {"path": "/home/u/Notes", "ts": 1700000000000, "open": true},
+            "b2": {"path": "/home/u/Work"}
+        }}"#;
+        let mut vaults = parse_obsidian_config(content);
+        vaults.sort();
+        assert_eq!(vaults.len(), 2);
+        assert_eq!(vaults[0], (PathBuf::from("/home/u/Notes"), Some(1700000000000)));
+        assert_eq!(vaults[1], (PathBuf::from("/home/u/Work"), None));
+        assert!(parse_obsidian_config("not json").is_empty());
+    }
+
+    #[test]
+    fn test_scan_finds_vaults_and_skips_hidden() {
+        let tmp = TempDir::new("truthgit-setup-scan");
+        let root = tmp.path();
+        fs::create_dir_all(root.join("Notes/.obsidian")).unwrap();
+        fs::create_dir_all(root.join(".cache/Hidden/.obsidian")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg/.obsidian")).unwrap();
+
+        let vaults = scan_for_vaults(root);
+        assert_eq!(vaults, vec![root.join("Notes")]);
+    }
+
+    #[test]
+    fn test_scan_finds_truth_repos() {
+        let tmp = TempDir::new("truthgit-setup-repos");
+        let root = tmp.path();
+        fs::create_dir_all(root.join("project/.truth/objects/cl")).unwrap();
+        fs::create_dir_all(root.join("other/.truth")).unwrap();
+
+        let repos = scan_for_truth_repos(root);
+        assert_eq!(repos, vec![root.join("project/.truth")]);
+    }
+}
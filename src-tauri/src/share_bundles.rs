@@ -0,0 +1,540 @@
+// ==================== SHARE BUNDLES ====================
+//
+// Hands a colleague a few claims rather than the whole repository. A share
+// bundle is a zip holding the selected claim objects, optionally every
+// verification of those claims with the evidence they cite, and a manifest
+// listing each object with its SHA-256. The manifest is signed with the
+// receipt key (see receipts.rs) and carries the public key, so the receiver
+// can tell the bundle is intact and which key signed it. The signature proves
+// integrity, not identity: anyone can make a key and write any name into
+// `created_by`, so the audit trail records only the signer's fingerprint and
+// it is up to the receiver to recognize it. Objects are stored
+// compressed but never sealed, whatever the sender's encryption setting;
+// importing seals them again if the receiving repo is encrypted.
+//
+// Importing checks the signature, every object's digest and `$hash`, then
+// adds the objects the active repo lacks and records the import in the
+// audit trail.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::repo_crypto::Binding;
+use crate::security_events::{self, SecurityEventKind};
+use crate::truth_store::{self, ObjectId, ObjectKind, TruthStore};
+use crate::verification::Verification;
+use crate::{
+    append_audit_entry, consent, identity, mock, receipts, repo_crypto, repo_state, run_blocking, sanitize_error,
+    AuditEntry, MAX_DECOMPRESSED_SIZE,
+};
+
+pub const IMPORT_ACTION: &str = "share_import";
+
+const BUNDLE_FORMAT: u32 = 1;
+const MANIFEST_FILE: &str = "bundle.json";
+/// Bundles are for handing over a handful of claims
+const MAX_BUNDLE_CLAIMS: usize = 500;
+const MAX_BUNDLE_OBJECTS: usize = 10_000;
+const MAX_MANIFEST_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleObject {
+    pub kind: ObjectKind,
+    pub hash: ObjectId,
+    /// Of the object as stored in the bundle, hex
+    pub sha256: String,
+}
+
+/// The signed part of a bundle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub created_at: String,
+    /// Display name of whoever made the bundle, as the sender set it; not
+    /// proven by the signature
+    #[serde(default)]
+    pub created_by: Option<String>,
+    pub signer_fingerprint: String,
+    /// The claims selected; the other objects are their verification chains
+    pub claims: Vec<String>,
+    pub objects: Vec<BundleObject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedManifest {
+    pub manifest: BundleManifest,
+    /// Ed25519 public key, base64url
+    pub public_key: String,
+    /// Ed25519 signature over the JSON of `manifest`, base64url
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BundleCounts {
+    pub claims: usize,
+    pub verifications: usize,
+    pub evidence: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareBundle {
+    pub path: String,
+    pub objects: BundleCounts,
+    pub signer_fingerprint: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareImport {
+    pub objects: BundleCounts,
+    /// Objects new to the active repo
+    pub imported: usize,
+    /// Objects it already had
+    pub duplicates: usize,
+    pub created_at: String,
+    /// Unverified; see `BundleManifest::created_by`
+    pub created_by: Option<String>,
+    pub signer_fingerprint: String,
+    /// Signed with this desktop's own receipt key
+    pub signed_by_me: bool,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn entry_name(kind: ObjectKind, hash: &ObjectId) -> String {
+    let (prefix, rest) = hash.as_str().split_at(2);
+    format!("objects/{}/{}/{}", kind.dir(), prefix, rest)
+}
+
+fn count(objects: &[BundleObject]) -> BundleCounts {
+    let mut counts = BundleCounts::default();
+    for object in objects {
+        match object.kind {
+            ObjectKind::Claim => counts.claims += 1,
+            ObjectKind::Verification => counts.verifications += 1,
+            ObjectKind::Evidence => counts.evidence += 1,
+        }
+    }
+    counts
+}
+
+fn signing_bytes(manifest: &BundleManifest) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(manifest).map_err(|e| format!("Failed to encode bundle manifest: {}", e))
+}
+
+fn sign(manifest: BundleManifest, key: &SigningKey) -> Result<SignedManifest, String> {
+    let signature = key.sign(&signing_bytes(&manifest)?);
+    Ok(SignedManifest {
+        manifest,
+        public_key: URL_SAFE_NO_PAD.encode(key.verifying_key().to_bytes()),
+        signature: URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+    })
+}
+
+/// Check the signature and that the fingerprint belongs to the embedded key
+fn check_signature(signed: &SignedManifest) -> Result<(), String> {
+    let public_key: [u8; 32] = URL_SAFE_NO_PAD
+        .decode(&signed.public_key)
+        .ok()
+        .and_then(|k| k.try_into().ok())
+        .ok_or("Bundle public key is malformed")?;
+    let signature: [u8; 64] = URL_SAFE_NO_PAD
+        .decode(&signed.signature)
+        .ok()
+        .and_then(|s| s.try_into().ok())
+        .ok_or("Bundle signature is malformed")?;
+    if identity::fingerprint(&public_key) != signed.manifest.signer_fingerprint {
+        return Err("Signer fingerprint doesn't match the bundle's key".to_string());
+    }
+    let key = VerifyingKey::from_bytes(&public_key).map_err(|_| "Bundle public key is invalid".to_string())?;
+    key.verify(&signing_bytes(&signed.manifest)?, &Signature::from_bytes(&signature))
+        .map_err(|_| "Signature doesn't match: the bundle was altered or signed with another key".to_string())
+}
+
+/// The selected claims plus, if asked, their verifications and the evidence
+/// those cite, in the order they are written to the bundle
+fn select(store: &TruthStore, hashes: &[String], include_verifications: bool) -> Result<Vec<StoredObject>, String> {
+    let mut claims = BTreeSet::new();
+    for hash in hashes {
+        let id = ObjectId::parse(hash.trim())?;
+        if !store.contains(ObjectKind::Claim, &id) {
+            return Err(format!("Claim not found: {}", id));
+        }
+        claims.insert(id);
+    }
+    if claims.is_empty() {
+        return Err("Select at least one claim to share".to_string());
+    }
+    if claims.len() > MAX_BUNDLE_CLAIMS {
+        return Err(format!("A bundle can hold at most {} claims", MAX_BUNDLE_CLAIMS));
+    }
+    let wanted: BTreeSet<&str> = claims.iter().map(|c| c.as_str()).collect();
+
+    let mut selected: Vec<(ObjectKind, ObjectId)> = claims.iter().map(|id| (ObjectKind::Claim, id.clone())).collect();
+    if include_verifications {
+        let mut evidence = BTreeSet::new();
+        for entry in store.iter(ObjectKind::Verification) {
+            // Unreadable verifications are reported by the listing, not here
+            let Ok(value) = store.read_json(ObjectKind::Verification, &entry.id) else {
+                continue;
+            };
+            let verification = Verification::from(value);
+            if !wanted.contains(verification.claim_hash.as_str()) {
+                continue;
+            }
+            evidence.extend(
+                verification
+                    .evidence
+                    .iter()
+                    .filter_map(|hash| ObjectId::parse(hash).ok())
+                    .filter(|id| store.contains(ObjectKind::Evidence, id)),
+            );
+            selected.push((ObjectKind::Verification, entry.id));
+        }
+        selected.extend(evidence.into_iter().map(|id| (ObjectKind::Evidence, id)));
+    }
+    if selected.len() > MAX_BUNDLE_OBJECTS {
+        return Err(format!("A bundle can hold at most {} objects", MAX_BUNDLE_OBJECTS));
+    }
+
+    selected
+        .into_iter()
+        .map(|(kind, id)| {
+            let bytes = repo_crypto::open(store.root(), Binding::Object(kind, &id), &store.read_raw(kind, &id)?)?;
+            Ok(StoredObject { kind, id, bytes })
+        })
+        .collect()
+}
+
+struct StoredObject {
+    kind: ObjectKind,
+    id: ObjectId,
+    /// Compressed, unsealed
+    bytes: Vec<u8>,
+}
+
+fn write_bundle(dest: &Path, signed: &SignedManifest, objects: &[StoredObject]) -> Result<(), String> {
+    let write_err = |e: String| sanitize_error(&format!("Failed to write bundle: {}", e));
+    let tmp = dest.with_extension(format!("tmp-{}", uuid::Uuid::new_v4().simple()));
+    let result = (|| {
+        let mut zip = zip::ZipWriter::new(File::create(&tmp).map_err(|e| e.to_string())?);
+        // Objects are already compressed
+        let stored = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        let manifest = serde_json::to_vec_pretty(signed).map_err(|e| e.to_string())?;
+        zip.start_file(
+            MANIFEST_FILE,
+            stored.compression_method(zip::CompressionMethod::Deflated),
+        )
+        .map_err(|e| e.to_string())?;
+        zip.write_all(&manifest).map_err(|e| e.to_string())?;
+        for object in objects {
+            zip.start_file(entry_name(object.kind, &object.id), stored)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(&object.bytes).map_err(|e| e.to_string())?;
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp, dest).map_err(|e| e.to_string())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result.map_err(write_err)
+}
+
+fn create_bundle(
+    repo: &Path,
+    dest: &Path,
+    hashes: &[String],
+    include_verifications: bool,
+    key: &SigningKey,
+) -> Result<ShareBundle, String> {
+    let store = TruthStore::new(repo);
+    if !store.is_repo() {
+        return Err("No truth repository to share from".to_string());
+    }
+    let dest = if dest.is_dir() {
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        dest.join(format!("truthgit-share-{}.zip", stamp))
+    } else {
+        dest.to_path_buf()
+    };
+    if dest.starts_with(repo) {
+        return Err("Bundle destination must be outside the repository".to_string());
+    }
+
+    let objects = select(&store, hashes, include_verifications)?;
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        created_by: identity::current().map(|a| a.name),
+        signer_fingerprint: identity::fingerprint(key.verifying_key().as_bytes()),
+        claims: objects
+            .iter()
+            .filter(|o| o.kind == ObjectKind::Claim)
+            .map(|o| o.id.to_string())
+            .collect(),
+        objects: objects
+            .iter()
+            .map(|o| BundleObject {
+                kind: o.kind,
+                hash: o.id.clone(),
+                sha256: sha256_hex(&o.bytes),
+            })
+            .collect(),
+    };
+    let signed = sign(manifest, key)?;
+    write_bundle(&dest, &signed, &objects)?;
+    Ok(ShareBundle {
+        path: dest.to_string_lossy().to_string(),
+        objects: count(&signed.manifest.objects),
+        signer_fingerprint: signed.manifest.signer_fingerprint,
+    })
+}
+
+/// Read and check a bundle: signature, then each listed object's digest,
+/// decoding and `$hash`. Nothing is written.
+fn open_bundle(src: &Path) -> Result<(SignedManifest, Vec<StoredObject>), String> {
+    let file = File::open(src).map_err(|e| sanitize_error(&format!("Failed to open bundle: {}", e)))?;
+    let mut zip = zip::ZipArchive::new(io::BufReader::new(file)).map_err(|_| "Not a share bundle".to_string())?;
+    let read = |entry: &mut dyn Read, limit: u64| -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        entry
+            .take(limit + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read bundle: {}", e))?;
+        if bytes.len() as u64 > limit {
+            return Err("The bundle holds a file that is too large".to_string());
+        }
+        Ok(bytes)
+    };
+
+    let manifest = {
+        let mut entry = zip
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| "Not a share bundle (no manifest)".to_string())?;
+        read(&mut entry, MAX_MANIFEST_SIZE)?
+    };
+    let signed: SignedManifest =
+        serde_json::from_slice(&manifest).map_err(|e| format!("Bundle manifest is malformed: {}", e))?;
+    if signed.manifest.format > BUNDLE_FORMAT {
+        return Err("This bundle was made by a newer version of the app".to_string());
+    }
+    if let Err(e) = check_signature(&signed) {
+        security_events::record(SecurityEventKind::SignatureFailure, "import_share_bundle", &e);
+        return Err(e);
+    }
+    if signed.manifest.objects.len() > MAX_BUNDLE_OBJECTS {
+        return Err(format!("A bundle can hold at most {} objects", MAX_BUNDLE_OBJECTS));
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut objects = Vec::with_capacity(signed.manifest.objects.len());
+    for listed in &signed.manifest.objects {
+        let name = entry_name(listed.kind, &listed.hash);
+        if !seen.insert(name.clone()) {
+            return Err(format!("The bundle lists {} twice", name));
+        }
+        let mut entry = zip
+            .by_name(&name)
+            .map_err(|_| format!("The bundle is missing {}", name))?;
+        let bytes = read(&mut entry, MAX_DECOMPRESSED_SIZE as u64)?;
+        if sha256_hex(&bytes) != listed.sha256 {
+            return Err(format!("{} doesn't match the signed manifest", name));
+        }
+        if listed.kind == ObjectKind::Evidence {
+            truth_store::inflate(&bytes, MAX_DECOMPRESSED_SIZE)?;
+        } else {
+            let value = truth_store::decode_json(&bytes)?;
+            let embedded = value.get("$hash").and_then(|h| h.as_str());
+            if embedded.is_some_and(|hash| hash != listed.hash.as_str()) {
+                return Err(format!("{}: content doesn't match its hash", name));
+            }
+        }
+        objects.push(StoredObject {
+            kind: listed.kind,
+            id: listed.hash.clone(),
+            bytes,
+        });
+    }
+    Ok((signed, objects))
+}
+
+/// Add the bundle's objects `repo` lacks; returns (imported, duplicates)
+fn import_objects(repo: &Path, objects: Vec<StoredObject>) -> Result<(usize, usize), String> {
+    let store = TruthStore::new(repo);
+    let (mut imported, mut duplicates) = (0, 0);
+    for object in objects {
+        if store.contains(object.kind, &object.id) {
+            duplicates += 1;
+            continue;
+        }
+        let bytes = repo_crypto::seal(repo, Binding::Object(object.kind, &object.id), object.bytes)?;
+        store.write_raw(object.kind, &object.id, &bytes)?;
+        imported += 1;
+    }
+    Ok((imported, duplicates))
+}
+
+/// Write a signed bundle of the claims in `hashes` to `path` (a file, or a
+/// folder to create it in), with their verification chains if asked
+#[tauri::command]
+pub async fn create_share_bundle(
+    hashes: Vec<String>,
+    include_verifications: bool,
+    path: String,
+) -> Result<ShareBundle, String> {
+    if mock::is_active() {
+        return Err("Share bundles are disabled in mock mode".to_string());
+    }
+    let repo = repo_state::ready_path()?;
+    let dest = PathBuf::from(path.trim());
+    if !dest.is_absolute() {
+        return Err("Bundle destination must be an absolute path".to_string());
+    }
+    let bundle = run_blocking(move || {
+        let key = receipts::load_or_create_key()?;
+        create_bundle(&repo, &dest, &hashes, include_verifications, &key)
+    })
+    .await?;
+    log::info!(
+        "Created share bundle with {} claims and {} verifications",
+        bundle.objects.claims,
+        bundle.objects.verifications
+    );
+    Ok(bundle)
+}
+
+/// Check a share bundle and add its objects to the active repo
+#[tauri::command]
+pub async fn import_share_bundle(path: String) -> Result<ShareImport, String> {
+    if mock::is_active() {
+        return Err("Share bundles are disabled in mock mode".to_string());
+    }
+    let repo = repo_state::ready_path()?;
+    let src = PathBuf::from(path.trim());
+    consent::require_read(&src).await?;
+    run_blocking(move || {
+        if !TruthStore::new(&repo).is_repo() {
+            return Err("No active truth repository to import into".to_string());
+        }
+        let (signed, objects) = open_bundle(&src)?;
+        let (imported, duplicates) = import_objects(&repo, objects)?;
+        let manifest = signed.manifest;
+        let own_fingerprint = receipts::own_fingerprint()?;
+
+        let entry = AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action: IMPORT_ACTION.to_string(),
+            claim: format!(
+                "Imported {} claims signed by {}",
+                manifest.claims.len(),
+                manifest.signer_fingerprint
+            ),
+            result_status: imported.to_string(),
+            result_action: manifest.signer_fingerprint.clone(),
+            actor: identity::current(),
+            app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            ..Default::default()
+        };
+        if let Err(e) = append_audit_entry(entry) {
+            log::warn!("Failed to record bundle import in the audit trail: {}", e);
+        }
+        log::info!(
+            "Imported share bundle: {} new objects, {} duplicates",
+            imported,
+            duplicates
+        );
+        Ok(ShareImport {
+            objects: count(&manifest.objects),
+            imported,
+            duplicates,
+            created_at: manifest.created_at,
+            created_by: manifest.created_by,
+            signed_by_me: own_fingerprint.as_deref() == Some(manifest.signer_fingerprint.as_str()),
+            signer_fingerprint: manifest.signer_fingerprint,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use flate2::write::ZlibEncoder;
+
+    fn compressed(json: &str) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn put(store: &TruthStore, kind: ObjectKind, hash: &str, json: &str) {
+        store
+            .write_raw(kind, &ObjectId::parse(hash).unwrap(), &compressed(json))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let tmp = TempDir::new("truthgit-share");
+        let root = tmp.path();
+        let (sender, receiver) = (root.join("sender"), root.join("receiver"));
+        let store = TruthStore::new(&sender);
+        put(
+            &store,
+            ObjectKind::Claim,
+            "aa01",
+            r#"{"$hash": "aa01", "content": "Water boils at 100 C"}"#,
+        );
+        put(
+            &store,
+            ObjectKind::Claim,
+            "bb02",
+            r#"{"$hash": "bb02", "content": "The moon is cheese"}"#,
+        );
+        put(
+            &store,
+            ObjectKind::Verification,
+            "cc03",
+            r#"{"$hash": "cc03", "claim_hash": "aa01"}"#,
+        );
+        put(
+            &store,
+            ObjectKind::Verification,
+            "dd04",
+            r#"{"$hash": "dd04", "claim_hash": "bb02"}"#,
+        );
+        put(
+            &TruthStore::new(&receiver),
+            ObjectKind::Claim,
+            "aa01",
+            r#"{"$hash": "aa01"}"#,
+        );
+        let key = SigningKey::from_bytes(&[7; 32]);
+
+        let dest = root.join("bundle.zip");
+        let bundle = create_bundle(&sender, &dest, &["aa01".to_string()], true, &key).unwrap();
+        assert_eq!((bundle.objects.claims, bundle.objects.verifications), (1, 1));
+
+        let (signed, objects) = open_bundle(&dest).unwrap();
+        assert_eq!(signed.manifest.claims, vec!["aa01".to_string()]);
+        assert_eq!(import_objects(&receiver, objects).unwrap(), (1, 1));
+        let receiving = TruthStore::new(&receiver);
+        assert!(receiving.contains(ObjectKind::Verification, &ObjectId::parse("cc03").unwrap()));
+        assert!(!receiving.contains(ObjectKind::Verification, &ObjectId::parse("dd04").unwrap()));
+
+        let mut tampered = signed.clone();
+        tampered.manifest.claims.push("bb02".to_string());
+        assert!(check_signature(&tampered).unwrap_err().contains("altered"));
+    }
+}
@@ -0,0 +1,292 @@
+// ==================== GLOBAL SHORTCUTS ====================
+//
+// System-wide keyboard shortcuts, configured under `shortcuts` in settings
+// (an empty accelerator disables that action). Bindings are re-registered
+// whenever settings change. Conflicts are caught in two places: duplicate or
+// reserved bindings fail settings validation, and bindings another app
+// already holds are reported per action by `get_shortcut_status`.
+//
+// quick_verify verifies the clipboard and quick_capture appends it to the
+// inbox note, both in the background; quick_search and new_claim show the
+// window and emit `shortcut://action` for the frontend.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter, Listener};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::settings_events::{SettingsChanged, SETTINGS_CHANGED_EVENT};
+use crate::{clipboard_verify, quick_capture, tray, SETTINGS};
+
+pub const SHORTCUT_ACTION_EVENT: &str = "shortcut://action";
+
+/// Shortcuts every app relies on; binding them globally would break copy/paste etc.
+const RESERVED_SHORTCUTS: &[&str] = &[
+    "CommandOrControl+A",
+    "CommandOrControl+C",
+    "CommandOrControl+Q",
+    "CommandOrControl+S",
+    "CommandOrControl+V",
+    "CommandOrControl+W",
+    "CommandOrControl+X",
+    "CommandOrControl+Z",
+    "Alt+F4",
+    "Alt+Tab",
+];
+
+static STATUS: RwLock<Vec<ShortcutStatus>> = RwLock::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutSettings {
+    pub quick_verify: String,
+    pub quick_search: String,
+    pub new_claim: String,
+    pub quick_capture: String,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        Self {
+            quick_verify: "CommandOrControl+Shift+V".to_string(),
+            quick_search: "CommandOrControl+Shift+K".to_string(),
+            new_claim: "CommandOrControl+Shift+N".to_string(),
+            quick_capture: "CommandOrControl+Shift+J".to_string(),
+        }
+    }
+}
+
+impl ShortcutSettings {
+    /// (action, accelerator) pairs, disabled actions included
+    pub fn bindings(&self) -> [(&'static str, &str); 4] {
+        [
+            ("quick_verify", self.quick_verify.as_str()),
+            ("quick_search", self.quick_search.as_str()),
+            ("new_claim", self.new_claim.as_str()),
+            ("quick_capture", self.quick_capture.as_str()),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutStatus {
+    pub action: String,
+    pub accelerator: String,
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShortcutConflict {
+    pub action: String,
+    pub accelerator: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutAction {
+    /// "quick_search" or "new_claim"
+    pub action: String,
+}
+
+fn modifier_name(token: &str) -> Option<&'static str> {
+    // CommandOrControl is Cmd on macOS and Ctrl elsewhere; fold the native name into it
+    let macos = cfg!(target_os = "macos");
+    match token.to_lowercase().as_str() {
+        "commandorcontrol" | "cmdorctrl" | "commandorctrl" | "cmdorcontrol" => Some("CommandOrControl"),
+        "control" | "ctrl" => Some(if macos { "Control" } else { "CommandOrControl" }),
+        "super" | "cmd" | "command" | "meta" => Some(if macos { "CommandOrControl" } else { "Super" }),
+        "alt" | "option" => Some("Alt"),
+        "shift" => Some("Shift"),
+        _ => None,
+    }
+}
+
+/// Canonical form of an accelerator, so that "shift+ctrl+k" and
+/// "CmdOrCtrl+Shift+K" compare equal. Requires at least one modifier: a bare
+/// global key would swallow that key in every other app.
+pub fn normalize_accelerator(accelerator: &str) -> Result<String, String> {
+    let mut modifiers: Vec<&str> = Vec::new();
+    let mut key: Option<String> = None;
+    for token in accelerator.split('+').map(str::trim) {
+        if token.is_empty() {
+            return Err(format!("\"{}\" is not a valid shortcut", accelerator));
+        }
+        match modifier_name(token) {
+            Some(modifier) if !modifiers.contains(&modifier) => modifiers.push(modifier),
+            Some(_) => {}
+            None if key.is_none() => key = Some(token.to_uppercase()),
+            None => return Err(format!("\"{}\" has more than one non-modifier key", accelerator)),
+        }
+    }
+    let key = key.ok_or_else(|| format!("\"{}\" has no key, only modifiers", accelerator))?;
+    if modifiers.is_empty() {
+        return Err(format!("\"{}\" needs a modifier such as Ctrl, Alt or Shift", accelerator));
+    }
+    let order = ["CommandOrControl", "Control", "Super", "Alt", "Shift"];
+    modifiers.sort_by_key(|m| order.iter().position(|o| o == m));
+    Ok(format!("{}+{}", modifiers.join("+"), key))
+}
+
+/// Invalid, reserved and duplicate bindings; disabled (empty) actions are skipped
+pub fn find_conflicts(shortcuts: &ShortcutSettings) -> Vec<ShortcutConflict> {
+    let mut conflicts = Vec::new();
+    let mut seen: Vec<(&str, String)> = Vec::new();
+    for (action, accelerator) in shortcuts.bindings() {
+        if accelerator.trim().is_empty() {
+            continue;
+        }
+        let conflict = |message: String| ShortcutConflict {
+            action: action.to_string(),
+            accelerator: accelerator.to_string(),
+            message,
+        };
+        let normalized = match normalize_accelerator(accelerator) {
+            Ok(normalized) => normalized,
+            Err(e) => {
+                conflicts.push(conflict(e));
+                continue;
+            }
+        };
+        if RESERVED_SHORTCUTS.iter().any(|r| normalize_accelerator(r).as_deref() == Ok(normalized.as_str())) {
+            conflicts.push(conflict(format!("{} is a standard system shortcut", accelerator)));
+        } else if let Some((other, _)) = seen.iter().find(|(_, n)| *n == normalized) {
+            conflicts.push(conflict(format!("{} is already used by {}", accelerator, other)));
+        }
+        seen.push((action, normalized));
+    }
+    conflicts
+}
+
+fn run_action(app: &AppHandle, action: &str) {
+    match action {
+        "quick_verify" => clipboard_verify::spawn_verify(app),
+        "quick_capture" => quick_capture::spawn_capture(app),
+        _ => {
+            tray::show_main_window(app);
+            let payload = ShortcutAction {
+                action: action.to_string(),
+            };
+            if let Err(e) = app.emit(SHORTCUT_ACTION_EVENT, payload) {
+                log::warn!("Failed to emit {}: {}", SHORTCUT_ACTION_EVENT, e);
+            }
+        }
+    }
+}
+
+fn register(app: &AppHandle, action: &'static str, accelerator: &str) -> Result<(), String> {
+    let shortcut = Shortcut::from_str(accelerator).map_err(|e| format!("Invalid shortcut: {}", e))?;
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                run_action(app, action);
+            }
+        })
+        .map_err(|e| format!("Could not register (possibly in use by another app): {}", e))
+}
+
+/// Replace all registered shortcuts with the configured ones
+fn apply(app: &AppHandle, shortcuts: &ShortcutSettings) {
+    if let Err(e) = app.global_shortcut().unregister_all() {
+        log::warn!("Failed to unregister shortcuts: {}", e);
+    }
+    let conflicts = find_conflicts(shortcuts);
+
+    let mut statuses = Vec::new();
+    for (action, accelerator) in shortcuts.bindings() {
+        if accelerator.trim().is_empty() {
+            continue;
+        }
+        let result = match conflicts.iter().find(|c| c.action == action) {
+            Some(conflict) => Err(conflict.message.clone()),
+            None => register(app, action, accelerator.trim()),
+        };
+        if let Err(e) = &result {
+            log::warn!("Shortcut {} for {} not registered: {}", accelerator, action, e);
+        }
+        statuses.push(ShortcutStatus {
+            action: action.to_string(),
+            accelerator: accelerator.to_string(),
+            registered: result.is_ok(),
+            error: result.err(),
+        });
+    }
+    if let Ok(mut status) = STATUS.write() {
+        *status = statuses;
+    }
+}
+
+/// Register the configured shortcuts and keep them in sync with settings
+pub fn init(app: &AppHandle) {
+    let initial = SETTINGS.read().map(|s| s.shortcuts.clone()).unwrap_or_default();
+    apply(app, &initial);
+
+    let handle = app.clone();
+    let current = std::sync::Mutex::new(initial);
+    app.listen(SETTINGS_CHANGED_EVENT, move |event| {
+        let Ok(changed) = serde_json::from_str::<SettingsChanged>(event.payload()) else {
+            return;
+        };
+        let Ok(mut current) = current.lock() else {
+            return;
+        };
+        if *current != changed.settings.shortcuts {
+            *current = changed.settings.shortcuts.clone();
+            apply(&handle, &current);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_shortcut_status() -> Result<Vec<ShortcutStatus>, String> {
+    Ok(STATUS.read().map_err(|e| format!("Lock error: {}", e))?.clone())
+}
+
+/// Check candidate bindings before saving, for inline errors in the Settings UI
+#[tauri::command]
+pub async fn check_shortcut_conflicts(shortcuts: ShortcutSettings) -> Result<Vec<ShortcutConflict>, String> {
+    Ok(find_conflicts(&shortcuts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_accelerator() {
+        assert_eq!(
+            normalize_accelerator("shift+cmdorctrl+k").unwrap(),
+            normalize_accelerator("CommandOrControl+Shift+K").unwrap()
+        );
+        assert!(normalize_accelerator("K").is_err());
+        assert!(normalize_accelerator("Ctrl+Shift").is_err());
+        assert!(normalize_accelerator("Ctrl+K+L").is_err());
+        assert!(normalize_accelerator("Ctrl++").is_err());
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        assert!(find_conflicts(&ShortcutSettings::default()).is_empty());
+
+        let shortcuts = ShortcutSettings {
+            quick_verify: "CommandOrControl+Shift+K".to_string(),
+            quick_search: "Shift+CmdOrCtrl+K".to_string(),
+            new_claim: "CmdOrCtrl+C".to_string(),
+            quick_capture: String::new(),
+        };
+        let conflicts = find_conflicts(&shortcuts);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].action, "quick_search");
+        assert!(conflicts[0].message.contains("quick_verify"));
+        assert_eq!(conflicts[1].action, "new_claim");
+
+        // Disabled actions never conflict
+        let shortcuts = ShortcutSettings {
+            quick_search: String::new(),
+            new_claim: String::new(),
+            ..Default::default()
+        };
+        assert!(find_conflicts(&shortcuts).is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+// ==================== SINGLE INSTANCE ====================
+//
+// Only one copy of the app runs at a time: two would race on settings.json,
+// audit.json and the indexes. A second launch hands its command line to the
+// running instance over the single-instance plugin's IPC and exits. The
+// running instance treats truthgit:// arguments as deep links and existing
+// files as a drop on the window, then brings its window forward.
+
+use std::path::{Path, PathBuf};
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Wry};
+
+use crate::deep_links::{self, DEEP_LINK_SCHEME};
+use crate::{file_intake, tray};
+
+/// Arguments of a second launch: deep links, and files relative to its
+/// working directory. The executable name and flags are skipped.
+fn classify_args(argv: &[String], cwd: &Path) -> (Vec<String>, Vec<PathBuf>) {
+    let prefix = format!("{}://", DEEP_LINK_SCHEME);
+    let mut links = Vec::new();
+    let mut files = Vec::new();
+    for arg in argv.iter().skip(1) {
+        if arg.to_ascii_lowercase().starts_with(&prefix) {
+            links.push(arg.clone());
+        } else if !arg.starts_with('-') {
+            let path = cwd.join(arg);
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    (links, files)
+}
+
+fn on_second_launch(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    let (links, files) = classify_args(&argv, Path::new(&cwd));
+    log::info!(
+        "Second launch forwarded {} link(s) and {} file(s)",
+        links.len(),
+        files.len()
+    );
+    if !links.is_empty() {
+        deep_links::handle_links(app, &links);
+    }
+    if !files.is_empty() {
+        file_intake::handle_drop(app, files);
+    }
+    tray::show_main_window(app);
+}
+
+/// Must be the first plugin registered, so a second instance exits before
+/// any other plugin starts
+pub fn plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_single_instance::init(on_second_launch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_classify_args() {
+        let tmp = TempDir::new("truthgit-single-instance");
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("notes.md"), "claim").unwrap();
+
+        let argv: Vec<String> = ["truthgit-desktop", "--minimized", "TruthGit://claim/abc123", "notes.md", "missing.md"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (links, files) = classify_args(&argv, dir);
+        assert_eq!(links, vec!["TruthGit://claim/abc123"]);
+        assert_eq!(files, vec![dir.join("notes.md")]);
+    }
+}
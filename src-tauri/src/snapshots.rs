@@ -0,0 +1,257 @@
+// ==================== REPO SNAPSHOTS ====================
+//
+// What changed in the truth repo between two points in time, for a "what
+// happened this week" view. The repo keeps no snapshots of its own, so the
+// state at a moment is rebuilt from timestamps: claims created by then, and
+// each claim's newest verification at or before then. Comparing two such
+// states gives new claims, changed verdicts and confidence shifts; audit
+// volume counts the audit entries written between the two moments. Records
+// with a missing or unreadable timestamp are left out.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::exports::field;
+use crate::index::created_at;
+use crate::verification::Verification;
+use crate::{mock, read_audit_trail, read_claims, read_verifications, run_blocking, AuditEntry};
+
+/// Smallest confidence move reported when the verdict stayed the same
+const CONFIDENCE_SHIFT: f64 = 0.05;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewClaim {
+    pub hash: String,
+    pub content: String,
+    pub domain: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerdictChange {
+    pub hash: String,
+    pub claim: String,
+    /// None if the claim had no verification at the first date
+    pub from: Option<String>,
+    pub to: String,
+    pub confidence: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfidenceShift {
+    pub hash: String,
+    pub claim: String,
+    pub verdict: String,
+    pub from: f64,
+    pub to: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditVolume {
+    pub total: usize,
+    pub by_action: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDiff {
+    /// The two moments compared, RFC 3339
+    pub from: String,
+    pub to: String,
+    /// Oldest first
+    pub new_claims: Vec<NewClaim>,
+    pub verdict_changes: Vec<VerdictChange>,
+    /// Largest shift first
+    pub confidence_shifts: Vec<ConfidenceShift>,
+    pub audit: AuditVolume,
+}
+
+/// An RFC 3339 timestamp, or a YYYY-MM-DD date meaning the end of that day
+pub fn parse_moment(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.to_utc());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.succ_opt()?.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc() - chrono::Duration::nanoseconds(1))
+        .ok_or_else(|| format!("Invalid date: {} (use YYYY-MM-DD or an RFC 3339 timestamp)", value))
+}
+
+pub fn instant(at: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(at).ok().map(|t| t.to_utc())
+}
+
+/// Each claim's newest verification at or before `at`
+pub fn verdicts_at(verifications: &[Verification], at: DateTime<Utc>) -> HashMap<&str, (DateTime<Utc>, &Verification)> {
+    let mut latest: HashMap<&str, (DateTime<Utc>, &Verification)> = HashMap::new();
+    for v in verifications {
+        let Some(time) = instant(&v.timestamp).filter(|t| *t <= at) else {
+            continue;
+        };
+        if v.claim_hash.is_empty() {
+            continue;
+        }
+        let newer = latest.get(v.claim_hash.as_str()).is_none_or(|(seen, _)| time > *seen);
+        if newer {
+            latest.insert(&v.claim_hash, (time, v));
+        }
+    }
+    latest
+}
+
+pub fn diff(
+    claims: &[serde_json::Value],
+    verifications: &[Verification],
+    audit: &[AuditEntry],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> SnapshotDiff {
+    let in_window = |at: &str| instant(at).is_some_and(|t| t > from && t <= to);
+
+    let mut new_claims: Vec<NewClaim> = claims
+        .iter()
+        .filter(|claim| in_window(created_at(claim)))
+        .map(|claim| NewClaim {
+            hash: field(claim, "$hash").to_string(),
+            content: field(claim, "content").to_string(),
+            domain: field(claim, "domain").to_string(),
+            created_at: created_at(claim).to_string(),
+        })
+        .collect();
+    new_claims.sort_by_key(|c| instant(&c.created_at));
+
+    let content: HashMap<&str, &str> = claims
+        .iter()
+        .map(|claim| (field(claim, "$hash"), field(claim, "content")))
+        .collect();
+    let text = |v: &Verification| {
+        content
+            .get(v.claim_hash.as_str())
+            .filter(|c| !c.is_empty())
+            .map_or(v.claim.clone(), |c| c.to_string())
+    };
+
+    let before = verdicts_at(verifications, from);
+    let mut verdict_changes = Vec::new();
+    let mut confidence_shifts = Vec::new();
+    let mut after: Vec<_> = verdicts_at(verifications, to).into_iter().collect();
+    after.sort_by_key(|(hash, _)| *hash);
+    for (hash, (_, now)) in after {
+        let then = before.get(hash).map(|(_, v)| *v);
+        match then {
+            Some(then) if then.verdict.eq_ignore_ascii_case(&now.verdict) => {
+                if let (Some(a), Some(b)) = (then.confidence, now.confidence) {
+                    if (b - a).abs() >= CONFIDENCE_SHIFT {
+                        confidence_shifts.push(ConfidenceShift {
+                            hash: hash.to_string(),
+                            claim: text(now),
+                            verdict: now.verdict.clone(),
+                            from: a,
+                            to: b,
+                        });
+                    }
+                }
+            }
+            _ => verdict_changes.push(VerdictChange {
+                hash: hash.to_string(),
+                claim: text(now),
+                from: then.map(|v| v.verdict.clone()),
+                to: now.verdict.clone(),
+                confidence: now.confidence,
+            }),
+        }
+    }
+    confidence_shifts.sort_by(|a, b| (b.to - b.from).abs().total_cmp(&(a.to - a.from).abs()));
+
+    let mut volume = AuditVolume::default();
+    for entry in audit.iter().filter(|e| in_window(&e.timestamp)) {
+        volume.total += 1;
+        *volume.by_action.entry(entry.action.clone()).or_default() += 1;
+    }
+
+    SnapshotDiff {
+        from: from.to_rfc3339(),
+        to: to.to_rfc3339(),
+        new_claims,
+        verdict_changes,
+        confidence_shifts,
+        audit: volume,
+    }
+}
+
+/// What changed between `date_a` and `date_b` (YYYY-MM-DD, meaning the end
+/// of that day, or RFC 3339). The dates may be given in either order.
+#[tauri::command]
+pub async fn compare_repo_snapshots(date_a: String, date_b: String) -> Result<SnapshotDiff, String> {
+    let (a, b) = (parse_moment(&date_a)?, parse_moment(&date_b)?);
+    let (from, to) = if a <= b { (a, b) } else { (b, a) };
+    run_blocking(move || {
+        let claims = read_claims(None)?.claims;
+        let verifications = read_verifications()?.verifications;
+        let audit = if mock::is_active() {
+            Vec::new()
+        } else {
+            read_audit_trail()?
+        };
+        Ok(diff(&claims, &verifications, &audit, from, to))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_between_dates() {
+        let claims = vec![
+            json!({"$hash": "aa", "content": "Old claim", "metadata": {"created_at": "2026-01-01T00:00:00Z"}}),
+            json!({"$hash": "bb", "content": "New claim", "metadata": {"created_at": "2026-01-06T00:00:00Z"}}),
+        ];
+        let verifications = [
+            json!({"claim_hash": "aa", "verdict": "PASSED", "confidence": 0.9, "timestamp": "2026-01-02T00:00:00Z"}),
+            json!({"claim_hash": "aa", "verdict": "PASSED", "confidence": 0.6, "timestamp": "2026-01-06T00:00:00Z"}),
+            json!({"claim_hash": "bb", "verdict": "FAILED", "confidence": 0.3, "timestamp": "2026-01-07T00:00:00Z"}),
+            json!({"claim_hash": "bb", "verdict": "PASSED", "timestamp": "2026-02-01T00:00:00Z"}),
+        ]
+        .map(Verification::from);
+        let audit = vec![
+            AuditEntry {
+                action: "governance_verify".to_string(),
+                timestamp: "2026-01-06T12:00:00+02:00".to_string(),
+                ..Default::default()
+            },
+            AuditEntry {
+                action: "governance_verify".to_string(),
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let result = diff(
+            &claims,
+            &verifications,
+            &audit,
+            parse_moment("2026-01-04").unwrap(),
+            parse_moment("2026-01-10T00:00:00Z").unwrap(),
+        );
+        assert_eq!(result.new_claims.len(), 1);
+        assert_eq!(result.new_claims[0].hash, "bb");
+        let [change] = &result.verdict_changes[..] else {
+            panic!("expected one verdict change");
+        };
+        assert_eq!(
+            (change.claim.as_str(), change.from.as_deref(), change.to.as_str()),
+            ("New claim", None, "FAILED")
+        );
+        let [shift] = &result.confidence_shifts[..] else {
+            panic!("expected one confidence shift");
+        };
+        assert_eq!((shift.from, shift.to), (0.9, 0.6));
+        assert_eq!(result.audit.total, 1);
+        assert!(parse_moment("last week").is_err());
+    }
+}
@@ -0,0 +1,331 @@
+// ==================== CLAIM SOURCES ====================
+//
+// Where a claim came from: the URL, its title, when it was accessed and,
+// once captured, the hash of an archived copy. Sources written by the CLI
+// sit in the claim's `metadata.sources`; claim objects are content-addressed
+// and can't change, so sources added from the app are kept per claim hash in
+// sources.json at the repo root. Listings merge both, claim sources first.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::identity::{self, Actor};
+use crate::journal::{self, Store};
+use crate::json_store::{load_json, save_json, StoreLock};
+use crate::reviews::{short_hash, validate_hash};
+use crate::{get_truth_path, mock, read_claim, read_claims, repo_state, run_blocking};
+
+const MAX_URL_LEN: usize = 2048;
+const MAX_TITLE_LEN: usize = 300;
+
+/// Serializes read-modify-write cycles on sources.json
+static SOURCES_LOCK: StoreLock = StoreLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClaimSource {
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Date the source was read (YYYY-MM-DD)
+    #[serde(default)]
+    pub accessed: Option<String>,
+    /// SHA-256 of an archived copy stored as a blob object
+    #[serde(default)]
+    pub archived_hash: Option<String>,
+    /// Who added the source from the app; None for sources in the claim itself
+    #[serde(default)]
+    pub added_by: Option<Actor>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnsourcedClaim {
+    pub hash: String,
+    pub content: String,
+    pub domain: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnsourcedReport {
+    /// Claims looked at
+    pub total: usize,
+    pub unsourced: Vec<UnsourcedClaim>,
+    /// The claim index was still being built, so some claims may be missing
+    pub stale: bool,
+}
+
+fn sources_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("sources.json")
+}
+
+pub(crate) fn load_sources(truth_path: &Path) -> Result<BTreeMap<String, Vec<ClaimSource>>, String> {
+    let _guard = SOURCES_LOCK.lock(&sources_path(truth_path))?;
+    load_json(&sources_path(truth_path))
+}
+
+/// Sources in a claim's `metadata.sources`. Entries that don't parse or have
+/// no URL are skipped rather than hiding the rest.
+fn embedded_sources(claim: &serde_json::Value) -> Vec<ClaimSource> {
+    let Some(entries) = claim.pointer("/metadata/sources").and_then(|s| s.as_array()) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| serde_json::from_value::<ClaimSource>(entry.clone()).ok())
+        .filter(|s| !s.url.trim().is_empty())
+        .map(|s| ClaimSource { added_by: None, ..s })
+        .collect()
+}
+
+/// Claim sources followed by added ones whose URL isn't already listed
+fn merge(embedded: Vec<ClaimSource>, added: Vec<ClaimSource>) -> Vec<ClaimSource> {
+    let mut sources = embedded;
+    for source in added {
+        if !sources.iter().any(|s| s.url == source.url) {
+            sources.push(source);
+        }
+    }
+    sources
+}
+
+fn validate(source: ClaimSource) -> Result<ClaimSource, String> {
+    let url = source.url.trim();
+    if url.len() > MAX_URL_LEN {
+        return Err(format!("URL is too long (max {} characters)", MAX_URL_LEN));
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid source URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Source URL must be an http(s) link".to_string());
+    }
+    let title = source.title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    if title.as_ref().is_some_and(|t| t.chars().count() > MAX_TITLE_LEN) {
+        return Err(format!("Source title must be under {} characters", MAX_TITLE_LEN));
+    }
+    let accessed = match source.accessed.map(|a| a.trim().to_string()).filter(|a| !a.is_empty()) {
+        Some(date) => {
+            chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid accessed date: {} (use YYYY-MM-DD)", date))?;
+            date
+        }
+        None => chrono::Utc::now().format("%Y-%m-%d").to_string(),
+    };
+    let archived_hash = source
+        .archived_hash
+        .map(|h| h.trim().to_lowercase())
+        .filter(|h| !h.is_empty());
+    if let Some(hash) = &archived_hash {
+        validate_hash(hash)?;
+    }
+    Ok(ClaimSource {
+        url: parsed.to_string(),
+        title,
+        accessed: Some(accessed),
+        archived_hash,
+        added_by: None,
+    })
+}
+
+/// Change the sources added to one claim, returning what it had before
+fn update_sources(
+    truth_path: &Path,
+    hash: &str,
+    change: impl FnOnce(&mut Vec<ClaimSource>) -> Result<(), String>,
+) -> Result<Option<Vec<ClaimSource>>, String> {
+    let _guard = SOURCES_LOCK.lock(&sources_path(truth_path))?;
+    let mut all: BTreeMap<String, Vec<ClaimSource>> = load_json(&sources_path(truth_path))?;
+    let before = all.get(hash).cloned();
+    let added = all.entry(hash.to_string()).or_default();
+    change(added)?;
+    if added.is_empty() {
+        all.remove(hash);
+    }
+    save_json(&sources_path(truth_path), &all)?;
+    Ok(before)
+}
+
+/// Put a claim's added sources back as they were (undo)
+pub(crate) fn restore(hash: &str, before: Option<serde_json::Value>) -> Result<(), String> {
+    let truth_path = repo_state::ready_path()?;
+    let _guard = SOURCES_LOCK.lock(&sources_path(&truth_path))?;
+    let mut all: BTreeMap<String, Vec<ClaimSource>> = load_json(&sources_path(&truth_path))?;
+    journal::restore_entry(&mut all, hash, before)?;
+    save_json(&sources_path(&truth_path), &all)
+}
+
+fn claim_sources(truth_path: &Path, hash: &str) -> Result<Vec<ClaimSource>, String> {
+    let embedded = embedded_sources(&read_claim(hash.to_string())?);
+    let added = load_sources(truth_path)?.remove(hash).unwrap_or_default();
+    Ok(merge(embedded, added))
+}
+
+/// Record a source for a claim. Adding a URL the claim already lists updates
+/// its title, accessed date and archived copy.
+#[tauri::command]
+pub async fn add_claim_source(hash: String, source: ClaimSource) -> Result<Vec<ClaimSource>, String> {
+    if mock::is_active() {
+        return Err("Claim sources are disabled in mock mode".to_string());
+    }
+    let hash = hash.trim().to_lowercase();
+    validate_hash(&hash)?;
+    let mut source = validate(source)?;
+    source.added_by = identity::current();
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        read_claim(hash.clone())?;
+        let before = update_sources(&truth_path, &hash, |added| {
+            match added.iter_mut().find(|s| s.url == source.url) {
+                Some(existing) => *existing = source,
+                None => added.push(source),
+            }
+            Ok(())
+        })?;
+        journal::record(
+            Store::Sources,
+            &hash,
+            format!("Added source to claim {}", short_hash(&hash)),
+            before.as_ref(),
+        );
+        log::info!("Added source to claim {}", hash);
+        claim_sources(&truth_path, &hash)
+    })
+    .await
+}
+
+/// Remove a source added from the app. Sources in the claim object itself
+/// can't be removed. `undo_last_change` brings it back.
+#[tauri::command]
+pub async fn remove_claim_source(hash: String, url: String) -> Result<Vec<ClaimSource>, String> {
+    if mock::is_active() {
+        return Err("Claim sources are disabled in mock mode".to_string());
+    }
+    let hash = hash.trim().to_lowercase();
+    validate_hash(&hash)?;
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        let before = update_sources(&truth_path, &hash, |added| {
+            let count = added.len();
+            added.retain(|s| s.url != url.trim());
+            if added.len() == count {
+                return Err("Only sources added in the app can be removed".to_string());
+            }
+            Ok(())
+        })?;
+        journal::record(
+            Store::Sources,
+            &hash,
+            format!("Removed source from claim {}", short_hash(&hash)),
+            before.as_ref(),
+        );
+        claim_sources(&truth_path, &hash)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn list_claim_sources(hash: String) -> Result<Vec<ClaimSource>, String> {
+    let hash = hash.trim().to_lowercase();
+    validate_hash(&hash)?;
+    if mock::is_active() {
+        return run_blocking(move || Ok(embedded_sources(&read_claim(hash)?))).await;
+    }
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        claim_sources(&truth_path, &hash)
+    })
+    .await
+}
+
+/// Claims with no source in their metadata or in sources.json
+#[tauri::command]
+pub async fn unsourced_claims_report() -> Result<UnsourcedReport, String> {
+    run_blocking(|| {
+        let listing = read_claims(None)?;
+        let added = match get_truth_path() {
+            Some(truth_path) if !mock::is_active() => load_sources(&truth_path)?,
+            _ => BTreeMap::new(),
+        };
+        let text = |claim: &serde_json::Value, key: &str| {
+            claim.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+        };
+        let unsourced = listing
+            .claims
+            .iter()
+            .filter(|claim| {
+                let hash = text(claim, "$hash");
+                embedded_sources(claim).is_empty() && added.get(&hash).is_none_or(|s| s.is_empty())
+            })
+            .map(|claim| UnsourcedClaim {
+                hash: text(claim, "$hash"),
+                content: text(claim, "content"),
+                domain: text(claim, "domain"),
+            })
+            .collect();
+        Ok(UnsourcedReport {
+            total: listing.claims.len(),
+            unsourced,
+            stale: listing.stale,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_and_added_sources_merge() {
+        let claim = serde_json::json!({
+            "content": "Water boils at 100°C",
+            "metadata": { "sources": [
+                { "url": "https://example.org/boiling" },
+                "https://example.org/bare-string",
+                { "url": "https://example.org/pressure", "title": "Pressure tables" },
+                { "title": "no url" },
+            ]},
+        });
+        let embedded = embedded_sources(&claim);
+        assert_eq!(embedded.len(), 2);
+        assert_eq!(embedded[1].title.as_deref(), Some("Pressure tables"));
+
+        let added = vec![
+            ClaimSource {
+                url: "https://example.org/boiling".to_string(),
+                ..Default::default()
+            },
+            ClaimSource {
+                url: "https://example.org/altitude".to_string(),
+                ..Default::default()
+            },
+        ];
+        let urls: Vec<String> = merge(embedded, added).into_iter().map(|s| s.url).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.org/boiling",
+                "https://example.org/pressure",
+                "https://example.org/altitude"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_source() {
+        let source = |url: &str, accessed: Option<&str>| ClaimSource {
+            url: url.to_string(),
+            title: Some("  ".to_string()),
+            accessed: accessed.map(str::to_string),
+            ..Default::default()
+        };
+        let valid = validate(source(" https://example.org/a ", Some("2026-03-01"))).unwrap();
+        assert_eq!(valid.url, "https://example.org/a");
+        assert_eq!(valid.title, None);
+        assert_eq!(valid.accessed.as_deref(), Some("2026-03-01"));
+        assert!(validate(source("https://example.org/a", None))
+            .unwrap()
+            .accessed
+            .is_some());
+        assert!(validate(source("ftp://example.org/a", None)).is_err());
+        assert!(validate(source("https://example.org/a", Some("March 1st"))).is_err());
+    }
+}
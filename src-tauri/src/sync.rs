@@ -0,0 +1,472 @@
+// ==================== REMOTE SYNC ====================
+//
+// Push/pull of truth repo objects to a TruthGit API. Both sides compare
+// object inventories by hash and only transfer what the other side lacks.
+// Objects travel as their raw compressed bytes so hashes are preserved.
+//
+// Remote endpoints:
+//   GET  {remote}/api/objects/inventory        -> { objects: [{ kind, hash }] }
+//   GET  {remote}/api/objects/{kind}/{hash}    -> raw object bytes
+//   POST {remote}/api/objects/{kind}/{hash}    <- raw object bytes
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter};
+
+use crate::auth::authorized_client;
+use crate::json_store::{lock_file, write_atomic};
+use crate::notifications::{self, NotificationKind};
+use crate::rate_limit;
+use crate::remotes::resolve_remote_url;
+use crate::sync_conflicts::{self, RefOutcome};
+use crate::tasks::{self, TaskHandle};
+use crate::truth_store::{self, ObjectId, ObjectKind, TruthStore};
+use crate::{get_truth_path, mock, repo_crypto, run_blocking, sanitize_error};
+
+pub const SYNC_PROGRESS_EVENT: &str = "sync://progress";
+
+/// Compressed objects larger than this are refused in either direction
+const MAX_TRANSFER_SIZE: usize = 10 * 1024 * 1024;
+
+/// Oldest entries are dropped past this many
+const MAX_SYNC_LOG_ENTRIES: usize = 500;
+
+static SYNC_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Ordered by kind, then hash
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ObjectRef {
+    pub kind: ObjectKind,
+    pub hash: ObjectId,
+}
+
+/// An inventory entry as the remote sent it; kinds this app doesn't sync
+/// and malformed hashes are dropped
+#[derive(Debug, Deserialize)]
+struct RemoteObject {
+    kind: String,
+    hash: String,
+}
+
+impl RemoteObject {
+    fn into_ref(self) -> Option<ObjectRef> {
+        let kind = ObjectKind::from_dir(&self.kind).filter(|k| ObjectKind::SYNCED.contains(k))?;
+        Some(ObjectRef {
+            kind,
+            hash: ObjectId::parse(&self.hash).ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    data: Option<T>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteInventory {
+    objects: Vec<RemoteObject>,
+    #[serde(default)]
+    head: Option<String>,
+}
+
+/// What the remote holds: its objects and where its HEAD points
+pub struct RemoteState {
+    pub objects: HashSet<ObjectRef>,
+    pub head: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncProgress {
+    pub remote: String,
+    pub direction: String,
+    pub done: usize,
+    pub total: usize,
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncFailure {
+    pub hash: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub remote: String,
+    /// "push" or "pull"
+    pub direction: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub local_count: usize,
+    pub remote_count: usize,
+    pub transferred: usize,
+    pub failed: Vec<SyncFailure>,
+    /// Hashes of the claim objects transferred
+    #[serde(default)]
+    pub claims: Vec<String>,
+    /// What happened to HEAD; None when objects failed and refs were left alone
+    #[serde(default)]
+    pub ref_outcome: Option<RefOutcome>,
+}
+
+/// Clears the in-progress flag however the sync ends
+pub struct SyncGuard;
+
+impl SyncGuard {
+    pub fn acquire() -> Result<Self, String> {
+        if SYNC_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+            return Err("A sync is already running".to_string());
+        }
+        Ok(SyncGuard)
+    }
+}
+
+impl Drop for SyncGuard {
+    fn drop(&mut self) {
+        SYNC_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+pub fn local_inventory(truth_path: &Path) -> HashSet<ObjectRef> {
+    let store = TruthStore::new(truth_path);
+    ObjectKind::SYNCED
+        .into_iter()
+        .flat_map(|kind| store.iter(kind))
+        .map(|entry| ObjectRef {
+            kind: entry.kind,
+            hash: entry.id,
+        })
+        .collect()
+}
+
+/// Objects in `from` that `to` lacks, in a stable order
+pub fn missing(from: &HashSet<ObjectRef>, to: &HashSet<ObjectRef>) -> Vec<ObjectRef> {
+    let mut objects: Vec<ObjectRef> = from.difference(to).cloned().collect();
+    objects.sort();
+    objects
+}
+
+/// Hashes of the claim objects among `objects`
+pub fn claim_hashes(objects: &[ObjectRef]) -> Vec<String> {
+    objects
+        .iter()
+        .filter(|o| o.kind == ObjectKind::Claim)
+        .map(|o| o.hash.to_string())
+        .collect()
+}
+
+pub fn sync_preconditions() -> Result<PathBuf, String> {
+    if mock::is_active() {
+        return Err("Sync is disabled in mock mode".to_string());
+    }
+    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+    if !TruthStore::new(&truth_path).is_repo() {
+        return Err("Truth repository not found".to_string());
+    }
+    repo_crypto::require_plain_repo("remotes")?;
+    Ok(truth_path)
+}
+
+pub async fn fetch_remote_state(client: &reqwest::Client, remote: &str) -> Result<RemoteState, String> {
+    let response = client
+        .get(format!("{}/api/objects/inventory", remote))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to remote: {}", e))?;
+    rate_limit::record(remote, &response);
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {} for inventory", response.status()));
+    }
+    let result: ApiResponse<RemoteInventory> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse remote inventory: {}", e))?;
+    let inventory = result
+        .data
+        .ok_or_else(|| result.error.unwrap_or_else(|| "Remote returned no inventory".to_string()))?;
+
+    Ok(RemoteState {
+        objects: inventory
+            .objects
+            .into_iter()
+            .filter_map(RemoteObject::into_ref)
+            .collect(),
+        head: inventory.head.map(|h| h.trim().to_string()).filter(|h| !h.is_empty()),
+    })
+}
+
+async fn upload_object(client: &reqwest::Client, remote: &str, truth_path: &Path, object: &ObjectRef) -> Result<(), String> {
+    let bytes = TruthStore::new(truth_path).read_raw(object.kind, &object.hash)?;
+    if bytes.len() > MAX_TRANSFER_SIZE {
+        return Err("Object too large to sync".to_string());
+    }
+    let response = client
+        .post(format!("{}/api/objects/{}/{}", remote, object.kind.dir(), object.hash))
+        .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+        .body(bytes)
+        .send()
+        .await
+        .map_err(|e| format!("Upload failed: {}", e))?;
+    rate_limit::record(remote, &response);
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Download an object, check it decodes (and matches its `$hash` when it
+/// carries one), then move it into place so readers never see partial files
+pub async fn download_object(client: &reqwest::Client, remote: &str, truth_path: &Path, object: &ObjectRef) -> Result<(), String> {
+    let response = client
+        .get(format!("{}/api/objects/{}/{}", remote, object.kind.dir(), object.hash))
+        .send()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?;
+    rate_limit::record(remote, &response);
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {}", response.status()));
+    }
+    if response.content_length().unwrap_or(0) as usize > MAX_TRANSFER_SIZE {
+        return Err("Object too large to sync".to_string());
+    }
+    let bytes = response.bytes().await.map_err(|e| format!("Download failed: {}", e))?;
+    if bytes.len() > MAX_TRANSFER_SIZE {
+        return Err("Object too large to sync".to_string());
+    }
+
+    let value = truth_store::decode_json(&bytes)?;
+    if value.get("$hash").and_then(|h| h.as_str()).is_some_and(|embedded| embedded != object.hash.as_str()) {
+        return Err("Object content does not match its hash".to_string());
+    }
+    TruthStore::new(truth_path).write_raw(object.kind, &object.hash, &bytes)
+}
+
+fn emit_progress(app: &AppHandle, progress: SyncProgress) {
+    if let Err(e) = app.emit(SYNC_PROGRESS_EVENT, progress) {
+        log::warn!("Failed to emit {}: {}", SYNC_PROGRESS_EVENT, e);
+    }
+}
+
+fn sync_log_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("sync-log.json")
+}
+
+pub fn append_sync_log(truth_path: &Path, report: &SyncReport) {
+    let path = sync_log_path(truth_path);
+    let _lock = match lock_file(&path) {
+        Ok(lock) => lock,
+        Err(e) => {
+            log::warn!("Failed to write sync log: {}", e);
+            return;
+        }
+    };
+    let mut entries: Vec<SyncReport> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    entries.insert(0, report.clone());
+    entries.truncate(MAX_SYNC_LOG_ENTRIES);
+    match serde_json::to_string_pretty(&entries) {
+        Ok(content) => {
+            if let Err(e) = write_atomic(&path, content.as_bytes()) {
+                log::warn!("Failed to write sync log: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize sync log: {}", e),
+    }
+}
+
+/// Transfer `pending` objects in one direction, emitting progress as we go,
+/// and return the ones that made it. Individual failures are collected
+/// rather than aborting the whole sync; cancelling the task stops before the
+/// next object.
+pub async fn transfer_objects(
+    app: &AppHandle,
+    task: &TaskHandle,
+    client: &reqwest::Client,
+    remote: &str,
+    truth_path: &Path,
+    pending: &[ObjectRef],
+    direction: &str,
+) -> (Vec<ObjectRef>, Vec<SyncFailure>) {
+    let total = pending.len();
+    let mut transferred = Vec::new();
+    let mut failed = Vec::new();
+    let progress = |done: usize, current: Option<String>| SyncProgress {
+        remote: remote.to_string(),
+        direction: direction.to_string(),
+        done,
+        total,
+        current,
+    };
+    emit_progress(app, progress(0, None));
+
+    for (i, object) in pending.iter().enumerate() {
+        if task.is_cancelled() {
+            break;
+        }
+        let result = match rate_limit::pace(remote).await {
+            Err(e) => Err(e),
+            Ok(()) if direction == "push" => upload_object(client, remote, truth_path, object).await,
+            Ok(()) => download_object(client, remote, truth_path, object).await,
+        };
+        match result {
+            Ok(()) => transferred.push(object.clone()),
+            Err(e) => failed.push(SyncFailure {
+                hash: object.hash.to_string(),
+                error: sanitize_error(&e),
+            }),
+        }
+        emit_progress(app, progress(i + 1, Some(object.hash.to_string())));
+        task.progress((i + 1) as u64, total as u64, format!("{} {} of {} objects", direction, i + 1, total));
+    }
+    (transferred, failed)
+}
+
+async fn run_sync(
+    app: AppHandle,
+    task: TaskHandle,
+    remote: Option<String>,
+    direction: &str,
+) -> Result<SyncReport, String> {
+    let truth_path = sync_preconditions()?;
+    let remote = resolve_remote_url(remote, direction)?;
+    let _guard = SyncGuard::acquire()?;
+    let started_at = chrono::Utc::now().to_rfc3339();
+
+    let client = authorized_client(&remote).await?;
+    let remote_state = fetch_remote_state(&client, &remote).await?;
+    let local_objects = {
+        let truth_path = truth_path.clone();
+        run_blocking(move || Ok(local_inventory(&truth_path))).await?
+    };
+
+    let pending = if direction == "push" {
+        missing(&local_objects, &remote_state.objects)
+    } else {
+        missing(&remote_state.objects, &local_objects)
+    };
+    let (transferred, failed) = transfer_objects(&app, &task, &client, &remote, &truth_path, &pending, direction).await;
+    task.check_cancelled()?;
+
+    // Never move HEAD to a state whose objects didn't all arrive
+    let ref_outcome = if failed.is_empty() {
+        Some(sync_conflicts::reconcile_head(&client, &remote, &truth_path, remote_state.head.clone(), direction).await?)
+    } else {
+        None
+    };
+
+    let report = SyncReport {
+        remote,
+        direction: direction.to_string(),
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        local_count: local_objects.len(),
+        remote_count: remote_state.objects.len(),
+        transferred: transferred.len(),
+        failed,
+        claims: claim_hashes(&transferred),
+        ref_outcome,
+    };
+    append_sync_log(&truth_path, &report);
+    notifications::notify(
+        NotificationKind::SyncComplete,
+        if direction == "push" { "Push finished" } else { "Pull finished" },
+        &format!("{} transferred, {} failed", report.transferred, report.failed.len()),
+    );
+    Ok(report)
+}
+
+/// Upload local objects the remote lacks
+#[tauri::command]
+pub async fn push_claims(
+    app: AppHandle,
+    remote: Option<String>,
+    task_id: Option<String>,
+) -> Result<SyncReport, String> {
+    let handle = app.clone();
+    tasks::run(&app, task_id, "sync", "Pushing claims", |task| run_sync(handle, task, remote, "push")).await
+}
+
+/// Download remote objects missing locally
+#[tauri::command]
+pub async fn pull_claims(
+    app: AppHandle,
+    remote: Option<String>,
+    task_id: Option<String>,
+) -> Result<SyncReport, String> {
+    let handle = app.clone();
+    tasks::run(&app, task_id, "sync", "Pulling claims", |task| run_sync(handle, task, remote, "pull")).await
+}
+
+/// Sync runs, newest first
+pub fn read_sync_log(truth_path: &Path) -> Result<Vec<SyncReport>, String> {
+    let path = sync_log_path(truth_path);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read sync log: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse sync log: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_sync_log() -> Result<Vec<SyncReport>, String> {
+    let truth_path = get_truth_path().ok_or("Could not find home directory")?;
+    read_sync_log(&truth_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    fn obj(kind: &str, hash: &str) -> ObjectRef {
+        ObjectRef {
+            kind: ObjectKind::from_dir(kind).unwrap(),
+            hash: ObjectId::parse(hash).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_hash_validation_blocks_traversal() {
+        assert!(ObjectId::is_valid("ab12cd34"));
+        assert!(!ObjectId::is_valid("../../etc/passwd"));
+        assert!(!ObjectId::is_valid("ab/cd"));
+        assert!(!ObjectId::is_valid("abc"));
+        let remote = |kind: &str, hash: &str| RemoteObject {
+            kind: kind.to_string(),
+            hash: hash.to_string(),
+        };
+        assert_eq!(remote("cl", "ab12cd34").into_ref(), Some(obj("cl", "ab12cd34")));
+        assert_eq!(remote("ev", "ab12cd34").into_ref(), None);
+        assert_eq!(remote("vf", "../../x").into_ref(), None);
+    }
+
+    #[test]
+    fn test_missing_is_set_difference() {
+        let local: HashSet<_> = [obj("cl", "aaaa"), obj("cl", "bbbb"), obj("vf", "cccc")].into_iter().collect();
+        let remote: HashSet<_> = [obj("cl", "bbbb"), obj("vf", "dddd")].into_iter().collect();
+        assert_eq!(missing(&local, &remote), vec![obj("cl", "aaaa"), obj("vf", "cccc")]);
+        assert_eq!(missing(&remote, &local), vec![obj("vf", "dddd")]);
+    }
+
+    #[test]
+    fn test_local_inventory_reads_object_layout() {
+        let tmp = TempDir::new("truthgit-sync-inventory");
+        let root = tmp.path();
+        fs::create_dir_all(root.join("objects/cl/ab")).unwrap();
+        fs::create_dir_all(root.join("objects/vf/cd")).unwrap();
+        fs::write(root.join("objects/cl/ab/cdef01"), b"x").unwrap();
+        fs::write(root.join("objects/vf/cd/ef0123"), b"x").unwrap();
+
+        let inventory = local_inventory(root);
+        assert_eq!(inventory.len(), 2);
+        assert!(inventory.contains(&obj("cl", "abcdef01")));
+        assert!(inventory.contains(&obj("vf", "cdef0123")));
+    }
+}
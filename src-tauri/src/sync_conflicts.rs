@@ -0,0 +1,351 @@
+// ==================== SYNC CONFLICTS ====================
+//
+// Objects are content-addressed, so transferring them never conflicts; HEAD
+// does. For each remote we remember the HEAD both sides agreed on at the last
+// sync. If both the local and the remote HEAD have moved since, sync stops
+// short of touching either ref and records a conflict for the user to resolve.
+//
+// Remote ref endpoint:
+//   PUT {remote}/api/refs/head  <- { head, expected }  (409 if expected is stale)
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::auth::authorized_client;
+use crate::json_store::write_atomic;
+use crate::{repo_state, run_blocking};
+use crate::remotes::resolve_remote_url;
+use crate::sync::{
+    append_sync_log, claim_hashes, fetch_remote_state, local_inventory, missing, sync_preconditions, transfer_objects,
+    SyncGuard, SyncReport,
+};
+use crate::tasks::{self, TaskHandle};
+use crate::truth_store::TruthStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefOutcome {
+    UpToDate,
+    /// Local HEAD moved to the remote's
+    FastForwarded,
+    /// Remote HEAD moved to ours
+    Pushed,
+    /// Local has moved on; a push will update the remote
+    LocalAhead,
+    /// Remote has moved on; a pull will update local
+    RemoteAhead,
+    /// Both moved; see `get_sync_conflicts`
+    Conflict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RefStatus {
+    UpToDate,
+    LocalAhead,
+    RemoteAhead,
+    Diverged,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RemoteSyncState {
+    /// HEAD both sides had after the last successful sync
+    head: Option<String>,
+    synced_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub remote: String,
+    pub base_head: Option<String>,
+    pub local_head: Option<String>,
+    pub remote_head: Option<String>,
+    pub detected_at: String,
+}
+
+fn state_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("sync-state.json")
+}
+
+fn conflicts_path(truth_path: &Path) -> PathBuf {
+    truth_path.join("sync-conflicts.json")
+}
+
+fn read_json<T: serde::de::DeserializeOwned + Default>(path: &Path) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize sync state: {}", e))?;
+    write_atomic(path, content.as_bytes()).map_err(|e| format!("Failed to write sync state: {}", e))
+}
+
+/// `base` is None when this remote has never been synced
+fn classify(base: Option<&Option<String>>, local: &Option<String>, remote: &Option<String>) -> RefStatus {
+    if local == remote {
+        return RefStatus::UpToDate;
+    }
+    let (local_moved, remote_moved) = match base {
+        Some(base) => (local != base, remote != base),
+        // No shared history: an empty side is simply behind
+        None => (local.is_some(), remote.is_some()),
+    };
+    match (local_moved, remote_moved) {
+        (true, true) => RefStatus::Diverged,
+        (true, false) => RefStatus::LocalAhead,
+        (false, true) => RefStatus::RemoteAhead,
+        (false, false) => RefStatus::UpToDate,
+    }
+}
+
+async fn push_head(client: &reqwest::Client, remote: &str, head: &str, expected: &Option<String>) -> Result<(), String> {
+    let response = client
+        .put(format!("{}/api/refs/head", remote))
+        .json(&serde_json::json!({ "head": head, "expected": expected }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to update remote HEAD: {}", e))?;
+    if response.status() == reqwest::StatusCode::CONFLICT {
+        return Err("Remote HEAD changed during sync; sync again".to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Remote returned {} for HEAD update", response.status()));
+    }
+    Ok(())
+}
+
+/// Remember the agreed HEAD for `remote` and drop any conflict recorded for it
+fn record_agreed(truth_path: &Path, remote: &str, head: Option<String>) -> Result<(), String> {
+    let mut state: HashMap<String, RemoteSyncState> = read_json(&state_path(truth_path));
+    state.insert(
+        remote.to_string(),
+        RemoteSyncState {
+            head,
+            synced_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    write_json(&state_path(truth_path), &state)?;
+
+    let mut conflicts: Vec<SyncConflict> = read_json(&conflicts_path(truth_path));
+    let before = conflicts.len();
+    conflicts.retain(|c| c.remote != remote);
+    if conflicts.len() != before {
+        write_json(&conflicts_path(truth_path), &conflicts)?;
+    }
+    Ok(())
+}
+
+fn record_conflict(truth_path: &Path, conflict: SyncConflict) -> Result<(), String> {
+    let mut conflicts: Vec<SyncConflict> = read_json(&conflicts_path(truth_path));
+    conflicts.retain(|c| c.remote != conflict.remote);
+    conflicts.push(conflict);
+    write_json(&conflicts_path(truth_path), &conflicts)
+}
+
+/// Bring HEAD in line after objects were transferred, without ever
+/// overwriting a ref that moved on both sides
+pub async fn reconcile_head(
+    client: &reqwest::Client,
+    remote: &str,
+    truth_path: &Path,
+    remote_head: Option<String>,
+    direction: &str,
+) -> Result<RefOutcome, String> {
+    let state: HashMap<String, RemoteSyncState> = read_json(&state_path(truth_path));
+    let base = state.get(remote).map(|s| &s.head);
+    let local_head = TruthStore::new(truth_path).head();
+
+    match (classify(base, &local_head, &remote_head), direction) {
+        (RefStatus::UpToDate, _) => {
+            record_agreed(truth_path, remote, local_head)?;
+            Ok(RefOutcome::UpToDate)
+        }
+        (RefStatus::LocalAhead, "push") => {
+            if let Some(head) = &local_head {
+                push_head(client, remote, head, &remote_head).await?;
+            }
+            record_agreed(truth_path, remote, local_head)?;
+            Ok(RefOutcome::Pushed)
+        }
+        (RefStatus::RemoteAhead, "pull") => {
+            if let Some(head) = &remote_head {
+                TruthStore::new(truth_path).set_head(head)?;
+            }
+            record_agreed(truth_path, remote, remote_head)?;
+            Ok(RefOutcome::FastForwarded)
+        }
+        (RefStatus::LocalAhead, _) => Ok(RefOutcome::LocalAhead),
+        (RefStatus::RemoteAhead, _) => Ok(RefOutcome::RemoteAhead),
+        (RefStatus::Diverged, _) => {
+            log::warn!("Sync conflict: local and remote HEAD have both moved");
+            record_conflict(
+                truth_path,
+                SyncConflict {
+                    remote: remote.to_string(),
+                    base_head: base.cloned().flatten(),
+                    local_head,
+                    remote_head,
+                    detected_at: chrono::Utc::now().to_rfc3339(),
+                },
+            )?;
+            Ok(RefOutcome::Conflict)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_sync_conflicts() -> Result<Vec<SyncConflict>, String> {
+    let truth_path = repo_state::ready_path()?;
+    Ok(read_json(&conflicts_path(&truth_path)))
+}
+
+/// Resolve a recorded conflict:
+/// - "prefer-local": the remote HEAD is moved to ours
+/// - "prefer-remote": our HEAD is moved to the remote's
+/// - "merge": claims from both sides are copied to both, then our HEAD becomes the shared one
+#[tauri::command]
+pub async fn resolve_sync_conflict(
+    app: AppHandle,
+    remote: Option<String>,
+    strategy: String,
+    task_id: Option<String>,
+) -> Result<SyncReport, String> {
+    if !["prefer-local", "prefer-remote", "merge"].contains(&strategy.as_str()) {
+        return Err(format!("Unknown resolution strategy '{}'", strategy));
+    }
+    let handle = app.clone();
+    tasks::run(&app, task_id, "sync", "Resolving sync conflict", |task| resolve(handle, task, remote, strategy)).await
+}
+
+async fn resolve(
+    app: AppHandle,
+    task: TaskHandle,
+    remote: Option<String>,
+    strategy: String,
+) -> Result<SyncReport, String> {
+    let truth_path = sync_preconditions()?;
+    let remote = resolve_remote_url(remote, "pull")?;
+    let _guard = SyncGuard::acquire()?;
+    let started_at = chrono::Utc::now().to_rfc3339();
+
+    let conflicts: Vec<SyncConflict> = read_json(&conflicts_path(&truth_path));
+    let conflict = conflicts
+        .into_iter()
+        .find(|c| c.remote == remote)
+        .ok_or("No conflict recorded for this remote")?;
+
+    let client = authorized_client(&remote).await?;
+    let remote_state = fetch_remote_state(&client, &remote).await?;
+    if remote_state.head != conflict.remote_head {
+        return Err("Remote HEAD changed since the conflict was detected; sync again".to_string());
+    }
+    let local_objects = {
+        let truth_path = truth_path.clone();
+        run_blocking(move || Ok(local_inventory(&truth_path))).await?
+    };
+
+    let mut transferred = Vec::new();
+    let mut failed = Vec::new();
+    if strategy != "prefer-local" {
+        let pending = missing(&remote_state.objects, &local_objects);
+        let (done, f) = transfer_objects(&app, &task, &client, &remote, &truth_path, &pending, "pull").await;
+        transferred.extend(done);
+        failed.extend(f);
+    }
+    if strategy != "prefer-remote" {
+        let pending = missing(&local_objects, &remote_state.objects);
+        let (done, f) = transfer_objects(&app, &task, &client, &remote, &truth_path, &pending, "push").await;
+        transferred.extend(done);
+        failed.extend(f);
+    }
+    task.check_cancelled()?;
+    if !failed.is_empty() {
+        return Err(format!("{} objects failed to transfer; conflict left unresolved", failed.len()));
+    }
+
+    let local_head = TruthStore::new(&truth_path).head();
+    let (agreed, outcome) = if strategy == "prefer-remote" {
+        if let Some(head) = &remote_state.head {
+            TruthStore::new(&truth_path).set_head(head)?;
+        }
+        (remote_state.head.clone(), RefOutcome::FastForwarded)
+    } else {
+        if let Some(head) = &local_head {
+            push_head(&client, &remote, head, &remote_state.head).await?;
+        }
+        (local_head, RefOutcome::Pushed)
+    };
+    record_agreed(&truth_path, &remote, agreed)?;
+
+    let report = SyncReport {
+        remote,
+        direction: format!("resolve:{}", strategy),
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        local_count: local_objects.len(),
+        remote_count: remote_state.objects.len(),
+        transferred: transferred.len(),
+        failed,
+        claims: claim_hashes(&transferred),
+        ref_outcome: Some(outcome),
+    };
+    append_sync_log(&truth_path, &report);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    fn head(h: &str) -> Option<String> {
+        Some(h.to_string())
+    }
+
+    #[test]
+    fn test_classify_with_shared_base() {
+        let base = head("a");
+        assert_eq!(classify(Some(&base), &head("a"), &head("a")), RefStatus::UpToDate);
+        assert_eq!(classify(Some(&base), &head("b"), &head("a")), RefStatus::LocalAhead);
+        assert_eq!(classify(Some(&base), &head("a"), &head("c")), RefStatus::RemoteAhead);
+        assert_eq!(classify(Some(&base), &head("b"), &head("c")), RefStatus::Diverged);
+        // Both moved to the same place: nothing to do
+        assert_eq!(classify(Some(&base), &head("b"), &head("b")), RefStatus::UpToDate);
+    }
+
+    #[test]
+    fn test_classify_without_history() {
+        assert_eq!(classify(None, &head("a"), &None), RefStatus::LocalAhead);
+        assert_eq!(classify(None, &None, &head("a")), RefStatus::RemoteAhead);
+        assert_eq!(classify(None, &head("a"), &head("b")), RefStatus::Diverged);
+    }
+
+    #[test]
+    fn test_agreeing_clears_conflict() {
+        let tmp = TempDir::new("truthgit-sync-conflicts");
+        let root = tmp.path();
+
+        record_conflict(
+            root,
+            SyncConflict {
+                remote: "https://r".to_string(),
+                base_head: head("a"),
+                local_head: head("b"),
+                remote_head: head("c"),
+                detected_at: String::new(),
+            },
+        )
+        .unwrap();
+        assert_eq!(read_json::<Vec<SyncConflict>>(&conflicts_path(root)).len(), 1);
+
+        record_agreed(root, "https://r", head("c")).unwrap();
+        assert!(read_json::<Vec<SyncConflict>>(&conflicts_path(root)).is_empty());
+        let state: HashMap<String, RemoteSyncState> = read_json(&state_path(root));
+        assert_eq!(state["https://r"].head, head("c"));
+    }
+}
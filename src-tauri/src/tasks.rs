@@ -0,0 +1,274 @@
+// ==================== BACKGROUND TASKS ====================
+//
+// Shared progress and cancellation for long-running commands (sync, batch
+// verification, backup/restore, log export, file intake). Each run gets a
+// task id, which the frontend may choose up front by passing `taskId` so it
+// can cancel before the command returns. Progress is emitted as
+// `task://progress` events, throttled to a few per second, and always ends
+// with one event in a final state. Cancellation is cooperative: the
+// operation stops at its next checkpoint.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+pub const TASK_PROGRESS_EVENT: &str = "task://progress";
+pub const TASK_CANCELLED_ERROR: &str = "Cancelled";
+
+const MIN_EMIT_INTERVAL: Duration = Duration::from_millis(150);
+const MAX_TASK_ID_LEN: usize = 64;
+
+static TASKS: std::sync::LazyLock<Mutex<HashMap<String, TaskEntry>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskProgress {
+    pub task_id: String,
+    /// "sync", "batch_verify", "backup", "restore", "export_logs", "intake", …
+    pub kind: String,
+    pub label: String,
+    pub state: TaskState,
+    /// None while the total is unknown
+    pub percent: Option<f64>,
+    pub message: Option<String>,
+    pub started_at: String,
+}
+
+struct TaskEntry {
+    progress: TaskProgress,
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: String,
+    app: Option<AppHandle>,
+    cancel: Arc<AtomicBool>,
+    last_emit: Arc<Mutex<Option<Instant>>>,
+}
+
+fn validate_task_id(id: &str) -> Result<(), String> {
+    if id.is_empty()
+        || id.len() > MAX_TASK_ID_LEN
+        || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("Invalid task id".to_string());
+    }
+    Ok(())
+}
+
+fn percent(done: u64, total: u64) -> Option<f64> {
+    if total == 0 {
+        return None;
+    }
+    Some((done.min(total) as f64 / total as f64 * 100.0 * 10.0).round() / 10.0)
+}
+
+impl TaskHandle {
+    fn start(app: &AppHandle, task_id: Option<String>, kind: &str, label: &str) -> Result<Self, String> {
+        let id = task_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        validate_task_id(&id)?;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let progress = TaskProgress {
+            task_id: id.clone(),
+            kind: kind.to_string(),
+            label: label.to_string(),
+            state: TaskState::Running,
+            percent: None,
+            message: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        {
+            let mut tasks = TASKS.lock().map_err(|e| format!("Lock error: {}", e))?;
+            if tasks.contains_key(&id) {
+                return Err(format!("Task {} is already running", id));
+            }
+            tasks.insert(
+                id.clone(),
+                TaskEntry {
+                    progress: progress.clone(),
+                    cancel: cancel.clone(),
+                },
+            );
+        }
+        let handle = Self {
+            id,
+            app: Some(app.clone()),
+            cancel,
+            last_emit: Arc::new(Mutex::new(None)),
+        };
+        handle.emit(progress);
+        Ok(handle)
+    }
+
+    /// A task that is not registered and emits nothing, for unit tests
+    #[cfg(test)]
+    pub fn detached() -> Self {
+        Self {
+            id: "detached".to_string(),
+            app: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            last_emit: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn emit(&self, progress: TaskProgress) {
+        if let Some(app) = &self.app {
+            if let Err(e) = app.emit(TASK_PROGRESS_EVENT, progress) {
+                log::warn!("Failed to emit {}: {}", TASK_PROGRESS_EVENT, e);
+            }
+        }
+    }
+
+    /// Update progress; `total` of 0 means unknown. Emission is throttled
+    /// except for the first and last step.
+    pub fn progress(&self, done: u64, total: u64, message: impl Into<String>) {
+        let progress = {
+            let Ok(mut tasks) = TASKS.lock() else {
+                return;
+            };
+            let Some(entry) = tasks.get_mut(&self.id) else {
+                return;
+            };
+            entry.progress.percent = percent(done, total);
+            entry.progress.message = Some(message.into());
+            entry.progress.clone()
+        };
+
+        let due = {
+            let Ok(mut last) = self.last_emit.lock() else {
+                return;
+            };
+            let due = done == 0 || done >= total || last.is_none_or(|t| t.elapsed() >= MIN_EMIT_INTERVAL);
+            if due {
+                *last = Some(Instant::now());
+            }
+            due
+        };
+        if due {
+            self.emit(progress);
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Checkpoint for long loops: `Err(TASK_CANCELLED_ERROR)` once cancelled
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(TASK_CANCELLED_ERROR.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn finish<T>(&self, result: &Result<T, String>) {
+        let entry = TASKS.lock().ok().and_then(|mut tasks| tasks.remove(&self.id));
+        let Some(TaskEntry { mut progress, .. }) = entry else {
+            return;
+        };
+        progress.state = match result {
+            Ok(_) if self.is_cancelled() => TaskState::Cancelled,
+            Ok(_) => TaskState::Completed,
+            Err(e) if e == TASK_CANCELLED_ERROR => TaskState::Cancelled,
+            Err(_) => TaskState::Failed,
+        };
+        if progress.state == TaskState::Completed {
+            progress.percent = Some(100.0);
+        }
+        if let Err(e) = result {
+            progress.message = Some(e.clone());
+        }
+        self.emit(progress);
+    }
+}
+
+/// Run `operation` as a registered task and emit its final state, whatever
+/// the outcome
+pub async fn run<T, F, Fut>(
+    app: &AppHandle,
+    task_id: Option<String>,
+    kind: &str,
+    label: &str,
+    operation: F,
+) -> Result<T, String>
+where
+    F: FnOnce(TaskHandle) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let task = TaskHandle::start(app, task_id, kind, label)?;
+    let result = operation(task.clone()).await;
+    task.finish(&result);
+    result
+}
+
+/// Ask a running task to stop. Returns false if no such task is running.
+#[tauri::command]
+pub async fn cancel_task(task_id: String) -> Result<bool, String> {
+    let tasks = TASKS.lock().map_err(|e| format!("Lock error: {}", e))?;
+    match tasks.get(&task_id) {
+        Some(entry) => {
+            entry.cancel.store(true, Ordering::Relaxed);
+            log::info!("Cancellation requested for task {} ({})", task_id, entry.progress.kind);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Tasks currently running, e.g. to restore progress bars after a reload
+#[tauri::command]
+pub async fn list_tasks() -> Result<Vec<TaskProgress>, String> {
+    let tasks = TASKS.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut running: Vec<TaskProgress> = tasks.values().map(|e| e.progress.clone()).collect();
+    running.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(running)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(0, 0), None);
+        assert_eq!(percent(1, 3), Some(33.3));
+        assert_eq!(percent(5, 4), Some(100.0));
+    }
+
+    #[test]
+    fn test_task_id_validation() {
+        assert!(validate_task_id("3f2a-batch_1").is_ok());
+        assert!(validate_task_id("").is_err());
+        assert!(validate_task_id("../x").is_err());
+        assert!(validate_task_id(&"a".repeat(MAX_TASK_ID_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_detached_task_cancellation() {
+        let task = TaskHandle::detached();
+        assert!(task.check_cancelled().is_ok());
+        task.cancel.store(true, Ordering::Relaxed);
+        assert_eq!(task.check_cancelled().unwrap_err(), TASK_CANCELLED_ERROR);
+        // Progress on an unregistered task is a no-op
+        task.progress(1, 2, "halfway");
+    }
+}
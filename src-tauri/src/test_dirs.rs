@@ -0,0 +1,31 @@
+// ==================== TEST DIRS ====================
+//
+// Scratch directories for the unit tests and for `test_support`. Each one
+// is unique to its test and removed when the guard drops, so a failing
+// assert doesn't leave it behind the way cleanup at the end of a test does.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A directory under the system temp dir, removed on drop
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(prefix: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path).expect("create temp dir");
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
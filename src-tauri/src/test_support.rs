@@ -0,0 +1,428 @@
+// ==================== TEST SUPPORT ====================
+//
+// Synthetic truth repos and vaults for the integration tests under tests/
+// and the benchmarks under benches/, built only with the `test-support`
+// feature:
+// - `RepoBuilder` writes N claims, M verifications and an audit trail in
+//   temp dirs, through the same object store the app reads;
+// - `VaultBuilder` writes markdown notes spread over folders, each linking
+//   to the one before and naming a topic that search can find;
+// - `activate` points settings at them, so the functions at the bottom run
+//   the actual commands against the fixture.
+// Settings are global, so an activation holds a process-wide lock and tests
+// that use it run one at a time. Config and cache files (claim index,
+// domains, ...) go to a sandbox dir for the life of the process instead of
+// the user's.
+
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+pub use crate::compression::Codec;
+use crate::compression::Encoder;
+use crate::index::ClaimListing;
+use crate::json_store;
+use crate::objects::{self, StoredObject};
+pub use crate::repo_state::{RepoState, RepoStatus};
+pub use crate::test_dirs::TempDir;
+use crate::truth_store::{ObjectId, ObjectKind, TruthStore};
+use crate::{AuditEntry, SearchResult, VaultNote, VerificationListing, SETTINGS};
+
+/// Domains claims and notes are spread over, in turn
+pub const DOMAINS: &[&str] = &["science", "medical", "financial", "legal", "engineering"];
+/// Topics notes mention, in turn; note `i` is the only one in every
+/// `TOPICS.len()` to mention `TOPICS[i % TOPICS.len()]`
+pub const TOPICS: &[&str] = &[
+    "telescope",
+    "glacier",
+    "enzyme",
+    "dividend",
+    "statute",
+    "orchid",
+    "firmware",
+];
+const VERDICTS: &[&str] = &["PASSED", "FAILED", "UNCERTAIN"];
+const SUBJECTS: &[&str] = &[
+    "Water",
+    "The market",
+    "A vaccine",
+    "The court",
+    "The cache",
+    "Light",
+    "Inflation",
+];
+const PREDICATES: &[&str] = &["rises with", "depends on", "reduces", "is unaffected by", "doubles"];
+const OBJECTS: &[&str] = &[
+    "temperature",
+    "interest rates",
+    "infection",
+    "precedent",
+    "load",
+    "pressure",
+];
+/// Fixture objects are dated from here, one hour apart
+const EPOCH: &str = "2025-01-01T00:00:00Z";
+
+/// Held by an activation; see `activate`
+static ACTIVE: Mutex<()> = Mutex::new(());
+
+fn timestamp(hours: usize) -> String {
+    let epoch = chrono::DateTime::parse_from_rfc3339(EPOCH).expect("valid epoch");
+    (epoch + chrono::Duration::hours(hours as i64))
+        .with_timezone(&chrono::Utc)
+        .to_rfc3339()
+}
+
+fn hash_of(value: &serde_json::Value) -> String {
+    format!("{:x}", Sha256::digest(value.to_string().as_bytes()))
+}
+
+/// Text of fixture claim `i`; unique per `i`
+pub fn claim_text(i: usize) -> String {
+    format!(
+        "{} {} {} (fixture claim {})",
+        SUBJECTS[i % SUBJECTS.len()],
+        PREDICATES[i % PREDICATES.len()],
+        OBJECTS[i % OBJECTS.len()],
+        i
+    )
+}
+
+/// A truth repo written by `RepoBuilder`
+pub struct TruthRepo {
+    dir: TempDir,
+    /// Claim hashes, oldest first
+    pub claims: Vec<String>,
+    /// Verification hashes, oldest first
+    pub verifications: Vec<String>,
+}
+
+impl TruthRepo {
+    /// The `.truth` directory
+    pub fn path(&self) -> PathBuf {
+        self.dir.path().join(".truth")
+    }
+
+    pub fn store(&self) -> TruthStore {
+        TruthStore::new(self.path())
+    }
+
+    /// Files of the claim objects, in storage order
+    pub fn claim_paths(&self) -> Vec<PathBuf> {
+        self.store().iter(ObjectKind::Claim).map(|entry| entry.path).collect()
+    }
+}
+
+/// Builds a truth repo of generated claims, verifications and audit entries.
+/// Claim `i` is in domain `DOMAINS[i % DOMAINS.len()]` and created `i` hours
+/// after 2025-01-01; verification `j` is of claim `j % claims`.
+#[derive(Debug, Clone, Default)]
+pub struct RepoBuilder {
+    claims: usize,
+    verifications: usize,
+    audit_entries: usize,
+    codec: Codec,
+}
+
+impl RepoBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn claims(mut self, count: usize) -> Self {
+        self.claims = count;
+        self
+    }
+
+    pub fn verifications(mut self, count: usize) -> Self {
+        self.verifications = count;
+        self
+    }
+
+    pub fn audit_entries(mut self, count: usize) -> Self {
+        self.audit_entries = count;
+        self
+    }
+
+    /// Compress objects with `codec` (zlib by default), whatever the
+    /// settings say
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub fn build(&self) -> TruthRepo {
+        assert!(
+            self.claims > 0 || self.verifications == 0,
+            "verifications need claims to verify"
+        );
+        let mut repo = TruthRepo {
+            dir: TempDir::new("truthgit-fixture-repo"),
+            claims: Vec::with_capacity(self.claims),
+            verifications: Vec::with_capacity(self.verifications),
+        };
+        let store = repo.store();
+        fs::create_dir_all(store.objects_dir()).expect("create objects dir");
+
+        let mut texts = Vec::with_capacity(self.claims);
+        for i in 0..self.claims {
+            let content = claim_text(i);
+            let body = json!({
+                "$type": "claim",
+                "content": content,
+                "confidence": 0.5 + (i % 50) as f64 / 100.0,
+                "domain": DOMAINS[i % DOMAINS.len()],
+                "state": if i % 3 == 0 { "pending" } else { "verified" },
+                "metadata": {
+                    "tags": ["fixture"],
+                    "created_at": timestamp(i),
+                    "created_by": "fixture",
+                },
+            });
+            let hash = hash_of(&body);
+            let mut claim = body;
+            claim["$hash"] = json!(hash);
+            write_object(&store, self.codec, ObjectKind::Claim, &hash, &claim);
+            repo.claims.push(hash);
+            texts.push(content);
+        }
+
+        for j in 0..self.verifications {
+            let i = j % self.claims;
+            let body = json!({
+                "$type": "verification",
+                "$version": 2,
+                "claim_hash": repo.claims[i],
+                "claim": texts[i],
+                "domain": DOMAINS[i % DOMAINS.len()],
+                "verdict": VERDICTS[j % VERDICTS.len()],
+                "confidence": 0.9,
+                "validators": ["fixture-validator-a", "fixture-validator-b"],
+                "timestamp": timestamp(i + j + 1),
+            });
+            let hash = hash_of(&body);
+            let mut verification = body;
+            verification["$hash"] = json!(hash);
+            write_object(&store, self.codec, ObjectKind::Verification, &hash, &verification);
+            repo.verifications.push(hash);
+        }
+
+        if self.audit_entries > 0 {
+            // Newest first, as the app keeps it
+            let entries: Vec<AuditEntry> = (0..self.audit_entries)
+                .rev()
+                .map(|k| AuditEntry {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    timestamp: timestamp(k),
+                    action: "governance_verify".to_string(),
+                    claim: claim_text(k),
+                    domain: DOMAINS[k % DOMAINS.len()].to_string(),
+                    risk_profile: "medium".to_string(),
+                    result_status: VERDICTS[k % VERDICTS.len()].to_string(),
+                    result_action: "record".to_string(),
+                    confidence: 0.9,
+                    ..Default::default()
+                })
+                .collect();
+            write_audit(&repo.path(), &entries);
+        }
+        repo
+    }
+}
+
+fn write_object(store: &TruthStore, codec: Codec, kind: ObjectKind, hash: &str, value: &serde_json::Value) {
+    let id = ObjectId::parse(hash).expect("fixture hash is hex");
+    let mut encoder = Encoder::object(codec, Vec::new()).expect("start encoder");
+    encoder
+        .write_all(value.to_string().as_bytes())
+        .expect("compress object");
+    let compressed = encoder.finish().expect("compress object");
+    store.write_raw(kind, &id, &compressed).expect("write fixture object");
+}
+
+/// Replace the audit trail of the repo at `root`
+pub fn write_audit(root: &Path, entries: &[AuditEntry]) {
+    let content = serde_json::to_vec_pretty(entries).expect("serialize audit");
+    fs::write(root.join("audit.json"), content).expect("write audit.json");
+}
+
+/// A vault written by `VaultBuilder`
+pub struct Vault {
+    dir: TempDir,
+    /// Note paths relative to the vault, in the order written
+    pub notes: Vec<String>,
+}
+
+impl Vault {
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+/// Builds a vault of generated markdown notes. Note `i` goes in folder
+/// `i % folders`, mentions `TOPICS[i % TOPICS.len()]` once and links to
+/// note `i - 1`.
+#[derive(Debug, Clone)]
+pub struct VaultBuilder {
+    notes: usize,
+    folders: usize,
+    paragraphs: usize,
+}
+
+impl Default for VaultBuilder {
+    fn default() -> Self {
+        Self {
+            notes: 0,
+            folders: 1,
+            paragraphs: 3,
+        }
+    }
+}
+
+impl VaultBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn notes(mut self, count: usize) -> Self {
+        self.notes = count;
+        self
+    }
+
+    pub fn folders(mut self, count: usize) -> Self {
+        self.folders = count.max(1);
+        self
+    }
+
+    /// Filler paragraphs per note, to make notes as long as real ones
+    pub fn paragraphs(mut self, count: usize) -> Self {
+        self.paragraphs = count;
+        self
+    }
+
+    pub fn build(&self) -> Vault {
+        let mut vault = Vault {
+            dir: TempDir::new("truthgit-fixture-vault"),
+            notes: Vec::with_capacity(self.notes),
+        };
+        fs::create_dir_all(vault.path().join(".obsidian")).expect("create .obsidian");
+        for i in 0..self.notes {
+            let relative = format!("folder-{}/note-{}.md", i % self.folders, i);
+            let mut content = format!(
+                "---\ntags: [fixture, {}]\n---\n# Note {}\n\nThis note is about the {}.\n",
+                DOMAINS[i % DOMAINS.len()],
+                i,
+                TOPICS[i % TOPICS.len()]
+            );
+            for p in 0..self.paragraphs {
+                content.push('\n');
+                content.push_str(&claim_text(i + p));
+                content.push_str(". Nothing else in this paragraph matters to a search.\n");
+            }
+            if i > 0 {
+                content.push_str(&format!("\nSee [[note-{}]].\n", i - 1));
+            }
+            let path = vault.path().join(&relative);
+            fs::create_dir_all(path.parent().expect("note has a folder")).expect("create note folder");
+            fs::write(&path, content).expect("write note");
+            vault.notes.push(relative);
+        }
+        vault
+    }
+}
+
+/// Send config and cache files to a temp dir for the rest of the process
+fn sandbox() {
+    json_store::SANDBOX.get_or_init(|| {
+        let root = std::env::temp_dir().join(format!("truthgit-sandbox-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create sandbox");
+        root
+    });
+}
+
+/// Settings pointed at a fixture; dropping it restores the previous paths
+/// and lets the next test activate
+pub struct Activation {
+    previous: (String, String),
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Drop for Activation {
+    fn drop(&mut self) {
+        let mut settings = SETTINGS.write().unwrap_or_else(|e| e.into_inner());
+        (settings.truth_repo_path, settings.vault_path) = std::mem::take(&mut self.previous);
+    }
+}
+
+/// Make `truth_repo` and `vault` the active repo and vault. Neither has to
+/// exist, e.g. to test how commands handle a missing repo.
+pub fn activate(truth_repo: &Path, vault: &Path) -> Activation {
+    sandbox();
+    let lock = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut settings = SETTINGS.write().unwrap_or_else(|e| e.into_inner());
+    let previous = (
+        std::mem::replace(&mut settings.truth_repo_path, truth_repo.to_string_lossy().to_string()),
+        std::mem::replace(&mut settings.vault_path, vault.to_string_lossy().to_string()),
+    );
+    Activation { previous, _lock: lock }
+}
+
+/// Run a command to completion on a runtime shared by all tests
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .expect("start runtime")
+        })
+        .block_on(future)
+}
+
+/// Inflate and parse one object the way listings do; the JSON of a claim or
+/// verification
+pub fn read_object(path: &Path) -> Result<serde_json::Value, String> {
+    match objects::read_object(path).map_err(|e| e.to_string())? {
+        StoredObject::Claim(value) | StoredObject::Verification(value) => Ok(value),
+        other => Err(format!("Not a claim or verification: {:?}", other.kind())),
+    }
+}
+
+// ---------- commands ----------
+
+pub fn list_claims(ontological_type: Option<&str>) -> Result<ClaimListing, String> {
+    block_on(crate::list_claims(ontological_type.map(str::to_string)))
+}
+
+pub fn get_claim(hash: &str) -> Result<serde_json::Value, String> {
+    block_on(crate::get_claim(hash.to_string()))
+}
+
+pub fn list_verifications() -> Result<VerificationListing, String> {
+    block_on(crate::list_verifications())
+}
+
+pub fn get_audit_trail() -> Result<Vec<AuditEntry>, String> {
+    block_on(crate::get_audit_trail())
+}
+
+pub fn add_audit_entry(entry: AuditEntry) -> Result<String, String> {
+    block_on(crate::add_audit_entry(entry))
+}
+
+pub fn search_notes(query: &str) -> Result<Vec<SearchResult>, String> {
+    block_on(crate::search_notes(query.to_string()))
+}
+
+pub fn read_note(relative_path: &str) -> Result<VaultNote, String> {
+    block_on(crate::read_note(relative_path.to_string()))
+}
+
+pub fn get_repo_state() -> Result<RepoStatus, String> {
+    block_on(crate::repo_state::get_repo_state())
+}
@@ -0,0 +1,219 @@
+// ==================== CLAIM TIMELINE ====================
+//
+// Everything that happened to one claim in a single feed for the claim
+// detail page: its verifications, audit entries recorded for it, review
+// state changes, annotations (evidence attached to its verifications and
+// curation edits such as sources and pins) and the syncs that moved it.
+// Each store keeps its own records; this only merges them by time.
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::evidence;
+use crate::exports::field;
+use crate::identity::Actor;
+use crate::journal::{self, Store};
+use crate::reviews::{self, validate_hash};
+use crate::sync::{self, SyncReport};
+use crate::verification::Verification;
+use crate::{mock, read_audit_trail, read_claim, read_verifications, repo_state, run_blocking, AuditEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineKind {
+    Verification,
+    Audit,
+    Review,
+    Annotation,
+    Sync,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEvent {
+    pub at: String,
+    pub kind: TimelineKind,
+    pub summary: String,
+    pub actor: Option<Actor>,
+    /// The record the event came from
+    pub detail: serde_json::Value,
+}
+
+fn event(at: &str, kind: TimelineKind, summary: String, actor: Option<Actor>, detail: impl Serialize) -> TimelineEvent {
+    TimelineEvent {
+        at: at.to_string(),
+        kind,
+        summary,
+        actor,
+        detail: serde_json::to_value(detail).unwrap_or_default(),
+    }
+}
+
+fn verification_events(verifications: &[Verification], hash: &str) -> Vec<TimelineEvent> {
+    verifications
+        .iter()
+        .filter(|v| v.claim_hash == hash)
+        .map(|v| {
+            let mut summary = format!("Verified: {}", v.verdict);
+            if let Some(confidence) = v.confidence {
+                summary.push_str(&format!(" ({:.0}%)", confidence * 100.0));
+            }
+            event(&v.timestamp, TimelineKind::Verification, summary, None, v)
+        })
+        .collect()
+}
+
+/// Audit entries recorded for the claim's text or hash. Review entries are
+/// left out: the review history already has them.
+fn audit_events(entries: Vec<AuditEntry>, content: &str, hash: &str) -> Vec<TimelineEvent> {
+    entries
+        .into_iter()
+        .filter(|e| e.action != "review" && (e.claim == hash || (!content.is_empty() && e.claim == content)))
+        .map(|e| {
+            let summary = format!("{}: {}", e.action, e.result_status);
+            event(&e.timestamp.clone(), TimelineKind::Audit, summary, e.actor.clone(), e)
+        })
+        .collect()
+}
+
+fn review_events(hash: &str) -> Result<Vec<TimelineEvent>, String> {
+    let review = reviews::load_reviews()?.remove(hash).unwrap_or_default();
+    Ok(review
+        .history
+        .into_iter()
+        .map(|step| {
+            let summary = format!("Review: {} -> {}", step.from.as_str(), step.to.as_str());
+            event(
+                &step.at.clone(),
+                TimelineKind::Review,
+                summary,
+                Some(step.reviewer.clone()),
+                step,
+            )
+        })
+        .collect())
+}
+
+/// Evidence on the claim's verifications, and source and pin edits
+fn annotation_events(truth_path: &Path, hash: &str) -> Result<Vec<TimelineEvent>, String> {
+    let evidence = evidence::claim_evidence(truth_path, hash)?.into_iter().map(|e| {
+        let summary = format!("Evidence attached: {}", e.name);
+        event(
+            &e.attached_at.clone(),
+            TimelineKind::Annotation,
+            summary,
+            e.attached_by.clone(),
+            e,
+        )
+    });
+    let edits = journal::changes_for(truth_path, hash)?
+        .into_iter()
+        .filter(|c| matches!(c.store, Store::Sources | Store::Watchlist))
+        .map(|c| {
+            let summary = c.description.clone();
+            event(&c.at.clone(), TimelineKind::Annotation, summary, c.actor.clone(), c)
+        });
+    Ok(evidence.chain(edits).collect())
+}
+
+/// Syncs that transferred the claim, or failed to
+fn sync_events(log: Vec<SyncReport>, hash: &str) -> Vec<TimelineEvent> {
+    log.into_iter()
+        .filter_map(|report| {
+            let summary = if report.claims.iter().any(|h| h == hash) {
+                format!("Synced ({}) with {}", report.direction, report.remote)
+            } else if report.failed.iter().any(|f| f.hash == hash) {
+                format!("Sync ({}) with {} failed", report.direction, report.remote)
+            } else {
+                return None;
+            };
+            Some(event(
+                &report.finished_at.clone(),
+                TimelineKind::Sync,
+                summary,
+                None,
+                report,
+            ))
+        })
+        .collect()
+}
+
+/// Newest first. Timestamps are compared as instants, so entries written
+/// with different UTC offsets still interleave correctly.
+fn sort_events(events: &mut [TimelineEvent]) {
+    let instant = |at: &str| chrono::DateTime::parse_from_rfc3339(at).ok().map(|t| t.to_utc());
+    events.sort_by(|a, b| instant(&b.at).cmp(&instant(&a.at)).then_with(|| b.at.cmp(&a.at)));
+}
+
+/// Everything that happened to one claim, newest first
+#[tauri::command]
+pub async fn get_claim_timeline(hash: String) -> Result<Vec<TimelineEvent>, String> {
+    validate_hash(&hash)?;
+    run_blocking(move || {
+        let claim = read_claim(hash.clone())?;
+        let mut events = verification_events(&read_verifications()?.verifications, &hash);
+        if !mock::is_active() {
+            let truth_path = repo_state::ready_path()?;
+            let content = field(&claim, "content");
+            events.extend(audit_events(read_audit_trail()?, content, &hash));
+            events.extend(review_events(&hash)?);
+            events.extend(annotation_events(&truth_path, &hash)?);
+            events.extend(sync_events(sync::read_sync_log(&truth_path)?, &hash));
+        }
+        sort_events(&mut events);
+        Ok(events)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn audit(action: &str, claim: &str, timestamp: &str) -> AuditEntry {
+        AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: timestamp.to_string(),
+            action: action.to_string(),
+            claim: claim.to_string(),
+            domain: String::new(),
+            risk_profile: String::new(),
+            result_status: "PASSED".to_string(),
+            result_action: String::new(),
+            confidence: 0.9,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_timeline_merges_and_sorts() {
+        let verifications = [
+            json!({"claim_hash": "abc", "verdict": "PASSED", "confidence": 0.9, "timestamp": "2026-01-02T10:00:00Z"}),
+            json!({"claim_hash": "def", "verdict": "FAILED", "timestamp": "2026-01-03T10:00:00Z"}),
+        ]
+        .map(Verification::from);
+        let entries = vec![
+            audit("governance_verify", "Water is wet", "2026-01-01T12:00:00+02:00"),
+            audit("review", "Water is wet", "2026-01-04T10:00:00Z"),
+            audit("governance_verify", "Something else", "2026-01-05T10:00:00Z"),
+        ];
+        let mut events = verification_events(&verifications, "abc");
+        events.extend(audit_events(entries, "Water is wet", "abc"));
+        events.push(event(
+            "2026-01-01T11:00:00Z",
+            TimelineKind::Sync,
+            String::new(),
+            None,
+            (),
+        ));
+        sort_events(&mut events);
+
+        let kinds: Vec<TimelineKind> = events.iter().map(|e| e.kind).collect();
+        // 12:00+02:00 is 10:00Z, before the 11:00Z sync
+        assert_eq!(
+            kinds,
+            vec![TimelineKind::Verification, TimelineKind::Sync, TimelineKind::Audit]
+        );
+        assert_eq!(events[0].summary, "Verified: PASSED (90%)");
+    }
+}
@@ -0,0 +1,147 @@
+// ==================== SYSTEM TRAY ====================
+//
+// Tray icon with a live repo summary and quick actions. With
+// `minimize_to_tray` enabled, closing the window hides it instead of quitting,
+// so the settings watcher, live events and local API keep running.
+// "Verify Clipboard Text" and "Capture Clipboard Text" run in the background
+// and report via notification; "Open Audit Trail" shows the window and emits
+// `tray://action` for the frontend to route.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{clipboard_verify, quick_capture, sync_conflicts, SETTINGS};
+
+pub const TRAY_ACTION_EVENT: &str = "tray://action";
+
+const TRAY_ID: &str = "main";
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrayAction {
+    /// "open_audit"
+    pub action: String,
+}
+
+/// One-line summary shown in the tray menu and tooltip
+fn status_line(repo_exists: bool, claims: usize, conflicts: usize) -> String {
+    if !repo_exists {
+        return "No truth repository".to_string();
+    }
+    let claims = match claims {
+        1 => "1 claim".to_string(),
+        n => format!("{} claims", n),
+    };
+    match conflicts {
+        0 => claims,
+        1 => format!("{} · 1 sync conflict", claims),
+        n => format!("{} · {} sync conflicts", claims, n),
+    }
+}
+
+async fn current_status() -> String {
+    let conflicts = sync_conflicts::get_sync_conflicts().await.map(|c| c.len()).unwrap_or(0);
+    match crate::get_truth_status(None).await {
+        Ok(status) => status_line(status.exists, status.claims_count, conflicts),
+        Err(e) => format!("Status unavailable: {}", e),
+    }
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn run_action(app: &AppHandle, action: &str) {
+    show_main_window(app);
+    let payload = TrayAction {
+        action: action.to_string(),
+    };
+    if let Err(e) = app.emit(TRAY_ACTION_EVENT, payload) {
+        log::warn!("Failed to emit {}: {}", TRAY_ACTION_EVENT, e);
+    }
+}
+
+/// Create the tray icon and keep its status line current
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let status = MenuItem::with_id(app, "status", "Loading…", false, None::<&str>)?;
+    let show = MenuItem::with_id(app, "show", "Show TruthGit", true, None::<&str>)?;
+    let verify_clipboard = MenuItem::with_id(app, "verify_clipboard", "Verify Clipboard Text", true, None::<&str>)?;
+    let capture_clipboard =
+        MenuItem::with_id(app, "capture_clipboard", "Capture Clipboard Text", true, None::<&str>)?;
+    let open_audit = MenuItem::with_id(app, "open_audit", "Open Audit Trail", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &status,
+            &PredefinedMenuItem::separator(app)?,
+            &show,
+            &verify_clipboard,
+            &capture_clipboard,
+            &open_audit,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("TruthGit")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show" => show_main_window(app),
+            "verify_clipboard" => clipboard_verify::spawn_verify(app),
+            "capture_clipboard" => quick_capture::spawn_capture(app),
+            "open_audit" => run_action(app, event.id.as_ref()),
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let line = current_status().await;
+            let _ = status.set_text(&line);
+            let _ = tray.set_tooltip(Some(format!("TruthGit — {}", line)));
+            tokio::time::sleep(STATUS_REFRESH_INTERVAL).await;
+        }
+    });
+    Ok(())
+}
+
+/// Close button behaviour: hide to tray when the user opted in
+pub fn hide_on_close() -> bool {
+    SETTINGS.read().map(|s| s.minimize_to_tray).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_line() {
+        assert_eq!(status_line(false, 0, 0), "No truth repository");
+        assert_eq!(status_line(true, 1, 0), "1 claim");
+        assert_eq!(status_line(true, 42, 2), "42 claims · 2 sync conflicts");
+    }
+}
@@ -0,0 +1,396 @@
+// ==================== TRUTH STORE ====================
+//
+// The on-disk layout of a truth repo in one place: objects live under
+// objects/<kind>/<first two hash chars>/<rest> (cl for claims, vf for
+// verifications, ev for evidence blobs), HEAD names the current commit and
+// refs/ holds named ones. Claims, verifications, sync, backup and the index
+// go through `TruthStore` instead of joining paths and inflating objects
+// themselves. Object IDs are checked to be plain hex when they're parsed, so
+// a hash from the frontend or a remote can't point outside the store.
+// What an object's content turns out to be is `objects::classify`'s job.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::compression::{self, Encoder};
+use crate::objects;
+use crate::repo_crypto::{self, Binding};
+use crate::{repo_state, ObjectError, MAX_DECOMPRESSED_SIZE};
+
+pub const OBJECTS_DIR: &str = "objects";
+pub const REFS_DIR: &str = "refs";
+pub const HEAD_FILE: &str = "HEAD";
+
+/// Where an object is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ObjectKind {
+    #[serde(rename = "cl")]
+    Claim,
+    #[serde(rename = "vf")]
+    Verification,
+    #[serde(rename = "ev")]
+    Evidence,
+}
+
+impl ObjectKind {
+    /// Kinds exchanged with remotes
+    pub const SYNCED: [ObjectKind; 2] = [ObjectKind::Claim, ObjectKind::Verification];
+
+    pub fn dir(self) -> &'static str {
+        match self {
+            Self::Claim => "cl",
+            Self::Verification => "vf",
+            Self::Evidence => "ev",
+        }
+    }
+
+    pub fn from_dir(dir: &str) -> Option<Self> {
+        match dir {
+            "cl" => Some(Self::Claim),
+            "vf" => Some(Self::Verification),
+            "ev" => Some(Self::Evidence),
+            _ => None,
+        }
+    }
+}
+
+/// An object hash: 4 to 128 hex characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(transparent)]
+pub struct ObjectId(String);
+
+impl ObjectId {
+    pub fn parse(hash: &str) -> Result<Self, String> {
+        if Self::is_valid(hash) {
+            Ok(Self(hash.to_string()))
+        } else {
+            Err(format!(
+                "Invalid object hash: {}",
+                hash.chars().take(80).collect::<String>()
+            ))
+        }
+    }
+
+    /// Hashes are used to build paths, so only plain hex is accepted
+    pub fn is_valid(hash: &str) -> bool {
+        hash.len() >= 4 && hash.len() <= 128 && hash.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Directory prefix and file name
+    fn split(&self) -> (&str, &str) {
+        self.0.split_at(2)
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hash = String::deserialize(deserializer)?;
+        Self::parse(&hash).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An object found by `TruthStore::iter`
+#[derive(Debug, Clone)]
+pub struct StoredEntry {
+    pub kind: ObjectKind,
+    pub id: ObjectId,
+    pub path: PathBuf,
+}
+
+impl StoredEntry {
+    /// "<prefix>/<rest>", as the index keys objects
+    pub fn key(&self) -> String {
+        let (prefix, rest) = self.id.split();
+        format!("{}/{}", prefix, rest)
+    }
+
+    /// Path under objects/ ("cl/ab/cdef…"), as warnings name objects
+    pub fn object_name(&self) -> String {
+        format!("{}/{}", self.kind.dir(), self.key())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruthStore {
+    root: PathBuf,
+}
+
+impl TruthStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The repo configured in settings, once it is ready to be read
+    pub fn active() -> Result<Self, String> {
+        repo_state::ready_path().map(Self::new)
+    }
+
+    /// A truth repo is any directory with an objects/ directory
+    pub fn is_repo(&self) -> bool {
+        self.objects_dir().is_dir()
+    }
+
+    /// Create the empty layout
+    pub fn create(&self) -> Result<(), std::io::Error> {
+        for kind in ObjectKind::SYNCED {
+            fs::create_dir_all(self.kind_dir(kind))?;
+        }
+        Ok(())
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn objects_dir(&self) -> PathBuf {
+        self.root.join(OBJECTS_DIR)
+    }
+
+    pub fn kind_dir(&self, kind: ObjectKind) -> PathBuf {
+        self.objects_dir().join(kind.dir())
+    }
+
+    pub fn object_path(&self, kind: ObjectKind, id: &ObjectId) -> PathBuf {
+        let (prefix, rest) = id.split();
+        self.kind_dir(kind).join(prefix).join(rest)
+    }
+
+    pub fn contains(&self, kind: ObjectKind, id: &ObjectId) -> bool {
+        self.object_path(kind, id).is_file()
+    }
+
+    /// Objects of `kind`; files whose names aren't object hashes (temp
+    /// files, stray copies) are skipped
+    pub fn iter(&self, kind: ObjectKind) -> impl Iterator<Item = StoredEntry> {
+        WalkDir::new(self.kind_dir(kind))
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(move |e| {
+                let prefix = e.path().parent()?.file_name()?.to_str()?;
+                let name = e.file_name().to_str()?;
+                if prefix.len() != 2 {
+                    return None;
+                }
+                let id = ObjectId::parse(&format!("{}{}", prefix, name)).ok()?;
+                Some(StoredEntry {
+                    kind,
+                    id,
+                    path: e.into_path(),
+                })
+            })
+    }
+
+    pub fn count(&self, kind: ObjectKind) -> usize {
+        self.iter(kind).count()
+    }
+
+    /// The object as stored: compressed, and sealed if the repo is encrypted
+    pub fn read_raw(&self, kind: ObjectKind, id: &ObjectId) -> Result<Vec<u8>, String> {
+        fs::read(self.object_path(kind, id)).map_err(|e| format!("Failed to read object {}: {}", id, e))
+    }
+
+    pub fn read_json(&self, kind: ObjectKind, id: &ObjectId) -> Result<serde_json::Value, ObjectError> {
+        decompress_object(&self.object_path(kind, id))
+    }
+
+    /// Store bytes in their final form. Written next to the target and
+    /// renamed, so readers never see a partial object.
+    pub fn write_raw(&self, kind: ObjectKind, id: &ObjectId, bytes: &[u8]) -> Result<(), String> {
+        let target = self.object_path(kind, id);
+        let dir = target.parent().ok_or("Invalid object path")?;
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create object dir: {}", e))?;
+        let tmp = dir.join(format!(".{}.tmp-{}", id, uuid::Uuid::new_v4().simple()));
+        let result = fs::write(&tmp, bytes)
+            .and_then(|_| fs::rename(&tmp, &target))
+            .map_err(|e| format!("Failed to write object {}: {}", id, e));
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result
+    }
+
+    /// Compress `parts` (e.g. a type header and the content, so a large body
+    /// isn't copied) with the configured codec, seal the result if the repo
+    /// is encrypted and store it. Objects are content-addressed, so an
+    /// existing one is left as is.
+    pub fn write(&self, kind: ObjectKind, id: &ObjectId, parts: &[&[u8]]) -> Result<(), String> {
+        if self.contains(kind, id) {
+            return Ok(());
+        }
+        let compress_err = |e: std::io::Error| format!("Failed to compress object: {}", e);
+        let mut encoder = Encoder::object(compression::object_codec(), Vec::new()).map_err(compress_err)?;
+        for part in parts {
+            encoder.write_all(part).map_err(compress_err)?;
+        }
+        let compressed = encoder.finish().map_err(compress_err)?;
+        self.write_raw(kind, id, &repo_crypto::seal(&self.root, Binding::Object(kind, id), compressed)?)
+    }
+
+    /// Current commit, if HEAD is set
+    pub fn head(&self) -> Option<String> {
+        fs::read_to_string(self.root.join(HEAD_FILE))
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+    }
+
+    pub fn set_head(&self, head: &str) -> Result<(), String> {
+        let tmp = self.root.join(format!("{}.tmp", HEAD_FILE));
+        fs::write(&tmp, format!("{}\n", head)).map_err(|e| format!("Failed to write HEAD: {}", e))?;
+        fs::rename(&tmp, self.root.join(HEAD_FILE)).map_err(|e| format!("Failed to update HEAD: {}", e))
+    }
+}
+
+/// Open (if sealed) and inflate the stored bytes of object `id` of `kind`
+/// in the repo at `root` into a buffer capped at `limit` bytes
+pub fn inflate_stored(
+    root: &Path,
+    kind: ObjectKind,
+    id: &ObjectId,
+    raw: &[u8],
+    limit: usize,
+) -> Result<Vec<u8>, ObjectError> {
+    if !repo_crypto::is_sealed(raw) {
+        return inflate(raw, limit);
+    }
+    let opened =
+        repo_crypto::open(root, Binding::Object(kind, id), raw).map_err(|message| ObjectError::Encrypted { message })?;
+    inflate(&opened, limit)
+}
+
+/// Inflate plain object bytes into a buffer capped at `limit` bytes. Sealed
+/// bytes only open where they are stored, through `inflate_stored`.
+pub fn inflate(compressed: &[u8], limit: usize) -> Result<Vec<u8>, ObjectError> {
+    if compressed.is_empty() {
+        return Err(ObjectError::Corrupt {
+            message: "empty object file".to_string(),
+        });
+    }
+    if repo_crypto::is_sealed(compressed) {
+        return Err(ObjectError::Encrypted {
+            message: "the object is encrypted and not in its repo".to_string(),
+        });
+    }
+
+    // SECURITY: Read at most limit + 1 bytes to prevent OOM from malicious compressed data
+    let mut decompressed = Vec::with_capacity(compressed.len().saturating_mul(4).min(limit));
+    compression::object_decoder(compressed)
+        .map_err(|e| ObjectError::Corrupt { message: e.to_string() })?
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| ObjectError::Corrupt { message: e.to_string() })?;
+    if decompressed.len() > limit {
+        return Err(ObjectError::TooLarge { limit });
+    }
+    Ok(decompressed)
+}
+
+/// Inflate an object file straight from a memory map. Callers parse from the
+/// bytes without an intermediate `String`.
+pub fn inflate_object(path: &Path, limit: usize) -> Result<Vec<u8>, ObjectError> {
+    let io_err = |e: std::io::Error| ObjectError::Io { message: e.to_string() };
+    let file = File::open(path).map_err(io_err)?;
+    if file.metadata().map_err(io_err)?.len() == 0 {
+        return Err(ObjectError::Corrupt {
+            message: "empty object file".to_string(),
+        });
+    }
+    // SAFETY: objects are content-addressed and never modified in place; the
+    // map is read-only and dropped before this function returns.
+    let mmap = unsafe { memmap2::Mmap::map(&file) }.map_err(io_err)?;
+    match locate(path) {
+        Some((root, kind, id)) => inflate_stored(root, kind, &id, &mmap, limit),
+        None => inflate(&mmap, limit),
+    }
+}
+
+/// The repo, kind and id of an object file, from where it sits under objects/
+fn locate(path: &Path) -> Option<(&Path, ObjectKind, ObjectId)> {
+    let name = path.file_name()?.to_str()?;
+    let prefix_dir = path.parent()?;
+    let prefix = prefix_dir.file_name()?.to_str()?;
+    let kind_dir = prefix_dir.parent()?;
+    let kind = ObjectKind::from_dir(kind_dir.file_name()?.to_str()?)?;
+    let objects_dir = kind_dir.parent()?;
+    if prefix.len() != 2 || objects_dir.file_name()? != OBJECTS_DIR {
+        return None;
+    }
+    let id = ObjectId::parse(&format!("{}{}", prefix, name)).ok()?;
+    Some((objects_dir.parent()?, kind, id))
+}
+
+/// Decode stored bytes as a JSON object, skipping its type header if it has one
+pub fn decode_json(raw: &[u8]) -> Result<serde_json::Value, ObjectError> {
+    parse_json(&inflate(raw, MAX_DECOMPRESSED_SIZE)?)
+}
+
+/// Decode a JSON object file, skipping its type header if it has one
+pub fn decompress_object(path: &Path) -> Result<serde_json::Value, ObjectError> {
+    parse_json(&inflate_object(path, MAX_DECOMPRESSED_SIZE)?)
+}
+
+fn parse_json(bytes: &[u8]) -> Result<serde_json::Value, ObjectError> {
+    let (_, body) = objects::split_header(bytes);
+    serde_json::from_slice(body).map_err(|e| ObjectError::InvalidJson { message: e.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+
+    #[test]
+    fn test_store_round_trip_and_iter() {
+        let tmp = TempDir::new("truthgit-store");
+        let root = tmp.path();
+        let store = TruthStore::new(root);
+        assert!(!store.is_repo());
+        store.create().unwrap();
+        assert!(store.is_repo());
+
+        let id = ObjectId::parse("ab12cd34").unwrap();
+        store
+            .write(ObjectKind::Claim, &id, &[br#"{"$hash":"ab12cd34","content":"x"}"#])
+            .unwrap();
+        assert!(store
+            .object_path(ObjectKind::Claim, &id)
+            .ends_with("objects/cl/ab/12cd34"));
+        assert_eq!(store.read_json(ObjectKind::Claim, &id).unwrap()["content"], "x");
+        assert!(!store.contains(ObjectKind::Verification, &id));
+
+        // Temp files and names that aren't hashes are not objects
+        let dir = store.kind_dir(ObjectKind::Claim).join("ab");
+        fs::write(dir.join(".12cd34.tmp-1"), b"x").unwrap();
+        fs::write(dir.join("notes.txt"), b"x").unwrap();
+        let entries: Vec<_> = store.iter(ObjectKind::Claim).collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key(), "ab/12cd34");
+        assert_eq!(entries[0].object_name(), "cl/ab/12cd34");
+
+        assert_eq!(store.head(), None);
+        store.set_head("ab12cd34").unwrap();
+        assert_eq!(store.head().as_deref(), Some("ab12cd34"));
+
+        assert!(ObjectId::parse("../../etc/passwd").is_err());
+        assert!(ObjectId::parse("ab/cd").is_err());
+        assert!(ObjectId::parse("abc").is_err());
+        assert!(serde_json::from_str::<ObjectId>("\"zz99\"").is_err());
+    }
+}
@@ -0,0 +1,286 @@
+// ==================== APP UPDATES ====================
+//
+// Self-update through the Tauri updater on the release channel picked in
+// settings (stable or beta). `check_for_updates` looks for a newer release,
+// `download_update` fetches it and has the updater verify its signature
+// against the public key built into this release (TRUTHGIT_UPDATER_PUBKEY
+// at build time; builds without one can't update), then emits
+// `update://ready`; `install_update` installs it and restarts. The first
+// start on a new version runs diagnostics and reports the result as
+// `update://health`, so a broken update is noticed right away.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::diagnostics::{self, CheckStatus};
+use crate::json_store::{config_path, load_json, save_json};
+use crate::{http_client, mock, tasks, SETTINGS};
+
+pub const CHANNEL_STABLE: &str = "stable";
+pub const CHANNEL_BETA: &str = "beta";
+pub const CHANNELS: &[&str] = &[CHANNEL_STABLE, CHANNEL_BETA];
+
+pub const UPDATE_READY_EVENT: &str = "update://ready";
+pub const UPDATE_HEALTH_EVENT: &str = "update://health";
+
+const RELEASES_URL: &str = "https://github.com/lumensyntax-org/truthgit-desktop/releases";
+const CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+/// minisign public key of the release signing key, set by the release build
+const UPDATER_PUBKEY: Option<&str> = option_env!("TRUTHGIT_UPDATER_PUBKEY");
+
+/// Release found by the last check; downloading uses it
+static AVAILABLE: Mutex<Option<Update>> = Mutex::new(None);
+/// Downloaded and verified update waiting to be installed
+static READY: Mutex<Option<(Update, Vec<u8>)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub channel: String,
+    pub current_version: String,
+    pub available: bool,
+    pub version: Option<String>,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// The last installed update, kept in update_state.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateRecord {
+    pub from_version: String,
+    pub to_version: String,
+    pub channel: String,
+    pub installed_at: String,
+    /// Diagnostics result on the first start of `to_version`
+    #[serde(default)]
+    pub health: Option<CheckStatus>,
+    #[serde(default)]
+    pub health_checked_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateStatus {
+    pub channel: String,
+    pub current_version: String,
+    /// Version downloaded and waiting for `install_update`
+    pub ready_version: Option<String>,
+    pub last_update: Option<UpdateRecord>,
+}
+
+pub fn default_update_channel() -> String {
+    CHANNEL_STABLE.to_string()
+}
+
+fn current_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+fn state_path() -> std::path::PathBuf {
+    config_path("update_state.json")
+}
+
+/// Update manifest for a channel: stable follows the latest release, beta
+/// the manifest attached to the `beta` release
+fn channel_endpoint(channel: &str) -> Result<reqwest::Url, String> {
+    let url = match channel {
+        CHANNEL_STABLE => format!("{}/latest/download/latest.json", RELEASES_URL),
+        CHANNEL_BETA => format!("{}/download/beta/latest.json", RELEASES_URL),
+        other => return Err(format!("Unknown update channel '{}'", other)),
+    };
+    reqwest::Url::parse(&url).map_err(|e| format!("Invalid update endpoint: {}", e))
+}
+
+/// The record still needs its post-update health check on this version
+fn needs_health_check(record: &UpdateRecord, version: &str) -> bool {
+    record.to_version == version && record.health.is_none()
+}
+
+fn settings_snapshot() -> Result<(String, String), String> {
+    let settings = SETTINGS.read().map_err(|e| format!("Settings lock error: {}", e))?;
+    Ok((settings.update_channel.clone(), settings.proxy_url.clone()))
+}
+
+/// Run the post-update health check if this is the first start after an update
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Ok(Some(mut record)) = load_json::<Option<UpdateRecord>>(&state_path()) else {
+            return;
+        };
+        if !needs_health_check(&record, &current_version()) {
+            return;
+        }
+        let report = match diagnostics::run_diagnostics().await {
+            Ok(report) => report,
+            Err(e) => {
+                log::warn!("Post-update health check failed: {}", e);
+                return;
+            }
+        };
+        record.health = Some(report.overall);
+        record.health_checked_at = Some(chrono::Utc::now().to_rfc3339());
+        log::info!(
+            "Updated {} -> {}: health {:?}",
+            record.from_version,
+            record.to_version,
+            report.overall
+        );
+        if let Err(e) = save_json(&state_path(), &Some(&record)) {
+            log::warn!("Failed to save update state: {}", e);
+        }
+        if let Err(e) = app.emit(UPDATE_HEALTH_EVENT, &record) {
+            log::warn!("Failed to emit update health: {}", e);
+        }
+    });
+}
+
+/// Look for a newer release on the configured channel
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, String> {
+    if mock::is_active() {
+        return Err("Updates are disabled in mock mode".to_string());
+    }
+    let pubkey = UPDATER_PUBKEY
+        .filter(|key| !key.trim().is_empty())
+        .ok_or("This build has no update signing key, so updates can't be verified")?;
+    let (channel, proxy_url) = settings_snapshot()?;
+    let mut builder = app
+        .updater_builder()
+        .pubkey(pubkey)
+        .endpoints(vec![channel_endpoint(&channel)?])
+        .map_err(|e| format!("Invalid update endpoint: {}", e))?
+        .timeout(CHECK_TIMEOUT);
+    if !proxy_url.trim().is_empty() {
+        builder = builder.proxy(http_client::parse_proxy_url(&proxy_url)?);
+    }
+    let update = builder
+        .build()
+        .map_err(|e| format!("Updater unavailable: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    let info = UpdateInfo {
+        channel,
+        current_version: current_version(),
+        available: update.is_some(),
+        version: update.as_ref().map(|u| u.version.clone()),
+        date: update.as_ref().and_then(|u| u.date.map(|d| d.to_string())),
+        notes: update.as_ref().and_then(|u| u.body.clone()),
+    };
+    *AVAILABLE.lock().map_err(|e| format!("Lock error: {}", e))? = update;
+    Ok(info)
+}
+
+/// Download the release found by `check_for_updates`. The updater rejects
+/// it unless its signature matches this build's update key.
+#[tauri::command]
+pub async fn download_update(app: AppHandle, task_id: Option<String>) -> Result<String, String> {
+    let update = AVAILABLE
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .clone()
+        .ok_or("No update available; check for updates first")?;
+    let version = update.version.clone();
+    let bytes = tasks::run(&app, task_id, "update_download", "Downloading update", |task| {
+        let update = update.clone();
+        async move {
+            let mut downloaded = 0u64;
+            update
+                .download(
+                    |chunk, total| {
+                        downloaded += chunk as u64;
+                        let total = total.unwrap_or(0);
+                        task.progress(downloaded, total, format!("Downloaded {} KB", downloaded / 1024));
+                    },
+                    || {},
+                )
+                .await
+                .map_err(|e| format!("Failed to download update: {}", e))
+        }
+    })
+    .await?;
+
+    *READY.lock().map_err(|e| format!("Lock error: {}", e))? = Some((update, bytes));
+    log::info!("Update {} downloaded and verified", version);
+    if let Err(e) = app.emit(UPDATE_READY_EVENT, &version) {
+        log::warn!("Failed to emit update ready: {}", e);
+    }
+    Ok(version)
+}
+
+/// Install the downloaded update and restart into it
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    let (update, bytes) = READY
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .take()
+        .ok_or("No update has been downloaded")?;
+    let (channel, _) = settings_snapshot()?;
+    let record = UpdateRecord {
+        from_version: current_version(),
+        to_version: update.version.clone(),
+        channel,
+        installed_at: chrono::Utc::now().to_rfc3339(),
+        health: None,
+        health_checked_at: None,
+    };
+    // Saved first: on Windows the installer takes over and this process never
+    // gets past `install`. A failed install puts the previous record back.
+    let previous = load_json::<Option<UpdateRecord>>(&state_path()).unwrap_or_default();
+    save_json(&state_path(), &Some(&record))?;
+    if let Err(e) = update.install(&bytes) {
+        if let Err(e) = save_json(&state_path(), &previous) {
+            log::warn!("Failed to restore update state: {}", e);
+        }
+        return Err(format!("Failed to install update: {}", e));
+    }
+    log::info!("Installed update {}, restarting", record.to_version);
+    app.restart();
+}
+
+#[tauri::command]
+pub async fn get_update_status() -> Result<UpdateStatus, String> {
+    let (channel, _) = settings_snapshot()?;
+    let ready_version = READY
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .as_ref()
+        .map(|(update, _)| update.version.clone());
+    Ok(UpdateStatus {
+        channel,
+        current_version: current_version(),
+        ready_version,
+        last_update: load_json(&state_path())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_endpoints() {
+        assert!(channel_endpoint(CHANNEL_STABLE)
+            .unwrap()
+            .as_str()
+            .ends_with("/releases/latest/download/latest.json"));
+        assert!(channel_endpoint(CHANNEL_BETA)
+            .unwrap()
+            .as_str()
+            .contains("/download/beta/"));
+        assert!(channel_endpoint("nightly").is_err());
+
+        let mut record = UpdateRecord {
+            to_version: "0.3.0".to_string(),
+            ..Default::default()
+        };
+        assert!(needs_health_check(&record, "0.3.0"));
+        assert!(!needs_health_check(&record, "0.2.6"));
+        record.health = Some(CheckStatus::Ok);
+        assert!(!needs_health_check(&record, "0.3.0"));
+    }
+}
@@ -0,0 +1,359 @@
+// ==================== ENCRYPTED NOTES ====================
+//
+// Vault notes encrypted with age (binary or armored `.age` files, e.g.
+// `Salaries.md.age`) become readable once the user unlocks them for the
+// session with a passphrase or an age identity (AGE-SECRET-KEY-...). The
+// secret is held in memory only and `lock_encrypted_notes` drops it.
+//
+// Searching them needs their text, so the decrypted notes are indexed in
+// vault-notes.age in the cache directory, age-encrypted with the same
+// secret: plaintext never lands on disk, and each search only decrypts the
+// notes that changed since the index was written.
+
+use age::armor::ArmoredReader;
+use age::secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use walkdir::WalkDir;
+
+use crate::json_store::{cache_path, write_atomic};
+use crate::{get_vault_path, mock, run_blocking, validate_path_within_base, MAX_VAULT_FILES};
+
+pub const NOTES_LOCKED_ERROR: &str = "This note is encrypted; unlock encrypted notes first";
+
+const AGE_MAGIC: &[u8] = b"age-encryption.org/";
+const ARMOR_MAGIC: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+const IDENTITY_PREFIX: &str = "AGE-SECRET-KEY-";
+const MAX_NOTE_SIZE: u64 = 5 * 1024 * 1024;
+
+enum NoteSecret {
+    Passphrase(String),
+    Identity(age::x25519::Identity),
+}
+
+struct Session {
+    secret: NoteSecret,
+    /// Loaded from disk on the first search after unlocking
+    index: Option<NoteIndex>,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct NoteIndex {
+    vault: String,
+    /// By path relative to the vault
+    notes: HashMap<String, IndexedNote>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexedNote {
+    modified: Option<String>,
+    content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EncryptedNotesStatus {
+    pub unlocked: bool,
+    pub encrypted_notes: usize,
+}
+
+fn index_path() -> PathBuf {
+    cache_path("vault-notes.age")
+}
+
+/// Whether `path` is an age file, judged by its header rather than its name
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut prefix = [0u8; ARMOR_MAGIC.len()];
+    let Ok(read) = fs::File::open(path).and_then(|mut f| f.read(&mut prefix)) else {
+        return false;
+    };
+    prefix[..read].starts_with(AGE_MAGIC) || prefix[..read].starts_with(ARMOR_MAGIC)
+}
+
+fn parse_secret(secret: &str) -> Result<NoteSecret, String> {
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err("Enter a passphrase or an age identity".to_string());
+    }
+    if secret.starts_with(IDENTITY_PREFIX) {
+        return secret
+            .parse()
+            .map(NoteSecret::Identity)
+            .map_err(|e| format!("Invalid age identity: {}", e));
+    }
+    Ok(NoteSecret::Passphrase(secret.to_string()))
+}
+
+fn decrypt_with(secret: &NoteSecret, input: impl Read) -> Result<Vec<u8>, String> {
+    let decryptor =
+        age::Decryptor::new(ArmoredReader::new(input)).map_err(|e| format!("Not a readable age file: {}", e))?;
+    let reader = match secret {
+        NoteSecret::Passphrase(passphrase) => {
+            let identity = age::scrypt::Identity::new(SecretString::from(passphrase.clone()));
+            decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity))
+        }
+        NoteSecret::Identity(identity) => decryptor.decrypt(std::iter::once(identity as &dyn age::Identity)),
+    }
+    .map_err(|_| "Wrong passphrase or key for this note".to_string())?;
+    let mut plain = Vec::new();
+    reader
+        .take(MAX_NOTE_SIZE + 1)
+        .read_to_end(&mut plain)
+        .map_err(|e| format!("Failed to decrypt note: {}", e))?;
+    if plain.len() as u64 > MAX_NOTE_SIZE {
+        return Err("Encrypted note is too large".to_string());
+    }
+    Ok(plain)
+}
+
+fn encrypt_with(secret: &NoteSecret, plain: &[u8]) -> Result<Vec<u8>, String> {
+    let encryptor = match secret {
+        NoteSecret::Passphrase(passphrase) => {
+            age::Encryptor::with_user_passphrase(SecretString::from(passphrase.clone()))
+        }
+        NoteSecret::Identity(identity) => {
+            let recipient = identity.to_public();
+            age::Encryptor::with_recipients(std::iter::once(&recipient as &dyn age::Recipient))
+                .map_err(|e| format!("Failed to encrypt note: {}", e))?
+        }
+    };
+    let write_err = |e: std::io::Error| format!("Failed to encrypt note: {}", e);
+    let mut writer = encryptor.wrap_output(Vec::new()).map_err(write_err)?;
+    writer.write_all(plain).map_err(write_err)?;
+    writer.finish().map_err(write_err)
+}
+
+fn decrypt_note(secret: &NoteSecret, path: &Path) -> Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to read note: {}", e))?;
+    String::from_utf8(decrypt_with(secret, file)?).map_err(|_| "Encrypted note is not UTF-8 text".to_string())
+}
+
+/// Decrypt a note with the session secret
+pub fn read_note(path: &Path) -> Result<String, String> {
+    let session = SESSION.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let session = session.as_ref().ok_or(NOTES_LOCKED_ERROR)?;
+    decrypt_note(&session.secret, path)
+}
+
+fn modified(path: &Path) -> Option<String> {
+    let time = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339())
+}
+
+/// Encrypted notes in the vault, by path relative to it
+fn encrypted_notes(vault: &Path) -> Vec<(String, PathBuf)> {
+    WalkDir::new(vault)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .take(MAX_VAULT_FILES)
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "age"))
+        .filter(|e| is_encrypted(e.path()))
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(vault).ok()?.to_string_lossy().to_string();
+            Some((relative, e.into_path()))
+        })
+        .collect()
+}
+
+fn load_index(secret: &NoteSecret, vault: &str) -> NoteIndex {
+    let loaded = fs::File::open(index_path())
+        .map_err(|e| e.to_string())
+        .and_then(|file| decrypt_with(secret, file))
+        .and_then(|bytes| serde_json::from_slice::<NoteIndex>(&bytes).map_err(|e| e.to_string()));
+    match loaded {
+        Ok(index) if index.vault == vault => index,
+        Ok(_) => NoteIndex::default(),
+        Err(e) => {
+            log::debug!("Rebuilding the encrypted note index: {}", e);
+            NoteIndex::default()
+        }
+    }
+}
+
+/// Bring the index up to date with the vault; true if anything changed
+fn refresh_index(secret: &NoteSecret, index: &mut NoteIndex, vault: &Path) -> bool {
+    let vault_key = vault.to_string_lossy().to_string();
+    let mut changed = index.vault != vault_key;
+    if changed {
+        *index = NoteIndex {
+            vault: vault_key,
+            notes: HashMap::new(),
+        };
+    }
+    let notes = encrypted_notes(vault);
+    let before = index.notes.len();
+    index.notes.retain(|path, _| notes.iter().any(|(p, _)| p == path));
+    changed |= index.notes.len() != before;
+
+    for (relative, path) in notes {
+        let modified = modified(&path);
+        if index.notes.get(&relative).is_some_and(|n| n.modified == modified) {
+            continue;
+        }
+        match decrypt_note(secret, &path) {
+            Ok(content) => {
+                index.notes.insert(relative, IndexedNote { modified, content });
+                changed = true;
+            }
+            // Notes encrypted to a different key stay unsearchable
+            Err(e) => log::debug!("Skipping encrypted note in search: {}", e),
+        }
+    }
+    changed
+}
+
+/// Text of the encrypted notes the session can read, by path relative to
+/// the vault; empty while they are locked
+pub fn searchable_notes(vault: &Path) -> Result<Vec<(String, String)>, String> {
+    let mut session = SESSION.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let Some(session) = session.as_mut() else {
+        return Ok(Vec::new());
+    };
+    let vault_key = vault.to_string_lossy().to_string();
+    let secret = &session.secret;
+    let index = session.index.get_or_insert_with(|| load_index(secret, &vault_key));
+    if refresh_index(secret, index, vault) {
+        let saved = serde_json::to_vec(&*index)
+            .map_err(|e| e.to_string())
+            .and_then(|json| encrypt_with(secret, &json))
+            .and_then(|bytes| write_atomic(&index_path(), &bytes).map_err(|e| e.to_string()));
+        if let Err(e) = saved {
+            log::warn!("Failed to save the encrypted note index: {}", e);
+        }
+    }
+    Ok(index
+        .notes
+        .iter()
+        .map(|(path, note)| (path.clone(), note.content.clone()))
+        .collect())
+}
+
+fn vault() -> Result<PathBuf, String> {
+    get_vault_path()
+        .filter(|p| p.exists())
+        .ok_or_else(|| "Vault not found".to_string())
+}
+
+#[tauri::command]
+pub async fn get_encrypted_notes_status() -> Result<EncryptedNotesStatus, String> {
+    run_blocking(|| {
+        let unlocked = SESSION.lock().map_err(|e| format!("Lock error: {}", e))?.is_some();
+        Ok(EncryptedNotesStatus {
+            unlocked,
+            encrypted_notes: vault().map(|v| encrypted_notes(&v).len()).unwrap_or(0),
+        })
+    })
+    .await
+}
+
+/// Unlock encrypted notes for this session with a passphrase or an age
+/// identity. The secret is checked against the first encrypted note.
+#[tauri::command]
+pub async fn unlock_encrypted_notes(secret: String) -> Result<EncryptedNotesStatus, String> {
+    run_blocking(move || {
+        let secret = parse_secret(&secret)?;
+        let notes = vault().map(|v| encrypted_notes(&v)).unwrap_or_default();
+        if let Some((relative, path)) = notes.first() {
+            decrypt_note(&secret, path).map_err(|e| format!("Couldn't unlock {}: {}", relative, e))?;
+        }
+        *SESSION.lock().map_err(|e| format!("Lock error: {}", e))? = Some(Session { secret, index: None });
+        log::info!("Unlocked encrypted notes");
+        Ok(EncryptedNotesStatus {
+            unlocked: true,
+            encrypted_notes: notes.len(),
+        })
+    })
+    .await
+}
+
+/// Forget the secret and the decrypted index
+#[tauri::command]
+pub async fn lock_encrypted_notes() -> Result<(), String> {
+    *SESSION.lock().map_err(|e| format!("Lock error: {}", e))? = None;
+    log::info!("Locked encrypted notes");
+    Ok(())
+}
+
+/// Write an encrypted note (`*.age`) with the session secret, creating it
+/// or replacing an existing encrypted one. Plain notes are never overwritten.
+#[tauri::command]
+pub async fn save_encrypted_note(relative_path: String, content: String) -> Result<(), String> {
+    if mock::is_active() {
+        return Err("Writing notes is disabled in mock mode".to_string());
+    }
+    if content.len() as u64 > MAX_NOTE_SIZE {
+        return Err("Note is too large".to_string());
+    }
+    run_blocking(move || {
+        let vault = vault()?;
+        let relative = Path::new(&relative_path);
+        let name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .filter(|n| n.ends_with(".age") && !n.starts_with('.'))
+            .ok_or("Encrypted notes must be saved as a .age file")?;
+        let dir = match relative.parent().map(|p| p.to_string_lossy().to_string()) {
+            Some(parent) if !parent.is_empty() => validate_path_within_base(&vault, &parent)?,
+            _ => vault.clone(),
+        };
+        let path = dir.join(&name);
+        if path.exists() && !is_encrypted(&path) {
+            return Err("A plain note with this name already exists".to_string());
+        }
+
+        let session = SESSION.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let session = session.as_ref().ok_or(NOTES_LOCKED_ERROR)?;
+        let encrypted = encrypt_with(&session.secret, content.as_bytes())?;
+        write_atomic(&path, &encrypted).map_err(|e| format!("Failed to write note: {}", e))?;
+        log::info!("Saved encrypted note");
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dirs::TempDir;
+    use age::secrecy::ExposeSecret;
+
+    #[test]
+    fn test_identity_roundtrip_and_index_refresh() {
+        let identity = age::x25519::Identity::generate();
+        let secret = parse_secret(identity.to_string().expose_secret()).unwrap();
+        assert!(matches!(parse_secret("correct horse"), Ok(NoteSecret::Passphrase(_))));
+
+        let tmp = TempDir::new("truthgit-vault-crypto");
+        let vault = tmp.path();
+        fs::create_dir_all(vault.join(".obsidian")).unwrap();
+        fs::write(
+            vault.join("Secret.md.age"),
+            encrypt_with(&secret, b"salary bands").unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            vault.join(".obsidian/cache.age"),
+            encrypt_with(&secret, b"hidden").unwrap(),
+        )
+        .unwrap();
+        fs::write(vault.join("Plain.age"), "not age").unwrap();
+
+        assert!(is_encrypted(&vault.join("Secret.md.age")));
+        assert!(!is_encrypted(&vault.join("Plain.age")));
+        assert_eq!(
+            decrypt_note(&secret, &vault.join("Secret.md.age")).unwrap(),
+            "salary bands"
+        );
+
+        let mut index = NoteIndex::default();
+        assert!(refresh_index(&secret, &mut index, vault));
+        assert_eq!(index.notes.keys().collect::<Vec<_>>(), vec!["Secret.md.age"]);
+        assert!(!refresh_index(&secret, &mut index, vault));
+    }
+}
@@ -0,0 +1,206 @@
+// ==================== VERIFICATIONS ====================
+//
+// Verification objects (objects/vf) come from several generations of the
+// `truthgit` engine. Version 1 wrote `status` and plain validator names;
+// version 2 writes `verdict`, evidence refs and the signer, and newer tools
+// stamp `$version` explicitly. `Verification` reads all of them into one
+// shape so the listing, exports, reports and attestations share a stable
+// contract with the frontend. Reading is lenient: a missing or mistyped
+// field becomes empty rather than failing the object, and fields the app
+// doesn't model are kept in `extra`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Newest object version the app knows how to read
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Fields read into `Verification`; everything else goes to `extra`
+const KNOWN_FIELDS: &[&str] = &[
+    "$type",
+    "$version",
+    "version",
+    "$hash",
+    "hash",
+    "claim_hash",
+    "claim",
+    "domain",
+    "verdict",
+    "status",
+    "confidence",
+    "validators",
+    "evidence",
+    "evidence_refs",
+    "signer",
+    "signed_by",
+    "timestamp",
+    "verified_at",
+    "audit_ref",
+    "extra",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Value")]
+pub struct Verification {
+    /// Object version, stated or inferred from the fields present
+    pub version: u32,
+    /// Hash of the verification object itself
+    pub hash: String,
+    pub claim_hash: String,
+    /// Claim text as verified
+    pub claim: String,
+    pub domain: String,
+    /// e.g. "PASSED"; `status` in version 1 objects
+    pub verdict: String,
+    /// 0.0-1.0
+    pub confidence: Option<f64>,
+    /// Hashes of the evidence objects the verdict rests on
+    pub evidence: Vec<String>,
+    pub validators: Vec<String>,
+    /// Key ID or identity that signed the verification
+    pub signer: Option<String>,
+    /// RFC 3339
+    pub timestamp: String,
+    /// Audit entry written alongside the verification
+    pub audit_ref: Option<String>,
+    /// Fields not modeled above, as stored
+    pub extra: BTreeMap<String, Value>,
+}
+
+fn text(object: &Map<String, Value>, names: &[&str]) -> String {
+    names
+        .iter()
+        .filter_map(|name| match object.get(*name)? {
+            Value::String(s) => Some(s.trim().to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .find(|s| !s.is_empty())
+        .unwrap_or_default()
+}
+
+/// Strings, or objects naming one under `name`, `hash` or `id`
+fn refs(object: &Map<String, Value>, names: &[&str]) -> Vec<String> {
+    let Some(items) = names.iter().find_map(|name| object.get(*name)?.as_array()) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Value::String(s) => Some(s.as_str()),
+            Value::Object(o) => ["name", "hash", "$hash", "id"].iter().find_map(|k| o.get(*k)?.as_str()),
+            _ => None,
+        })
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Numbers or numeric strings; percentages above 1 are scaled down
+fn confidence(value: Option<&Value>) -> Option<f64> {
+    let number = match value? {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => s.trim().trim_end_matches('%').parse().ok()?,
+        _ => return None,
+    };
+    let number = if number > 1.0 { number / 100.0 } else { number };
+    Some(number).filter(|c| (0.0..=1.0).contains(c))
+}
+
+fn detect_version(object: &Map<String, Value>) -> u32 {
+    let stated = ["$version", "version"]
+        .iter()
+        .find_map(|name| match object.get(*name)? {
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.trim().trim_start_matches('v').parse().ok(),
+            _ => None,
+        });
+    match stated {
+        Some(version) => u32::try_from(version).unwrap_or(u32::MAX).max(1),
+        None if object.contains_key("verdict") => 2,
+        None => 1,
+    }
+}
+
+impl Verification {
+    pub fn from_value(value: Value) -> Self {
+        let Value::Object(object) = value else {
+            return Self {
+                version: 1,
+                ..Self::default()
+            };
+        };
+        let mut extra: BTreeMap<String, Value> = match object.get("extra") {
+            Some(Value::Object(extra)) => extra.clone().into_iter().collect(),
+            _ => BTreeMap::new(),
+        };
+        extra.extend(
+            object
+                .iter()
+                .filter(|(k, _)| !KNOWN_FIELDS.contains(&k.as_str()))
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        let optional = |names: &[&str]| Some(text(&object, names)).filter(|s| !s.is_empty());
+        Self {
+            version: detect_version(&object),
+            hash: text(&object, &["$hash", "hash"]),
+            claim_hash: text(&object, &["claim_hash"]).to_lowercase(),
+            claim: text(&object, &["claim"]),
+            domain: text(&object, &["domain"]),
+            verdict: text(&object, &["verdict", "status"]),
+            confidence: confidence(object.get("confidence")),
+            evidence: refs(&object, &["evidence", "evidence_refs"]),
+            validators: refs(&object, &["validators"]),
+            signer: optional(&["signer", "signed_by"]),
+            timestamp: text(&object, &["timestamp", "verified_at"]),
+            audit_ref: optional(&["audit_ref"]),
+            extra,
+        }
+    }
+
+    /// Written by a newer engine than this app knows; fields may be missing
+    pub fn is_newer(&self) -> bool {
+        self.version > CURRENT_VERSION
+    }
+}
+
+impl From<Value> for Verification {
+    fn from(value: Value) -> Self {
+        Self::from_value(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_each_version() {
+        let v1 = Verification::from_value(serde_json::json!({
+            "$type": "verification", "$hash": "ab12", "claim_hash": "FF00", "status": "FAILED",
+            "confidence": "85%", "validators": ["a", {"name": "b"}], "timestamp": "2025-06-01T00:00:00Z",
+        }));
+        assert_eq!(v1.version, 1);
+        assert_eq!((v1.claim_hash.as_str(), v1.verdict.as_str()), ("ff00", "FAILED"));
+        assert_eq!(v1.confidence, Some(0.85));
+        assert_eq!(v1.validators, ["a", "b"]);
+
+        let v2 = Verification::from_value(serde_json::json!({
+            "$hash": "cd34", "claim_hash": "ff00", "verdict": "PASSED", "confidence": 0.9,
+            "evidence": [{"hash": "e1"}], "signer": "key-1", "consensus": {"rounds": 2},
+        }));
+        assert_eq!(v2.version, 2);
+        assert_eq!(v2.evidence, ["e1"]);
+        assert_eq!(v2.signer.as_deref(), Some("key-1"));
+        assert_eq!(v2.extra.get("consensus"), Some(&serde_json::json!({"rounds": 2})));
+        assert!(!v2.is_newer());
+
+        let round_trip: Verification = serde_json::from_value(serde_json::to_value(&v2).unwrap()).unwrap();
+        assert_eq!(round_trip, v2);
+
+        let future = Verification::from_value(serde_json::json!({"$version": 3, "confidence": "high"}));
+        assert!(future.is_newer());
+        assert_eq!(future.confidence, None);
+    }
+}
@@ -0,0 +1,451 @@
+// ==================== WATCHLIST ====================
+//
+// Pinned claims the user wants to keep an eye on. Pins live in
+// watchlist.json in the truth repo, with the status and confidence each
+// claim last verified with. A background loop re-verifies pinned claims
+// every `watchlist.recheck_hours` and, when the status or confidence moved,
+// emits `watchlist://changed` and an opt-in notification.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::identity::{self, Actor};
+use crate::journal::{self, Store};
+use crate::json_store::{load_json, save_json, StoreLock};
+use crate::notifications::{self, NotificationKind};
+use crate::reviews::{short_hash, validate_hash};
+use crate::tasks::{self, TaskHandle};
+use crate::work_queue::{self, NewItem, QueueSource};
+use crate::{mock, read_claim, read_verifications, repo_state, run_blocking, GovernanceResult, SETTINGS};
+
+pub const WATCHLIST_CHANGED_EVENT: &str = "watchlist://changed";
+
+/// How often the scheduler looks for pins that are due
+const SCHEDULER_TICK: Duration = Duration::from_secs(15 * 60);
+/// Smallest confidence move reported as a change
+const CONFIDENCE_CHANGE: f64 = 0.05;
+const MAX_PINNED: usize = 200;
+
+/// Serializes read-modify-write cycles on watchlist.json
+static WATCHLIST_LOCK: StoreLock = StoreLock::new();
+
+/// Set while a re-check runs, so the scheduler and a manual run don't overlap
+static RECHECK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchlistSettings {
+    /// Hours between re-verifications of each pinned claim; 0 turns them off
+    pub recheck_hours: u32,
+}
+
+impl Default for WatchlistSettings {
+    fn default() -> Self {
+        Self { recheck_hours: 24 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pin {
+    pub pinned_at: String,
+    pub pinned_by: Option<Actor>,
+    /// Last verification result; seeded from the newest stored verification
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    #[serde(default)]
+    pub checked_at: Option<String>,
+    /// Why the last re-check failed, cleared by the next successful one
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchedClaim {
+    pub hash: String,
+    /// None if the claim object can't be read any more
+    pub claim: Option<serde_json::Value>,
+    pub pin: Pin,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchlistChange {
+    pub hash: String,
+    pub claim: String,
+    pub previous_status: Option<String>,
+    pub status: String,
+    pub previous_confidence: Option<f64>,
+    pub confidence: f64,
+    pub checked_at: String,
+}
+
+/// Clears the running flag however the re-check ends
+struct RecheckGuard;
+
+impl RecheckGuard {
+    fn acquire() -> Result<Self, String> {
+        if RECHECK_RUNNING.swap(true, Ordering::SeqCst) {
+            return Err("Pinned claims are already being re-checked".to_string());
+        }
+        Ok(RecheckGuard)
+    }
+}
+
+impl Drop for RecheckGuard {
+    fn drop(&mut self) {
+        RECHECK_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+fn watchlist_path() -> Result<PathBuf, String> {
+    let truth_path = repo_state::ready_path()?;
+    Ok(truth_path.join("watchlist.json"))
+}
+
+fn load_pins() -> Result<BTreeMap<String, Pin>, String> {
+    let path = watchlist_path()?;
+    let _guard = WATCHLIST_LOCK.lock(&path)?;
+    load_json(&path)
+}
+
+fn update_pins<T>(change: impl FnOnce(&mut BTreeMap<String, Pin>) -> Result<T, String>) -> Result<T, String> {
+    let path = watchlist_path()?;
+    let _guard = WATCHLIST_LOCK.lock(&path)?;
+    let mut pins = load_json(&path)?;
+    let result = change(&mut pins)?;
+    save_json(&path, &pins)?;
+    Ok(result)
+}
+
+/// Whether a re-check moved the claim enough to tell the user
+fn changed(pin: &Pin, result: &GovernanceResult) -> bool {
+    let status_changed = pin
+        .status
+        .as_deref()
+        .is_some_and(|s| !s.eq_ignore_ascii_case(&result.status));
+    let confidence_changed = pin
+        .confidence
+        .is_some_and(|c| (c - result.confidence).abs() >= CONFIDENCE_CHANGE);
+    status_changed || confidence_changed
+}
+
+fn is_due(pin: &Pin, now: chrono::DateTime<chrono::Utc>, recheck_hours: u32) -> bool {
+    match pin.checked_at.as_deref().map(chrono::DateTime::parse_from_rfc3339) {
+        Some(Ok(checked)) => now - checked.with_timezone(&chrono::Utc) >= chrono::Duration::hours(recheck_hours.into()),
+        _ => true,
+    }
+}
+
+fn field(claim: &serde_json::Value, name: &str) -> String {
+    claim
+        .get(name)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+/// Re-verify one pinned claim and record the outcome on its pin
+async fn recheck_claim(app: &AppHandle, hash: &str) -> Result<Option<WatchlistChange>, String> {
+    let claim = {
+        let hash = hash.to_string();
+        run_blocking(move || read_claim(hash)).await?
+    };
+    let content = field(&claim, "content");
+    if content.is_empty() {
+        return Err("Claim has no content to verify".to_string());
+    }
+    let mut risk_profile = field(&claim, "risk_profile");
+    if risk_profile.is_empty() {
+        risk_profile = SETTINGS
+            .read()
+            .map(|s| s.default_risk_profile.clone())
+            .unwrap_or_default();
+    }
+    let checked_at = chrono::Utc::now().to_rfc3339();
+    let outcome = crate::run_governance_verify(content.clone(), field(&claim, "domain"), risk_profile, None).await;
+
+    let hash = hash.to_string();
+    run_blocking(move || {
+        update_pins(|pins| {
+            let Some(pin) = pins.get_mut(&hash) else {
+                return Ok(None); // unpinned while it was being verified
+            };
+            pin.checked_at = Some(checked_at.clone());
+            let result = match outcome {
+                Ok(result) => result,
+                Err(e) => {
+                    pin.error = Some(e);
+                    return Ok(None);
+                }
+            };
+            let change = changed(pin, &result).then(|| WatchlistChange {
+                hash: hash.clone(),
+                claim: content,
+                previous_status: pin.status.clone(),
+                status: result.status.clone(),
+                previous_confidence: pin.confidence,
+                confidence: result.confidence,
+                checked_at,
+            });
+            pin.status = Some(result.status);
+            pin.confidence = Some(result.confidence);
+            pin.error = None;
+            Ok(change)
+        })
+    })
+    .await
+    .inspect(|change| {
+        if let Some(change) = change {
+            announce(app, change);
+        }
+    })
+}
+
+fn announce(app: &AppHandle, change: &WatchlistChange) {
+    if let Err(e) = app.emit(WATCHLIST_CHANGED_EVENT, change) {
+        log::warn!("Failed to emit {}: {}", WATCHLIST_CHANGED_EVENT, e);
+    }
+    let previous = change.previous_status.as_deref().unwrap_or("unverified");
+    notifications::notify(
+        NotificationKind::WatchlistChange,
+        &format!("Pinned claim changed: {} → {}", previous, change.status),
+        &format!(
+            "{} ({}% confidence)",
+            notifications::excerpt(&change.claim),
+            (change.confidence * 100.0).round()
+        ),
+    );
+}
+
+/// Re-verify `hashes` in work-queue order, reporting progress on `task`
+async fn recheck(app: &AppHandle, task: &TaskHandle, hashes: Vec<String>) -> Result<Vec<WatchlistChange>, String> {
+    let _guard = RecheckGuard::acquire()?;
+    let total = hashes.len() as u64;
+    let queued = hashes
+        .into_iter()
+        .map(|hash| NewItem {
+            claim: String::new(),
+            claim_hash: Some(hash),
+            domain: String::new(),
+            risk_profile: String::new(),
+        })
+        .collect();
+    let _queue = work_queue::enqueue(task, QueueSource::Watchlist, queued)?;
+    let mut changes = Vec::new();
+    let mut done = 0;
+    while let Some(item) = work_queue::next(task).await? {
+        let hash = item.claim_hash.unwrap_or_default();
+        match recheck_claim(app, &hash).await {
+            Ok(change) => changes.extend(change),
+            Err(e) => log::warn!("Re-check of pinned claim {} failed: {}", hash, e),
+        }
+        done += 1;
+        task.progress(done, total, format!("Re-checked {} of {} pinned claims", done, total));
+    }
+    task.check_cancelled()?;
+    Ok(changes)
+}
+
+/// Start the background loop that re-verifies pins as they come due
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCHEDULER_TICK).await;
+            let recheck_hours = SETTINGS.read().map(|s| s.watchlist.recheck_hours).unwrap_or(0);
+            if recheck_hours == 0 || mock::is_active() || RECHECK_RUNNING.load(Ordering::SeqCst) {
+                continue;
+            }
+            let now = chrono::Utc::now();
+            let due: Vec<String> = match run_blocking(load_pins).await {
+                Ok(pins) => pins
+                    .into_iter()
+                    .filter(|(_, pin)| is_due(pin, now, recheck_hours))
+                    .map(|(hash, _)| hash)
+                    .collect(),
+                Err(e) => {
+                    log::warn!("Failed to read watchlist: {}", e);
+                    continue;
+                }
+            };
+            if due.is_empty() {
+                continue;
+            }
+            let handle = app.clone();
+            let result = tasks::run(
+                &app,
+                None,
+                "watchlist",
+                "Re-checking pinned claims",
+                |task| async move { recheck(&handle, &task, due).await },
+            )
+            .await;
+            if let Err(e) = result {
+                log::warn!("Scheduled re-check of pinned claims stopped: {}", e);
+            }
+        }
+    });
+}
+
+/// Pin a claim, seeding its last result from the newest stored verification
+#[tauri::command]
+pub async fn pin_claim(hash: String) -> Result<WatchedClaim, String> {
+    if mock::is_active() {
+        return Err("The watchlist is disabled in mock mode".to_string());
+    }
+    let hash = hash.trim().to_lowercase();
+    validate_hash(&hash)?;
+
+    run_blocking(move || {
+        let claim = read_claim(hash.clone())?;
+        let latest = read_verifications()?
+            .verifications
+            .into_iter()
+            .find(|v| v.claim_hash == hash);
+        let status = latest
+            .as_ref()
+            .map(|v| v.verdict.clone())
+            .filter(|s| !s.is_empty());
+        let confidence = latest
+            .as_ref()
+            .and_then(|v| v.confidence)
+            .or_else(|| claim.get("confidence").and_then(|c| c.as_f64()));
+
+        let (pin, created) = update_pins(|pins| {
+            if let Some(pin) = pins.get(&hash) {
+                return Ok((pin.clone(), false));
+            }
+            if pins.len() >= MAX_PINNED {
+                return Err(format!("The watchlist is full ({} claims)", MAX_PINNED));
+            }
+            let pin = Pin {
+                pinned_at: chrono::Utc::now().to_rfc3339(),
+                pinned_by: identity::current(),
+                status,
+                confidence,
+                checked_at: latest.as_ref().map(|v| v.timestamp.clone()),
+                error: None,
+            };
+            pins.insert(hash.clone(), pin.clone());
+            Ok((pin, true))
+        })?;
+        if created {
+            journal::record::<Pin>(Store::Watchlist, &hash, format!("Pinned claim {}", short_hash(&hash)), None);
+        }
+        log::info!("Pinned claim {}", hash);
+        Ok(WatchedClaim {
+            hash,
+            claim: Some(claim),
+            pin,
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn unpin_claim(hash: String) -> Result<bool, String> {
+    let hash = hash.trim().to_lowercase();
+    validate_hash(&hash)?;
+    run_blocking(move || {
+        let removed = update_pins(|pins| Ok(pins.remove(&hash)))?;
+        if let Some(pin) = &removed {
+            journal::record(Store::Watchlist, &hash, format!("Unpinned claim {}", short_hash(&hash)), Some(pin));
+        }
+        Ok(removed.is_some())
+    })
+    .await
+}
+
+/// Put a pin back as it was (undo)
+pub(crate) fn restore(hash: &str, before: Option<serde_json::Value>) -> Result<(), String> {
+    update_pins(|pins| journal::restore_entry(pins, hash, before))
+}
+
+/// Pinned claims, most recently pinned first
+#[tauri::command]
+pub async fn list_watchlist() -> Result<Vec<WatchedClaim>, String> {
+    if mock::is_active() {
+        return Ok(Vec::new());
+    }
+    run_blocking(|| {
+        let mut watched: Vec<WatchedClaim> = load_pins()?
+            .into_iter()
+            .map(|(hash, pin)| WatchedClaim {
+                claim: read_claim(hash.clone()).ok(),
+                hash,
+                pin,
+            })
+            .collect();
+        watched.sort_by(|a, b| b.pin.pinned_at.cmp(&a.pin.pinned_at));
+        Ok(watched)
+    })
+    .await
+}
+
+/// Re-verify every pinned claim now, whether or not it is due
+#[tauri::command]
+pub async fn recheck_watchlist(app: AppHandle, task_id: Option<String>) -> Result<Vec<WatchlistChange>, String> {
+    if mock::is_active() {
+        return Err("The watchlist is disabled in mock mode".to_string());
+    }
+    let hashes: Vec<String> = run_blocking(load_pins).await?.into_keys().collect();
+    let handle = app.clone();
+    tasks::run(
+        &app,
+        task_id,
+        "watchlist",
+        "Re-checking pinned claims",
+        |task| async move { recheck(&handle, &task, hashes).await },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(status: &str, confidence: f64) -> GovernanceResult {
+        GovernanceResult {
+            status: status.to_string(),
+            action: "proceed".to_string(),
+            confidence,
+            reason: String::new(),
+            audit_ref: String::new(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_changed() {
+        let pin = Pin {
+            status: Some("PASSED".to_string()),
+            confidence: Some(0.9),
+            ..Default::default()
+        };
+        assert!(!changed(&pin, &result("passed", 0.88)));
+        assert!(changed(&pin, &result("FAILED", 0.9)));
+        assert!(changed(&pin, &result("PASSED", 0.8)));
+        assert!(!changed(&Pin::default(), &result("FAILED", 0.1)));
+    }
+
+    #[test]
+    fn test_is_due() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-02T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let checked = |at: &str| Pin {
+            checked_at: Some(at.to_string()),
+            ..Default::default()
+        };
+        assert!(is_due(&Pin::default(), now, 24));
+        assert!(is_due(&checked("2026-03-01T12:00:00Z"), now, 24));
+        assert!(!is_due(&checked("2026-03-01T13:00:00Z"), now, 24));
+        assert!(is_due(&checked("not a date"), now, 24));
+    }
+}
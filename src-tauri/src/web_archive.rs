@@ -0,0 +1,290 @@
+// ==================== WEB ARCHIVE ====================
+//
+// Snapshots of cited web pages, so a source still says what it said when a
+// claim was checked after the page moves or changes. `archive_url` fetches
+// the page, keeps its readable text (scripts, styles and markup dropped) and
+// stores it as a content-hashed blob next to evidence files. The returned
+// hash goes into a claim source's `archived_hash`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::evidence::store_blob;
+use crate::truth_store::{inflate_object, ObjectId, ObjectKind, TruthStore};
+use crate::{consent, http_client, mock, objects, repo_state, run_blocking};
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_PAGE_SIZE: usize = 5 * 1024 * 1024;
+const MAX_URL_LEN: usize = 2048;
+
+/// Elements whose content is never readable text
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style", "noscript", "template", "svg", "iframe"];
+/// Elements that start a new line of text
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p",
+    "br",
+    "div",
+    "li",
+    "ul",
+    "ol",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "blockquote",
+    "pre",
+    "table",
+    "dt",
+    "dd",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageSnapshot {
+    /// URL as requested
+    pub url: String,
+    /// URL after redirects
+    pub final_url: String,
+    pub title: Option<String>,
+    pub fetched_at: String,
+    pub content_type: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchivedUrl {
+    /// SHA-256 of the stored snapshot
+    pub hash: String,
+    pub url: String,
+    pub final_url: String,
+    pub title: Option<String>,
+    pub fetched_at: String,
+    /// Characters of readable text kept
+    pub text_length: usize,
+}
+
+/// Decode the handful of entities that matter for readable text
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let Some(end) = rest[..rest.len().min(12)].find(';') else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Lowercase element name of a tag body ("/P class=x" -> ("p", true))
+fn tag_name(tag: &str) -> (String, bool) {
+    let closing = tag.starts_with('/');
+    let name = tag
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    (name, closing)
+}
+
+/// The page title and its readable text, one block element per line
+pub fn readable_text(html: &str) -> (Option<String>, String) {
+    let mut text = String::new();
+    let mut title: Option<String> = None;
+    let mut skipping: Option<String> = None;
+    let mut in_title = false;
+    let mut rest = html;
+
+    while let Some(open) = rest.find('<') {
+        let chunk = &rest[..open];
+        if skipping.is_none() {
+            if in_title {
+                title.get_or_insert_with(String::new).push_str(chunk);
+            } else {
+                text.push_str(chunk);
+            }
+        }
+        rest = &rest[open + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let (name, closing) = tag_name(&rest[..close]);
+        rest = &rest[close + 1..];
+
+        if let Some(skipped) = &skipping {
+            if closing && *skipped == name {
+                skipping = None;
+            }
+            continue;
+        }
+        if SKIPPED_ELEMENTS.contains(&name.as_str()) && !closing {
+            skipping = Some(name);
+        } else if name == "title" {
+            in_title = !closing;
+        } else if BLOCK_ELEMENTS.contains(&name.as_str()) {
+            text.push('\n');
+        }
+    }
+    if skipping.is_none() {
+        text.push_str(rest);
+    }
+
+    let lines: Vec<String> = decode_entities(&text)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect();
+    let title = title
+        .map(|t| decode_entities(&t).split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|t| !t.is_empty());
+    (title, lines.join("\n"))
+}
+
+async fn fetch_page(url: &reqwest::Url) -> Result<PageSnapshot, String> {
+    consent::require_host(url.as_str()).await?;
+    let fetched = http_client::fetch_allowed(url.as_str(), "page", FETCH_TIMEOUT, MAX_PAGE_SIZE).await?;
+    let final_url = fetched.final_url;
+    let content_type = if fetched.content_type.is_empty() {
+        "text/html".to_string()
+    } else {
+        fetched.content_type
+    };
+
+    let body = String::from_utf8_lossy(&fetched.bytes);
+    let (title, text) = if content_type.starts_with("text/html") || content_type.contains("xhtml") {
+        readable_text(&body)
+    } else if content_type.starts_with("text/plain") {
+        (None, body.trim().to_string())
+    } else {
+        return Err(format!(
+            "Only HTML and plain text pages can be archived (got {})",
+            content_type
+        ));
+    };
+    if text.is_empty() {
+        return Err("Page has no readable text".to_string());
+    }
+    Ok(PageSnapshot {
+        url: url.to_string(),
+        final_url,
+        title,
+        fetched_at: chrono::Utc::now().to_rfc3339(),
+        content_type,
+        text,
+    })
+}
+
+/// Fetch a web page and store a snapshot of its readable text in the repo
+#[tauri::command]
+pub async fn archive_url(url: String) -> Result<ArchivedUrl, String> {
+    if mock::is_active() {
+        return Err("Archiving is disabled in mock mode".to_string());
+    }
+    let url = url.trim();
+    if url.len() > MAX_URL_LEN {
+        return Err(format!("URL is too long (max {} characters)", MAX_URL_LEN));
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Only http(s) pages can be archived".to_string());
+    }
+    let snapshot = fetch_page(&parsed).await?;
+
+    run_blocking(move || {
+        let truth_path = repo_state::ready_path()?;
+        let bytes = serde_json::to_vec(&snapshot).map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+        let hash = store_blob(&truth_path, &bytes)?;
+        log::info!("Archived {} as {}", snapshot.url, hash);
+        Ok(ArchivedUrl {
+            hash,
+            url: snapshot.url,
+            final_url: snapshot.final_url,
+            title: snapshot.title,
+            fetched_at: snapshot.fetched_at,
+            text_length: snapshot.text.chars().count(),
+        })
+    })
+    .await
+}
+
+/// A snapshot stored by `archive_url`
+#[tauri::command]
+pub async fn get_archived_url(hash: String) -> Result<PageSnapshot, String> {
+    let hash = hash.trim().to_lowercase();
+    let id = ObjectId::parse(&hash)
+        .ok()
+        .filter(|_| hash.len() == 64)
+        .ok_or("Invalid snapshot hash")?;
+    run_blocking(move || {
+        let path = TruthStore::active()?.object_path(ObjectKind::Evidence, &id);
+        if !path.exists() {
+            return Err(format!("Snapshot not found: {}", hash));
+        }
+        let bytes = inflate_object(&path, MAX_PAGE_SIZE * 2)?;
+        let (_, body) = objects::split_header(&bytes);
+        serde_json::from_slice(body).map_err(|_| format!("{} is not a web page snapshot", hash))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readable_text() {
+        let html = r#"<!DOCTYPE html><html><head><title> Boiling &amp; pressure </title>
+            <style>p { color: red }</style><script>var x = "<p>";</script></head>
+            <body><!-- nav <p>hidden</p> --><h1>Water</h1><p>Boils at   100&#176;C
+            at sea level.</p><NOSCRIPT>Enable JS</NOSCRIPT><ul><li>1 atm</li><li>&lt;2 km</li></ul></body></html>"#;
+        let (title, text) = readable_text(html);
+        assert_eq!(title.as_deref(), Some("Boiling & pressure"));
+        assert_eq!(text, "Water\nBoils at 100°C\nat sea level.\n1 atm\n<2 km");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &amp;&#x41;&#66; &bogus; & b"), "a &AB &bogus; & b");
+    }
+}
@@ -0,0 +1,243 @@
+// ==================== WORK QUEUE ====================
+//
+// Verification work that is waiting to run: the claims of a batch and the
+// pinned claims a watchlist re-check hasn't got to yet. A task enqueues its
+// items up front and takes them back one at a time with `next`, which hands
+// out the highest-priority item that isn't paused, oldest first among equals.
+// `get_task_queue` lists what is waiting, and the user can reprioritize,
+// pause or drop items while their task runs. A task whose remaining items
+// are all paused waits until one is resumed or dropped, or the task is
+// cancelled. Nothing is queued across restarts or while the backend is
+// unreachable: a verification that can't run fails as it always did.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::tasks::TaskHandle;
+
+/// How often a task with only paused items left looks again
+const PAUSED_POLL: Duration = Duration::from_millis(500);
+const MAX_PRIORITY: i32 = 100;
+
+static QUEUE: std::sync::LazyLock<Mutex<Queue>> = std::sync::LazyLock::new(|| Mutex::new(Queue::default()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueSource {
+    Batch,
+    Watchlist,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueueItem {
+    pub id: String,
+    /// Task that will run the item, for task://progress and cancel_task
+    pub task_id: String,
+    pub source: QueueSource,
+    /// Claim text; empty for pinned claims, which are read when they run
+    pub claim: String,
+    pub claim_hash: Option<String>,
+    pub domain: String,
+    /// Empty for pinned claims, which use their own or the default
+    pub risk_profile: String,
+    /// Higher runs first; items start at 0
+    pub priority: i32,
+    pub paused: bool,
+    pub enqueued_at: String,
+    #[serde(skip)]
+    seq: u64,
+}
+
+/// What a task enqueues; see `QueueItem`
+pub struct NewItem {
+    pub claim: String,
+    pub claim_hash: Option<String>,
+    pub domain: String,
+    pub risk_profile: String,
+}
+
+#[derive(Default)]
+struct Queue {
+    items: Vec<QueueItem>,
+    next_seq: u64,
+}
+
+#[derive(Debug, PartialEq)]
+enum Next {
+    Item(QueueItem),
+    /// Items are left, but all of them are paused
+    Paused,
+    Empty,
+}
+
+/// Run order: runnable before paused, then by priority, then oldest first
+fn run_order(a: &QueueItem, b: &QueueItem) -> std::cmp::Ordering {
+    a.paused
+        .cmp(&b.paused)
+        .then(b.priority.cmp(&a.priority))
+        .then(a.seq.cmp(&b.seq))
+}
+
+impl Queue {
+    fn push(&mut self, task_id: &str, source: QueueSource, item: NewItem) {
+        self.next_seq += 1;
+        self.items.push(QueueItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            task_id: task_id.to_string(),
+            source,
+            claim: item.claim,
+            claim_hash: item.claim_hash,
+            domain: item.domain,
+            risk_profile: item.risk_profile,
+            priority: 0,
+            paused: false,
+            enqueued_at: chrono::Utc::now().to_rfc3339(),
+            seq: self.next_seq,
+        });
+    }
+
+    fn take_next(&mut self, task_id: &str) -> Next {
+        let next = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.task_id == task_id)
+            .min_by(|(_, a), (_, b)| run_order(a, b))
+            .map(|(i, item)| (i, item.paused));
+        match next {
+            None => Next::Empty,
+            Some((_, true)) => Next::Paused,
+            Some((i, false)) => Next::Item(self.items.remove(i)),
+        }
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut QueueItem, String> {
+        self.items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .ok_or_else(|| "No such item in the queue; it may already have run".to_string())
+    }
+}
+
+/// Removes whatever the task left in the queue however it ends
+pub struct QueueGuard {
+    task_id: String,
+}
+
+impl Drop for QueueGuard {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = QUEUE.lock() {
+            queue.items.retain(|item| item.task_id != self.task_id);
+        }
+    }
+}
+
+/// Queue `items` for `task`; they stay listed until taken or the guard drops
+pub fn enqueue(task: &TaskHandle, source: QueueSource, items: Vec<NewItem>) -> Result<QueueGuard, String> {
+    let mut queue = QUEUE.lock().map_err(|e| format!("Lock error: {}", e))?;
+    for item in items {
+        queue.push(task.id(), source, item);
+    }
+    Ok(QueueGuard {
+        task_id: task.id().to_string(),
+    })
+}
+
+/// The task's next item to run, waiting while everything left is paused.
+/// None once its items are done or dropped, or the task is cancelled.
+pub async fn next(task: &TaskHandle) -> Result<Option<QueueItem>, String> {
+    loop {
+        if task.is_cancelled() {
+            return Ok(None);
+        }
+        let next = QUEUE
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?
+            .take_next(task.id());
+        match next {
+            Next::Item(item) => return Ok(Some(item)),
+            Next::Empty => return Ok(None),
+            Next::Paused => tokio::time::sleep(PAUSED_POLL).await,
+        }
+    }
+}
+
+/// Items waiting to run, in the order they will run within their task
+#[tauri::command]
+pub async fn get_task_queue() -> Result<Vec<QueueItem>, String> {
+    let queue = QUEUE.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut items = queue.items.clone();
+    items.sort_by(run_order);
+    Ok(items)
+}
+
+#[tauri::command]
+pub async fn reprioritize_queue_item(id: String, priority: i32) -> Result<QueueItem, String> {
+    if !(-MAX_PRIORITY..=MAX_PRIORITY).contains(&priority) {
+        return Err(format!("Priority must be between -{} and {}", MAX_PRIORITY, MAX_PRIORITY));
+    }
+    let mut queue = QUEUE.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let item = queue.get_mut(&id)?;
+    item.priority = priority;
+    Ok(item.clone())
+}
+
+/// Hold an item back without dropping it, or let it run again
+#[tauri::command]
+pub async fn set_queue_item_paused(id: String, paused: bool) -> Result<QueueItem, String> {
+    let mut queue = QUEUE.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let item = queue.get_mut(&id)?;
+    item.paused = paused;
+    Ok(item.clone())
+}
+
+/// Take an item out of the queue. Returns false if it isn't queued any more.
+#[tauri::command]
+pub async fn drop_queue_item(id: String) -> Result<bool, String> {
+    let mut queue = QUEUE.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let before = queue.items.len();
+    queue.items.retain(|item| item.id != id);
+    let dropped = queue.items.len() < before;
+    if dropped {
+        log::info!("Dropped queue item {}", id);
+    }
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(text: &str) -> NewItem {
+        NewItem {
+            claim: text.to_string(),
+            claim_hash: None,
+            domain: "general".to_string(),
+            risk_profile: "medium".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_take_next_follows_priority_and_pause() {
+        let mut queue = Queue::default();
+        for text in ["a", "b", "c"] {
+            queue.push("t1", QueueSource::Batch, claim(text));
+        }
+        queue.push("t2", QueueSource::Watchlist, claim("other task"));
+        queue.items[2].priority = 5;
+        queue.items[0].paused = true;
+
+        let mut taken = Vec::new();
+        while let Next::Item(item) = queue.take_next("t1") {
+            taken.push(item.claim);
+        }
+        assert_eq!(taken, ["c", "b"]);
+        assert_eq!(queue.take_next("t1"), Next::Paused);
+
+        queue.get_mut(&queue.items[0].id.clone()).unwrap().paused = false;
+        assert!(matches!(queue.take_next("t1"), Next::Item(item) if item.claim == "a"));
+        assert_eq!(queue.take_next("t1"), Next::Empty);
+        assert_eq!(queue.items.len(), 1);
+    }
+}
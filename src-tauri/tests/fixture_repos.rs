@@ -0,0 +1,141 @@
+//! Commands run against generated truth repos and vaults. Needs the
+//! `test-support` feature: `cargo test --features test-support`.
+
+use app_lib::test_support::{self as ts, RepoBuilder, RepoState, VaultBuilder};
+use app_lib::AuditEntry;
+use std::fs;
+
+#[test]
+fn lists_and_reads_claims() {
+    let repo = RepoBuilder::new().claims(25).build();
+    let vault = VaultBuilder::new().build();
+    let _active = ts::activate(&repo.path(), vault.path());
+
+    let listing = ts::list_claims(None).unwrap();
+    assert_eq!(listing.claims.len(), 25);
+    assert!(listing.warnings.is_empty());
+    // Newest first
+    assert_eq!(listing.claims[0]["$hash"], repo.claims[24].as_str());
+    assert_eq!(listing.claims[24]["$hash"], repo.claims[0].as_str());
+
+    let claim = ts::get_claim(&repo.claims[3]).unwrap();
+    assert_eq!(claim["content"], ts::claim_text(3));
+    assert_eq!(claim["domain"], ts::DOMAINS[3]);
+    assert!(ts::get_claim("../../etc/passwd").is_err());
+    assert!(ts::get_claim(&"0".repeat(64)).is_err());
+}
+
+#[test]
+fn lists_verifications_of_claims() {
+    let repo = RepoBuilder::new().claims(10).verifications(12).build();
+    let vault = VaultBuilder::new().build();
+    let _active = ts::activate(&repo.path(), vault.path());
+
+    let listing = ts::list_verifications().unwrap();
+    assert_eq!(listing.verifications.len(), 12);
+    assert!(listing.warnings.is_empty());
+    assert!(listing
+        .verifications
+        .windows(2)
+        .all(|pair| pair[0].timestamp >= pair[1].timestamp));
+    for verification in &listing.verifications {
+        assert!(repo.claims.contains(&verification.claim_hash));
+        assert!(repo.verifications.contains(&verification.hash));
+        assert_eq!(verification.validators.len(), 2);
+    }
+}
+
+#[test]
+fn appends_to_the_audit_trail_but_not_a_damaged_one() {
+    let repo = RepoBuilder::new().claims(3).audit_entries(5).build();
+    let vault = VaultBuilder::new().build();
+    let _active = ts::activate(&repo.path(), vault.path());
+
+    assert_eq!(ts::get_audit_trail().unwrap().len(), 5);
+    let id = ts::add_audit_entry(AuditEntry {
+        action: "note".to_string(),
+        claim: ts::claim_text(1),
+        ..Default::default()
+    })
+    .unwrap();
+    let trail = ts::get_audit_trail().unwrap();
+    assert_eq!(trail.len(), 6);
+    assert_eq!(trail[0].id, id);
+
+    // Actions the app records itself can't be forged
+    let forged = AuditEntry {
+        action: "governance_verify".to_string(),
+        claim: ts::claim_text(1),
+        ..Default::default()
+    };
+    assert!(ts::add_audit_entry(forged).is_err());
+
+    let audit_file = repo.path().join("audit.json");
+    fs::write(&audit_file, "[{\"id\": ").unwrap();
+    let error = ts::get_audit_trail().unwrap_err();
+    assert!(error.contains("parse"), "{}", error);
+    let note = AuditEntry {
+        action: "note".to_string(),
+        claim: ts::claim_text(2),
+        ..Default::default()
+    };
+    let error = ts::add_audit_entry(note).unwrap_err();
+    assert!(error.starts_with("The truth repository is damaged"), "{}", error);
+    assert_eq!(fs::read_to_string(&audit_file).unwrap(), "[{\"id\": ");
+    assert_eq!(ts::get_repo_state().unwrap().state, RepoState::Corrupt);
+
+    ts::write_audit(&repo.path(), &[]);
+    assert_eq!(ts::get_repo_state().unwrap().state, RepoState::Ready);
+    assert!(ts::get_audit_trail().unwrap().is_empty());
+}
+
+#[test]
+fn commands_report_a_missing_or_uninitialized_repo() {
+    let dir = ts::TempDir::new("truthgit-missing");
+    let repo = dir.path().join(".truth");
+    let vault = VaultBuilder::new().build();
+    let _active = ts::activate(&repo, vault.path());
+
+    let status = ts::get_repo_state().unwrap();
+    assert_eq!(status.state, RepoState::Missing);
+    let error = ts::list_claims(None).unwrap_err();
+    assert!(error.starts_with("The truth repository is missing"), "{}", error);
+    assert!(ts::list_verifications()
+        .unwrap_err()
+        .starts_with("The truth repository is missing"));
+
+    fs::create_dir_all(&repo).unwrap();
+    assert_eq!(ts::get_repo_state().unwrap().state, RepoState::Uninitialized);
+    assert!(ts::get_audit_trail()
+        .unwrap_err()
+        .starts_with("The truth repository is not initialized"));
+
+    fs::create_dir_all(repo.join("objects")).unwrap();
+    assert_eq!(ts::get_repo_state().unwrap().state, RepoState::Ready);
+    assert!(ts::list_claims(None).unwrap().claims.is_empty());
+}
+
+#[test]
+fn searches_and_reads_notes() {
+    let repo = RepoBuilder::new().build();
+    let vault = VaultBuilder::new().notes(30).folders(3).build();
+    let _active = ts::activate(&repo.path(), vault.path());
+
+    // Notes 0, 7, 14, 21 and 28 are about the first topic
+    let results = ts::search_notes(ts::TOPICS[0]).unwrap();
+    let mut found: Vec<&str> = results.iter().map(|r| r.path.as_str()).collect();
+    found.sort_unstable();
+    let mut expected: Vec<&str> = (0..30)
+        .step_by(ts::TOPICS.len())
+        .map(|i| vault.notes[i].as_str())
+        .collect();
+    expected.sort_unstable();
+    assert_eq!(found, expected);
+    assert!(ts::search_notes("nothing-mentions-this").unwrap().is_empty());
+
+    let note = ts::read_note(&vault.notes[7]).unwrap();
+    assert_eq!(note.name, "note-7");
+    assert!(note.content.contains("[[note-6]]"));
+    assert!(!note.encrypted);
+    assert!(ts::read_note("../outside.md").is_err());
+}
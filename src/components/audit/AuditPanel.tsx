@@ -11,6 +11,10 @@ import {
   Clock,
   Shield,
   Search,
+  Stamp,
+  Paperclip,
+  Link,
+  FileText,
 } from 'lucide-react';
 
 interface AuditEntry {
@@ -23,6 +27,30 @@ interface AuditEntry {
   result_status: string;
   result_action: string;
   confidence: number;
+  actor: { name: string; email: string | null; key_fingerprint: string | null } | null;
+  source?: string | null;
+  claim_hash?: string | null;
+  verification_hash?: string | null;
+  backend?: string | null;
+  app_version?: string | null;
+  duration_ms?: number | null;
+}
+
+export interface Evidence {
+  id: string;
+  audit_id: string;
+  kind: 'file' | 'url';
+  name: string;
+  hash: string | null;
+  size: number | null;
+  attached_at: string;
+}
+
+interface ReceiptCheck {
+  valid: boolean;
+  signed_by_me: boolean;
+  message: string;
+  receipt: { audit_id: string; verdict: string; confidence: number; timestamp: string; signer: string | null };
 }
 
 const ACTION_ICONS: Record<string, React.ReactNode> = {
@@ -51,6 +79,14 @@ export function AuditPanel() {
   const [error, setError] = useState<string | null>(null);
   const [searchQuery, setSearchQuery] = useState('');
   const [filterAction, setFilterAction] = useState<string>('all');
+  const [copiedReceipt, setCopiedReceipt] = useState<string | null>(null);
+  const [receiptInput, setReceiptInput] = useState('');
+  const [receiptCheck, setReceiptCheck] = useState<ReceiptCheck | null>(null);
+  const [receiptError, setReceiptError] = useState<string | null>(null);
+  const [evidenceFor, setEvidenceFor] = useState<string | null>(null);
+  const [evidence, setEvidence] = useState<Evidence[]>([]);
+  const [evidenceInput, setEvidenceInput] = useState('');
+  const [evidenceError, setEvidenceError] = useState<string | null>(null);
 
   const loadAuditTrail = useCallback(async () => {
     setLoading(true);
@@ -69,6 +105,55 @@ export function AuditPanel() {
     loadAuditTrail();
   }, [loadAuditTrail]);
 
+  const handleCopyReceipt = async (auditId: string) => {
+    setReceiptError(null);
+    try {
+      const { encoded } = await invoke<{ encoded: string }>('generate_receipt', { auditId });
+      await navigator.clipboard.writeText(encoded);
+      setCopiedReceipt(auditId);
+      setTimeout(() => setCopiedReceipt(null), 2000);
+    } catch (err) {
+      setReceiptError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleToggleEvidence = async (auditId: string) => {
+    setEvidenceInput('');
+    setEvidenceError(null);
+    if (evidenceFor === auditId) {
+      setEvidenceFor(null);
+      return;
+    }
+    setEvidenceFor(auditId);
+    setEvidence([]);
+    try {
+      setEvidence(await invoke<Evidence[]>('list_evidence', { auditId }));
+    } catch (err) {
+      setEvidenceError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleAttachEvidence = async (auditId: string) => {
+    setEvidenceError(null);
+    try {
+      const attached = await invoke<Evidence>('attach_evidence', { auditId, fileOrUrl: evidenceInput });
+      setEvidence((prev) => (prev.some((e) => e.id === attached.id) ? prev : [...prev, attached]));
+      setEvidenceInput('');
+    } catch (err) {
+      setEvidenceError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleVerifyReceipt = async () => {
+    setReceiptCheck(null);
+    setReceiptError(null);
+    try {
+      setReceiptCheck(await invoke<ReceiptCheck>('verify_receipt', { receipt: receiptInput }));
+    } catch (err) {
+      setReceiptError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
   const filteredEntries = entries.filter((entry) => {
     if (filterAction !== 'all' && entry.result_action !== filterAction) {
       return false;
@@ -210,6 +295,39 @@ export function AuditPanel() {
         />
       </div>
 
+      {/* Receipt check */}
+      <div className="space-y-2">
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={receiptInput}
+            onChange={(e) => setReceiptInput(e.target.value)}
+            placeholder="Paste a receipt (tgr1.…) to verify it"
+            className="flex-1 px-4 py-2 bg-zinc-900 border border-zinc-800 rounded-lg text-zinc-100 placeholder-zinc-500 font-mono text-sm focus:outline-none focus:border-zinc-700"
+          />
+          <button
+            onClick={handleVerifyReceipt}
+            disabled={!receiptInput.trim()}
+            className="px-4 py-2 bg-zinc-800 hover:bg-zinc-700 rounded-lg text-zinc-300 text-sm transition-colors disabled:opacity-50"
+          >
+            Verify
+          </button>
+        </div>
+        {receiptCheck && (
+          <p className={`text-xs ${receiptCheck.valid ? 'text-green-400' : 'text-red-400'}`}>
+            {receiptCheck.message}
+            {receiptCheck.valid && (
+              <span className="text-zinc-400">
+                {' '}· {receiptCheck.receipt.verdict} at {Math.round(receiptCheck.receipt.confidence * 100)}%
+                {receiptCheck.receipt.signer && ` · issued by ${receiptCheck.receipt.signer}`}
+                {receiptCheck.signed_by_me && ' (this desktop)'}
+              </span>
+            )}
+          </p>
+        )}
+        {receiptError && <p className="text-xs text-red-400">{receiptError}</p>}
+      </div>
+
       {/* Timeline */}
       {loading ? (
         <div className="space-y-4">
@@ -303,6 +421,31 @@ export function AuditPanel() {
                           <span className="text-xs text-zinc-500">
                             {Math.round(entry.confidence * 100)}% confidence
                           </span>
+
+                          {entry.actor && (
+                            <span
+                              className="text-xs text-zinc-500"
+                              title={[entry.actor.email, entry.actor.key_fingerprint].filter(Boolean).join(' · ')}
+                            >
+                              by {entry.actor.name}
+                            </span>
+                          )}
+
+                          {entry.source && (
+                            <span className="text-xs px-2 py-0.5 rounded-full bg-zinc-800 text-zinc-400">
+                              via {entry.source}
+                            </span>
+                          )}
+
+                          {entry.backend && (
+                            <span
+                              className="text-xs text-zinc-500"
+                              title={entry.app_version ? `TruthGit Desktop ${entry.app_version}` : undefined}
+                            >
+                              {entry.backend}
+                              {entry.duration_ms != null && ` · ${(entry.duration_ms / 1000).toFixed(1)}s`}
+                            </span>
+                          )}
                         </div>
                       </div>
 
@@ -318,8 +461,31 @@ export function AuditPanel() {
                     </div>
 
                     <div className="mt-3 pt-3 border-t border-zinc-800 flex items-center justify-between text-xs">
-                      <span className="text-zinc-500 font-mono">
-                        {entry.id}
+                      <span className="flex items-center gap-2 text-zinc-500 font-mono">
+                        <span
+                          title={[
+                            entry.claim_hash && `claim ${entry.claim_hash}`,
+                            entry.verification_hash && `verification ${entry.verification_hash}`,
+                          ].filter(Boolean).join('\n') || undefined}
+                        >
+                          {entry.id}
+                        </span>
+                        <button
+                          onClick={() => handleCopyReceipt(entry.id)}
+                          title="Copy a signed receipt for this verdict"
+                          className="flex items-center gap-1 font-sans text-zinc-500 hover:text-zinc-300 transition-colors"
+                        >
+                          <Stamp className="w-3 h-3" />
+                          {copiedReceipt === entry.id ? 'Copied' : 'Receipt'}
+                        </button>
+                        <button
+                          onClick={() => handleToggleEvidence(entry.id)}
+                          title="Supporting files and links for this verdict"
+                          className="flex items-center gap-1 font-sans text-zinc-500 hover:text-zinc-300 transition-colors"
+                        >
+                          <Paperclip className="w-3 h-3" />
+                          Evidence
+                        </button>
                       </span>
                       <span className={`${
                         entry.result_status === 'PASSED'
@@ -333,6 +499,41 @@ export function AuditPanel() {
                         {entry.result_status}
                       </span>
                     </div>
+
+                    {evidenceFor === entry.id && (
+                      <div className="mt-3 space-y-2 text-xs">
+                        {evidence.map((item) => (
+                          <div key={item.id} className="flex items-center gap-2 text-zinc-400">
+                            {item.kind === 'url' ? <Link className="w-3 h-3" /> : <FileText className="w-3 h-3" />}
+                            {item.kind === 'url' ? (
+                              <a href={item.name} target="_blank" rel="noreferrer" className="truncate hover:text-zinc-200">
+                                {item.name}
+                              </a>
+                            ) : (
+                              <span className="truncate" title={item.hash ?? undefined}>{item.name}</span>
+                            )}
+                            <span className="text-zinc-600 shrink-0">{formatDate(item.attached_at)}</span>
+                          </div>
+                        ))}
+                        {evidence.length === 0 && <p className="text-zinc-600">No evidence attached</p>}
+                        <div className="flex gap-2">
+                          <input
+                            value={evidenceInput}
+                            onChange={(e) => setEvidenceInput(e.target.value)}
+                            placeholder="File path or https:// link"
+                            className="flex-1 px-2 py-1 bg-zinc-800 border border-zinc-700 rounded text-zinc-200 placeholder-zinc-500"
+                          />
+                          <button
+                            onClick={() => handleAttachEvidence(entry.id)}
+                            disabled={!evidenceInput.trim()}
+                            className="px-2 py-1 bg-zinc-800 hover:bg-zinc-700 rounded text-zinc-300 disabled:opacity-50"
+                          >
+                            Attach
+                          </button>
+                        </div>
+                        {evidenceError && <p className="text-red-400">{evidenceError}</p>}
+                      </div>
+                    )}
                   </div>
                 </motion.div>
               ))}
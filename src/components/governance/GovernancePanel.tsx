@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { motion } from 'framer-motion';
 import {
   CheckCircle,
@@ -6,6 +6,8 @@ import {
   AlertTriangle,
   Loader2,
   RefreshCw,
+  RotateCcw,
+  History,
 } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 
@@ -19,8 +21,65 @@ interface GovernanceResult {
   reason: string;
   audit_ref: string;
   ontological_type: string | null;
+  actor: { name: string; email: string | null; key_fingerprint: string | null } | null;
 }
 
+interface RecentVerification {
+  id: string;
+  claim: string;
+  domain: string;
+  risk_profile: RiskProfile;
+  verified_at: string;
+  status: string | null;
+  confidence: number | null;
+}
+
+interface ClaimTemplate {
+  id: string;
+  name: string;
+  domain: string;
+  text: string;
+  risk_profile: RiskProfile | null;
+  variables: string[];
+}
+
+interface InstantiatedClaim {
+  claim: string;
+  domain: string;
+  risk_profile: RiskProfile | null;
+}
+
+interface FrequentDomain {
+  domain: string;
+  count: number;
+  risk_profile: RiskProfile;
+}
+
+interface Domain {
+  name: string;
+  description: string;
+  default_risk_profile: RiskProfile | null;
+  parent: string | null;
+  keywords?: string[];
+}
+
+interface DomainSuggestion {
+  domain: string | null;
+  confidence: number;
+  risk_profile: RiskProfile | null;
+  matched: string[];
+}
+
+/** Offered until domains are registered; afterwards only registered ones are accepted */
+const BUILTIN_DOMAINS = [
+  { value: 'general', label: 'General' },
+  { value: 'science', label: 'Science' },
+  { value: 'medical', label: 'Medical' },
+  { value: 'financial', label: 'Financial' },
+  { value: 'legal', label: 'Legal' },
+  { value: 'engineering', label: 'Engineering' },
+];
+
 export function GovernancePanel() {
   const [claim, setClaim] = useState('');
   const [domain, setDomain] = useState('general');
@@ -28,6 +87,100 @@ export function GovernancePanel() {
   const [loading, setLoading] = useState(false);
   const [result, setResult] = useState<GovernanceResult | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [registeredDomains, setRegisteredDomains] = useState<Domain[]>([]);
+  const [recent, setRecent] = useState<RecentVerification[]>([]);
+  const [frequentDomains, setFrequentDomains] = useState<FrequentDomain[]>([]);
+  const [templates, setTemplates] = useState<ClaimTemplate[]>([]);
+  const [templateId, setTemplateId] = useState('');
+  const [templateVars, setTemplateVars] = useState<Record<string, string>>({});
+  const [suggested, setSuggested] = useState<DomainSuggestion | null>(null);
+  // Set once the user picks a domain, so suggestions stop overriding it
+  const domainPicked = useRef(false);
+
+  useEffect(() => {
+    setTemplateId('');
+    invoke<ClaimTemplate[]>('list_claim_templates', { domain })
+      .then(setTemplates)
+      .catch(() => setTemplates([]));
+  }, [domain]);
+
+  const template = templates.find((t) => t.id === templateId);
+
+  const handleFillTemplate = async () => {
+    if (!template) return;
+    setError(null);
+    try {
+      const filled = await invoke<InstantiatedClaim>('instantiate_claim_template', {
+        id: template.id,
+        vars: Object.fromEntries(template.variables.map((v) => [v, templateVars[v] ?? ''])),
+      });
+      setClaim(filled.claim);
+      if (filled.risk_profile) setRiskProfile(filled.risk_profile);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const loadRecent = () => {
+    invoke<RecentVerification[]>('list_recent_verifications')
+      .then(setRecent)
+      .catch(() => setRecent([]));
+  };
+
+  useEffect(loadRecent, []);
+
+  useEffect(() => {
+    Promise.all([
+      invoke<Domain[]>('list_domains').catch(() => [] as Domain[]),
+      invoke<FrequentDomain[]>('get_frequent_domains').catch(() => [] as FrequentDomain[]),
+    ]).then(([domains, used]) => {
+      setRegisteredDomains(domains);
+      setFrequentDomains(used);
+      // Start from the domain used most, if it's still offered
+      const favourite = used.find((u) => domains.length === 0 || domains.some((d) => d.name === u.domain));
+      if (favourite) {
+        setDomain(favourite.domain);
+        setRiskProfile(favourite.risk_profile);
+      } else if (domains.length > 0) {
+        setDomain((current) => (domains.some((d) => d.name === current) ? current : domains[0].name));
+      }
+    });
+  }, []);
+
+  // The risk profile usually picked for a domain, else the domain's default
+  const selectDomain = (name: string) => {
+    setDomain(name);
+    const risk = frequentDomains.find((u) => u.domain === name)?.risk_profile
+      ?? registeredDomains.find((d) => d.name === name)?.default_risk_profile;
+    if (risk) setRiskProfile(risk);
+  };
+
+  const handleDomainChange = (name: string) => {
+    domainPicked.current = true;
+    setSuggested(null);
+    selectDomain(name);
+  };
+
+  const domainOptions = registeredDomains.length > 0
+    ? registeredDomains.map((d) => ({ value: d.name, label: d.parent ? `${d.parent} / ${d.name}` : d.name }))
+    : BUILTIN_DOMAINS;
+
+  // Preselect the domain the claim reads like, until one is picked by hand
+  useEffect(() => {
+    if (!claim.trim()) domainPicked.current = false;
+    if (domainPicked.current || claim.trim().length < 10) return;
+    const timer = setTimeout(() => {
+      invoke<DomainSuggestion>('suggest_domain', { text: claim })
+        .then((s) => {
+          if (domainPicked.current || !s.domain || s.confidence < 0.5) return;
+          if (!domainOptions.some((o) => o.value === s.domain)) return;
+          setSuggested(s);
+          selectDomain(s.domain);
+        })
+        .catch(() => setSuggested(null));
+    }, 400);
+    return () => clearTimeout(timer);
+  }, [claim]);
 
   const examples = [
     { claim: "Water boils at 100°C", domain: "science" },
@@ -35,42 +188,34 @@ export function GovernancePanel() {
     { claim: "The API should retry on failure", domain: "engineering" },
   ];
 
-  const handleVerify = async () => {
-    if (!claim.trim() || loading) return;
+  // Runs a verification; the backend writes its audit entry. `verify` is
+  // governance_verify or rerun_verification
+  const runVerification = async (verify: () => Promise<GovernanceResult>) => {
     setLoading(true);
     setResult(null);
     setError(null);
 
     try {
-      const res = await invoke<GovernanceResult>('governance_verify', {
-        claim,
-        domain,
-        riskProfile,
-      });
-      setResult(res);
-
-      // Log to audit trail
-      try {
-        await invoke('add_audit_entry', {
-          entry: {
-            id: res.audit_ref,
-            timestamp: new Date().toISOString(),
-            action: 'governance_verify',
-            claim,
-            domain,
-            risk_profile: riskProfile,
-            result_status: res.status,
-            result_action: res.action,
-            confidence: res.confidence,
-          },
-        });
-      } catch (auditErr) {
-        console.warn('Failed to log audit entry:', auditErr);
-      }
+      setResult(await verify());
     } catch (err) {
       setError(err instanceof Error ? err.message : String(err));
     }
     setLoading(false);
+    loadRecent();
+  };
+
+  const handleVerify = () => {
+    if (!claim.trim() || loading) return;
+    runVerification(() => invoke<GovernanceResult>('governance_verify', { claim, domain, riskProfile }));
+  };
+
+  const handleRerun = (run: RecentVerification) => {
+    if (loading) return;
+    setClaim(run.claim);
+    domainPicked.current = true;
+    setDomain(run.domain);
+    setRiskProfile(run.risk_profile);
+    runVerification(() => invoke<GovernanceResult>('rerun_verification', { id: run.id }));
   };
 
   const getActionStyle = (action: GovernanceAction) => {
@@ -115,16 +260,18 @@ export function GovernancePanel() {
             <label className="block text-sm text-white/40 mb-2">Domain</label>
             <select
               value={domain}
-              onChange={(e) => setDomain(e.target.value)}
+              onChange={(e) => handleDomainChange(e.target.value)}
               className="w-full px-4 py-2.5 bg-white/5 border border-white/10 rounded-lg text-sm text-white/80"
             >
-              <option value="general">General</option>
-              <option value="science">Science</option>
-              <option value="medical">Medical</option>
-              <option value="financial">Financial</option>
-              <option value="legal">Legal</option>
-              <option value="engineering">Engineering</option>
+              {domainOptions.map((d) => (
+                <option key={d.value} value={d.value}>{d.label}</option>
+              ))}
             </select>
+            {suggested?.domain === domain && (
+              <p className="mt-1 text-xs text-white/30">
+                Suggested{suggested.matched.length > 0 ? ` from: ${suggested.matched.slice(0, 4).join(', ')}` : ''}
+              </p>
+            )}
           </div>
 
           <div>
@@ -149,6 +296,42 @@ export function GovernancePanel() {
           </div>
         </div>
 
+        {/* Templates for the selected domain */}
+        {templates.length > 0 && (
+          <div className="mb-4">
+            <label className="block text-sm text-white/40 mb-2">Template</label>
+            <div className="flex flex-wrap items-center gap-2">
+              <select
+                value={templateId}
+                onChange={(e) => { setTemplateId(e.target.value); setTemplateVars({}); }}
+                className="px-3 py-2 bg-white/5 border border-white/10 rounded-lg text-sm text-white/80"
+              >
+                <option value="">None</option>
+                {templates.map((t) => (
+                  <option key={t.id} value={t.id} title={t.text}>{t.name}</option>
+                ))}
+              </select>
+              {template?.variables.map((v) => (
+                <input
+                  key={v}
+                  value={templateVars[v] ?? ''}
+                  onChange={(e) => setTemplateVars({ ...templateVars, [v]: e.target.value })}
+                  placeholder={v}
+                  className="px-3 py-2 bg-white/5 border border-white/10 rounded-lg text-sm text-white placeholder-white/30"
+                />
+              ))}
+              {template && (
+                <button
+                  onClick={handleFillTemplate}
+                  className="px-3 py-2 bg-white/5 hover:bg-white/10 border border-white/10 rounded-lg text-sm text-white/70 transition"
+                >
+                  Fill claim
+                </button>
+              )}
+            </div>
+          </div>
+        )}
+
         {/* Examples */}
         <div className="mb-4">
           <label className="block text-sm text-white/40 mb-2">Examples</label>
@@ -156,7 +339,7 @@ export function GovernancePanel() {
             {examples.map((ex, i) => (
               <button
                 key={i}
-                onClick={() => { setClaim(ex.claim); setDomain(ex.domain); }}
+                onClick={() => { domainPicked.current = true; setClaim(ex.claim); setDomain(ex.domain); }}
                 className="text-xs px-3 py-1.5 bg-white/5 hover:bg-white/10 border border-white/10 rounded-full transition text-white/50"
               >
                 {ex.claim}
@@ -182,6 +365,46 @@ export function GovernancePanel() {
         </button>
       </div>
 
+      {/* Recent verifications */}
+      {recent.length > 0 && (
+        <div className="p-4 rounded-xl bg-white/[0.02] border border-white/5">
+          <h4 className="flex items-center gap-2 text-sm font-medium text-white/60 mb-3">
+            <History className="w-4 h-4" />
+            Recent
+          </h4>
+          <ul className="space-y-1">
+            {recent.slice(0, 8).map((run) => (
+              <li key={run.id} className="flex items-center justify-between gap-3 text-sm">
+                <button
+                  onClick={() => {
+                    domainPicked.current = true;
+                    setClaim(run.claim);
+                    setDomain(run.domain);
+                    setRiskProfile(run.risk_profile);
+                  }}
+                  className="flex-1 min-w-0 text-left truncate text-white/70 hover:text-white"
+                  title={`${run.domain} · ${run.risk_profile} risk`}
+                >
+                  {run.claim}
+                </button>
+                <span className="text-xs text-white/40 shrink-0">
+                  {run.status ?? 'failed'}
+                  {run.confidence !== null && ` · ${Math.round(run.confidence * 100)}%`}
+                </span>
+                <button
+                  onClick={() => handleRerun(run)}
+                  disabled={loading}
+                  title="Run again"
+                  className="p-1 text-white/40 hover:text-white/80 disabled:opacity-40 transition"
+                >
+                  <RotateCcw className="w-3.5 h-3.5" />
+                </button>
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       {/* Result Card */}
       {result && (
         <motion.div
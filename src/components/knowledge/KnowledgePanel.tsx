@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { motion, AnimatePresence } from 'framer-motion';
 import {
   BookOpen,
@@ -16,6 +17,7 @@ interface VaultStatus {
   path: string;
   file_count: number;
   folder_count: number;
+  stale: boolean;
 }
 
 interface SearchResult {
@@ -25,6 +27,14 @@ interface SearchResult {
   line_numbers: number[];
 }
 
+interface NoteMatch {
+  path: string;
+  name: string;
+  alias: string | null;
+  score: number;
+  exact: boolean;
+}
+
 export function KnowledgePanel() {
   const [selectedNote, setSelectedNote] = useState<string | null>(null);
   const [vaultStatus, setVaultStatus] = useState<VaultStatus | null>(null);
@@ -32,6 +42,7 @@ export function KnowledgePanel() {
   const [searchResults, setSearchResults] = useState<SearchResult[]>([]);
   const [isSearching, setIsSearching] = useState(false);
   const [showSearch, setShowSearch] = useState(false);
+  const [nameMatches, setNameMatches] = useState<NoteMatch[]>([]);
 
   const loadVaultStatus = useCallback(async () => {
     try {
@@ -46,6 +57,32 @@ export function KnowledgePanel() {
     loadVaultStatus();
   }, [loadVaultStatus]);
 
+  useEffect(() => {
+    const unlisten = listen('index://ready', () => loadVaultStatus());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadVaultStatus]);
+
+  // Quick open: notes whose name or alias matches, updated as you type
+  useEffect(() => {
+    const name = searchQuery.trim();
+    if (!name) {
+      setNameMatches([]);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      invoke<NoteMatch[]>('resolve_note', { name, limit: 5 })
+        .then((matches) => !cancelled && setNameMatches(matches))
+        .catch(() => !cancelled && setNameMatches([]));
+    }, 150);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [searchQuery]);
+
   const handleSearch = async () => {
     if (!searchQuery.trim()) {
       setSearchResults([]);
@@ -164,6 +201,24 @@ export function KnowledgePanel() {
                 )}
               </div>
 
+              {/* Quick Open */}
+              {nameMatches.length > 0 && (
+                <div className="mt-3 space-y-1">
+                  {nameMatches.map((match) => (
+                    <button
+                      key={match.path}
+                      onClick={() => handleSelectSearchResult(match.path)}
+                      className="w-full text-left px-3 py-1.5 rounded-md hover:bg-zinc-800 flex items-center gap-2"
+                    >
+                      <FileText className="w-4 h-4 text-emerald-400 shrink-0" />
+                      <span className="text-zinc-100">{match.name}</span>
+                      {match.alias && <span className="text-xs text-zinc-500">alias “{match.alias}”</span>}
+                      <span className="text-xs text-zinc-600 font-mono truncate">{match.path}</span>
+                    </button>
+                  ))}
+                </div>
+              )}
+
               {/* Search Results */}
               {searchResults.length > 0 && (
                 <div className="mt-3 max-h-64 overflow-y-auto space-y-2">
@@ -2,13 +2,20 @@ import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import ReactMarkdown from 'react-markdown';
 import remarkGfm from 'remark-gfm';
-import { FileText, Clock, Loader2, AlertCircle } from 'lucide-react';
+import { FileText, Clock, Loader2, AlertCircle, Lock } from 'lucide-react';
 
 interface VaultNote {
   path: string;
   name: string;
   content: string;
   modified: string | null;
+  encrypted: boolean;
+}
+
+interface NoteMatch {
+  path: string;
+  name: string;
+  exact: boolean;
 }
 
 interface NoteViewerProps {
@@ -20,6 +27,9 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
   const [note, setNote] = useState<VaultNote | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [secret, setSecret] = useState('');
+  const [unlocking, setUnlocking] = useState(false);
+  const [linkError, setLinkError] = useState<string | null>(null);
 
   const loadNote = useCallback(async () => {
     if (!notePath) {
@@ -29,6 +39,7 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
 
     setLoading(true);
     setError(null);
+    setLinkError(null);
     try {
       const result = await invoke<VaultNote>('read_note', {
         relativePath: notePath,
@@ -46,6 +57,19 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
     loadNote();
   }, [loadNote]);
 
+  const unlock = async () => {
+    setUnlocking(true);
+    try {
+      await invoke('unlock_encrypted_notes', { secret });
+      setSecret('');
+      await loadNote();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setUnlocking(false);
+    }
+  };
+
   const formatDate = (dateStr: string | null) => {
     if (!dateStr) return null;
     try {
@@ -65,12 +89,26 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
   const processContent = (content: string) => {
     return content.replace(/\[\[([^\]]+)\]\]/g, (_, linkText) => {
       const parts = linkText.split('|');
-      const path = parts[0].trim();
-      const display = parts[1]?.trim() || path;
-      return `[${display}](obsidian://${path})`;
+      const target = parts[0].trim();
+      const display = parts[1]?.trim() || target;
+      return `[${display}](obsidian://${encodeURIComponent(target)})`;
     });
   };
 
+  // Link targets match names case-insensitively, aliases and close spellings
+  const openWikiLink = async (target: string) => {
+    try {
+      const [best] = await invoke<NoteMatch[]>('resolve_note', { name: target, limit: 1 });
+      if (best) {
+        onLinkClick?.(best.path);
+      } else {
+        setLinkError(`No note named "${target}"`);
+      }
+    } catch (err) {
+      setLinkError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
   if (!notePath) {
     return (
       <div className="h-full flex items-center justify-center">
@@ -93,6 +131,39 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
     );
   }
 
+  if (error && notePath.endsWith('.age')) {
+    return (
+      <div className="h-full flex items-center justify-center">
+        <form
+          className="text-center w-80"
+          onSubmit={(e) => {
+            e.preventDefault();
+            unlock();
+          }}
+        >
+          <Lock className="w-12 h-12 text-amber-400 mx-auto mb-4" />
+          <p className="text-zinc-300">This note is encrypted</p>
+          <p className="text-zinc-500 text-sm mt-1">{error}</p>
+          <input
+            type="password"
+            value={secret}
+            onChange={(e) => setSecret(e.target.value)}
+            placeholder="Passphrase or AGE-SECRET-KEY-..."
+            className="mt-4 w-full px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-md text-sm text-zinc-200"
+            autoFocus
+          />
+          <button
+            type="submit"
+            disabled={!secret || unlocking}
+            className="mt-3 w-full px-3 py-2 rounded-md bg-purple-600 hover:bg-purple-500 disabled:opacity-50 text-sm text-white"
+          >
+            {unlocking ? 'Unlocking…' : 'Unlock for this session'}
+          </button>
+        </form>
+      </div>
+    );
+  }
+
   if (error) {
     return (
       <div className="h-full flex items-center justify-center">
@@ -114,7 +185,10 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
       {/* Header */}
       <div className="flex items-center justify-between px-6 py-3 border-b border-zinc-800 shrink-0">
         <div>
-          <h1 className="text-lg font-semibold text-zinc-100">{note.name}</h1>
+          <h1 className="text-lg font-semibold text-zinc-100 flex items-center gap-2">
+            {note.encrypted && <Lock className="w-4 h-4 text-amber-400" />}
+            {note.name}
+          </h1>
           <p className="text-xs text-zinc-500 font-mono">{note.path}</p>
         </div>
         {note.modified && (
@@ -125,6 +199,13 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
         )}
       </div>
 
+      {linkError && (
+        <div className="flex items-center gap-2 px-6 py-2 border-b border-zinc-800 text-sm text-amber-400">
+          <AlertCircle className="w-4 h-4" />
+          {linkError}
+        </div>
+      )}
+
       {/* Content */}
       <div className="flex-1 overflow-y-auto p-6">
         <article className="prose prose-invert prose-zinc max-w-none
@@ -150,10 +231,10 @@ export function NoteViewer({ notePath, onLinkClick }: NoteViewerProps) {
             components={{
               a: ({ href, children }) => {
                 if (href?.startsWith('obsidian://')) {
-                  const notePath = href.replace('obsidian://', '') + '.md';
+                  const target = decodeURIComponent(href.replace('obsidian://', ''));
                   return (
                     <button
-                      onClick={() => onLinkClick?.(notePath)}
+                      onClick={() => openWikiLink(target)}
                       className="text-purple-400 hover:underline"
                     >
                       {children}
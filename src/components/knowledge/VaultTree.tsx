@@ -9,6 +9,7 @@ import {
   ChevronDown,
   RefreshCw,
   Home,
+  Lock,
 } from 'lucide-react';
 
 interface VaultFile {
@@ -50,20 +51,22 @@ function TreeNode({ file, depth, onSelectNote, selectedPath }: TreeNodeProps) {
     }
   }, [file.path, file.is_dir]);
 
+  const isSelected = selectedPath === file.path;
+  const isMarkdown = file.extension === 'md';
+  // age-encrypted notes open in the viewer, which asks to unlock them
+  const isEncrypted = file.extension === 'age';
+
   const handleClick = () => {
     if (file.is_dir) {
       if (!isOpen && children.length === 0) {
         loadChildren();
       }
       setIsOpen(!isOpen);
-    } else if (file.extension === 'md') {
+    } else if (isMarkdown || isEncrypted) {
       onSelectNote(file.path);
     }
   };
 
-  const isSelected = selectedPath === file.path;
-  const isMarkdown = file.extension === 'md';
-
   return (
     <div>
       <button
@@ -93,7 +96,11 @@ function TreeNode({ file, depth, onSelectNote, selectedPath }: TreeNodeProps) {
         ) : (
           <>
             <span className="w-4" />
-            <FileText className={`w-4 h-4 ${isMarkdown ? 'text-blue-400' : 'text-zinc-500'}`} />
+            {isEncrypted ? (
+              <Lock className="w-4 h-4 text-amber-400" />
+            ) : (
+              <FileText className={`w-4 h-4 ${isMarkdown ? 'text-blue-400' : 'text-zinc-500'}`} />
+            )}
           </>
         )}
         <span className="truncate">{file.name}</span>
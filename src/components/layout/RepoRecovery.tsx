@@ -0,0 +1,83 @@
+import { useState, useEffect, type ReactNode } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { AlertTriangle, RefreshCw, Settings } from 'lucide-react';
+
+type RepoState = 'missing' | 'uninitialized' | 'corrupt' | 'ready';
+
+interface RepoStatus {
+  state: RepoState;
+  path: string;
+  detail: string | null;
+}
+
+const TITLES: Record<Exclude<RepoState, 'ready'>, string> = {
+  missing: 'Truth repository not found',
+  uninitialized: 'Not a truth repository yet',
+  corrupt: 'Truth repository is damaged',
+};
+
+const HINTS: Record<Exclude<RepoState, 'ready'>, string> = {
+  missing:
+    'The folder may have been moved, renamed or be on a drive that is not mounted. Point Settings at where it is now, or restore a backup.',
+  uninitialized:
+    'Run `truthgit init` in this folder, point Settings at an existing repository, or import one from a zip archive.',
+  corrupt:
+    'Nothing will be written to it until this is fixed. Restore a backup or retention archive, or repair the file shown above and check again.',
+};
+
+// Shown in place of the panels while the truth repository isn't usable, so
+// one screen explains what is wrong instead of every panel failing on its own
+export function RepoRecovery({ children, onOpenSettings }: { children: ReactNode; onOpenSettings: () => void }) {
+  const [status, setStatus] = useState<RepoStatus | null>(null);
+  const [checking, setChecking] = useState(false);
+
+  const check = () => {
+    setChecking(true);
+    invoke<RepoStatus>('get_repo_state')
+      .then(setStatus)
+      .catch((err) => console.error('Failed to check truth repository:', err))
+      .finally(() => setChecking(false));
+  };
+
+  useEffect(() => {
+    check();
+    const unlisten = listen<RepoStatus>('repo://state', (event) => setStatus(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!status || status.state === 'ready') {
+    return <>{children}</>;
+  }
+
+  return (
+    <div className="max-w-xl mx-auto mt-16 bg-zinc-900 border border-zinc-700 rounded-xl p-6">
+      <div className="flex items-center gap-3 mb-3">
+        <AlertTriangle className="w-6 h-6 text-amber-400" />
+        <h3 className="text-lg font-semibold text-zinc-100">{TITLES[status.state]}</h3>
+      </div>
+      <p className="text-sm text-zinc-400 font-mono break-all">{status.path}</p>
+      {status.detail && <p className="text-zinc-300 mt-3">{status.detail}</p>}
+      <p className="text-sm text-zinc-500 mt-3">{HINTS[status.state]}</p>
+      <div className="flex justify-end gap-3 mt-6">
+        <button
+          onClick={check}
+          disabled={checking}
+          className="flex items-center gap-2 px-4 py-2 rounded-lg bg-zinc-800 hover:bg-zinc-700 text-zinc-300 text-sm disabled:opacity-50"
+        >
+          <RefreshCw className={`w-4 h-4 ${checking ? 'animate-spin' : ''}`} />
+          Check again
+        </button>
+        <button
+          onClick={onOpenSettings}
+          className="flex items-center gap-2 px-4 py-2 rounded-lg bg-purple-600 hover:bg-purple-500 text-white text-sm"
+        >
+          <Settings className="w-4 h-4" />
+          Open Settings
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
 import { useState } from 'react';
 import { Sidebar, type View } from './Sidebar';
 import { Header } from './Header';
+import { RepoRecovery } from './RepoRecovery';
 import { GovernancePanel } from '../governance/GovernancePanel';
 import { TruthPanel } from '../truth/TruthPanel';
 import { AgentsPanel } from '../agents/AgentsPanel';
@@ -22,13 +23,21 @@ export function Shell() {
         <Header currentView={currentView} />
 
         <main className="flex-1 overflow-auto p-6">
-          {currentView === 'governance' && <GovernancePanel />}
-          {currentView === 'agents' && <AgentsPanel />}
-          {currentView === 'truth' && <TruthPanel />}
-          {currentView === 'audit' && <AuditPanel />}
-          {currentView === 'knowledge' && <KnowledgePanel />}
-          {currentView === 'terminal' && <TerminalPanel />}
-          {currentView === 'settings' && <SettingsPanel />}
+          {/* Settings and the terminal stay reachable to repair the repository */}
+          {currentView === 'settings' || currentView === 'terminal' ? (
+            <>
+              {currentView === 'terminal' && <TerminalPanel />}
+              {currentView === 'settings' && <SettingsPanel />}
+            </>
+          ) : (
+            <RepoRecovery onOpenSettings={() => setCurrentView('settings')}>
+              {currentView === 'governance' && <GovernancePanel />}
+              {currentView === 'agents' && <AgentsPanel />}
+              {currentView === 'truth' && <TruthPanel />}
+              {currentView === 'audit' && <AuditPanel />}
+              {currentView === 'knowledge' && <KnowledgePanel />}
+            </RepoRecovery>
+          )}
         </main>
       </div>
     </div>
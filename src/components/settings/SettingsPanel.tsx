@@ -10,6 +10,11 @@ import {
   Wifi,
   WifiOff,
   AlertTriangle,
+  Plug,
+  Keyboard,
+  User,
+  BarChart3,
+  KeyRound,
 } from 'lucide-react';
 
 interface AppSettings {
@@ -20,6 +25,125 @@ interface AppSettings {
   default_risk_profile: 'low' | 'medium' | 'high';
   terminal_font_size: number;
   auto_save_audit: boolean;
+  proxy_url: string;
+  no_proxy: string;
+  ca_bundle_path: string;
+  local_api_enabled: boolean;
+  local_api_port: number;
+  minimize_to_tray: boolean;
+  notifications: NotificationSettings;
+  shortcuts: ShortcutSettings;
+  identity: IdentitySettings;
+  approval_policy: ApprovalPolicy;
+  watchlist: WatchlistSettings;
+  feeds: FeedSettings;
+  digest: DigestSettings;
+  quick_capture: QuickCaptureSettings;
+  retention: RetentionSettings;
+  compression: CompressionSettings;
+  compaction: CompactionSettings;
+  hooks: HookSettings;
+  locale: 'en' | 'es';
+  usage_metrics: boolean;
+  update_channel: 'stable' | 'beta';
+}
+
+interface UpdateInfo {
+  current_version: string;
+  available: boolean;
+  version: string | null;
+}
+
+interface UsageReport {
+  enabled: boolean;
+  since: string | null;
+  total: number;
+  features: { feature: string; count: number; errors: number; average_ms: number }[];
+}
+
+interface PermissionGrant {
+  kind: 'shell' | 'read_path' | 'remote_host' | 'trust_dir';
+  scope: string;
+  granted_at: string;
+}
+
+const PERMISSION_LABELS: Record<PermissionGrant['kind'], string> = {
+  shell: 'Run',
+  read_path: 'Read files in',
+  remote_host: 'Connect to',
+  trust_dir: 'Use as vault or repository',
+};
+
+interface NotificationSettings {
+  verification_complete: boolean;
+  escalation: boolean;
+  sync_complete: boolean;
+  watchlist_change: boolean;
+}
+
+// Who audit entries, verifications and reviews are attributed to
+interface IdentitySettings {
+  display_name: string;
+  email: string;
+}
+
+// Claims in these domains, or at or above this risk, need a second reviewer
+interface ApprovalPolicy {
+  domains: string[];
+  min_risk_profile: 'low' | 'medium' | 'high' | null;
+}
+
+// Pinned claims are re-verified this often; 0 turns scheduled re-checks off
+interface WatchlistSettings {
+  recheck_hours: number;
+}
+
+// Watched feeds and pages are fetched this often; 0 turns scheduled checks off
+interface FeedSettings {
+  check_hours: number;
+}
+
+// A digest is written to digests/ and passed to digest hooks each period; null turns it off
+interface DigestSettings {
+  schedule: 'day' | 'week' | 'month' | null;
+}
+
+// Captured text is appended to inbox_note (relative to the vault)
+interface QuickCaptureSettings {
+  inbox_note: string;
+  extract_claims: boolean;
+}
+
+// Audit entries older than audit_days are archived, then removed; 0 keeps
+// them forever. An empty archive_dir keeps archives in the truth repo.
+interface RetentionSettings {
+  audit_days: number;
+  archive_dir: string;
+}
+
+// Codec for newly written objects and backups; both are always readable
+interface CompressionSettings {
+  objects: 'zlib' | 'zstd';
+  backups: 'zlib' | 'zstd';
+}
+
+// The truth repo is compacted once the last compaction is interval_days old; 0 turns it off
+interface CompactionSettings {
+  interval_days: number;
+}
+
+// Executables under hooks/<event>/ in the config directory; off by default
+interface HookSettings {
+  enabled: boolean;
+  timeout_secs: number;
+}
+
+// Accelerators such as "CommandOrControl+Shift+V"; empty disables the shortcut
+interface ShortcutSettings {
+  quick_verify: string;
+  quick_search: string;
+  new_claim: string;
+  quick_capture: string;
 }
 
 // SECURITY: Frontend defaults MUST match backend defaults (lib.rs)
@@ -34,6 +158,63 @@ const DEFAULT_SETTINGS: AppSettings = {
   default_risk_profile: 'medium',
   terminal_font_size: 14,
   auto_save_audit: true,
+  proxy_url: '',
+  no_proxy: '',
+  ca_bundle_path: '',
+  local_api_enabled: false,
+  local_api_port: 47821,
+  minimize_to_tray: false,
+  notifications: {
+    verification_complete: false,
+    escalation: false,
+    sync_complete: false,
+    watchlist_change: false,
+  },
+  shortcuts: {
+    quick_verify: 'CommandOrControl+Shift+V',
+    quick_search: 'CommandOrControl+Shift+K',
+    new_claim: 'CommandOrControl+Shift+N',
+    quick_capture: 'CommandOrControl+Shift+J',
+  },
+  identity: {
+    display_name: '',
+    email: '',
+  },
+  approval_policy: {
+    domains: [],
+    min_risk_profile: null,
+  },
+  watchlist: {
+    recheck_hours: 24,
+  },
+  feeds: {
+    check_hours: 6,
+  },
+  digest: {
+    schedule: null,
+  },
+  quick_capture: {
+    inbox_note: 'Inbox.md',
+    extract_claims: true,
+  },
+  retention: {
+    audit_days: 0,
+    archive_dir: '',
+  },
+  compression: {
+    objects: 'zlib',
+    backups: 'zlib',
+  },
+  compaction: {
+    interval_days: 0,
+  },
+  hooks: {
+    enabled: false,
+    timeout_secs: 10,
+  },
+  locale: 'en',
+  usage_metrics: false,
+  update_channel: 'stable',
 };
 
 // SECURITY ASSERTION: Verify defaults don't contain remote endpoints
@@ -156,11 +337,71 @@ export function SettingsPanel() {
   const [isLoading, setIsLoading] = useState(true);
   const [saveMessage, setSaveMessage] = useState<string | null>(null);
   const [showResetConfirm, setShowResetConfirm] = useState(false);
+  const [usage, setUsage] = useState<UsageReport | null>(null);
+  const [update, setUpdate] = useState<UpdateInfo | null>(null);
+  const [updateState, setUpdateState] = useState<'idle' | 'checking' | 'downloading' | 'ready'>('idle');
+  const [updateError, setUpdateError] = useState<string | null>(null);
+  const [grants, setGrants] = useState<PermissionGrant[]>([]);
 
   useEffect(() => {
     loadSettings();
+    invoke<UsageReport>('get_usage_metrics')
+      .then(setUsage)
+      .catch(() => setUsage(null));
+    invoke<PermissionGrant[]>('list_granted_permissions')
+      .then(setGrants)
+      .catch(() => setGrants([]));
   }, []);
 
+  const revokePermission = async (grant: PermissionGrant) => {
+    try {
+      setGrants(await invoke<PermissionGrant[]>('revoke_permission', { kind: grant.kind, scope: grant.scope }));
+    } catch (err) {
+      console.error('Failed to revoke permission:', err);
+    }
+  };
+
+  const checkForUpdates = async () => {
+    setUpdateState('checking');
+    setUpdateError(null);
+    try {
+      setUpdate(await invoke<UpdateInfo>('check_for_updates'));
+      setUpdateState('idle');
+    } catch (err) {
+      setUpdateError(String(err));
+      setUpdateState('idle');
+    }
+  };
+
+  const downloadUpdate = async () => {
+    setUpdateState('downloading');
+    setUpdateError(null);
+    try {
+      await invoke('download_update');
+      setUpdateState('ready');
+    } catch (err) {
+      setUpdateError(String(err));
+      setUpdateState('idle');
+    }
+  };
+
+  const installUpdate = async () => {
+    try {
+      await invoke('install_update');
+    } catch (err) {
+      setUpdateError(String(err));
+    }
+  };
+
+  const clearUsage = async () => {
+    try {
+      await invoke('clear_usage_metrics');
+      setUsage(await invoke<UsageReport>('get_usage_metrics'));
+    } catch (err) {
+      console.error('Failed to clear usage metrics:', err);
+    }
+  };
+
   const loadSettings = async () => {
     setIsLoading(true);
     try {
@@ -352,13 +593,31 @@ export function SettingsPanel() {
               </button>
             </div>
             {settings.api_mode === 'remote' && (
-              <div className="mt-4">
+              <div className="mt-4 space-y-4">
                 <InputField
                   label="API URL"
                   value={settings.api_url}
                   onChange={(v) => updateSetting('api_url', v)}
                   placeholder="https://truthgit-api-xxx.run.app"
                 />
+                <InputField
+                  label="Proxy URL (optional)"
+                  value={settings.proxy_url}
+                  onChange={(v) => updateSetting('proxy_url', v)}
+                  placeholder="http://proxy.example.com:3128"
+                />
+                <InputField
+                  label="Bypass Proxy For (comma-separated hosts)"
+                  value={settings.no_proxy}
+                  onChange={(v) => updateSetting('no_proxy', v)}
+                  placeholder="localhost, .internal.example.com"
+                />
+                <InputField
+                  label="Custom CA Bundle (PEM, optional)"
+                  value={settings.ca_bundle_path}
+                  onChange={(v) => updateSetting('ca_bundle_path', v)}
+                  placeholder="/etc/ssl/certs/corp-root.pem"
+                />
               </div>
             )}
           </SettingsSection>
@@ -381,6 +640,18 @@ export function SettingsPanel() {
               onChange={(v) => updateSetting('truth_repo_path', v)}
               placeholder="~/Almacen_IA/LumenSyntax-Main/.truth"
             />
+            <InputField
+              label="Inbox Note"
+              value={settings.quick_capture.inbox_note}
+              onChange={(v) => updateSetting('quick_capture', { ...settings.quick_capture, inbox_note: v })}
+              placeholder="Inbox.md"
+            />
+            <ToggleField
+              label="Offer Captured Claims for Verification"
+              description="Claims found in captured text open for review, like dropped files"
+              value={settings.quick_capture.extract_claims}
+              onChange={(v) => updateSetting('quick_capture', { ...settings.quick_capture, extract_claims: v })}
+            />
           </SettingsSection>
 
           {/* Terminal */}
@@ -423,12 +694,311 @@ export function SettingsPanel() {
                 { value: 'high', label: 'High - Conservative' },
               ]}
             />
+            <InputField
+              label="Second Reviewer Required For Domains"
+              value={settings.approval_policy.domains.join(', ')}
+              onChange={(v) =>
+                updateSetting('approval_policy', {
+                  ...settings.approval_policy,
+                  // Empty entries keep a trailing comma while typing; the backend ignores them
+                  domains: v.split(/,\s*/),
+                })
+              }
+              placeholder="e.g. healthcare, legal"
+            />
+            <SelectField
+              label="Second Reviewer Required From Risk"
+              value={settings.approval_policy.min_risk_profile ?? ''}
+              onChange={(v) =>
+                updateSetting('approval_policy', {
+                  ...settings.approval_policy,
+                  min_risk_profile: (v || null) as ApprovalPolicy['min_risk_profile'],
+                })
+              }
+              options={[
+                { value: '', label: 'Never' },
+                { value: 'medium', label: 'Medium and High' },
+                { value: 'high', label: 'High only' },
+              ]}
+            />
+            <SelectField
+              label="Re-check Pinned Claims"
+              value={String(settings.watchlist.recheck_hours)}
+              onChange={(v) => updateSetting('watchlist', { recheck_hours: Number(v) })}
+              options={[
+                { value: '0', label: 'Never' },
+                { value: '6', label: 'Every 6 hours' },
+                { value: '24', label: 'Daily (Default)' },
+                { value: '168', label: 'Weekly' },
+              ]}
+            />
+            <SelectField
+              label="Check Watched Sources"
+              value={String(settings.feeds.check_hours)}
+              onChange={(v) => updateSetting('feeds', { check_hours: Number(v) })}
+              options={[
+                { value: '0', label: 'Never' },
+                { value: '1', label: 'Hourly' },
+                { value: '6', label: 'Every 6 hours (Default)' },
+                { value: '24', label: 'Daily' },
+              ]}
+            />
+            <SelectField
+              label="Automatic Digest"
+              value={settings.digest.schedule ?? ''}
+              onChange={(v) =>
+                updateSetting('digest', { schedule: (v || null) as DigestSettings['schedule'] })
+              }
+              options={[
+                { value: '', label: 'Off (Default)' },
+                { value: 'day', label: 'Daily' },
+                { value: 'week', label: 'Weekly' },
+                { value: 'month', label: 'Monthly' },
+              ]}
+            />
+            <SelectField
+              label="Keep Audit Entries"
+              value={String(settings.retention.audit_days)}
+              onChange={(v) => updateSetting('retention', { ...settings.retention, audit_days: Number(v) })}
+              options={[
+                { value: '0', label: 'Forever (Default)' },
+                { value: '365', label: '1 year, then archive' },
+                { value: '730', label: '2 years, then archive' },
+                { value: '2555', label: '7 years, then archive' },
+              ]}
+            />
+            <InputField
+              label="Retention Archive Folder"
+              value={settings.retention.archive_dir}
+              onChange={(v) => updateSetting('retention', { ...settings.retention, archive_dir: v })}
+              placeholder="Default: archives/ in the truth repository"
+            />
+            <SelectField
+              label="Object Compression"
+              value={settings.compression.objects}
+              onChange={(v) =>
+                updateSetting('compression', { ...settings.compression, objects: v as CompressionSettings['objects'] })
+              }
+              options={[
+                { value: 'zlib', label: 'zlib (Default, readable by every tool)' },
+                { value: 'zstd', label: 'Zstandard (smaller, needs a recent CLI)' },
+              ]}
+            />
+            <SelectField
+              label="Backup Compression"
+              value={settings.compression.backups}
+              onChange={(v) =>
+                updateSetting('compression', { ...settings.compression, backups: v as CompressionSettings['backups'] })
+              }
+              options={[
+                { value: 'zlib', label: 'gzip (Default)' },
+                { value: 'zstd', label: 'Zstandard' },
+              ]}
+            />
+            <SelectField
+              label="Compact Repository"
+              value={String(settings.compaction.interval_days)}
+              onChange={(v) => updateSetting('compaction', { interval_days: Number(v) })}
+              options={[
+                { value: '0', label: 'On demand only (Default)' },
+                { value: '7', label: 'Weekly' },
+                { value: '30', label: 'Monthly' },
+              ]}
+            />
+            <SelectField
+              label="Message & Report Language"
+              value={settings.locale}
+              onChange={(v) => updateSetting('locale', v as 'en' | 'es')}
+              options={[
+                { value: 'en', label: 'English (Default)' },
+                { value: 'es', label: 'Español' },
+              ]}
+            />
             <ToggleField
               label="Auto-save Audit Entries"
               description="Automatically save all verification results to the audit log"
               value={settings.auto_save_audit}
               onChange={(v) => updateSetting('auto_save_audit', v)}
             />
+            <ToggleField
+              label="Keep Running in Tray"
+              description="Closing the window hides it to the system tray instead of quitting"
+              value={settings.minimize_to_tray}
+              onChange={(v) => updateSetting('minimize_to_tray', v)}
+            />
+            <ToggleField
+              label="Notify on Escalation"
+              description="Desktop notification when a claim is escalated for human review"
+              value={settings.notifications.escalation}
+              onChange={(v) => updateSetting('notifications', { ...settings.notifications, escalation: v })}
+            />
+            <ToggleField
+              label="Notify When Verifications Finish"
+              description="Batch verifications and verifications arriving from the remote"
+              value={settings.notifications.verification_complete}
+              onChange={(v) => updateSetting('notifications', { ...settings.notifications, verification_complete: v })}
+            />
+            <ToggleField
+              label="Notify When Sync Finishes"
+              description="Push and pull results, including failed transfers"
+              value={settings.notifications.sync_complete}
+              onChange={(v) => updateSetting('notifications', { ...settings.notifications, sync_complete: v })}
+            />
+            <ToggleField
+              label="Notify When Pinned Claims Change"
+              description="A re-check moved a pinned claim's status or confidence"
+              value={settings.notifications.watchlist_change}
+              onChange={(v) => updateSetting('notifications', { ...settings.notifications, watchlist_change: v })}
+            />
+          </SettingsSection>
+
+          {/* Shortcuts */}
+          <SettingsSection
+            icon={<User className="w-5 h-5 text-sky-400" />}
+            title="Identity"
+            description="Recorded on audit entries, verifications and claim reviews; the key fingerprint is your own signing key on this computer"
+          >
+            <InputField
+              label="Display Name"
+              value={settings.identity.display_name}
+              onChange={(v) => updateSetting('identity', { ...settings.identity, display_name: v })}
+              placeholder="Defaults to your system user name"
+            />
+            <InputField
+              label="Email"
+              value={settings.identity.email}
+              onChange={(v) => updateSetting('identity', { ...settings.identity, email: v })}
+              placeholder="you@example.org"
+            />
+          </SettingsSection>
+
+          <SettingsSection
+            icon={<Keyboard className="w-5 h-5 text-violet-400" />}
+            title="Global Shortcuts"
+            description="Work in any app without switching to TruthGit first; leave a field empty to disable it"
+          >
+            <InputField
+              label="Verify Clipboard"
+              value={settings.shortcuts.quick_verify}
+              onChange={(v) => updateSetting('shortcuts', { ...settings.shortcuts, quick_verify: v })}
+              placeholder="CommandOrControl+Shift+V"
+            />
+            <InputField
+              label="Quick Search"
+              value={settings.shortcuts.quick_search}
+              onChange={(v) => updateSetting('shortcuts', { ...settings.shortcuts, quick_search: v })}
+              placeholder="CommandOrControl+Shift+K"
+            />
+            <InputField
+              label="New Claim"
+              value={settings.shortcuts.new_claim}
+              onChange={(v) => updateSetting('shortcuts', { ...settings.shortcuts, new_claim: v })}
+              placeholder="CommandOrControl+Shift+N"
+            />
+            <InputField
+              label="Capture to Inbox"
+              value={settings.shortcuts.quick_capture}
+              onChange={(v) => updateSetting('shortcuts', { ...settings.shortcuts, quick_capture: v })}
+              placeholder="CommandOrControl+Shift+J"
+            />
+          </SettingsSection>
+
+          {/* Local API */}
+          <SettingsSection
+            icon={<Plug className="w-5 h-5 text-cyan-400" />}
+            title="Local API"
+            description="Let scripts and plugins on this machine use TruthGit"
+          >
+            <ToggleField
+              label="Enable Local API Server"
+              description="Listens on 127.0.0.1 only; requests need the token in ~/.config/truthgit/local-api-token"
+              value={settings.local_api_enabled}
+              onChange={(v) => updateSetting('local_api_enabled', v)}
+            />
+            {settings.local_api_enabled && (
+              <InputField
+                label="Port"
+                value={settings.local_api_port.toString()}
+                onChange={(v) => updateSetting('local_api_port', parseInt(v) || 0)}
+                placeholder="47821"
+              />
+            )}
+            <ToggleField
+              label="Run Automation Hooks"
+              description="Runs executables in ~/.config/truthgit/hooks before and after verification and on new claims"
+              value={settings.hooks.enabled}
+              onChange={(v) => updateSetting('hooks', { ...settings.hooks, enabled: v })}
+            />
+            {settings.hooks.enabled && (
+              <InputField
+                label="Hook Timeout (seconds)"
+                value={settings.hooks.timeout_secs.toString()}
+                onChange={(v) => updateSetting('hooks', { ...settings.hooks, timeout_secs: parseInt(v) || 0 })}
+                placeholder="10"
+              />
+            )}
+          </SettingsSection>
+
+          {/* Usage Metrics */}
+          <SettingsSection
+            icon={<BarChart3 className="w-5 h-5 text-indigo-400" />}
+            title="Usage Metrics"
+            description="Your own verification activity, counted on this machine only"
+          >
+            <ToggleField
+              label="Record Usage Metrics"
+              description="Counts features used and how long they take. Never sent anywhere."
+              value={settings.usage_metrics}
+              onChange={(v) => updateSetting('usage_metrics', v)}
+            />
+            {usage && usage.total > 0 && (
+              <div className="space-y-2">
+                <p className="text-sm text-zinc-400">
+                  {usage.total} uses recorded
+                  {usage.since ? ` since ${new Date(usage.since).toLocaleDateString()}` : ''}
+                </p>
+                <ul className="text-sm text-zinc-300 space-y-1">
+                  {usage.features.slice(0, 5).map((f) => (
+                    <li key={f.feature} className="flex justify-between">
+                      <span>{f.feature.replace(/_/g, ' ')}</span>
+                      <span className="text-zinc-500">
+                        {f.count}× · {f.average_ms} ms avg{f.errors > 0 ? ` · ${f.errors} failed` : ''}
+                      </span>
+                    </li>
+                  ))}
+                </ul>
+                <button onClick={clearUsage} className="text-xs text-zinc-500 hover:text-zinc-300">
+                  Clear recorded metrics
+                </button>
+              </div>
+            )}
+          </SettingsSection>
+
+          {/* Permissions */}
+          <SettingsSection
+            icon={<KeyRound className="w-5 h-5 text-rose-400" />}
+            title="Permissions"
+            description="Shell programs, folders outside the vault and hosts you've allowed; each is asked for the first time it's needed"
+          >
+            {grants.length === 0 ? (
+              <p className="text-sm text-zinc-500">Nothing allowed yet</p>
+            ) : (
+              <ul className="text-sm text-zinc-300 space-y-1">
+                {grants.map((grant) => (
+                  <li key={`${grant.kind}:${grant.scope}`} className="flex justify-between gap-4">
+                    <span className="truncate">
+                      {PERMISSION_LABELS[grant.kind]} <span className="font-mono">{grant.scope}</span>
+                    </span>
+                    <button
+                      onClick={() => revokePermission(grant)}
+                      className="text-xs text-zinc-500 hover:text-red-400 shrink-0"
+                    >
+                      Revoke
+                    </button>
+                  </li>
+                ))}
+              </ul>
+            )}
           </SettingsSection>
 
           {/* Version Info */}
@@ -448,6 +1018,39 @@ export function SettingsPanel() {
             <div className="mt-3 text-xs text-green-500">
               {settings.api_mode === 'local' ? '🔒 Local-First Mode Active' : '☁️ Remote API Mode'}
             </div>
+            <div className="mt-4 flex items-center justify-center gap-3 text-xs">
+              <select
+                value={settings.update_channel}
+                onChange={(e) => updateSetting('update_channel', e.target.value as 'stable' | 'beta')}
+                className="bg-zinc-800 border border-zinc-700 rounded px-2 py-1 text-zinc-300"
+              >
+                <option value="stable">Stable channel</option>
+                <option value="beta">Beta channel</option>
+              </select>
+              {updateState === 'ready' ? (
+                <button onClick={installUpdate} className="px-3 py-1 rounded bg-green-600 hover:bg-green-500 text-white">
+                  Install {update?.version} &amp; Restart
+                </button>
+              ) : update?.available ? (
+                <button
+                  onClick={downloadUpdate}
+                  disabled={updateState === 'downloading'}
+                  className="px-3 py-1 rounded bg-blue-600 hover:bg-blue-500 text-white disabled:opacity-50"
+                >
+                  {updateState === 'downloading' ? 'Downloading…' : `Download ${update.version}`}
+                </button>
+              ) : (
+                <button
+                  onClick={checkForUpdates}
+                  disabled={updateState === 'checking'}
+                  className="px-3 py-1 rounded bg-zinc-800 hover:bg-zinc-700 text-zinc-300 disabled:opacity-50"
+                >
+                  {updateState === 'checking' ? 'Checking…' : 'Check for Updates'}
+                </button>
+              )}
+            </div>
+            {update && !update.available && <p className="mt-2 text-xs text-zinc-500">You're up to date</p>}
+            {updateError && <p className="mt-2 text-xs text-red-400">{updateError}</p>}
           </div>
         </div>
       </div>
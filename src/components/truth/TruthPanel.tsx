@@ -1,9 +1,12 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { motion, AnimatePresence } from 'framer-motion';
-import { Database, X, Copy, Check } from 'lucide-react';
+import { Database, X, Copy, Check, AlertTriangle, Pin, PinOff, Link, FileText, Undo2 } from 'lucide-react';
+import type { Evidence } from '../audit/AuditPanel';
 import { RepoStatus } from './RepoStatus';
 import { ClaimsList } from './ClaimsList';
+import { Watchlist, type WatchedClaim } from './Watchlist';
 
 interface TruthRepoStatus {
   exists: boolean;
@@ -26,9 +29,83 @@ interface Claim {
     tags?: string[];
     created_at?: string;
     created_by?: string;
+    ontological_type?: string;
+    sources?: ClaimSource[];
   };
 }
 
+interface ClaimSource {
+  url: string;
+  title: string | null;
+  accessed: string | null;
+  archived_hash: string | null;
+}
+
+interface TimelineEvent {
+  at: string;
+  kind: 'verification' | 'audit' | 'review' | 'annotation' | 'sync';
+  summary: string;
+  actor: Actor | null;
+}
+
+type ReviewState = 'draft' | 'in-review' | 'approved' | 'retired';
+
+interface Actor {
+  name: string;
+  email: string | null;
+  key_fingerprint: string | null;
+}
+
+interface ClaimReview {
+  state: ReviewState;
+  reviewer: Actor | null;
+  updated_at: string | null;
+  pending_approval: { requested_by: Actor; requested_at: string; note: string | null } | null;
+}
+
+// Mirrors ReviewState::can_move_to in reviews.rs
+const REVIEW_TRANSITIONS: Record<ReviewState, ReviewState[]> = {
+  draft: ['in-review', 'retired'],
+  'in-review': ['approved', 'draft'],
+  approved: ['retired', 'in-review'],
+  retired: ['draft'],
+};
+
+interface OntologicalType {
+  name: string;
+  description: string;
+}
+
+/** An object under objects/cl/ that couldn't be read as a claim */
+interface ObjectWarning {
+  object: string;
+  kind: 'claim' | 'verification' | 'blob' | 'unknown' | null;
+  message: string;
+}
+
+interface ClaimStreamSummary {
+  task_id: string;
+  sent: number;
+  stale: boolean;
+  warnings: ObjectWarning[];
+}
+
+interface ClaimChunk {
+  task_id: string;
+  claims: Claim[];
+  sent: number;
+  total: number;
+  /** Cached copy served while the startup index is still being built */
+  stale: boolean;
+  done: boolean;
+}
+
+interface SavedFilter {
+  id: string;
+  name: string;
+  query: Record<string, unknown>;
+}
+
 export function TruthPanel() {
   const [status, setStatus] = useState<TruthRepoStatus | null>(null);
   const [claims, setClaims] = useState<Claim[]>([]);
@@ -36,14 +113,34 @@ export function TruthPanel() {
   const [claimsLoading, setClaimsLoading] = useState(true);
   const [statusError, setStatusError] = useState<string | null>(null);
   const [claimsError, setClaimsError] = useState<string | null>(null);
+  const [objectWarnings, setObjectWarnings] = useState<ObjectWarning[]>([]);
+  const [ontologicalTypes, setOntologicalTypes] = useState<OntologicalType[]>([]);
+  const [typeFilter, setTypeFilter] = useState('');
+  const [savedFilters, setSavedFilters] = useState<SavedFilter[]>([]);
+  const [activeFilter, setActiveFilter] = useState<SavedFilter | null>(null);
   const [selectedClaim, setSelectedClaim] = useState<Claim | null>(null);
   const [copied, setCopied] = useState(false);
+  const [review, setReview] = useState<ClaimReview | null>(null);
+  const [reviewError, setReviewError] = useState<string | null>(null);
+  const [watched, setWatched] = useState<WatchedClaim[]>([]);
+  const [rechecking, setRechecking] = useState(false);
+  const [evidence, setEvidence] = useState<Evidence[]>([]);
+  const [sources, setSources] = useState<ClaimSource[]>([]);
+  const [sourceUrl, setSourceUrl] = useState('');
+  const [sourceTitle, setSourceTitle] = useState('');
+  const [sourceError, setSourceError] = useState<string | null>(null);
+  const [unsourcedOnly, setUnsourcedOnly] = useState(false);
+  const [archiving, setArchiving] = useState<string | null>(null);
+  const [undoMessage, setUndoMessage] = useState<string | null>(null);
+  const [unsourced, setUnsourced] = useState<Set<string> | null>(null);
+  const [timeline, setTimeline] = useState<TimelineEvent[]>([]);
 
-  const loadStatus = useCallback(async () => {
+  // `refresh` recounts claim objects instead of using the cached count
+  const loadStatus = useCallback(async (refresh = false) => {
     setStatusLoading(true);
     setStatusError(null);
     try {
-      const result = await invoke<TruthRepoStatus>('get_truth_status');
+      const result = await invoke<TruthRepoStatus>('get_truth_status', { refresh });
       setStatus(result);
     } catch (err) {
       setStatusError(err instanceof Error ? err.message : String(err));
@@ -52,17 +149,79 @@ export function TruthPanel() {
     }
   }, []);
 
+  // Claims arrive in chunks, so the first page shows before the whole repo is read
   const loadClaims = useCallback(async () => {
     setClaimsLoading(true);
     setClaimsError(null);
+    if (activeFilter) {
+      try {
+        const listing = await invoke<{ claims: Claim[] }>('query_claims', { query: activeFilter.query });
+        setClaims(listing.claims);
+      } catch (err) {
+        setClaimsError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setClaimsLoading(false);
+      }
+      return;
+    }
+    const taskId = crypto.randomUUID();
+    let received: Claim[] = [];
+    const unlisten = await listen<ClaimChunk>('claims://chunk', (event) => {
+      if (event.payload.task_id !== taskId) return;
+      received = received.concat(event.payload.claims);
+      setClaims(received);
+      setClaimsLoading(false);
+    });
     try {
-      const result = await invoke<Claim[]>('list_claims');
-      setClaims(result);
+      const summary = await invoke<ClaimStreamSummary>('stream_claims', {
+        taskId,
+        ontologicalType: typeFilter || null,
+      });
+      setObjectWarnings(summary.warnings);
     } catch (err) {
       setClaimsError(err instanceof Error ? err.message : String(err));
     } finally {
+      unlisten();
       setClaimsLoading(false);
     }
+  }, [typeFilter, activeFilter]);
+
+  const loadWatchlist = useCallback(() => {
+    invoke<WatchedClaim[]>('list_watchlist')
+      .then(setWatched)
+      .catch(() => setWatched([]));
+  }, []);
+
+  // Scheduled re-checks report pinned claims whose status or confidence moved
+  useEffect(() => {
+    loadWatchlist();
+    const unlisten = listen('watchlist://changed', loadWatchlist);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadWatchlist]);
+
+  useEffect(() => {
+    invoke<SavedFilter[]>('list_saved_filters')
+      .then(setSavedFilters)
+      .catch(() => setSavedFilters([]));
+  }, []);
+
+  const deleteActiveFilter = async () => {
+    if (!activeFilter) return;
+    try {
+      await invoke('delete_filter', { id: activeFilter.id });
+      setSavedFilters((filters) => filters.filter((f) => f.id !== activeFilter.id));
+      setActiveFilter(null);
+    } catch (err) {
+      console.error('Failed to delete filter:', err);
+    }
+  };
+
+  useEffect(() => {
+    invoke<OntologicalType[]>('list_ontological_types')
+      .then(setOntologicalTypes)
+      .catch(() => setOntologicalTypes([]));
   }, []);
 
   useEffect(() => {
@@ -70,11 +229,160 @@ export function TruthPanel() {
     loadClaims();
   }, [loadStatus, loadClaims]);
 
+  // The first listing may come from the cache; reload once indexing finishes
+  useEffect(() => {
+    const unlisten = listen('index://ready', () => {
+      loadStatus();
+      loadClaims();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadStatus, loadClaims]);
+
   const handleRefresh = () => {
-    loadStatus();
+    loadStatus(true);
     loadClaims();
   };
 
+  useEffect(() => {
+    setReview(null);
+    setReviewError(null);
+    if (!selectedClaim) return;
+    invoke<ClaimReview>('get_claim_review', { hash: selectedClaim.$hash })
+      .then(setReview)
+      .catch((err) => setReviewError(String(err)));
+    setEvidence([]);
+    invoke<Evidence[]>('list_claim_evidence', { hash: selectedClaim.$hash })
+      .then(setEvidence)
+      .catch((err) => console.error('Failed to load evidence:', err));
+    setSources([]);
+    setSourceUrl('');
+    setSourceTitle('');
+    setSourceError(null);
+    invoke<ClaimSource[]>('list_claim_sources', { hash: selectedClaim.$hash })
+      .then(setSources)
+      .catch((err) => setSourceError(String(err)));
+  }, [selectedClaim]);
+
+  // Reloaded after review and source changes so they show up right away
+  useEffect(() => {
+    setTimeline([]);
+    if (!selectedClaim) return;
+    invoke<TimelineEvent[]>('get_claim_timeline', { hash: selectedClaim.$hash })
+      .then(setTimeline)
+      .catch((err) => console.error('Failed to load claim timeline:', err));
+  }, [selectedClaim, review, sources]);
+
+  useEffect(() => {
+    if (!unsourcedOnly) return;
+    invoke<{ unsourced: { hash: string }[] }>('unsourced_claims_report')
+      .then((report) => setUnsourced(new Set(report.unsourced.map((c) => c.hash))))
+      .catch((err) => console.error('Failed to load unsourced claims:', err));
+  }, [unsourcedOnly, sources]);
+
+  const handleAddSource = async () => {
+    if (!selectedClaim) return;
+    setSourceError(null);
+    try {
+      setSources(await invoke<ClaimSource[]>('add_claim_source', {
+        hash: selectedClaim.$hash,
+        source: { url: sourceUrl, title: sourceTitle || null },
+      }));
+      setSourceUrl('');
+      setSourceTitle('');
+    } catch (err) {
+      setSourceError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleArchiveSource = async (source: ClaimSource) => {
+    if (!selectedClaim) return;
+    setSourceError(null);
+    setArchiving(source.url);
+    try {
+      const archived = await invoke<{ hash: string; title: string | null }>('archive_url', { url: source.url });
+      setSources(await invoke<ClaimSource[]>('add_claim_source', {
+        hash: selectedClaim.$hash,
+        source: { ...source, title: source.title ?? archived.title, archived_hash: archived.hash },
+      }));
+    } catch (err) {
+      setSourceError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setArchiving(null);
+    }
+  };
+
+  const handleUndo = async () => {
+    try {
+      const change = await invoke<{ description: string } | null>('undo_last_change');
+      setUndoMessage(change ? `Undid: ${change.description}` : 'Nothing to undo');
+      loadWatchlist();
+      setSelectedClaim(null);
+    } catch (err) {
+      setUndoMessage(err instanceof Error ? err.message : String(err));
+    }
+    setTimeout(() => setUndoMessage(null), 4000);
+  };
+
+  const visibleClaims = unsourcedOnly && unsourced ? claims.filter((c) => unsourced.has(c.$hash)) : claims;
+
+  const handleReviewState = async (state: ReviewState) => {
+    if (!selectedClaim) return;
+    setReviewError(null);
+    try {
+      // Approvals go through approve() so the second-reviewer policy applies
+      const command = state === 'approved' ? 'approve' : 'set_claim_review_state';
+      setReview(await invoke<ClaimReview>(command, { hash: selectedClaim.$hash, state }));
+    } catch (err) {
+      setReviewError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleRequestApproval = async () => {
+    if (!selectedClaim) return;
+    setReviewError(null);
+    try {
+      setReview(await invoke<ClaimReview>('request_approval', { hash: selectedClaim.$hash }));
+    } catch (err) {
+      setReviewError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  const handleRecheck = async () => {
+    setRechecking(true);
+    try {
+      await invoke('recheck_watchlist');
+    } catch (err) {
+      console.error('Re-check failed:', err);
+    } finally {
+      setRechecking(false);
+      loadWatchlist();
+    }
+  };
+
+  const handleOpenPinned = async (hash: string) => {
+    const loaded = claims.find((c) => c.$hash === hash);
+    try {
+      setSelectedClaim(loaded ?? await invoke<Claim>('get_claim', { hash }));
+    } catch (err) {
+      console.error('Failed to open pinned claim:', err);
+    }
+  };
+
+  const isPinned = selectedClaim ? watched.some((w) => w.hash === selectedClaim.$hash) : false;
+
+  const handleTogglePin = async () => {
+    if (!selectedClaim) return;
+    setReviewError(null);
+    try {
+      await invoke(isPinned ? 'unpin_claim' : 'pin_claim', { hash: selectedClaim.$hash });
+      loadWatchlist();
+    } catch (err) {
+      setReviewError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
   const handleCopyHash = async (hash: string) => {
     await navigator.clipboard.writeText(hash);
     setCopied(true);
@@ -93,18 +401,89 @@ export function TruthPanel() {
             <p className="text-sm text-zinc-500">Local .truth/ claims and proofs</p>
           </div>
         </div>
+        <div className="flex items-center gap-3">
+          {undoMessage && <span className="text-xs text-zinc-400">{undoMessage}</span>}
+          <button
+            onClick={handleUndo}
+            title="Undo the last review, pin, source or evidence change"
+            className="flex items-center gap-1 px-2 py-1 text-xs text-zinc-300 bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+          >
+            <Undo2 className="w-3 h-3" />
+            Undo
+          </button>
+        </div>
       </div>
 
       <RepoStatus status={status} loading={statusLoading} error={statusError} />
 
+      <Watchlist watched={watched} rechecking={rechecking} onRecheck={handleRecheck} onOpen={handleOpenPinned} />
+
+      <div className="flex items-center justify-end gap-2">
+        <label className="flex items-center gap-2 mr-2 text-sm text-zinc-500">
+          <input type="checkbox" checked={unsourcedOnly} onChange={(e) => setUnsourcedOnly(e.target.checked)} />
+          Without sources
+        </label>
+        {savedFilters.length > 0 && (
+          <>
+            <label className="text-sm text-zinc-500">Saved filter</label>
+            <select
+              value={activeFilter?.id ?? ''}
+              onChange={(e) => setActiveFilter(savedFilters.find((f) => f.id === e.target.value) ?? null)}
+              className="px-3 py-1.5 bg-zinc-900 border border-zinc-800 rounded-lg text-sm text-zinc-300"
+            >
+              <option value="">None</option>
+              {savedFilters.map((f) => (
+                <option key={f.id} value={f.id}>{f.name}</option>
+              ))}
+            </select>
+            {activeFilter && (
+              <button
+                onClick={deleteActiveFilter}
+                title="Delete this saved filter"
+                className="p-1.5 text-zinc-500 hover:text-red-400"
+              >
+                <X className="w-4 h-4" />
+              </button>
+            )}
+          </>
+        )}
+        <label className="text-sm text-zinc-500">Type</label>
+        <select
+          value={typeFilter}
+          onChange={(e) => setTypeFilter(e.target.value)}
+          className="px-3 py-1.5 bg-zinc-900 border border-zinc-800 rounded-lg text-sm text-zinc-300"
+        >
+          <option value="">All types</option>
+          {ontologicalTypes.map((t) => (
+            <option key={t.name} value={t.name} title={t.description}>{t.name}</option>
+          ))}
+        </select>
+      </div>
+
       <ClaimsList
-        claims={claims}
+        claims={visibleClaims}
         loading={claimsLoading}
         error={claimsError}
         onRefresh={handleRefresh}
         onClaimClick={setSelectedClaim}
       />
 
+      {objectWarnings.length > 0 && (
+        <div className="bg-yellow-500/10 border border-yellow-500/20 rounded-xl p-4">
+          <div className="flex items-center gap-2 text-yellow-400 text-sm font-medium">
+            <AlertTriangle className="w-4 h-4" />
+            {objectWarnings.length} object{objectWarnings.length === 1 ? '' : 's'} in this repository couldn't be read
+          </div>
+          <ul className="mt-2 space-y-1 text-xs text-zinc-400">
+            {objectWarnings.map((w) => (
+              <li key={w.object}>
+                <code className="text-zinc-300 font-mono">{w.object}</code> — {w.message}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       <AnimatePresence>
         {selectedClaim && (
           <motion.div
@@ -123,12 +502,21 @@ export function TruthPanel() {
             >
               <div className="flex items-start justify-between mb-4">
                 <h2 className="text-lg font-semibold text-zinc-100">Claim Details</h2>
-                <button
-                  onClick={() => setSelectedClaim(null)}
-                  className="p-1 text-zinc-400 hover:text-zinc-300 transition-colors"
-                >
-                  <X className="w-5 h-5" />
-                </button>
+                <div className="flex items-center gap-1">
+                  <button
+                    onClick={handleTogglePin}
+                    title={isPinned ? 'Unpin claim' : 'Pin claim to the watchlist'}
+                    className="p-1 text-zinc-400 hover:text-zinc-300 transition-colors"
+                  >
+                    {isPinned ? <PinOff className="w-5 h-5" /> : <Pin className="w-5 h-5" />}
+                  </button>
+                  <button
+                    onClick={() => setSelectedClaim(null)}
+                    className="p-1 text-zinc-400 hover:text-zinc-300 transition-colors"
+                  >
+                    <X className="w-5 h-5" />
+                  </button>
+                </div>
               </div>
 
               <div className="space-y-4">
@@ -171,6 +559,133 @@ export function TruthPanel() {
                   </div>
                 </div>
 
+                <div>
+                  <label className="text-xs text-zinc-500 uppercase tracking-wide">Review</label>
+                  <div className="flex items-center justify-between gap-2 mt-1">
+                    <span className="text-sm text-zinc-300">
+                      {review ? review.state : '…'}
+                      {review?.reviewer && (
+                        <span className="text-zinc-500"> · {review.reviewer.name}</span>
+                      )}
+                    </span>
+                    <div className="flex gap-1">
+                      {review?.state === 'in-review' && !review.pending_approval && (
+                        <button
+                          onClick={handleRequestApproval}
+                          className="px-2 py-1 text-xs text-zinc-300 bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+                        >
+                          request approval
+                        </button>
+                      )}
+                      {review && REVIEW_TRANSITIONS[review.state].map((next) => (
+                        <button
+                          key={next}
+                          onClick={() => handleReviewState(next)}
+                          className="px-2 py-1 text-xs text-zinc-300 bg-zinc-800 hover:bg-zinc-700 rounded transition-colors"
+                        >
+                          {next}
+                        </button>
+                      ))}
+                    </div>
+                  </div>
+                  {review?.pending_approval && (
+                    <p className="text-xs text-amber-400 mt-1">
+                      Approval requested by {review.pending_approval.requested_by.name}; waiting for a second reviewer
+                    </p>
+                  )}
+                  {reviewError && <p className="text-xs text-red-400 mt-1">{reviewError}</p>}
+                </div>
+
+                <div>
+                  <label className="text-xs text-zinc-500 uppercase tracking-wide">Sources</label>
+                  <ul className="mt-1 space-y-1 text-sm">
+                    {sources.map((source) => (
+                      <li key={source.url} className="flex items-center gap-2 text-zinc-300">
+                        <Link className="w-4 h-4 text-zinc-500 shrink-0" />
+                        <a href={source.url} target="_blank" rel="noreferrer" className="truncate hover:text-zinc-100">
+                          {source.title ?? source.url}
+                        </a>
+                        {source.accessed && <span className="text-xs text-zinc-500 shrink-0">{source.accessed}</span>}
+                        {source.archived_hash ? (
+                          <span className="text-xs text-zinc-500 shrink-0" title={source.archived_hash}>archived</span>
+                        ) : (
+                          <button
+                            onClick={() => handleArchiveSource(source)}
+                            disabled={archiving !== null}
+                            title="Save a snapshot of the page's text in the repository"
+                            className="text-xs text-zinc-400 hover:text-zinc-200 shrink-0 disabled:opacity-50"
+                          >
+                            {archiving === source.url ? 'archiving…' : 'archive'}
+                          </button>
+                        )}
+                      </li>
+                    ))}
+                    {sources.length === 0 && <li className="text-zinc-500">No sources recorded</li>}
+                  </ul>
+                  <div className="flex gap-2 mt-2">
+                    <input
+                      value={sourceUrl}
+                      onChange={(e) => setSourceUrl(e.target.value)}
+                      placeholder="https://…"
+                      className="flex-1 px-2 py-1 text-sm bg-zinc-800 border border-zinc-700 rounded text-zinc-200 placeholder-zinc-500"
+                    />
+                    <input
+                      value={sourceTitle}
+                      onChange={(e) => setSourceTitle(e.target.value)}
+                      placeholder="Title (optional)"
+                      className="flex-1 px-2 py-1 text-sm bg-zinc-800 border border-zinc-700 rounded text-zinc-200 placeholder-zinc-500"
+                    />
+                    <button
+                      onClick={handleAddSource}
+                      disabled={!sourceUrl.trim()}
+                      className="px-2 py-1 text-xs text-zinc-300 bg-zinc-800 hover:bg-zinc-700 rounded transition-colors disabled:opacity-50"
+                    >
+                      add
+                    </button>
+                  </div>
+                  {sourceError && <p className="text-xs text-red-400 mt-1">{sourceError}</p>}
+                </div>
+
+                {evidence.length > 0 && (
+                  <div>
+                    <label className="text-xs text-zinc-500 uppercase tracking-wide">Evidence</label>
+                    <ul className="mt-1 space-y-1 text-sm">
+                      {evidence.map((item) => (
+                        <li key={item.id} className="flex items-center gap-2 text-zinc-300">
+                          {item.kind === 'url' ? (
+                            <Link className="w-4 h-4 text-zinc-500" />
+                          ) : (
+                            <FileText className="w-4 h-4 text-zinc-500" />
+                          )}
+                          {item.kind === 'url' ? (
+                            <a href={item.name} target="_blank" rel="noreferrer" className="truncate hover:text-zinc-100">
+                              {item.name}
+                            </a>
+                          ) : (
+                            <span className="truncate" title={item.hash ?? undefined}>{item.name}</span>
+                          )}
+                        </li>
+                      ))}
+                    </ul>
+                  </div>
+                )}
+
+                {timeline.length > 0 && (
+                  <div>
+                    <label className="text-xs text-zinc-500 uppercase tracking-wide">Timeline</label>
+                    <ul className="mt-1 space-y-1 text-sm max-h-48 overflow-y-auto">
+                      {timeline.map((item, i) => (
+                        <li key={`${item.at}-${i}`} className="flex items-baseline gap-2 text-zinc-300">
+                          <span className="text-xs text-zinc-500 shrink-0">{new Date(item.at).toLocaleString()}</span>
+                          <span className="text-xs text-zinc-500 shrink-0">{item.kind}</span>
+                          <span className="truncate">{item.summary}</span>
+                          {item.actor && <span className="text-xs text-zinc-500 shrink-0">{item.actor.name}</span>}
+                        </li>
+                      ))}
+                    </ul>
+                  </div>
+                )}
+
                 <div>
                   <label className="text-xs text-zinc-500 uppercase tracking-wide">Hash</label>
                   <div className="flex items-center gap-2 mt-1">
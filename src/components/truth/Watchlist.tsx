@@ -0,0 +1,77 @@
+import { Pin, RefreshCw, AlertTriangle } from 'lucide-react';
+
+export interface WatchedClaim {
+  hash: string;
+  claim: { content: string; domain: string } | null;
+  pin: {
+    pinned_at: string;
+    status: string | null;
+    confidence: number | null;
+    checked_at: string | null;
+    error: string | null;
+  };
+}
+
+interface WatchlistProps {
+  watched: WatchedClaim[];
+  rechecking: boolean;
+  onRecheck: () => void;
+  onOpen: (hash: string) => void;
+}
+
+const STATUS_COLORS: Record<string, string> = {
+  PASSED: 'text-green-400',
+  FAILED: 'text-red-400',
+  GAP: 'text-yellow-400',
+  MYSTERY: 'text-purple-400',
+};
+
+export function Watchlist({ watched, rechecking, onRecheck, onOpen }: WatchlistProps) {
+  if (watched.length === 0) return null;
+
+  return (
+    <div className="bg-zinc-900/50 border border-zinc-800 rounded-lg p-4">
+      <div className="flex items-center justify-between mb-3">
+        <div className="flex items-center gap-2 text-sm font-medium text-zinc-300">
+          <Pin className="w-4 h-4 text-purple-400" />
+          Pinned claims
+        </div>
+        <button
+          onClick={onRecheck}
+          disabled={rechecking}
+          className="flex items-center gap-1 px-2 py-1 text-xs text-zinc-300 bg-zinc-800 hover:bg-zinc-700 rounded transition-colors disabled:opacity-50"
+        >
+          <RefreshCw className={`w-3 h-3 ${rechecking ? 'animate-spin' : ''}`} />
+          Re-check now
+        </button>
+      </div>
+      <ul className="space-y-2">
+        {watched.map((w) => (
+          <li
+            key={w.hash}
+            onClick={() => onOpen(w.hash)}
+            className="flex items-center justify-between gap-4 text-sm cursor-pointer hover:bg-zinc-800/50 rounded px-2 py-1"
+          >
+            <span className="text-zinc-200 truncate">{w.claim?.content ?? w.hash}</span>
+            <span className="flex items-center gap-3 shrink-0 text-xs">
+              {w.pin.error && (
+                <span title={w.pin.error}>
+                  <AlertTriangle className="w-3 h-3 text-amber-400" />
+                </span>
+              )}
+              <span className={STATUS_COLORS[w.pin.status ?? ''] ?? 'text-zinc-500'}>
+                {w.pin.status ?? 'unverified'}
+              </span>
+              {w.pin.confidence !== null && (
+                <span className="text-zinc-400">{Math.round(w.pin.confidence * 100)}%</span>
+              )}
+              <span className="text-zinc-600">
+                {w.pin.checked_at ? new Date(w.pin.checked_at).toLocaleDateString() : 'never checked'}
+              </span>
+            </span>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
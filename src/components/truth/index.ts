@@ -2,3 +2,4 @@ export { TruthPanel } from './TruthPanel';
 export { ClaimsList } from './ClaimsList';
 export { ClaimCard } from './ClaimCard';
 export { RepoStatus } from './RepoStatus';
+export { Watchlist } from './Watchlist';